@@ -0,0 +1,401 @@
+use crate::db::{record_alert_delivery, AlertDelivery};
+use crate::diagnostics;
+use crate::forwarding::severity_rank;
+use crate::logs::{detect_host_os, NormalizedEvent};
+use crate::saved_filters::{self, find_saved_filter};
+use crate::settings::{load_alert_settings, AlertRule, SavedFilter};
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Duration, Utc};
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Events listed in one webhook post; the rest are only counted.
+const MAX_EVENTS_PER_ALERT: usize = 10;
+const MAX_MESSAGE_CHARS: usize = 200;
+const WEBHOOK_TIMEOUT_SECONDS: u64 = 10;
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+/// A rule with its message pattern compiled and its saved filter loaded once per evaluation pass.
+pub struct CompiledRule {
+    pub rule: AlertRule,
+    pattern: Option<Regex>,
+    saved_filter: Option<SavedFilter>,
+}
+
+impl CompiledRule {
+    pub fn new(rule: AlertRule) -> Result<Self, String> {
+        let pattern = if rule.message_pattern.is_empty() {
+            None
+        } else {
+            Some(
+                RegexBuilder::new(rule.message_pattern.as_str())
+                    .case_insensitive(true)
+                    .size_limit(PATTERN_SIZE_LIMIT)
+                    .build()
+                    .map_err(|error| {
+                        format!(
+                            "Alert rule \"{}\" has an invalid message pattern: {error}",
+                            rule.name
+                        )
+                    })?,
+            )
+        };
+        let saved_filter = match rule.saved_filter_id.as_deref() {
+            Some(filter_id) => Some(find_saved_filter(filter_id).map_err(|error| {
+                format!(
+                    "Alert rule \"{}\" uses a missing filter: {error}",
+                    rule.name
+                )
+            })?),
+            None => None,
+        };
+        Ok(Self {
+            rule,
+            pattern,
+            saved_filter,
+        })
+    }
+
+    pub fn matches(&self, event: &NormalizedEvent) -> bool {
+        let rule = &self.rule;
+        if !rule.min_severity.is_empty()
+            && severity_rank(event.severity.as_str()) < severity_rank(rule.min_severity.as_str())
+        {
+            return false;
+        }
+        if !rule.providers.is_empty()
+            && !rule
+                .providers
+                .iter()
+                .any(|provider| provider.eq_ignore_ascii_case(event.provider.as_str()))
+        {
+            return false;
+        }
+        if !rule.event_ids.is_empty()
+            && !event
+                .event_id
+                .is_some_and(|event_id| rule.event_ids.contains(&event_id))
+        {
+            return false;
+        }
+        if self
+            .saved_filter
+            .as_ref()
+            .is_some_and(|filter| !saved_filters::matches(filter, event, Utc::now()))
+        {
+            return false;
+        }
+        self.pattern
+            .as_ref()
+            .is_none_or(|pattern| pattern.is_match(event.message.as_str()))
+    }
+}
+
+/// Rate-limit bookkeeping for one rule, kept for the lifetime of the process.
+#[derive(Debug, Default)]
+struct RuleState {
+    last_sent: Option<DateTime<Utc>>,
+    suppressed: u32,
+    /// Newest event timestamp already considered, so re-collected events do not alert twice.
+    high_water: Option<DateTime<Utc>>,
+}
+
+impl RuleState {
+    /// Drops events at or before the high-water mark and advances it past the rest.
+    fn take_new<'a>(&mut self, matched: Vec<&'a NormalizedEvent>) -> Vec<&'a NormalizedEvent> {
+        let fresh: Vec<&NormalizedEvent> = matched
+            .into_iter()
+            .filter(
+                |event| match (parse_timestamp(event.timestamp.as_str()), self.high_water) {
+                    (Some(at), Some(mark)) => at > mark,
+                    _ => true,
+                },
+            )
+            .collect();
+        let newest = fresh
+            .iter()
+            .filter_map(|event| parse_timestamp(event.timestamp.as_str()))
+            .max();
+        if newest > self.high_water {
+            self.high_water = newest;
+        }
+        fresh
+    }
+
+    /// Returns the number of held-back matches to report when a post is allowed now, or
+    /// `None` while the rule is cooling down.
+    fn admit(&mut self, now: DateTime<Utc>, cooldown_seconds: u32, matched: u32) -> Option<u32> {
+        let cooling = self
+            .last_sent
+            .is_some_and(|sent| now - sent < Duration::seconds(i64::from(cooldown_seconds)));
+        if cooling {
+            self.suppressed += matched;
+            return None;
+        }
+        self.last_sent = Some(now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+static RULE_STATE: Mutex<BTreeMap<String, RuleState>> = Mutex::new(BTreeMap::new());
+
+fn event_line(event: &NormalizedEvent) -> String {
+    let mut message: String = event.message.chars().take(MAX_MESSAGE_CHARS).collect();
+    if event.message.chars().count() > MAX_MESSAGE_CHARS {
+        message.push('…');
+    }
+    let event_id = event
+        .event_id
+        .map(|id| format!(" ({id})"))
+        .unwrap_or_default();
+    format!(
+        "[{}] {} {}{} on {}: {}",
+        event.severity,
+        event.timestamp,
+        event.provider,
+        event_id,
+        event.source_host,
+        message.replace('\n', " ")
+    )
+}
+
+/// Builds the webhook body in the rule's format: Slack incoming-webhook text, a Teams
+/// MessageCard, or a generic JSON document with the full events.
+pub fn build_payload(rule: &AlertRule, events: &[&NormalizedEvent], suppressed: u32) -> Value {
+    let mut title = format!("{}: {} matching event(s)", rule.name, events.len());
+    if suppressed > 0 {
+        title.push_str(format!(", {suppressed} more held back by the cooldown").as_str());
+    }
+    let listed = &events[..events.len().min(MAX_EVENTS_PER_ALERT)];
+    let lines: Vec<String> = listed.iter().map(|event| event_line(event)).collect();
+    match rule.webhook_format.as_str() {
+        "slack" => {
+            let bullets: Vec<String> = lines.iter().map(|line| format!("• {line}")).collect();
+            json!({ "text": format!("*{title}*\n{}", bullets.join("\n")) })
+        }
+        "teams" => json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": title,
+            "title": title,
+            "text": lines.join("\n\n"),
+        }),
+        _ => json!({
+            "rule": { "id": rule.id, "name": rule.name },
+            "matched": events.len(),
+            "suppressed": suppressed,
+            "sentAt": Utc::now().to_rfc3339(),
+            "events": listed,
+        }),
+    }
+}
+
+fn post_alert(
+    client: &reqwest::blocking::Client,
+    rule: &AlertRule,
+    events: &[&NormalizedEvent],
+    suppressed: u32,
+) -> AlertDelivery {
+    let mut delivery = AlertDelivery {
+        id: 0,
+        rule_id: rule.id.clone(),
+        rule_name: rule.name.clone(),
+        sent_at: Utc::now().to_rfc3339(),
+        matched: events.len() as u32,
+        suppressed,
+        success: false,
+        http_status: None,
+        error: None,
+    };
+    match client
+        .post(rule.webhook_url.as_str())
+        .json(&build_payload(rule, events, suppressed))
+        .send()
+    {
+        Ok(response) => {
+            let status = response.status();
+            delivery.http_status = Some(status.as_u16());
+            delivery.success = status.is_success();
+            if !delivery.success {
+                delivery.error = Some(format!("Webhook returned HTTP {status}"));
+            }
+        }
+        Err(error) => delivery.error = Some(format!("Webhook request failed: {error}")),
+    }
+    if let Err(error) = record_alert_delivery(&delivery) {
+        diagnostics::warn("alerts", error);
+    }
+    delivery
+}
+
+fn webhook_client() -> Result<reqwest::blocking::Client, String> {
+    reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(WEBHOOK_TIMEOUT_SECONDS))
+        .build()
+        .map_err(|error| format!("Failed to build alert HTTP client: {error}"))
+}
+
+/// Checks newly collected or replayed events against the enabled alert rules and posts each
+/// rule's webhook, subject to its cooldown. Imported events never alert.
+pub fn evaluate_events(events: &[NormalizedEvent]) {
+    if events.is_empty() {
+        return;
+    }
+    let rules: Vec<CompiledRule> = load_alert_settings()
+        .rules
+        .into_iter()
+        .filter(|rule| rule.enabled && !rule.webhook_url.is_empty())
+        .filter_map(|rule| match CompiledRule::new(rule) {
+            Ok(rule) => Some(rule),
+            Err(error) => {
+                diagnostics::warn("alerts", error);
+                None
+            }
+        })
+        .collect();
+    if rules.is_empty() {
+        return;
+    }
+
+    let mut client = None;
+    let now = Utc::now();
+    for compiled in &rules {
+        let matched: Vec<&NormalizedEvent> = events
+            .iter()
+            .filter(|event| !event.imported && compiled.matches(event))
+            .collect();
+        if matched.is_empty() {
+            continue;
+        }
+        let (fresh, suppressed) = {
+            let Ok(mut states) = RULE_STATE.lock() else {
+                return;
+            };
+            let state = states.entry(compiled.rule.id.clone()).or_default();
+            let fresh = state.take_new(matched);
+            if fresh.is_empty() {
+                continue;
+            }
+            let admitted = state.admit(now, compiled.rule.cooldown_seconds, fresh.len() as u32);
+            (fresh, admitted)
+        };
+        let Some(suppressed) = suppressed else {
+            continue;
+        };
+
+        if client.is_none() {
+            match webhook_client() {
+                Ok(built) => client = Some(built),
+                Err(error) => {
+                    diagnostics::warn("alerts", error);
+                    return;
+                }
+            }
+        }
+        let Some(client) = client.as_ref() else {
+            return;
+        };
+        let delivery = post_alert(client, &compiled.rule, fresh.as_slice(), suppressed);
+        if let Some(error) = delivery.error {
+            diagnostics::warn(
+                "alerts",
+                format!(
+                    "Alert \"{}\" was not delivered: {error}",
+                    compiled.rule.name
+                ),
+            );
+        }
+    }
+}
+
+/// Posts a sample event through `rule` without checking its criteria or cooldown.
+pub fn send_test_alert(rule: AlertRule) -> Result<AlertDelivery, String> {
+    let compiled = CompiledRule::new(rule)?;
+    if compiled.rule.webhook_url.is_empty() {
+        return Err("Alert rule has no webhook URL.".to_string());
+    }
+    let event = NormalizedEvent::new(
+        detect_host_os(),
+        "Hermes",
+        "application",
+        "Hermes Log Analyst",
+        None,
+        "information",
+        "Test alert from Hermes Log Analyst",
+        "localhost",
+    );
+    let client = webhook_client()?;
+    Ok(post_alert(&client, &compiled.rule, &[&event], 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn rule() -> AlertRule {
+        AlertRule {
+            id: "disk".to_string(),
+            name: "Disk errors".to_string(),
+            enabled: true,
+            min_severity: "error".to_string(),
+            providers: vec!["disk".to_string()],
+            event_ids: vec![7, 51],
+            message_pattern: r"bad\s+block".to_string(),
+            webhook_url: "http://localhost/hook".to_string(),
+            webhook_format: "slack".to_string(),
+            cooldown_seconds: 300,
+            saved_filter_id: None,
+        }
+    }
+
+    fn event(severity: &str, event_id: Option<u32>, message: &str) -> NormalizedEvent {
+        NormalizedEvent::new(
+            SupportedOs::Windows,
+            "System",
+            "system",
+            "Disk",
+            event_id,
+            severity,
+            message,
+            "host-a",
+        )
+    }
+
+    #[test]
+    fn matches_severity_provider_event_id_and_pattern() {
+        let compiled = CompiledRule::new(rule()).unwrap();
+        assert!(compiled.matches(&event("critical", Some(7), "Bad  Block at 0x10")));
+        assert!(!compiled.matches(&event("warning", Some(7), "bad block")));
+        assert!(!compiled.matches(&event("error", Some(8), "bad block")));
+        assert!(!compiled.matches(&event("error", Some(51), "retry succeeded")));
+
+        let mut invalid = rule();
+        invalid.message_pattern = "(".to_string();
+        assert!(CompiledRule::new(invalid).is_err());
+    }
+
+    #[test]
+    fn cooldown_holds_back_and_reports_suppressed_matches() {
+        let mut state = RuleState::default();
+        let now = Utc::now();
+        assert_eq!(state.admit(now, 300, 2), Some(0));
+        assert_eq!(state.admit(now + Duration::seconds(60), 300, 3), None);
+        assert_eq!(state.admit(now + Duration::seconds(301), 300, 1), Some(3));
+
+        let first = event("error", Some(7), "bad block");
+        let older = NormalizedEvent {
+            timestamp: "2000-01-01T00:00:00Z".to_string(),
+            ..first.clone()
+        };
+        assert_eq!(state.take_new(vec![&first]).len(), 1);
+        assert!(state.take_new(vec![&first, &older]).is_empty());
+
+        let payload = build_payload(&rule(), &[&first], 3);
+        let text = payload["text"].as_str().unwrap();
+        assert!(text.starts_with("*Disk errors: 1 matching event(s), 3 more held back"));
+        assert!(text.contains("• [error]"));
+    }
+}
@@ -0,0 +1,266 @@
+use crate::logs::NormalizedEvent;
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_BUCKET_MINUTES: i64 = 10;
+/// Buckets of history each bucket is compared against (6 hours at the default size).
+const BASELINE_BUCKETS: usize = 36;
+/// A provider needs this much history before its buckets can be judged.
+const MIN_BASELINE_BUCKETS: usize = 6;
+const Z_THRESHOLD: f64 = 3.0;
+const MIN_BURST_COUNT: u64 = 5;
+/// Keeps a perfectly quiet baseline from turning a handful of errors into an infinite z-score.
+const MIN_STD_DEV: f64 = 1.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnomalyWindow {
+    pub provider: String,
+    pub log_name: String,
+    pub start: String,
+    pub end: String,
+    pub peak_at: String,
+    pub event_count: u64,
+    pub peak_count: u64,
+    pub baseline_mean: f64,
+    pub z_score: f64,
+    /// Peak bucket count relative to the baseline mean; `None` when the provider was silent before.
+    pub ratio: Option<f64>,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnomalyReport {
+    pub bucket_minutes: i64,
+    pub providers_scanned: usize,
+    pub windows: Vec<AnomalyWindow>,
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn mean_and_std(values: &[u64]) -> (f64, f64) {
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<u64>() as f64 / count;
+    let variance = values
+        .iter()
+        .map(|value| (*value as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    (mean, variance.sqrt())
+}
+
+struct Burst {
+    first: usize,
+    last: usize,
+    total: u64,
+    peak: usize,
+    peak_count: u64,
+    baseline_mean: f64,
+    z_score: f64,
+}
+
+/// Finds bursts of error/critical events per provider by comparing each time bucket with a rolling
+/// baseline of the preceding buckets. Adjacent anomalous buckets are merged into one window.
+pub fn detect_anomalies(
+    events: &[NormalizedEvent],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    bucket_minutes: i64,
+) -> AnomalyReport {
+    let bucket_minutes = bucket_minutes.clamp(1, 24 * 60);
+    let bucket = Duration::minutes(bucket_minutes);
+    let bucket_count = (((end - start).num_seconds() / bucket.num_seconds()).max(0) + 1) as usize;
+
+    let mut series: BTreeMap<(String, String), Vec<u64>> = BTreeMap::new();
+    for event in events {
+        if !matches!(event.severity.as_str(), "error" | "critical") {
+            continue;
+        }
+        let Some(at) = parse_timestamp(event.timestamp.as_str()) else {
+            continue;
+        };
+        if at < start || at > end {
+            continue;
+        }
+        let index = ((at - start).num_seconds() / bucket.num_seconds()) as usize;
+        let counts = series
+            .entry((event.provider.clone(), event.log_name.clone()))
+            .or_insert_with(|| vec![0; bucket_count]);
+        if let Some(count) = counts.get_mut(index) {
+            *count += 1;
+        }
+    }
+
+    let bucket_start = |index: usize| start + bucket * index as i32;
+    let mut windows = Vec::new();
+    for ((provider, log_name), counts) in &series {
+        let mut bursts: Vec<Burst> = Vec::new();
+        for index in MIN_BASELINE_BUCKETS..counts.len() {
+            let count = counts[index];
+            if count < MIN_BURST_COUNT {
+                continue;
+            }
+            let baseline = &counts[index.saturating_sub(BASELINE_BUCKETS)..index];
+            let (mean, std_dev) = mean_and_std(baseline);
+            let z_score = (count as f64 - mean) / std_dev.max(MIN_STD_DEV);
+            if z_score < Z_THRESHOLD {
+                continue;
+            }
+            match bursts.last_mut() {
+                Some(burst) if burst.last + 1 == index => {
+                    burst.last = index;
+                    burst.total += count;
+                    if count > burst.peak_count {
+                        burst.peak = index;
+                        burst.peak_count = count;
+                    }
+                    burst.z_score = burst.z_score.max(z_score);
+                }
+                _ => bursts.push(Burst {
+                    first: index,
+                    last: index,
+                    total: count,
+                    peak: index,
+                    peak_count: count,
+                    baseline_mean: mean,
+                    z_score,
+                }),
+            }
+        }
+
+        for burst in bursts {
+            let ratio = (burst.baseline_mean > 0.0)
+                .then(|| round1(burst.peak_count as f64 / burst.baseline_mean));
+            let peak_at = bucket_start(burst.peak);
+            let summary = match ratio {
+                Some(ratio) => format!(
+                    "{provider} errors spiked {ratio}x ({} events) at {}",
+                    burst.total,
+                    peak_at.format("%Y-%m-%d %H:%M UTC")
+                ),
+                None => format!(
+                    "{provider} started logging errors ({} events) at {}",
+                    burst.total,
+                    peak_at.format("%Y-%m-%d %H:%M UTC")
+                ),
+            };
+            windows.push(AnomalyWindow {
+                provider: provider.clone(),
+                log_name: log_name.clone(),
+                start: bucket_start(burst.first).to_rfc3339(),
+                end: bucket_start(burst.last + 1).to_rfc3339(),
+                peak_at: peak_at.to_rfc3339(),
+                event_count: burst.total,
+                peak_count: burst.peak_count,
+                baseline_mean: round1(burst.baseline_mean),
+                z_score: round1(burst.z_score),
+                ratio,
+                summary,
+            });
+        }
+    }
+
+    windows.sort_by(|left, right| right.z_score.total_cmp(&left.z_score));
+    AnomalyReport {
+        bucket_minutes,
+        providers_scanned: series.len(),
+        windows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        id: usize,
+        timestamp: DateTime<Utc>,
+        provider: &str,
+        severity: &str,
+    ) -> NormalizedEvent {
+        NormalizedEvent {
+            id: id.to_string(),
+            timestamp: timestamp.to_rfc3339(),
+            os: "windows".to_string(),
+            log_name: "System".to_string(),
+            category: "system".to_string(),
+            provider: provider.to_string(),
+            event_id: Some(7),
+            severity: severity.to_string(),
+            message: "The device has a bad block.".to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    fn start() -> DateTime<Utc> {
+        parse_timestamp("2026-03-01T00:00:00Z").unwrap()
+    }
+
+    #[test]
+    fn flags_spike_against_rolling_baseline() {
+        let start = start();
+        let end = start + Duration::hours(6);
+        let mut events = Vec::new();
+        // One disk error per 10 minutes until 03:10, then 50 at 03:12.
+        for bucket in 0..19 {
+            events.push(event(
+                events.len(),
+                start + Duration::minutes(bucket * 10 + 1),
+                "disk",
+                "error",
+            ));
+        }
+        for offset in 0..50 {
+            events.push(event(
+                events.len(),
+                start + Duration::minutes(192) + Duration::seconds(offset),
+                "disk",
+                "error",
+            ));
+        }
+        // Informational noise never counts.
+        for offset in 0..200 {
+            events.push(event(
+                events.len(),
+                start + Duration::seconds(offset),
+                "Kernel-General",
+                "information",
+            ));
+        }
+
+        let report = detect_anomalies(events.as_slice(), start, end, 10);
+        assert_eq!(report.providers_scanned, 1);
+        assert_eq!(report.windows.len(), 1);
+        let window = &report.windows[0];
+        assert_eq!(window.provider, "disk");
+        assert_eq!(window.event_count, 50);
+        assert_eq!(window.peak_at, "2026-03-01T03:10:00+00:00");
+        assert_eq!(window.ratio, Some(50.0));
+        assert!(window.summary.contains("spiked 50x"));
+    }
+
+    #[test]
+    fn ignores_steady_error_rates() {
+        let start = start();
+        let end = start + Duration::hours(6);
+        let events: Vec<NormalizedEvent> = (0..36 * 8)
+            .map(|index| {
+                event(
+                    index,
+                    start + Duration::seconds(index as i64 * 75),
+                    "Service Control Manager",
+                    "error",
+                )
+            })
+            .collect();
+
+        let report = detect_anomalies(events.as_slice(), start, end, 10);
+        assert!(report.windows.is_empty());
+    }
+}
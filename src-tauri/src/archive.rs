@@ -0,0 +1,352 @@
+//! Cold-storage archive for pruned events. When the retention policy names an archive folder,
+//! S3 bucket or Azure Blob container (see `archive_store`), events are written there as day
+//! partitions before retention deletes them, so older incidents can still be investigated.
+//!
+//! Layout: `events/YYYY-MM-DD/<run>-<id>.ndjson.gz`, one UTC day per folder and one gzipped
+//! NDJSON export (with its schema header) per retention run. Search lists the days in a range
+//! that hold matching events; rehydration loads chosen days back into the database, marked so
+//! retention holds them instead of deleting them on its next run.
+
+use crate::archive_store::ArchiveStore;
+use crate::db;
+use crate::event_export::{parse_event_export, EventExportWriter};
+use crate::logs::NormalizedEvent;
+use crate::timestamps::{parse_timestamp, DisplayTimezone};
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::Read;
+use uuid::Uuid;
+
+const EVENTS_DIR: &str = "events";
+const PARTITION_EXTENSION: &str = ".ndjson.gz";
+const DAY_FORMAT: &str = "%Y-%m-%d";
+/// A day with more pruned events than this is split across several files.
+const MAX_EVENTS_PER_FILE: usize = 50_000;
+pub const MAX_REHYDRATE_DAYS: usize = 31;
+/// Search downloads every partition in its range, so the range is capped.
+pub const MAX_SEARCH_DAYS: i64 = 31;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivePartition {
+    /// UTC day, `YYYY-MM-DD`.
+    pub day: String,
+    pub files: usize,
+    pub bytes: u64,
+    /// Distinct events archived for the day.
+    pub events: usize,
+    /// Events matching the query; every event for an empty query.
+    pub matches: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveSearchReport {
+    /// Days with at least one match, oldest first.
+    pub partitions: Vec<ArchivePartition>,
+    pub total_matches: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RehydrateReport {
+    pub days: usize,
+    pub events: usize,
+}
+
+/// Reads `YYYY-MM-DD` or any timestamp as a UTC day.
+pub fn parse_day(value: &str) -> Result<NaiveDate, String> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, DAY_FORMAT)
+        .ok()
+        .or_else(|| parse_timestamp(value).map(|parsed| parsed.date_naive()))
+        .ok_or_else(|| format!("{value} is not a date."))
+}
+
+fn day_prefix(day: NaiveDate) -> String {
+    format!("{EVENTS_DIR}/{}/", day.format(DAY_FORMAT))
+}
+
+/// Collects events arriving oldest first and writes each day's share to its partition. Events
+/// without a readable time are filed under the day they were archived.
+struct PartitionWriter<'a> {
+    store: &'a ArchiveStore,
+    run: String,
+    today: NaiveDate,
+    day: Option<NaiveDate>,
+    pending: Vec<NormalizedEvent>,
+    written: usize,
+}
+
+impl<'a> PartitionWriter<'a> {
+    fn new(store: &'a ArchiveStore, now: DateTime<Utc>) -> Self {
+        Self {
+            store,
+            run: now.format("%Y%m%dT%H%M%SZ").to_string(),
+            today: now.date_naive(),
+            day: None,
+            pending: Vec::new(),
+            written: 0,
+        }
+    }
+
+    fn push(&mut self, event: NormalizedEvent) -> Result<(), String> {
+        let day = parse_timestamp(event.timestamp.as_str())
+            .map(|parsed| parsed.date_naive())
+            .unwrap_or(self.today);
+        if self.day != Some(day) || self.pending.len() >= MAX_EVENTS_PER_FILE {
+            self.flush()?;
+            self.day = Some(day);
+        }
+        self.pending.push(event);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        let Some(day) = self.day else {
+            return Ok(());
+        };
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut writer =
+            EventExportWriter::start(encoder, "ndjson", self.pending.len(), DisplayTimezone::Utc)?;
+        for event in &self.pending {
+            writer.write_event(event)?;
+        }
+        let (encoder, _) = writer.finish()?;
+        let body = encoder
+            .finish()
+            .map_err(|error| format!("Failed to compress archive partition: {error}"))?;
+        let key = format!(
+            "{}{}-{}{PARTITION_EXTENSION}",
+            day_prefix(day),
+            self.run,
+            Uuid::new_v4().simple()
+        );
+        self.store.put(key.as_str(), body)?;
+        self.written += self.pending.len();
+        self.pending.clear();
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<usize, String> {
+        self.flush()?;
+        Ok(self.written)
+    }
+}
+
+/// Writes `events` into their day partitions in `store`. Returns how many were written.
+pub fn archive_events(
+    store: &ArchiveStore,
+    events: impl IntoIterator<Item = NormalizedEvent>,
+    now: DateTime<Utc>,
+) -> Result<usize, String> {
+    let mut writer = PartitionWriter::new(store, now);
+    for event in events {
+        writer.push(event)?;
+    }
+    writer.finish()
+}
+
+/// Archives the stored events the matching prune call is about to delete: those before
+/// `cutoff`, or the `limit` oldest.
+pub fn archive_oldest_events(
+    store: &ArchiveStore,
+    cutoff: Option<&str>,
+    limit: Option<u64>,
+    now: DateTime<Utc>,
+) -> Result<usize, String> {
+    let mut writer = PartitionWriter::new(store, now);
+    db::for_each_oldest_event(cutoff, limit, |event| writer.push(event))?;
+    writer.finish()
+}
+
+/// Every event archived for `day`, once each, with the number and size of its files. A day
+/// rehydrated and pruned again is archived twice, so later copies of an event id are dropped.
+fn read_partition(
+    store: &ArchiveStore,
+    day: NaiveDate,
+) -> Result<(Vec<NormalizedEvent>, usize, u64), String> {
+    let files = store
+        .list(day_prefix(day).as_str())?
+        .into_iter()
+        .filter(|object| object.key.ends_with(PARTITION_EXTENSION))
+        .collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    let mut events = Vec::new();
+    for file in &files {
+        let mut raw = String::new();
+        MultiGzDecoder::new(store.get(file.key.as_str())?.as_slice())
+            .read_to_string(&mut raw)
+            .map_err(|error| format!("Archive partition {} is unreadable: {error}", file.key))?;
+        let parsed = parse_event_export(raw.as_str())
+            .map_err(|error| format!("Archive partition {} is unreadable: {error}", file.key))?;
+        events.extend(
+            parsed
+                .into_iter()
+                .filter(|event| seen.insert(event.id.clone())),
+        );
+    }
+    let bytes = files.iter().map(|file| file.bytes).sum();
+    Ok((events, files.len(), bytes))
+}
+
+/// Case-insensitive match of every whitespace-separated term against the message, provider
+/// or host.
+fn matches_query(event: &NormalizedEvent, terms: &[String]) -> bool {
+    let message = event.message.to_lowercase();
+    let provider = event.provider.to_lowercase();
+    let host = event.source_host.to_lowercase();
+    terms.iter().all(|term| {
+        message.contains(term.as_str())
+            || provider.contains(term.as_str())
+            || host.contains(term.as_str())
+    })
+}
+
+/// Lists the archived days from `start` to `end`, inclusive and at most `MAX_SEARCH_DAYS`,
+/// holding events that match `query`, with their match counts. Nothing is loaded into the
+/// database.
+pub fn search_archive(
+    store: &ArchiveStore,
+    query: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<ArchiveSearchReport, String> {
+    if end < start {
+        return Err("The archive search range ends before it starts.".to_string());
+    }
+    if (end - start).num_days() >= MAX_SEARCH_DAYS {
+        return Err(format!(
+            "Search at most {MAX_SEARCH_DAYS} days of the archive at a time."
+        ));
+    }
+    let terms = query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    let mut report = ArchiveSearchReport::default();
+    for day in start.iter_days().take_while(|day| *day <= end) {
+        let (events, files, bytes) = read_partition(store, day)?;
+        let matches = events
+            .iter()
+            .filter(|event| matches_query(event, terms.as_slice()))
+            .count();
+        if matches == 0 {
+            continue;
+        }
+        report.total_matches += matches;
+        report.partitions.push(ArchivePartition {
+            day: day.format(DAY_FORMAT).to_string(),
+            files,
+            bytes,
+            events: events.len(),
+            matches,
+        });
+    }
+    Ok(report)
+}
+
+/// Loads every event archived for `days` back into the database, where retention holds them
+/// for a while before dropping them again.
+pub fn rehydrate_days(
+    store: &ArchiveStore,
+    days: &[String],
+    now: DateTime<Utc>,
+) -> Result<RehydrateReport, String> {
+    if days.len() > MAX_REHYDRATE_DAYS {
+        return Err(format!(
+            "Rehydrate at most {MAX_REHYDRATE_DAYS} days at a time."
+        ));
+    }
+    let mut report = RehydrateReport::default();
+    for day in days {
+        // Only a plain date names a partition, so a day can never point outside the archive.
+        let day = NaiveDate::parse_from_str(day.trim(), DAY_FORMAT)
+            .map_err(|_| format!("{} is not an archived day.", day.trim()))?;
+        let (events, _, _) = read_partition(store, day)?;
+        db::save_rehydrated_events(events.as_slice(), now.to_rfc3339().as_str())?;
+        report.days += 1;
+        report.events += events.len();
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn event(timestamp: &str, message: &str) -> NormalizedEvent {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Linux,
+            "journal",
+            "system",
+            "sshd",
+            None,
+            "error",
+            message,
+            "web01",
+        );
+        event.timestamp = timestamp.to_string();
+        event.assign_stable_id();
+        event
+    }
+
+    #[test]
+    fn archives_day_partitions_and_searches_them_by_range() {
+        let dir = std::env::temp_dir().join(format!("hla-archive-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create archive dir");
+        let store = ArchiveStore::Folder(dir.clone());
+        let now = "2026-03-10T12:00:00Z".parse().expect("now");
+        let day = |value: &str| parse_day(value).expect("day");
+        let first = vec![
+            event("2026-03-01T08:00:00+00:00", "Disk I/O error on sda"),
+            event("2026-03-01T09:00:00+00:00", "session opened"),
+            event("2026-03-02T10:00:00+00:00", "disk i/o ERROR on sdb"),
+            event("not a time", "disk quota warning"),
+        ];
+        assert_eq!(
+            archive_events(&store, first.clone(), now).expect("archive"),
+            4
+        );
+        // A day archived again after rehydration is not counted twice.
+        archive_events(&store, first[..1].to_vec(), now).expect("archive again");
+
+        let report = search_archive(&store, "disk error", day("2026-03-01"), day("2026-03-10"))
+            .expect("search");
+        let days = report
+            .partitions
+            .iter()
+            .map(|partition| (partition.day.as_str(), partition.matches))
+            .collect::<Vec<_>>();
+        assert_eq!(days, vec![("2026-03-01", 1), ("2026-03-02", 1)]);
+        assert_eq!(report.partitions[0].events, 2);
+        assert_eq!(report.partitions[0].files, 2);
+        assert_eq!(report.total_matches, 2);
+
+        let ranged = search_archive(&store, "disk", day("2026-03-02"), day("2026-03-10"))
+            .expect("ranged search");
+        let days = ranged
+            .partitions
+            .iter()
+            .map(|partition| partition.day.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(days, vec!["2026-03-02", "2026-03-10"]);
+        assert!(search_archive(&store, "disk", day("2026-01-01"), day("2026-03-10")).is_err());
+        assert!(search_archive(&store, "disk", day("2026-03-10"), day("2026-03-01")).is_err());
+
+        let (events, _, _) = read_partition(&store, day("2026-03-01")).expect("read");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].message, "Disk I/O error on sda");
+        assert_eq!(events[0].timestamp, "2026-03-01T08:00:00+00:00");
+
+        assert!(rehydrate_days(&store, &["../2026-03-01".to_string()], now).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
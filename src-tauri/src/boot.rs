@@ -0,0 +1,307 @@
+use crate::logs::NormalizedEvent;
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const BASELINE_BOOTS: usize = 5;
+const REGRESSION_RATIO: f64 = 1.5;
+const REGRESSION_MIN_DELTA_MS: u64 = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootRecord {
+    pub timestamp: String,
+    pub event_id: String,
+    pub source: String,
+    pub duration_ms: u64,
+    pub kernel_ms: Option<u64>,
+    pub userspace_ms: Option<u64>,
+    pub baseline_ms: Option<u64>,
+    pub regression: bool,
+    pub preceded_by_update: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootPerformanceResult {
+    pub boots: Vec<BootRecord>,
+    pub average_ms: Option<u64>,
+    pub latest_ms: Option<u64>,
+    pub regression_count: usize,
+    pub warnings: Vec<String>,
+}
+
+/// Parses systemd durations such as `1min 2.345s`, `850ms` or `7.4s` into milliseconds.
+fn parse_systemd_duration(value: &str) -> Option<u64> {
+    let mut total = 0f64;
+    let mut matched = false;
+    for token in value.split_whitespace() {
+        let split_at = token
+            .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+            .unwrap_or(token.len());
+        let (number, unit) = token.split_at(split_at);
+        let Ok(number) = number.parse::<f64>() else {
+            continue;
+        };
+        let factor = match unit {
+            "ms" => 1.0,
+            "s" => 1_000.0,
+            "min" => 60_000.0,
+            "h" => 3_600_000.0,
+            "us" | "µs" => 0.001,
+            _ => continue,
+        };
+        total += number * factor;
+        matched = true;
+    }
+    matched.then_some(total.round() as u64)
+}
+
+/// Extracts `(total, kernel, userspace)` from systemd's "Startup finished in ..." message.
+fn parse_systemd_startup(message: &str) -> Option<(u64, Option<u64>, Option<u64>)> {
+    let rest = message.split("Startup finished in").nth(1)?;
+    let (parts, total) = match rest.rsplit_once('=') {
+        Some((parts, total)) => (parts, total),
+        None => ("", rest),
+    };
+    let total = parse_systemd_duration(total.trim().trim_end_matches('.'))?;
+    let mut kernel = None;
+    let mut userspace = None;
+    for part in parts.split('+') {
+        let part = part.trim();
+        if let Some(value) = part.strip_suffix("(kernel)") {
+            kernel = parse_systemd_duration(value);
+        } else if let Some(value) = part.strip_suffix("(userspace)") {
+            userspace = parse_systemd_duration(value);
+        }
+    }
+    Some((total, kernel, userspace))
+}
+
+/// Windows Diagnostics-Performance 100 reports the boot duration either as rendered text
+/// ("Boot Duration : 45123ms") or as event data ("BootTime=45123").
+fn parse_windows_boot_duration(message: &str) -> Option<(u64, Option<u64>, Option<u64>)> {
+    let read_value = |keys: &[&str]| {
+        keys.iter().find_map(|key| {
+            // Skip matches embedded in longer names (BootTime inside MainPathBootTime).
+            let (index, _) = message.match_indices(key).find(|(index, _)| {
+                message[..*index]
+                    .chars()
+                    .next_back()
+                    .map(|ch| !ch.is_ascii_alphanumeric())
+                    .unwrap_or(true)
+            })?;
+            let digits: String = message[index + key.len()..]
+                .trim_start_matches([' ', ':', '='])
+                .chars()
+                .take_while(|ch| ch.is_ascii_digit())
+                .collect();
+            digits.parse::<u64>().ok()
+        })
+    };
+    let total = read_value(&["BootTime", "Boot Duration"])?;
+    let kernel = read_value(&["MainPathBootTime", "MainPath Boot Time"]);
+    let userspace = read_value(&["BootPostBootTime", "PostBoot Boot Time"]);
+    Some((total, kernel, userspace))
+}
+
+fn boot_duration(event: &NormalizedEvent) -> Option<(u64, Option<u64>, Option<u64>)> {
+    match event.os.as_str() {
+        "windows" => {
+            if event.event_id == Some(100)
+                && event
+                    .provider
+                    .to_ascii_lowercase()
+                    .contains("diagnostics-performance")
+            {
+                parse_windows_boot_duration(event.message.as_str())
+            } else {
+                None
+            }
+        }
+        _ => {
+            if event.message.contains("Startup finished in") {
+                parse_systemd_startup(event.message.as_str())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+pub fn is_update_event(event: &NormalizedEvent) -> bool {
+    let provider = event.provider.to_ascii_lowercase();
+    let lower = event.message.to_ascii_lowercase();
+    (provider.contains("windowsupdateclient") && matches!(event.event_id, Some(19) | Some(43)))
+        || (provider.contains("servicing") && event.event_id == Some(2))
+        || ((provider.contains("dpkg") || provider.contains("apt") || provider.contains("dnf") || provider.contains("packagekit"))
+            && (lower.contains("upgrade") || lower.contains("install")))
+        || (provider.contains("softwareupdate") && lower.contains("install"))
+}
+
+fn median(values: &[u64]) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        Some((sorted[mid - 1] + sorted[mid]) / 2)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+pub fn analyze_boot_performance(events: &[NormalizedEvent]) -> BootPerformanceResult {
+    let mut boots: Vec<(DateTime<Utc>, BootRecord)> = events
+        .iter()
+        .filter_map(|event| {
+            let (duration_ms, kernel_ms, userspace_ms) = boot_duration(event)?;
+            let at = parse_timestamp(event.timestamp.as_str())?;
+            Some((
+                at,
+                BootRecord {
+                    timestamp: event.timestamp.clone(),
+                    event_id: event.id.clone(),
+                    source: event.provider.clone(),
+                    duration_ms,
+                    kernel_ms,
+                    userspace_ms,
+                    baseline_ms: None,
+                    regression: false,
+                    preceded_by_update: false,
+                },
+            ))
+        })
+        .collect();
+    boots.sort_by(|left, right| left.0.cmp(&right.0));
+
+    let updates: Vec<DateTime<Utc>> = events
+        .iter()
+        .filter(|event| is_update_event(event))
+        .filter_map(|event| parse_timestamp(event.timestamp.as_str()))
+        .collect();
+
+    let mut history: Vec<u64> = Vec::new();
+    let mut previous_at: Option<DateTime<Utc>> = None;
+    for (at, boot) in boots.iter_mut() {
+        boot.preceded_by_update = updates
+            .iter()
+            .any(|update| *update <= *at && previous_at.map(|prev| *update > prev).unwrap_or(true));
+
+        let window_start = history.len().saturating_sub(BASELINE_BOOTS);
+        boot.baseline_ms = median(&history[window_start..]);
+        if let Some(baseline) = boot.baseline_ms {
+            boot.regression = (boot.duration_ms as f64) > (baseline as f64) * REGRESSION_RATIO
+                && boot.duration_ms.saturating_sub(baseline) >= REGRESSION_MIN_DELTA_MS;
+        }
+
+        history.push(boot.duration_ms);
+        previous_at = Some(*at);
+    }
+
+    let boots: Vec<BootRecord> = boots.into_iter().map(|(_, boot)| boot).collect();
+    let mut result = BootPerformanceResult {
+        average_ms: if boots.is_empty() {
+            None
+        } else {
+            Some(boots.iter().map(|boot| boot.duration_ms).sum::<u64>() / boots.len() as u64)
+        },
+        latest_ms: boots.last().map(|boot| boot.duration_ms),
+        regression_count: boots.iter().filter(|boot| boot.regression).count(),
+        boots,
+        warnings: Vec::new(),
+    };
+
+    if result.boots.is_empty() {
+        result.warnings.push(
+            "No boot timing records found in this range. On Windows, include the Diagnostics-Performance/Operational channel in the ingest profile."
+                .to_string(),
+        );
+    }
+    for boot in result
+        .boots
+        .iter()
+        .filter(|boot| boot.regression && boot.preceded_by_update)
+    {
+        result.warnings.push(format!(
+            "Boot at {} took {}s after an update was installed (baseline {}s).",
+            boot.timestamp,
+            boot.duration_ms / 1000,
+            boot.baseline_ms.unwrap_or_default() / 1000
+        ));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(os: &str, timestamp: &str, provider: &str, event_id: Option<u32>, message: &str) -> NormalizedEvent {
+        NormalizedEvent {
+            id: format!("evt-{timestamp}"),
+            timestamp: timestamp.to_string(),
+            os: os.to_string(),
+            log_name: "System".to_string(),
+            category: "system".to_string(),
+            provider: provider.to_string(),
+            event_id,
+            severity: "information".to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn parses_systemd_startup_message() {
+        let parsed = parse_systemd_startup(
+            "Startup finished in 2.345s (kernel) + 1min 5.100s (userspace) = 1min 7.445s.",
+        );
+        assert_eq!(parsed, Some((67_445, Some(2_345), Some(65_100))));
+    }
+
+    #[test]
+    fn flags_regression_after_update() {
+        let mut events = vec![];
+        for day in 1..=5 {
+            events.push(event(
+                "linux",
+                &format!("2026-03-0{day}T08:00:00Z"),
+                "systemd",
+                None,
+                "Startup finished in 2s (kernel) + 8s (userspace) = 10s.",
+            ));
+        }
+        events.push(event(
+            "linux",
+            "2026-03-05T20:00:00Z",
+            "dpkg",
+            None,
+            "status installed linux-image 6.8",
+        ));
+        events.push(event(
+            "linux",
+            "2026-03-06T08:00:00Z",
+            "systemd",
+            None,
+            "Startup finished in 2s (kernel) + 38s (userspace) = 40s.",
+        ));
+
+        let result = analyze_boot_performance(&events);
+        assert_eq!(result.boots.len(), 6);
+        let latest = result.boots.last().unwrap();
+        assert!(latest.regression);
+        assert!(latest.preceded_by_update);
+        assert_eq!(result.regression_count, 1);
+    }
+
+    #[test]
+    fn parses_windows_boot_event_data() {
+        let parsed = parse_windows_boot_duration("Data: BootTime=45123, MainPathBootTime=20000, BootPostBootTime=25123");
+        assert_eq!(parsed, Some((45_123, Some(20_000), Some(25_123))));
+    }
+}
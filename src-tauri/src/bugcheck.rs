@@ -0,0 +1,406 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashCodeExplanation {
+    pub code: String,
+    pub known: bool,
+    pub kind: String,
+    pub name: String,
+    pub description: String,
+    pub typical_causes: Vec<String>,
+    pub next_steps: Vec<String>,
+}
+
+struct KnownCode {
+    code: u32,
+    kind: &'static str,
+    name: &'static str,
+    description: &'static str,
+    typical_causes: &'static [&'static str],
+    next_steps: &'static [&'static str],
+}
+
+const KNOWN_CODES: &[KnownCode] = &[
+    KnownCode {
+        code: 0x0000_000A,
+        kind: "bugcheck",
+        name: "IRQL_NOT_LESS_OR_EQUAL",
+        description: "Kernel-mode code touched pageable or invalid memory at a raised interrupt level.",
+        typical_causes: &["Faulty or outdated driver", "Incompatible security/filter driver", "Defective RAM"],
+        next_steps: &["Update or roll back recently changed drivers", "Run Windows Memory Diagnostic", "Check the dump's faulting module before replacing hardware"],
+    },
+    KnownCode {
+        code: 0x0000_0019,
+        kind: "bugcheck",
+        name: "BAD_POOL_HEADER",
+        description: "A kernel pool allocation header was corrupted.",
+        typical_causes: &["Driver memory corruption", "Antivirus or backup filter drivers", "Defective RAM"],
+        next_steps: &["Enable Driver Verifier for recently installed drivers", "Update storage and security drivers", "Test memory"],
+    },
+    KnownCode {
+        code: 0x0000_001A,
+        kind: "bugcheck",
+        name: "MEMORY_MANAGEMENT",
+        description: "The memory manager detected a severe internal inconsistency.",
+        typical_causes: &["Defective or mis-seated RAM", "Unstable XMP/overclock settings", "Driver memory corruption"],
+        next_steps: &["Run a full memory test", "Reset BIOS memory settings to defaults", "Update chipset and storage drivers"],
+    },
+    KnownCode {
+        code: 0x0000_001E,
+        kind: "bugcheck",
+        name: "KMODE_EXCEPTION_NOT_HANDLED",
+        description: "A kernel-mode program raised an exception that no handler caught.",
+        typical_causes: &["Faulty driver", "Incompatible system service", "Hardware fault"],
+        next_steps: &["Identify the faulting module in the dump", "Update or remove the driver", "Check for recent firmware updates"],
+    },
+    KnownCode {
+        code: 0x0000_003B,
+        kind: "bugcheck",
+        name: "SYSTEM_SERVICE_EXCEPTION",
+        description: "An exception occurred while executing a routine that transitions from user to kernel mode.",
+        typical_causes: &["Graphics or antivirus driver faults", "Corrupted system files", "Outdated drivers"],
+        next_steps: &["Update graphics and security drivers", "Run sfc /scannow and DISM /RestoreHealth", "Review the faulting module"],
+    },
+    KnownCode {
+        code: 0x0000_0050,
+        kind: "bugcheck",
+        name: "PAGE_FAULT_IN_NONPAGED_AREA",
+        description: "The system referenced memory that is invalid or not resident.",
+        typical_causes: &["Faulty driver or service", "Defective RAM", "Corrupted NTFS volume"],
+        next_steps: &["Test memory", "Run chkdsk on the system volume", "Update or roll back the suspected driver"],
+    },
+    KnownCode {
+        code: 0x0000_007A,
+        kind: "bugcheck",
+        name: "KERNEL_DATA_INPAGE_ERROR",
+        description: "A page of kernel data could not be read from the paging file into memory.",
+        typical_causes: &["Failing disk or SSD", "Loose storage cabling", "Storage controller driver issues"],
+        next_steps: &["Check SMART/disk health", "Review storage (disk, stornvme, Ntfs) events before the crash", "Update storage controller firmware and drivers"],
+    },
+    KnownCode {
+        code: 0x0000_007B,
+        kind: "bugcheck",
+        name: "INACCESSIBLE_BOOT_DEVICE",
+        description: "Windows lost access to the system partition during startup.",
+        typical_causes: &["Storage controller mode change (AHCI/RAID)", "Missing boot storage driver", "Boot disk failure"],
+        next_steps: &["Revert recent BIOS storage mode changes", "Check disk health", "Use recovery to repair boot configuration"],
+    },
+    KnownCode {
+        code: 0x0000_007E,
+        kind: "bugcheck",
+        name: "SYSTEM_THREAD_EXCEPTION_NOT_HANDLED",
+        description: "A system thread generated an exception that the error handler did not catch.",
+        typical_causes: &["Incompatible or outdated driver", "Graphics driver faults after updates"],
+        next_steps: &["Identify the faulting driver in the dump", "Roll back or update that driver", "Boot into Safe Mode if the crash repeats at startup"],
+    },
+    KnownCode {
+        code: 0x0000_009F,
+        kind: "bugcheck",
+        name: "DRIVER_POWER_STATE_FAILURE",
+        description: "A driver failed to complete a power state transition (sleep, hibernate, or resume) in time.",
+        typical_causes: &["Network, USB, or graphics drivers blocking power IRPs", "Outdated firmware", "Docking station or peripheral drivers"],
+        next_steps: &["Update network, USB, and graphics drivers", "Update BIOS/firmware", "Check the dump for the blocked device stack"],
+    },
+    KnownCode {
+        code: 0x0000_00C2,
+        kind: "bugcheck",
+        name: "BAD_POOL_CALLER",
+        description: "The current thread made an invalid kernel pool request.",
+        typical_causes: &["Buggy driver", "Third-party security or VPN software"],
+        next_steps: &["Update or remove recently installed drivers", "Use Driver Verifier to pinpoint the caller"],
+    },
+    KnownCode {
+        code: 0x0000_00D1,
+        kind: "bugcheck",
+        name: "DRIVER_IRQL_NOT_LESS_OR_EQUAL",
+        description: "A driver accessed pageable or invalid memory at a raised interrupt level.",
+        typical_causes: &["Network adapter drivers", "Storage or filter drivers", "Outdated third-party drivers"],
+        next_steps: &["Update the driver named in the dump", "Roll back recent driver updates", "Check vendor release notes for known issues"],
+    },
+    KnownCode {
+        code: 0x0000_00EF,
+        kind: "bugcheck",
+        name: "CRITICAL_PROCESS_DIED",
+        description: "A critical system process terminated unexpectedly.",
+        typical_causes: &["Corrupted system files", "Failing storage", "Security software terminating system processes"],
+        next_steps: &["Run sfc /scannow and DISM /RestoreHealth", "Check disk health", "Review recent security software changes"],
+    },
+    KnownCode {
+        code: 0x0000_00F4,
+        kind: "bugcheck",
+        name: "CRITICAL_OBJECT_TERMINATION",
+        description: "A process or thread crucial to system operation exited or was terminated.",
+        typical_causes: &["Storage I/O failures", "Disk cable or controller problems", "Malware or security software interference"],
+        next_steps: &["Check disk and controller health", "Review storage events before the crash", "Scan for malware"],
+    },
+    KnownCode {
+        code: 0x0000_0101,
+        kind: "bugcheck",
+        name: "CLOCK_WATCHDOG_TIMEOUT",
+        description: "A secondary processor did not respond to a clock interrupt in time.",
+        typical_causes: &["CPU instability or overclocking", "Outdated BIOS/microcode", "Hardware fault"],
+        next_steps: &["Remove overclocks and reset BIOS defaults", "Update BIOS and chipset drivers", "Check CPU temperatures"],
+    },
+    KnownCode {
+        code: 0x0000_0116,
+        kind: "bugcheck",
+        name: "VIDEO_TDR_FAILURE",
+        description: "The display driver stopped responding and could not be recovered.",
+        typical_causes: &["Graphics driver faults", "GPU overheating or overclocking", "Insufficient power to the GPU"],
+        next_steps: &["Clean-install the graphics driver", "Check GPU temperatures and power", "Remove GPU overclocks"],
+    },
+    KnownCode {
+        code: 0x0000_0124,
+        kind: "bugcheck",
+        name: "WHEA_UNCORRECTABLE_ERROR",
+        description: "The hardware reported an uncorrectable error (CPU, memory, or bus).",
+        typical_causes: &["CPU or memory hardware faults", "Overclocking or undervolting", "Overheating", "Outdated BIOS"],
+        next_steps: &["Review WHEA-Logger events for the failing component", "Reset BIOS to defaults and update firmware", "Engage hardware vendor if errors persist"],
+    },
+    KnownCode {
+        code: 0x0000_0133,
+        kind: "bugcheck",
+        name: "DPC_WATCHDOG_VIOLATION",
+        description: "A deferred procedure call ran too long or the system spent too long at DISPATCH_LEVEL.",
+        typical_causes: &["Storage (SATA/NVMe) controller drivers", "Outdated SSD firmware", "Network drivers"],
+        next_steps: &["Update storage controller drivers and SSD firmware", "Check storage events before the crash", "Update network drivers"],
+    },
+    KnownCode {
+        code: 0x0000_0139,
+        kind: "bugcheck",
+        name: "KERNEL_SECURITY_CHECK_FAILURE",
+        description: "The kernel detected corruption of a critical data structure.",
+        typical_causes: &["Incompatible drivers", "Memory corruption", "Corrupted system files"],
+        next_steps: &["Update or remove recently installed drivers", "Test memory", "Run sfc /scannow"],
+    },
+    KnownCode {
+        code: 0x0000_013A,
+        kind: "bugcheck",
+        name: "KERNEL_MODE_HEAP_CORRUPTION",
+        description: "The kernel heap manager detected corruption.",
+        typical_causes: &["Graphics or third-party kernel drivers", "Memory faults"],
+        next_steps: &["Update graphics drivers", "Use Driver Verifier on third-party drivers", "Test memory"],
+    },
+    KnownCode {
+        code: 0x0000_0154,
+        kind: "bugcheck",
+        name: "UNEXPECTED_STORE_EXCEPTION",
+        description: "The kernel store component caught an unexpected exception.",
+        typical_causes: &["Failing disk or SSD", "Antivirus interference", "Outdated storage drivers"],
+        next_steps: &["Check disk health", "Update storage drivers and firmware", "Temporarily disable third-party antivirus to test"],
+    },
+    KnownCode {
+        code: 0x8000_0003,
+        kind: "exception",
+        name: "STATUS_BREAKPOINT",
+        description: "A breakpoint or assertion was hit outside of a debugger.",
+        typical_causes: &["Application assertion or deliberate fail-fast", "Debug build deployed to users"],
+        next_steps: &["Check the application's own logs for assertion messages", "Update the application"],
+    },
+    KnownCode {
+        code: 0xC000_0005,
+        kind: "exception",
+        name: "STATUS_ACCESS_VIOLATION",
+        description: "The process read or wrote memory it does not own.",
+        typical_causes: &["Application bug", "Incompatible plugin or injected DLL", "Corrupted installation"],
+        next_steps: &["Update or repair the application", "Check the faulting module for third-party DLLs", "Disable add-ins or overlays"],
+    },
+    KnownCode {
+        code: 0xC000_001D,
+        kind: "exception",
+        name: "STATUS_ILLEGAL_INSTRUCTION",
+        description: "The process executed an invalid CPU instruction.",
+        typical_causes: &["Binary built for newer CPU features", "Memory corruption", "CPU instability"],
+        next_steps: &["Confirm the application supports this CPU", "Remove overclocks", "Reinstall the application"],
+    },
+    KnownCode {
+        code: 0xC000_00FD,
+        kind: "exception",
+        name: "STATUS_STACK_OVERFLOW",
+        description: "The thread exhausted its stack, usually through unbounded recursion.",
+        typical_causes: &["Application bug", "Unexpectedly large input"],
+        next_steps: &["Update the application", "Capture the input or workflow that triggers the crash"],
+    },
+    KnownCode {
+        code: 0xC000_0142,
+        kind: "exception",
+        name: "STATUS_DLL_INIT_FAILED",
+        description: "A DLL failed to initialize while the process was starting.",
+        typical_causes: &["Desktop heap exhaustion", "Missing or corrupted runtime DLLs", "Security software blocking the load"],
+        next_steps: &["Repair Visual C++ runtimes", "Reinstall the application", "Check security software block logs"],
+    },
+    KnownCode {
+        code: 0xC000_0374,
+        kind: "exception",
+        name: "STATUS_HEAP_CORRUPTION",
+        description: "The user-mode heap detected corruption.",
+        typical_causes: &["Application memory bug", "Incompatible plugin or shell extension"],
+        next_steps: &["Update the application and its plugins", "Check the faulting module for third-party code"],
+    },
+    KnownCode {
+        code: 0xC000_0409,
+        kind: "exception",
+        name: "STATUS_STACK_BUFFER_OVERRUN",
+        description: "A security check or fail-fast detected a stack buffer overrun or invalid state.",
+        typical_causes: &["Application bug triggering fail-fast", "Corrupted runtime", "Incompatible injected DLLs"],
+        next_steps: &["Update the application", "Repair Visual C++ runtimes", "Remove overlays or injected tools"],
+    },
+    KnownCode {
+        code: 0xE043_4352,
+        kind: "exception",
+        name: "CLR_EXCEPTION",
+        description: "An unhandled .NET exception terminated the process.",
+        typical_causes: &["Application bug", "Missing .NET runtime components", "Corrupted configuration"],
+        next_steps: &["Check Application log event 1026 (.NET Runtime) for the managed stack", "Repair the .NET runtime", "Update the application"],
+    },
+];
+
+struct KnownSignal {
+    name: &'static str,
+    description: &'static str,
+    typical_causes: &'static [&'static str],
+    next_steps: &'static [&'static str],
+}
+
+const KNOWN_SIGNALS: &[KnownSignal] = &[
+    KnownSignal {
+        name: "SIGSEGV",
+        description: "The process accessed memory it is not allowed to access.",
+        typical_causes: &["Application bug", "Incompatible shared library", "Defective RAM if many processes crash"],
+        next_steps: &["Update the application and its libraries", "Inspect the core dump backtrace", "Run a memory test if crashes are widespread"],
+    },
+    KnownSignal {
+        name: "SIGABRT",
+        description: "The process aborted itself, usually after a failed assertion or detected corruption.",
+        typical_causes: &["Failed assertion", "Heap corruption detected by the allocator", "Uncaught C++ exception"],
+        next_steps: &["Check the journal for the assertion text just before the crash", "Update the application"],
+    },
+    KnownSignal {
+        name: "SIGBUS",
+        description: "The process accessed memory that cannot be backed, such as a truncated mapped file.",
+        typical_causes: &["File truncated while memory-mapped", "Storage I/O errors", "Misaligned access on strict architectures"],
+        next_steps: &["Check for disk errors", "Verify the files the application maps were not modified"],
+    },
+    KnownSignal {
+        name: "SIGILL",
+        description: "The process executed an illegal CPU instruction.",
+        typical_causes: &["Binary built for newer CPU features", "Memory corruption"],
+        next_steps: &["Confirm the build targets this CPU", "Reinstall the package"],
+    },
+    KnownSignal {
+        name: "SIGFPE",
+        description: "The process performed an invalid arithmetic operation such as division by zero.",
+        typical_causes: &["Application bug", "Unexpected input"],
+        next_steps: &["Update the application", "Capture the input that triggers the crash"],
+    },
+    KnownSignal {
+        name: "SIGKILL",
+        description: "The process was forcibly killed, often by the out-of-memory killer.",
+        typical_causes: &["Out-of-memory killer", "Administrator or supervisor termination"],
+        next_steps: &["Search the journal for 'Out of memory' around the crash", "Review memory limits for the service"],
+    },
+];
+
+/// Parses a crash code written as `0x0000009F`, `9F`, `0000009F`, or a decimal value such as `159`
+/// (the form Kernel-Power 41 uses for BugcheckCode).
+pub fn parse_crash_code(code: &str) -> Option<u32> {
+    let trimmed = code.trim();
+    if let Some(hex) = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    {
+        return u32::from_str_radix(hex, 16).ok();
+    }
+    if trimmed.is_empty() || !trimmed.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let has_hex_letters = trimmed.chars().any(|ch| ch.is_ascii_alphabetic());
+    if has_hex_letters || trimmed.len() == 8 {
+        u32::from_str_radix(trimmed, 16).ok()
+    } else {
+        trimmed.parse::<u32>().ok()
+    }
+}
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+pub fn explain_crash_code(code: &str) -> CrashCodeExplanation {
+    let trimmed = code.trim();
+    let upper = trimmed.to_ascii_uppercase();
+    if let Some(signal) = KNOWN_SIGNALS
+        .iter()
+        .find(|signal| upper == signal.name || upper == signal.name[3..])
+    {
+        return CrashCodeExplanation {
+            code: signal.name.to_string(),
+            known: true,
+            kind: "signal".to_string(),
+            name: signal.name.to_string(),
+            description: signal.description.to_string(),
+            typical_causes: to_strings(signal.typical_causes),
+            next_steps: to_strings(signal.next_steps),
+        };
+    }
+
+    let parsed = parse_crash_code(trimmed);
+    if let Some(entry) = parsed.and_then(|value| KNOWN_CODES.iter().find(|entry| entry.code == value)) {
+        return CrashCodeExplanation {
+            code: format!("0x{:08X}", entry.code),
+            known: true,
+            kind: entry.kind.to_string(),
+            name: entry.name.to_string(),
+            description: entry.description.to_string(),
+            typical_causes: to_strings(entry.typical_causes),
+            next_steps: to_strings(entry.next_steps),
+        };
+    }
+
+    CrashCodeExplanation {
+        code: parsed
+            .map(|value| format!("0x{value:08X}"))
+            .unwrap_or_else(|| trimmed.to_string()),
+        known: false,
+        kind: "unknown".to_string(),
+        name: "Unknown code".to_string(),
+        description: "Hermes does not have a reference entry for this code yet.".to_string(),
+        typical_causes: Vec::new(),
+        next_steps: vec![
+            "Search Microsoft's bug check code reference or the vendor knowledge base for this code.".to_string(),
+            "Review the related events and suspected module for additional context.".to_string(),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_and_decimal_codes() {
+        assert_eq!(parse_crash_code("0x0000009F"), Some(0x9F));
+        assert_eq!(parse_crash_code("0000009F"), Some(0x9F));
+        assert_eq!(parse_crash_code("9f"), Some(0x9F));
+        assert_eq!(parse_crash_code("159"), Some(0x9F));
+        assert_eq!(parse_crash_code("not a code"), None);
+    }
+
+    #[test]
+    fn explains_known_bugcheck() {
+        let explanation = explain_crash_code("0x9F");
+        assert!(explanation.known);
+        assert_eq!(explanation.name, "DRIVER_POWER_STATE_FAILURE");
+        assert_eq!(explanation.code, "0x0000009F");
+        assert!(!explanation.next_steps.is_empty());
+    }
+
+    #[test]
+    fn explains_signals_and_unknown_codes() {
+        assert_eq!(explain_crash_code("segv").name, "SIGSEGV");
+        let unknown = explain_crash_code("0x12345678");
+        assert!(!unknown.known);
+        assert_eq!(unknown.code, "0x12345678");
+    }
+}
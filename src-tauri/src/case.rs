@@ -0,0 +1,286 @@
+use crate::crash::CrashRecord;
+use crate::db::EventBookmark;
+use crate::event_export::{build_export, parse_event_export};
+use crate::logs::NormalizedEvent;
+use crate::report::ReportAnalysis;
+use chrono::Utc;
+use dirs::data_local_dir;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub const CASE_EXTENSION: &str = "hlacase";
+pub const CASE_FORMAT: &str = "hermes.case";
+pub const CASE_FORMAT_VERSION: u32 = 1;
+/// Full memory dumps can run to gigabytes; larger raw files are left out and noted instead.
+const MAX_RAW_FILE_BYTES: u64 = 256 * 1024 * 1024;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const CRASH_ENTRY: &str = "crash.json";
+const EVENTS_ENTRY: &str = "events.ndjson";
+const NOTES_ENTRY: &str = "notes.json";
+const RAW_PREFIX: &str = "raw/";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseManifest {
+    pub format: String,
+    pub format_version: u32,
+    pub exported_at: String,
+    pub crash_id: String,
+    pub event_count: usize,
+    /// File name of the raw crash file under `raw/`, when it was included.
+    pub raw_file: Option<String>,
+    /// Why the raw crash file is missing, when the crash had one.
+    pub raw_file_skipped: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseNotes {
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub bookmarks: Vec<EventBookmark>,
+    pub analysis: Option<ReportAnalysis>,
+}
+
+pub struct ImportedCase {
+    pub manifest: CaseManifest,
+    pub crash: CrashRecord,
+    pub events: Vec<NormalizedEvent>,
+    pub notes: CaseNotes,
+    pub raw_file: Option<PathBuf>,
+}
+
+/// Where raw crash files from imported cases are unpacked, one folder per crash.
+pub fn cases_dir() -> Result<PathBuf, String> {
+    let mut base = data_local_dir().ok_or("Unable to resolve local data directory")?;
+    base.push("hermes-log-analyst");
+    base.push("cases");
+    fs::create_dir_all(&base).map_err(|e| format!("Failed to create cases directory: {e}"))?;
+    Ok(base)
+}
+
+fn zip_error(error: impl std::fmt::Display) -> String {
+    format!("Failed to write case bundle: {error}")
+}
+
+/// Keeps only the final path component so an entry name can never escape the target folder.
+fn safe_file_name(name: &str) -> Option<String> {
+    let file_name = name.rsplit(['/', '\\']).next()?.trim();
+    if file_name.is_empty() || file_name == "." || file_name == ".." {
+        return None;
+    }
+    Some(file_name.to_string())
+}
+
+fn write_json<W: Write + Seek, T: Serialize>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    value: &T,
+) -> Result<(), String> {
+    let payload = serde_json::to_vec_pretty(value).map_err(zip_error)?;
+    zip.start_file(name, SimpleFileOptions::default())
+        .map_err(zip_error)?;
+    zip.write_all(payload.as_slice()).map_err(zip_error)
+}
+
+/// Writes a `.hlacase` bundle: the crash, its related events in the NDJSON export format,
+/// analysis notes and, when small enough, a copy of the raw crash file.
+pub fn write_case<W: Write + Seek>(
+    out: W,
+    crash: &CrashRecord,
+    events: &[NormalizedEvent],
+    notes: &CaseNotes,
+) -> Result<CaseManifest, String> {
+    let mut zip = ZipWriter::new(out);
+    let raw_path = crash.raw_path.as_deref().map(Path::new);
+    let mut raw_file = None;
+    let mut raw_file_skipped = None;
+
+    if let Some(path) = raw_path {
+        match fs::metadata(path) {
+            Ok(metadata) if metadata.len() > MAX_RAW_FILE_BYTES => {
+                raw_file_skipped = Some(format!(
+                    "Raw crash file is {} MB, above the {} MB bundle limit.",
+                    metadata.len() / (1024 * 1024),
+                    MAX_RAW_FILE_BYTES / (1024 * 1024)
+                ));
+            }
+            Ok(_) => {
+                let name = path
+                    .file_name()
+                    .and_then(|name| safe_file_name(name.to_string_lossy().as_ref()))
+                    .unwrap_or_else(|| "crash.bin".to_string());
+                let mut source = fs::File::open(path).map_err(zip_error)?;
+                zip.start_file(
+                    format!("{RAW_PREFIX}{name}"),
+                    SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+                )
+                .map_err(zip_error)?;
+                std::io::copy(&mut source, &mut zip).map_err(zip_error)?;
+                raw_file = Some(name);
+            }
+            Err(error) => {
+                raw_file_skipped = Some(format!("Raw crash file is not readable: {error}"));
+            }
+        }
+    }
+
+    let manifest = CaseManifest {
+        format: CASE_FORMAT.to_string(),
+        format_version: CASE_FORMAT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        crash_id: crash.id.clone(),
+        event_count: events.len(),
+        raw_file,
+        raw_file_skipped,
+    };
+    write_json(&mut zip, MANIFEST_ENTRY, &manifest)?;
+    write_json(&mut zip, CRASH_ENTRY, crash)?;
+    write_json(&mut zip, NOTES_ENTRY, notes)?;
+    let events_payload = build_export("ndjson", events)?;
+    zip.start_file(EVENTS_ENTRY, SimpleFileOptions::default())
+        .map_err(zip_error)?;
+    zip.write_all(events_payload.as_slice())
+        .map_err(zip_error)?;
+    zip.finish().map_err(zip_error)?;
+    Ok(manifest)
+}
+
+fn read_entry<R: Read + Seek>(zip: &mut ZipArchive<R>, name: &str) -> Result<String, String> {
+    let mut entry = zip
+        .by_name(name)
+        .map_err(|_| format!("Case bundle is missing {name}."))?;
+    let mut text = String::new();
+    entry
+        .read_to_string(&mut text)
+        .map_err(|error| format!("Failed to read {name} from case bundle: {error}"))?;
+    Ok(text)
+}
+
+/// Reads a `.hlacase` bundle. The raw crash file, if any, is unpacked under `raw_dir/<crash id>`
+/// and the returned crash points at it; the crash and events are flagged as imported.
+pub fn read_case<R: Read + Seek>(reader: R, raw_dir: &Path) -> Result<ImportedCase, String> {
+    let mut zip =
+        ZipArchive::new(reader).map_err(|error| format!("Not a valid case bundle: {error}"))?;
+    let manifest: CaseManifest =
+        serde_json::from_str(read_entry(&mut zip, MANIFEST_ENTRY)?.as_str())
+            .map_err(|error| format!("Case manifest is invalid: {error}"))?;
+    if manifest.format != CASE_FORMAT {
+        return Err("File is not a Hermes case bundle.".to_string());
+    }
+    if manifest.format_version > CASE_FORMAT_VERSION {
+        return Err(format!(
+            "Case bundle version {} is newer than this app supports ({CASE_FORMAT_VERSION}).",
+            manifest.format_version
+        ));
+    }
+
+    let mut crash: CrashRecord = serde_json::from_str(read_entry(&mut zip, CRASH_ENTRY)?.as_str())
+        .map_err(|error| format!("Case crash record is invalid: {error}"))?;
+    let notes: CaseNotes = serde_json::from_str(read_entry(&mut zip, NOTES_ENTRY)?.as_str())
+        .map_err(|error| format!("Case notes are invalid: {error}"))?;
+    let mut events = parse_event_export(read_entry(&mut zip, EVENTS_ENTRY)?.as_str())?;
+    for event in &mut events {
+        event.imported = true;
+    }
+
+    let mut raw_file = None;
+    if let Some(name) = manifest.raw_file.as_deref().and_then(safe_file_name) {
+        let crash_dir = raw_dir.join(safe_file_name(crash.id.as_str()).unwrap_or_default());
+        fs::create_dir_all(&crash_dir)
+            .map_err(|error| format!("Failed to create case folder: {error}"))?;
+        let target = crash_dir.join(name.as_str());
+        let mut entry = zip
+            .by_name(format!("{RAW_PREFIX}{name}").as_str())
+            .map_err(|_| "Case bundle is missing its raw crash file.".to_string())?;
+        let mut file = fs::File::create(&target)
+            .map_err(|error| format!("Failed to unpack raw crash file: {error}"))?;
+        std::io::copy(&mut entry, &mut file)
+            .map_err(|error| format!("Failed to unpack raw crash file: {error}"))?;
+        raw_file = Some(target);
+    }
+
+    crash.imported = true;
+    crash.raw_path = raw_file
+        .as_ref()
+        .map(|path| path.to_string_lossy().to_string());
+    Ok(ImportedCase {
+        manifest,
+        crash,
+        events,
+        notes,
+        raw_file,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+    use std::io::Cursor;
+
+    #[test]
+    fn bundle_round_trips_crash_events_notes_and_raw_file() {
+        let work_dir = std::env::temp_dir().join(format!("hermes-case-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&work_dir).unwrap();
+        let raw_path = work_dir.join("app.dmp");
+        fs::write(&raw_path, b"MDMP-test").unwrap();
+
+        let mut crash = CrashRecord::new(
+            "windows",
+            "WER",
+            "Application Crash",
+            None,
+            "app.exe crashed",
+            Some("app.exe"),
+            Some(raw_path.to_string_lossy().as_ref()),
+            "host-a",
+            false,
+        );
+        crash.details = Some("stack".to_string());
+        let event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "Application",
+            "application",
+            "Application Error",
+            Some(1000),
+            "error",
+            "Faulting application app.exe",
+            "host-a",
+        );
+        let notes = CaseNotes {
+            notes: Some("Escalating to kernel team".to_string()),
+            bookmarks: vec![EventBookmark {
+                event_id: event.id.clone(),
+                note: Some("first fault".to_string()),
+                created_at: "2024-03-01T10:00:00Z".to_string(),
+            }],
+            analysis: None,
+        };
+
+        let mut bundle = Cursor::new(Vec::new());
+        let manifest =
+            write_case(&mut bundle, &crash, std::slice::from_ref(&event), &notes).unwrap();
+        assert_eq!(manifest.raw_file.as_deref(), Some("app.dmp"));
+
+        bundle.set_position(0);
+        let imported = read_case(bundle, work_dir.join("cases").as_path()).unwrap();
+        assert_eq!(imported.crash.id, crash.id);
+        assert!(imported.crash.imported);
+        assert_eq!(imported.events.len(), 1);
+        assert_eq!(imported.events[0].id, event.id);
+        assert!(imported.events[0].imported);
+        assert_eq!(imported.notes.bookmarks.len(), 1);
+        let raw_file = imported.raw_file.unwrap();
+        assert_eq!(fs::read(&raw_file).unwrap(), b"MDMP-test");
+        assert_eq!(
+            imported.crash.raw_path.as_deref(),
+            Some(raw_file.to_string_lossy().as_ref())
+        );
+        let _ = fs::remove_dir_all(&work_dir);
+    }
+}
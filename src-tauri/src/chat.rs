@@ -0,0 +1,186 @@
+use crate::crash::CrashRecord;
+use crate::db::{ChatMessage, ChatSession};
+use crate::logs::NormalizedEvent;
+use crate::rca::redact_sensitive_text;
+use crate::summarize::compact_events;
+
+/// Characters of earlier conversation replayed to the model with each new message.
+const MAX_HISTORY_CHARS: usize = 24_000;
+const CONTEXT_EVENT_TOKEN_BUDGET: usize = 1500;
+const MAX_TITLE_CHARS: usize = 60;
+
+/// Title for a new conversation taken from its first message.
+pub fn title_from_message(content: &str) -> String {
+    let line = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("New conversation");
+    if line.chars().count() <= MAX_TITLE_CHARS {
+        line.to_string()
+    } else {
+        let truncated: String = line.chars().take(MAX_TITLE_CHARS).collect();
+        format!("{}...", truncated.trim_end())
+    }
+}
+
+/// Renders the crash and events attached to a message into the text stored with it and sent to
+/// the model.
+pub fn build_context_block(
+    crash: Option<&CrashRecord>,
+    events: &[NormalizedEvent],
+    redact: bool,
+) -> Option<String> {
+    let protect = |value: &str| {
+        if redact {
+            redact_sensitive_text(value)
+        } else {
+            value.to_string()
+        }
+    };
+    let mut lines = Vec::new();
+    if let Some(crash) = crash {
+        lines.push(format!(
+            "Crash {}: {} at {} ({})",
+            crash.id,
+            protect(crash.summary.as_str()),
+            crash.timestamp,
+            protect(crash.crash_type.as_str())
+        ));
+        if let Some(code) = crash.code.as_deref() {
+            lines.push(format!("- Code: {}", protect(code)));
+        }
+        if let Some(component) = crash.suspected_component.as_deref() {
+            lines.push(format!("- Suspected component: {}", protect(component)));
+        }
+    }
+    if !events.is_empty() {
+        let (event_lines, omitted) = compact_events(events, CONTEXT_EVENT_TOKEN_BUDGET, redact);
+        lines.push(format!("Attached events ({}):", events.len()));
+        lines.extend(event_lines);
+        if omitted > 0 {
+            lines.push(format!("({omitted} lower-priority messages omitted)"));
+        }
+    }
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn render_turn(message: &ChatMessage) -> String {
+    let speaker = if message.role == "assistant" {
+        "Assistant"
+    } else {
+        "User"
+    };
+    match message.context.as_deref() {
+        Some(context) => format!("{speaker}: {}\n[Context]\n{context}", message.content),
+        None => format!("{speaker}: {}", message.content),
+    }
+}
+
+/// Builds a single prompt from the conversation so far. The oldest turns are dropped first when
+/// the history exceeds the character budget; the new message is always kept.
+pub fn build_chat_prompt(history: &[ChatMessage], message: &ChatMessage) -> String {
+    let mut turns = Vec::new();
+    let mut used = 0;
+    let mut dropped = 0;
+    for previous in history.iter().rev() {
+        let turn = render_turn(previous);
+        if used + turn.len() > MAX_HISTORY_CHARS {
+            dropped = history.len() - turns.len();
+            break;
+        }
+        used += turn.len();
+        turns.push(turn);
+    }
+    turns.reverse();
+
+    let mut prompt = vec![
+        "You are a troubleshooting assistant helping an IT technician investigate logs and crashes.".to_string(),
+        "Answer the latest user message using the conversation and any attached context. Prefer evidence-backed reasoning and suggest safe read-only checks before changes.".to_string(),
+        "Preserve \"<sensitive info redacted>\" placeholders exactly if present.".to_string(),
+        String::new(),
+    ];
+    if dropped > 0 {
+        prompt.push(format!("({dropped} earlier messages omitted.)"));
+    }
+    prompt.extend(turns);
+    prompt.push(render_turn(message));
+    prompt.push("Assistant:".to_string());
+    prompt.join("\n\n")
+}
+
+/// Markdown transcript for exporting a conversation.
+pub fn render_chat_markdown(session: &ChatSession, messages: &[ChatMessage]) -> String {
+    let mut lines = vec![
+        format!("# {}", session.title),
+        String::new(),
+        format!("Started {}.", session.created_at),
+    ];
+    for message in messages {
+        lines.push(String::new());
+        let heading = match (message.role.as_str(), message.model.as_deref()) {
+            ("assistant", Some(model)) => {
+                format!("## Assistant ({model}) - {}", message.created_at)
+            }
+            ("assistant", None) => format!("## Assistant - {}", message.created_at),
+            _ => format!("## User - {}", message.created_at),
+        };
+        lines.push(heading);
+        lines.push(String::new());
+        lines.push(message.content.clone());
+        if let Some(context) = message.context.as_deref() {
+            lines.push(String::new());
+            lines.push("```".to_string());
+            lines.push(context.to_string());
+            lines.push("```".to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            id: content.to_string(),
+            session_id: "s1".to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            context: None,
+            profile_name: None,
+            model: None,
+            created_at: "2026-03-01T10:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn prompt_keeps_recent_history_within_budget() {
+        let long = "x".repeat(MAX_HISTORY_CHARS);
+        let history = vec![
+            message("user", format!("old {long}").as_str()),
+            message("assistant", format!("older answer {long}").as_str()),
+            message("user", "Why did explorer crash?"),
+            message("assistant", "A shell extension faulted."),
+        ];
+        let mut latest = message("user", "Which extension?");
+        latest.context = Some("Crash c1: explorer.exe".to_string());
+
+        let prompt = build_chat_prompt(history.as_slice(), &latest);
+        assert!(!prompt.contains("old x"));
+        assert!(prompt.contains("(2 earlier messages omitted.)"));
+        assert!(prompt.contains("Assistant: A shell extension faulted."));
+        assert!(prompt
+            .ends_with("User: Which extension?\n[Context]\nCrash c1: explorer.exe\n\nAssistant:"));
+    }
+
+    #[test]
+    fn titles_are_trimmed_to_first_line() {
+        assert_eq!(
+            title_from_message("\n  Disk errors on FS-01\nmore"),
+            "Disk errors on FS-01"
+        );
+        assert!(title_from_message(&"a".repeat(100)).ends_with("..."));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::crash::CrashRecord;
+use crate::logs::NormalizedEvent;
+use crate::timestamps::parse_timestamp;
+use serde::{Deserialize, Serialize};
+
+const TEMPORAL_WEIGHT: f64 = 40.0;
+const SEVERITY_WEIGHT: f64 = 25.0;
+const COMPONENT_WEIGHT: f64 = 35.0;
+/// Events after the crash are usually fallout rather than cause.
+const AFTER_CRASH_FACTOR: f64 = 0.6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoredEvent {
+    #[serde(flatten)]
+    pub event: NormalizedEvent,
+    pub relevance_score: f64,
+    pub relevance_reasons: Vec<String>,
+}
+
+fn severity_weight(severity: &str) -> f64 {
+    match severity {
+        "critical" => 1.0,
+        "error" => 0.8,
+        "warning" => 0.5,
+        _ => 0.1,
+    }
+}
+
+/// Names that identify the crashing component: the suspected module plus its stem
+/// (`ntdll.dll` also matches `ntdll`), and the process named in the summary.
+fn component_terms(crash: &CrashRecord) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut push = |value: &str| {
+        let base = value
+            .rsplit(['\\', '/'])
+            .next()
+            .unwrap_or(value)
+            .trim()
+            .to_ascii_lowercase();
+        if base.len() < 3 {
+            return;
+        }
+        let stem = base
+            .rsplit_once('.')
+            .map(|(stem, _)| stem.to_string())
+            .filter(|stem| stem.len() >= 3);
+        // Full names go first so reasons quote the most specific match.
+        for term in std::iter::once(base).chain(stem) {
+            if !terms.contains(&term) {
+                terms.push(term);
+            }
+        }
+    };
+    if let Some(component) = crash.suspected_component.as_deref() {
+        push(component);
+    }
+    // Summaries look like "Application Crash: foo.exe" or "Application Error: foo.exe (0x... in bar.dll)".
+    if let Some((_, rest)) = crash.summary.split_once(": ") {
+        if let Some(process) = rest.split_whitespace().next() {
+            push(process);
+        }
+    }
+    terms
+}
+
+fn describe_offset(seconds: i64) -> String {
+    let magnitude = seconds.abs();
+    let amount = if magnitude < 120 {
+        format!("{magnitude}s")
+    } else {
+        format!("{}m", magnitude / 60)
+    };
+    if seconds <= 0 {
+        format!("{amount} before the crash")
+    } else {
+        format!("{amount} after the crash")
+    }
+}
+
+/// Ranks events around a crash by temporal proximity, severity and whether they mention the
+/// crashing component or code. Scores range from 0 to 100.
+pub fn score_related_events(
+    crash: &CrashRecord,
+    events: Vec<NormalizedEvent>,
+    window_minutes: i64,
+) -> Vec<ScoredEvent> {
+    let crash_at = parse_timestamp(crash.timestamp.as_str());
+    let window_seconds = (window_minutes.max(1) * 60) as f64;
+    let terms = component_terms(crash);
+    let code = crash
+        .code
+        .as_deref()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| value.len() >= 3);
+
+    let mut scored: Vec<ScoredEvent> = events
+        .into_iter()
+        .map(|event| {
+            let mut score = 0.0;
+            let mut reasons = Vec::new();
+
+            if let (Some(crash_at), Some(event_at)) =
+                (crash_at, parse_timestamp(event.timestamp.as_str()))
+            {
+                let offset = (event_at - crash_at).num_seconds();
+                let closeness = (1.0 - offset.abs() as f64 / window_seconds).clamp(0.0, 1.0);
+                let factor = if offset > 0 { AFTER_CRASH_FACTOR } else { 1.0 };
+                score += TEMPORAL_WEIGHT * closeness * factor;
+                reasons.push(describe_offset(offset));
+            }
+
+            score += SEVERITY_WEIGHT * severity_weight(event.severity.as_str());
+            if matches!(event.severity.as_str(), "critical" | "error" | "warning") {
+                reasons.push(format!("{} severity", event.severity));
+            }
+
+            let haystack = format!("{} {}", event.provider, event.message).to_ascii_lowercase();
+            if let Some(term) = terms.iter().find(|term| haystack.contains(term.as_str())) {
+                score += COMPONENT_WEIGHT;
+                reasons.push(format!("mentions {term}"));
+            } else if let Some(code) = code.as_deref().filter(|code| haystack.contains(code)) {
+                score += COMPONENT_WEIGHT * 0.8;
+                reasons.push(format!("mentions crash code {code}"));
+            }
+
+            ScoredEvent {
+                event,
+                relevance_score: (score * 10.0).round() / 10.0,
+                relevance_reasons: reasons,
+            }
+        })
+        .collect();
+
+    scored.sort_by(|left, right| {
+        right
+            .relevance_score
+            .total_cmp(&left.relevance_score)
+            .then_with(|| right.event.timestamp.cmp(&left.event.timestamp))
+    });
+    scored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, timestamp: &str, provider: &str, severity: &str, message: &str) -> NormalizedEvent {
+        NormalizedEvent {
+            id: id.to_string(),
+            timestamp: timestamp.to_string(),
+            os: "windows".to_string(),
+            log_name: "Application".to_string(),
+            category: "application".to_string(),
+            provider: provider.to_string(),
+            event_id: None,
+            severity: severity.to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    fn crash() -> CrashRecord {
+        let mut crash = CrashRecord::new(
+            "windows",
+            "WER",
+            "APPCRASH",
+            Some("0xc0000005"),
+            "APPCRASH: outlook.exe",
+            Some("C:\\Windows\\System32\\mso.dll"),
+            None,
+            "localhost",
+            true,
+        );
+        crash.timestamp = "2026-03-01T10:00:00Z".to_string();
+        crash
+    }
+
+    #[test]
+    fn ranks_component_matches_above_nearer_noise() {
+        let events = vec![
+            event("near-info", "2026-03-01T09:59:55Z", "Service Control Manager", "information", "Service started."),
+            event("module", "2026-03-01T09:55:00Z", "Application Error", "error", "Faulting module name: mso.dll"),
+            event("after", "2026-03-01T10:00:30Z", "Outlook", "error", "Outlook restarted"),
+        ];
+
+        let ranked = score_related_events(&crash(), events, 15);
+        assert_eq!(ranked[0].event.id, "module");
+        assert!(ranked[0].relevance_reasons.iter().any(|reason| reason == "mentions mso.dll"));
+        assert!(ranked[0].relevance_reasons.iter().any(|reason| reason == "5m before the crash"));
+        assert_eq!(ranked.last().unwrap().event.id, "near-info");
+    }
+
+    #[test]
+    fn matches_crash_code_when_component_is_absent() {
+        let events = vec![event(
+            "code",
+            "2026-03-01T10:00:00Z",
+            "Windows Error Reporting",
+            "information",
+            "Exception code 0xC0000005 reported",
+        )];
+        let ranked = score_related_events(&crash(), events, 15);
+        assert!(ranked[0]
+            .relevance_reasons
+            .iter()
+            .any(|reason| reason == "mentions crash code 0xc0000005"));
+    }
+}
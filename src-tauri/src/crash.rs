@@ -1,5 +1,7 @@
 use crate::logs::NormalizedEvent;
+use crate::timestamps::{from_epoch_micros, from_epoch_seconds};
 use chrono::{DateTime, Utc};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
@@ -24,6 +26,8 @@ pub struct CrashRecord {
     pub raw_path: Option<String>,
     pub source_host: String,
     pub imported: bool,
+    #[serde(default)]
+    pub details: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +58,8 @@ pub struct MinidumpAnalysisResult {
     pub escalate_if: Vec<String>,
     pub warnings: Vec<String>,
     pub unavailable_reason: Option<String>,
+    pub crashing_thread_stack: Vec<String>,
+    pub os_build: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -64,6 +70,18 @@ struct DumpHeaderInfo {
     timestamp: Option<String>,
 }
 
+/// Crash facts recovered from the dump body rather than from surrounding logs.
+#[derive(Debug, Clone, Default)]
+struct DumpDetails {
+    exception_code: Option<String>,
+    faulting_module: Option<String>,
+    bugcheck_parameters: Vec<String>,
+    crashing_thread_stack: Vec<String>,
+    os_build: Option<String>,
+}
+
+const MAX_STACK_FRAMES: usize = 24;
+
 impl CrashRecord {
     pub fn new(
         os: &str,
@@ -88,6 +106,7 @@ impl CrashRecord {
             raw_path: raw_path.map(ToString::to_string),
             source_host: source_host.to_string(),
             imported,
+            details: None,
         }
     }
 }
@@ -145,14 +164,28 @@ pub fn analyze_windows_minidump(
         }
     };
 
+    let details = match read_dump_details(path.as_path()) {
+        Ok(details) => details,
+        Err(error) => {
+            warnings.push(error);
+            DumpDetails::default()
+        }
+    };
+
     let bugcheck_code = crash
         .code
         .clone()
+        .or_else(|| details.exception_code.clone())
         .or_else(|| infer_bugcheck_code(related_events));
-    let bugcheck_parameters = infer_bugcheck_parameters(related_events);
+    let bugcheck_parameters = if details.bugcheck_parameters.is_empty() {
+        infer_bugcheck_parameters(related_events)
+    } else {
+        details.bugcheck_parameters.clone()
+    };
     let suspected_module = crash
         .suspected_component
         .clone()
+        .or_else(|| details.faulting_module.clone())
         .or_else(|| infer_suspected_module(related_events));
     let likely_cause_category =
         infer_likely_cause_category(bugcheck_code.as_deref(), suspected_module.as_deref(), related_events);
@@ -178,7 +211,7 @@ pub fn analyze_windows_minidump(
         likely_cause_category.as_str(),
         related_events.len(),
     );
-    let crash_details = build_crash_details(
+    let mut crash_details = build_crash_details(
         crash,
         raw_path,
         dump_kind.as_str(),
@@ -188,6 +221,15 @@ pub fn analyze_windows_minidump(
         bugcheck_code.as_deref(),
         bugcheck_parameters.as_slice(),
     );
+    if let Some(build) = &details.os_build {
+        crash_details.push(format!("OS build: {build}"));
+    }
+    if !details.crashing_thread_stack.is_empty() {
+        crash_details.push(format!(
+            "Crashing thread stack (scanned): {}",
+            details.crashing_thread_stack.join(" <- ")
+        ));
+    }
     let verify_first = build_verify_first(
         likely_cause_category.as_str(),
         suspected_module.as_deref(),
@@ -226,6 +268,8 @@ pub fn analyze_windows_minidump(
         escalate_if,
         warnings,
         unavailable_reason: None,
+        crashing_thread_stack: details.crashing_thread_stack,
+        os_build: details.os_build,
     })
 }
 
@@ -270,6 +314,8 @@ fn unavailable_minidump_analysis(
         ],
         warnings: Vec::new(),
         unavailable_reason: Some(reason),
+        crashing_thread_stack: Vec::new(),
+        os_build: None,
     }
 }
 
@@ -291,7 +337,7 @@ fn read_dump_header(path: &Path) -> Result<DumpHeaderInfo, String> {
     let timestamp = if timestamp_raw == 0 {
         None
     } else {
-        DateTime::<Utc>::from_timestamp(timestamp_raw as i64, 0).map(|value| value.to_rfc3339())
+        from_epoch_seconds(i64::from(timestamp_raw)).map(|value| value.to_rfc3339())
     };
 
     Ok(DumpHeaderInfo {
@@ -302,10 +348,164 @@ fn read_dump_header(path: &Path) -> Result<DumpHeaderInfo, String> {
     })
 }
 
+fn read_dump_details(path: &Path) -> Result<DumpDetails, String> {
+    let mut file =
+        fs::File::open(path).map_err(|error| format!("Failed to open dump file: {error}"))?;
+    let mut signature = [0u8; 8];
+    use std::io::Read;
+    file.read_exact(&mut signature)
+        .map_err(|error| format!("Failed to read dump signature: {error}"))?;
+    drop(file);
+
+    match &signature {
+        [b'M', b'D', b'M', b'P', ..] => read_user_minidump_details(path),
+        b"PAGEDU64" | b"PAGEDUMP" => read_kernel_dump_details(path),
+        _ => Err("Dump signature is not recognized; skipping dump body parsing.".to_string()),
+    }
+}
+
+/// User-mode minidumps (MDMP) carry an exception stream, module list and thread stacks.
+fn read_user_minidump_details(path: &Path) -> Result<DumpDetails, String> {
+    use minidump::{
+        Minidump, MinidumpException, MinidumpModuleList, MinidumpSystemInfo, MinidumpThreadList,
+        Module,
+    };
+
+    let dump = Minidump::read_path(path)
+        .map_err(|error| format!("Failed to parse minidump: {error}"))?;
+    let mut details = DumpDetails::default();
+
+    let system_info = dump.get_stream::<MinidumpSystemInfo>().ok();
+    if let Some(info) = &system_info {
+        details.os_build = Some(format!(
+            "{}.{}.{}",
+            info.raw.major_version, info.raw.minor_version, info.raw.build_number
+        ));
+    }
+
+    let modules = dump.get_stream::<MinidumpModuleList>().ok();
+    let describe_address = |address: u64| -> Option<String> {
+        let module = modules.as_ref()?.module_at_address(address)?;
+        let code_file = module.code_file();
+        // Module paths are recorded in the dump's native (Windows) form.
+        let name = code_file
+            .rsplit(['\\', '/'])
+            .next()
+            .unwrap_or(code_file.as_ref())
+            .to_string();
+        Some(format!("{name}+0x{:x}", address - module.base_address()))
+    };
+
+    let Ok(exception) = dump.get_stream::<MinidumpException>() else {
+        return Ok(details);
+    };
+    let record = &exception.raw.exception_record;
+    details.exception_code = Some(format!("0x{:08X}", record.exception_code));
+    details.bugcheck_parameters = record
+        .exception_information
+        .iter()
+        .take(record.number_parameters.min(4) as usize)
+        .map(|value| format!("0x{value:X}"))
+        .collect();
+    details.faulting_module = describe_address(record.exception_address)
+        .and_then(|frame| frame.split('+').next().map(ToString::to_string));
+
+    // Without symbols we cannot unwind precisely, so scan the crashing thread's stack memory
+    // for return addresses that land inside loaded modules.
+    let threads = dump.get_stream::<MinidumpThreadList>().ok();
+    let stack = threads
+        .as_ref()
+        .and_then(|list| list.get_thread(exception.thread_id))
+        .and_then(|thread| thread.stack.as_ref());
+    if let Some(stack) = stack {
+        let word_size = match system_info.as_ref().map(|info| info.cpu) {
+            Some(minidump::system_info::Cpu::X86) | Some(minidump::system_info::Cpu::Arm) => 4,
+            _ => 8,
+        };
+        if let Some(frame) = describe_address(record.exception_address) {
+            details.crashing_thread_stack.push(frame);
+        }
+        for chunk in stack.bytes.chunks_exact(word_size) {
+            let address = if word_size == 4 {
+                u64::from(u32::from_le_bytes(chunk.try_into().unwrap_or([0; 4])))
+            } else {
+                u64::from_le_bytes(chunk.try_into().unwrap_or([0; 8]))
+            };
+            if let Some(frame) = describe_address(address) {
+                if details.crashing_thread_stack.last() != Some(&frame) {
+                    details.crashing_thread_stack.push(frame);
+                }
+            }
+            if details.crashing_thread_stack.len() >= MAX_STACK_FRAMES {
+                break;
+            }
+        }
+    }
+
+    Ok(details)
+}
+
+/// Kernel dumps (C:\Windows\Minidump\*.dmp, MEMORY.DMP) start with a DUMP_HEADER that records the
+/// bugcheck code and parameters; the OS build lives in the MinorVersion field.
+fn read_kernel_dump_details(path: &Path) -> Result<DumpDetails, String> {
+    let mut file =
+        fs::File::open(path).map_err(|error| format!("Failed to open kernel dump: {error}"))?;
+    let mut buffer = [0u8; 0x60];
+    use std::io::Read;
+    file.read_exact(&mut buffer)
+        .map_err(|error| format!("Failed to read kernel dump header: {error}"))?;
+    Ok(parse_kernel_dump_header(&buffer))
+}
+
+fn parse_kernel_dump_header(buffer: &[u8; 0x60]) -> DumpDetails {
+    let read_u32 = |offset: usize| {
+        u32::from_le_bytes(buffer[offset..offset + 4].try_into().unwrap_or([0; 4]))
+    };
+    let read_u64 = |offset: usize| {
+        u64::from_le_bytes(buffer[offset..offset + 8].try_into().unwrap_or([0; 8]))
+    };
+    let is_64 = &buffer[4..8] == b"DU64";
+    // MinorVersion holds the build number; MajorVersion only marks free (0xF) or checked (0xC)
+    // builds, so the Windows version itself is not in the header.
+    let build = read_u32(12);
+
+    let (code, parameters) = if is_64 {
+        (
+            read_u32(0x38),
+            (0..4)
+                .map(|index| format!("0x{:X}", read_u64(0x40 + index * 8)))
+                .collect::<Vec<_>>(),
+        )
+    } else {
+        (
+            read_u32(0x28),
+            (0..4)
+                .map(|index| format!("0x{:X}", read_u32(0x2C + index * 4)))
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    DumpDetails {
+        exception_code: (code != 0).then(|| format!("0x{code:08X}")),
+        faulting_module: None,
+        bugcheck_parameters: if code != 0 { parameters } else { Vec::new() },
+        crashing_thread_stack: Vec::new(),
+        os_build: (build != 0).then(|| build.to_string()),
+    }
+}
+
+/// Identifies bugcheck reports by provider and event ID so detection does not depend on the
+/// language the message was rendered in.
+fn is_bugcheck_report(event: &NormalizedEvent) -> bool {
+    let provider = event.provider.to_ascii_lowercase();
+    event.event_id == Some(1001)
+        && (provider.contains("bugcheck") || provider.contains("wer-systemerrorreporting"))
+}
+
 fn infer_bugcheck_code(events: &[NormalizedEvent]) -> Option<String> {
     for event in events {
         let lower = event.message.to_ascii_lowercase();
-        if lower.contains("bugcheck") || lower.contains("stop code") {
+        if is_bugcheck_report(event) || lower.contains("bugcheck") || lower.contains("stop code") {
             if let Some(code) = first_hex_token(event.message.as_str()) {
                 return Some(code);
             }
@@ -317,7 +517,7 @@ fn infer_bugcheck_code(events: &[NormalizedEvent]) -> Option<String> {
 fn infer_bugcheck_parameters(events: &[NormalizedEvent]) -> Vec<String> {
     for event in events {
         let lower = event.message.to_ascii_lowercase();
-        if !(lower.contains("bugcheck") || lower.contains("parameter")) {
+        if !(is_bugcheck_report(event) || lower.contains("bugcheck") || lower.contains("parameter")) {
             continue;
         }
         let tokens = collect_hex_tokens(event.message.as_str(), 4);
@@ -600,28 +800,305 @@ fn title_case_label(value: &str) -> String {
         .join(" ")
 }
 
-pub fn import_host_crashes(limit: usize) -> Result<Vec<CrashRecord>, String> {
+/// Imports crash reports written on this host, newest first. `since` skips older reports and
+/// lets the scan prune whole report folders.
+pub fn import_host_crashes(
+    limit: usize,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<CrashRecord>, String> {
     let capped = limit.clamp(1, 2000);
+    let since = since.map(SystemTime::from);
 
     #[cfg(target_os = "windows")]
     {
-        return Ok(import_windows_crashes(capped));
+        return Ok(import_windows_crashes(capped, since));
     }
 
     #[cfg(target_os = "macos")]
     {
-        return Ok(import_macos_crashes(capped));
+        return Ok(import_macos_crashes(capped, since));
     }
 
     #[cfg(target_os = "linux")]
     {
-        return Ok(import_linux_crashes(capped));
+        return Ok(import_linux_crashes(capped, since));
     }
 
     #[allow(unreachable_code)]
     Ok(Vec::new())
 }
 
+/// A directory the crash watcher observes for new reports.
+#[derive(Debug, Clone)]
+pub struct CrashWatchRoot {
+    pub path: PathBuf,
+    pub recursive: bool,
+}
+
+/// Directories where the host OS drops crash reports, in the same places `import_host_crashes` scans.
+pub fn crash_watch_roots() -> Vec<CrashWatchRoot> {
+    #[cfg(target_os = "windows")]
+    {
+        let mut roots: Vec<CrashWatchRoot> = windows_wer_roots()
+            .into_iter()
+            .map(|path| CrashWatchRoot { path, recursive: true })
+            .collect();
+        roots.push(CrashWatchRoot {
+            path: PathBuf::from(r"C:\Windows\Minidump"),
+            recursive: false,
+        });
+        // Only MEMORY.DMP matters here; watching C:\Windows recursively would be far too noisy.
+        roots.push(CrashWatchRoot {
+            path: PathBuf::from(r"C:\Windows"),
+            recursive: false,
+        });
+        return roots;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        return macos_report_roots()
+            .into_iter()
+            .map(|path| CrashWatchRoot { path, recursive: true })
+            .collect();
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        return vec![
+            CrashWatchRoot {
+                path: PathBuf::from(LINUX_CRASH_DIR),
+                recursive: true,
+            },
+            CrashWatchRoot {
+                path: PathBuf::from(SYSTEMD_COREDUMP_DIR),
+                recursive: false,
+            },
+        ];
+    }
+
+    #[allow(unreachable_code)]
+    Vec::new()
+}
+
+/// True when a changed file under a watch root looks like a crash report this module can parse.
+pub fn is_crash_report_path(path: &Path) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        return is_windows_wer_file(path) || is_windows_dump_file(path);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        return is_macos_report_file(path);
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        return is_linux_crash_file(path);
+    }
+
+    #[allow(unreachable_code)]
+    false
+}
+
+/// Parses crash reports reported by the watcher. Files that vanished before parsing are skipped.
+pub fn import_crash_files(paths: &[PathBuf]) -> Vec<CrashRecord> {
+    let existing: Vec<&PathBuf> = paths
+        .iter()
+        .filter(|path| path.is_file() && is_crash_report_path(path))
+        .collect();
+
+    #[cfg(target_os = "windows")]
+    {
+        let crashes = existing
+            .into_iter()
+            .map(|path| {
+                if is_windows_wer_file(path) {
+                    parse_windows_wer(path)
+                } else {
+                    parse_windows_dump(path)
+                }
+            })
+            .collect();
+        return dedupe_and_limit(crashes, paths.len());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let crashes = existing
+            .into_iter()
+            .map(|path| parse_macos_report(path))
+            .collect();
+        return dedupe_and_limit(crashes, paths.len());
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let (coredumps, reports): (Vec<&PathBuf>, Vec<&PathBuf>) = existing
+            .into_iter()
+            .partition(|path| path.starts_with(SYSTEMD_COREDUMP_DIR));
+        let mut crashes: Vec<CrashRecord> = reports
+            .into_iter()
+            .map(|path| parse_linux_report(path))
+            .collect();
+        // Prefer coredumpctl's richer view so watcher imports match manual imports.
+        if !coredumps.is_empty() {
+            match import_coredumpctl_crashes(coredumps.len()) {
+                Some(records) => crashes.extend(records),
+                None => crashes.extend(coredumps.into_iter().map(|path| parse_linux_report(path))),
+            }
+        }
+        return dedupe_and_limit(crashes, paths.len());
+    }
+
+    #[allow(unreachable_code)]
+    Vec::new()
+}
+
+/// Reads `label: value` from a rendered Windows message, stopping at the next comma or line break.
+fn rendered_event_value(message: &str, label: &str) -> Option<String> {
+    let index = message.find(label)?;
+    let rest = &message[index + label.len()..];
+    let end = rest.find([',', '\r', '\n']).unwrap_or(rest.len());
+    let value = rest[..end].trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Reads `Key=value` from the `Data: ...` fallback used when a message cannot be rendered.
+fn event_data_value(message: &str, key: &str) -> Option<String> {
+    let data = message.strip_prefix("Data: ")?;
+    data.split(", ").find_map(|pair| {
+        let (name, value) = pair.split_once('=')?;
+        let value = value.trim();
+        (name.trim().eq_ignore_ascii_case(key) && !value.is_empty()).then(|| value.to_string())
+    })
+}
+
+fn event_value(message: &str, label: &str, key: &str) -> Option<String> {
+    rendered_event_value(message, label).or_else(|| event_data_value(message, key))
+}
+
+fn normalize_hex_code(value: &str) -> String {
+    let trimmed = value.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    format!("0x{}", digits.to_ascii_uppercase())
+}
+
+fn is_bugcheck_event(event: &NormalizedEvent) -> bool {
+    let provider = event.provider.to_ascii_lowercase();
+    event.event_id == Some(1001)
+        && (provider.contains("wer-systemerrorreporting") || provider == "bugcheck")
+}
+
+fn is_application_error_event(event: &NormalizedEvent) -> bool {
+    event.event_id == Some(1000) && event.provider.eq_ignore_ascii_case("Application Error")
+}
+
+fn event_crash(event: &NormalizedEvent, crash_type: &str, code: Option<&str>, summary: &str) -> CrashRecord {
+    let mut crash = CrashRecord::new(
+        "windows",
+        "EventLog",
+        crash_type,
+        code,
+        summary,
+        None,
+        None,
+        event.source_host.as_str(),
+        event.imported,
+    );
+    crash.id = stable_id(format!("windows|EventLog|{}", event.id).as_str());
+    crash.timestamp = event.timestamp.clone();
+    crash
+}
+
+fn application_error_crash(event: &NormalizedEvent) -> Option<CrashRecord> {
+    let message = event.message.as_str();
+    let app = event_value(message, "Faulting application name:", "AppName")?;
+    let module = event_value(message, "Faulting module name:", "ModuleName");
+    let code = event_value(message, "Exception code:", "ExceptionCode").map(|value| normalize_hex_code(value.as_str()));
+    let summary = match (code.as_deref(), module.as_deref()) {
+        (Some(code), Some(module)) => format!("Application Error: {app} ({code} in {module})"),
+        (Some(code), None) => format!("Application Error: {app} ({code})"),
+        _ => format!("Application Error: {app}"),
+    };
+
+    let mut crash = event_crash(event, "Application Error", code.as_deref(), summary.as_str());
+    crash.suspected_component = module.clone().or_else(|| Some(app.clone()));
+    let details: Vec<String> = [
+        ("Application version", event_value(message, "version:", "AppVersion")),
+        ("Module version", event_data_value(message, "ModuleVersion")),
+        ("Fault offset", event_value(message, "Fault offset:", "FaultingOffset")),
+        ("Application path", event_value(message, "Faulting application path:", "AppPath")),
+        ("Module path", event_value(message, "Faulting module path:", "ModulePath")),
+        ("Report id", event_value(message, "Report Id:", "IntegratorReportId")),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| value.map(|value| format!("{label}: {value}")))
+    .collect();
+    if !details.is_empty() {
+        crash.details = Some(details.join("\n"));
+    }
+    Some(crash)
+}
+
+fn bugcheck_crash(event: &NormalizedEvent) -> Option<CrashRecord> {
+    let message = event.message.as_str();
+    let raw = message
+        .split("The bugcheck was:")
+        .nth(1)
+        .map(|rest| rest.trim().to_string())
+        .or_else(|| event_data_value(message, "param1"))?;
+    let (code, parameters) = match raw.split_once('(') {
+        Some((code, rest)) => (code.trim(), rest.split(')').next().unwrap_or_default().trim()),
+        None => (raw.split_whitespace().next().unwrap_or_default(), ""),
+    };
+    if code.is_empty() {
+        return None;
+    }
+    let code = normalize_hex_code(code);
+    let explanation = crate::bugcheck::explain_crash_code(code.as_str());
+    let summary = if explanation.known {
+        format!("Blue Screen: {code} {}", explanation.name)
+    } else {
+        format!("Blue Screen: {code}")
+    };
+
+    let mut crash = event_crash(event, "Blue Screen", Some(code.as_str()), summary.as_str());
+    crash.raw_path = message
+        .split("A dump was saved in:")
+        .nth(1)
+        .and_then(|rest| rest.split_once(". ").map(|(path, _)| path).or(Some(rest)))
+        .map(|path| path.trim().trim_end_matches('.').to_string())
+        .or_else(|| event_data_value(message, "param2"))
+        .filter(|path| !path.is_empty());
+    if !parameters.is_empty() {
+        crash.details = Some(format!("Bugcheck parameters: {parameters}"));
+    }
+    Some(crash)
+}
+
+/// Turns Application Error 1000 and BugCheck 1001 events into crash records so they show up
+/// next to WER and dump imports. Records are keyed on the event id, so re-syncing is idempotent.
+pub fn crashes_from_events(events: &[NormalizedEvent]) -> Vec<CrashRecord> {
+    events
+        .iter()
+        .filter(|event| event.os == "windows")
+        .filter_map(|event| {
+            if is_application_error_event(event) {
+                application_error_crash(event)
+            } else if is_bugcheck_event(event) {
+                bugcheck_crash(event)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 fn build_imported_crash(
     os: &str,
     source: &str,
@@ -671,41 +1148,92 @@ fn system_time_to_rfc3339(value: SystemTime) -> String {
     DateTime::<Utc>::from(value).to_rfc3339()
 }
 
-fn scan_files<F>(roots: &[PathBuf], matcher: F, max_scan: usize) -> Vec<PathBuf>
+/// Walks `roots` in parallel and returns the newest `max_scan` files accepted by `matcher`. With
+/// `since`, files older than it are skipped, and so are whole report folders below a root: WER and
+/// DiagnosticReports folders are written once, so an old folder cannot hold a newer report.
+fn scan_files<F>(
+    roots: &[PathBuf],
+    matcher: F,
+    max_scan: usize,
+    since: Option<SystemTime>,
+) -> Vec<PathBuf>
 where
-    F: Fn(&Path) -> bool,
+    F: Fn(&Path) -> bool + Sync,
 {
-    let mut stack = roots.to_vec();
-    let mut matches: Vec<(PathBuf, SystemTime)> = Vec::new();
-
-    while let Some(path) = stack.pop() {
-        let Ok(meta) = fs::symlink_metadata(&path) else {
-            continue;
-        };
-
-        if meta.is_dir() {
-            let Ok(entries) = fs::read_dir(&path) else {
-                continue;
+    let mut matches: Vec<(PathBuf, SystemTime)> = roots
+        .par_iter()
+        .flat_map_iter(|root| {
+            let Ok(meta) = fs::symlink_metadata(root) else {
+                return Vec::new();
             };
-            for entry in entries.flatten() {
-                stack.push(entry.path());
+            if meta.is_dir() {
+                return scan_directory(root, &matcher, since);
             }
-            continue;
-        }
-
-        if !meta.is_file() || !matcher(&path) {
-            continue;
-        }
-
-        let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
-        matches.push((path, modified));
-    }
+            let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            if meta.is_file() && !is_older_than(modified, since) && matcher(root) {
+                vec![(root.clone(), modified)]
+            } else {
+                Vec::new()
+            }
+        })
+        .collect();
 
     matches.sort_by(|left, right| right.1.cmp(&left.1));
     matches.truncate(max_scan);
     matches.into_iter().map(|entry| entry.0).collect()
 }
 
+fn is_older_than(modified: SystemTime, since: Option<SystemTime>) -> bool {
+    since.is_some_and(|cutoff| modified < cutoff)
+}
+
+/// Scans one directory level in parallel. Entry types come from the directory listing, so only
+/// matching files and (when pruning) subdirectories are stat'ed.
+fn scan_directory<F>(
+    dir: &Path,
+    matcher: &F,
+    since: Option<SystemTime>,
+) -> Vec<(PathBuf, SystemTime)>
+where
+    F: Fn(&Path) -> bool + Sync,
+{
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let entries: Vec<fs::DirEntry> = entries.flatten().collect();
+
+    entries
+        .into_par_iter()
+        .flat_map_iter(|entry| {
+            let Ok(file_type) = entry.file_type() else {
+                return Vec::new();
+            };
+            let path = entry.path();
+            let modified = || {
+                entry
+                    .metadata()
+                    .and_then(|meta| meta.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH)
+            };
+
+            if file_type.is_dir() {
+                if since.is_some() && is_older_than(modified(), since) {
+                    return Vec::new();
+                }
+                return scan_directory(path.as_path(), matcher, since);
+            }
+            if !file_type.is_file() || !matcher(path.as_path()) {
+                return Vec::new();
+            }
+            let modified = modified();
+            if is_older_than(modified, since) {
+                return Vec::new();
+            }
+            vec![(path, modified)]
+        })
+        .collect()
+}
+
 fn read_lines_limited(path: &Path, max_lines: usize, max_bytes: usize) -> Vec<String> {
     let Ok(file) = fs::File::open(path) else {
         return Vec::new();
@@ -747,9 +1275,7 @@ fn dedupe_and_limit(mut crashes: Vec<CrashRecord>, limit: usize) -> Vec<CrashRec
 }
 
 #[cfg(target_os = "windows")]
-fn import_windows_crashes(limit: usize) -> Vec<CrashRecord> {
-    let mut crashes = Vec::new();
-
+fn windows_wer_roots() -> Vec<PathBuf> {
     let mut wer_roots = vec![
         PathBuf::from(r"C:\ProgramData\Microsoft\Windows\WER\ReportArchive"),
         PathBuf::from(r"C:\ProgramData\Microsoft\Windows\WER\ReportQueue"),
@@ -759,28 +1285,40 @@ fn import_windows_crashes(limit: usize) -> Vec<CrashRecord> {
         wer_roots.push(base.join("ReportArchive"));
         wer_roots.push(base.join("ReportQueue"));
     }
+    wer_roots
+}
 
-    let wer_files = scan_files(
-        &wer_roots,
-        |path| path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.eq_ignore_ascii_case("wer")).unwrap_or(false),
-        limit.saturating_mul(4),
-    );
+#[cfg(target_os = "windows")]
+fn is_windows_wer_file(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.eq_ignore_ascii_case("wer")).unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn is_windows_dump_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|value| value.to_str()).unwrap_or_default();
+    name.eq_ignore_ascii_case("MEMORY.DMP")
+        || path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("dmp"))
+            .unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn import_windows_crashes(limit: usize, since: Option<SystemTime>) -> Vec<CrashRecord> {
+    let mut crashes = Vec::new();
+
+    let wer_files =
+        scan_files(&windows_wer_roots(), is_windows_wer_file, limit.saturating_mul(4), since);
     for file in wer_files {
         crashes.push(parse_windows_wer(file.as_path()));
     }
 
     let dump_files = scan_files(
         &[PathBuf::from(r"C:\Windows\Minidump"), PathBuf::from(r"C:\Windows")],
-        |path| {
-            let name = path.file_name().and_then(|value| value.to_str()).unwrap_or_default();
-            name.eq_ignore_ascii_case("MEMORY.DMP")
-                || path
-                    .extension()
-                    .and_then(|ext| ext.to_str())
-                    .map(|ext| ext.eq_ignore_ascii_case("dmp"))
-                    .unwrap_or(false)
-        },
+        is_windows_dump_file,
         limit.saturating_mul(4),
+        since,
     );
     for file in dump_files {
         crashes.push(parse_windows_dump(file.as_path()));
@@ -798,11 +1336,18 @@ fn parse_windows_wer(path: &Path) -> CrashRecord {
         }
     }
 
-    let crash_type = pick_map_value(
-        &fields,
-        &["FriendlyEventName", "ProblemType", "Sig[0].Value", "EventType"],
-    )
-    .unwrap_or("Crash Report");
+    let is_hang = pick_map_value(&fields, &["EventType", "ProblemType"])
+        .map(crate::hangs::is_hang_problem_type)
+        .unwrap_or(false);
+    let crash_type = if is_hang {
+        "Application Hang"
+    } else {
+        pick_map_value(
+            &fields,
+            &["FriendlyEventName", "ProblemType", "Sig[0].Value", "EventType"],
+        )
+        .unwrap_or("Crash Report")
+    };
     let code = pick_map_value(
         &fields,
         &["BugcheckCode", "ExceptionCode", "Sig[8].Value", "Sig[9].Value", "Sig[1].Value"],
@@ -831,36 +1376,45 @@ fn parse_windows_dump(path: &Path) -> CrashRecord {
     let file_name = trim_file_name(path);
     let is_kernel = file_name.eq_ignore_ascii_case("MEMORY.DMP");
     let crash_type = if is_kernel { "Kernel Memory Dump" } else { "Minidump" };
-    let summary = format!("{crash_type}: {file_name}");
+    let details = read_dump_details(path).unwrap_or_default();
+    let summary = match (&details.exception_code, &details.faulting_module) {
+        (Some(code), Some(module)) => format!("{crash_type}: {file_name} ({code} in {module})"),
+        (Some(code), None) => format!("{crash_type}: {file_name} ({code})"),
+        _ => format!("{crash_type}: {file_name}"),
+    };
     build_imported_crash(
         "windows",
         if is_kernel { "KernelDump" } else { "Minidump" },
         crash_type,
-        None,
+        details.exception_code.as_deref(),
         summary.as_str(),
-        None,
+        details.faulting_module.as_deref(),
         Some(path),
         file_timestamp(path),
     )
 }
 
 #[cfg(target_os = "macos")]
-fn import_macos_crashes(limit: usize) -> Vec<CrashRecord> {
+fn macos_report_roots() -> Vec<PathBuf> {
     let mut roots = vec![PathBuf::from("/Library/Logs/DiagnosticReports")];
     if let Some(home) = dirs::home_dir() {
         roots.push(home.join("Library").join("Logs").join("DiagnosticReports"));
     }
+    roots
+}
 
-    let files = scan_files(
-        &roots,
-        |path| {
-            path.extension()
-                .and_then(|ext| ext.to_str())
-                .map(|ext| matches!(ext.to_ascii_lowercase().as_str(), "crash" | "panic" | "ips"))
-                .unwrap_or(false)
-        },
-        limit.saturating_mul(4),
-    );
+#[cfg(target_os = "macos")]
+fn is_macos_report_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| matches!(ext.to_ascii_lowercase().as_str(), "crash" | "panic" | "ips"))
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "macos")]
+fn import_macos_crashes(limit: usize, since: Option<SystemTime>) -> Vec<CrashRecord> {
+    let files =
+        scan_files(&macos_report_roots(), is_macos_report_file, limit.saturating_mul(4), since);
 
     let crashes = files
         .into_iter()
@@ -904,27 +1458,254 @@ fn parse_macos_report(path: &Path) -> CrashRecord {
 }
 
 #[cfg(target_os = "linux")]
-fn import_linux_crashes(limit: usize) -> Vec<CrashRecord> {
-    let roots = vec![PathBuf::from("/var/crash"), PathBuf::from("/var/lib/systemd/coredump")];
-    let files = scan_files(
-        &roots,
-        |path| {
-            let ext = path.extension().and_then(|value| value.to_str()).unwrap_or_default();
-            let name = path.file_name().and_then(|value| value.to_str()).unwrap_or_default();
-            ext.eq_ignore_ascii_case("crash")
-                || ext.eq_ignore_ascii_case("dmp")
-                || name.starts_with("core")
-        },
-        limit.saturating_mul(4),
-    );
+const LINUX_CRASH_DIR: &str = "/var/crash";
 
-    let crashes = files
+#[cfg(target_os = "linux")]
+const SYSTEMD_COREDUMP_DIR: &str = "/var/lib/systemd/coredump";
+
+#[cfg(target_os = "linux")]
+fn is_linux_crash_file(path: &Path) -> bool {
+    let ext = path.extension().and_then(|value| value.to_str()).unwrap_or_default();
+    let name = path.file_name().and_then(|value| value.to_str()).unwrap_or_default();
+    ext.eq_ignore_ascii_case("crash") || ext.eq_ignore_ascii_case("dmp") || name.starts_with("core")
+}
+
+#[cfg(target_os = "linux")]
+fn import_linux_crashes(limit: usize, since: Option<SystemTime>) -> Vec<CrashRecord> {
+    let coredumps = import_coredumpctl_crashes(limit);
+    let mut roots = vec![PathBuf::from(LINUX_CRASH_DIR)];
+    // coredumpctl already describes everything under the systemd coredump store, so only glob it
+    // when coredumpctl is unavailable.
+    if coredumps.is_none() {
+        roots.push(PathBuf::from(SYSTEMD_COREDUMP_DIR));
+    }
+    let files = scan_files(&roots, is_linux_crash_file, limit.saturating_mul(4), since);
+
+    let mut crashes = files
         .into_iter()
         .map(|path| parse_linux_report(path.as_path()))
         .collect::<Vec<_>>();
+    crashes.extend(coredumps.unwrap_or_default().into_iter().filter(|crash| {
+        DateTime::parse_from_rfc3339(crash.timestamp.as_str())
+            .map(|timestamp| !is_older_than(SystemTime::from(timestamp), since))
+            .unwrap_or(true)
+    }));
     dedupe_and_limit(crashes, limit)
 }
 
+/// Maximum number of `coredumpctl info` calls per import; each one reads the journal.
+#[cfg(target_os = "linux")]
+const COREDUMPCTL_INFO_LIMIT: usize = 25;
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CoredumpListEntry {
+    time_micros: i64,
+    pid: u64,
+    signal: Option<i64>,
+    exe: String,
+    corefile: Option<String>,
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CoredumpInfo {
+    signal: Option<String>,
+    command_line: Option<String>,
+    executable: Option<String>,
+    storage: Option<String>,
+    backtrace: Vec<String>,
+}
+
+/// Returns `None` when coredumpctl is missing or fails, so callers can fall back to globbing.
+#[cfg(target_os = "linux")]
+fn import_coredumpctl_crashes(limit: usize) -> Option<Vec<CrashRecord>> {
+    let output = std::process::Command::new("coredumpctl")
+        .args(["list", "--json=short", "--no-pager", "--reverse"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        // coredumpctl exits non-zero when there are simply no coredumps.
+        let stderr = String::from_utf8_lossy(&output.stderr).to_ascii_lowercase();
+        return if stderr.contains("no coredumps found") {
+            Some(Vec::new())
+        } else {
+            None
+        };
+    }
+
+    let mut entries = parse_coredumpctl_list(String::from_utf8_lossy(&output.stdout).as_ref())?;
+    entries.sort_by(|left, right| right.time_micros.cmp(&left.time_micros));
+    entries.truncate(limit);
+
+    let crashes = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let info = if index < COREDUMPCTL_INFO_LIMIT {
+                read_coredumpctl_info(entry.pid)
+            } else {
+                None
+            };
+            build_coredump_crash(entry, info.as_ref())
+        })
+        .collect();
+    Some(crashes)
+}
+
+#[cfg(target_os = "linux")]
+fn read_coredumpctl_info(pid: u64) -> Option<CoredumpInfo> {
+    let output = std::process::Command::new("coredumpctl")
+        .args(["info", "--no-pager", pid.to_string().as_str()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_coredumpctl_info(
+        String::from_utf8_lossy(&output.stdout).as_ref(),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn parse_coredumpctl_list(text: &str) -> Option<Vec<CoredumpListEntry>> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let rows = value.as_array()?;
+    Some(
+        rows.iter()
+            .filter_map(|row| {
+                Some(CoredumpListEntry {
+                    time_micros: row.get("time")?.as_i64()?,
+                    pid: row.get("pid")?.as_u64()?,
+                    signal: row.get("sig").and_then(|value| value.as_i64()),
+                    exe: row
+                        .get("exe")
+                        .and_then(|value| value.as_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                    corefile: row
+                        .get("corefile")
+                        .and_then(|value| value.as_str())
+                        .map(ToString::to_string),
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn parse_coredumpctl_info(text: &str) -> CoredumpInfo {
+    let mut info = CoredumpInfo::default();
+    let mut in_stack = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("Stack trace of thread") {
+            // Only the first (crashing) thread is interesting.
+            in_stack = info.backtrace.is_empty();
+            continue;
+        }
+        if in_stack {
+            if trimmed.starts_with('#') {
+                if info.backtrace.len() < MAX_STACK_FRAMES {
+                    info.backtrace.push(trimmed.to_string());
+                }
+                continue;
+            }
+            if trimmed.is_empty() || !info.backtrace.is_empty() {
+                in_stack = false;
+            }
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(": ") else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "Signal" => info.signal = Some(value),
+            "Command Line" => info.command_line = Some(value),
+            "Executable" => info.executable = Some(value),
+            "Storage" => info.storage = Some(value),
+            _ => {}
+        }
+    }
+    info
+}
+
+/// Maps `11 (SEGV)` or a bare signal number to a `SIGSEGV`-style name.
+#[cfg(target_os = "linux")]
+fn signal_name(info_signal: Option<&str>, number: Option<i64>) -> Option<String> {
+    if let Some(value) = info_signal {
+        if let Some(name) = value
+            .split_once('(')
+            .and_then(|(_, rest)| rest.strip_suffix(')'))
+        {
+            return Some(format!("SIG{name}"));
+        }
+    }
+    let name = match number? {
+        4 => "SIGILL",
+        5 => "SIGTRAP",
+        6 => "SIGABRT",
+        7 => "SIGBUS",
+        8 => "SIGFPE",
+        11 => "SIGSEGV",
+        31 => "SIGSYS",
+        other => return Some(format!("signal {other}")),
+    };
+    Some(name.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn build_coredump_crash(entry: &CoredumpListEntry, info: Option<&CoredumpInfo>) -> CrashRecord {
+    let executable = info
+        .and_then(|value| value.executable.clone())
+        .unwrap_or_else(|| entry.exe.clone());
+    let process = basename(executable.as_str())
+        .unwrap_or(executable.as_str())
+        .to_string();
+    let code = signal_name(info.and_then(|value| value.signal.as_deref()), entry.signal);
+    let summary = match &code {
+        Some(signal) => format!("Core dump: {process} (pid {}, {signal})", entry.pid),
+        None => format!("Core dump: {process} (pid {})", entry.pid),
+    };
+    // Storage reads "/var/lib/systemd/coredump/core...zst (present)".
+    let storage = info
+        .and_then(|value| value.storage.as_deref())
+        .filter(|_| entry.corefile.as_deref() == Some("present"))
+        .map(|value| value.split(" (").next().unwrap_or(value).to_string());
+    let timestamp = from_epoch_micros(entry.time_micros)
+        .map(|value| value.to_rfc3339())
+        .unwrap_or_else(|| Utc::now().to_rfc3339());
+
+    let mut crash = build_imported_crash(
+        "linux",
+        "systemd-coredump",
+        "Core Dump",
+        code.as_deref(),
+        summary.as_str(),
+        Some(process.as_str()),
+        storage.as_deref().map(Path::new),
+        timestamp,
+    );
+    if storage.is_none() {
+        crash.id = stable_id(format!("linux|coredumpctl|{}|{}", entry.pid, entry.time_micros).as_str());
+    }
+
+    let mut details = Vec::new();
+    if let Some(command_line) = info.and_then(|value| value.command_line.as_deref()) {
+        details.push(format!("Command line: {command_line}"));
+    }
+    details.push(format!("Executable: {executable}"));
+    if let Some(info) = info {
+        if !info.backtrace.is_empty() {
+            details.push("Backtrace:".to_string());
+            details.extend(info.backtrace.iter().cloned());
+        }
+    }
+    crash.details = Some(details.join("\n"));
+    crash
+}
+
 #[cfg(target_os = "linux")]
 fn parse_linux_report(path: &Path) -> CrashRecord {
     let ext = path.extension().and_then(|value| value.to_str()).unwrap_or_default();
@@ -1139,6 +1920,9 @@ pub fn analyze_linux_minidump(
     if let Some(component) = &suspected_component {
         crash_details.push(format!("Suspected component: {component}"));
     }
+    if let Some(details) = &crash.details {
+        crash_details.extend(details.lines().map(ToString::to_string));
+    }
 
     // Build verify and escalate suggestions
     let mut verify_first = vec![
@@ -1181,6 +1965,8 @@ pub fn analyze_linux_minidump(
         escalate_if,
         warnings,
         unavailable_reason,
+        crashing_thread_stack: Vec::new(),
+        os_build: None,
     })
 }
 
@@ -1188,6 +1974,24 @@ pub fn analyze_linux_minidump(
 mod tests {
     use super::*;
 
+    #[test]
+    fn scan_files_walks_nested_folders_and_prunes_by_time() {
+        let root = std::env::temp_dir().join(format!("hla-scan-{}", Uuid::new_v4()));
+        let nested = root.join("ReportArchive").join("AppCrash_demo");
+        fs::create_dir_all(&nested).expect("create report folders");
+        fs::write(nested.join("Report.wer"), "EventType=APPCRASH").expect("write report");
+        fs::write(root.join("notes.txt"), "ignored").expect("write other file");
+        let is_wer = |path: &Path| path.extension().and_then(|ext| ext.to_str()) == Some("wer");
+
+        let found = scan_files(std::slice::from_ref(&root), is_wer, 10, None);
+        assert_eq!(found, vec![nested.join("Report.wer")]);
+
+        let future = SystemTime::now() + std::time::Duration::from_secs(3600);
+        assert!(scan_files(std::slice::from_ref(&root), is_wer, 10, Some(future)).is_empty());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn test_first_hex_token() {
         assert_eq!(first_hex_token("BugCheck 0xC0000005"), Some("0xC0000005".to_string()));
@@ -1195,6 +1999,81 @@ mod tests {
         assert_eq!(first_hex_token("No hex here!"), None);
     }
 
+    #[test]
+    fn test_parse_kernel_dump_header() {
+        let mut buffer = [0u8; 0x60];
+        buffer[0..8].copy_from_slice(b"PAGEDU64");
+        buffer[12..16].copy_from_slice(&19045u32.to_le_bytes());
+        buffer[0x38..0x3C].copy_from_slice(&0x9Fu32.to_le_bytes());
+        buffer[0x40..0x48].copy_from_slice(&3u64.to_le_bytes());
+
+        let details = parse_kernel_dump_header(&buffer);
+        assert_eq!(details.exception_code.as_deref(), Some("0x0000009F"));
+        assert_eq!(details.bugcheck_parameters.first().map(String::as_str), Some("0x3"));
+        assert_eq!(details.os_build.as_deref(), Some("19045"));
+
+        let mut buffer = [0u8; 0x60];
+        buffer[0..8].copy_from_slice(b"PAGEDUMP");
+        buffer[12..16].copy_from_slice(&7601u32.to_le_bytes());
+        buffer[0x20..0x24].copy_from_slice(&0x14Cu32.to_le_bytes());
+        buffer[0x24..0x28].copy_from_slice(&2u32.to_le_bytes());
+        buffer[0x28..0x2C].copy_from_slice(&0xD1u32.to_le_bytes());
+        for (index, parameter) in [0x10u32, 0x2, 0x0, 0x8A3F1C20].iter().enumerate() {
+            let offset = 0x2C + index * 4;
+            buffer[offset..offset + 4].copy_from_slice(&parameter.to_le_bytes());
+        }
+
+        let details = parse_kernel_dump_header(&buffer);
+        assert_eq!(details.exception_code.as_deref(), Some("0x000000D1"));
+        assert_eq!(
+            details.bugcheck_parameters,
+            vec!["0x10", "0x2", "0x0", "0x8A3F1C20"]
+        );
+        assert_eq!(details.os_build.as_deref(), Some("7601"));
+    }
+
+    #[test]
+    fn test_crashes_from_events() {
+        let event = |id: &str, provider: &str, event_id: u32, message: &str| NormalizedEvent {
+            id: id.to_string(),
+            timestamp: "2026-03-01T10:00:00Z".to_string(),
+            os: "windows".to_string(),
+            log_name: "Application".to_string(),
+            category: "application".to_string(),
+            provider: provider.to_string(),
+            event_id: Some(event_id),
+            severity: "error".to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        };
+        let events = vec![
+            event(
+                "evt-1",
+                "Application Error",
+                1000,
+                "Faulting application name: app.exe, version: 1.2.0.0, time stamp: 0x5f000000\r\nFaulting module name: ntdll.dll, version: 10.0.19041.1, time stamp: 0x1\r\nException code: 0xc0000005\r\nFault offset: 0x000000000004f6a1",
+            ),
+            event(
+                "evt-2",
+                "Microsoft-Windows-WER-SystemErrorReporting",
+                1001,
+                "The computer has rebooted from a bugcheck.  The bugcheck was: 0x0000009f (0x0000000000000003, 0xffff). A dump was saved in: C:\\Windows\\MEMORY.DMP. Report Id: abc.",
+            ),
+            event("evt-3", "Windows Error Reporting", 1001, "Fault bucket 123"),
+        ];
+
+        let crashes = crashes_from_events(&events);
+        assert_eq!(crashes.len(), 2);
+        assert_eq!(crashes[0].code.as_deref(), Some("0xC0000005"));
+        assert_eq!(crashes[0].suspected_component.as_deref(), Some("ntdll.dll"));
+        assert_eq!(crashes[0].timestamp, "2026-03-01T10:00:00Z");
+        assert_eq!(crashes[1].crash_type, "Blue Screen");
+        assert_eq!(crashes[1].code.as_deref(), Some("0x0000009F"));
+        assert_eq!(crashes[1].raw_path.as_deref(), Some("C:\\Windows\\MEMORY.DMP"));
+        assert_eq!(crashes_from_events(&events)[0].id, crashes[0].id);
+    }
+
     #[test]
     fn test_infer_bugcheck_code() {
         let events = vec![
@@ -1210,6 +2089,7 @@ mod tests {
                 message: "BugCheck 0xC0000005, ...".to_string(),
                 source_host: "host-001".to_string(),
                 imported: true,
+                ..NormalizedEvent::default()
             },
         ];
 
@@ -1217,6 +2097,26 @@ mod tests {
         assert_eq!(code, Some("0xC0000005".to_string()));
     }
 
+    #[test]
+    fn test_infer_bugcheck_code_from_localized_message() {
+        let events = vec![NormalizedEvent {
+            id: "1".to_string(),
+            timestamp: "2024-03-27T10:00:05Z".to_string(),
+            os: "windows".to_string(),
+            log_name: "System".to_string(),
+            category: "system".to_string(),
+            provider: "Microsoft-Windows-WER-SystemErrorReporting".to_string(),
+            event_id: Some(1001),
+            severity: "error".to_string(),
+            message: "Der Computer wurde nach einem schwerwiegenden Fehler neu gestartet: 0x0000009f (0x0000000000000003, 0xffffc50f8a7e1060)".to_string(),
+            source_host: "host-001".to_string(),
+            imported: true,
+            ..NormalizedEvent::default()
+        }];
+
+        assert_eq!(infer_bugcheck_code(&events), Some("0x0000009f".to_string()));
+    }
+
     #[test]
     fn test_infer_module_name() {
         let events = vec![
@@ -1232,6 +2132,7 @@ mod tests {
                 message: "Probably caused by : nvlddmkm.sys".to_string(),
                 source_host: "host-001".to_string(),
                 imported: true,
+                ..NormalizedEvent::default()
             },
         ];
 
@@ -1253,6 +2154,7 @@ mod tests {
             raw_path: Some("/var/crash/core.123456".to_string()),
             source_host: "host-001".to_string(),
             imported: true,
+            details: None,
         };
 
         let related_events = vec![
@@ -1268,6 +2170,7 @@ mod tests {
                 message: "Process 123456 received signal SIGSEGV from application libfoo.so".to_string(),
                 source_host: "host-001".to_string(),
                 imported: true,
+                ..NormalizedEvent::default()
             }
         ];
 
@@ -1292,6 +2195,7 @@ mod tests {
             raw_path: None, // No dump file path
             source_host: "host-001".to_string(),
             imported: true,
+            details: None,
         };
 
         let related_events = vec![];
@@ -1302,6 +2206,32 @@ mod tests {
         assert_eq!(result.crash_id, "crash-002");
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_coredumpctl_output() {
+        let entries = parse_coredumpctl_list(
+            r#"[{"time":1711533600000000,"pid":4242,"uid":1000,"gid":1000,"sig":11,"corefile":"present","exe":"/usr/bin/foo","size":1024}]"#,
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].pid, 4242);
+
+        let info = parse_coredumpctl_info(
+            "           PID: 4242 (foo)\n        Signal: 11 (SEGV)\n  Command Line: foo --bar\n    Executable: /usr/bin/foo\n       Storage: /var/lib/systemd/coredump/core.foo.zst (present)\n       Message: Process 4242 (foo) of user 1000 dumped core.\n\n                Stack trace of thread 4242:\n                #0  0x00007f raise (libc.so.6 + 0x3e)\n                #1  0x00007f abort (libc.so.6 + 0x26)\n\n                Stack trace of thread 4243:\n                #0  0x00007f poll (libc.so.6 + 0x10)\n",
+        );
+        assert_eq!(info.command_line.as_deref(), Some("foo --bar"));
+        assert_eq!(info.backtrace.len(), 2);
+
+        let crash = build_coredump_crash(&entries[0], Some(&info));
+        assert_eq!(crash.code.as_deref(), Some("SIGSEGV"));
+        assert_eq!(crash.crash_type, "Core Dump");
+        assert_eq!(
+            crash.raw_path.as_deref(),
+            Some("/var/lib/systemd/coredump/core.foo.zst")
+        );
+        assert!(crash.details.unwrap().contains("Backtrace:"));
+    }
+
     #[test]
     fn test_infer_signal_from_events() {
         let events = vec![
@@ -1317,6 +2247,7 @@ mod tests {
                 message: "Process 123456 received signal SIGSEGV from application libfoo.so".to_string(),
                 source_host: "host-001".to_string(),
                 imported: true,
+                ..NormalizedEvent::default()
             },
             NormalizedEvent {
                 id: "event-002".to_string(),
@@ -1330,6 +2261,7 @@ mod tests {
                 message: "Application crashed with signal 11".to_string(),
                 source_host: "host-001".to_string(),
                 imported: true,
+                ..NormalizedEvent::default()
             }
         ];
 
@@ -0,0 +1,282 @@
+use crate::crash::CrashRecord;
+use crate::event_export::{csv_escape, csv_unguard, parse_csv, stored_timestamp};
+use crate::timestamps::{parse_timestamp, DisplayTimezone};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const CRASH_EXPORT_SCHEMA: &str = "hermes.crashes";
+/// Version 2 writes timestamps in the document's `timezone` instead of always in UTC.
+pub const CRASH_EXPORT_SCHEMA_VERSION: u32 = 2;
+pub const CRASH_CSV_COLUMNS: [&str; 12] = [
+    "id",
+    "timestamp",
+    "os",
+    "source",
+    "crashType",
+    "code",
+    "summary",
+    "suspectedComponent",
+    "rawPath",
+    "sourceHost",
+    "imported",
+    "details",
+];
+
+/// Narrows a crash export. Every field is optional; an empty filter exports everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashExportFilter {
+    /// Matched case-insensitively against the summary, code and suspected component.
+    pub text: Option<String>,
+    pub crash_type: Option<String>,
+    pub os: Option<String>,
+    /// RFC3339 bounds, inclusive.
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CrashExportDocument<'a> {
+    schema: &'static str,
+    schema_version: u32,
+    exported_at: String,
+    timezone: String,
+    crash_count: usize,
+    crashes: &'a [CrashRecord],
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+pub fn matches_filter(filter: &CrashExportFilter, crash: &CrashRecord) -> bool {
+    if let Some(text) = non_empty(filter.text.as_deref()) {
+        let needle = text.to_ascii_lowercase();
+        let found = [
+            Some(crash.summary.as_str()),
+            crash.code.as_deref(),
+            crash.suspected_component.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|value| value.to_ascii_lowercase().contains(needle.as_str()));
+        if !found {
+            return false;
+        }
+    }
+    if let Some(crash_type) = non_empty(filter.crash_type.as_deref()) {
+        if !crash.crash_type.eq_ignore_ascii_case(crash_type) {
+            return false;
+        }
+    }
+    if let Some(os) = non_empty(filter.os.as_deref()) {
+        if !crash.os.eq_ignore_ascii_case(os) {
+            return false;
+        }
+    }
+    let timestamp = parse_timestamp(crash.timestamp.as_str());
+    let bounds = [
+        (non_empty(filter.start.as_deref()), true),
+        (non_empty(filter.end.as_deref()), false),
+    ];
+    for (bound, is_start) in bounds {
+        let Some(bound) = bound.and_then(parse_timestamp) else {
+            continue;
+        };
+        match timestamp {
+            Some(at) if (is_start && at >= bound) || (!is_start && at <= bound) => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn crash_csv_row(crash: &CrashRecord) -> String {
+    [
+        crash.id.as_str(),
+        crash.timestamp.as_str(),
+        crash.os.as_str(),
+        crash.source.as_str(),
+        crash.crash_type.as_str(),
+        crash.code.as_deref().unwrap_or_default(),
+        crash.summary.as_str(),
+        crash.suspected_component.as_deref().unwrap_or_default(),
+        crash.raw_path.as_deref().unwrap_or_default(),
+        crash.source_host.as_str(),
+        if crash.imported { "true" } else { "false" },
+        crash.details.as_deref().unwrap_or_default(),
+    ]
+    .map(csv_escape)
+    .join(",")
+}
+
+/// Renders crashes as the app's JSON document or CSV with `CRASH_CSV_COLUMNS` as the header,
+/// with timestamps shown in `timezone`.
+pub fn build_crash_export(
+    extension: &str,
+    crashes: &[CrashRecord],
+    timezone: DisplayTimezone,
+) -> Result<String, String> {
+    let crashes: Vec<CrashRecord> = crashes
+        .iter()
+        .map(|crash| CrashRecord {
+            timestamp: timezone.format(crash.timestamp.as_str()),
+            ..crash.clone()
+        })
+        .collect();
+    let crashes = crashes.as_slice();
+    match extension {
+        "json" => serde_json::to_string_pretty(&CrashExportDocument {
+            schema: CRASH_EXPORT_SCHEMA,
+            schema_version: CRASH_EXPORT_SCHEMA_VERSION,
+            exported_at: timezone.convert(Utc::now()).to_rfc3339(),
+            timezone: timezone.name(),
+            crash_count: crashes.len(),
+            crashes,
+        })
+        .map_err(|error| format!("Failed to serialize crash export: {error}")),
+        "csv" => {
+            let mut lines = vec![CRASH_CSV_COLUMNS.join(",")];
+            lines.extend(crashes.iter().map(crash_csv_row));
+            Ok(lines.join("\n"))
+        }
+        _ => Err("Unsupported crash export format.".to_string()),
+    }
+}
+
+fn crash_from_csv(
+    header: &[String],
+    record: &[String],
+    line: usize,
+) -> Result<CrashRecord, String> {
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|column| column.trim() == name)
+            .and_then(|index| record.get(index))
+            .map(|value| csv_unguard(value))
+            .unwrap_or_default()
+    };
+    let optional = |name: &str| Some(column(name)).filter(|value| !value.is_empty());
+    let crash = CrashRecord {
+        id: column("id"),
+        timestamp: stored_timestamp(column("timestamp").as_str()),
+        os: column("os"),
+        source: column("source"),
+        crash_type: column("crashType"),
+        code: optional("code"),
+        summary: column("summary"),
+        suspected_component: optional("suspectedComponent"),
+        raw_path: optional("rawPath"),
+        source_host: optional("sourceHost").unwrap_or_else(|| "localhost".to_string()),
+        imported: true,
+        details: optional("details"),
+    };
+    if crash.id.is_empty() || crash.timestamp.is_empty() || crash.crash_type.is_empty() {
+        return Err(format!(
+            "Row {line} is missing the id, timestamp or crashType column."
+        ));
+    }
+    Ok(crash)
+}
+
+/// Reads a crash export written by `build_crash_export`, or a bare JSON array of crashes.
+/// Every record comes back flagged as imported with a UTC timestamp; ids are kept so
+/// re-importing updates in place.
+pub fn parse_crash_export(raw: &str) -> Result<Vec<CrashRecord>, String> {
+    let trimmed = raw.trim_start_matches('\u{feff}').trim();
+    if trimmed.starts_with('[') || trimmed.starts_with('{') {
+        let document = serde_json::from_str::<Value>(trimmed)
+            .map_err(|error| format!("Crash export is not valid JSON: {error}"))?;
+        let values = match document {
+            Value::Array(values) => values,
+            Value::Object(mut object) => match object.remove("crashes") {
+                Some(Value::Array(values)) => values,
+                _ => return Err("Crash export has no crashes array.".to_string()),
+            },
+            _ => return Err("Crash export has no crashes array.".to_string()),
+        };
+        return values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                serde_json::from_value::<CrashRecord>(value)
+                    .map(|crash| CrashRecord {
+                        imported: true,
+                        timestamp: stored_timestamp(crash.timestamp.as_str()),
+                        ..crash
+                    })
+                    .map_err(|error| format!("Crash {} is not valid: {error}", index + 1))
+            })
+            .collect();
+    }
+
+    let mut records = parse_csv(trimmed)?.into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+    if !header.iter().any(|column| column.trim() == "crashType") {
+        return Err("CSV is not a Hermes crash export (missing crashType column).".to_string());
+    }
+    records
+        .enumerate()
+        .map(|(index, record)| crash_from_csv(&header, &record, index + 2))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crash() -> CrashRecord {
+        let mut crash = CrashRecord::new(
+            "windows",
+            "WER",
+            "Application Crash",
+            Some("0xc0000005"),
+            "app.exe crashed, \"twice\"\nsecond line",
+            Some("=ntdll.dll"),
+            None,
+            "host-a",
+            false,
+        );
+        crash.timestamp = "2024-03-01T10:00:00+00:00".to_string();
+        crash
+    }
+
+    #[test]
+    fn csv_and_json_round_trip_as_imported() {
+        let original = crash();
+        for format in ["json", "csv"] {
+            let zone = DisplayTimezone::from_name("America/New_York").unwrap();
+            let raw = build_crash_export(format, std::slice::from_ref(&original), zone).unwrap();
+            assert!(raw.contains("2024-03-01T05:00:00-05:00"), "{format}");
+            let parsed = parse_crash_export(raw.as_str()).unwrap();
+            assert_eq!(parsed.len(), 1, "{format}");
+            assert_eq!(parsed[0].id, original.id);
+            assert_eq!(parsed[0].timestamp, original.timestamp);
+            assert_eq!(parsed[0].summary, original.summary);
+            assert_eq!(parsed[0].suspected_component, original.suspected_component);
+            assert_eq!(parsed[0].raw_path, None);
+            assert!(parsed[0].imported);
+        }
+    }
+
+    #[test]
+    fn filter_checks_text_type_and_range() {
+        let crash = crash();
+        let mut filter = CrashExportFilter {
+            text: Some("NTDLL".to_string()),
+            start: Some("2024-03-01T00:00:00Z".to_string()),
+            ..CrashExportFilter::default()
+        };
+        assert!(matches_filter(&filter, &crash));
+        filter.end = Some("2024-03-01T09:00:00Z".to_string());
+        assert!(!matches_filter(&filter, &crash));
+        filter.end = None;
+        filter.crash_type = Some("Blue Screen".to_string());
+        assert!(!matches_filter(&filter, &crash));
+    }
+}
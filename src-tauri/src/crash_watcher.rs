@@ -0,0 +1,137 @@
+use crate::crash::{crash_watch_roots, import_crash_files, is_crash_report_path};
+use crate::db::save_crashes;
+use crate::diagnostics;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+pub const CRASHES_IMPORTED_EVENT: &str = "hla://crashes-imported";
+
+/// Crash reporters write files in several steps; wait for this much quiet before parsing.
+const SETTLE_DELAY: Duration = Duration::from_secs(3);
+const IDLE_WAIT: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashesImportedPayload {
+    pub count: usize,
+    pub crash_ids: Vec<String>,
+}
+
+/// Starts the background watcher over the host's crash report directories.
+pub fn start(app: AppHandle) {
+    let spawned = std::thread::Builder::new()
+        .name("crash-watcher".to_string())
+        .spawn(move || run(app));
+    if let Err(error) = spawned {
+        diagnostics::warn(
+            "crash",
+            format!("Failed to start crash watcher thread: {error}"),
+        );
+    }
+}
+
+fn run(app: AppHandle) {
+    let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = match notify::recommended_watcher(sender) {
+        Ok(value) => value,
+        Err(error) => {
+            diagnostics::warn("crash", format!("Failed to create crash watcher: {error}"));
+            return;
+        }
+    };
+
+    let mut watched = 0usize;
+    for root in crash_watch_roots() {
+        if !root.path.is_dir() {
+            continue;
+        }
+        let mode = if root.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        match watcher.watch(root.path.as_path(), mode) {
+            Ok(()) => watched += 1,
+            Err(error) => diagnostics::warn(
+                "crash",
+                format!("Failed to watch {}: {error}", root.path.display()),
+            ),
+        }
+    }
+    if watched == 0 {
+        diagnostics::info("crash", "No crash report directories found to watch");
+        return;
+    }
+    diagnostics::info(
+        "crash",
+        format!("Watching {watched} crash report directories"),
+    );
+
+    let mut pending: BTreeSet<PathBuf> = BTreeSet::new();
+    loop {
+        let wait = if pending.is_empty() {
+            IDLE_WAIT
+        } else {
+            SETTLE_DELAY
+        };
+        match receiver.recv_timeout(wait) {
+            Ok(Ok(event)) => {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    pending.extend(
+                        event
+                            .paths
+                            .into_iter()
+                            .filter(|path| is_crash_report_path(path)),
+                    );
+                }
+            }
+            Ok(Err(error)) => {
+                diagnostics::warn("crash", format!("Crash watcher error: {error}"));
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if !pending.is_empty() {
+                    let paths: Vec<PathBuf> = std::mem::take(&mut pending).into_iter().collect();
+                    import_and_notify(&app, paths.as_slice());
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+fn import_and_notify(app: &AppHandle, paths: &[PathBuf]) {
+    let crashes = import_crash_files(paths);
+    if crashes.is_empty() {
+        return;
+    }
+    if let Err(error) = save_crashes(&crashes) {
+        diagnostics::warn(
+            "crash",
+            format!("Failed to save watched crash reports: {error}"),
+        );
+        return;
+    }
+
+    let payload = CrashesImportedPayload {
+        count: crashes.len(),
+        crash_ids: crashes.into_iter().map(|crash| crash.id).collect(),
+    };
+    diagnostics::info(
+        "crash",
+        format!(
+            "Imported {} new crash report(s) from watcher",
+            payload.count
+        ),
+    );
+    if let Err(error) = app.emit(CRASHES_IMPORTED_EVENT, payload) {
+        diagnostics::warn(
+            "crash",
+            format!("Failed to emit crash import event: {error}"),
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use crate::crash::CrashRecord;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+const APPLICATION: &str = "Application";
+const SYSTEM: &str = "System";
+const SECURITY: &str = "Security";
+const DIAGNOSTICS_PERFORMANCE: &str = "Microsoft-Windows-Diagnostics-Performance/Operational";
+const PRINT_ADMIN: &str = "Microsoft-Windows-PrintService/Admin";
+const PRINT_OPERATIONAL: &str = "Microsoft-Windows-PrintService/Operational";
+const REMOTE_SESSIONS: &str = "Microsoft-Windows-TerminalServices-LocalSessionManager/Operational";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionSuggestion {
+    pub crash_id: String,
+    pub start: String,
+    pub end: String,
+    /// Windows channels to collect; empty for Linux and macOS, which have no channel selection.
+    pub channels: Vec<String>,
+    pub reasons: Vec<String>,
+}
+
+fn push_channel(channels: &mut Vec<String>, channel: &str) {
+    if !channels.iter().any(|existing| existing == channel) {
+        channels.push(channel.to_string());
+    }
+}
+
+/// Recommends the window and channels worth collecting for a crash. System failures look further
+/// back than application crashes because their causes (drivers, updates, power) build up earlier.
+pub fn suggest_collection_window(
+    crash: &CrashRecord,
+    now: DateTime<Utc>,
+) -> Result<CollectionSuggestion, String> {
+    let crashed_at = DateTime::parse_from_rfc3339(crash.timestamp.as_str())
+        .map_err(|_| "Crash timestamp is not a valid RFC3339 time.".to_string())?
+        .with_timezone(&Utc);
+    let crash_type = crash.crash_type.to_ascii_lowercase();
+    let component = crash
+        .suspected_component
+        .as_deref()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let windows = crash.os == "windows";
+
+    let mut channels = Vec::new();
+    let mut reasons = Vec::new();
+    if windows {
+        push_channel(&mut channels, APPLICATION);
+        push_channel(&mut channels, SYSTEM);
+    }
+
+    let (before, after) = if crash_type.contains("blue screen")
+        || crash_type.contains("bugcheck")
+        || crash_type.contains("kernel")
+    {
+        reasons.push(
+            "System failure: looking back 2 hours for driver, update and power events.".to_string(),
+        );
+        if windows {
+            push_channel(&mut channels, DIAGNOSTICS_PERFORMANCE);
+        }
+        (Duration::hours(2), Duration::minutes(15))
+    } else if crash_type.contains("hang") {
+        reasons.push(
+            "Hang: collecting 30 minutes before to catch resource pressure and slow I/O."
+                .to_string(),
+        );
+        if windows {
+            push_channel(&mut channels, DIAGNOSTICS_PERFORMANCE);
+        }
+        (Duration::minutes(30), Duration::minutes(10))
+    } else {
+        reasons.push("Application crash: collecting 30 minutes around the failure.".to_string());
+        (Duration::minutes(30), Duration::minutes(5))
+    };
+
+    if windows && (component.contains("spool") || component.contains("print")) {
+        push_channel(&mut channels, PRINT_ADMIN);
+        push_channel(&mut channels, PRINT_OPERATIONAL);
+        reasons.push("Print component involved: adding PrintService channels.".to_string());
+    }
+    if windows
+        && (component.contains("termsrv")
+            || component.contains("rdp")
+            || component.contains("mstsc"))
+    {
+        push_channel(&mut channels, REMOTE_SESSIONS);
+        push_channel(&mut channels, SECURITY);
+        reasons.push(
+            "Remote Desktop component involved: adding session and logon channels.".to_string(),
+        );
+    }
+
+    let end = (crashed_at + after).min(now);
+    let start = (crashed_at - before).min(end);
+    Ok(CollectionSuggestion {
+        crash_id: crash.id.clone(),
+        start: start.to_rfc3339(),
+        end: end.to_rfc3339(),
+        channels,
+        reasons,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crash(os: &str, crash_type: &str, component: Option<&str>) -> CrashRecord {
+        let mut crash = CrashRecord::new(
+            os,
+            "WER",
+            crash_type,
+            None,
+            crash_type,
+            component,
+            None,
+            "localhost",
+            false,
+        );
+        crash.timestamp = "2026-03-01T10:00:00Z".to_string();
+        crash
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-02T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn system_failures_get_a_wider_window_and_diagnostics_channel() {
+        let suggestion =
+            suggest_collection_window(&crash("windows", "Blue Screen", None), now()).unwrap();
+        assert_eq!(suggestion.start, "2026-03-01T08:00:00+00:00");
+        assert_eq!(suggestion.end, "2026-03-01T10:15:00+00:00");
+        assert_eq!(
+            suggestion.channels,
+            vec![APPLICATION, SYSTEM, DIAGNOSTICS_PERFORMANCE]
+        );
+    }
+
+    #[test]
+    fn print_crash_adds_print_channels_and_caps_end_at_now() {
+        let now = DateTime::parse_from_rfc3339("2026-03-01T10:02:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let suggestion =
+            suggest_collection_window(&crash("windows", "APPCRASH", Some("spoolsv.exe")), now)
+                .unwrap();
+        assert_eq!(suggestion.end, "2026-03-01T10:02:00+00:00");
+        assert!(suggestion.channels.contains(&PRINT_ADMIN.to_string()));
+    }
+
+    #[test]
+    fn linux_crashes_have_no_channels() {
+        let suggestion =
+            suggest_collection_window(&crash("linux", "Kernel Panic", None), now()).unwrap();
+        assert!(suggestion.channels.is_empty());
+        assert_eq!(suggestion.start, "2026-03-01T08:00:00+00:00");
+    }
+}
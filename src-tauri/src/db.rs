@@ -1,25 +1,522 @@
-use crate::{crash::CrashRecord, logs::NormalizedEvent};
+use crate::{
+    crash::CrashRecord,
+    encryption,
+    inventory::{InventoryItem, InventorySnapshotSummary},
+    logs::NormalizedEvent,
+    nl_query::EventQueryFilter,
+    regex_search,
+    stability::StabilityDay,
+};
+use chrono::{DateTime, Utc};
 use dirs::data_local_dir;
-use rusqlite::{params, Connection, Row};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use regex::Regex;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, ErrorCode, Row, Transaction, TransactionBehavior};
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
 
-fn db_path() -> Result<PathBuf, String> {
+const ALERT_DELIVERY_LOG_LIMIT: i64 = 1000;
+const SYNC_HISTORY_LIMIT: i64 = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventHistogramBucket {
+    pub bucket_start: String,
+    pub total: u64,
+    pub by_severity: BTreeMap<String, u64>,
+}
+
+/// One numeric reading from a non-log collector such as SMART.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricSample {
+    pub timestamp: String,
+    pub source_host: String,
+    pub source: String,
+    pub device: String,
+    pub name: String,
+    pub value: f64,
+    pub unit: Option<String>,
+    pub status: Option<String>,
+}
+
+/// One `source_host` seen in the stored events or crashes, for the host picker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceHostSummary {
+    pub host: String,
+    pub event_count: u64,
+    pub crash_count: u64,
+    pub last_seen: Option<String>,
+}
+
+/// A user-pinned event with an optional note, shown on incident timelines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventBookmark {
+    pub event_id: String,
+    pub note: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatSession {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub message_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessage {
+    pub id: String,
+    pub session_id: String,
+    /// "user" or "assistant".
+    pub role: String,
+    pub content: String,
+    /// Event or crash details attached to a user message, as sent to the model.
+    pub context: Option<String>,
+    pub profile_name: Option<String>,
+    pub model: Option<String>,
+    pub created_at: String,
+}
+
+/// An opt-in recording of the queries and filters used during an investigation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuerySession {
+    pub id: String,
+    pub name: String,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub step_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryStep {
+    pub seq: u32,
+    pub timestamp: String,
+    pub action: String,
+    pub params: serde_json::Value,
+}
+
+/// Events collected from one log or channel during a sync.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSourceCount {
+    pub log_name: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableStats {
+    pub name: String,
+    pub rows: u64,
+    /// Pages held by the table itself, indexes excluded.
+    pub bytes: u64,
+    pub index_bytes: u64,
+    /// Range of the `timestamp` column, for tables that have one.
+    pub oldest: Option<String>,
+    pub newest: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexStats {
+    pub name: String,
+    pub table: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStats {
+    pub path: String,
+    pub file_bytes: u64,
+    pub wal_bytes: u64,
+    /// Space on the free list, which `compact_database` returns to the file system.
+    pub free_bytes: u64,
+    pub page_size: u64,
+    pub tables: Vec<TableStats>,
+    pub indexes: Vec<IndexStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactionResult {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub reclaimed_bytes: u64,
+}
+
+/// One collection run (refresh, backfill, range or crash-window sync) and how it went.
+/// One entry of the merged crash and event timeline; `kind` says which record it carries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "record", rename_all = "camelCase")]
+pub enum RecordTimelineEntry {
+    Event(NormalizedEvent),
+    Crash(CrashRecord),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordTimeline {
+    pub entries: Vec<RecordTimelineEntry>,
+    /// More entries matched than the limit allowed; the latest ones were left out.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncHistoryEntry {
+    pub id: i64,
+    pub kind: String,
+    pub source_host: String,
+    pub started_at: String,
+    pub finished_at: String,
+    /// `ok`, `partial` (warnings or recoverable errors) or `failed`.
+    pub status: String,
+    pub collected: usize,
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+    pub sources: Vec<SyncSourceCount>,
+}
+
+/// One attempt to post an alert rule's webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertDelivery {
+    pub id: i64,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub sent_at: String,
+    pub matched: u32,
+    /// Matches held back by the rule's cooldown since the previous post.
+    pub suppressed: u32,
+    pub success: bool,
+    pub http_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// A stored event matched by a Sigma rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigmaDetection {
+    pub rule_id: String,
+    pub rule_title: String,
+    pub level: String,
+    /// Id of the stored event, for drill-down.
+    pub event_id: String,
+    pub event_timestamp: String,
+    pub source_host: String,
+    pub provider: String,
+    pub event_code: Option<u32>,
+    pub message: String,
+    pub detected_at: String,
+}
+
+/// A threat-intel indicator: `ip`, `domain`, `hash` or `filename`, stored lowercased.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IocIndicator {
+    pub feed: String,
+    pub kind: String,
+    pub value: String,
+}
+
+/// Indicator counts of one feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IocFeedSummary {
+    pub feed: String,
+    pub added_at: String,
+    pub counts: BTreeMap<String, u64>,
+}
+
+/// A stored event containing an indicator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IocMatch {
+    pub feed: String,
+    pub kind: String,
+    pub indicator: String,
+    /// `message`, or the event field the indicator was found in.
+    pub field: String,
+    pub event_id: String,
+    pub event_timestamp: String,
+    pub source_host: String,
+    pub provider: String,
+    pub event_code: Option<u32>,
+    pub message: String,
+    pub matched_at: String,
+}
+
+/// A run of events from one host with the same provider, event ID and message, shown as one row.
+/// `event` is the first event of the run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepeatedEvent {
+    #[serde(flatten)]
+    pub event: NormalizedEvent,
+    pub repeat_count: u32,
+    pub first_timestamp: String,
+    pub last_timestamp: String,
+}
+
+/// Queue depth and delivery bookkeeping for one integration's forwarding spool.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardSpoolState {
+    pub integration_id: String,
+    pub queued: u64,
+    pub oldest_queued_timestamp: Option<String>,
+    pub last_attempt_at: Option<String>,
+    pub last_success_at: Option<String>,
+    pub last_delivered_timestamp: Option<String>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    pub delivered_total: u64,
+    pub dropped_total: u64,
+}
+
+/// The open workspace's database file, when it is not the default `events.db`.
+static DATABASE_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// The app data directory, which holds `events.db` and the settings files.
+pub fn data_dir() -> Result<PathBuf, String> {
     let mut base = data_local_dir().ok_or("Unable to resolve local data directory")?;
     base.push("hermes-log-analyst");
     fs::create_dir_all(&base).map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    Ok(base)
+}
+
+pub fn default_db_path() -> Result<PathBuf, String> {
+    let mut base = data_dir()?;
     base.push("events.db");
     Ok(base)
 }
 
-fn open_connection() -> Result<Connection, String> {
+pub fn db_path() -> Result<PathBuf, String> {
+    let selected = DATABASE_FILE
+        .lock()
+        .map_err(|_| "Database file selection is poisoned".to_string())?
+        .clone();
+    match selected {
+        Some(path) => Ok(path),
+        None => default_db_path(),
+    }
+}
+
+/// Points later connections at another database file (`None` for `events.db`). Connections to
+/// the previous file are closed as they are returned to the pool.
+pub fn set_database_file(path: Option<PathBuf>) -> Result<(), String> {
+    *DATABASE_FILE
+        .lock()
+        .map_err(|_| "Database file selection is poisoned".to_string())? = path;
+    Ok(())
+}
+
+/// Idle connections kept for reuse. In WAL mode they can all read while one of them writes.
+const POOL_IDLE_LIMIT: usize = 4;
+/// How long a statement waits on another connection's write lock before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long `replace_database` waits for borrowed connections to come back.
+#[cfg(feature = "sqlcipher")]
+const POOL_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Open connections to the database at `path`. The schema is ensured once per path, when the
+/// first connection to it is opened. `in_use` counts borrowed connections to any file.
+struct ConnectionPool {
+    path: Option<PathBuf>,
+    idle: Vec<Connection>,
+    in_use: usize,
+}
+
+static POOL: Mutex<ConnectionPool> = Mutex::new(ConnectionPool {
+    path: None,
+    idle: Vec::new(),
+    in_use: 0,
+});
+/// Signalled whenever a borrowed connection is returned.
+static POOL_RETURNED: Condvar = Condvar::new();
+
+/// A connection borrowed from the pool; it goes back when dropped. It counts as borrowed from
+/// before it is opened, so `replace_database` never misses one being set up.
+struct PooledConnection {
+    conn: Option<Connection>,
+    path: PathBuf,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+            .as_ref()
+            .expect("pooled connection is present until dropped")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn
+            .as_mut()
+            .expect("pooled connection is present until dropped")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        let conn = self.conn.take();
+        if let Ok(mut pool) = POOL.lock() {
+            pool.in_use = pool.in_use.saturating_sub(1);
+            if let Some(conn) = conn {
+                if pool.path.as_ref() == Some(&self.path) && pool.idle.len() < POOL_IDLE_LIMIT {
+                    pool.idle.push(conn);
+                }
+            }
+            POOL_RETURNED.notify_all();
+        }
+    }
+}
+
+fn open_connection() -> Result<PooledConnection, String> {
     let path = db_path()?;
-    let conn = Connection::open(path).map_err(|e| format!("Failed to open SQLite database: {e}"))?;
-    ensure_schema(&conn)?;
+    let (mut pooled, schema_ready) = {
+        let mut pool = POOL
+            .lock()
+            .map_err(|_| "Database connection pool is poisoned".to_string())?;
+        pool.in_use += 1;
+        let mut pooled = PooledConnection {
+            conn: None,
+            path: path.clone(),
+        };
+        if pool.path.as_ref() == Some(&path) {
+            if let Some(conn) = pool.idle.pop() {
+                pooled.conn = Some(conn);
+                return Ok(pooled);
+            }
+            (pooled, true)
+        } else {
+            (pooled, false)
+        }
+    };
+
+    let conn = open_unlocked(path.as_path())?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|e| format!("Failed to set SQLite busy timeout: {e}"))?;
+    register_regexp(&conn)?;
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to enable SQLite WAL mode: {e}"))?;
+    conn.pragma_update(None, "synchronous", "NORMAL")
+        .map_err(|e| format!("Failed to set SQLite synchronous mode: {e}"))?;
+    if !schema_ready {
+        ensure_schema(&conn)?;
+        let mut pool = POOL
+            .lock()
+            .map_err(|_| "Database connection pool is poisoned".to_string())?;
+        if pool.path.as_ref() != Some(&path) {
+            pool.path = Some(path.clone());
+            pool.idle.clear();
+        }
+    }
+    pooled.conn = Some(conn);
+    Ok(pooled)
+}
+
+/// Opens `path` directly, outside the pool, with its key applied when it is encrypted.
+fn open_unlocked(path: &Path) -> Result<Connection, String> {
+    let conn =
+        Connection::open(path).map_err(|e| format!("Failed to open SQLite database: {e}"))?;
+    if let Some(key) = encryption::database_key(path)? {
+        conn.pragma_update(None, "key", encryption::key_pragma(key.as_str()))
+            .map_err(|e| format!("Failed to unlock encrypted database: {e}"))?;
+    }
     Ok(conn)
 }
 
+/// Folds the write-ahead log of the database at `path` into the file and empties it, using a
+/// connection of its own. Fails if another connection kept the checkpoint from finishing.
+pub fn checkpoint_file(path: &Path) -> Result<(), String> {
+    let conn = open_unlocked(path)?;
+    let busy = conn
+        .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map_err(|e| format!("Failed to checkpoint SQLite WAL: {e}"))?;
+    if busy != 0 {
+        return Err(
+            "The database is still in use; its write-ahead log could not be folded in.".to_string(),
+        );
+    }
+    conn.close()
+        .map_err(|(_, e)| format!("Failed to close SQLite database: {e}"))
+}
+
+/// Folds the write-ahead log back into `events.db`, so the file is complete on its own if it is
+/// copied to another machine. Called when the app exits.
+pub fn checkpoint() -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+        .map_err(|e| format!("Failed to checkpoint SQLite WAL: {e}"))
+}
+
+/// Rewrites `events.db`: `export` gets the current file and a fresh path to write the new one to,
+/// which then replaces it. The pool is emptied and held for the whole swap, after waiting for
+/// borrowed connections to come back, so no connection has either file open meanwhile.
+#[cfg(feature = "sqlcipher")]
+pub fn replace_database(
+    export: impl FnOnce(&std::path::Path, &std::path::Path) -> Result<(), String>,
+) -> Result<(), String> {
+    let path = db_path()?;
+    let mut pool = POOL
+        .lock()
+        .map_err(|_| "Database connection pool is poisoned".to_string())?;
+    pool.idle.clear();
+    pool.path = None;
+    // The guard is held until the swap is done.
+    let (_pool, waited) = POOL_RETURNED
+        .wait_timeout_while(pool, POOL_DRAIN_TIMEOUT, |pool| pool.in_use > 0)
+        .map_err(|_| "Database connection pool is poisoned".to_string())?;
+    if waited.timed_out() {
+        return Err(
+            "The database is still in use; try again when the current sync or export finishes."
+                .to_string(),
+        );
+    }
+    checkpoint_file(path.as_path())?;
+
+    let replacement = path.with_extension("db.rewrite");
+    let _ = fs::remove_file(&replacement);
+    if let Err(error) = export(path.as_path(), replacement.as_path()) {
+        let _ = fs::remove_file(&replacement);
+        return Err(error);
+    }
+    // No connection is open, so these are stale: left over from the old file after the
+    // checkpoint, and they would be replayed against the new one.
+    for sidecar in ["-wal", "-shm"] {
+        let mut name = path.clone().into_os_string();
+        name.push(sidecar);
+        let _ = fs::remove_file(PathBuf::from(name));
+    }
+    fs::rename(&replacement, &path).map_err(|e| format!("Failed to replace database file: {e}"))
+}
+
 fn dedupe_events(events: Vec<NormalizedEvent>) -> Vec<NormalizedEvent> {
     let mut seen = HashSet::new();
     let mut deduped = Vec::with_capacity(events.len());
@@ -45,7 +542,98 @@ fn dedupe_events(events: Vec<NormalizedEvent>) -> Vec<NormalizedEvent> {
     deduped
 }
 
+/// One step of the schema history. `schema_version` records which steps a database has had.
+struct Migration {
+    version: u32,
+    description: &'static str,
+    apply: fn(&Connection) -> Result<(), String>,
+}
+
+/// Ordered schema migrations. Append new steps with the next version; never edit or reorder a
+/// step that has shipped, since existing databases will not run it again.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Base schema",
+        apply: base_schema,
+    },
+    Migration {
+        version: 2,
+        description: "Sigma detections",
+        apply: sigma_detections_schema,
+    },
+    Migration {
+        version: 3,
+        description: "IOC feeds and matches",
+        apply: ioc_schema,
+    },
+    Migration {
+        version: 4,
+        description: "Epoch millisecond timestamps",
+        apply: timestamp_ms_schema,
+    },
+    Migration {
+        version: 5,
+        description: "Repeated event counts",
+        apply: repeat_count_schema,
+    },
+    Migration {
+        version: 6,
+        description: "Rehydrated archive events",
+        apply: rehydrated_schema,
+    },
+    Migration {
+        version: 7,
+        description: "Journal cursors",
+        apply: journal_cursor_schema,
+    },
+];
+
+/// Brings the database up to the newest migration. Each step runs in its own immediate
+/// transaction, so two connections opened at once cannot both apply it.
 fn ensure_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        );",
+    )
+    .map_err(|e| format!("Failed to create schema_version table: {e}"))?;
+
+    for migration in MIGRATIONS {
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)
+            .map_err(|e| format!("Failed to start schema migration: {e}"))?;
+        let applied: bool = tx
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM schema_version WHERE version = ?1)",
+                params![migration.version],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to read schema version: {e}"))?;
+        if applied {
+            continue;
+        }
+        (migration.apply)(&tx).map_err(|error| {
+            format!(
+                "Schema migration {} ({}) failed: {error}",
+                migration.version, migration.description
+            )
+        })?;
+        tx.execute(
+            "INSERT INTO schema_version (version, description, applied_at) VALUES (?1, ?2, ?3)",
+            params![migration.version, migration.description, Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| format!("Failed to record schema version: {e}"))?;
+        tx.commit()
+            .map_err(|e| format!("Failed to commit schema migration: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Version 1: every table as of the introduction of `schema_version`. Databases created before
+/// then may lack some columns, so those are added where missing.
+fn base_schema(conn: &Connection) -> Result<(), String> {
     conn.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS events (
@@ -59,7 +647,16 @@ fn ensure_schema(conn: &Connection) -> Result<(), String> {
             severity TEXT NOT NULL,
             message TEXT NOT NULL,
             source_host TEXT NOT NULL DEFAULT 'localhost',
-            imported INTEGER NOT NULL DEFAULT 0
+            imported INTEGER NOT NULL DEFAULT 0,
+            user_sid TEXT,
+            user_name TEXT,
+            computer TEXT,
+            process_id INTEGER,
+            thread_id INTEGER,
+            keywords TEXT,
+            opcode TEXT,
+            task TEXT,
+            metadata TEXT
         );
 
         CREATE INDEX IF NOT EXISTS idx_events_timestamp ON events(timestamp);
@@ -77,67 +674,520 @@ fn ensure_schema(conn: &Connection) -> Result<(), String> {
             suspected_component TEXT,
             raw_path TEXT,
             source_host TEXT NOT NULL DEFAULT 'localhost',
-            imported INTEGER NOT NULL DEFAULT 0
+            imported INTEGER NOT NULL DEFAULT 0,
+            details TEXT
         );
 
         CREATE INDEX IF NOT EXISTS idx_crashes_timestamp ON crashes(timestamp);
         CREATE INDEX IF NOT EXISTS idx_crashes_os ON crashes(os);
+
+        CREATE TABLE IF NOT EXISTS metrics (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp TEXT NOT NULL,
+            source_host TEXT NOT NULL DEFAULT 'localhost',
+            source TEXT NOT NULL,
+            device TEXT NOT NULL,
+            name TEXT NOT NULL,
+            value REAL NOT NULL,
+            unit TEXT,
+            status TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_metrics_timestamp ON metrics(timestamp);
+        CREATE INDEX IF NOT EXISTS idx_metrics_device ON metrics(source, device, name);
+
+        CREATE TABLE IF NOT EXISTS inventory_snapshots (
+            id TEXT PRIMARY KEY,
+            timestamp TEXT NOT NULL,
+            source_host TEXT NOT NULL DEFAULT 'localhost',
+            item_count INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS inventory_items (
+            snapshot_id TEXT NOT NULL REFERENCES inventory_snapshots(id) ON DELETE CASCADE,
+            kind TEXT NOT NULL,
+            name TEXT NOT NULL,
+            display_name TEXT,
+            version TEXT,
+            state TEXT,
+            start_mode TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_inventory_snapshots_host ON inventory_snapshots(source_host, timestamp);
+        CREATE INDEX IF NOT EXISTS idx_inventory_items_snapshot ON inventory_items(snapshot_id);
+
+        CREATE TABLE IF NOT EXISTS stability_index (
+            day TEXT NOT NULL,
+            source_host TEXT NOT NULL DEFAULT 'localhost',
+            stability_index REAL NOT NULL,
+            daily_score REAL NOT NULL,
+            app_crashes INTEGER NOT NULL DEFAULT 0,
+            hangs INTEGER NOT NULL DEFAULT 0,
+            system_failures INTEGER NOT NULL DEFAULT 0,
+            unexpected_shutdowns INTEGER NOT NULL DEFAULT 0,
+            error_count INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (day, source_host)
+        );
+
+        CREATE TABLE IF NOT EXISTS forward_spool (
+            seq INTEGER PRIMARY KEY AUTOINCREMENT,
+            integration_id TEXT NOT NULL,
+            event_id TEXT NOT NULL,
+            event_timestamp TEXT NOT NULL,
+            enqueued_at TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            UNIQUE(integration_id, event_id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_forward_spool_integration ON forward_spool(integration_id, seq);
+
+        CREATE TABLE IF NOT EXISTS event_bookmarks (
+            event_id TEXT PRIMARY KEY,
+            note TEXT,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS template_embeddings (
+            model TEXT NOT NULL,
+            template TEXT NOT NULL,
+            vector BLOB NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (model, template)
+        );
+
+        CREATE TABLE IF NOT EXISTS chat_sessions (
+            id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS chat_messages (
+            id TEXT PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            role TEXT NOT NULL,
+            content TEXT NOT NULL,
+            context TEXT,
+            profile_name TEXT,
+            model TEXT,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_chat_messages_session ON chat_messages(session_id, created_at);
+
+        CREATE TABLE IF NOT EXISTS query_sessions (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            ended_at TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS query_session_steps (
+            session_id TEXT NOT NULL,
+            seq INTEGER NOT NULL,
+            timestamp TEXT NOT NULL,
+            action TEXT NOT NULL,
+            params TEXT NOT NULL,
+            PRIMARY KEY (session_id, seq)
+        );
+
+        CREATE TABLE IF NOT EXISTS forward_state (
+            integration_id TEXT PRIMARY KEY,
+            last_attempt_at TEXT,
+            last_success_at TEXT,
+            last_delivered_timestamp TEXT,
+            last_error TEXT,
+            consecutive_failures INTEGER NOT NULL DEFAULT 0,
+            delivered_total INTEGER NOT NULL DEFAULT 0,
+            dropped_total INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS alert_deliveries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            rule_id TEXT NOT NULL,
+            rule_name TEXT NOT NULL,
+            sent_at TEXT NOT NULL,
+            matched INTEGER NOT NULL,
+            suppressed INTEGER NOT NULL,
+            success INTEGER NOT NULL,
+            http_status INTEGER,
+            error TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS sync_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            source_host TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            finished_at TEXT NOT NULL,
+            status TEXT NOT NULL,
+            collected INTEGER NOT NULL,
+            warnings TEXT NOT NULL,
+            errors TEXT NOT NULL,
+            sources TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS import_offsets (
+            fingerprint TEXT NOT NULL,
+            source_host TEXT NOT NULL,
+            path TEXT NOT NULL,
+            offset INTEGER NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (fingerprint, source_host)
+        );
         ",
     )
     .map_err(|e| format!("Failed to create schema: {e}"))?;
 
-    // Migration for existing tables (ignore errors if column already exists)
-    let _ = conn.execute("ALTER TABLE events ADD COLUMN source_host TEXT NOT NULL DEFAULT 'localhost'", []);
-    let _ = conn.execute("ALTER TABLE crashes ADD COLUMN source_host TEXT NOT NULL DEFAULT 'localhost'", []);
-    
+    for (table, column, definition) in [
+        ("events", "source_host", "TEXT NOT NULL DEFAULT 'localhost'"),
+        ("crashes", "source_host", "TEXT NOT NULL DEFAULT 'localhost'"),
+        ("crashes", "details", "TEXT"),
+        ("events", "raw_payload", "BLOB"),
+        ("events", "user_sid", "TEXT"),
+        ("events", "user_name", "TEXT"),
+        ("events", "computer", "TEXT"),
+        ("events", "process_id", "INTEGER"),
+        ("events", "thread_id", "INTEGER"),
+        ("events", "keywords", "TEXT"),
+        ("events", "opcode", "TEXT"),
+        ("events", "task", "TEXT"),
+        ("events", "metadata", "TEXT"),
+    ] {
+        add_column_if_missing(conn, table, column, definition)?;
+    }
+    Ok(())
+}
+
+/// Version 2: hits from Sigma rule scans, one per rule and stored event.
+fn sigma_detections_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS sigma_detections (
+            rule_id TEXT NOT NULL,
+            rule_title TEXT NOT NULL,
+            level TEXT NOT NULL,
+            event_id TEXT NOT NULL,
+            event_timestamp TEXT NOT NULL,
+            source_host TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            event_code INTEGER,
+            message TEXT NOT NULL,
+            detected_at TEXT NOT NULL,
+            PRIMARY KEY (rule_id, event_id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_sigma_detections_timestamp ON sigma_detections(event_timestamp);
+        ",
+    )
+    .map_err(|e| format!("Failed to create sigma_detections table: {e}"))
+}
+
+/// Version 3: threat-intel indicators by feed, and the stored events they were found in.
+fn ioc_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS ioc_indicators (
+            feed TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            value TEXT NOT NULL,
+            added_at TEXT NOT NULL,
+            PRIMARY KEY (feed, kind, value)
+        );
+
+        CREATE TABLE IF NOT EXISTS ioc_matches (
+            feed TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            indicator TEXT NOT NULL,
+            field TEXT NOT NULL,
+            event_id TEXT NOT NULL,
+            event_timestamp TEXT NOT NULL,
+            source_host TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            event_code INTEGER,
+            message TEXT NOT NULL,
+            matched_at TEXT NOT NULL,
+            PRIMARY KEY (feed, indicator, event_id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ioc_matches_timestamp ON ioc_matches(event_timestamp);
+        ",
+    )
+    .map_err(|e| format!("Failed to create IOC tables: {e}"))
+}
+
+/// Version 4: event and crash timestamps as epoch milliseconds. The three collectors do not all
+/// write the same text form, so range, order and retention queries compare these instead of
+/// running `julianday` over every row.
+fn timestamp_ms_schema(conn: &Connection) -> Result<(), String> {
+    for table in ["events", "crashes"] {
+        add_column_if_missing(conn, table, "timestamp_ms", "INTEGER")?;
+        backfill_timestamp_ms(conn, table)?;
+    }
+    conn.execute_batch(
+        "
+        CREATE INDEX IF NOT EXISTS idx_events_timestamp_ms ON events(timestamp_ms);
+        CREATE INDEX IF NOT EXISTS idx_events_host_timestamp_ms ON events(source_host, timestamp_ms);
+        CREATE INDEX IF NOT EXISTS idx_crashes_timestamp_ms ON crashes(timestamp_ms);
+        ",
+    )
+    .map_err(|e| format!("Failed to create timestamp_ms indexes: {e}"))
+}
+
+/// Version 5: events collapsed at ingest keep one row per run, with how many events it stands
+/// for and when the last of them was logged.
+fn repeat_count_schema(conn: &Connection) -> Result<(), String> {
+    add_column_if_missing(conn, "events", "repeat_count", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column_if_missing(conn, "events", "last_timestamp", "TEXT")
+}
+
+/// Events restored from the archive carry the time they were restored, which keeps retention
+/// from deleting them again until their hold runs out.
+fn rehydrated_schema(conn: &Connection) -> Result<(), String> {
+    add_column_if_missing(conn, "events", "rehydrated_at_ms", "INTEGER")
+}
+
+/// Version 7: how far back from its newest entry the journal has been read, per journal filter,
+/// so refreshes through libsystemd read only what is new.
+fn journal_cursor_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS journal_cursors (
+            filter_key TEXT PRIMARY KEY,
+            cursor TEXT NOT NULL,
+            oldest_usec INTEGER NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        ",
+    )
+    .map_err(|e| format!("Failed to create journal_cursors table: {e}"))
+}
+
+/// Fills `timestamp_ms` for rows stored before the column existed, parsing the text the same way
+/// inserts do. Rows whose timestamp cannot be parsed keep NULL and fall outside every range.
+fn backfill_timestamp_ms(conn: &Connection, table: &str) -> Result<(), String> {
+    let rows = {
+        let mut stmt = conn
+            .prepare(format!("SELECT rowid, timestamp FROM {table} WHERE timestamp_ms IS NULL").as_str())
+            .map_err(|e| format!("Failed to read {table} timestamps: {e}"))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| format!("Failed to read {table} timestamps: {e}"))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read {table} timestamps: {e}"))?
+    };
+    let mut update = conn
+        .prepare(format!("UPDATE {table} SET timestamp_ms = ?1 WHERE rowid = ?2").as_str())
+        .map_err(|e| format!("Failed to prepare {table} timestamp backfill: {e}"))?;
+    for (rowid, timestamp) in rows {
+        let Some(millis) = timestamp_ms(timestamp.as_str()) else {
+            continue;
+        };
+        update
+            .execute(params![millis, rowid])
+            .map_err(|e| format!("Failed to backfill {table}.timestamp_ms: {e}"))?;
+    }
     Ok(())
 }
 
+/// Epoch milliseconds of a stored timestamp or query bound. `None` binds as NULL, which matches
+/// no range, as `julianday` of an unreadable value did.
+fn timestamp_ms(value: &str) -> Option<i64> {
+    crate::timestamps::parse_timestamp(value).map(|parsed| parsed.timestamp_millis())
+}
+
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), String> {
+    if table_columns(conn, table)?.contains(column) {
+        return Ok(());
+    }
+    conn.execute(
+        format!("ALTER TABLE {table} ADD COLUMN {column} {definition}").as_str(),
+        [],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to add {table}.{column}: {e}"))
+}
+
+/// Registers `REGEXP` so `message REGEXP ?` runs patterns built by `regex_search::compile`.
+/// SQLite keeps the compiled pattern for the rest of the statement.
+fn register_regexp(conn: &Connection) -> Result<(), String> {
+    conn.create_scalar_function(
+        "regexp",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let pattern = ctx.get_or_create_aux(0, |value| -> Result<Regex, String> {
+                regex_search::compile(value.as_str().map_err(|e| e.to_string())?)
+            })?;
+            Ok(match ctx.get_raw(1) {
+                ValueRef::Text(text) => std::str::from_utf8(text).is_ok_and(|text| pattern.is_match(text)),
+                _ => false,
+            })
+        },
+    )
+    .map_err(|e| format!("Failed to register SQLite REGEXP function: {e}"))
+}
+
+/// Event columns in the order `row_to_event` reads them.
+const EVENT_COLUMNS: [&str; 20] = [
+    "id",
+    "timestamp",
+    "os",
+    "log_name",
+    "category",
+    "provider",
+    "event_id",
+    "severity",
+    "message",
+    "source_host",
+    "imported",
+    "user_sid",
+    "user_name",
+    "computer",
+    "process_id",
+    "thread_id",
+    "keywords",
+    "opcode",
+    "task",
+    "metadata",
+];
+
+/// `EVENT_COLUMNS` as a select list, optionally qualified with a table alias.
+fn event_columns(alias: Option<&str>) -> String {
+    EVENT_COLUMNS
+        .iter()
+        .map(|column| match alias {
+            Some(alias) => format!("{alias}.{column}"),
+            None => column.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn row_to_event(row: &Row<'_>) -> rusqlite::Result<NormalizedEvent> {
+    row_to_event_at(row, 0)
+}
+
+/// Reads an event whose `EVENT_COLUMNS` start at column `offset`.
+fn row_to_event_at(row: &Row<'_>, offset: usize) -> rusqlite::Result<NormalizedEvent> {
     Ok(NormalizedEvent {
-        id: row.get(0)?,
-        timestamp: row.get(1)?,
-        os: row.get(2)?,
-        log_name: row.get(3)?,
-        category: row.get(4)?,
-        provider: row.get(5)?,
-        event_id: row.get(6)?,
-        severity: row.get(7)?,
-        message: row.get(8)?,
-        source_host: row.get(9)?,
-        imported: row.get::<_, i64>(10)? != 0,
+        id: row.get(offset)?,
+        timestamp: row.get(offset + 1)?,
+        os: row.get(offset + 2)?,
+        log_name: row.get(offset + 3)?,
+        category: row.get(offset + 4)?,
+        provider: row.get(offset + 5)?,
+        event_id: row.get(offset + 6)?,
+        severity: row.get(offset + 7)?,
+        message: row.get(offset + 8)?,
+        source_host: row.get(offset + 9)?,
+        imported: row.get::<_, i64>(offset + 10)? != 0,
+        user_sid: row.get(offset + 11)?,
+        user_name: row.get(offset + 12)?,
+        computer: row.get(offset + 13)?,
+        process_id: row.get(offset + 14)?,
+        thread_id: row.get(offset + 15)?,
+        keywords: row.get(offset + 16)?,
+        opcode: row.get(offset + 17)?,
+        task: row.get(offset + 18)?,
+        metadata: row
+            .get::<_, Option<String>>(offset + 19)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        raw_payload: None,
     })
 }
 
 fn row_to_crash(row: &Row<'_>) -> rusqlite::Result<CrashRecord> {
+    row_to_crash_at(row, 0)
+}
+
+fn row_to_crash_at(row: &Row<'_>, offset: usize) -> rusqlite::Result<CrashRecord> {
     Ok(CrashRecord {
-        id: row.get(0)?,
-        timestamp: row.get(1)?,
-        os: row.get(2)?,
-        source: row.get(3)?,
-        crash_type: row.get(4)?,
-        code: row.get(5)?,
-        summary: row.get(6)?,
-        suspected_component: row.get(7)?,
-        raw_path: row.get(8)?,
-        source_host: row.get(9)?,
-        imported: row.get::<_, i64>(10)? != 0,
+        id: row.get(offset)?,
+        timestamp: row.get(offset + 1)?,
+        os: row.get(offset + 2)?,
+        source: row.get(offset + 3)?,
+        crash_type: row.get(offset + 4)?,
+        code: row.get(offset + 5)?,
+        summary: row.get(offset + 6)?,
+        suspected_component: row.get(offset + 7)?,
+        raw_path: row.get(offset + 8)?,
+        source_host: row.get(offset + 9)?,
+        imported: row.get::<_, i64>(offset + 10)? != 0,
+        details: row.get(offset + 11)?,
     })
 }
 
+/// Raw records are zlib-compressed; rendered XML and JSON lines shrink several times over.
+fn compress_payload(payload: &str) -> Option<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(payload.as_bytes()).ok()?;
+    encoder.finish().ok()
+}
+
+fn decompress_payload(blob: &[u8]) -> Result<String, String> {
+    let mut payload = String::new();
+    ZlibDecoder::new(blob)
+        .read_to_string(&mut payload)
+        .map_err(|e| format!("Failed to decompress raw event payload: {e}"))?;
+    Ok(payload)
+}
+
 pub fn save_local_events(events: &[NormalizedEvent]) -> Result<(), String> {
+    upsert_events(events.iter().map(|event| (event, 1, None)), None)
+}
+
+/// Stores runs collapsed at ingest as one row each. A run of one is stored like any event.
+pub fn save_repeated_events(runs: &[RepeatedEvent]) -> Result<(), String> {
+    upsert_events(
+        runs.iter().map(|run| {
+            let last = (run.repeat_count > 1).then_some(run.last_timestamp.as_str());
+            (&run.event, run.repeat_count, last)
+        }),
+        None,
+    )
+}
+
+/// Stores events read back from the archive, marked with `rehydrated_at` so retention holds
+/// them. Events already in the database are left as they are.
+pub fn save_rehydrated_events(
+    events: &[NormalizedEvent],
+    rehydrated_at: &str,
+) -> Result<(), String> {
+    upsert_events(
+        events.iter().map(|event| (event, 1, None)),
+        timestamp_ms(rehydrated_at),
+    )
+}
+
+/// Upserts events with their repeat count and, for collapsed runs, the last timestamp. A row
+/// saved again keeps the larger count, so re-syncing a window does not undo collapsing.
+/// `rehydrated_at_ms` only applies to rows that are new.
+fn upsert_events<'a>(
+    rows: impl Iterator<Item = (&'a NormalizedEvent, u32, Option<&'a str>)>,
+    rehydrated_at_ms: Option<i64>,
+) -> Result<(), String> {
     let mut conn = open_connection()?;
     let tx = conn
         .transaction()
         .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
 
-    for event in events {
+    for (event, repeat_count, last_timestamp) in rows {
         tx.execute(
             "
-            INSERT INTO events (id, timestamp, os, log_name, category, provider, event_id, severity, message, source_host, imported)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 0)
+            INSERT INTO events (id, timestamp, os, log_name, category, provider, event_id, severity, message, source_host, imported, raw_payload,
+                                user_sid, user_name, computer, process_id, thread_id, keywords, opcode, task, metadata, timestamp_ms,
+                                repeat_count, last_timestamp, rehydrated_at_ms)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25)
             ON CONFLICT(id) DO UPDATE SET
                 timestamp=excluded.timestamp,
+                timestamp_ms=excluded.timestamp_ms,
+                repeat_count=MAX(events.repeat_count, excluded.repeat_count),
+                last_timestamp=COALESCE(excluded.last_timestamp, events.last_timestamp),
                 os=excluded.os,
                 log_name=excluded.log_name,
                 category=excluded.category,
@@ -145,7 +1195,17 @@ pub fn save_local_events(events: &[NormalizedEvent]) -> Result<(), String> {
                 event_id=excluded.event_id,
                 severity=excluded.severity,
                 message=excluded.message,
-                source_host=excluded.source_host
+                source_host=excluded.source_host,
+                raw_payload=COALESCE(excluded.raw_payload, events.raw_payload),
+                user_sid=excluded.user_sid,
+                user_name=excluded.user_name,
+                computer=excluded.computer,
+                process_id=excluded.process_id,
+                thread_id=excluded.thread_id,
+                keywords=excluded.keywords,
+                opcode=excluded.opcode,
+                task=excluded.task,
+                metadata=excluded.metadata
             ",
             params![
                 event.id,
@@ -158,6 +1218,24 @@ pub fn save_local_events(events: &[NormalizedEvent]) -> Result<(), String> {
                 event.severity,
                 event.message,
                 event.source_host,
+                if event.imported { 1 } else { 0 },
+                event.raw_payload.as_deref().and_then(compress_payload),
+                event.user_sid,
+                event.user_name,
+                event.computer,
+                event.process_id,
+                event.thread_id,
+                event.keywords,
+                event.opcode,
+                event.task,
+                event
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| serde_json::to_string(metadata).ok()),
+                timestamp_ms(event.timestamp.as_str()),
+                repeat_count,
+                last_timestamp,
+                rehydrated_at_ms,
             ],
         )
         .map_err(|e| format!("Failed to upsert event: {e}"))?;
@@ -173,12 +1251,12 @@ pub fn get_local_events(limit: u32, host: Option<&str>) -> Result<Vec<Normalized
     let conn = open_connection()?;
     
     let query = if host.is_some() {
-        "SELECT id, timestamp, os, log_name, category, provider, event_id, severity, message, source_host, imported FROM events WHERE source_host = ?1 ORDER BY timestamp DESC LIMIT ?2"
+        format!("SELECT {} FROM events WHERE source_host = ?1 ORDER BY timestamp_ms DESC LIMIT ?2", event_columns(None))
     } else {
-        "SELECT id, timestamp, os, log_name, category, provider, event_id, severity, message, source_host, imported FROM events ORDER BY timestamp DESC LIMIT ?1"
+        format!("SELECT {} FROM events ORDER BY timestamp_ms DESC LIMIT ?1", event_columns(None))
     };
 
-    let mut stmt = conn.prepare(query).map_err(|e| format!("Failed to prepare query: {e}"))?;
+    let mut stmt = conn.prepare(query.as_str()).map_err(|e| format!("Failed to prepare query: {e}"))?;
 
     let rows = if let Some(h) = host {
         stmt.query_map(params![h, limit], row_to_event)
@@ -194,32 +1272,336 @@ pub fn get_local_events(limit: u32, host: Option<&str>) -> Result<Vec<Normalized
     Ok(events)
 }
 
-pub fn get_local_events_range(from: &str, to: &str, limit: u32, host: Option<&str>) -> Result<Vec<NormalizedEvent>, String> {
+/// Loads the given events, skipping ids that are no longer stored, oldest first.
+pub fn get_events_by_ids(ids: &[String]) -> Result<Vec<NormalizedEvent>, String> {
     let conn = open_connection()?;
-    
-    let query = if host.is_some() {
-        "SELECT id, timestamp, os, log_name, category, provider, event_id, severity, message, source_host, imported FROM events WHERE julianday(timestamp) >= julianday(?1) AND julianday(timestamp) <= julianday(?2) AND source_host = ?3 ORDER BY timestamp DESC LIMIT ?4"
-    } else {
-        "SELECT id, timestamp, os, log_name, category, provider, event_id, severity, message, source_host, imported FROM events WHERE julianday(timestamp) >= julianday(?1) AND julianday(timestamp) <= julianday(?2) ORDER BY timestamp DESC LIMIT ?3"
-    };
-
-    let mut stmt = conn.prepare(query).map_err(|e| format!("Failed to prepare range query: {e}"))?;
-
-    let rows = if let Some(h) = host {
-        stmt.query_map(params![from, to, h, limit], row_to_event)
-    } else {
-        stmt.query_map(params![from, to, limit], row_to_event)
-    }.map_err(|e| format!("Failed to execute range query: {e}"))?;
+    let mut stmt = conn
+        .prepare(format!("SELECT {} FROM events WHERE id = ?1", event_columns(None)).as_str())
+        .map_err(|e| format!("Failed to prepare event lookup: {e}"))?;
 
-    let mut events = Vec::new();
-    for row in rows {
-        events.push(row.map_err(|e| format!("Failed to parse range row: {e}"))?);
+    let mut events = Vec::with_capacity(ids.len());
+    for id in ids {
+        match stmt.query_row(params![id], row_to_event) {
+            Ok(event) => events.push(event),
+            Err(rusqlite::Error::QueryReturnedNoRows) => {}
+            Err(e) => return Err(format!("Failed to read event {id}: {e}")),
+        }
     }
+    events.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
+    Ok(events)
+}
 
-    Ok(dedupe_events(events))
+/// The original record stored for an event, or `None` when its source kept none.
+pub fn get_event_raw(id: &str) -> Result<Option<String>, String> {
+    let conn = open_connection()?;
+    let blob = match conn.query_row(
+        "SELECT raw_payload FROM events WHERE id = ?1",
+        params![id],
+        |row| row.get::<_, Option<Vec<u8>>>(0),
+    ) {
+        Ok(blob) => blob,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Err(format!("Event {id} is not stored.")),
+        Err(e) => return Err(format!("Failed to read raw event payload: {e}")),
+    };
+    blob.as_deref().map(decompress_payload).transpose()
 }
 
-pub fn get_local_events_window(
+/// Distinct log names stored for a host, for validating filters.
+pub fn get_log_names(host: &str) -> Result<Vec<String>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare("SELECT DISTINCT log_name FROM events WHERE source_host = ?1 ORDER BY log_name ASC")
+        .map_err(|e| format!("Failed to prepare log name query: {e}"))?;
+    let rows = stmt
+        .query_map(params![host], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Failed to execute log name query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse log name row: {e}"))
+}
+
+/// Every host with stored events or crashes, most recently seen first.
+pub fn get_source_hosts() -> Result<Vec<SourceHostSummary>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT source_host, SUM(events), SUM(crashes), MAX(last_seen) FROM (
+                SELECT source_host, COUNT(*) AS events, 0 AS crashes, MAX(timestamp) AS last_seen
+                FROM events GROUP BY source_host
+                UNION ALL
+                SELECT source_host, 0, COUNT(*), MAX(timestamp)
+                FROM crashes GROUP BY source_host
+            )
+            GROUP BY source_host
+            ORDER BY MAX(last_seen) DESC, source_host ASC
+            "#,
+        )
+        .map_err(|e| format!("Failed to prepare source host query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(SourceHostSummary {
+                host: row.get(0)?,
+                event_count: row.get::<_, i64>(1)?.max(0) as u64,
+                crash_count: row.get::<_, i64>(2)?.max(0) as u64,
+                last_seen: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute source host query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse source host row: {e}"))
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[derive(Default)]
+struct FilterSql {
+    clauses: Vec<String>,
+    values: Vec<rusqlite::types::Value>,
+}
+
+impl FilterSql {
+    fn bind(&mut self, value: impl Into<String>) -> String {
+        self.values.push(rusqlite::types::Value::Text(value.into()));
+        format!("?{}", self.values.len())
+    }
+
+    fn bind_timestamp(&mut self, value: &str) -> String {
+        self.values.push(timestamp_ms(value).map_or(rusqlite::types::Value::Null, rusqlite::types::Value::Integer));
+        format!("?{}", self.values.len())
+    }
+}
+
+/// WHERE clause and bound values for an event filter. Every value is bound, never interpolated.
+fn event_filter_clause(filter: &EventQueryFilter, host: Option<&str>) -> (String, Vec<rusqlite::types::Value>) {
+    let mut sql = FilterSql::default();
+    if let Some(host) = host {
+        let host = sql.bind(host);
+        sql.clauses.push(format!("source_host = {host}"));
+    }
+    if let Some(text) = filter.text.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        let pattern = format!("%{}%", escape_like(text));
+        let message = sql.bind(pattern.as_str());
+        let provider = sql.bind(pattern);
+        sql.clauses.push(format!("(message LIKE {message} ESCAPE '\\' OR provider LIKE {provider} ESCAPE '\\')"));
+    }
+    if !filter.severities.is_empty() {
+        let placeholders = filter.severities.iter().map(|severity| sql.bind(severity.as_str())).collect::<Vec<_>>().join(", ");
+        sql.clauses.push(format!("severity IN ({placeholders})"));
+    }
+    if let Some(log_name) = filter.log_name.as_deref() {
+        let log_name = sql.bind(log_name);
+        sql.clauses.push(format!("log_name = {log_name} COLLATE NOCASE"));
+    }
+    if let Some(category) = filter.category.as_deref() {
+        let category = sql.bind(category);
+        sql.clauses.push(format!("category = {category}"));
+    }
+    if let Some(event_id) = filter.event_id {
+        sql.values.push(rusqlite::types::Value::Integer(i64::from(event_id)));
+        sql.clauses.push(format!("event_id = ?{}", sql.values.len()));
+    }
+    if let Some(provider) = filter.provider.as_deref() {
+        let provider = sql.bind(provider);
+        sql.clauses.push(format!("provider = {provider} COLLATE NOCASE"));
+    }
+    if !filter.providers.is_empty() {
+        let placeholders = filter.providers.iter().map(|provider| sql.bind(provider.as_str())).collect::<Vec<_>>().join(", ");
+        sql.clauses.push(format!("provider COLLATE NOCASE IN ({placeholders})"));
+    }
+    if let Some(start) = filter.start.as_deref() {
+        let start = sql.bind_timestamp(start);
+        sql.clauses.push(format!("timestamp_ms >= {start}"));
+    }
+    if let Some(end) = filter.end.as_deref() {
+        let end = sql.bind_timestamp(end);
+        sql.clauses.push(format!("timestamp_ms <= {end}"));
+    }
+
+    let clause = if sql.clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", sql.clauses.join(" AND "))
+    };
+    (clause, sql.values)
+}
+
+/// Streams every event matching `filter` in timestamp order without loading them into memory.
+/// The count and the rows are read in one transaction so `start` sees the exact total.
+pub fn stream_matching_events<S>(
+    filter: &EventQueryFilter,
+    host: Option<&str>,
+    start: impl FnOnce(usize) -> Result<S, String>,
+    mut write: impl FnMut(&mut S, NormalizedEvent) -> Result<(), String>,
+) -> Result<(S, usize), String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+    let (clause, values) = event_filter_clause(filter, host);
+
+    let total: i64 = tx
+        .query_row(
+            format!("SELECT COUNT(*) FROM events{clause}").as_str(),
+            rusqlite::params_from_iter(values.iter()),
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count matching events: {e}"))?;
+    let mut sink = start(total.max(0) as usize)?;
+
+    let mut written = 0usize;
+    {
+        let mut stmt = tx
+            .prepare(format!("SELECT {} FROM events{clause} ORDER BY timestamp_ms ASC", event_columns(None)).as_str())
+            .map_err(|e| format!("Failed to prepare event export query: {e}"))?;
+        let mut rows = stmt
+            .query(rusqlite::params_from_iter(values.iter()))
+            .map_err(|e| format!("Failed to execute event export query: {e}"))?;
+        while let Some(row) = rows.next().map_err(|e| format!("Failed to read event row: {e}"))? {
+            let event = row_to_event(row).map_err(|e| format!("Failed to parse event row: {e}"))?;
+            write(&mut sink, event)?;
+            written += 1;
+        }
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to finish event export query: {e}"))?;
+    Ok((sink, written))
+}
+
+/// Visits every event matching `filter` in timestamp order, with its raw payload attached, so
+/// rule scans can read fields the normalized columns do not keep. Returns how many were visited.
+pub fn for_each_event_with_raw(
+    filter: &EventQueryFilter,
+    host: Option<&str>,
+    mut visit: impl FnMut(NormalizedEvent) -> Result<(), String>,
+) -> Result<usize, String> {
+    let conn = open_connection()?;
+    let (clause, values) = event_filter_clause(filter, host);
+    let mut stmt = conn
+        .prepare(format!("SELECT {}, raw_payload FROM events{clause} ORDER BY timestamp_ms ASC", event_columns(None)).as_str())
+        .map_err(|e| format!("Failed to prepare event scan query: {e}"))?;
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(values.iter()))
+        .map_err(|e| format!("Failed to execute event scan query: {e}"))?;
+    let mut visited = 0usize;
+    while let Some(row) = rows.next().map_err(|e| format!("Failed to read event row: {e}"))? {
+        let mut event = row_to_event(row).map_err(|e| format!("Failed to parse event row: {e}"))?;
+        let blob: Option<Vec<u8>> = row
+            .get(EVENT_COLUMNS.len())
+            .map_err(|e| format!("Failed to read raw event payload: {e}"))?;
+        event.raw_payload = blob.as_deref().map(decompress_payload).transpose()?;
+        visit(event)?;
+        visited += 1;
+    }
+    Ok(visited)
+}
+
+/// Visits every event matching `filter` in timestamp order as a run, carrying the repeat count
+/// and last timestamp of rows collapsed at ingest. Returns how many rows were visited.
+pub fn for_each_event_run(
+    filter: &EventQueryFilter,
+    host: Option<&str>,
+    mut visit: impl FnMut(RepeatedEvent) -> Result<(), String>,
+) -> Result<usize, String> {
+    let conn = open_connection()?;
+    let (clause, values) = event_filter_clause(filter, host);
+    let mut stmt = conn
+        .prepare(format!("SELECT {}, repeat_count, last_timestamp FROM events{clause} ORDER BY timestamp_ms ASC", event_columns(None)).as_str())
+        .map_err(|e| format!("Failed to prepare repeated event query: {e}"))?;
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(values.iter()))
+        .map_err(|e| format!("Failed to execute repeated event query: {e}"))?;
+    let mut visited = 0usize;
+    while let Some(row) = rows.next().map_err(|e| format!("Failed to read event row: {e}"))? {
+        let event = row_to_event(row).map_err(|e| format!("Failed to parse event row: {e}"))?;
+        let repeat_count: i64 = row
+            .get(EVENT_COLUMNS.len())
+            .map_err(|e| format!("Failed to read event repeat count: {e}"))?;
+        let last_timestamp: Option<String> = row
+            .get(EVENT_COLUMNS.len() + 1)
+            .map_err(|e| format!("Failed to read event last timestamp: {e}"))?;
+        visit(RepeatedEvent {
+            repeat_count: repeat_count.clamp(1, i64::from(u32::MAX)) as u32,
+            first_timestamp: event.timestamp.clone(),
+            last_timestamp: last_timestamp.unwrap_or_else(|| event.timestamp.clone()),
+            event,
+        })?;
+        visited += 1;
+    }
+    Ok(visited)
+}
+
+/// SQLite virtual machine steps between checks of a regex search's deadline.
+const REGEX_SEARCH_PROGRESS_STEPS: i32 = 10_000;
+
+/// Events whose message matches `pattern`, newest first. A scan still running after
+/// `time_limit` is interrupted and the events found so far are returned with `true`.
+pub fn search_events_regex(pattern: &str, host: Option<&str>, limit: usize, time_limit: Duration) -> Result<(Vec<NormalizedEvent>, bool), String> {
+    let conn = open_connection()?;
+    let deadline = Instant::now() + time_limit;
+    conn.progress_handler(REGEX_SEARCH_PROGRESS_STEPS, Some(move || Instant::now() >= deadline));
+    let result = collect_regex_matches(&conn, pattern, host, limit);
+    // The connection goes back to the pool; later queries must not inherit the deadline.
+    conn.progress_handler(0, None::<fn() -> bool>);
+    result
+}
+
+fn collect_regex_matches(conn: &Connection, pattern: &str, host: Option<&str>, limit: usize) -> Result<(Vec<NormalizedEvent>, bool), String> {
+    let host_clause = if host.is_some() { "source_host = ?3 AND " } else { "" };
+    let mut stmt = conn
+        .prepare(format!("SELECT {} FROM events WHERE {host_clause}message REGEXP ?1 ORDER BY timestamp_ms DESC LIMIT ?2", event_columns(None)).as_str())
+        .map_err(|e| format!("Failed to prepare regex search query: {e}"))?;
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let mut rows = match host {
+        Some(host) => stmt.query(params![pattern, limit, host]),
+        None => stmt.query(params![pattern, limit]),
+    }
+    .map_err(|e| format!("Failed to execute regex search query: {e}"))?;
+    let mut events = Vec::new();
+    loop {
+        match rows.next() {
+            Ok(Some(row)) => events.push(row_to_event(row).map_err(|e| format!("Failed to parse event row: {e}"))?),
+            Ok(None) => return Ok((events, false)),
+            Err(rusqlite::Error::SqliteFailure(error, _)) if error.code == ErrorCode::OperationInterrupted => {
+                return Ok((events, true))
+            }
+            Err(e) => return Err(format!("Failed to read event row: {e}")),
+        }
+    }
+}
+
+pub fn get_local_events_range(from: &str, to: &str, limit: u32, host: Option<&str>) -> Result<Vec<NormalizedEvent>, String> {
+    let conn = open_connection()?;
+    
+    let query = if host.is_some() {
+        format!("SELECT {} FROM events WHERE timestamp_ms >= ?1 AND timestamp_ms <= ?2 AND source_host = ?3 ORDER BY timestamp_ms DESC LIMIT ?4", event_columns(None))
+    } else {
+        format!("SELECT {} FROM events WHERE timestamp_ms >= ?1 AND timestamp_ms <= ?2 ORDER BY timestamp_ms DESC LIMIT ?3", event_columns(None))
+    };
+
+    let mut stmt = conn.prepare(query.as_str()).map_err(|e| format!("Failed to prepare range query: {e}"))?;
+
+    let rows = if let Some(h) = host {
+        stmt.query_map(params![timestamp_ms(from), timestamp_ms(to), h, limit], row_to_event)
+    } else {
+        stmt.query_map(params![timestamp_ms(from), timestamp_ms(to), limit], row_to_event)
+    }.map_err(|e| format!("Failed to execute range query: {e}"))?;
+
+    let mut events = Vec::new();
+    for row in rows {
+        events.push(row.map_err(|e| format!("Failed to parse range row: {e}"))?);
+    }
+
+    Ok(dedupe_events(events))
+}
+
+pub fn get_local_events_window(
     from: &str,
     to: &str,
     limit: u32,
@@ -228,19 +1610,19 @@ pub fn get_local_events_window(
     let conn = open_connection()?;
 
     let query = if host.is_some() {
-        "SELECT id, timestamp, os, log_name, category, provider, event_id, severity, message, source_host, imported FROM events WHERE julianday(timestamp) >= julianday(?1) AND julianday(timestamp) <= julianday(?2) AND source_host = ?3 ORDER BY timestamp DESC LIMIT ?4"
+        format!("SELECT {} FROM events WHERE timestamp_ms >= ?1 AND timestamp_ms <= ?2 AND source_host = ?3 ORDER BY timestamp_ms DESC LIMIT ?4", event_columns(None))
     } else {
-        "SELECT id, timestamp, os, log_name, category, provider, event_id, severity, message, source_host, imported FROM events WHERE julianday(timestamp) >= julianday(?1) AND julianday(timestamp) <= julianday(?2) ORDER BY timestamp DESC LIMIT ?3"
+        format!("SELECT {} FROM events WHERE timestamp_ms >= ?1 AND timestamp_ms <= ?2 ORDER BY timestamp_ms DESC LIMIT ?3", event_columns(None))
     };
 
     let mut stmt = conn
-        .prepare(query)
+        .prepare(query.as_str())
         .map_err(|e| format!("Failed to prepare window query: {e}"))?;
 
     let rows = if let Some(h) = host {
-        stmt.query_map(params![from, to, h, limit], row_to_event)
+        stmt.query_map(params![timestamp_ms(from), timestamp_ms(to), h, limit], row_to_event)
     } else {
-        stmt.query_map(params![from, to, limit], row_to_event)
+        stmt.query_map(params![timestamp_ms(from), timestamp_ms(to), limit], row_to_event)
     }
     .map_err(|e| format!("Failed to execute window query: {e}"))?;
 
@@ -261,10 +1643,11 @@ pub fn save_crashes(crashes: &[CrashRecord]) -> Result<(), String> {
     for crash in crashes {
         tx.execute(
             "
-            INSERT INTO crashes (id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            INSERT INTO crashes (id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported, details, timestamp_ms)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
             ON CONFLICT(id) DO UPDATE SET
                 timestamp=excluded.timestamp,
+                timestamp_ms=excluded.timestamp_ms,
                 os=excluded.os,
                 source=excluded.source,
                 crash_type=excluded.crash_type,
@@ -273,7 +1656,8 @@ pub fn save_crashes(crashes: &[CrashRecord]) -> Result<(), String> {
                 suspected_component=excluded.suspected_component,
                 raw_path=excluded.raw_path,
                 source_host=excluded.source_host,
-                imported=excluded.imported
+                imported=excluded.imported,
+                details=excluded.details
             ",
             params![
                 crash.id,
@@ -287,6 +1671,8 @@ pub fn save_crashes(crashes: &[CrashRecord]) -> Result<(), String> {
                 crash.raw_path,
                 crash.source_host,
                 if crash.imported { 1 } else { 0 },
+                crash.details,
+                timestamp_ms(crash.timestamp.as_str()),
             ],
         )
         .map_err(|e| format!("Failed to upsert crash: {e}"))?;
@@ -302,9 +1688,9 @@ pub fn get_crashes(limit: u32, host: Option<&str>) -> Result<Vec<CrashRecord>, S
     let conn = open_connection()?;
     
     let query = if host.is_some() {
-        "SELECT id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported FROM crashes WHERE source_host = ?1 ORDER BY timestamp DESC LIMIT ?2"
+        "SELECT id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported, details FROM crashes WHERE source_host = ?1 ORDER BY timestamp_ms DESC LIMIT ?2"
     } else {
-        "SELECT id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported FROM crashes ORDER BY timestamp DESC LIMIT ?1"
+        "SELECT id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported, details FROM crashes ORDER BY timestamp_ms DESC LIMIT ?1"
     };
 
     let mut stmt = conn.prepare(query).map_err(|e| format!("Failed to prepare crash query: {e}"))?;
@@ -327,7 +1713,7 @@ pub fn get_crash_by_id(crash_id: &str) -> Result<Option<CrashRecord>, String> {
     let conn = open_connection()?;
     let mut stmt = conn
         .prepare(
-            "SELECT id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported FROM crashes WHERE id = ?1 LIMIT 1",
+            "SELECT id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported, details FROM crashes WHERE id = ?1 LIMIT 1",
         )
         .map_err(|e| format!("Failed to prepare crash-by-id query: {e}"))?;
 
@@ -338,27 +1724,392 @@ pub fn get_crash_by_id(crash_id: &str) -> Result<Option<CrashRecord>, String> {
     }
 }
 
+/// Events matching `filter` and crashes in the same window, merged oldest first by one query.
+/// Crashes only honour the filter's host, time bounds and text, since they carry no severity,
+/// log or provider; `include_crashes` leaves them out entirely.
+pub fn get_timeline(
+    filter: &EventQueryFilter,
+    host: Option<&str>,
+    include_crashes: bool,
+    limit: u32,
+) -> Result<RecordTimeline, String> {
+    let conn = open_connection()?;
+    let (event_clause, values) = event_filter_clause(filter, host);
+    let mut sql = FilterSql { clauses: Vec::new(), values };
+    if !include_crashes {
+        sql.clauses.push("0".to_string());
+    }
+    if let Some(host) = host {
+        let host = sql.bind(host);
+        sql.clauses.push(format!("source_host = {host}"));
+    }
+    if let Some(text) = filter.text.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        let pattern = format!("%{}%", escape_like(text));
+        let summary = sql.bind(pattern.as_str());
+        let component = sql.bind(pattern);
+        sql.clauses.push(format!("(summary LIKE {summary} ESCAPE '\\' OR suspected_component LIKE {component} ESCAPE '\\')"));
+    }
+    if let Some(start) = filter.start.as_deref() {
+        let start = sql.bind_timestamp(start);
+        sql.clauses.push(format!("timestamp_ms >= {start}"));
+    }
+    if let Some(end) = filter.end.as_deref() {
+        let end = sql.bind_timestamp(end);
+        sql.clauses.push(format!("timestamp_ms <= {end}"));
+    }
+    let crash_clause = if sql.clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", sql.clauses.join(" AND "))
+    };
+    // Both selects share one column layout: the kind, then the record's own columns, with
+    // crashes padded out to the width of an event row.
+    sql.values.push(rusqlite::types::Value::Integer(i64::from(limit) + 1));
+    let query = format!(
+        "SELECT * FROM (
+            SELECT 'event' AS kind, {}, timestamp_ms FROM events{event_clause}
+            UNION ALL
+            SELECT 'crash', id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, source_host, imported, details, {}, timestamp_ms FROM crashes{crash_clause}
+        ) ORDER BY timestamp_ms ASC, kind ASC LIMIT ?{}",
+        event_columns(None),
+        ["NULL"; EVENT_COLUMNS.len() - 12].join(", "),
+        sql.values.len()
+    );
+
+    let mut stmt = conn.prepare(query.as_str()).map_err(|e| format!("Failed to prepare timeline query: {e}"))?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(sql.values.iter()), |row| {
+            if row.get::<_, String>(0)? == "crash" {
+                row_to_crash_at(row, 1).map(RecordTimelineEntry::Crash)
+            } else {
+                row_to_event_at(row, 1).map(RecordTimelineEntry::Event)
+            }
+        })
+        .map_err(|e| format!("Failed to execute timeline query: {e}"))?;
+
+    let mut timeline = RecordTimeline::default();
+    for row in rows {
+        timeline.entries.push(row.map_err(|e| format!("Failed to parse timeline row: {e}"))?);
+    }
+    if timeline.entries.len() > limit as usize {
+        timeline.entries.truncate(limit as usize);
+        timeline.truncated = true;
+    }
+    Ok(timeline)
+}
+
 pub fn prune_events_before(cutoff: &str) -> Result<usize, String> {
     let conn = open_connection()?;
     let deleted = conn
         .execute(
-            "DELETE FROM events WHERE julianday(timestamp) < julianday(?1)",
-            [cutoff],
+            "DELETE FROM events WHERE timestamp_ms < ?1 AND rehydrated_at_ms IS NULL",
+            params![timestamp_ms(cutoff)],
         )
         .map_err(|e| format!("Failed to prune events: {e}"))?;
     Ok(deleted)
 }
 
+/// Bytes held by live pages. Pages freed by pruning are reused by SQLite, so this, not the file
+/// size, is what grows as events are stored.
+pub fn database_used_bytes() -> Result<u64, String> {
+    let conn = open_connection()?;
+    let pragma = |name: &str| -> Result<u64, String> {
+        conn.query_row(format!("PRAGMA {name}").as_str(), [], |row| row.get::<_, i64>(0))
+            .map(|value| value.max(0) as u64)
+            .map_err(|e| format!("Failed to read database {name}: {e}"))
+    };
+    let pages = pragma("page_count")?.saturating_sub(pragma("freelist_count")?);
+    Ok(pages * pragma("page_size")?)
+}
+
+/// Deletes the oldest `share` (0..=1) of stored information-level events.
+pub fn prune_oldest_information_events(share: f64) -> Result<usize, String> {
+    let conn = open_connection()?;
+    let total: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM events WHERE severity = 'information' AND rehydrated_at_ms IS NULL",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count information events: {e}"))?;
+    let limit = (total as f64 * share.clamp(0.0, 1.0)).ceil() as i64;
+    if limit <= 0 {
+        return Ok(0);
+    }
+    let deleted = conn
+        .execute(
+            "
+            DELETE FROM events
+            WHERE id IN (
+                SELECT id FROM events
+                WHERE severity = 'information' AND rehydrated_at_ms IS NULL
+                ORDER BY timestamp_ms ASC
+                LIMIT ?1
+            )
+            ",
+            params![limit],
+        )
+        .map_err(|e| format!("Failed to prune information events: {e}"))?;
+    Ok(deleted)
+}
+
+/// Deletes rehydrated events restored before `cutoff`. They are still in the archive, so they
+/// are not archived again.
+pub fn release_rehydrated_events(cutoff: &str) -> Result<usize, String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "DELETE FROM events WHERE rehydrated_at_ms < ?1",
+        params![timestamp_ms(cutoff)],
+    )
+    .map_err(|e| format!("Failed to release rehydrated events: {e}"))
+}
+
+pub fn prune_crashes_before(cutoff: &str) -> Result<usize, String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "DELETE FROM crashes WHERE timestamp_ms < ?1",
+        params![timestamp_ms(cutoff)],
+    )
+    .map_err(|e| format!("Failed to prune crashes: {e}"))
+}
+
+/// Events retention may delete, which leaves out rehydrated events still in their hold.
+pub fn count_events() -> Result<u64, String> {
+    let conn = open_connection()?;
+    conn.query_row(
+        "SELECT COUNT(*) FROM events WHERE rehydrated_at_ms IS NULL",
+        [],
+        |row| row.get::<_, i64>(0),
+    )
+        .map(|count| count.max(0) as u64)
+        .map_err(|e| format!("Failed to count events: {e}"))
+}
+
+/// Deletes the `count` oldest events regardless of severity.
+pub fn prune_oldest_events(count: u64) -> Result<usize, String> {
+    if count == 0 {
+        return Ok(0);
+    }
+    let conn = open_connection()?;
+    conn.execute(
+        "
+        DELETE FROM events
+        WHERE id IN (
+            SELECT id FROM events
+            WHERE rehydrated_at_ms IS NULL
+            ORDER BY timestamp_ms ASC
+            LIMIT ?1
+        )
+        ",
+        params![count.min(i64::MAX as u64) as i64],
+    )
+    .map_err(|e| format!("Failed to prune oldest events: {e}"))
+}
+
+/// Visits events oldest first, in the order the prune functions delete them: those before
+/// `cutoff` when given, at most `limit` when given. Returns how many were visited.
+pub fn for_each_oldest_event(
+    cutoff: Option<&str>,
+    limit: Option<u64>,
+    mut visit: impl FnMut(NormalizedEvent) -> Result<(), String>,
+) -> Result<usize, String> {
+    let cutoff_ms = match cutoff {
+        // Like `prune_events_before`, a cutoff that cannot be read matches nothing.
+        Some(cutoff) => match timestamp_ms(cutoff) {
+            Some(ms) => Some(ms),
+            None => return Ok(0),
+        },
+        None => None,
+    };
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            format!(
+                "SELECT {} FROM events WHERE (?1 IS NULL OR timestamp_ms < ?1) AND rehydrated_at_ms IS NULL ORDER BY timestamp_ms ASC LIMIT ?2",
+                event_columns(None)
+            )
+            .as_str(),
+        )
+        .map_err(|e| format!("Failed to prepare oldest event query: {e}"))?;
+    // SQLite reads a negative LIMIT as no limit.
+    let limit = limit.map_or(-1, |limit| limit.min(i64::MAX as u64) as i64);
+    let mut rows = stmt
+        .query(params![cutoff_ms, limit])
+        .map_err(|e| format!("Failed to execute oldest event query: {e}"))?;
+    let mut visited = 0usize;
+    while let Some(row) = rows.next().map_err(|e| format!("Failed to read event row: {e}"))? {
+        visit(row_to_event(row).map_err(|e| format!("Failed to parse event row: {e}"))?)?;
+        visited += 1;
+    }
+    Ok(visited)
+}
+
+/// Size of the database file in bytes, free pages included.
+pub fn database_file_bytes() -> Result<u64, String> {
+    let conn = open_connection()?;
+    let pragma = |name: &str| -> Result<u64, String> {
+        conn.query_row(format!("PRAGMA {name}").as_str(), [], |row| row.get::<_, i64>(0))
+            .map(|value| value.max(0) as u64)
+            .map_err(|e| format!("Failed to read database {name}: {e}"))
+    };
+    Ok(pragma("page_count")? * pragma("page_size")?)
+}
+
+/// Returns free pages to the file system. Databases already in incremental auto-vacuum mode only
+/// release their free list; older ones are switched to it with a one-off `VACUUM`.
+pub fn reclaim_free_pages() -> Result<(), String> {
+    let conn = open_connection()?;
+    let auto_vacuum: i64 = conn
+        .query_row("PRAGMA auto_vacuum", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read auto_vacuum mode: {e}"))?;
+    if auto_vacuum == 2 {
+        conn.execute_batch("PRAGMA incremental_vacuum;")
+    } else {
+        conn.execute_batch("PRAGMA auto_vacuum = INCREMENTAL; VACUUM;")
+    }
+    .map_err(|e| format!("Failed to reclaim database space: {e}"))
+}
+
+/// Per-table and per-index sizes from SQLite's `dbstat` table, with row counts and timestamp
+/// ranges, largest tables first.
+pub fn get_database_stats() -> Result<DatabaseStats, String> {
+    let path = db_path()?;
+    let conn = open_connection()?;
+    let pragma = |name: &str| -> Result<u64, String> {
+        conn.query_row(format!("PRAGMA {name}").as_str(), [], |row| row.get::<_, i64>(0))
+            .map(|value| value.max(0) as u64)
+            .map_err(|e| format!("Failed to read database {name}: {e}"))
+    };
+    let page_size = pragma("page_size")?;
+    let file_bytes = pragma("page_count")? * page_size;
+    let free_bytes = pragma("freelist_count")? * page_size;
+
+    let mut sizes = conn
+        .prepare("SELECT name, SUM(pgsize) FROM dbstat GROUP BY name")
+        .map_err(|e| format!("Failed to read database page sizes: {e}"))?;
+    let sizes = sizes
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?.max(0) as u64,
+            ))
+        })
+        .map_err(|e| format!("Failed to read database page sizes: {e}"))?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| format!("Failed to read database page sizes: {e}"))?;
+
+    let mut objects = conn
+        .prepare(
+            "
+            SELECT type, name, tbl_name FROM sqlite_master
+            WHERE type = 'index' OR (type = 'table' AND name NOT LIKE 'sqlite_%')
+            ORDER BY name
+            ",
+        )
+        .map_err(|e| format!("Failed to list database tables: {e}"))?;
+    let objects = objects
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to list database tables: {e}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to list database tables: {e}"))?;
+
+    let indexes = objects
+        .iter()
+        .filter(|(kind, _, _)| kind == "index")
+        .map(|(_, name, table)| IndexStats {
+            name: name.clone(),
+            table: table.clone(),
+            bytes: sizes.get(name).copied().unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut tables = Vec::new();
+    for (_, name, _) in objects.iter().filter(|(kind, _, _)| kind == "table") {
+        let rows = conn
+            .query_row(
+                format!("SELECT COUNT(*) FROM \"{name}\"").as_str(),
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map_err(|e| format!("Failed to count {name} rows: {e}"))?;
+        let (oldest, newest) = if table_columns(&conn, name)?.contains("timestamp") {
+            conn.query_row(
+                format!("SELECT MIN(timestamp), MAX(timestamp) FROM \"{name}\"").as_str(),
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| format!("Failed to read {name} time range: {e}"))?
+        } else {
+            (None, None)
+        };
+        tables.push(TableStats {
+            name: name.clone(),
+            rows: rows.max(0) as u64,
+            bytes: sizes.get(name).copied().unwrap_or_default(),
+            index_bytes: indexes
+                .iter()
+                .filter(|index| &index.table == name)
+                .map(|index| index.bytes)
+                .sum(),
+            oldest,
+            newest,
+        });
+    }
+    tables.sort_by(|left, right| {
+        (right.bytes + right.index_bytes).cmp(&(left.bytes + left.index_bytes))
+    });
+
+    let mut wal = path.clone().into_os_string();
+    wal.push("-wal");
+    let wal_bytes = fs::metadata(PathBuf::from(wal))
+        .map(|meta| meta.len())
+        .unwrap_or_default();
+
+    Ok(DatabaseStats {
+        path: path.to_string_lossy().to_string(),
+        file_bytes,
+        wal_bytes,
+        free_bytes,
+        page_size,
+        tables,
+        indexes,
+    })
+}
+
+/// Rebuilds the whole database file with `VACUUM`, after folding in the write-ahead log, and
+/// refreshes the query planner statistics. Blocks other writers while it runs.
+pub fn compact_database() -> Result<CompactionResult, String> {
+    checkpoint()?;
+    let bytes_before = database_file_bytes()?;
+    let conn = open_connection()?;
+    conn.execute_batch("VACUUM; PRAGMA optimize;")
+        .map_err(|e| format!("Failed to compact database: {e}"))?;
+    drop(conn);
+    checkpoint()?;
+    let bytes_after = database_file_bytes()?;
+    Ok(CompactionResult {
+        bytes_before,
+        bytes_after,
+        reclaimed_bytes: bytes_before.saturating_sub(bytes_after),
+    })
+}
+
 pub fn prune_events_outside(start: &str, end: &str) -> Result<usize, String> {
     let conn = open_connection()?;
     let deleted = conn
         .execute(
             "
             DELETE FROM events
-            WHERE julianday(timestamp) < julianday(?1)
-               OR julianday(timestamp) > julianday(?2)
+            WHERE (timestamp_ms < ?1 OR timestamp_ms > ?2)
+              AND rehydrated_at_ms IS NULL
             ",
-            params![start, end],
+            params![timestamp_ms(start), timestamp_ms(end)],
         )
         .map_err(|e| format!("Failed to prune events outside range: {e}"))?;
     Ok(deleted)
@@ -445,16 +2196,20 @@ pub fn correlate_crash_events(
     let conn = open_connection()?;
     let mut stmt = conn
         .prepare(
-            "
-            SELECT e.id, e.timestamp, e.os, e.log_name, e.category, e.provider, e.event_id, e.severity, e.message, e.source_host, e.imported
+            format!(
+                "
+            SELECT {}
             FROM events e
             JOIN crashes c ON c.id = ?1
             WHERE e.os = c.os
               AND e.source_host = c.source_host
-              AND ABS((julianday(e.timestamp) - julianday(c.timestamp)) * 24 * 60) <= ?2
-            ORDER BY ABS((julianday(e.timestamp) - julianday(c.timestamp)) * 24 * 60) ASC, e.timestamp DESC
+              AND e.timestamp_ms BETWEEN c.timestamp_ms - ?2 * 60000 AND c.timestamp_ms + ?2 * 60000
+            ORDER BY ABS(e.timestamp_ms - c.timestamp_ms) ASC, e.timestamp_ms DESC
             LIMIT ?3
             ",
+                event_columns(Some("e"))
+            )
+            .as_str(),
         )
         .map_err(|e| format!("Failed to prepare correlation query: {e}"))?;
 
@@ -469,3 +2224,1525 @@ pub fn correlate_crash_events(
 
     Ok(dedupe_events(events))
 }
+
+/// Buckets per day used for histogram queries ("hour" => 24, "day" => 1).
+pub fn histogram_buckets_per_day(bucket: &str) -> Option<u32> {
+    match bucket.trim().to_ascii_lowercase().as_str() {
+        "hour" | "hourly" => Some(24),
+        "day" | "daily" => Some(1),
+        _ => None,
+    }
+}
+
+pub fn get_event_histogram(
+    from: &str,
+    to: &str,
+    buckets_per_day: u32,
+    host: Option<&str>,
+) -> Result<Vec<EventHistogramBucket>, String> {
+    let conn = open_connection()?;
+
+    // Buckets count whole bucket lengths since the Unix epoch, so day buckets start at midnight UTC.
+    let bucket_ms = 86_400_000 / i64::from(buckets_per_day.max(1));
+    let query = if host.is_some() {
+        "
+        SELECT timestamp_ms / ?3 AS bucket, severity, COUNT(*)
+        FROM events
+        WHERE timestamp_ms >= ?1 AND timestamp_ms <= ?2 AND source_host = ?4
+        GROUP BY bucket, severity
+        ORDER BY bucket ASC
+        "
+    } else {
+        "
+        SELECT timestamp_ms / ?3 AS bucket, severity, COUNT(*)
+        FROM events
+        WHERE timestamp_ms >= ?1 AND timestamp_ms <= ?2
+        GROUP BY bucket, severity
+        ORDER BY bucket ASC
+        "
+    };
+
+    let mut stmt = conn
+        .prepare(query)
+        .map_err(|e| format!("Failed to prepare histogram query: {e}"))?;
+
+    let map_row = |row: &Row<'_>| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+        ))
+    };
+    let rows = if let Some(h) = host {
+        stmt.query_map(params![timestamp_ms(from), timestamp_ms(to), bucket_ms, h], map_row)
+    } else {
+        stmt.query_map(params![timestamp_ms(from), timestamp_ms(to), bucket_ms], map_row)
+    }
+    .map_err(|e| format!("Failed to execute histogram query: {e}"))?;
+
+    let mut grouped: BTreeMap<i64, BTreeMap<String, u64>> = BTreeMap::new();
+    for row in rows {
+        let (bucket, severity, count) =
+            row.map_err(|e| format!("Failed to parse histogram row: {e}"))?;
+        *grouped
+            .entry(bucket)
+            .or_default()
+            .entry(severity)
+            .or_insert(0) += count.max(0) as u64;
+    }
+
+    let mut buckets = Vec::with_capacity(grouped.len());
+    for (bucket, by_severity) in grouped {
+        let Some(start) = DateTime::<Utc>::from_timestamp_millis(bucket * bucket_ms) else {
+            continue;
+        };
+        buckets.push(EventHistogramBucket {
+            bucket_start: start.to_rfc3339(),
+            total: by_severity.values().sum(),
+            by_severity,
+        });
+    }
+
+    Ok(buckets)
+}
+
+pub fn save_metrics(samples: &[MetricSample]) -> Result<(), String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+
+    for sample in samples {
+        tx.execute(
+            "
+            INSERT INTO metrics (timestamp, source_host, source, device, name, value, unit, status)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ",
+            params![
+                sample.timestamp,
+                sample.source_host,
+                sample.source,
+                sample.device,
+                sample.name,
+                sample.value,
+                sample.unit,
+                sample.status,
+            ],
+        )
+        .map_err(|e| format!("Failed to insert metric: {e}"))?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit metric transaction: {e}"))?;
+
+    Ok(())
+}
+
+pub fn get_metrics(
+    source: &str,
+    from: &str,
+    to: &str,
+    limit: u32,
+    host: Option<&str>,
+) -> Result<Vec<MetricSample>, String> {
+    let conn = open_connection()?;
+    let query = if host.is_some() {
+        "
+        SELECT timestamp, source_host, source, device, name, value, unit, status
+        FROM metrics
+        WHERE source = ?1 AND julianday(timestamp) >= julianday(?2) AND julianday(timestamp) <= julianday(?3) AND source_host = ?5
+        ORDER BY timestamp DESC
+        LIMIT ?4
+        "
+    } else {
+        "
+        SELECT timestamp, source_host, source, device, name, value, unit, status
+        FROM metrics
+        WHERE source = ?1 AND julianday(timestamp) >= julianday(?2) AND julianday(timestamp) <= julianday(?3)
+        ORDER BY timestamp DESC
+        LIMIT ?4
+        "
+    };
+
+    let mut stmt = conn
+        .prepare(query)
+        .map_err(|e| format!("Failed to prepare metrics query: {e}"))?;
+
+    let map_row = |row: &Row<'_>| {
+        Ok(MetricSample {
+            timestamp: row.get(0)?,
+            source_host: row.get(1)?,
+            source: row.get(2)?,
+            device: row.get(3)?,
+            name: row.get(4)?,
+            value: row.get(5)?,
+            unit: row.get(6)?,
+            status: row.get(7)?,
+        })
+    };
+    let rows = if let Some(h) = host {
+        stmt.query_map(params![source, from, to, limit, h], map_row)
+    } else {
+        stmt.query_map(params![source, from, to, limit], map_row)
+    }
+    .map_err(|e| format!("Failed to execute metrics query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse metric row: {e}"))
+}
+
+pub fn save_inventory_snapshot(
+    snapshot_id: &str,
+    timestamp: &str,
+    host: &str,
+    items: &[InventoryItem],
+) -> Result<(), String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+
+    tx.execute(
+        "INSERT INTO inventory_snapshots (id, timestamp, source_host, item_count) VALUES (?1, ?2, ?3, ?4)",
+        params![snapshot_id, timestamp, host, items.len() as i64],
+    )
+    .map_err(|e| format!("Failed to insert inventory snapshot: {e}"))?;
+    for item in items {
+        tx.execute(
+            "
+            INSERT INTO inventory_items (snapshot_id, kind, name, display_name, version, state, start_mode)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ",
+            params![
+                snapshot_id,
+                item.kind,
+                item.name,
+                item.display_name,
+                item.version,
+                item.state,
+                item.start_mode,
+            ],
+        )
+        .map_err(|e| format!("Failed to insert inventory item: {e}"))?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit inventory transaction: {e}"))?;
+
+    Ok(())
+}
+
+/// Snapshots inside `[from, to]` plus the last one before `from`, oldest first, so every change in
+/// the range can be diffed against its predecessor.
+pub fn get_inventory_snapshots(
+    from: &str,
+    to: &str,
+    host: &str,
+) -> Result<Vec<InventorySnapshotSummary>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT id, timestamp, source_host, item_count FROM (
+                SELECT id, timestamp, source_host, item_count FROM inventory_snapshots
+                WHERE source_host = ?3 AND julianday(timestamp) < julianday(?1)
+                ORDER BY timestamp DESC
+                LIMIT 1
+            )
+            UNION ALL
+            SELECT id, timestamp, source_host, item_count FROM inventory_snapshots
+            WHERE source_host = ?3 AND julianday(timestamp) >= julianday(?1) AND julianday(timestamp) <= julianday(?2)
+            ORDER BY timestamp ASC
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare inventory snapshot query: {e}"))?;
+
+    let rows = stmt
+        .query_map(params![from, to, host], |row| {
+            Ok(InventorySnapshotSummary {
+                id: row.get(0)?,
+                timestamp: row.get(1)?,
+                source_host: row.get(2)?,
+                item_count: row.get::<_, i64>(3)?.max(0) as usize,
+            })
+        })
+        .map_err(|e| format!("Failed to execute inventory snapshot query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse inventory snapshot row: {e}"))
+}
+
+pub fn get_latest_inventory_timestamp(host: &str) -> Result<Option<String>, String> {
+    let conn = open_connection()?;
+    conn.query_row(
+        "SELECT MAX(timestamp) FROM inventory_snapshots WHERE source_host = ?1",
+        params![host],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .map_err(|e| format!("Failed to read latest inventory snapshot: {e}"))
+}
+
+pub fn get_inventory_items(snapshot_id: &str) -> Result<Vec<InventoryItem>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT kind, name, display_name, version, state, start_mode
+            FROM inventory_items
+            WHERE snapshot_id = ?1
+            ORDER BY kind, name
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare inventory item query: {e}"))?;
+
+    let rows = stmt
+        .query_map(params![snapshot_id], |row| {
+            Ok(InventoryItem {
+                kind: row.get(0)?,
+                name: row.get(1)?,
+                display_name: row.get(2)?,
+                version: row.get(3)?,
+                state: row.get(4)?,
+                start_mode: row.get(5)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute inventory item query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse inventory item row: {e}"))
+}
+
+/// Appends events to an integration's spool in arrival order, skipping ones already queued. When the
+/// spool grows past `max_events` the oldest entries are dropped and counted. Returns the number dropped.
+pub fn enqueue_forward_events(
+    integration_id: &str,
+    events: &[NormalizedEvent],
+    max_events: u32,
+    enqueued_at: &str,
+) -> Result<u64, String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+
+    for event in events {
+        let payload = serde_json::to_string(event)
+            .map_err(|e| format!("Failed to serialize spooled event: {e}"))?;
+        tx.execute(
+            "
+            INSERT OR IGNORE INTO forward_spool (integration_id, event_id, event_timestamp, enqueued_at, payload)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ",
+            params![integration_id, event.id, event.timestamp, enqueued_at, payload],
+        )
+        .map_err(|e| format!("Failed to spool event: {e}"))?;
+    }
+
+    let queued: i64 = tx
+        .query_row(
+            "SELECT COUNT(*) FROM forward_spool WHERE integration_id = ?1",
+            params![integration_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count spooled events: {e}"))?;
+    let overflow = (queued - max_events as i64).max(0);
+    if overflow > 0 {
+        tx.execute(
+            "
+            DELETE FROM forward_spool WHERE seq IN (
+                SELECT seq FROM forward_spool WHERE integration_id = ?1 ORDER BY seq ASC LIMIT ?2
+            )
+            ",
+            params![integration_id, overflow],
+        )
+        .map_err(|e| format!("Failed to trim forwarding spool: {e}"))?;
+        tx.execute(
+            "
+            INSERT INTO forward_state (integration_id, dropped_total) VALUES (?1, ?2)
+            ON CONFLICT(integration_id) DO UPDATE SET dropped_total = dropped_total + excluded.dropped_total
+            ",
+            params![integration_id, overflow],
+        )
+        .map_err(|e| format!("Failed to record dropped spool events: {e}"))?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit spool transaction: {e}"))?;
+
+    Ok(overflow as u64)
+}
+
+/// Oldest spooled events for an integration, with their sequence numbers.
+pub fn peek_forward_events(
+    integration_id: &str,
+    limit: u32,
+) -> Result<Vec<(i64, NormalizedEvent)>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT seq, payload FROM forward_spool
+            WHERE integration_id = ?1
+            ORDER BY seq ASC
+            LIMIT ?2
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare spool query: {e}"))?;
+
+    let rows = stmt
+        .query_map(params![integration_id, limit], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| format!("Failed to execute spool query: {e}"))?;
+
+    let mut events = Vec::new();
+    for row in rows {
+        let (seq, payload) = row.map_err(|e| format!("Failed to parse spool row: {e}"))?;
+        let event = serde_json::from_str::<NormalizedEvent>(payload.as_str())
+            .map_err(|e| format!("Failed to decode spooled event: {e}"))?;
+        events.push((seq, event));
+    }
+    Ok(events)
+}
+
+/// Removes delivered events (everything up to `last_seq`) and records the successful attempt.
+pub fn ack_forward_events(
+    integration_id: &str,
+    last_seq: i64,
+    delivered: u64,
+    last_delivered_timestamp: &str,
+    now: &str,
+) -> Result<(), String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+
+    tx.execute(
+        "DELETE FROM forward_spool WHERE integration_id = ?1 AND seq <= ?2",
+        params![integration_id, last_seq],
+    )
+    .map_err(|e| format!("Failed to remove delivered spool events: {e}"))?;
+    tx.execute(
+        "
+        INSERT INTO forward_state (integration_id, last_attempt_at, last_success_at, last_delivered_timestamp, last_error, consecutive_failures, delivered_total)
+        VALUES (?1, ?2, ?2, ?3, NULL, 0, ?4)
+        ON CONFLICT(integration_id) DO UPDATE SET
+            last_attempt_at = excluded.last_attempt_at,
+            last_success_at = excluded.last_success_at,
+            last_delivered_timestamp = excluded.last_delivered_timestamp,
+            last_error = NULL,
+            consecutive_failures = 0,
+            delivered_total = delivered_total + excluded.delivered_total
+        ",
+        params![integration_id, now, last_delivered_timestamp, delivered as i64],
+    )
+    .map_err(|e| format!("Failed to record forwarding delivery: {e}"))?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit spool transaction: {e}"))?;
+
+    Ok(())
+}
+
+pub fn record_forward_failure(integration_id: &str, error: &str, now: &str) -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "
+        INSERT INTO forward_state (integration_id, last_attempt_at, last_error, consecutive_failures)
+        VALUES (?1, ?2, ?3, 1)
+        ON CONFLICT(integration_id) DO UPDATE SET
+            last_attempt_at = excluded.last_attempt_at,
+            last_error = excluded.last_error,
+            consecutive_failures = consecutive_failures + 1
+        ",
+        params![integration_id, now, error],
+    )
+    .map_err(|e| format!("Failed to record forwarding failure: {e}"))?;
+    Ok(())
+}
+
+pub fn get_forward_spool_state(integration_id: &str) -> Result<ForwardSpoolState, String> {
+    let conn = open_connection()?;
+    let (queued, oldest_queued_timestamp) = conn
+        .query_row(
+            "SELECT COUNT(*), MIN(event_timestamp) FROM forward_spool WHERE integration_id = ?1",
+            params![integration_id],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .map_err(|e| format!("Failed to read forwarding spool depth: {e}"))?;
+
+    let state = conn.query_row(
+        "
+        SELECT last_attempt_at, last_success_at, last_delivered_timestamp, last_error,
+               consecutive_failures, delivered_total, dropped_total
+        FROM forward_state
+        WHERE integration_id = ?1
+        ",
+        params![integration_id],
+        |row| {
+            Ok(ForwardSpoolState {
+                integration_id: integration_id.to_string(),
+                last_attempt_at: row.get(0)?,
+                last_success_at: row.get(1)?,
+                last_delivered_timestamp: row.get(2)?,
+                last_error: row.get(3)?,
+                consecutive_failures: row.get::<_, i64>(4)?.max(0) as u32,
+                delivered_total: row.get::<_, i64>(5)?.max(0) as u64,
+                dropped_total: row.get::<_, i64>(6)?.max(0) as u64,
+                ..ForwardSpoolState::default()
+            })
+        },
+    );
+    let mut state = match state {
+        Ok(state) => state,
+        Err(rusqlite::Error::QueryReturnedNoRows) => ForwardSpoolState {
+            integration_id: integration_id.to_string(),
+            ..ForwardSpoolState::default()
+        },
+        Err(error) => return Err(format!("Failed to read forwarding state: {error}")),
+    };
+    state.queued = queued.max(0) as u64;
+    state.oldest_queued_timestamp = oldest_queued_timestamp;
+    Ok(state)
+}
+
+/// Drops the spool and delivery state of integrations that no longer exist.
+pub fn prune_forward_spools(active_ids: &[String]) -> Result<usize, String> {
+    let conn = open_connection()?;
+    let mut removed = 0;
+    for table in ["forward_spool", "forward_state"] {
+        let mut ids = conn
+            .prepare(format!("SELECT DISTINCT integration_id FROM {table}").as_str())
+            .map_err(|e| format!("Failed to prepare spool cleanup query: {e}"))?
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to execute spool cleanup query: {e}"))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to parse spool cleanup row: {e}"))?;
+        ids.retain(|id| !active_ids.contains(id));
+        for id in ids {
+            removed += conn
+                .execute(format!("DELETE FROM {table} WHERE integration_id = ?1").as_str(), params![id])
+                .map_err(|e| format!("Failed to remove stale spool entries: {e}"))?;
+        }
+    }
+    Ok(removed)
+}
+
+/// Appends to the alert delivery log, keeping only the newest `ALERT_DELIVERY_LOG_LIMIT` rows.
+pub fn record_alert_delivery(delivery: &AlertDelivery) -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "
+        INSERT INTO alert_deliveries (rule_id, rule_name, sent_at, matched, suppressed, success, http_status, error)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        ",
+        params![
+            delivery.rule_id,
+            delivery.rule_name,
+            delivery.sent_at,
+            delivery.matched,
+            delivery.suppressed,
+            delivery.success,
+            delivery.http_status,
+            delivery.error
+        ],
+    )
+    .map_err(|e| format!("Failed to record alert delivery: {e}"))?;
+    conn.execute(
+        "DELETE FROM alert_deliveries WHERE id <= (SELECT MAX(id) FROM alert_deliveries) - ?1",
+        params![ALERT_DELIVERY_LOG_LIMIT],
+    )
+    .map_err(|e| format!("Failed to trim alert delivery log: {e}"))?;
+    Ok(())
+}
+
+pub fn get_alert_deliveries(limit: u32) -> Result<Vec<AlertDelivery>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT id, rule_id, rule_name, sent_at, matched, suppressed, success, http_status, error
+            FROM alert_deliveries
+            ORDER BY id DESC
+            LIMIT ?1
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare alert delivery query: {e}"))?;
+    let rows = stmt
+        .query_map(params![limit], |row| {
+            Ok(AlertDelivery {
+                id: row.get(0)?,
+                rule_id: row.get(1)?,
+                rule_name: row.get(2)?,
+                sent_at: row.get(3)?,
+                matched: row.get(4)?,
+                suppressed: row.get(5)?,
+                success: row.get(6)?,
+                http_status: row.get(7)?,
+                error: row.get(8)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute alert delivery query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse alert delivery row: {e}"))
+}
+
+/// Records Sigma matches, keeping the first detection of each rule and event pair. Returns how
+/// many were new.
+pub fn save_sigma_detections(detections: &[SigmaDetection]) -> Result<usize, String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+    let mut inserted = 0usize;
+    {
+        let mut stmt = tx
+            .prepare(
+                "
+                INSERT OR IGNORE INTO sigma_detections
+                    (rule_id, rule_title, level, event_id, event_timestamp, source_host, provider, event_code, message, detected_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                ",
+            )
+            .map_err(|e| format!("Failed to prepare Sigma detection insert: {e}"))?;
+        for detection in detections {
+            inserted += stmt
+                .execute(params![
+                    detection.rule_id,
+                    detection.rule_title,
+                    detection.level,
+                    detection.event_id,
+                    detection.event_timestamp,
+                    detection.source_host,
+                    detection.provider,
+                    detection.event_code,
+                    detection.message,
+                    detection.detected_at
+                ])
+                .map_err(|e| format!("Failed to save Sigma detection: {e}"))?;
+        }
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit Sigma detections: {e}"))?;
+    Ok(inserted)
+}
+
+/// Newest detections first, optionally for one rule and host.
+pub fn get_sigma_detections(limit: u32, rule_id: Option<&str>, host: Option<&str>) -> Result<Vec<SigmaDetection>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT rule_id, rule_title, level, event_id, event_timestamp, source_host, provider, event_code, message, detected_at
+            FROM sigma_detections
+            WHERE (?1 IS NULL OR rule_id = ?1) AND (?2 IS NULL OR source_host = ?2)
+            ORDER BY julianday(event_timestamp) DESC
+            LIMIT ?3
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare Sigma detection query: {e}"))?;
+    let rows = stmt
+        .query_map(params![rule_id, host, limit], |row| {
+            Ok(SigmaDetection {
+                rule_id: row.get(0)?,
+                rule_title: row.get(1)?,
+                level: row.get(2)?,
+                event_id: row.get(3)?,
+                event_timestamp: row.get(4)?,
+                source_host: row.get(5)?,
+                provider: row.get(6)?,
+                event_code: row.get(7)?,
+                message: row.get(8)?,
+                detected_at: row.get(9)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute Sigma detection query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse Sigma detection row: {e}"))
+}
+
+/// Deletes the detections of one rule, or of every rule when `rule_id` is `None`.
+pub fn delete_sigma_detections(rule_id: Option<&str>) -> Result<usize, String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "DELETE FROM sigma_detections WHERE ?1 IS NULL OR rule_id = ?1",
+        params![rule_id],
+    )
+    .map_err(|e| format!("Failed to delete Sigma detections: {e}"))
+}
+
+/// Replaces every indicator of `feed` with `indicators`. Returns how many were stored.
+pub fn replace_ioc_feed(feed: &str, indicators: &[IocIndicator], added_at: &str) -> Result<usize, String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+    tx.execute("DELETE FROM ioc_indicators WHERE feed = ?1", params![feed])
+        .map_err(|e| format!("Failed to clear IOC feed: {e}"))?;
+    let mut inserted = 0usize;
+    {
+        let mut stmt = tx
+            .prepare("INSERT OR IGNORE INTO ioc_indicators (feed, kind, value, added_at) VALUES (?1, ?2, ?3, ?4)")
+            .map_err(|e| format!("Failed to prepare IOC insert: {e}"))?;
+        for indicator in indicators {
+            inserted += stmt
+                .execute(params![feed, indicator.kind, indicator.value, added_at])
+                .map_err(|e| format!("Failed to save IOC: {e}"))?;
+        }
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit IOC feed: {e}"))?;
+    Ok(inserted)
+}
+
+pub fn get_ioc_indicators() -> Result<Vec<IocIndicator>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare("SELECT feed, kind, value FROM ioc_indicators ORDER BY feed, kind, value")
+        .map_err(|e| format!("Failed to prepare IOC query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(IocIndicator {
+                feed: row.get(0)?,
+                kind: row.get(1)?,
+                value: row.get(2)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute IOC query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse IOC row: {e}"))
+}
+
+pub fn get_ioc_feeds() -> Result<Vec<IocFeedSummary>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare("SELECT feed, kind, COUNT(*), MAX(added_at) FROM ioc_indicators GROUP BY feed, kind ORDER BY feed, kind")
+        .map_err(|e| format!("Failed to prepare IOC feed query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to execute IOC feed query: {e}"))?;
+
+    let mut feeds: Vec<IocFeedSummary> = Vec::new();
+    for row in rows {
+        let (feed, kind, count, added_at) = row.map_err(|e| format!("Failed to parse IOC feed row: {e}"))?;
+        if feeds.last().is_none_or(|last| last.feed != feed) {
+            feeds.push(IocFeedSummary {
+                feed: feed.clone(),
+                added_at: added_at.clone(),
+                counts: BTreeMap::new(),
+            });
+        }
+        if let Some(summary) = feeds.last_mut() {
+            summary.counts.insert(kind, count.max(0) as u64);
+            if added_at > summary.added_at {
+                summary.added_at = added_at;
+            }
+        }
+    }
+    Ok(feeds)
+}
+
+/// Deletes a feed's indicators and the matches found with them.
+pub fn delete_ioc_feed(feed: &str) -> Result<(), String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+    tx.execute("DELETE FROM ioc_indicators WHERE feed = ?1", params![feed])
+        .map_err(|e| format!("Failed to delete IOC feed: {e}"))?;
+    tx.execute("DELETE FROM ioc_matches WHERE feed = ?1", params![feed])
+        .map_err(|e| format!("Failed to delete IOC matches: {e}"))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit IOC feed deletion: {e}"))
+}
+
+/// Records IOC matches, keeping the first match of each feed, indicator and event. Returns how
+/// many were new.
+pub fn save_ioc_matches(matches: &[IocMatch]) -> Result<usize, String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+    let mut inserted = 0usize;
+    {
+        let mut stmt = tx
+            .prepare(
+                "
+                INSERT OR IGNORE INTO ioc_matches
+                    (feed, kind, indicator, field, event_id, event_timestamp, source_host, provider, event_code, message, matched_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                ",
+            )
+            .map_err(|e| format!("Failed to prepare IOC match insert: {e}"))?;
+        for found in matches {
+            inserted += stmt
+                .execute(params![
+                    found.feed,
+                    found.kind,
+                    found.indicator,
+                    found.field,
+                    found.event_id,
+                    found.event_timestamp,
+                    found.source_host,
+                    found.provider,
+                    found.event_code,
+                    found.message,
+                    found.matched_at
+                ])
+                .map_err(|e| format!("Failed to save IOC match: {e}"))?;
+        }
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit IOC matches: {e}"))?;
+    Ok(inserted)
+}
+
+/// Newest matches first, optionally for one feed and host.
+pub fn get_ioc_matches(limit: u32, feed: Option<&str>, host: Option<&str>) -> Result<Vec<IocMatch>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT feed, kind, indicator, field, event_id, event_timestamp, source_host, provider, event_code, message, matched_at
+            FROM ioc_matches
+            WHERE (?1 IS NULL OR feed = ?1) AND (?2 IS NULL OR source_host = ?2)
+            ORDER BY julianday(event_timestamp) DESC
+            LIMIT ?3
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare IOC match query: {e}"))?;
+    let rows = stmt
+        .query_map(params![feed, host, limit], |row| {
+            Ok(IocMatch {
+                feed: row.get(0)?,
+                kind: row.get(1)?,
+                indicator: row.get(2)?,
+                field: row.get(3)?,
+                event_id: row.get(4)?,
+                event_timestamp: row.get(5)?,
+                source_host: row.get(6)?,
+                provider: row.get(7)?,
+                event_code: row.get(8)?,
+                message: row.get(9)?,
+                matched_at: row.get(10)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute IOC match query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse IOC match row: {e}"))
+}
+
+/// Appends to the sync history, keeping only the newest `SYNC_HISTORY_LIMIT` runs.
+pub fn record_sync_history(entry: &SyncHistoryEntry) -> Result<(), String> {
+    let conn = open_connection()?;
+    let warnings = serde_json::to_string(&entry.warnings).unwrap_or_else(|_| "[]".to_string());
+    let errors = serde_json::to_string(&entry.errors).unwrap_or_else(|_| "[]".to_string());
+    let sources = serde_json::to_string(&entry.sources).unwrap_or_else(|_| "[]".to_string());
+    conn.execute(
+        "
+        INSERT INTO sync_history (kind, source_host, started_at, finished_at, status, collected, warnings, errors, sources)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+        ",
+        params![
+            entry.kind,
+            entry.source_host,
+            entry.started_at,
+            entry.finished_at,
+            entry.status,
+            entry.collected as i64,
+            warnings,
+            errors,
+            sources
+        ],
+    )
+    .map_err(|e| format!("Failed to record sync history: {e}"))?;
+    conn.execute(
+        "DELETE FROM sync_history WHERE id <= (SELECT MAX(id) FROM sync_history) - ?1",
+        params![SYNC_HISTORY_LIMIT],
+    )
+    .map_err(|e| format!("Failed to trim sync history: {e}"))?;
+    Ok(())
+}
+
+/// Newest sync runs first, optionally for one `source_host`.
+pub fn get_sync_history(limit: u32, source_host: Option<&str>) -> Result<Vec<SyncHistoryEntry>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT id, kind, source_host, started_at, finished_at, status, collected, warnings, errors, sources
+            FROM sync_history
+            WHERE ?1 IS NULL OR source_host = ?1
+            ORDER BY id DESC
+            LIMIT ?2
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare sync history query: {e}"))?;
+    let rows = stmt
+        .query_map(params![source_host, limit], |row| {
+            let list = |index: usize| -> rusqlite::Result<Vec<String>> {
+                Ok(serde_json::from_str(row.get::<_, String>(index)?.as_str()).unwrap_or_default())
+            };
+            Ok(SyncHistoryEntry {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                source_host: row.get(2)?,
+                started_at: row.get(3)?,
+                finished_at: row.get(4)?,
+                status: row.get(5)?,
+                collected: row.get::<_, i64>(6)?.max(0) as usize,
+                warnings: list(7)?,
+                errors: list(8)?,
+                sources: serde_json::from_str(row.get::<_, String>(9)?.as_str()).unwrap_or_default(),
+            })
+        })
+        .map_err(|e| format!("Failed to execute sync history query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse sync history row: {e}"))
+}
+
+/// Where the last directory import of a file (identified by its content fingerprint) stopped.
+pub fn get_import_offset(fingerprint: &str, source_host: &str) -> Result<Option<u64>, String> {
+    let conn = open_connection()?;
+    match conn.query_row(
+        "SELECT offset FROM import_offsets WHERE fingerprint = ?1 AND source_host = ?2",
+        params![fingerprint, source_host],
+        |row| row.get::<_, i64>(0),
+    ) {
+        Ok(offset) => Ok(Some(offset.max(0) as u64)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Failed to read import offset: {e}")),
+    }
+}
+
+pub fn save_import_offset(
+    fingerprint: &str,
+    source_host: &str,
+    path: &str,
+    offset: u64,
+) -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "
+        INSERT INTO import_offsets (fingerprint, source_host, path, offset, updated_at)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        ON CONFLICT(fingerprint, source_host) DO UPDATE SET
+            path=excluded.path,
+            offset=excluded.offset,
+            updated_at=excluded.updated_at
+        ",
+        params![
+            fingerprint,
+            source_host,
+            path,
+            offset as i64,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| format!("Failed to save import offset: {e}"))?;
+    Ok(())
+}
+
+pub fn save_stability_days(days: &[StabilityDay]) -> Result<(), String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+
+    for day in days {
+        tx.execute(
+            "
+            INSERT OR REPLACE INTO stability_index
+                (day, source_host, stability_index, daily_score, app_crashes, hangs, system_failures, unexpected_shutdowns, error_count)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ",
+            params![
+                day.day,
+                day.source_host,
+                day.index,
+                day.daily_score,
+                day.app_crashes,
+                day.hangs,
+                day.system_failures,
+                day.unexpected_shutdowns,
+                day.error_count,
+            ],
+        )
+        .map_err(|e| format!("Failed to save stability day: {e}"))?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit stability transaction: {e}"))?;
+
+    Ok(())
+}
+
+/// Stored stability days in `[from_day, to_day]` (YYYY-MM-DD), oldest first.
+pub fn get_stability_days(
+    from_day: &str,
+    to_day: &str,
+    host: &str,
+) -> Result<Vec<StabilityDay>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT day, source_host, stability_index, daily_score, app_crashes, hangs, system_failures, unexpected_shutdowns, error_count
+            FROM stability_index
+            WHERE source_host = ?3 AND day >= ?1 AND day <= ?2
+            ORDER BY day ASC
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare stability query: {e}"))?;
+
+    let rows = stmt
+        .query_map(params![from_day, to_day, host], |row| {
+            Ok(StabilityDay {
+                day: row.get(0)?,
+                source_host: row.get(1)?,
+                index: row.get(2)?,
+                daily_score: row.get(3)?,
+                app_crashes: row.get(4)?,
+                hangs: row.get(5)?,
+                system_failures: row.get(6)?,
+                unexpected_shutdowns: row.get(7)?,
+                error_count: row.get(8)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute stability query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse stability row: {e}"))
+}
+
+pub fn save_event_bookmark(event_id: &str, note: Option<&str>, created_at: &str) -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "
+        INSERT INTO event_bookmarks (event_id, note, created_at) VALUES (?1, ?2, ?3)
+        ON CONFLICT(event_id) DO UPDATE SET note = excluded.note
+        ",
+        params![event_id, note, created_at],
+    )
+    .map_err(|e| format!("Failed to save event bookmark: {e}"))?;
+    Ok(())
+}
+
+pub fn delete_event_bookmark(event_id: &str) -> Result<bool, String> {
+    let conn = open_connection()?;
+    let removed = conn
+        .execute("DELETE FROM event_bookmarks WHERE event_id = ?1", params![event_id])
+        .map_err(|e| format!("Failed to delete event bookmark: {e}"))?;
+    Ok(removed > 0)
+}
+
+pub fn get_event_bookmarks() -> Result<Vec<EventBookmark>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare("SELECT event_id, note, created_at FROM event_bookmarks ORDER BY created_at DESC")
+        .map_err(|e| format!("Failed to prepare bookmark query: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(EventBookmark {
+                event_id: row.get(0)?,
+                note: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute bookmark query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse bookmark row: {e}"))
+}
+
+/// Bookmarked events whose timestamp falls in `[from, to]`, paired with their bookmark.
+pub fn get_bookmarked_events_range(
+    from: &str,
+    to: &str,
+    host: &str,
+) -> Result<Vec<(NormalizedEvent, EventBookmark)>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            format!(
+                "
+            SELECT {}, b.note, b.created_at
+            FROM event_bookmarks b
+            JOIN events e ON e.id = b.event_id
+            WHERE e.timestamp_ms >= ?1 AND e.timestamp_ms <= ?2 AND e.source_host = ?3
+            ORDER BY e.timestamp_ms ASC
+            ",
+                event_columns(Some("e"))
+            )
+            .as_str(),
+        )
+        .map_err(|e| format!("Failed to prepare bookmarked events query: {e}"))?;
+    let rows = stmt
+        .query_map(params![timestamp_ms(from), timestamp_ms(to), host], |row| {
+            let event = row_to_event(row)?;
+            let bookmark = EventBookmark {
+                event_id: event.id.clone(),
+                note: row.get(EVENT_COLUMNS.len())?,
+                created_at: row.get(EVENT_COLUMNS.len() + 1)?,
+            };
+            Ok((event, bookmark))
+        })
+        .map_err(|e| format!("Failed to execute bookmarked events query: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse bookmarked event row: {e}"))
+}
+
+pub fn save_query_session(session: &QuerySession) -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO query_sessions (id, name, started_at, ended_at) VALUES (?1, ?2, ?3, ?4)",
+        params![session.id, session.name, session.started_at, session.ended_at],
+    )
+    .map_err(|e| format!("Failed to save query session: {e}"))?;
+    Ok(())
+}
+
+/// Appends a step to a session, numbering it after the last recorded step.
+pub fn append_query_step(
+    session_id: &str,
+    timestamp: &str,
+    action: &str,
+    params: &serde_json::Value,
+) -> Result<u32, String> {
+    let conn = open_connection()?;
+    let next: u32 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(seq), 0) + 1 FROM query_session_steps WHERE session_id = ?1",
+            params![session_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to number query step: {e}"))?;
+    conn.execute(
+        "INSERT INTO query_session_steps (session_id, seq, timestamp, action, params) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![session_id, next, timestamp, action, params.to_string()],
+    )
+    .map_err(|e| format!("Failed to save query step: {e}"))?;
+    Ok(next)
+}
+
+fn row_to_query_session(row: &Row<'_>) -> rusqlite::Result<QuerySession> {
+    Ok(QuerySession {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        started_at: row.get(2)?,
+        ended_at: row.get(3)?,
+        step_count: row.get(4)?,
+    })
+}
+
+const QUERY_SESSION_COLUMNS: &str = "
+    SELECT s.id, s.name, s.started_at, s.ended_at,
+           (SELECT COUNT(*) FROM query_session_steps st WHERE st.session_id = s.id)
+    FROM query_sessions s
+";
+
+pub fn get_query_sessions() -> Result<Vec<QuerySession>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(format!("{QUERY_SESSION_COLUMNS} ORDER BY s.started_at DESC").as_str())
+        .map_err(|e| format!("Failed to prepare query session list: {e}"))?;
+    let rows = stmt
+        .query_map([], row_to_query_session)
+        .map_err(|e| format!("Failed to execute query session list: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse query session row: {e}"))
+}
+
+pub fn get_query_session(session_id: &str) -> Result<Option<QuerySession>, String> {
+    let conn = open_connection()?;
+    match conn.query_row(
+        format!("{QUERY_SESSION_COLUMNS} WHERE s.id = ?1").as_str(),
+        params![session_id],
+        row_to_query_session,
+    ) {
+        Ok(session) => Ok(Some(session)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Failed to read query session: {e}")),
+    }
+}
+
+pub fn get_query_steps(session_id: &str) -> Result<Vec<QueryStep>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT seq, timestamp, action, params FROM query_session_steps WHERE session_id = ?1 ORDER BY seq ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query step list: {e}"))?;
+    let rows = stmt
+        .query_map(params![session_id], |row| {
+            let params: String = row.get(3)?;
+            Ok(QueryStep {
+                seq: row.get(0)?,
+                timestamp: row.get(1)?,
+                action: row.get(2)?,
+                params: serde_json::from_str(params.as_str()).unwrap_or(serde_json::Value::Null),
+            })
+        })
+        .map_err(|e| format!("Failed to execute query step list: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse query step row: {e}"))
+}
+
+pub fn delete_query_session(session_id: &str) -> Result<bool, String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "DELETE FROM query_session_steps WHERE session_id = ?1",
+        params![session_id],
+    )
+    .map_err(|e| format!("Failed to delete query steps: {e}"))?;
+    let removed = conn
+        .execute("DELETE FROM query_sessions WHERE id = ?1", params![session_id])
+        .map_err(|e| format!("Failed to delete query session: {e}"))?;
+    Ok(removed > 0)
+}
+
+pub fn save_chat_session(session: &ChatSession) -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO chat_sessions (id, title, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)",
+        params![session.id, session.title, session.created_at, session.updated_at],
+    )
+    .map_err(|e| format!("Failed to save chat session: {e}"))?;
+    Ok(())
+}
+
+fn row_to_chat_session(row: &Row<'_>) -> rusqlite::Result<ChatSession> {
+    Ok(ChatSession {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        created_at: row.get(2)?,
+        updated_at: row.get(3)?,
+        message_count: row.get(4)?,
+    })
+}
+
+const CHAT_SESSION_COLUMNS: &str = "
+    SELECT s.id, s.title, s.created_at, s.updated_at,
+           (SELECT COUNT(*) FROM chat_messages m WHERE m.session_id = s.id)
+    FROM chat_sessions s
+";
+
+pub fn get_chat_sessions() -> Result<Vec<ChatSession>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(format!("{CHAT_SESSION_COLUMNS} ORDER BY s.updated_at DESC").as_str())
+        .map_err(|e| format!("Failed to prepare chat session list: {e}"))?;
+    let rows = stmt
+        .query_map([], row_to_chat_session)
+        .map_err(|e| format!("Failed to execute chat session list: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse chat session row: {e}"))
+}
+
+pub fn get_chat_session(session_id: &str) -> Result<Option<ChatSession>, String> {
+    let conn = open_connection()?;
+    match conn.query_row(
+        format!("{CHAT_SESSION_COLUMNS} WHERE s.id = ?1").as_str(),
+        params![session_id],
+        row_to_chat_session,
+    ) {
+        Ok(session) => Ok(Some(session)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Failed to read chat session: {e}")),
+    }
+}
+
+/// Stores a message and bumps the session's `updated_at` so recent conversations sort first.
+pub fn append_chat_message(message: &ChatMessage) -> Result<(), String> {
+    let mut conn = open_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start DB transaction: {e}"))?;
+    tx.execute(
+        "
+        INSERT INTO chat_messages (id, session_id, role, content, context, profile_name, model, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        ",
+        params![
+            message.id,
+            message.session_id,
+            message.role,
+            message.content,
+            message.context,
+            message.profile_name,
+            message.model,
+            message.created_at
+        ],
+    )
+    .map_err(|e| format!("Failed to save chat message: {e}"))?;
+    tx.execute(
+        "UPDATE chat_sessions SET updated_at = ?2 WHERE id = ?1",
+        params![message.session_id, message.created_at],
+    )
+    .map_err(|e| format!("Failed to update chat session: {e}"))?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit chat message: {e}"))
+}
+
+pub fn get_chat_messages(session_id: &str) -> Result<Vec<ChatMessage>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare(
+            "
+            SELECT id, session_id, role, content, context, profile_name, model, created_at
+            FROM chat_messages
+            WHERE session_id = ?1
+            ORDER BY created_at ASC, rowid ASC
+            ",
+        )
+        .map_err(|e| format!("Failed to prepare chat message list: {e}"))?;
+    let rows = stmt
+        .query_map(params![session_id], |row| {
+            Ok(ChatMessage {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                role: row.get(2)?,
+                content: row.get(3)?,
+                context: row.get(4)?,
+                profile_name: row.get(5)?,
+                model: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })
+        .map_err(|e| format!("Failed to execute chat message list: {e}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse chat message row: {e}"))
+}
+
+pub fn delete_chat_session(session_id: &str) -> Result<bool, String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "DELETE FROM chat_messages WHERE session_id = ?1",
+        params![session_id],
+    )
+    .map_err(|e| format!("Failed to delete chat messages: {e}"))?;
+    let removed = conn
+        .execute("DELETE FROM chat_sessions WHERE id = ?1", params![session_id])
+        .map_err(|e| format!("Failed to delete chat session: {e}"))?;
+    Ok(removed > 0)
+}
+
+/// Stored embedding vectors for one model, keyed by message template.
+pub fn get_template_embeddings(model: &str) -> Result<HashMap<String, Vec<u8>>, String> {
+    let conn = open_connection()?;
+    let mut stmt = conn
+        .prepare("SELECT template, vector FROM template_embeddings WHERE model = ?1")
+        .map_err(|e| format!("Failed to prepare embedding query: {e}"))?;
+    let rows = stmt
+        .query_map(params![model], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })
+        .map_err(|e| format!("Failed to execute embedding query: {e}"))?;
+
+    rows.collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| format!("Failed to parse embedding row: {e}"))
+}
+
+pub fn save_template_embedding(model: &str, template: &str, vector: &[u8]) -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO template_embeddings (model, template, vector, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![model, template, vector, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| format!("Failed to save embedding: {e}"))?;
+    Ok(())
+}
+
+const DATABASE_IMPORT_BATCH_SIZE: usize = 5000;
+
+/// Counts from merging another machine's database into this one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseImportSummary {
+    pub events: usize,
+    pub crashes: usize,
+    pub hosts: Vec<String>,
+}
+
+/// `column` when `table` has it, otherwise the `fallback` expression, so databases written by
+/// builds that predate a migration can still be read without modifying them.
+fn table_columns(conn: &Connection, table: &str) -> Result<HashSet<String>, String> {
+    let mut stmt = conn
+        .prepare(format!("PRAGMA table_info({table})").as_str())
+        .map_err(|e| format!("Failed to read {table} columns: {e}"))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| format!("Failed to read {table} columns: {e}"))?
+        .collect::<Result<HashSet<_>, _>>()
+        .map_err(|e| format!("Failed to read {table} columns: {e}"))?;
+    Ok(columns)
+}
+
+fn column_or(conn: &Connection, table: &str, column: &str, fallback: &str) -> Result<String, String> {
+    Ok(if table_columns(conn, table)?.contains(column) {
+        column.to_string()
+    } else {
+        fallback.to_string()
+    })
+}
+
+/// Merges the events and crashes of a database written by Hermes on another machine. Rows that
+/// machine stored as `localhost` are tagged with `host` and get ids derived from it, so they
+/// never overwrite this machine's own rows. Every merged row is marked imported, and merging
+/// the same file again updates the rows it added before.
+pub fn import_database(path: &std::path::Path, host: &str) -> Result<DatabaseImportSummary, String> {
+    let local = db_path()?;
+    if fs::canonicalize(path).ok() == fs::canonicalize(&local).ok() {
+        return Err("The selected file is this machine's own database.".to_string());
+    }
+    let other = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open database to import: {e}"))?;
+    let tables = other
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table'")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<HashSet<_>, _>>()
+        })
+        .map_err(|e| format!("Failed to read database to import (is it a SQLite file?): {e}"))?;
+    if !tables.contains("events") {
+        return Err("The selected file is not a Hermes database (no events table).".to_string());
+    }
+
+    let mut summary = DatabaseImportSummary::default();
+    let mut hosts = std::collections::BTreeSet::new();
+    let retag = |source_host: &mut String| {
+        if source_host.is_empty() || source_host == "localhost" {
+            *source_host = host.to_string();
+            true
+        } else {
+            false
+        }
+    };
+
+    let source_host = column_or(&other, "events", "source_host", "'localhost'")?;
+    let mut enrichment = Vec::new();
+    for column in &EVENT_COLUMNS[11..] {
+        enrichment.push(column_or(&other, "events", column, "NULL")?);
+    }
+    let mut stmt = other
+        .prepare(format!("SELECT id, timestamp, os, log_name, category, provider, event_id, severity, message, {source_host}, 1, {} FROM events", enrichment.join(", ")).as_str())
+        .map_err(|e| format!("Failed to prepare event import query: {e}"))?;
+    let mut rows = stmt
+        .query([])
+        .map_err(|e| format!("Failed to execute event import query: {e}"))?;
+    let mut batch = Vec::with_capacity(DATABASE_IMPORT_BATCH_SIZE);
+    while let Some(row) = rows.next().map_err(|e| format!("Failed to read event row: {e}"))? {
+        let mut event = row_to_event(row).map_err(|e| format!("Failed to parse event row: {e}"))?;
+        if retag(&mut event.source_host) {
+            event.assign_stable_id();
+        }
+        hosts.insert(event.source_host.clone());
+        batch.push(event);
+        if batch.len() >= DATABASE_IMPORT_BATCH_SIZE {
+            save_local_events(&batch)?;
+            summary.events += batch.len();
+            batch.clear();
+        }
+    }
+    save_local_events(&batch)?;
+    summary.events += batch.len();
+
+    if tables.contains("crashes") {
+        let source_host = column_or(&other, "crashes", "source_host", "'localhost'")?;
+        let details = column_or(&other, "crashes", "details", "NULL")?;
+        let mut stmt = other
+            .prepare(format!("SELECT id, timestamp, os, source, crash_type, code, summary, suspected_component, raw_path, {source_host}, 1, {details} FROM crashes").as_str())
+            .map_err(|e| format!("Failed to prepare crash import query: {e}"))?;
+        let crashes = stmt
+            .query_map([], row_to_crash)
+            .map_err(|e| format!("Failed to execute crash import query: {e}"))?
+            .map(|row| {
+                let mut crash = row.map_err(|e| format!("Failed to parse crash row: {e}"))?;
+                if retag(&mut crash.source_host) {
+                    crash.id = format!("{host}:{}", crash.id);
+                }
+                hosts.insert(crash.source_host.clone());
+                Ok(crash)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        save_crashes(&crashes)?;
+        summary.crashes = crashes.len();
+    }
+
+    summary.hosts = hosts.into_iter().collect();
+    Ok(summary)
+}
+
+/// The newest journal entry read for a filter, and the realtime (epoch microseconds) back to
+/// which every entry before it has been read too.
+#[cfg(all(target_os = "linux", feature = "sd-journal"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalCursor {
+    pub cursor: String,
+    pub oldest_usec: u64,
+}
+
+#[cfg(all(target_os = "linux", feature = "sd-journal"))]
+pub fn get_journal_cursor(filter_key: &str) -> Result<Option<JournalCursor>, String> {
+    let conn = open_connection()?;
+    let cursor = conn.query_row(
+        "SELECT cursor, oldest_usec FROM journal_cursors WHERE filter_key = ?1",
+        params![filter_key],
+        |row| {
+            Ok(JournalCursor {
+                cursor: row.get(0)?,
+                oldest_usec: row.get::<_, i64>(1)?.max(0) as u64,
+            })
+        },
+    );
+    match cursor {
+        Ok(cursor) => Ok(Some(cursor)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(error) => Err(format!("Failed to read journal cursor: {error}")),
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "sd-journal"))]
+pub fn save_journal_cursor(
+    filter_key: &str,
+    cursor: &JournalCursor,
+    updated_at: &str,
+) -> Result<(), String> {
+    let conn = open_connection()?;
+    conn.execute(
+        "
+        INSERT INTO journal_cursors (filter_key, cursor, oldest_usec, updated_at)
+        VALUES (?1, ?2, ?3, ?4)
+        ON CONFLICT(filter_key) DO UPDATE SET
+            cursor = excluded.cursor,
+            oldest_usec = excluded.oldest_usec,
+            updated_at = excluded.updated_at
+        ",
+        params![
+            filter_key,
+            cursor.cursor,
+            cursor.oldest_usec.min(i64::MAX as u64) as i64,
+            updated_at
+        ],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to save journal cursor: {e}"))
+}
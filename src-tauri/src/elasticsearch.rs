@@ -0,0 +1,155 @@
+use crate::forwarding::authorize;
+use crate::logs::NormalizedEvent;
+use crate::settings::IntegrationConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Events per `_bulk` request when pushing a stored event set.
+pub const BULK_BATCH_SIZE: usize = 500;
+const MAX_REPORTED_ERRORS: usize = 5;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkPushResult {
+    pub sent: usize,
+    pub failed: usize,
+    pub batches: usize,
+    /// First few per-document errors reported by the cluster.
+    pub errors: Vec<String>,
+}
+
+fn bulk_url(endpoint: &str) -> String {
+    let trimmed = endpoint.trim().trim_end_matches('/');
+    if trimmed.ends_with("/_bulk") {
+        trimmed.to_string()
+    } else {
+        format!("{trimmed}/_bulk")
+    }
+}
+
+/// Builds an NDJSON `_bulk` body. The event id is the document id, so pushing the same events
+/// twice overwrites instead of duplicating them.
+pub fn bulk_body(index: &str, events: &[NormalizedEvent]) -> Result<String, String> {
+    let mut body = String::new();
+    for event in events {
+        let action = json!({ "index": { "_index": index, "_id": event.id } });
+        let mut document = serde_json::to_value(event)
+            .map_err(|error| format!("Failed to serialize event for bulk push: {error}"))?;
+        document["@timestamp"] = Value::from(event.timestamp.as_str());
+        body.push_str(action.to_string().as_str());
+        body.push('\n');
+        body.push_str(document.to_string().as_str());
+        body.push('\n');
+    }
+    Ok(body)
+}
+
+/// Counts rejected documents in a `_bulk` response and keeps the first few reasons.
+pub fn bulk_failures(response: &Value) -> (usize, Vec<String>) {
+    if response.get("errors").and_then(Value::as_bool) != Some(true) {
+        return (0, Vec::new());
+    }
+    let mut failed = 0;
+    let mut reasons = Vec::new();
+    for item in response
+        .get("items")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let Some(error) = item
+            .as_object()
+            .and_then(|item| item.values().next())
+            .and_then(|result| result.get("error"))
+        else {
+            continue;
+        };
+        failed += 1;
+        if reasons.len() < MAX_REPORTED_ERRORS {
+            let kind = error.get("type").and_then(Value::as_str).unwrap_or("error");
+            let reason = error
+                .get("reason")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            reasons.push(format!("{kind}: {reason}"));
+        }
+    }
+    (failed, reasons)
+}
+
+/// Sends one `_bulk` request. Transport and HTTP errors fail the whole batch; documents the
+/// cluster rejects individually are counted in the result instead.
+pub fn send_bulk(
+    client: &reqwest::blocking::Client,
+    integration: &IntegrationConfig,
+    events: &[NormalizedEvent],
+) -> Result<BulkPushResult, String> {
+    if events.is_empty() {
+        return Ok(BulkPushResult::default());
+    }
+    let body = bulk_body(integration.index.as_str(), events)?;
+    let request = client
+        .post(bulk_url(integration.endpoint.as_str()))
+        .header("Content-Type", "application/x-ndjson")
+        .body(body);
+    let response = authorize(request, integration, "ApiKey")?
+        .send()
+        .map_err(|error| format!("Elasticsearch bulk request failed: {error}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Elasticsearch returned HTTP {status}"));
+    }
+    let payload: Value = response
+        .json()
+        .map_err(|error| format!("Elasticsearch returned an unreadable response: {error}"))?;
+    let (failed, errors) = bulk_failures(&payload);
+    Ok(BulkPushResult {
+        sent: events.len() - failed.min(events.len()),
+        failed,
+        batches: 1,
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    #[test]
+    fn builds_bulk_lines_and_counts_rejections() {
+        let event = NormalizedEvent::new(
+            SupportedOs::Linux,
+            "journal",
+            "system",
+            "kernel",
+            None,
+            "error",
+            "disk failure",
+            "host-a",
+        );
+        let body = bulk_body("hermes-events", std::slice::from_ref(&event)).unwrap();
+        let lines: Vec<Value> = body
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["index"]["_id"], event.id.as_str());
+        assert_eq!(lines[1]["@timestamp"], event.timestamp.as_str());
+        assert_eq!(bulk_url("https://es:9200/"), "https://es:9200/_bulk");
+
+        let response = json!({
+            "errors": true,
+            "items": [
+                { "index": { "status": 201 } },
+                { "index": { "status": 400, "error": { "type": "mapper_parsing_exception", "reason": "bad field" } } }
+            ]
+        });
+        let (failed, reasons) = bulk_failures(&response);
+        assert_eq!(failed, 1);
+        assert_eq!(
+            reasons,
+            vec!["mapper_parsing_exception: bad field".to_string()]
+        );
+    }
+}
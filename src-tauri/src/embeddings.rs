@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+const EMBEDDING_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarEventMatch {
+    pub template: String,
+    pub similarity: f32,
+    pub occurrences: usize,
+    pub first_seen: String,
+    pub last_seen: String,
+    /// Most recent occurrence of the template.
+    pub event: crate::logs::NormalizedEvent,
+}
+
+/// Embedding model used when the caller does not name one.
+pub fn default_embedding_model(provider: &str) -> Option<&'static str> {
+    match provider {
+        "ollama" => Some("nomic-embed-text"),
+        "openai" => Some("text-embedding-3-small"),
+        "lmstudio" | "openai_compatible" => Some("text-embedding-nomic-embed-text-v1.5"),
+        _ => None,
+    }
+}
+
+fn embeddings_endpoint(provider: &str, base_url: &str) -> String {
+    let base = base_url.trim().trim_end_matches('/');
+    if provider == "ollama" {
+        format!("{base}/api/embeddings")
+    } else if base.ends_with("/v1") {
+        format!("{base}/embeddings")
+    } else {
+        format!("{base}/v1/embeddings")
+    }
+}
+
+/// Reads a vector from an Ollama (`embedding`) or OpenAI-style (`data[0].embedding`) reply.
+pub fn parse_embedding_response(value: &Value) -> Option<Vec<f32>> {
+    let array = value
+        .get("embedding")
+        .or_else(|| value.get("data")?.get(0)?.get("embedding"))?
+        .as_array()?;
+    let vector: Vec<f32> = array
+        .iter()
+        .filter_map(Value::as_f64)
+        .map(|number| number as f32)
+        .collect();
+    (!vector.is_empty() && vector.len() == array.len()).then_some(vector)
+}
+
+/// Requests an embedding for `text` from an Ollama or OpenAI-compatible endpoint.
+pub fn request_embedding(
+    provider: &str,
+    base_url: &str,
+    model: &str,
+    text: &str,
+    api_key: Option<&str>,
+) -> Result<Vec<f32>, String> {
+    let payload = if provider == "ollama" {
+        serde_json::json!({ "model": model, "prompt": text })
+    } else {
+        serde_json::json!({ "model": model, "input": text })
+    };
+    let client = reqwest::blocking::Client::builder()
+        .timeout(EMBEDDING_TIMEOUT)
+        .build()
+        .map_err(|error| format!("Failed to initialize HTTP client: {error}"))?;
+    let mut request = client
+        .post(embeddings_endpoint(provider, base_url))
+        .json(&payload);
+    if let Some(key) = api_key.filter(|value| !value.trim().is_empty()) {
+        request = request.bearer_auth(key);
+    }
+    let response = request
+        .send()
+        .map_err(|error| format!("Failed requesting embedding: {error}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!(
+            "Embedding request failed (HTTP {}). Check that model '{model}' is available.",
+            status.as_u16()
+        ));
+    }
+    let body: Value = response
+        .json()
+        .map_err(|error| format!("Failed to parse embedding response: {error}"))?;
+    parse_embedding_response(&body)
+        .ok_or_else(|| "Embedding response did not include a vector.".to_string())
+}
+
+pub fn cosine_similarity(left: &[f32], right: &[f32]) -> f32 {
+    if left.len() != right.len() || left.is_empty() {
+        return 0.0;
+    }
+    let mut dot = 0.0f32;
+    let mut left_norm = 0.0f32;
+    let mut right_norm = 0.0f32;
+    for (a, b) in left.iter().zip(right) {
+        dot += a * b;
+        left_norm += a * a;
+        right_norm += b * b;
+    }
+    if left_norm == 0.0 || right_norm == 0.0 {
+        return 0.0;
+    }
+    dot / (left_norm.sqrt() * right_norm.sqrt())
+}
+
+/// Little-endian `f32` bytes, the form vectors are stored in SQLite.
+pub fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+pub fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors_round_trip_and_compare() {
+        let vector = vec![0.5, -1.25, 3.0];
+        assert_eq!(decode_vector(encode_vector(&vector).as_slice()), vector);
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn parses_ollama_and_openai_replies() {
+        let ollama = serde_json::json!({ "embedding": [0.1, 0.2] });
+        let openai = serde_json::json!({ "data": [{ "embedding": [0.3, 0.4, 0.5] }] });
+        assert_eq!(parse_embedding_response(&ollama).unwrap().len(), 2);
+        assert_eq!(parse_embedding_response(&openai).unwrap().len(), 3);
+        assert!(parse_embedding_response(&serde_json::json!({ "error": "no" })).is_none());
+    }
+}
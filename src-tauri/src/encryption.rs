@@ -0,0 +1,245 @@
+//! Opt-in SQLCipher encryption of `events.db`, available when built with the `sqlcipher` feature.
+//! The raw key is kept in the OS keychain; switching encryption on or off exports the database
+//! into a new file with the new key and swaps it in place of the old one.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+#[cfg(feature = "sqlcipher")]
+const KEYCHAIN_SERVICE: &str = "hermes-log-analyst.database";
+
+/// Keys by database file name, which is also the keychain account. The keychain is read once per
+/// file; later connections reuse the cached key.
+static KEYS: Mutex<BTreeMap<String, Option<String>>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptionStatus {
+    /// Whether this build includes SQLCipher.
+    pub available: bool,
+    pub enabled: bool,
+}
+
+/// The value for `PRAGMA key` and `ATTACH ... KEY`: a raw 256-bit key, so SQLCipher skips its
+/// passphrase derivation.
+pub fn key_pragma(key: &str) -> String {
+    format!("x'{key}'")
+}
+
+fn account(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Key for the database at `path`, or `None` while it is stored in plain text.
+pub fn database_key(path: &Path) -> Result<Option<String>, String> {
+    if !cfg!(feature = "sqlcipher") {
+        return Ok(None);
+    }
+    let account = account(path);
+    let mut cached = KEYS
+        .lock()
+        .map_err(|_| "Database key cache is poisoned".to_string())?;
+    if let Some(key) = cached.get(account.as_str()) {
+        return Ok(key.clone());
+    }
+    let key = read_key(account.as_str())?;
+    cached.insert(account, key.clone());
+    Ok(key)
+}
+
+/// Encryption of the open workspace's database.
+pub fn encryption_status() -> Result<EncryptionStatus, String> {
+    Ok(EncryptionStatus {
+        available: cfg!(feature = "sqlcipher"),
+        enabled: database_key(crate::db::db_path()?.as_path())?.is_some(),
+    })
+}
+
+/// Encrypts the existing plain-text database under a new key. The key is only stored once the
+/// encrypted copy has been written, and removed again if the copy cannot be swapped in.
+#[cfg(feature = "sqlcipher")]
+pub fn enable_encryption() -> Result<EncryptionStatus, String> {
+    let path = crate::db::db_path()?;
+    if database_key(path.as_path())?.is_some() {
+        return encryption_status();
+    }
+    let account = account(path.as_path());
+    let key = new_key();
+    // An encrypted database whose key is lost cannot be recovered, so the keychain has to hand
+    // the key back before anything is rewritten.
+    let entry = keychain_entry(account.as_str())?;
+    entry
+        .set_password(key.as_str())
+        .map_err(|error| format!("Unable to save database key in OS keychain: {error}"))?;
+    if read_key(account.as_str())?.as_deref() != Some(key.as_str()) {
+        let _ = entry.delete_credential();
+        return Err(
+            "The OS keychain did not keep the database key; encryption was not enabled."
+                .to_string(),
+        );
+    }
+    crate::db::replace_database(|current, replacement| {
+        export(current, None, replacement, Some(key.as_str()))?;
+        store_key(account.as_str(), Some(key.as_str()))
+    })
+    .inspect_err(|_| {
+        let _ = store_key(account.as_str(), None);
+    })?;
+    encryption_status()
+}
+
+/// Decrypts the database back to plain text and deletes its key.
+#[cfg(feature = "sqlcipher")]
+pub fn disable_encryption() -> Result<EncryptionStatus, String> {
+    let path = crate::db::db_path()?;
+    let Some(key) = database_key(path.as_path())? else {
+        return encryption_status();
+    };
+    let account = account(path.as_path());
+    crate::db::replace_database(|current, replacement| {
+        export(current, Some(key.as_str()), replacement, None)?;
+        store_key(account.as_str(), None)
+    })
+    .inspect_err(|_| {
+        let _ = store_key(account.as_str(), Some(key.as_str()));
+    })?;
+    encryption_status()
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+pub fn enable_encryption() -> Result<EncryptionStatus, String> {
+    Err("This build does not include SQLCipher; rebuild with the `sqlcipher` feature.".to_string())
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+pub fn disable_encryption() -> Result<EncryptionStatus, String> {
+    encryption_status()
+}
+
+/// Copies every table of `source` into a new database at `target` with `sqlcipher_export`. An
+/// empty key on either side means plain text.
+#[cfg(feature = "sqlcipher")]
+fn export(
+    source: &std::path::Path,
+    source_key: Option<&str>,
+    target: &std::path::Path,
+    target_key: Option<&str>,
+) -> Result<(), String> {
+    let conn = rusqlite::Connection::open(source)
+        .map_err(|e| format!("Failed to open SQLite database: {e}"))?;
+    if let Some(key) = source_key {
+        conn.pragma_update(None, "key", key_pragma(key))
+            .map_err(|e| format!("Failed to unlock database: {e}"))?;
+    }
+    conn.execute(
+        "ATTACH DATABASE ?1 AS target KEY ?2",
+        rusqlite::params![
+            target.to_string_lossy(),
+            target_key.map(key_pragma).unwrap_or_default()
+        ],
+    )
+    .map_err(|e| format!("Failed to create rewritten database: {e}"))?;
+    conn.query_row("SELECT sqlcipher_export('target')", [], |_| Ok(()))
+        .map_err(|e| format!("Failed to copy database contents: {e}"))?;
+    conn.execute_batch("DETACH DATABASE target")
+        .map_err(|e| format!("Failed to finish rewritten database: {e}"))
+}
+
+/// 64 hex digits from two random UUIDs.
+#[cfg(feature = "sqlcipher")]
+fn new_key() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+#[cfg(feature = "sqlcipher")]
+fn keychain_entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, account)
+        .map_err(|error| format!("Unable to open OS keychain entry: {error}"))
+}
+
+#[cfg(feature = "sqlcipher")]
+fn read_key(account: &str) -> Result<Option<String>, String> {
+    match keychain_entry(account)?.get_password() {
+        Ok(value) if value.trim().is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value.trim().to_string())),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(format!(
+            "Unable to read database key from OS keychain: {error}"
+        )),
+    }
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn read_key(_account: &str) -> Result<Option<String>, String> {
+    Ok(None)
+}
+
+/// Saves or deletes the key in the keychain and the cache together.
+#[cfg(feature = "sqlcipher")]
+fn store_key(account: &str, key: Option<&str>) -> Result<(), String> {
+    let entry = keychain_entry(account)?;
+    match key {
+        Some(key) => entry
+            .set_password(key)
+            .map_err(|error| format!("Unable to save database key in OS keychain: {error}"))?,
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(error) => {
+                return Err(format!(
+                    "Unable to remove database key from OS keychain: {error}"
+                ))
+            }
+        },
+    }
+    if let Ok(mut cached) = KEYS.lock() {
+        cached.insert(account.to_string(), key.map(str::to_string));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "sqlcipher"))]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn export_round_trips_through_encryption() {
+        let dir = std::env::temp_dir().join(format!("hla-encryption-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (plain, encrypted, decrypted) = (
+            dir.join("plain.db"),
+            dir.join("encrypted.db"),
+            dir.join("decrypted.db"),
+        );
+        Connection::open(&plain)
+            .unwrap()
+            .execute_batch("CREATE TABLE notes (body TEXT); INSERT INTO notes VALUES ('secret');")
+            .unwrap();
+
+        let key = new_key();
+        export(&plain, None, &encrypted, Some(key.as_str())).unwrap();
+        assert!(!std::fs::read(&encrypted)
+            .unwrap()
+            .starts_with(b"SQLite format 3"));
+        let locked = Connection::open(&encrypted).unwrap();
+        assert!(locked
+            .query_row("SELECT COUNT(*) FROM notes", [], |row| row.get::<_, i64>(0))
+            .is_err());
+
+        export(&encrypted, Some(key.as_str()), &decrypted, None).unwrap();
+        let body: String = Connection::open(&decrypted)
+            .unwrap()
+            .query_row("SELECT body FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(body, "secret");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
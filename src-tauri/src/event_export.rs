@@ -0,0 +1,818 @@
+use crate::logs::NormalizedEvent;
+use crate::parquet_export::ParquetEventWriter;
+use crate::siem::{cef_line, leef_line};
+use crate::timestamps::{parse_timestamp, DisplayTimezone};
+use chrono::Utc;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub const EVENT_EXPORT_SCHEMA: &str = "hermes.events";
+/// Bumped whenever an export gains or changes columns. Version 0 is the bare JSON array written
+/// before exports carried a header; importers accept every version and keep unknown columns.
+/// Version 2 writes timestamps in the header's `timezone` instead of always in UTC.
+pub const EVENT_EXPORT_SCHEMA_VERSION: u32 = 2;
+pub const EVENT_EXPORT_FIELDS: [&str; 11] = [
+    "id",
+    "timestamp",
+    "os",
+    "logName",
+    "category",
+    "provider",
+    "eventId",
+    "severity",
+    "message",
+    "sourceHost",
+    "imported",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EventExportHeader {
+    schema: &'static str,
+    schema_version: u32,
+    exported_at: String,
+    timezone: String,
+    fields: Vec<&'static str>,
+    event_count: usize,
+}
+
+fn export_header(event_count: usize, timezone: DisplayTimezone) -> EventExportHeader {
+    EventExportHeader {
+        schema: EVENT_EXPORT_SCHEMA,
+        schema_version: EVENT_EXPORT_SCHEMA_VERSION,
+        exported_at: timezone.convert(Utc::now()).to_rfc3339(),
+        timezone: timezone.name(),
+        fields: EVENT_EXPORT_FIELDS.to_vec(),
+        event_count,
+    }
+}
+
+pub const CSV_HEADER: &str =
+    "timestamp,os,logName,category,provider,eventId,severity,message,source";
+
+pub fn csv_escape(value: &str) -> String {
+    let text = csv_formula_safe(value);
+    if text.contains(',') || text.contains('"') || text.contains('\n') || text.contains('\r') {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+fn csv_formula_safe(value: &str) -> String {
+    let leading_trimmed = value.trim_start_matches([' ', '\t', '\r', '\n']);
+    match leading_trimmed.chars().next() {
+        Some('=') | Some('+') | Some('-') | Some('@') => format!("'{value}"),
+        _ => value.to_string(),
+    }
+}
+
+/// Undoes the spreadsheet formula guard `csv_escape` adds, so values round-trip unchanged.
+pub fn csv_unguard(value: &str) -> String {
+    match value.strip_prefix('\'') {
+        Some(rest)
+            if matches!(
+                rest.trim_start_matches([' ', '\t', '\r', '\n'])
+                    .chars()
+                    .next(),
+                Some('=' | '+' | '-' | '@')
+            ) =>
+        {
+            rest.to_string()
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Splits RFC 4180 CSV into records, honouring quoted commas, quotes and line breaks.
+pub fn parse_csv(raw: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = raw.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(ch),
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            _ => field.push(ch),
+        }
+    }
+    if in_quotes {
+        return Err("CSV ends inside a quoted field.".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn source_label(event: &NormalizedEvent) -> &'static str {
+    if event.imported {
+        "Imported"
+    } else {
+        "Live/Local"
+    }
+}
+
+fn csv_row(event: &NormalizedEvent, timezone: DisplayTimezone) -> String {
+    [
+        csv_escape(timezone.format(event.timestamp.as_str()).as_str()),
+        csv_escape(event.os.as_str()),
+        csv_escape(event.log_name.as_str()),
+        csv_escape(event.category.as_str()),
+        csv_escape(event.provider.as_str()),
+        csv_escape(
+            event
+                .event_id
+                .map(|id| id.to_string())
+                .unwrap_or_default()
+                .as_str(),
+        ),
+        csv_escape(event.severity.as_str()),
+        csv_escape(event.message.as_str()),
+        csv_escape(source_label(event)),
+    ]
+    .join(",")
+}
+
+fn plain_text_block(event: &NormalizedEvent, timezone: DisplayTimezone) -> String {
+    [
+        format!("Timestamp: {}", timezone.format(event.timestamp.as_str())),
+        format!("OS: {}", event.os),
+        format!("Type: {} / {}", event.log_name, event.category),
+        format!("Provider: {}", event.provider),
+        format!(
+            "Event ID: {}",
+            event
+                .event_id
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        ),
+        format!("Severity: {}", event.severity),
+        format!("Source: {}", source_label(event)),
+        format!("Message: {}", event.message),
+        "---".to_string(),
+    ]
+    .join("\n")
+}
+
+pub fn is_supported_format(extension: &str) -> bool {
+    matches!(
+        extension,
+        "json" | "ndjson" | "csv" | "txt" | "parquet" | "cef" | "leef"
+    )
+}
+
+struct TextExport<W: Write> {
+    out: W,
+    extension: &'static str,
+    timezone: DisplayTimezone,
+    written: usize,
+}
+
+enum ExportTarget<W: Write + Send> {
+    Text(TextExport<W>),
+    Parquet(Box<ParquetEventWriter<W>>),
+}
+
+/// Writes an export one event at a time so large exports never need every event in memory.
+/// `event_count` is only used for the JSON/NDJSON header. Text formats show timestamps in
+/// `timezone`; Parquet keeps its typed UTC column and CEF/LEEF their epoch times.
+pub struct EventExportWriter<W: Write + Send> {
+    target: ExportTarget<W>,
+}
+
+impl<W: Write + Send> EventExportWriter<W> {
+    pub fn start(
+        mut out: W,
+        extension: &str,
+        event_count: usize,
+        timezone: DisplayTimezone,
+    ) -> Result<Self, String> {
+        let write_error = |error: std::io::Error| format!("Failed to write export: {error}");
+        let extension = match extension {
+            "json" => {
+                let header = serde_json::to_string_pretty(&export_header(event_count, timezone))
+                    .map_err(|error| format!("Failed to serialize export header: {error}"))?;
+                let open = header.strip_suffix("\n}").unwrap_or(header.as_str());
+                write!(out, "{open},\n  \"events\": [").map_err(write_error)?;
+                "json"
+            }
+            "ndjson" => {
+                let header = serde_json::to_string(&export_header(event_count, timezone))
+                    .map_err(|error| format!("Failed to serialize export header: {error}"))?;
+                out.write_all(header.as_bytes()).map_err(write_error)?;
+                "ndjson"
+            }
+            "csv" => {
+                out.write_all(CSV_HEADER.as_bytes()).map_err(write_error)?;
+                "csv"
+            }
+            "txt" => "txt",
+            "cef" => "cef",
+            "leef" => "leef",
+            "parquet" => {
+                return Ok(Self {
+                    target: ExportTarget::Parquet(Box::new(ParquetEventWriter::start(out)?)),
+                })
+            }
+            _ => return Err("Unsupported export format.".to_string()),
+        };
+        Ok(Self {
+            target: ExportTarget::Text(TextExport {
+                out,
+                extension,
+                timezone,
+                written: 0,
+            }),
+        })
+    }
+
+    pub fn write_event(&mut self, event: &NormalizedEvent) -> Result<(), String> {
+        let text = match &mut self.target {
+            ExportTarget::Parquet(writer) => return writer.write_event(event),
+            ExportTarget::Text(text) => text,
+        };
+        let timezone = text.timezone;
+        let serialize = || {
+            let shown = NormalizedEvent {
+                timestamp: timezone.format(event.timestamp.as_str()),
+                ..event.clone()
+            };
+            serde_json::to_string(&shown)
+                .map_err(|error| format!("Failed to serialize export event: {error}"))
+        };
+        let chunk = match text.extension {
+            "json" if text.written == 0 => format!("\n    {}", serialize()?),
+            "json" => format!(",\n    {}", serialize()?),
+            "ndjson" => format!("\n{}", serialize()?),
+            "csv" => format!("\n{}", csv_row(event, timezone)),
+            "cef" if text.written == 0 => cef_line(event),
+            "cef" => format!("\n{}", cef_line(event)),
+            "leef" if text.written == 0 => leef_line(event),
+            "leef" => format!("\n{}", leef_line(event)),
+            _ if text.written == 0 => plain_text_block(event, timezone),
+            _ => format!("\n{}", plain_text_block(event, timezone)),
+        };
+        text.out
+            .write_all(chunk.as_bytes())
+            .map_err(|error| format!("Failed to write export: {error}"))?;
+        text.written += 1;
+        Ok(())
+    }
+
+    /// Closes the document and returns the output and the number of events written.
+    pub fn finish(self) -> Result<(W, usize), String> {
+        let mut text = match self.target {
+            ExportTarget::Parquet(writer) => return writer.finish(),
+            ExportTarget::Text(text) => text,
+        };
+        if text.extension == "json" {
+            let close = if text.written == 0 {
+                "]\n}"
+            } else {
+                "\n  ]\n}"
+            };
+            text.out
+                .write_all(close.as_bytes())
+                .map_err(|error| format!("Failed to write export: {error}"))?;
+        }
+        text.out
+            .flush()
+            .map_err(|error| format!("Failed to write export: {error}"))?;
+        Ok((text.out, text.written))
+    }
+}
+
+/// Renders a whole export in memory, for selections already held by the caller. Timestamps stay
+/// in UTC, since these go into bundles meant for another install rather than for reading.
+pub fn build_export(extension: &str, events: &[NormalizedEvent]) -> Result<Vec<u8>, String> {
+    let mut writer =
+        EventExportWriter::start(Vec::new(), extension, events.len(), DisplayTimezone::Utc)?;
+    for event in events {
+        writer.write_event(event)?;
+    }
+    writer.finish().map(|(bytes, _)| bytes)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportCompression {
+    Gzip,
+    Zip,
+}
+
+impl ExportCompression {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            Self::Zip => "zip",
+        }
+    }
+}
+
+/// Splits a requested format such as `csv.gz` or `ndjson.zip` into the event format and its
+/// compression. Parquet is refused with a compression suffix because it is compressed already.
+pub fn parse_export_format(format: &str) -> Result<(String, Option<ExportCompression>), String> {
+    let format = format.trim().to_ascii_lowercase();
+    let (base, compression) = if let Some(base) = format
+        .strip_suffix(".gz")
+        .or_else(|| format.strip_suffix(".gzip"))
+    {
+        (base, Some(ExportCompression::Gzip))
+    } else if let Some(base) = format.strip_suffix(".zip") {
+        (base, Some(ExportCompression::Zip))
+    } else {
+        (format.as_str(), None)
+    };
+    if !is_supported_format(base) {
+        return Err("Unsupported export format.".to_string());
+    }
+    if base == "parquet" && compression.is_some() {
+        return Err("Parquet exports are already compressed.".to_string());
+    }
+    Ok((base.to_string(), compression))
+}
+
+/// An export file that compresses as it is written, so large exports never exist
+/// uncompressed on disk. A zip archive holds the export as its single entry.
+pub enum ExportFile {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zip(ZipWriter<BufWriter<File>>),
+}
+
+impl ExportFile {
+    pub fn create(path: &Path, compression: Option<ExportCompression>) -> Result<Self, String> {
+        let file =
+            File::create(path).map_err(|error| format!("Failed to create export file: {error}"))?;
+        let out = BufWriter::new(file);
+        match compression {
+            None => Ok(Self::Plain(out)),
+            Some(ExportCompression::Gzip) => Ok(Self::Gzip(GzEncoder::new(
+                out,
+                flate2::Compression::default(),
+            ))),
+            Some(ExportCompression::Zip) => {
+                let entry = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .and_then(|name| name.strip_suffix(".zip").map(str::to_string))
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| "events".to_string());
+                let mut zip = ZipWriter::new(out);
+                zip.start_file(
+                    entry,
+                    SimpleFileOptions::default()
+                        .compression_method(CompressionMethod::Deflated)
+                        .large_file(true),
+                )
+                .map_err(|error| format!("Failed to start export archive: {error}"))?;
+                Ok(Self::Zip(zip))
+            }
+        }
+    }
+
+    /// Writes the compression trailer and flushes the file.
+    pub fn finish(self) -> Result<(), String> {
+        let write_error = |error: std::io::Error| format!("Failed to write export: {error}");
+        let mut out = match self {
+            Self::Plain(out) => out,
+            Self::Gzip(encoder) => encoder.finish().map_err(write_error)?,
+            Self::Zip(zip) => zip
+                .finish()
+                .map_err(|error| format!("Failed to finish export archive: {error}"))?,
+        };
+        out.flush().map_err(write_error)
+    }
+}
+
+impl Write for ExportFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(out) => out.write(buf),
+            Self::Gzip(out) => out.write(buf),
+            Self::Zip(out) => out.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(out) => out.flush(),
+            Self::Gzip(out) => out.flush(),
+            Self::Zip(out) => out.flush(),
+        }
+    }
+}
+
+/// Streams `events` into `path` in `extension` format, compressed as requested.
+pub fn write_export_file(
+    path: &Path,
+    extension: &str,
+    compression: Option<ExportCompression>,
+    events: &[NormalizedEvent],
+    timezone: DisplayTimezone,
+) -> Result<usize, String> {
+    let file = ExportFile::create(path, compression)?;
+    let mut writer = EventExportWriter::start(file, extension, events.len(), timezone)?;
+    for event in events {
+        writer.write_event(event)?;
+    }
+    let (file, written) = writer.finish()?;
+    file.finish()?;
+    Ok(written)
+}
+
+fn header_version(value: &Value) -> Result<Option<u64>, String> {
+    match value.get("schemaVersion") {
+        None => Ok(None),
+        Some(version) => version
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| "Export header has an invalid schemaVersion.".to_string()),
+    }
+}
+
+fn event_from_value(value: Value, position: usize) -> Result<NormalizedEvent, String> {
+    serde_json::from_value::<NormalizedEvent>(value)
+        .map_err(|error| format!("Event {position} is not a valid Hermes event: {error}"))
+}
+
+/// Reads a JSON or NDJSON event export of any schema version. Version 0 files are a bare array;
+/// later versions carry a header. Columns added by newer releases are ignored.
+pub fn parse_event_export(raw: &str) -> Result<Vec<NormalizedEvent>, String> {
+    let trimmed = raw.trim_start_matches('\u{feff}').trim();
+    if trimmed.starts_with('[') {
+        let values = serde_json::from_str::<Vec<Value>>(trimmed)
+            .map_err(|error| format!("Export is not a valid JSON array: {error}"))?;
+        return values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| event_from_value(value, index + 1))
+            .collect();
+    }
+
+    if let Ok(mut document) = serde_json::from_str::<Value>(trimmed) {
+        if let Some(events) = document.get_mut("events").map(Value::take) {
+            header_version(&document)?;
+            let Value::Array(values) = events else {
+                return Err("Export document has no events array.".to_string());
+            };
+            return values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| event_from_value(value, index + 1))
+                .collect();
+        }
+    }
+
+    let mut events = Vec::new();
+    for (index, line) in trimmed.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value = serde_json::from_str::<Value>(line)
+            .map_err(|error| format!("Line {} is not valid JSON: {error}", index + 1))?;
+        if events.is_empty() && value.get("message").is_none() && header_version(&value)?.is_some()
+        {
+            continue;
+        }
+        events.push(event_from_value(value, index + 1)?);
+    }
+    Ok(events)
+}
+
+fn event_from_csv(
+    header: &[String],
+    record: &[String],
+    line: usize,
+) -> Result<NormalizedEvent, String> {
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|column| column.trim() == name)
+            .and_then(|index| record.get(index))
+            .map(|value| csv_unguard(value))
+            .unwrap_or_default()
+    };
+    let optional = |name: &str| Some(column(name)).filter(|value| !value.is_empty());
+    let event_id = match optional("eventId") {
+        Some(value) => Some(
+            value
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("Row {line} has an invalid eventId \"{value}\"."))?,
+        ),
+        None => None,
+    };
+    let mut event = NormalizedEvent {
+        id: column("id"),
+        timestamp: column("timestamp"),
+        os: column("os"),
+        log_name: column("logName"),
+        category: column("category"),
+        provider: column("provider"),
+        event_id,
+        severity: optional("severity").unwrap_or_else(|| "information".to_string()),
+        message: column("message"),
+        source_host: optional("sourceHost").unwrap_or_else(|| "localhost".to_string()),
+        imported: true,
+        user_sid: None,
+        user_name: None,
+        computer: None,
+        process_id: None,
+        thread_id: None,
+        keywords: None,
+        opcode: None,
+        task: None,
+        metadata: None,
+        raw_payload: None,
+    };
+    if event.timestamp.is_empty() {
+        return Err(format!("Row {line} is missing the timestamp column."));
+    }
+    if event.id.is_empty() {
+        event.assign_stable_id();
+    }
+    Ok(event)
+}
+
+/// Reads the CSV event export. Columns are matched by name, so reordered or extra columns are
+/// fine; the `id` and `sourceHost` columns are optional since CSV exports do not write them.
+pub fn parse_event_csv(raw: &str) -> Result<Vec<NormalizedEvent>, String> {
+    let mut records = parse_csv(raw)?.into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+    for required in ["timestamp", "message"] {
+        if !header.iter().any(|column| column.trim() == required) {
+            return Err(format!(
+                "CSV is not a Hermes event export (missing {required} column)."
+            ));
+        }
+    }
+    records
+        .enumerate()
+        .map(|(index, record)| event_from_csv(&header, &record, index + 2))
+        .collect()
+}
+
+/// Reads an export file written by this app, decompressing `.gz` and `.zip` exports. Zip
+/// archives are read from their first entry.
+pub fn read_export_file(path: &Path) -> Result<String, String> {
+    let read_error = |error: std::io::Error| format!("Failed to read export file: {error}");
+    let file = File::open(path).map_err(read_error)?;
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let mut raw = String::new();
+    match extension.as_str() {
+        "gz" => {
+            MultiGzDecoder::new(file)
+                .read_to_string(&mut raw)
+                .map_err(read_error)?;
+        }
+        "zip" => {
+            let mut archive = ZipArchive::new(file)
+                .map_err(|error| format!("Export archive is not a valid zip: {error}"))?;
+            archive
+                .by_index(0)
+                .map_err(|error| format!("Export archive is empty: {error}"))?
+                .read_to_string(&mut raw)
+                .map_err(read_error)?;
+        }
+        _ => {
+            BufReader::new(file)
+                .read_to_string(&mut raw)
+                .map_err(read_error)?;
+        }
+    }
+    Ok(raw)
+}
+
+/// UTC RFC3339 for a timestamp an export wrote in any zone; unreadable values are kept as is.
+pub fn stored_timestamp(value: &str) -> String {
+    parse_timestamp(value)
+        .map(|parsed| parsed.to_rfc3339())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Reads a CSV, JSON or NDJSON event export for loading into another install. Every event
+/// comes back flagged as imported; ids are kept so re-importing updates in place. Timestamps
+/// written in another zone are brought back to the UTC form events are stored in.
+pub fn parse_event_import(raw: &str) -> Result<Vec<NormalizedEvent>, String> {
+    let trimmed = raw.trim_start_matches('\u{feff}').trim_start();
+    let mut events = if trimmed.starts_with('[') || trimmed.starts_with('{') {
+        parse_event_export(trimmed)?
+    } else {
+        parse_event_csv(trimmed)?
+    };
+    for event in &mut events {
+        event.imported = true;
+        event.timestamp = stored_timestamp(event.timestamp.as_str());
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+    use serde_json::Value;
+
+    #[test]
+    fn exports_carry_schema_header_and_manifest() {
+        let events = vec![NormalizedEvent::new(
+            SupportedOs::Linux,
+            "journal",
+            "system",
+            "kernel",
+            None,
+            "error",
+            "disk failure",
+            "localhost",
+        )];
+
+        let json: Value = serde_json::from_slice(&build_export("json", &events).unwrap()).unwrap();
+        assert_eq!(json["schemaVersion"], EVENT_EXPORT_SCHEMA_VERSION);
+        assert_eq!(
+            json["fields"].as_array().unwrap().len(),
+            EVENT_EXPORT_FIELDS.len()
+        );
+        assert_eq!(json["events"][0]["message"], "disk failure");
+
+        let ndjson = String::from_utf8(build_export("ndjson", &events).unwrap()).unwrap();
+        let lines = ndjson.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let header: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(header["schema"], EVENT_EXPORT_SCHEMA);
+        assert_eq!(header["eventCount"], 1);
+        let event: Value = serde_json::from_str(lines[1]).unwrap();
+        for field in EVENT_EXPORT_FIELDS {
+            assert!(event.get(field).is_some(), "missing {field}");
+        }
+
+        let mut newer = event.clone();
+        newer["machine"] = Value::from("build-01");
+        let future = format!(
+            "{{\"schemaVersion\":3,\"fields\":[]}}\n{}\n",
+            serde_json::to_string(&newer).unwrap()
+        );
+        assert_eq!(parse_event_export(future.as_str()).unwrap().len(), 1);
+        assert_eq!(
+            parse_event_export(json.to_string().as_str()).unwrap().len(),
+            1
+        );
+        assert_eq!(
+            parse_event_export(ndjson.as_str()).unwrap()[0].message,
+            "disk failure"
+        );
+    }
+    #[test]
+    fn writes_gzip_and_zip_exports() {
+        use std::io::Read;
+
+        assert_eq!(
+            parse_export_format("CSV.gz").unwrap(),
+            ("csv".to_string(), Some(ExportCompression::Gzip))
+        );
+        assert!(parse_export_format("parquet.zip").is_err());
+
+        let events = vec![NormalizedEvent::new(
+            SupportedOs::Windows,
+            "System",
+            "system",
+            "disk",
+            Some(7),
+            "error",
+            "bad block",
+            "localhost",
+        )];
+        let dir = std::env::temp_dir().join(format!("hermes-export-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let expected = build_export("ndjson", &events).unwrap();
+
+        let gz_path = dir.join("events.ndjson.gz");
+        write_export_file(
+            &gz_path,
+            "ndjson",
+            Some(ExportCompression::Gzip),
+            &events,
+            DisplayTimezone::Utc,
+        )
+        .unwrap();
+        let mut unpacked = Vec::new();
+        flate2::read::GzDecoder::new(File::open(&gz_path).unwrap())
+            .read_to_end(&mut unpacked)
+            .unwrap();
+        assert_eq!(unpacked.len(), expected.len());
+
+        let zip_path = dir.join("events.ndjson.zip");
+        write_export_file(
+            &zip_path,
+            "ndjson",
+            Some(ExportCompression::Zip),
+            &events,
+            DisplayTimezone::Utc,
+        )
+        .unwrap();
+        let mut archive = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        let mut entry = archive.by_name("events.ndjson").unwrap();
+        let mut text = String::new();
+        entry.read_to_string(&mut text).unwrap();
+        assert_eq!(
+            parse_event_export(text.as_str()).unwrap()[0].message,
+            "bad block"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reimports_csv_and_json_exports_as_imported() {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "System",
+            "system",
+            "Disk",
+            Some(7),
+            "error",
+            "=bad block, \"sector\" 12\nretrying",
+            "host-a",
+        );
+        event.timestamp = "2024-03-01T10:00:00+00:00".to_string();
+
+        for format in ["csv", "json", "ndjson"] {
+            let raw =
+                String::from_utf8(build_export(format, std::slice::from_ref(&event)).unwrap())
+                    .unwrap();
+            let parsed = parse_event_import(raw.as_str()).unwrap();
+            assert_eq!(parsed.len(), 1, "{format}");
+            assert_eq!(parsed[0].message, event.message, "{format}");
+            assert_eq!(parsed[0].event_id, Some(7), "{format}");
+            assert!(parsed[0].imported, "{format}");
+        }
+
+        let extra = "note,timestamp,message,eventId\nx,2024-03-01T10:00:00Z,hello,\n";
+        let parsed = parse_event_import(extra).unwrap();
+        assert_eq!(parsed[0].message, "hello");
+        assert_eq!(parsed[0].event_id, None);
+        assert!(parsed[0].id.starts_with("evt-"));
+        assert!(parse_event_import("when,what\n1,2\n").is_err());
+    }
+
+    #[test]
+    fn writes_timestamps_in_the_chosen_zone() {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Linux,
+            "journal",
+            "system",
+            "kernel",
+            None,
+            "error",
+            "disk failure",
+            "localhost",
+        );
+        event.timestamp = "2024-07-01T10:00:00+00:00".to_string();
+        let zone = DisplayTimezone::from_name("Europe/Berlin").unwrap();
+
+        for format in ["csv", "ndjson", "txt"] {
+            let mut writer = EventExportWriter::start(Vec::new(), format, 1, zone).unwrap();
+            writer.write_event(&event).unwrap();
+            let raw = String::from_utf8(writer.finish().unwrap().0).unwrap();
+            assert!(raw.contains("2024-07-01T12:00:00+02:00"), "{format}");
+            if format != "txt" {
+                let parsed = parse_event_import(raw.as_str()).unwrap();
+                assert_eq!(parsed[0].timestamp, event.timestamp, "{format}");
+            }
+        }
+    }
+}
@@ -0,0 +1,569 @@
+use crate::db::{
+    ack_forward_events, enqueue_forward_events, get_forward_spool_state, peek_forward_events,
+    record_forward_failure, ForwardSpoolState,
+};
+use crate::diagnostics;
+use crate::elasticsearch;
+use crate::logs::NormalizedEvent;
+use crate::loki;
+use crate::settings::{
+    get_integration_secret, load_integration_settings, ForwardingFilter, IntegrationConfig,
+};
+use crate::syslog;
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Condvar, Mutex, PoisonError};
+
+const SPOOL_BATCH_SIZE: u32 = 500;
+const BASE_RETRY_SECONDS: i64 = 30;
+const MAX_RETRY_SECONDS: i64 = 30 * 60;
+const DELIVERY_TIMEOUT_SECONDS: u64 = 15;
+/// How often the delivery worker wakes without new events, to retry spools that backed off.
+const RETRY_POLL: std::time::Duration = std::time::Duration::from_secs(BASE_RETRY_SECONDS as u64);
+
+/// Set when events are spooled; the delivery worker clears it and flushes.
+static PENDING: Mutex<bool> = Mutex::new(false);
+static WAKE: Condvar = Condvar::new();
+/// Held while flushing, so the worker and a manual flush never send the same batch twice.
+static FLUSHING: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardingPreview {
+    pub integration_id: String,
+    pub scanned: usize,
+    pub matched: usize,
+    /// Counts of dropped events keyed by the first filter rule that rejected them.
+    pub excluded: BTreeMap<String, usize>,
+    pub events: Vec<NormalizedEvent>,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardingStatus {
+    pub name: String,
+    pub kind: String,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub spool: ForwardSpoolState,
+    /// Age of the oldest undelivered event, or zero when the spool is empty.
+    pub lag_seconds: Option<i64>,
+    pub next_retry_at: Option<String>,
+}
+
+pub fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "critical" => 4,
+        "error" => 3,
+        "warning" => 2,
+        "information" => 1,
+        _ => 0,
+    }
+}
+
+/// Returns `None` when the filter forwards the event, otherwise the rule that dropped it.
+pub fn exclusion_reason(
+    filter: &ForwardingFilter,
+    event: &NormalizedEvent,
+) -> Option<&'static str> {
+    if event.imported && !filter.include_imported {
+        return Some("imported");
+    }
+    if !filter.min_severity.is_empty()
+        && severity_rank(event.severity.as_str()) < severity_rank(filter.min_severity.as_str())
+    {
+        return Some("severity");
+    }
+    if !filter.categories.is_empty()
+        && !filter
+            .categories
+            .iter()
+            .any(|category| category.eq_ignore_ascii_case(event.category.as_str()))
+    {
+        return Some("category");
+    }
+    if !filter.providers.is_empty()
+        && !filter
+            .providers
+            .iter()
+            .any(|provider| provider.eq_ignore_ascii_case(event.provider.as_str()))
+    {
+        return Some("provider");
+    }
+    if !filter.event_ids.is_empty()
+        && !event
+            .event_id
+            .is_some_and(|event_id| filter.event_ids.contains(&event_id))
+    {
+        return Some("eventId");
+    }
+    let message = event.message.to_ascii_lowercase();
+    if filter
+        .exclude_patterns
+        .iter()
+        .any(|pattern| message.contains(pattern.to_ascii_lowercase().as_str()))
+    {
+        return Some("excludePattern");
+    }
+    None
+}
+
+pub fn matches_filter(filter: &ForwardingFilter, event: &NormalizedEvent) -> bool {
+    exclusion_reason(filter, event).is_none()
+}
+
+/// Applies an integration's filter to stored events without sending anything, so users can see
+/// exactly what a forwarder would emit before enabling it.
+pub fn preview_forwarding(
+    integration_id: &str,
+    filter: &ForwardingFilter,
+    events: Vec<NormalizedEvent>,
+    limit: usize,
+) -> ForwardingPreview {
+    let scanned = events.len();
+    let mut matched = 0;
+    let mut excluded = BTreeMap::new();
+    let mut kept = Vec::new();
+    for event in events {
+        match exclusion_reason(filter, &event) {
+            Some(reason) => *excluded.entry(reason.to_string()).or_insert(0) += 1,
+            None => {
+                matched += 1;
+                if kept.len() < limit {
+                    kept.push(event);
+                }
+            }
+        }
+    }
+    ForwardingPreview {
+        integration_id: integration_id.to_string(),
+        scanned,
+        matched,
+        excluded,
+        truncated: matched > kept.len(),
+        events: kept,
+    }
+}
+
+/// Exponential backoff after consecutive delivery failures, capped at 30 minutes.
+pub fn retry_delay_seconds(consecutive_failures: u32) -> i64 {
+    if consecutive_failures == 0 {
+        return 0;
+    }
+    let exponent = (consecutive_failures - 1).min(16);
+    (BASE_RETRY_SECONDS << exponent).min(MAX_RETRY_SECONDS)
+}
+
+fn next_retry_at(state: &ForwardSpoolState) -> Option<DateTime<Utc>> {
+    if state.consecutive_failures == 0 {
+        return None;
+    }
+    let last_attempt = parse_timestamp(state.last_attempt_at.as_deref()?)?;
+    Some(last_attempt + Duration::seconds(retry_delay_seconds(state.consecutive_failures)))
+}
+
+fn lag_seconds(state: &ForwardSpoolState, now: DateTime<Utc>) -> Option<i64> {
+    if state.queued == 0 {
+        return Some(0);
+    }
+    let oldest = parse_timestamp(state.oldest_queued_timestamp.as_deref()?)?;
+    Some((now - oldest).num_seconds().max(0))
+}
+
+/// Applies the integration's basic or API-key auth. `api_key_scheme` is the Authorization scheme
+/// the destination expects for keys, e.g. `ApiKey` for Elasticsearch or `Bearer` for Loki.
+pub fn authorize(
+    request: reqwest::blocking::RequestBuilder,
+    integration: &IntegrationConfig,
+    api_key_scheme: &str,
+) -> Result<reqwest::blocking::RequestBuilder, String> {
+    match integration.auth_type.as_str() {
+        "basic" => {
+            let password = get_integration_secret(integration.id.as_str())?;
+            Ok(request.basic_auth(integration.username.as_str(), password))
+        }
+        "apiKey" => {
+            let key = get_integration_secret(integration.id.as_str())?
+                .ok_or_else(|| format!("No API key is stored for {}.", integration.name))?;
+            Ok(request.header("Authorization", format!("{api_key_scheme} {key}")))
+        }
+        _ => Ok(request),
+    }
+}
+
+fn deliver(
+    client: &reqwest::blocking::Client,
+    integration: &IntegrationConfig,
+    events: &[NormalizedEvent],
+) -> Result<(), String> {
+    match integration.kind.as_str() {
+        "webhook" => {
+            let response = client
+                .post(integration.endpoint.as_str())
+                .json(&serde_json::json!({ "events": events }))
+                .send()
+                .map_err(|error| format!("Webhook request failed: {error}"))?;
+            if !response.status().is_success() {
+                return Err(format!("Webhook returned HTTP {}", response.status()));
+            }
+            Ok(())
+        }
+        "elasticsearch" => {
+            let result = elasticsearch::send_bulk(client, integration, events)?;
+            // Rejected documents fail the same way on every retry, so they are logged, not requeued.
+            if result.failed > 0 {
+                diagnostics::warn(
+                    "forwarding",
+                    format!(
+                        "{} rejected {} of {} events: {}",
+                        integration.name,
+                        result.failed,
+                        events.len(),
+                        result.errors.join("; ")
+                    ),
+                );
+            }
+            Ok(())
+        }
+        "loki" => loki::push(client, integration, events),
+        "syslog" => syslog::send_events(
+            integration,
+            events,
+            std::time::Duration::from_secs(DELIVERY_TIMEOUT_SECONDS),
+        ),
+        other => Err(format!(
+            "No sender is available for {other} integrations yet"
+        )),
+    }
+}
+
+/// Orders events by their parsed time, so mixed offsets deliver in the order they happened.
+/// Unparseable timestamps go first.
+fn sort_oldest_first(events: &mut [NormalizedEvent]) {
+    events.sort_by_cached_key(|event| {
+        parse_timestamp(event.timestamp.as_str()).map(|value| value.timestamp_millis())
+    });
+}
+
+/// Queues freshly synced events for every enabled integration whose filter accepts them. The spool
+/// lives in SQLite so events survive restarts while a destination is unreachable.
+pub fn spool_events(events: &[NormalizedEvent]) {
+    if events.is_empty() {
+        return;
+    }
+    let settings = load_integration_settings();
+    let now = Utc::now().to_rfc3339();
+    for integration in settings
+        .integrations
+        .iter()
+        .filter(|integration| integration.enabled)
+    {
+        let mut accepted: Vec<NormalizedEvent> = events
+            .iter()
+            .filter(|event| matches_filter(&integration.filter, event))
+            .cloned()
+            .collect();
+        if accepted.is_empty() {
+            continue;
+        }
+        sort_oldest_first(accepted.as_mut_slice());
+        match enqueue_forward_events(
+            integration.id.as_str(),
+            accepted.as_slice(),
+            integration.spool_max_events,
+            now.as_str(),
+        ) {
+            Ok(0) => {}
+            Ok(dropped) => diagnostics::warn(
+                "forwarding",
+                format!(
+                    "Spool for {} is full; dropped {dropped} oldest events",
+                    integration.name
+                ),
+            ),
+            Err(error) => diagnostics::warn(
+                "forwarding",
+                format!("Failed to spool events for {}: {error}", integration.name),
+            ),
+        }
+    }
+}
+
+/// Delivers spooled events oldest-first in batches. A failed batch stays queued and the integration
+/// backs off before the next attempt unless `force` is set.
+pub fn flush_spools(force: bool) {
+    let _flushing = FLUSHING.lock().unwrap_or_else(PoisonError::into_inner);
+    let settings = load_integration_settings();
+    let enabled: Vec<&IntegrationConfig> = settings
+        .integrations
+        .iter()
+        .filter(|integration| integration.enabled)
+        .collect();
+    if enabled.is_empty() {
+        return;
+    }
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(DELIVERY_TIMEOUT_SECONDS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(error) => {
+            diagnostics::warn(
+                "forwarding",
+                format!("Failed to build HTTP client: {error}"),
+            );
+            return;
+        }
+    };
+
+    for integration in enabled {
+        let id = integration.id.as_str();
+        if !force {
+            let retry_at = get_forward_spool_state(id)
+                .ok()
+                .and_then(|state| next_retry_at(&state));
+            if retry_at.is_some_and(|retry_at| Utc::now() < retry_at) {
+                continue;
+            }
+        }
+        loop {
+            let batch = match peek_forward_events(id, SPOOL_BATCH_SIZE) {
+                Ok(batch) => batch,
+                Err(error) => {
+                    diagnostics::warn(
+                        "forwarding",
+                        format!("Failed to read spool for {}: {error}", integration.name),
+                    );
+                    break;
+                }
+            };
+            let Some((last_seq, last_event)) = batch.last() else {
+                break;
+            };
+            let last_seq = *last_seq;
+            let last_timestamp = last_event.timestamp.clone();
+            let events: Vec<NormalizedEvent> = batch.into_iter().map(|(_, event)| event).collect();
+            let now = Utc::now().to_rfc3339();
+            match deliver(&client, integration, events.as_slice()) {
+                Ok(()) => {
+                    if let Err(error) = ack_forward_events(
+                        id,
+                        last_seq,
+                        events.len() as u64,
+                        last_timestamp.as_str(),
+                        now.as_str(),
+                    ) {
+                        diagnostics::warn(
+                            "forwarding",
+                            format!(
+                                "Failed to acknowledge delivery for {}: {error}",
+                                integration.name
+                            ),
+                        );
+                        break;
+                    }
+                    if events.len() < SPOOL_BATCH_SIZE as usize {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    diagnostics::warn(
+                        "forwarding",
+                        format!("Delivery to {} failed: {error}", integration.name),
+                    );
+                    let _ = record_forward_failure(id, error.as_str(), now.as_str());
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Spools a synced batch and wakes the delivery worker, so a slow destination never holds up
+/// the sync that produced the events.
+pub fn forward_events(events: &[NormalizedEvent]) {
+    spool_events(events);
+    *PENDING.lock().unwrap_or_else(PoisonError::into_inner) = true;
+    WAKE.notify_one();
+}
+
+/// Starts the delivery worker, which flushes the spools whenever events are queued and every
+/// `RETRY_POLL` otherwise.
+pub fn start() {
+    let spawned = std::thread::Builder::new()
+        .name("forwarding".to_string())
+        .spawn(run);
+    if let Err(error) = spawned {
+        diagnostics::warn(
+            "forwarding",
+            format!("Failed to start forwarding thread: {error}"),
+        );
+    }
+}
+
+fn run() {
+    loop {
+        {
+            let pending = PENDING.lock().unwrap_or_else(PoisonError::into_inner);
+            let (mut pending, _) = WAKE
+                .wait_timeout_while(pending, RETRY_POLL, |pending| !*pending)
+                .unwrap_or_else(PoisonError::into_inner);
+            *pending = false;
+        }
+        flush_spools(false);
+    }
+}
+
+pub fn forwarding_status() -> Result<Vec<ForwardingStatus>, String> {
+    let now = Utc::now();
+    load_integration_settings()
+        .integrations
+        .into_iter()
+        .map(|integration| {
+            let spool = get_forward_spool_state(integration.id.as_str())?;
+            Ok(ForwardingStatus {
+                name: integration.name,
+                kind: integration.kind,
+                enabled: integration.enabled,
+                lag_seconds: lag_seconds(&spool, now),
+                next_retry_at: next_retry_at(&spool).map(|value| value.to_rfc3339()),
+                spool,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        id: &str,
+        severity: &str,
+        category: &str,
+        event_id: Option<u32>,
+        message: &str,
+    ) -> NormalizedEvent {
+        NormalizedEvent {
+            id: id.to_string(),
+            timestamp: "2026-03-01T10:00:00Z".to_string(),
+            os: "windows".to_string(),
+            log_name: "System".to_string(),
+            category: category.to_string(),
+            provider: "Service Control Manager".to_string(),
+            event_id,
+            severity: severity.to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn reports_first_rejecting_rule() {
+        let filter = ForwardingFilter {
+            min_severity: "warning".to_string(),
+            categories: vec!["system".to_string()],
+            exclude_patterns: vec!["password".to_string()],
+            ..ForwardingFilter::default()
+        };
+        let events = vec![
+            event(
+                "ok",
+                "error",
+                "system",
+                Some(7000),
+                "Service failed to start",
+            ),
+            event(
+                "info",
+                "information",
+                "system",
+                Some(7036),
+                "Service entered running state",
+            ),
+            event(
+                "app",
+                "error",
+                "application",
+                Some(1000),
+                "Faulting application",
+            ),
+            event(
+                "secret",
+                "warning",
+                "system",
+                Some(7009),
+                "Logon with Password=hunter2 failed",
+            ),
+        ];
+
+        let preview = preview_forwarding("integration-1", &filter, events, 10);
+        assert_eq!(preview.scanned, 4);
+        assert_eq!(preview.matched, 1);
+        assert_eq!(preview.events[0].id, "ok");
+        assert_eq!(preview.excluded.get("severity"), Some(&1));
+        assert_eq!(preview.excluded.get("category"), Some(&1));
+        assert_eq!(preview.excluded.get("excludePattern"), Some(&1));
+    }
+
+    #[test]
+    fn skips_imported_events_and_truncates() {
+        let filter = ForwardingFilter::default();
+        let mut imported = event("imported", "error", "system", None, "From an archive");
+        imported.imported = true;
+        let events = vec![
+            event("a", "error", "system", None, "a"),
+            event("b", "error", "system", None, "b"),
+            imported,
+        ];
+
+        let preview = preview_forwarding("integration-1", &filter, events, 1);
+        assert_eq!(preview.matched, 2);
+        assert_eq!(preview.events.len(), 1);
+        assert!(preview.truncated);
+        assert_eq!(preview.excluded.get("imported"), Some(&1));
+    }
+
+    #[test]
+    fn spools_events_in_time_order_across_offsets() {
+        let mut events = vec![
+            event("utc", "error", "system", None, "a"),
+            event("offset", "error", "system", None, "b"),
+            event("bad", "error", "system", None, "c"),
+        ];
+        events[0].timestamp = "2026-03-01T09:00:00Z".to_string();
+        // 08:00 UTC, but sorts after 09:00Z as text.
+        events[1].timestamp = "2026-03-01T10:00:00+02:00".to_string();
+        events[2].timestamp = "not a time".to_string();
+
+        sort_oldest_first(events.as_mut_slice());
+        let ids: Vec<&str> = events.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, vec!["bad", "offset", "utc"]);
+    }
+
+    #[test]
+    fn backs_off_exponentially_with_cap() {
+        assert_eq!(retry_delay_seconds(0), 0);
+        assert_eq!(retry_delay_seconds(1), 30);
+        assert_eq!(retry_delay_seconds(3), 120);
+        assert_eq!(retry_delay_seconds(40), MAX_RETRY_SECONDS);
+
+        let state = ForwardSpoolState {
+            queued: 3,
+            oldest_queued_timestamp: Some("2026-03-01T10:00:00Z".to_string()),
+            last_attempt_at: Some("2026-03-01T10:05:00Z".to_string()),
+            consecutive_failures: 2,
+            ..ForwardSpoolState::default()
+        };
+        let now = parse_timestamp("2026-03-01T10:06:00Z").unwrap();
+        assert_eq!(lag_seconds(&state, now), Some(360));
+        assert_eq!(
+            next_retry_at(&state).map(|value| value.to_rfc3339()),
+            Some("2026-03-01T10:06:00+00:00".to_string())
+        );
+    }
+}
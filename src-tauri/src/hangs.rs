@@ -0,0 +1,417 @@
+use crate::crash::CrashRecord;
+use crate::db::{self, MetricSample};
+use crate::logs::NormalizedEvent;
+use crate::perf::{CPU_METRIC, DISK_BUSY_METRIC, PERF_SOURCE};
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const PRESSURE_WINDOW_MINUTES: i64 = 5;
+/// Load snapshots are only taken with each refresh, so they are looked for further out than
+/// pressure events.
+const LOAD_WINDOW_MINUTES: i64 = 15;
+/// Peaks at or above these are listed as pressure signals.
+const HIGH_CPU_PERCENT: f64 = 90.0;
+const HIGH_DISK_BUSY_PERCENT: f64 = 90.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppHangOccurrence {
+    pub timestamp: String,
+    pub source: String,
+    pub version: Option<String>,
+    pub record_id: String,
+    pub source_host: String,
+    pub pressure_signals: Vec<String>,
+    /// Highest CPU use among the `perf` snapshots taken around the hang.
+    pub peak_cpu_percent: Option<f64>,
+    /// Busiest disk among those snapshots, as `(device, busy percent)`.
+    pub peak_disk_busy: Option<(String, f64)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppHangGroup {
+    pub application: String,
+    pub hang_count: usize,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub versions: Vec<String>,
+    pub occurrences_with_pressure: usize,
+    pub occurrences: Vec<AppHangOccurrence>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppHangReport {
+    pub total_hangs: usize,
+    pub applications: Vec<AppHangGroup>,
+    pub warnings: Vec<String>,
+}
+
+pub fn is_app_hang_event(event: &NormalizedEvent) -> bool {
+    event.os == "windows"
+        && event.event_id == Some(1002)
+        && event.provider.eq_ignore_ascii_case("Application Hang")
+}
+
+/// Returns true for WER problem types that describe hangs rather than crashes.
+pub fn is_hang_problem_type(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    lower.starts_with("apphang") || lower.contains("application hang")
+}
+
+/// Pulls `(application, version)` from a 1002 message, either the rendered text
+/// ("The program foo.exe version 1.2 stopped interacting with Windows") or raw event data.
+fn parse_hang_message(message: &str) -> Option<(String, Option<String>)> {
+    if let Some(rest) = message.split("The program ").nth(1) {
+        let (app, after) = rest.split_once(" version ")?;
+        let version = after
+            .split_whitespace()
+            .next()
+            .map(ToString::to_string);
+        return Some((app.trim().to_string(), version));
+    }
+
+    let data = message.strip_prefix("Data: ")?;
+    let mut values = data.split(", ").map(|pair| {
+        pair.split_once('=')
+            .map(|(_, value)| value.trim())
+            .unwrap_or(pair.trim())
+    });
+    let app = values.next()?.to_string();
+    let version = values.next().map(ToString::to_string);
+    Some((app, version))
+}
+
+fn pressure_signal(event: &NormalizedEvent) -> Option<String> {
+    let provider = event.provider.to_ascii_lowercase();
+    let label = if provider.contains("resource-exhaustion") {
+        "Low virtual memory"
+    } else if (provider == "disk" || provider.contains("stornvme") || provider.contains("storahci"))
+        && matches!(event.event_id, Some(7) | Some(11) | Some(51) | Some(129) | Some(153))
+    {
+        "Disk I/O retries or resets"
+    } else if provider.contains("ntfs") && event.severity != "information" {
+        "File system errors"
+    } else if provider.contains("display") && event.event_id == Some(4101) {
+        "Display driver timeout"
+    } else if provider.contains("kernel-processor-power") && event.event_id == Some(37) {
+        "CPU throttled by firmware"
+    } else {
+        return None;
+    };
+    Some(format!("{label} at {} ({})", event.timestamp, event.provider))
+}
+
+fn application_key(value: &str) -> String {
+    value
+        .rsplit(['\\', '/'])
+        .next()
+        .unwrap_or(value)
+        .trim()
+        .to_ascii_lowercase()
+}
+
+pub fn analyze_app_hangs(events: &[NormalizedEvent], crashes: &[CrashRecord]) -> AppHangReport {
+    let pressure: Vec<(DateTime<Utc>, String)> = events
+        .iter()
+        .filter_map(|event| {
+            let signal = pressure_signal(event)?;
+            Some((parse_timestamp(event.timestamp.as_str())?, signal))
+        })
+        .collect();
+    let window = chrono::Duration::minutes(PRESSURE_WINDOW_MINUTES);
+    let signals_near = |timestamp: &str| -> Vec<String> {
+        let Some(at) = parse_timestamp(timestamp) else {
+            return Vec::new();
+        };
+        pressure
+            .iter()
+            .filter(|(signal_at, _)| (*signal_at - at).abs() <= window)
+            .map(|(_, signal)| signal.clone())
+            .collect()
+    };
+
+    let mut grouped: BTreeMap<String, (String, Vec<AppHangOccurrence>)> = BTreeMap::new();
+    for event in events.iter().filter(|event| is_app_hang_event(event)) {
+        let Some((app, version)) = parse_hang_message(event.message.as_str()) else {
+            continue;
+        };
+        let entry = grouped
+            .entry(application_key(app.as_str()))
+            .or_insert_with(|| (app.clone(), Vec::new()));
+        entry.1.push(AppHangOccurrence {
+            timestamp: event.timestamp.clone(),
+            source: "Application Hang 1002".to_string(),
+            version,
+            record_id: event.id.clone(),
+            source_host: event.source_host.clone(),
+            pressure_signals: signals_near(event.timestamp.as_str()),
+            peak_cpu_percent: None,
+            peak_disk_busy: None,
+        });
+    }
+    for crash in crashes
+        .iter()
+        .filter(|crash| is_hang_problem_type(crash.crash_type.as_str()))
+    {
+        let app = crash
+            .suspected_component
+            .clone()
+            .unwrap_or_else(|| crash.summary.clone());
+        let entry = grouped
+            .entry(application_key(app.as_str()))
+            .or_insert_with(|| (app.clone(), Vec::new()));
+        // WER often records the same hang that already produced a 1002 event.
+        let duplicate = entry.1.iter().any(|existing| {
+            match (
+                parse_timestamp(existing.timestamp.as_str()),
+                parse_timestamp(crash.timestamp.as_str()),
+            ) {
+                (Some(left), Some(right)) => (left - right).abs() <= chrono::Duration::minutes(2),
+                _ => false,
+            }
+        });
+        if duplicate {
+            continue;
+        }
+        entry.1.push(AppHangOccurrence {
+            timestamp: crash.timestamp.clone(),
+            source: format!("WER {}", crash.crash_type),
+            version: None,
+            record_id: crash.id.clone(),
+            source_host: crash.source_host.clone(),
+            pressure_signals: signals_near(crash.timestamp.as_str()),
+            peak_cpu_percent: None,
+            peak_disk_busy: None,
+        });
+    }
+
+    let mut applications: Vec<AppHangGroup> = grouped
+        .into_values()
+        .map(|(application, mut occurrences)| {
+            occurrences.sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+            let mut versions: Vec<String> = occurrences
+                .iter()
+                .filter_map(|occurrence| occurrence.version.clone())
+                .collect();
+            versions.sort();
+            versions.dedup();
+            AppHangGroup {
+                application,
+                hang_count: occurrences.len(),
+                first_seen: occurrences
+                    .last()
+                    .map(|occurrence| occurrence.timestamp.clone())
+                    .unwrap_or_default(),
+                last_seen: occurrences
+                    .first()
+                    .map(|occurrence| occurrence.timestamp.clone())
+                    .unwrap_or_default(),
+                versions,
+                occurrences_with_pressure: occurrences
+                    .iter()
+                    .filter(|occurrence| !occurrence.pressure_signals.is_empty())
+                    .count(),
+                occurrences,
+            }
+        })
+        .collect();
+    applications.sort_by(|left, right| {
+        right
+            .hang_count
+            .cmp(&left.hang_count)
+            .then_with(|| right.last_seen.cmp(&left.last_seen))
+    });
+
+    AppHangReport {
+        total_hangs: applications.iter().map(|group| group.hang_count).sum(),
+        applications,
+        warnings: Vec::new(),
+    }
+}
+
+/// Records the CPU and disk peaks among the `perf` samples taken on the hang's host within the
+/// load window, and adds a pressure signal for any at or above the high-load thresholds.
+fn record_load_peaks(occurrence: &mut AppHangOccurrence, samples: &[MetricSample]) {
+    let Some(at) = parse_timestamp(occurrence.timestamp.as_str()) else {
+        return;
+    };
+    let window = chrono::Duration::minutes(LOAD_WINDOW_MINUTES);
+    let nearby = samples
+        .iter()
+        .filter(|sample| sample.source_host == occurrence.source_host)
+        .filter(|sample| {
+            parse_timestamp(sample.timestamp.as_str())
+                .is_some_and(|taken| taken >= at - window && taken <= at + window)
+        })
+        .collect::<Vec<_>>();
+    let peak = |name: &str| {
+        nearby
+            .iter()
+            .filter(|sample| sample.name == name)
+            .max_by(|left, right| left.value.total_cmp(&right.value))
+    };
+    if let Some(cpu) = peak(CPU_METRIC) {
+        occurrence.peak_cpu_percent = Some(cpu.value);
+        if cpu.value >= HIGH_CPU_PERCENT {
+            occurrence
+                .pressure_signals
+                .push(format!("CPU at {}% at {}", cpu.value, cpu.timestamp));
+        }
+    }
+    if let Some(disk) = peak(DISK_BUSY_METRIC) {
+        occurrence.peak_disk_busy = Some((disk.device.clone(), disk.value));
+        if disk.value >= HIGH_DISK_BUSY_PERCENT {
+            occurrence.pressure_signals.push(format!(
+                "Disk {} {}% busy at {}",
+                disk.device, disk.value, disk.timestamp
+            ));
+        }
+    }
+}
+
+fn count_pressure(group: &mut AppHangGroup) {
+    group.occurrences_with_pressure = group
+        .occurrences
+        .iter()
+        .filter(|occurrence| !occurrence.pressure_signals.is_empty())
+        .count();
+}
+
+/// Reads the stored `perf` snapshots around each hang and records the load peaks among them.
+pub fn attach_load_peaks(report: &mut AppHangReport) -> Result<(), String> {
+    let window = chrono::Duration::minutes(LOAD_WINDOW_MINUTES);
+    for group in &mut report.applications {
+        for occurrence in &mut group.occurrences {
+            let Some(at) = parse_timestamp(occurrence.timestamp.as_str()) else {
+                continue;
+            };
+            let samples = db::get_metrics(
+                PERF_SOURCE,
+                (at - window).to_rfc3339().as_str(),
+                (at + window).to_rfc3339().as_str(),
+                1000,
+                Some(occurrence.source_host.as_str()),
+            )?;
+            record_load_peaks(occurrence, samples.as_slice());
+        }
+        count_pressure(group);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(timestamp: &str, provider: &str, event_id: u32, message: &str) -> NormalizedEvent {
+        NormalizedEvent {
+            id: format!("evt-{timestamp}-{event_id}"),
+            timestamp: timestamp.to_string(),
+            os: "windows".to_string(),
+            log_name: "Application".to_string(),
+            category: "application".to_string(),
+            provider: provider.to_string(),
+            event_id: Some(event_id),
+            severity: "error".to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn groups_hangs_by_application_with_pressure() {
+        let events = vec![
+            event(
+                "2026-03-01T10:00:00Z",
+                "Application Hang",
+                1002,
+                "The program OUTLOOK.EXE version 16.0.1 stopped interacting with Windows and was closed.",
+            ),
+            event(
+                "2026-03-02T10:00:00Z",
+                "Application Hang",
+                1002,
+                "The program outlook.exe version 16.0.2 stopped interacting with Windows and was closed.",
+            ),
+            event("2026-03-02T09:58:00Z", "disk", 153, "The IO operation was retried."),
+        ];
+
+        let report = analyze_app_hangs(&events, &[]);
+        assert_eq!(report.total_hangs, 2);
+        assert_eq!(report.applications.len(), 1);
+        let group = &report.applications[0];
+        assert_eq!(group.versions, vec!["16.0.1".to_string(), "16.0.2".to_string()]);
+        assert_eq!(group.occurrences_with_pressure, 1);
+    }
+
+    #[test]
+    fn records_load_peaks_from_nearby_snapshots() {
+        let snapshot = |timestamp: &str, device: &str, name: &str, value: f64| MetricSample {
+            timestamp: timestamp.to_string(),
+            source_host: "localhost".to_string(),
+            source: PERF_SOURCE.to_string(),
+            device: device.to_string(),
+            name: name.to_string(),
+            value,
+            unit: Some("%".to_string()),
+            status: None,
+        };
+        let samples = vec![
+            snapshot("2026-03-02T09:50:00+00:00", "cpu", CPU_METRIC, 97.0),
+            snapshot("2026-03-02T09:50:00+00:00", "0 C:", DISK_BUSY_METRIC, 40.0),
+            snapshot("2026-03-02T10:05:00+00:00", "0 C:", DISK_BUSY_METRIC, 55.0),
+            snapshot("2026-03-01T12:00:00+00:00", "cpu", CPU_METRIC, 100.0),
+            MetricSample {
+                source_host: "pc02".to_string(),
+                ..snapshot("2026-03-01T10:00:00+00:00", "cpu", CPU_METRIC, 99.0)
+            },
+        ];
+        let events = vec![
+            event(
+                "2026-03-01T10:00:00Z",
+                "Application Hang",
+                1002,
+                "The program excel.exe version 16.0.1 stopped interacting with Windows.",
+            ),
+            event(
+                "2026-03-02T10:00:00Z",
+                "Application Hang",
+                1002,
+                "The program excel.exe version 16.0.1 stopped interacting with Windows.",
+            ),
+        ];
+
+        let mut report = analyze_app_hangs(&events, &[]);
+        let group = &mut report.applications[0];
+        for occurrence in &mut group.occurrences {
+            record_load_peaks(occurrence, samples.as_slice());
+        }
+        count_pressure(group);
+
+        assert_eq!(group.occurrences[0].peak_cpu_percent, Some(97.0));
+        assert_eq!(
+            group.occurrences[0].peak_disk_busy,
+            Some(("0 C:".to_string(), 55.0))
+        );
+        assert_eq!(
+            group.occurrences[0].pressure_signals,
+            vec!["CPU at 97% at 2026-03-02T09:50:00+00:00".to_string()]
+        );
+        // The snapshot two hours after the first hang is outside its window, and the one at the
+        // same time was taken on another host.
+        assert_eq!(group.occurrences[1].peak_cpu_percent, None);
+        assert_eq!(group.occurrences_with_pressure, 1);
+    }
+
+    #[test]
+    fn detects_hang_problem_types() {
+        assert!(is_hang_problem_type("AppHangB1"));
+        assert!(is_hang_problem_type("AppHangXProcB1"));
+        assert!(!is_hang_problem_type("APPCRASH"));
+    }
+}
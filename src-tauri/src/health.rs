@@ -0,0 +1,323 @@
+use crate::db::db_path;
+use crate::diagnostics;
+use crate::forwarding::{forwarding_status, ForwardingStatus};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+pub const HEALTH_ALERT_EVENT: &str = "hla://health-alert";
+
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const SYNC_FAILURE_WARNING: u32 = 3;
+const SYNC_FAILURE_CRITICAL: u32 = 6;
+const FORWARDER_LAG_WARNING_SECONDS: i64 = 60 * 60;
+const FORWARDER_FAILURE_WARNING: u32 = 5;
+const DISK_FREE_WARNING_PERCENT: f64 = 10.0;
+const DISK_FREE_CRITICAL_PERCENT: f64 = 3.0;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncHealth {
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<String>,
+    pub last_failure_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    pub path: String,
+    pub available_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthFinding {
+    /// Stable key so repeated checks can tell new problems from ongoing ones.
+    pub id: String,
+    pub severity: String,
+    pub title: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    pub status: String,
+    pub checked_at: String,
+    pub sync: SyncHealth,
+    pub disk: Option<DiskUsage>,
+    pub findings: Vec<HealthFinding>,
+}
+
+static SYNC_HEALTH: Mutex<SyncHealth> = Mutex::new(SyncHealth {
+    consecutive_failures: 0,
+    last_error: None,
+    last_success_at: None,
+    last_failure_at: None,
+});
+
+/// Records the outcome of a sync so repeated failures surface as a health finding.
+pub fn record_sync_result(error: Option<&str>) {
+    let Ok(mut health) = SYNC_HEALTH.lock() else {
+        return;
+    };
+    let now = Utc::now().to_rfc3339();
+    match error {
+        Some(error) => {
+            health.consecutive_failures += 1;
+            health.last_error = Some(error.to_string());
+            health.last_failure_at = Some(now);
+        }
+        None => {
+            health.consecutive_failures = 0;
+            health.last_error = None;
+            health.last_success_at = Some(now);
+        }
+    }
+}
+
+fn sync_health() -> SyncHealth {
+    SYNC_HEALTH
+        .lock()
+        .map(|health| health.clone())
+        .unwrap_or_default()
+}
+
+fn database_disk_usage() -> Option<DiskUsage> {
+    let path = db_path().ok()?;
+    let volume = path.parent()?;
+    Some(DiskUsage {
+        path: volume.display().to_string(),
+        available_bytes: fs2::available_space(volume).ok()?,
+        total_bytes: fs2::total_space(volume).ok()?,
+    })
+}
+
+fn finding(id: String, severity: &str, title: String, detail: String) -> HealthFinding {
+    HealthFinding {
+        id,
+        severity: severity.to_string(),
+        title,
+        detail,
+    }
+}
+
+/// Turns the collected health signals into findings, most severe first.
+pub fn evaluate_health(
+    sync: &SyncHealth,
+    forwarders: &[ForwardingStatus],
+    disk: Option<&DiskUsage>,
+) -> Vec<HealthFinding> {
+    let mut findings = Vec::new();
+
+    if sync.consecutive_failures >= SYNC_FAILURE_WARNING {
+        let severity = if sync.consecutive_failures >= SYNC_FAILURE_CRITICAL {
+            "critical"
+        } else {
+            "warning"
+        };
+        findings.push(finding(
+            "sync-failures".to_string(),
+            severity,
+            format!("{} syncs failed in a row", sync.consecutive_failures),
+            sync.last_error
+                .clone()
+                .unwrap_or_else(|| "No error details were recorded.".to_string()),
+        ));
+    }
+
+    for forwarder in forwarders.iter().filter(|forwarder| forwarder.enabled) {
+        let lag = forwarder.lag_seconds.unwrap_or(0);
+        if lag >= FORWARDER_LAG_WARNING_SECONDS
+            || forwarder.spool.consecutive_failures >= FORWARDER_FAILURE_WARNING
+        {
+            let mut detail = format!(
+                "{} events queued, oldest {} minutes behind.",
+                forwarder.spool.queued,
+                lag / 60
+            );
+            if let Some(error) = forwarder.spool.last_error.as_deref() {
+                detail.push_str(format!(" Last error: {error}").as_str());
+            }
+            findings.push(finding(
+                format!("forwarder-lag:{}", forwarder.spool.integration_id),
+                "warning",
+                format!("{} is falling behind", forwarder.name),
+                detail,
+            ));
+        }
+        if forwarder.spool.dropped_total > 0 {
+            findings.push(finding(
+                format!("forwarder-dropped:{}", forwarder.spool.integration_id),
+                "warning",
+                format!("{} spool overflowed", forwarder.name),
+                format!(
+                    "{} events were dropped because the spool limit was reached.",
+                    forwarder.spool.dropped_total
+                ),
+            ));
+        }
+    }
+
+    if let Some(disk) = disk.filter(|disk| disk.total_bytes > 0) {
+        let free_percent = disk.available_bytes as f64 * 100.0 / disk.total_bytes as f64;
+        let severity = if free_percent < DISK_FREE_CRITICAL_PERCENT {
+            Some("critical")
+        } else if free_percent < DISK_FREE_WARNING_PERCENT {
+            Some("warning")
+        } else {
+            None
+        };
+        if let Some(severity) = severity {
+            findings.push(finding(
+                "database-disk".to_string(),
+                severity,
+                "Database disk is nearly full".to_string(),
+                format!(
+                    "{:.1}% free ({} MB) on {}. New events may fail to save.",
+                    free_percent,
+                    disk.available_bytes / (1024 * 1024),
+                    disk.path
+                ),
+            ));
+        }
+    }
+
+    findings.sort_by_key(|finding| finding.severity != "critical");
+    findings
+}
+
+pub fn check_health() -> HealthReport {
+    let sync = sync_health();
+    let forwarders = forwarding_status().unwrap_or_else(|error| {
+        diagnostics::warn(
+            "health",
+            format!("Failed to read forwarding status: {error}"),
+        );
+        Vec::new()
+    });
+    let disk = database_disk_usage();
+    let findings = evaluate_health(&sync, forwarders.as_slice(), disk.as_ref());
+    let status = if findings
+        .iter()
+        .any(|finding| finding.severity == "critical")
+    {
+        "critical"
+    } else if findings.is_empty() {
+        "ok"
+    } else {
+        "warning"
+    };
+    HealthReport {
+        status: status.to_string(),
+        checked_at: Utc::now().to_rfc3339(),
+        sync,
+        disk,
+        findings,
+    }
+}
+
+/// Starts the background self-monitor. It emits `hla://health-alert` whenever a finding appears
+/// that was not present in the previous check.
+pub fn start(app: AppHandle) {
+    let spawned = std::thread::Builder::new()
+        .name("health-monitor".to_string())
+        .spawn(move || run(app));
+    if let Err(error) = spawned {
+        diagnostics::warn(
+            "health",
+            format!("Failed to start health monitor thread: {error}"),
+        );
+    }
+}
+
+fn run(app: AppHandle) {
+    std::thread::sleep(FIRST_CHECK_DELAY);
+    let mut previous: BTreeSet<String> = BTreeSet::new();
+    loop {
+        let report = check_health();
+        let current: BTreeSet<String> = report
+            .findings
+            .iter()
+            .map(|finding| finding.id.clone())
+            .collect();
+        let new_findings: Vec<&HealthFinding> = report
+            .findings
+            .iter()
+            .filter(|finding| !previous.contains(&finding.id))
+            .collect();
+        for finding in &new_findings {
+            diagnostics::warn("health", format!("{}: {}", finding.title, finding.detail));
+        }
+        if !new_findings.is_empty() {
+            if let Err(error) = app.emit(HEALTH_ALERT_EVENT, &report) {
+                diagnostics::warn("health", format!("Failed to emit health alert: {error}"));
+            }
+        }
+        previous = current;
+        std::thread::sleep(CHECK_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::ForwardSpoolState;
+
+    #[test]
+    fn reports_sync_forwarder_and_disk_problems() {
+        let sync = SyncHealth {
+            consecutive_failures: 3,
+            last_error: Some("Refresh collection failed".to_string()),
+            ..SyncHealth::default()
+        };
+        let forwarders = vec![ForwardingStatus {
+            name: "SIEM".to_string(),
+            kind: "webhook".to_string(),
+            enabled: true,
+            spool: ForwardSpoolState {
+                integration_id: "integration-1".to_string(),
+                queued: 120,
+                last_error: Some("Webhook returned HTTP 503".to_string()),
+                ..ForwardSpoolState::default()
+            },
+            lag_seconds: Some(2 * 60 * 60),
+            next_retry_at: None,
+        }];
+        let disk = DiskUsage {
+            path: "/data".to_string(),
+            available_bytes: 2 * 1024 * 1024 * 1024,
+            total_bytes: 100 * 1024 * 1024 * 1024,
+        };
+
+        let findings = evaluate_health(&sync, forwarders.as_slice(), Some(&disk));
+        let ids: Vec<&str> = findings.iter().map(|finding| finding.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "database-disk",
+                "sync-failures",
+                "forwarder-lag:integration-1"
+            ]
+        );
+        assert_eq!(findings[0].severity, "critical");
+        assert!(findings[2].detail.contains("120 minutes behind"));
+    }
+
+    #[test]
+    fn healthy_signals_produce_no_findings() {
+        let disk = DiskUsage {
+            path: "/data".to_string(),
+            available_bytes: 50,
+            total_bytes: 100,
+        };
+        assert!(evaluate_health(&SyncHealth::default(), &[], Some(&disk)).is_empty());
+    }
+}
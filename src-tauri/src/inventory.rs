@@ -0,0 +1,312 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Snapshots older than this are refreshed automatically during a local sync.
+pub const SNAPSHOT_INTERVAL_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryItem {
+    pub kind: String,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub version: Option<String>,
+    pub state: Option<String>,
+    pub start_mode: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventorySnapshotSummary {
+    pub id: String,
+    pub timestamp: String,
+    pub source_host: String,
+    pub item_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryChange {
+    pub kind: String,
+    pub name: String,
+    pub change: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryChangeSet {
+    pub from_timestamp: String,
+    pub to_timestamp: String,
+    pub changes: Vec<InventoryChange>,
+}
+
+impl InventoryItem {
+    fn new(kind: &str, name: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            display_name: None,
+            version: None,
+            state: None,
+            start_mode: None,
+        }
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToString::to_string)
+}
+
+fn changed_field(
+    changes: &mut Vec<InventoryChange>,
+    item: &InventoryItem,
+    change: &str,
+    before: &Option<String>,
+    after: &Option<String>,
+) {
+    if before != after {
+        changes.push(InventoryChange {
+            kind: item.kind.clone(),
+            name: item.name.clone(),
+            change: change.to_string(),
+            before: before.clone(),
+            after: after.clone(),
+        });
+    }
+}
+
+/// Compares two snapshots and lists added/removed items plus version, state and start mode changes.
+pub fn diff_inventory(before: &[InventoryItem], after: &[InventoryItem]) -> Vec<InventoryChange> {
+    let key = |item: &InventoryItem| (item.kind.clone(), item.name.to_ascii_lowercase());
+    let before_map: BTreeMap<_, &InventoryItem> = before.iter().map(|item| (key(item), item)).collect();
+    let after_map: BTreeMap<_, &InventoryItem> = after.iter().map(|item| (key(item), item)).collect();
+
+    let mut changes = Vec::new();
+    for (item_key, old) in &before_map {
+        if !after_map.contains_key(item_key) {
+            changes.push(InventoryChange {
+                kind: old.kind.clone(),
+                name: old.name.clone(),
+                change: "removed".to_string(),
+                before: old.version.clone(),
+                after: None,
+            });
+        }
+    }
+    for (item_key, new) in &after_map {
+        let Some(old) = before_map.get(item_key) else {
+            changes.push(InventoryChange {
+                kind: new.kind.clone(),
+                name: new.name.clone(),
+                change: "added".to_string(),
+                before: None,
+                after: new.version.clone(),
+            });
+            continue;
+        };
+        changed_field(&mut changes, new, "version", &old.version, &new.version);
+        changed_field(&mut changes, new, "state", &old.state, &new.state);
+        changed_field(&mut changes, new, "startMode", &old.start_mode, &new.start_mode);
+    }
+    changes.sort_by(|left, right| {
+        left.kind
+            .cmp(&right.kind)
+            .then_with(|| left.name.to_ascii_lowercase().cmp(&right.name.to_ascii_lowercase()))
+    });
+    changes
+}
+
+/// Reads the installed services and drivers of the local host.
+pub fn collect_inventory() -> Result<Vec<InventoryItem>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        return collect_windows_inventory();
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        return collect_macos_inventory();
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        return collect_linux_inventory();
+    }
+
+    #[allow(unreachable_code)]
+    Err("Inventory snapshots are not supported on this platform.".to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn collect_windows_inventory() -> Result<Vec<InventoryItem>, String> {
+    let script = "$s=Get-CimInstance Win32_Service | ForEach-Object { [pscustomobject]@{kind='service';name=$_.Name;displayName=$_.DisplayName;state=$_.State;startMode=$_.StartMode} }; \
+$d=Get-CimInstance Win32_PnPSignedDriver | Where-Object { $_.DeviceName } | ForEach-Object { [pscustomobject]@{kind='driver';name=$_.DeviceName;displayName=$_.InfName;version=$_.DriverVersion} }; \
+@($s) + @($d) | ConvertTo-Json -Compress";
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()
+        .map_err(|error| format!("Failed to run inventory query: {error}"))?;
+    let value: serde_json::Value = serde_json::from_slice(output.stdout.as_slice())
+        .map_err(|error| format!("Failed to parse inventory query output: {error}"))?;
+    let rows = match value {
+        serde_json::Value::Array(rows) => rows,
+        other => vec![other],
+    };
+    let read = |row: &serde_json::Value, key: &str| non_empty(row.get(key).and_then(|value| value.as_str()));
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let kind = read(row, "kind")?;
+            let mut item = InventoryItem::new(kind.as_str(), read(row, "name")?.as_str());
+            item.display_name = read(row, "displayName");
+            item.version = read(row, "version");
+            item.state = read(row, "state");
+            item.start_mode = read(row, "startMode");
+            Some(item)
+        })
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+fn collect_linux_inventory() -> Result<Vec<InventoryItem>, String> {
+    let output = std::process::Command::new("systemctl")
+        .args(["list-units", "--type=service", "--all", "--no-legend", "--plain", "--no-pager"])
+        .output()
+        .map_err(|error| format!("Failed to run systemctl: {error}"))?;
+    let mut items = parse_systemctl_units(String::from_utf8_lossy(&output.stdout).as_ref());
+
+    if let Ok(modules) = std::fs::read_to_string("/proc/modules") {
+        for line in modules.lines() {
+            let Some(name) = line.split_whitespace().next() else {
+                continue;
+            };
+            let mut item = InventoryItem::new("driver", name);
+            item.version = std::fs::read_to_string(format!("/sys/module/{name}/version"))
+                .ok()
+                .and_then(|value| non_empty(Some(value.as_str())));
+            item.state = Some("loaded".to_string());
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+/// Parses `systemctl list-units --plain --no-legend` rows: `unit load active sub description`.
+#[cfg(target_os = "linux")]
+fn parse_systemctl_units(text: &str) -> Vec<InventoryItem> {
+    text.lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let unit = parts.next()?;
+            let _load = parts.next()?;
+            let active = parts.next()?;
+            let sub = parts.next()?;
+            let description = parts.collect::<Vec<_>>().join(" ");
+            let mut item = InventoryItem::new("service", unit.trim_end_matches(".service"));
+            item.display_name = non_empty(Some(description.as_str()));
+            item.state = Some(format!("{active}/{sub}"));
+            Some(item)
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn collect_macos_inventory() -> Result<Vec<InventoryItem>, String> {
+    let output = std::process::Command::new("launchctl")
+        .arg("list")
+        .output()
+        .map_err(|error| format!("Failed to run launchctl: {error}"))?;
+    let mut items: Vec<InventoryItem> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pid = parts.next()?;
+            let _status = parts.next()?;
+            let label = parts.next()?;
+            let mut item = InventoryItem::new("service", label);
+            item.state = Some(if pid == "-" { "stopped" } else { "running" }.to_string());
+            Some(item)
+        })
+        .collect();
+
+    if let Ok(output) = std::process::Command::new("kmutil")
+        .args(["showloaded", "--list-only"])
+        .output()
+    {
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            // "... com.apple.driver.AppleACPIPlatform (6.1) 1B2C... <...>"
+            let Some(open) = line.find(" (") else {
+                continue;
+            };
+            let Some(name) = line[..open].split_whitespace().last() else {
+                continue;
+            };
+            let version = line[open + 2..].split(')').next();
+            let mut item = InventoryItem::new("driver", name);
+            item.version = non_empty(version);
+            item.state = Some("loaded".to_string());
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: &str, name: &str, version: Option<&str>, state: Option<&str>) -> InventoryItem {
+        let mut item = InventoryItem::new(kind, name);
+        item.version = version.map(ToString::to_string);
+        item.state = state.map(ToString::to_string);
+        item
+    }
+
+    #[test]
+    fn diffs_snapshots() {
+        let before = vec![
+            item("driver", "nvlddmkm", Some("31.0.15.1"), None),
+            item("service", "Spooler", None, Some("Running")),
+            item("service", "OldAgent", None, Some("Running")),
+        ];
+        let after = vec![
+            item("driver", "nvlddmkm", Some("31.0.15.5"), None),
+            item("service", "spooler", None, Some("Stopped")),
+            item("service", "NewAgent", None, Some("Running")),
+        ];
+
+        let changes = diff_inventory(&before, &after);
+        let summary: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|change| (change.name.as_str(), change.change.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("nvlddmkm", "version"),
+                ("NewAgent", "added"),
+                ("OldAgent", "removed"),
+                ("spooler", "state"),
+            ]
+        );
+        assert_eq!(changes[0].after.as_deref(), Some("31.0.15.5"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_systemctl_units() {
+        let items = parse_systemctl_units(
+            "cups.service loaded active running CUPS Scheduler\nsshd.service loaded inactive dead OpenSSH Daemon\n",
+        );
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].name, "cups");
+        assert_eq!(items[0].state.as_deref(), Some("active/running"));
+        assert_eq!(items[1].display_name.as_deref(), Some("OpenSSH Daemon"));
+    }
+}
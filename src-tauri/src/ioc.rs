@@ -0,0 +1,352 @@
+//! Offline indicator-of-compromise matching. A feed is a text or CSV list of IP addresses,
+//! domains, file hashes and file names (defanged forms like `evil[.]com` are accepted); a scan
+//! looks for them in stored event messages, Sysmon metadata and raw `<EventData>` fields and
+//! records each hit in the `ioc_matches` table.
+
+use crate::db::{self, IocFeedSummary, IocIndicator, IocMatch};
+use crate::logs::NormalizedEvent;
+use crate::nl_query::EventQueryFilter;
+use crate::sigma::raw_fields;
+use chrono::Utc;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::LazyLock;
+
+const MAX_FEED_NAME_LENGTH: usize = 80;
+const MAX_SKIPPED_EXAMPLES: usize = 5;
+const MAX_MESSAGE_CHARS: usize = 400;
+const SAVE_BATCH: usize = 500;
+/// Extensions that make a dotted name a file rather than a domain. `.com` and `.zip` are left
+/// out because they are also top-level domains.
+const FILE_EXTENSIONS: [&str; 25] = [
+    "exe", "dll", "sys", "scr", "bat", "cmd", "ps1", "psm1", "vbs", "js", "jse", "hta", "lnk",
+    "msi", "jar", "sh", "py", "elf", "so", "bin", "doc", "docm", "xls", "xlsm", "iso",
+];
+
+static DOMAIN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:[a-z0-9_](?:[a-z0-9_-]{0,61}[a-z0-9])?\.)+[a-z][a-z0-9-]{1,62}$")
+        .expect("domain pattern is valid")
+});
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IocImportResult {
+    pub feed: String,
+    pub imported: usize,
+    pub counts: BTreeMap<String, usize>,
+    /// Lines that held no recognizable indicator.
+    pub skipped: usize,
+    pub skipped_examples: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IocScanResult {
+    pub scanned_events: usize,
+    pub indicators: usize,
+    pub matches: usize,
+    /// Matches not recorded by an earlier scan.
+    pub new_matches: usize,
+}
+
+/// Replaces `[.]`, `(.)`, `[:]` and `hxxp` so defanged indicators can be matched.
+fn refang(value: &str) -> String {
+    value
+        .replace("[.]", ".")
+        .replace("(.)", ".")
+        .replace("[dot]", ".")
+        .replace("[:]", ":")
+        .replace("hxxp", "http")
+}
+
+fn is_file_name(value: &str) -> bool {
+    value
+        .rsplit_once('.')
+        .is_some_and(|(stem, extension)| !stem.is_empty() && FILE_EXTENSIONS.contains(&extension))
+}
+
+/// The kind and normalized value of one indicator: URLs reduce to their host and paths to
+/// their file name.
+fn classify(raw: &str) -> Option<(&'static str, String)> {
+    let value = refang(raw.trim().trim_matches(|ch| matches!(ch, '"' | '\'')))
+        .trim()
+        .to_ascii_lowercase();
+    if value.is_empty() {
+        return None;
+    }
+    if let Some((_, rest)) = value.split_once("://") {
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        let host = host.split(':').next().unwrap_or_default();
+        return classify(host);
+    }
+    if value.parse::<IpAddr>().is_ok() {
+        return Some(("ip", value));
+    }
+    if matches!(value.len(), 32 | 40 | 64) && value.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Some(("hash", value));
+    }
+    let name = value.rsplit(['\\', '/']).next().unwrap_or_default();
+    if is_file_name(name) {
+        return Some(("filename", name.to_string()));
+    }
+    if DOMAIN.is_match(value.as_str()) {
+        return Some(("domain", value));
+    }
+    None
+}
+
+/// Indicators in a feed, one per line. CSV lines contribute their first recognizable cell, so
+/// `type,value` and `value,description` layouts both work. `#` starts a comment.
+pub fn parse_feed(feed: &str, text: &str) -> (Vec<IocIndicator>, Vec<String>) {
+    let mut indicators = Vec::new();
+    let mut seen = HashSet::new();
+    let mut skipped = Vec::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((kind, value)) = line.split([',', ';', '\t']).find_map(classify) else {
+            skipped.push(line.to_string());
+            continue;
+        };
+        if seen.insert((kind, value.clone())) {
+            indicators.push(IocIndicator {
+                feed: feed.to_string(),
+                kind: kind.to_string(),
+                value,
+            });
+        }
+    }
+    (indicators, skipped)
+}
+
+/// Stores the indicators in `content` as feed `name`, replacing an earlier import of it.
+pub fn import_feed(name: &str, content: &str) -> Result<IocImportResult, String> {
+    let feed: String = name.trim().chars().take(MAX_FEED_NAME_LENGTH).collect();
+    if feed.is_empty() {
+        return Err("Feed name is required.".to_string());
+    }
+    let (indicators, skipped) = parse_feed(feed.as_str(), content);
+    if indicators.is_empty() {
+        return Err(
+            "The feed holds no recognizable IPs, domains, hashes or file names.".to_string(),
+        );
+    }
+    let imported = db::replace_ioc_feed(
+        feed.as_str(),
+        indicators.as_slice(),
+        Utc::now().to_rfc3339().as_str(),
+    )?;
+    let mut counts = BTreeMap::new();
+    for indicator in &indicators {
+        *counts.entry(indicator.kind.clone()).or_insert(0) += 1;
+    }
+    Ok(IocImportResult {
+        feed,
+        imported,
+        counts,
+        skipped: skipped.len(),
+        skipped_examples: skipped.into_iter().take(MAX_SKIPPED_EXAMPLES).collect(),
+    })
+}
+
+pub fn list_feeds() -> Result<Vec<IocFeedSummary>, String> {
+    db::get_ioc_feeds()
+}
+
+/// Indicators by value. A domain also matches its subdomains.
+struct IndicatorIndex {
+    by_value: HashMap<String, Vec<IocIndicator>>,
+}
+
+impl IndicatorIndex {
+    fn new(indicators: Vec<IocIndicator>) -> Self {
+        let mut by_value: HashMap<String, Vec<IocIndicator>> = HashMap::new();
+        for indicator in indicators {
+            by_value
+                .entry(indicator.value.clone())
+                .or_default()
+                .push(indicator);
+        }
+        Self { by_value }
+    }
+
+    fn lookup<'a>(&'a self, token: &str, found: &mut Vec<&'a IocIndicator>) {
+        if let Some(indicators) = self.by_value.get(token) {
+            found.extend(indicators.iter());
+        }
+        let mut rest = token;
+        while let Some((_, parent)) = rest.split_once('.') {
+            if let Some(indicators) = self.by_value.get(parent) {
+                found.extend(
+                    indicators
+                        .iter()
+                        .filter(|indicator| indicator.kind == "domain"),
+                );
+            }
+            rest = parent;
+        }
+        // `host:port`: try the host on its own. IPv6 addresses have several colons.
+        if let Some((host, port)) = token.split_once(':') {
+            if !port.contains(':') && port.chars().all(|ch| ch.is_ascii_digit()) {
+                self.lookup(host, found);
+            }
+        }
+    }
+
+    /// Indicators found in `text`. Paths split into their components, so file names match.
+    fn find<'a>(&'a self, text: &str) -> Vec<&'a IocIndicator> {
+        let mut found = Vec::new();
+        for token in text
+            .split(|ch: char| !(ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '_' | ':')))
+            .map(|token| token.trim_matches(|ch| matches!(ch, '.' | ':' | '-')))
+            .filter(|token| token.len() >= 3)
+        {
+            self.lookup(token.to_ascii_lowercase().as_str(), &mut found);
+        }
+        found
+    }
+
+    /// Matches in `event`, at most one per indicator, attributed to the first field it is in.
+    fn matches(&self, event: &NormalizedEvent, matched_at: &str) -> Vec<IocMatch> {
+        let mut fields: Vec<(String, String)> = event
+            .metadata
+            .iter()
+            .flatten()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        if let Some(payload) = event.raw_payload.as_deref() {
+            fields.extend(raw_fields(payload));
+        }
+        fields.push(("message".to_string(), event.message.clone()));
+
+        let mut seen = HashSet::new();
+        let mut matches = Vec::new();
+        for (field, text) in &fields {
+            for indicator in self.find(text.as_str()) {
+                if !seen.insert((indicator.feed.as_str(), indicator.value.as_str())) {
+                    continue;
+                }
+                matches.push(IocMatch {
+                    feed: indicator.feed.clone(),
+                    kind: indicator.kind.clone(),
+                    indicator: indicator.value.clone(),
+                    field: field.clone(),
+                    event_id: event.id.clone(),
+                    event_timestamp: event.timestamp.clone(),
+                    source_host: event.source_host.clone(),
+                    provider: event.provider.clone(),
+                    event_code: event.event_id,
+                    message: event.message.chars().take(MAX_MESSAGE_CHARS).collect(),
+                    matched_at: matched_at.to_string(),
+                });
+            }
+        }
+        matches
+    }
+}
+
+/// Checks the stored events matching `filter` against every loaded indicator and records the
+/// matches. Re-scanning the same events adds nothing new.
+pub fn scan(filter: &EventQueryFilter, host: Option<&str>) -> Result<IocScanResult, String> {
+    let indicators = db::get_ioc_indicators()?;
+    let mut result = IocScanResult {
+        indicators: indicators.len(),
+        ..IocScanResult::default()
+    };
+    if indicators.is_empty() {
+        return Ok(result);
+    }
+    let index = IndicatorIndex::new(indicators);
+    let matched_at = Utc::now().to_rfc3339();
+    let mut pending = Vec::new();
+    let mut new_matches = 0usize;
+    result.scanned_events = db::for_each_event_with_raw(filter, host, |event| {
+        pending.extend(index.matches(&event, matched_at.as_str()));
+        if pending.len() >= SAVE_BATCH {
+            result.matches += pending.len();
+            new_matches += db::save_ioc_matches(pending.as_slice())?;
+            pending.clear();
+        }
+        Ok(())
+    })?;
+    result.matches += pending.len();
+    result.new_matches = new_matches + db::save_ioc_matches(pending.as_slice())?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    #[test]
+    fn classifies_defanged_and_structured_indicators() {
+        let feed = "# demo feed\n\
+            type,value\n\
+            ip,203.0.113[.]7\n\
+            hxxps://update.evil-cdn[.]com/payload.bin\n\
+            C:\\Users\\Public\\dropper.exe\n\
+            44D88612FEA8A8F36DE82E1278ABB02F, EICAR md5\n\
+            not an indicator\n";
+        let (indicators, skipped) = parse_feed("demo", feed);
+        let found: Vec<(&str, &str)> = indicators
+            .iter()
+            .map(|indicator| (indicator.kind.as_str(), indicator.value.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("ip", "203.0.113.7"),
+                ("domain", "update.evil-cdn.com"),
+                ("filename", "dropper.exe"),
+                ("hash", "44d88612fea8a8f36de82e1278abb02f"),
+            ]
+        );
+        assert_eq!(skipped, vec!["type,value", "not an indicator"]);
+    }
+
+    #[test]
+    fn matches_sysmon_fields_and_subdomains() {
+        let (indicators, _) = parse_feed("demo", "evil-cdn.com\ndropper.exe\n203.0.113.7\n");
+        let index = IndicatorIndex::new(indicators);
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "Microsoft-Windows-Sysmon/Operational",
+            "security",
+            "Microsoft-Windows-Sysmon",
+            Some(3),
+            "information",
+            "Network connection detected to 203.0.113.7:443",
+            "ws01",
+        );
+        event.metadata = Some(BTreeMap::from([
+            (
+                "Image".to_string(),
+                "C:\\Users\\Public\\Dropper.exe".to_string(),
+            ),
+            (
+                "DestinationHostname".to_string(),
+                "a.b.evil-cdn.com".to_string(),
+            ),
+            ("DestinationIp".to_string(), "203.0.113.7".to_string()),
+        ]));
+        let matches = index.matches(&event, "now");
+        let found: Vec<(&str, &str)> = matches
+            .iter()
+            .map(|found| (found.indicator.as_str(), found.field.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("evil-cdn.com", "DestinationHostname"),
+                ("203.0.113.7", "DestinationIp"),
+                ("dropper.exe", "Image"),
+            ]
+        );
+    }
+}
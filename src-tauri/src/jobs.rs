@@ -0,0 +1,193 @@
+//! Background jobs for long collections and imports. A job runs off the command thread, reports
+//! progress as `hla://job-progress` events and ends with one carrying its result, so the UI can
+//! show a progress bar instead of waiting on a single command.
+
+use crate::diagnostics;
+use crate::logs::progress;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+pub const JOB_PROGRESS_EVENT: &str = "hla://job-progress";
+
+/// Progress events are rate-limited; the final state is always emitted.
+const EMIT_INTERVAL: Duration = Duration::from_millis(250);
+const FINISHED_JOBS_KEPT: usize = 20;
+
+static JOBS: Mutex<BTreeMap<String, JobStatus>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatus {
+    pub job_id: String,
+    pub kind: String,
+    /// `running`, `completed` or `failed`.
+    pub state: String,
+    pub collected: usize,
+    pub current_source: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub error: Option<String>,
+    /// The command's usual return value, once the job has completed.
+    pub result: Option<serde_json::Value>,
+}
+
+pub struct Job {
+    app: AppHandle,
+    job_id: String,
+    last_emit: Mutex<Option<Instant>>,
+}
+
+impl Job {
+    pub fn start(app: AppHandle, kind: &str) -> Arc<Self> {
+        let status = JobStatus {
+            job_id: Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            state: "running".to_string(),
+            collected: 0,
+            current_source: None,
+            started_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+            error: None,
+            result: None,
+        };
+        let job = Arc::new(Self {
+            app,
+            job_id: status.job_id.clone(),
+            last_emit: Mutex::new(None),
+        });
+        if let Ok(mut jobs) = JOBS.lock() {
+            prune_finished(&mut jobs);
+            jobs.insert(status.job_id.clone(), status.clone());
+        }
+        job.emit(&status);
+        job
+    }
+
+    pub fn id(&self) -> &str {
+        self.job_id.as_str()
+    }
+
+    /// Runs `work` with collector progress routed to this job.
+    pub fn run<T>(self: &Arc<Self>, work: impl FnOnce() -> T) -> T {
+        let job = self.clone();
+        progress::with_reporter(
+            Arc::new(move |source: &str, collected| job.progress(source, collected)),
+            work,
+        )
+    }
+
+    pub fn progress(&self, source: &str, collected: usize) {
+        let Some(status) = self.update(|status| {
+            status.collected = collected;
+            status.current_source = Some(source.to_string());
+        }) else {
+            return;
+        };
+        let due = match self.last_emit.lock() {
+            Ok(mut last) => {
+                let due = last.is_none_or(|at| at.elapsed() >= EMIT_INTERVAL);
+                if due {
+                    *last = Some(Instant::now());
+                }
+                due
+            }
+            Err(_) => false,
+        };
+        if due {
+            self.emit(&status);
+        }
+    }
+
+    pub fn finish<T: Serialize>(&self, result: &Result<T, String>) {
+        let status = self.update(|status| {
+            status.finished_at = Some(Utc::now().to_rfc3339());
+            match result {
+                Ok(value) => {
+                    status.state = "completed".to_string();
+                    status.result = serde_json::to_value(value).ok();
+                }
+                Err(error) => {
+                    status.state = "failed".to_string();
+                    status.error = Some(error.clone());
+                }
+            }
+        });
+        if let Some(status) = status {
+            self.emit(&status);
+        }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut JobStatus)) -> Option<JobStatus> {
+        let mut jobs = JOBS.lock().ok()?;
+        let status = jobs.get_mut(self.job_id.as_str())?;
+        change(status);
+        Some(status.clone())
+    }
+
+    fn emit(&self, status: &JobStatus) {
+        if let Err(error) = self.app.emit(JOB_PROGRESS_EVENT, status) {
+            diagnostics::warn("runtime", format!("Failed to emit job progress: {error}"));
+        }
+    }
+}
+
+pub fn job_status(job_id: &str) -> Option<JobStatus> {
+    JOBS.lock().ok()?.get(job_id).cloned()
+}
+
+/// Keeps every running job and only the newest finished ones.
+fn prune_finished(jobs: &mut BTreeMap<String, JobStatus>) {
+    let mut finished: Vec<(String, String)> = jobs
+        .values()
+        .filter_map(|status| {
+            status
+                .finished_at
+                .clone()
+                .map(|finished_at| (finished_at, status.job_id.clone()))
+        })
+        .collect();
+    if finished.len() <= FINISHED_JOBS_KEPT {
+        return;
+    }
+    finished.sort();
+    for (_, job_id) in &finished[..finished.len() - FINISHED_JOBS_KEPT] {
+        jobs.remove(job_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prunes_oldest_finished_jobs_only() {
+        let mut jobs = BTreeMap::new();
+        for index in 0..FINISHED_JOBS_KEPT + 5 {
+            let job_id = format!("job-{index:02}");
+            jobs.insert(
+                job_id.clone(),
+                JobStatus {
+                    job_id,
+                    kind: "refresh".to_string(),
+                    state: if index == 0 { "running" } else { "completed" }.to_string(),
+                    collected: 0,
+                    current_source: None,
+                    started_at: "2026-03-01T00:00:00+00:00".to_string(),
+                    finished_at: (index > 0).then(|| format!("2026-03-01T00:{index:02}:00+00:00")),
+                    error: None,
+                    result: None,
+                },
+            );
+        }
+        prune_finished(&mut jobs);
+        assert_eq!(jobs.len(), FINISHED_JOBS_KEPT + 1);
+        assert!(jobs.contains_key("job-00"));
+        assert!(!jobs.contains_key("job-04"));
+        assert!(jobs.contains_key("job-05"));
+    }
+}
@@ -0,0 +1,355 @@
+//! Known-issue rules: deterministic signatures for common problems. A rule matches events by
+//! provider, event id and message regex, or crashes by code, type and component regex, and maps
+//! them to a diagnosis and remediation. Rules come from the bundled `rules/known-issues.yaml`
+//! and from `*.yaml` files in the user's `known-issues` folder; a user rule replaces the bundled
+//! rule with the same id.
+
+use crate::crash::CrashRecord;
+use crate::db;
+use crate::logs::NormalizedEvent;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BUNDLED_RULES: &str = include_str!("../rules/known-issues.yaml");
+const USER_RULES_DIR: &str = "known-issues";
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct RuleFile {
+    rules: Vec<RuleSpec>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct RuleSpec {
+    id: String,
+    title: String,
+    diagnosis: String,
+    remediation: String,
+    references: Vec<String>,
+    event: Option<EventSignature>,
+    crash: Option<CrashSignature>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct EventSignature {
+    providers: Vec<String>,
+    ids: Vec<u32>,
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct CrashSignature {
+    code: Option<String>,
+    #[serde(rename = "type")]
+    crash_type: Option<String>,
+    component: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownIssueMatch {
+    pub rule_id: String,
+    pub title: String,
+    pub diagnosis: String,
+    pub remediation: String,
+    pub references: Vec<String>,
+    /// `bundled`, or the user rule file the rule came from.
+    pub origin: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownIssueReport {
+    /// `event` or `crash`.
+    pub target_kind: String,
+    pub target_id: String,
+    pub matches: Vec<KnownIssueMatch>,
+    /// Rule files or rules that were skipped, so a broken user rule does not fail silently.
+    pub rule_errors: Vec<String>,
+}
+
+struct CompiledEventSignature {
+    providers: Vec<String>,
+    ids: Vec<u32>,
+    message: Option<Regex>,
+}
+
+struct CompiledCrashSignature {
+    code: Option<Regex>,
+    crash_type: Option<Regex>,
+    component: Option<Regex>,
+}
+
+/// A rule with its regexes compiled.
+pub struct KnownIssueRule {
+    spec: RuleSpec,
+    origin: String,
+    event: Option<CompiledEventSignature>,
+    crash: Option<CompiledCrashSignature>,
+}
+
+fn compile(rule_id: &str, pattern: Option<&str>) -> Result<Option<Regex>, String> {
+    let Some(pattern) = pattern.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(PATTERN_SIZE_LIMIT)
+        .build()
+        .map(Some)
+        .map_err(|error| format!("Rule \"{rule_id}\" has an invalid pattern: {error}"))
+}
+
+impl KnownIssueRule {
+    fn new(spec: RuleSpec, origin: &str) -> Result<Self, String> {
+        if spec.id.trim().is_empty() || spec.title.trim().is_empty() {
+            return Err(format!("A rule in {origin} is missing its id or title."));
+        }
+        let event = match spec.event.as_ref() {
+            Some(signature) => Some(CompiledEventSignature {
+                providers: signature.providers.clone(),
+                ids: signature.ids.clone(),
+                message: compile(spec.id.as_str(), signature.message.as_deref())?,
+            }),
+            None => None,
+        };
+        let crash = match spec.crash.as_ref() {
+            Some(signature) => Some(CompiledCrashSignature {
+                code: compile(spec.id.as_str(), signature.code.as_deref())?,
+                crash_type: compile(spec.id.as_str(), signature.crash_type.as_deref())?,
+                component: compile(spec.id.as_str(), signature.component.as_deref())?,
+            }),
+            None => None,
+        };
+        if event.is_none() && crash.is_none() {
+            return Err(format!(
+                "Rule \"{}\" has neither an event nor a crash signature.",
+                spec.id
+            ));
+        }
+        Ok(Self {
+            spec,
+            origin: origin.to_string(),
+            event,
+            crash,
+        })
+    }
+
+    fn matches_event(&self, event: &NormalizedEvent) -> bool {
+        let Some(signature) = self.event.as_ref() else {
+            return false;
+        };
+        (signature.providers.is_empty()
+            || signature
+                .providers
+                .iter()
+                .any(|provider| provider.eq_ignore_ascii_case(event.provider.as_str())))
+            && (signature.ids.is_empty()
+                || event
+                    .event_id
+                    .is_some_and(|event_id| signature.ids.contains(&event_id)))
+            && signature
+                .message
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(event.message.as_str()))
+    }
+
+    fn matches_crash(&self, crash: &CrashRecord) -> bool {
+        let Some(signature) = self.crash.as_ref() else {
+            return false;
+        };
+        let field = |pattern: &Option<Regex>, value: Option<&str>| {
+            pattern
+                .as_ref()
+                .is_none_or(|pattern| value.is_some_and(|value| pattern.is_match(value.trim())))
+        };
+        field(&signature.code, crash.code.as_deref())
+            && field(&signature.crash_type, Some(crash.crash_type.as_str()))
+            && field(&signature.component, crash.suspected_component.as_deref())
+    }
+
+    fn to_match(&self) -> KnownIssueMatch {
+        KnownIssueMatch {
+            rule_id: self.spec.id.clone(),
+            title: self.spec.title.clone(),
+            diagnosis: self.spec.diagnosis.clone(),
+            remediation: self.spec.remediation.clone(),
+            references: self.spec.references.clone(),
+            origin: self.origin.clone(),
+        }
+    }
+}
+
+/// Parses one YAML rule file. Invalid rules are reported in `errors` and left out.
+fn parse_rules(text: &str, origin: &str, errors: &mut Vec<String>) -> Vec<KnownIssueRule> {
+    let file: RuleFile = match serde_yaml::from_str(text) {
+        Ok(file) => file,
+        Err(error) => {
+            errors.push(format!("Failed to parse {origin}: {error}"));
+            return Vec::new();
+        }
+    };
+    file.rules
+        .into_iter()
+        .filter_map(|spec| {
+            KnownIssueRule::new(spec, origin)
+                .map_err(|error| errors.push(error))
+                .ok()
+        })
+        .collect()
+}
+
+fn user_rules_dir() -> Result<PathBuf, String> {
+    let mut dir = db::data_dir()?;
+    dir.push(USER_RULES_DIR);
+    Ok(dir)
+}
+
+fn user_rule_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| {
+                    extension.eq_ignore_ascii_case("yaml") || extension.eq_ignore_ascii_case("yml")
+                })
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Bundled rules followed by user rules, with user rules replacing bundled ones by id.
+pub fn load_rules(errors: &mut Vec<String>) -> Vec<KnownIssueRule> {
+    let mut rules: BTreeMap<String, KnownIssueRule> = BTreeMap::new();
+    let mut order = Vec::new();
+    let mut add = |rule: KnownIssueRule| {
+        if !rules.contains_key(rule.spec.id.as_str()) {
+            order.push(rule.spec.id.clone());
+        }
+        rules.insert(rule.spec.id.clone(), rule);
+    };
+    for rule in parse_rules(BUNDLED_RULES, "bundled", errors) {
+        add(rule);
+    }
+    if let Ok(dir) = user_rules_dir() {
+        for path in user_rule_files(dir.as_path()) {
+            let origin = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            match fs::read_to_string(&path) {
+                Ok(text) => {
+                    for rule in parse_rules(text.as_str(), origin.as_str(), errors) {
+                        add(rule);
+                    }
+                }
+                Err(error) => errors.push(format!("Failed to read {origin}: {error}")),
+            }
+        }
+    }
+    order
+        .into_iter()
+        .filter_map(|id| rules.remove(id.as_str()))
+        .collect()
+}
+
+pub fn match_event(rules: &[KnownIssueRule], event: &NormalizedEvent) -> Vec<KnownIssueMatch> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches_event(event))
+        .map(KnownIssueRule::to_match)
+        .collect()
+}
+
+pub fn match_crash(rules: &[KnownIssueRule], crash: &CrashRecord) -> Vec<KnownIssueMatch> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches_crash(crash))
+        .map(KnownIssueRule::to_match)
+        .collect()
+}
+
+/// Known issues for the stored crash or event with `id`; crashes are looked up first.
+pub fn match_known_issues(id: &str) -> Result<KnownIssueReport, String> {
+    let mut rule_errors = Vec::new();
+    let rules = load_rules(&mut rule_errors);
+    if let Some(crash) = db::get_crash_by_id(id)? {
+        return Ok(KnownIssueReport {
+            target_kind: "crash".to_string(),
+            target_id: crash.id.clone(),
+            matches: match_crash(rules.as_slice(), &crash),
+            rule_errors,
+        });
+    }
+    let event = db::get_events_by_ids(&[id.to_string()])?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No crash or event with id {id} was found."))?;
+    Ok(KnownIssueReport {
+        target_kind: "event".to_string(),
+        target_id: event.id.clone(),
+        matches: match_event(rules.as_slice(), &event),
+        rule_errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    #[test]
+    fn bundled_rules_parse_and_match() {
+        let mut errors = Vec::new();
+        let rules = parse_rules(BUNDLED_RULES, "bundled", &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "System",
+            "system",
+            "Microsoft-Windows-Kernel-Power",
+            Some(41),
+            "critical",
+            "The system has rebooted without cleanly shutting down first.",
+            "localhost",
+        );
+        let matches = match_event(rules.as_slice(), &event);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].rule_id, "windows-unexpected-shutdown");
+
+        let crash = CrashRecord::new(
+            "windows",
+            "WER",
+            "Blue Screen",
+            Some("0x0000009F"),
+            "Bugcheck",
+            None,
+            None,
+            "localhost",
+            false,
+        );
+        let matches = match_crash(rules.as_slice(), &crash);
+        assert_eq!(matches[0].rule_id, "bugcheck-driver-power-state");
+    }
+
+    #[test]
+    fn invalid_rules_are_reported_and_skipped() {
+        let text = "rules:\n  - id: bad\n    title: Bad\n    event:\n      message: \"(\"\n  - id: empty\n    title: Empty\n";
+        let mut errors = Vec::new();
+        assert!(parse_rules(text, "user.yaml", &mut errors).is_empty());
+        assert_eq!(errors.len(), 2);
+    }
+}
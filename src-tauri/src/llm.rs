@@ -1,13 +1,23 @@
 use if_addrs::{get_if_addrs, IfAddr};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{BufRead, BufReader};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 const OLLAMA_PORT: u16 = 11434;
 const LM_STUDIO_PORT: u16 = 1234;
 
+pub const LLM_CHUNK_EVENT: &str = "hla://llm-chunk";
+pub const STREAM_CANCELLED: &str = "Analysis was cancelled.";
+
+/// Cancellation flags for in-flight streamed analyses, keyed by the caller's stream id.
+static ACTIVE_STREAMS: Mutex<BTreeMap<String, Arc<AtomicBool>>> = Mutex::new(BTreeMap::new());
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LlmEndpointCandidate {
@@ -306,10 +316,216 @@ pub fn scan_lan_providers(interface_id: Option<&str>, max_hosts: usize) -> Vec<L
     hits
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmChunk {
+    pub stream_id: String,
+    /// Changes when a fallback profile takes over, so listeners can discard partial output.
+    pub profile_id: String,
+    pub delta: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// Newline-delimited JSON from Ollama's `/api/chat`.
+    OllamaChat,
+    /// Server-sent events from OpenAI-compatible `/chat/completions`.
+    OpenAiSse,
+}
+
+pub fn register_stream(stream_id: &str) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+        streams.insert(stream_id.to_string(), flag.clone());
+    }
+    flag
+}
+
+pub fn finish_stream(stream_id: &str) {
+    if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+        streams.remove(stream_id);
+    }
+}
+
+/// Flags a running stream for cancellation. Returns false when no such stream is active.
+pub fn cancel_stream(stream_id: &str) -> bool {
+    let Ok(streams) = ACTIVE_STREAMS.lock() else {
+        return false;
+    };
+    match streams.get(stream_id) {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Parses one line of a streamed completion into `(delta, done)`. Keep-alives, comments and
+/// blank lines yield `None`.
+pub fn parse_stream_line(format: StreamFormat, line: &str) -> Option<(String, bool)> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    let payload = match format {
+        StreamFormat::OllamaChat => line,
+        StreamFormat::OpenAiSse => {
+            let data = line.strip_prefix("data:")?.trim();
+            if data == "[DONE]" {
+                return Some((String::new(), true));
+            }
+            data
+        }
+    };
+    let parsed: Value = serde_json::from_str(payload).ok()?;
+    match format {
+        StreamFormat::OllamaChat => {
+            let delta = parsed
+                .get("message")
+                .and_then(|message| message.get("content"))
+                .or_else(|| parsed.get("response"))
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let done = parsed.get("done").and_then(Value::as_bool).unwrap_or(false);
+            Some((delta.to_string(), done))
+        }
+        StreamFormat::OpenAiSse => {
+            let choice = parsed
+                .get("choices")
+                .and_then(Value::as_array)
+                .and_then(|choices| choices.first())?;
+            let delta = choice
+                .get("delta")
+                .and_then(|delta| delta.get("content"))
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let done = choice
+                .get("finish_reason")
+                .is_some_and(|reason| !reason.is_null());
+            Some((delta.to_string(), done))
+        }
+    }
+}
+
+/// Sends a streaming completion request and forwards each text delta to `on_delta` as it
+/// arrives. Returns the full text, or `STREAM_CANCELLED` once `cancel` is set.
+pub fn stream_chat_completion(
+    request: reqwest::blocking::RequestBuilder,
+    format: StreamFormat,
+    provider: &str,
+    cancel: &AtomicBool,
+    on_delta: &mut dyn FnMut(&str),
+) -> Result<String, String> {
+    let response = request
+        .send()
+        .map_err(|error| format!("Failed sending prompt to {provider}: {error}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!(
+            "{provider} analysis request failed (HTTP {}).",
+            status.as_u16()
+        ));
+    }
+
+    let mut collected = String::new();
+    for line in BufReader::new(response).lines() {
+        if cancel.load(Ordering::SeqCst) {
+            return Err(STREAM_CANCELLED.to_string());
+        }
+        let line = line.map_err(|error| format!("{provider} stream was interrupted: {error}"))?;
+        let Some((delta, done)) = parse_stream_line(format, line.as_str()) else {
+            continue;
+        };
+        if !delta.is_empty() {
+            collected.push_str(delta.as_str());
+            on_delta(delta.as_str());
+        }
+        if done {
+            break;
+        }
+    }
+
+    let text = collected.trim();
+    if text.is_empty() {
+        return Err(format!("{provider} response did not include generated text."));
+    }
+    Ok(text.to_string())
+}
+
+/// Reachability, credential and latency report for one configured LLM profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmProviderHealth {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub provider: String,
+    pub base_url: String,
+    pub reachable: bool,
+    /// `None` when the provider does not use credentials or the response did not say.
+    pub auth_valid: Option<bool>,
+    pub latency_ms: Option<u64>,
+    pub status_code: Option<u16>,
+    /// Whether the configured model is listed by the provider; `None` when no model is set.
+    pub model_available: Option<bool>,
+    pub message: String,
+}
+
+pub fn provider_requires_api_key(provider: &str) -> bool {
+    matches!(provider, "openai" | "perplexity" | "gemini" | "claude")
+}
+
+/// Interprets a health/models probe as `(reachable, auth_valid)`. Any HTTP answer means the
+/// endpoint is reachable; 401/403 mean the credentials were rejected.
+pub fn assess_provider_probe(
+    provider: &str,
+    status_code: Option<u16>,
+    has_api_key: bool,
+) -> (bool, Option<bool>) {
+    let uses_key = has_api_key || provider_requires_api_key(provider);
+    match status_code {
+        None if provider_requires_api_key(provider) && !has_api_key => (false, Some(false)),
+        None => (false, None),
+        Some(401) | Some(403) => (true, Some(false)),
+        Some(status) if (200..300).contains(&status) => (true, uses_key.then_some(true)),
+        Some(_) => (true, None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{assess_provider_probe, parse_stream_line, StreamFormat};
     use crate::LlmConnectionTestResult;
 
+    #[test]
+    fn test_parse_ollama_stream_lines() {
+        let chunk = r#"{"model":"llama3","message":{"role":"assistant","content":"Disk "},"done":false}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::OllamaChat, chunk),
+            Some(("Disk ".to_string(), false))
+        );
+        let last = r#"{"model":"llama3","message":{"role":"assistant","content":""},"done":true}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::OllamaChat, last),
+            Some((String::new(), true))
+        );
+    }
+
+    #[test]
+    fn test_parse_openai_sse_lines() {
+        let chunk = r#"data: {"choices":[{"index":0,"delta":{"content":"timeout"},"finish_reason":null}]}"#;
+        assert_eq!(
+            parse_stream_line(StreamFormat::OpenAiSse, chunk),
+            Some(("timeout".to_string(), false))
+        );
+        assert_eq!(parse_stream_line(StreamFormat::OpenAiSse, ": keep-alive"), None);
+        assert_eq!(
+            parse_stream_line(StreamFormat::OpenAiSse, "data: [DONE]"),
+            Some((String::new(), true))
+        );
+    }
+
     #[test]
     fn test_preferred_model_from_detected_models() {
         let result = LlmConnectionTestResult {
@@ -341,4 +557,14 @@ mod tests {
         let preferred_model = result.detected_models.first().cloned();
         assert_eq!(preferred_model, None);
     }
+
+    #[test]
+    fn test_assess_provider_probe() {
+        assert_eq!(assess_provider_probe("ollama", Some(200), false), (true, None));
+        assert_eq!(assess_provider_probe("openai", Some(200), true), (true, Some(true)));
+        assert_eq!(assess_provider_probe("claude", Some(401), true), (true, Some(false)));
+        assert_eq!(assess_provider_probe("gemini", None, false), (false, Some(false)));
+        assert_eq!(assess_provider_probe("lmstudio", None, false), (false, None));
+        assert_eq!(assess_provider_probe("lmstudio", Some(500), false), (true, None));
+    }
 }
@@ -0,0 +1,853 @@
+use crate::diagnostics;
+use crate::logs::{NormalizedEvent, SupportedOs};
+use crate::settings::ParserProfile;
+use crate::timestamps::{parse_timestamp_with_hint, TimestampContext, TimestampHint};
+use chrono::{DateTime, Utc};
+use flate2::read::MultiGzDecoder;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Events handed to the sink at a time, so large files never sit in memory whole.
+pub const IMPORT_BATCH_SIZE: usize = 5000;
+const DETECTION_SAMPLE_LINES: usize = 50;
+/// Continuation lines stop being appended once a message reaches this size.
+const MAX_MESSAGE_BYTES: usize = 64 * 1024;
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+const FINGERPRINT_BYTES: u64 = 1024;
+/// Rotations compressed with these are skipped by directory import; only gzip is read.
+const UNSUPPORTED_COMPRESSION: [&str; 5] = ["bz2", "xz", "zst", "zip", "7z"];
+
+static RFC5424_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^<(\d{1,3})>1 (\S+) (\S+) (\S+) (\S+) (\S+) (-|(?:\[(?:[^\]\\]|\\.)*\])+) ?(.*)$")
+        .expect("valid RFC 5424 pattern")
+});
+static RFC3164_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:<(\d{1,3})>)?([A-Z][a-z]{2}\s+\d{1,2} \d{2}:\d{2}:\d{2}|\d{4}-\d{2}-\d{2}T\S+) (\S+) ([^\s:\[]+)(?:\[(\d+)\])?: ?(.*)$",
+    )
+    .expect("valid RFC 3164 pattern")
+});
+static COMMON_LOG_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^(\S+) \S+ (\S+) \[([^\]]+)\] "([^"]*)" (\d{3}) (\S+)"#)
+        .expect("valid common log pattern")
+});
+static LEADING_TIMESTAMP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\[?(\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:[.,]\d+)?(?: ?(?:Z|[+-]\d{2}:?\d{2}))?|[A-Z][a-z]{2}\s+\d{1,2} \d{2}:\d{2}:\d{2})\]?[\s:|-]*(.*)$",
+    )
+    .expect("valid leading timestamp pattern")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LogFileFormat {
+    Rfc5424,
+    Rfc3164,
+    JsonLines,
+    CommonLog,
+    PlainText,
+}
+
+/// The fields one log line contributed; anything missing falls back to file-level defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedLine {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub severity: Option<&'static str>,
+    pub provider: Option<String>,
+    pub event_id: Option<u32>,
+    pub message: String,
+}
+
+/// Maps a syslog priority (facility * 8 + severity) to an event severity.
+fn syslog_severity(priority: &str) -> Option<&'static str> {
+    let severity = priority.parse::<u16>().ok()? % 8;
+    Some(match severity {
+        0..=2 => "critical",
+        3 => "error",
+        4 => "warning",
+        _ => "information",
+    })
+}
+
+/// Reads a level name such as `WARN`, `err` or `fatal`.
+pub fn severity_from_level(level: &str) -> Option<&'static str> {
+    match level.trim().to_ascii_lowercase().as_str() {
+        "fatal" | "critical" | "crit" | "emerg" | "emergency" | "alert" | "panic" => {
+            Some("critical")
+        }
+        "error" | "err" | "severe" => Some("error"),
+        "warning" | "warn" => Some("warning"),
+        "info" | "information" | "notice" | "debug" | "trace" | "verbose" => Some("information"),
+        _ => None,
+    }
+}
+
+/// Looks for a level keyword near the start of a free-text message.
+fn infer_severity(message: &str) -> &'static str {
+    let head: String = message.chars().take(80).collect();
+    head.split(|ch: char| !ch.is_ascii_alphabetic())
+        .filter(|word| word.len() >= 3)
+        .find_map(|word| {
+            severity_from_level(word).filter(|_| word.chars().all(|ch| ch.is_ascii_uppercase()))
+        })
+        .unwrap_or("information")
+}
+
+fn json_text<'a>(object: &'a Map<String, Value>, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| object.get(*key).and_then(Value::as_str))
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn parse_json_line(line: &str, context: &TimestampContext) -> Option<ParsedLine> {
+    let object = match serde_json::from_str::<Value>(line).ok()? {
+        Value::Object(object) => object,
+        _ => return None,
+    };
+    let timestamp = ["timestamp", "@timestamp", "time", "ts", "date", "datetime"]
+        .iter()
+        .find_map(|key| match object.get(*key)? {
+            Value::String(value) => {
+                parse_timestamp_with_hint(value.as_str(), &TimestampHint::Auto, context)
+            }
+            Value::Number(value) => {
+                parse_timestamp_with_hint(value.to_string().as_str(), &TimestampHint::Auto, context)
+            }
+            _ => None,
+        });
+    let severity = json_text(
+        &object,
+        &["level", "severity", "lvl", "log.level", "loglevel"],
+    )
+    .and_then(severity_from_level);
+    let event_id = ["eventId", "event_id", "code"]
+        .iter()
+        .find_map(|key| object.get(*key)?.as_u64())
+        .and_then(|value| u32::try_from(value).ok());
+    let message = json_text(&object, &["message", "msg", "log", "text"])
+        .map(str::to_string)
+        .unwrap_or_else(|| line.to_string());
+    Some(ParsedLine {
+        timestamp,
+        severity: severity.or_else(|| Some(infer_severity(message.as_str()))),
+        provider: json_text(
+            &object,
+            &["logger", "source", "app", "service", "component"],
+        )
+        .map(str::to_string),
+        event_id,
+        message,
+    })
+}
+
+impl LogFileFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rfc5424 => "rfc5424",
+            Self::Rfc3164 => "rfc3164",
+            Self::JsonLines => "jsonLines",
+            Self::CommonLog => "commonLog",
+            Self::PlainText => "plainText",
+        }
+    }
+
+    /// Parses one line. `None` means the line does not start a new entry in this format; the
+    /// importer then treats it as a continuation of the previous entry.
+    pub fn parse_line(self, line: &str, context: &TimestampContext) -> Option<ParsedLine> {
+        match self {
+            Self::Rfc5424 => {
+                let captures = RFC5424_LINE.captures(line)?;
+                let nil = |index: usize| {
+                    captures
+                        .get(index)
+                        .map(|value| value.as_str())
+                        .filter(|value| *value != "-")
+                };
+                Some(ParsedLine {
+                    timestamp: nil(2).and_then(|value| {
+                        parse_timestamp_with_hint(value, &TimestampHint::Rfc3339, context)
+                    }),
+                    severity: syslog_severity(&captures[1]),
+                    provider: nil(4).map(str::to_string),
+                    event_id: nil(6).and_then(|value| value.parse().ok()),
+                    message: captures[8].trim_start_matches('\u{feff}').to_string(),
+                })
+            }
+            Self::Rfc3164 => {
+                let captures = RFC3164_LINE.captures(line)?;
+                let message = captures[6].to_string();
+                Some(ParsedLine {
+                    timestamp: parse_timestamp_with_hint(
+                        &captures[2],
+                        &TimestampHint::Auto,
+                        context,
+                    ),
+                    severity: captures
+                        .get(1)
+                        .and_then(|priority| syslog_severity(priority.as_str()))
+                        .or_else(|| Some(infer_severity(message.as_str()))),
+                    provider: Some(captures[4].to_string()),
+                    event_id: None,
+                    message,
+                })
+            }
+            Self::JsonLines => parse_json_line(line, context),
+            Self::CommonLog => {
+                let captures = COMMON_LOG_LINE.captures(line)?;
+                let status = captures[5].parse::<u32>().ok();
+                Some(ParsedLine {
+                    timestamp: parse_timestamp_with_hint(
+                        &captures[3],
+                        &TimestampHint::Auto,
+                        context,
+                    ),
+                    severity: Some(match status {
+                        Some(500..) => "error",
+                        Some(400..) => "warning",
+                        _ => "information",
+                    }),
+                    provider: Some("http".to_string()),
+                    event_id: status,
+                    message: line.to_string(),
+                })
+            }
+            Self::PlainText => {
+                let captures = LEADING_TIMESTAMP.captures(line)?;
+                let timestamp = parse_timestamp_with_hint(
+                    captures[1].replace(',', ".").as_str(),
+                    &TimestampHint::Auto,
+                    context,
+                )?;
+                let message = captures[2].to_string();
+                Some(ParsedLine {
+                    timestamp: Some(timestamp),
+                    severity: Some(infer_severity(message.as_str())),
+                    provider: None,
+                    event_id: None,
+                    message,
+                })
+            }
+        }
+    }
+}
+
+/// A parser profile with its pattern and timestamp hint compiled once per import.
+#[derive(Clone)]
+pub struct ProfileParser {
+    pub profile: ParserProfile,
+    pattern: Regex,
+    hint: TimestampHint,
+}
+
+impl ProfileParser {
+    pub fn new(profile: ParserProfile) -> Result<Self, String> {
+        let pattern = RegexBuilder::new(profile.pattern.as_str())
+            .size_limit(PATTERN_SIZE_LIMIT)
+            .build()
+            .map_err(|error| {
+                format!(
+                    "Parser profile \"{}\" has an invalid pattern: {error}",
+                    profile.name
+                )
+            })?;
+        let hint = TimestampHint::from_name(profile.timestamp_format.as_str())
+            .map_err(|error| format!("Parser profile \"{}\": {error}", profile.name))?;
+        Ok(Self {
+            profile,
+            pattern,
+            hint,
+        })
+    }
+
+    pub fn parse_line(&self, line: &str, context: &TimestampContext) -> Option<ParsedLine> {
+        let captures = self.pattern.captures(line)?;
+        let group = |name: &str| {
+            captures
+                .name(name)
+                .map(|value| value.as_str().trim())
+                .filter(|value| !value.is_empty())
+        };
+        let message = group("message").unwrap_or(line).to_string();
+        let severity = group("severity")
+            .and_then(|raw| {
+                self.profile
+                    .severity_map
+                    .get(raw.to_ascii_lowercase().as_str())
+                    .and_then(|mapped| severity_from_level(mapped))
+                    .or_else(|| severity_from_level(raw))
+            })
+            .unwrap_or_else(|| infer_severity(message.as_str()));
+        Some(ParsedLine {
+            timestamp: group("timestamp")
+                .and_then(|value| parse_timestamp_with_hint(value, &self.hint, context)),
+            severity: Some(severity),
+            provider: group("provider").map(str::to_string),
+            event_id: group("event_id").and_then(|value| value.parse().ok()),
+            message,
+        })
+    }
+}
+
+/// How lines of one file are read: a built-in format or a user parser profile.
+#[derive(Clone)]
+pub enum LineParser {
+    Format(LogFileFormat),
+    Profile(ProfileParser),
+}
+
+impl LineParser {
+    pub fn parse_line(&self, line: &str, context: &TimestampContext) -> Option<ParsedLine> {
+        match self {
+            Self::Format(format) => format.parse_line(line, context),
+            Self::Profile(parser) => parser.parse_line(line, context),
+        }
+    }
+}
+
+/// Counts the sample lines `parse` accepts, or `None` unless that is a majority.
+fn majority_match(lines: &[&str], parse: impl Fn(&str) -> bool) -> Option<usize> {
+    let parsed = lines.iter().filter(|line| parse(line)).count();
+    (parsed * 2 > lines.len()).then_some(parsed)
+}
+
+fn sample_lines(sample: &[String]) -> Vec<&str> {
+    sample
+        .iter()
+        .map(|line| line.trim_end())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// The parser forced by `profile_id`, or the saved profiles to try during format detection.
+/// Saved profiles that no longer compile are left out of detection with a warning.
+pub fn parser_choice(
+    profiles: Vec<ParserProfile>,
+    profile_id: Option<&str>,
+) -> Result<(Option<LineParser>, Vec<ProfileParser>), String> {
+    if let Some(profile_id) = profile_id {
+        let profile = profiles
+            .into_iter()
+            .find(|profile| profile.id == profile_id)
+            .ok_or_else(|| format!("Parser profile '{profile_id}' was not found."))?;
+        return Ok((
+            Some(LineParser::Profile(ProfileParser::new(profile)?)),
+            Vec::new(),
+        ));
+    }
+    let candidates = profiles
+        .into_iter()
+        .filter_map(|profile| match ProfileParser::new(profile) {
+            Ok(parser) => Some(parser),
+            Err(error) => {
+                diagnostics::warn("import", error);
+                None
+            }
+        })
+        .collect();
+    Ok((None, candidates))
+}
+
+/// Reads one syslog message in either RFC 5424 or RFC 3164 form. Anything else, such as
+/// appliances that send bare text, is kept whole as the message.
+pub fn parse_syslog_message(message: &str, context: &TimestampContext) -> ParsedLine {
+    let message = message.trim_end_matches(['\r', '\n', '\0']);
+    LogFileFormat::Rfc5424
+        .parse_line(message, context)
+        .or_else(|| LogFileFormat::Rfc3164.parse_line(message, context))
+        .unwrap_or_else(|| ParsedLine {
+            severity: Some(infer_severity(message)),
+            message: message.to_string(),
+            ..ParsedLine::default()
+        })
+}
+
+/// Picks the structured format most of the sample parses as, or plain text when none fits.
+pub fn detect_format(sample: &[String], context: &TimestampContext) -> LogFileFormat {
+    let lines = sample_lines(sample);
+    [
+        LogFileFormat::Rfc5424,
+        LogFileFormat::JsonLines,
+        LogFileFormat::CommonLog,
+        LogFileFormat::Rfc3164,
+    ]
+    .into_iter()
+    .filter_map(|format| {
+        majority_match(&lines, |line| format.parse_line(line, context).is_some())
+            .map(|parsed| (format, parsed))
+    })
+    .max_by_key(|(_, parsed)| *parsed)
+    .map(|(format, _)| format)
+    .unwrap_or(LogFileFormat::PlainText)
+}
+
+/// Chooses the parser for a file. User profiles are more specific than the built-in formats,
+/// so the profile matching the most sample lines wins when any matches a majority.
+pub fn detect_parser(
+    sample: &[String],
+    profiles: Vec<ProfileParser>,
+    context: &TimestampContext,
+) -> LineParser {
+    let lines = sample_lines(sample);
+    profiles
+        .into_iter()
+        .filter_map(|parser| {
+            majority_match(&lines, |line| parser.parse_line(line, context).is_some())
+                .map(|parsed| (parser, parsed))
+        })
+        .max_by_key(|(_, parsed)| *parsed)
+        .map(|(parser, _)| LineParser::Profile(parser))
+        .unwrap_or_else(|| LineParser::Format(detect_format(sample, context)))
+}
+
+pub struct LogImportOptions {
+    pub log_name: String,
+    pub source_host: String,
+    pub os: SupportedOs,
+    /// Used for entries without a readable timestamp, normally the file's modification time.
+    pub fallback_timestamp: DateTime<Utc>,
+    pub context: TimestampContext,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogImportSummary {
+    /// The built-in format used, or `None` when a parser profile read the file.
+    pub format: Option<LogFileFormat>,
+    /// Name of the parser profile used, if any.
+    pub profile: Option<String>,
+    pub imported: usize,
+    /// Lines that could not be read and had no earlier entry to attach to.
+    pub skipped: usize,
+    /// Position in the file's (decompressed) content where reading stopped, which is where a
+    /// later incremental import resumes.
+    #[serde(skip)]
+    pub end_offset: u64,
+}
+
+fn build_event(parsed: ParsedLine, options: &LogImportOptions) -> NormalizedEvent {
+    let mut event = NormalizedEvent::new(
+        options.os,
+        options.log_name.as_str(),
+        "application",
+        parsed
+            .provider
+            .as_deref()
+            .unwrap_or(options.log_name.as_str()),
+        parsed.event_id,
+        parsed.severity.unwrap_or("information"),
+        parsed.message.as_str(),
+        options.source_host.as_str(),
+    );
+    event.timestamp = parsed
+        .timestamp
+        .unwrap_or(options.fallback_timestamp)
+        .to_rfc3339();
+    event
+}
+
+fn read_line<R: BufRead>(
+    reader: &mut R,
+    buffer: &mut Vec<u8>,
+    bytes_read: &mut u64,
+) -> Result<Option<String>, String> {
+    buffer.clear();
+    let read = reader
+        .read_until(b'\n', buffer)
+        .map_err(|error| format!("Failed to read log file: {error}"))?;
+    if read == 0 {
+        return Ok(None);
+    }
+    *bytes_read += read as u64;
+    let line = String::from_utf8_lossy(buffer.as_slice());
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+fn read_sample<R: BufRead>(
+    reader: &mut R,
+    buffer: &mut Vec<u8>,
+    bytes_read: &mut u64,
+) -> Result<Vec<String>, String> {
+    let mut sample = Vec::new();
+    while sample.len() < DETECTION_SAMPLE_LINES {
+        match read_line(reader, buffer, bytes_read)? {
+            Some(line) => sample.push(line),
+            None => break,
+        }
+    }
+    Ok(sample)
+}
+
+/// Reads a log file line by line, detecting its format from the first lines (unless `parser`
+/// is given, with `profiles` as extra candidates) and handing imported events to `sink` in
+/// batches. Lines that do not start a new
+/// entry, such as stack traces, are appended to the previous entry's message.
+pub fn import_log<R: BufRead>(
+    mut reader: R,
+    parser: Option<LineParser>,
+    profiles: Vec<ProfileParser>,
+    options: &LogImportOptions,
+    mut sink: impl FnMut(Vec<NormalizedEvent>) -> Result<(), String>,
+) -> Result<LogImportSummary, String> {
+    let mut buffer = Vec::new();
+    let mut bytes_read = 0;
+    let sample = read_sample(&mut reader, &mut buffer, &mut bytes_read)?;
+    let parser = parser.unwrap_or_else(|| detect_parser(&sample, profiles, &options.context));
+    // Plain text without any leading timestamps has no way to tell entries apart, so every
+    // line becomes its own event instead of one giant continuation.
+    let line_per_event = matches!(parser, LineParser::Format(LogFileFormat::PlainText))
+        && !sample
+            .iter()
+            .any(|line| parser.parse_line(line, &options.context).is_some());
+
+    let mut summary = LogImportSummary {
+        format: match &parser {
+            LineParser::Format(format) => Some(*format),
+            LineParser::Profile(_) => None,
+        },
+        profile: match &parser {
+            LineParser::Format(_) => None,
+            LineParser::Profile(profile) => Some(profile.profile.name.clone()),
+        },
+        imported: 0,
+        skipped: 0,
+        end_offset: 0,
+    };
+    let mut batch: Vec<NormalizedEvent> = Vec::new();
+    let mut pending: Option<ParsedLine> = None;
+    let mut finish_entry = |parsed: ParsedLine,
+                            batch: &mut Vec<NormalizedEvent>,
+                            summary: &mut LogImportSummary|
+     -> Result<(), String> {
+        let mut event = build_event(parsed, options);
+        event.assign_stable_id();
+        event.imported = true;
+        batch.push(event);
+        summary.imported += 1;
+        if batch.len() >= IMPORT_BATCH_SIZE {
+            sink(std::mem::take(batch))?;
+        }
+        Ok(())
+    };
+
+    let mut sample = sample.into_iter();
+    loop {
+        let line = match sample.next() {
+            Some(line) => line,
+            None => match read_line(&mut reader, &mut buffer, &mut bytes_read)? {
+                Some(line) => line,
+                None => break,
+            },
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parsed = if line_per_event {
+            Some(ParsedLine {
+                severity: Some(infer_severity(line.as_str())),
+                message: line.clone(),
+                ..ParsedLine::default()
+            })
+        } else {
+            parser.parse_line(line.as_str(), &options.context)
+        };
+        match (parsed, pending.as_mut()) {
+            (Some(parsed), _) => {
+                if let Some(previous) = pending.replace(parsed) {
+                    finish_entry(previous, &mut batch, &mut summary)?;
+                }
+            }
+            (None, Some(previous)) => {
+                if previous.message.len() < MAX_MESSAGE_BYTES {
+                    previous.message.push('\n');
+                    previous.message.push_str(line.as_str());
+                }
+            }
+            (None, None) => summary.skipped += 1,
+        }
+    }
+    if let Some(previous) = pending.take() {
+        finish_entry(previous, &mut batch, &mut summary)?;
+    }
+    if !batch.is_empty() {
+        sink(batch)?;
+    }
+    summary.end_offset = bytes_read;
+    Ok(summary)
+}
+
+/// Position of a file within its rotation set, oldest first: dated rotations by date, then
+/// numbered rotations from the highest number down, then the live file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum RotationRank {
+    Dated(String),
+    Numbered(Reverse<u32>),
+    Current,
+}
+
+/// Splits a file name into its rotation set (`app.log` for `app.log.2.gz` and
+/// `app.log-20240301`) and its place in that set.
+fn rotation_key(file_name: &str) -> (String, RotationRank) {
+    let name = file_name.strip_suffix(".gz").unwrap_or(file_name);
+    if let Some((base, suffix)) = name.rsplit_once('.') {
+        if let Ok(number) = suffix.parse::<u32>() {
+            if !base.is_empty() {
+                return (base.to_string(), RotationRank::Numbered(Reverse(number)));
+            }
+        }
+    }
+    if let Some((base, suffix)) = name.rsplit_once('-') {
+        if !base.is_empty() && suffix.len() >= 8 && suffix.bytes().all(|b| b.is_ascii_digit()) {
+            return (base.to_string(), RotationRank::Dated(suffix.to_string()));
+        }
+    }
+    (name.to_string(), RotationRank::Current)
+}
+
+/// Orders files so each rotation set is imported oldest first and ends with the live file.
+pub fn rotation_order(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    paths.sort_by_cached_key(|path| {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        rotation_key(name.as_str())
+    });
+    paths
+}
+
+/// Lists the importable files directly inside `dir` in rotation order. Hidden files and
+/// compressed rotations other than gzip are left out.
+pub fn list_log_directory(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|error| format!("Failed to read log directory: {error}"))?;
+    let files = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            let hidden = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            let unsupported = path.extension().is_some_and(|extension| {
+                UNSUPPORTED_COMPRESSION
+                    .iter()
+                    .any(|unsupported| extension.eq_ignore_ascii_case(unsupported))
+            });
+            !hidden && !unsupported
+        })
+        .collect();
+    Ok(rotation_order(files))
+}
+
+/// Opens a log file positioned `offset` bytes into its content, decompressing `.gz` files on
+/// the fly. Returns the offset actually used, which is zero when the file is now shorter.
+pub fn open_log_file(path: &Path, offset: u64) -> Result<(Box<dyn BufRead + Send>, u64), String> {
+    let open =
+        || File::open(path).map_err(|error| format!("Failed to open {}: {error}", path.display()));
+    let mut file = open()?;
+    let gzip = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gz"));
+    if !gzip {
+        let length = file
+            .metadata()
+            .map_err(|error| format!("Failed to read {}: {error}", path.display()))?
+            .len();
+        let offset = if offset > length { 0 } else { offset };
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|error| format!("Failed to seek in {}: {error}", path.display()))?;
+        return Ok((Box::new(BufReader::new(file)), offset));
+    }
+    let mut reader = BufReader::new(MultiGzDecoder::new(file));
+    let skipped = io::copy(&mut (&mut reader).take(offset), &mut io::sink())
+        .map_err(|error| format!("Failed to decompress {}: {error}", path.display()))?;
+    if skipped < offset {
+        return Ok((Box::new(BufReader::new(MultiGzDecoder::new(open()?))), 0));
+    }
+    Ok((Box::new(reader), offset))
+}
+
+/// Identifies a file by its first bytes rather than its name, so a saved offset follows the
+/// content when `app.log` is rotated to `app.log.1` or compressed to `app.log.2.gz`. Files
+/// shorter than the prefix get a new fingerprint as they grow and are read again from the
+/// start, which stable event IDs make harmless.
+pub fn file_fingerprint(path: &Path) -> Result<String, String> {
+    let (reader, _) = open_log_file(path, 0)?;
+    let mut prefix = Vec::with_capacity(FINGERPRINT_BYTES as usize);
+    reader
+        .take(FINGERPRINT_BYTES)
+        .read_to_end(&mut prefix)
+        .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in &prefix {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    Ok(format!("{hash:016x}-{}", prefix.len()))
+}
+
+/// Imports `path` from `offset` bytes into its content. Unless `parser` is given, the format
+/// is detected from the start of the file, so a resumed import reads new lines the same way.
+pub fn import_log_path(
+    path: &Path,
+    offset: u64,
+    parser: Option<LineParser>,
+    profiles: Vec<ProfileParser>,
+    options: &LogImportOptions,
+    sink: impl FnMut(Vec<NormalizedEvent>) -> Result<(), String>,
+) -> Result<LogImportSummary, String> {
+    let parser = match parser {
+        Some(parser) => parser,
+        None => {
+            let (mut reader, _) = open_log_file(path, 0)?;
+            let sample = read_sample(&mut reader, &mut Vec::new(), &mut 0)?;
+            detect_parser(&sample, profiles, &options.context)
+        }
+    };
+    let (reader, offset) = open_log_file(path, offset)?;
+    let mut summary = import_log(reader, Some(parser), Vec::new(), options, sink)?;
+    summary.end_offset += offset;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn options() -> LogImportOptions {
+        LogImportOptions {
+            log_name: "app.log".to_string(),
+            source_host: "customer-01".to_string(),
+            os: SupportedOs::Linux,
+            fallback_timestamp: Utc::now(),
+            context: TimestampContext::utc(),
+        }
+    }
+
+    fn import(raw: &str) -> (LogImportSummary, Vec<NormalizedEvent>) {
+        let mut events = Vec::new();
+        let summary = import_log(Cursor::new(raw), None, Vec::new(), &options(), |batch| {
+            events.extend(batch);
+            Ok(())
+        })
+        .unwrap();
+        (summary, events)
+    }
+
+    #[test]
+    fn detects_formats_from_sample_lines() {
+        let context = TimestampContext::utc();
+        let detect = |raw: &str| {
+            let lines: Vec<String> = raw.lines().map(str::to_string).collect();
+            detect_format(&lines, &context)
+        };
+        assert_eq!(
+            detect("<165>1 2024-03-01T10:00:00Z host app 42 ID47 - started"),
+            LogFileFormat::Rfc5424
+        );
+        assert_eq!(
+            detect("Mar  1 10:00:00 host sshd[42]: Accepted publickey"),
+            LogFileFormat::Rfc3164
+        );
+        assert_eq!(
+            detect(r#"{"time":"2024-03-01T10:00:00Z","level":"warn","msg":"slow"}"#),
+            LogFileFormat::JsonLines
+        );
+        assert_eq!(
+            detect(r#"10.0.0.1 - - [01/Mar/2024:10:00:00 +0000] "GET / HTTP/1.1" 503 12"#),
+            LogFileFormat::CommonLog
+        );
+        assert_eq!(
+            detect("2024-03-01 10:00:00,123 ERROR worker crashed"),
+            LogFileFormat::PlainText
+        );
+    }
+
+    #[test]
+    fn imports_plain_text_with_continuation_lines() {
+        let raw = "stray preamble\n2024-03-01 10:00:00,123 ERROR worker crashed\n  at main.rs:10\n\n2024-03-01 10:00:05 INFO restarted\n";
+        let (summary, events) = import(raw);
+        assert_eq!(summary.format, Some(LogFileFormat::PlainText));
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(events[0].severity, "error");
+        assert_eq!(events[0].message, "ERROR worker crashed\n  at main.rs:10");
+        assert_eq!(events[0].timestamp, "2024-03-01T10:00:00.123+00:00");
+        assert_eq!(events[0].provider, "app.log");
+        assert!(events.iter().all(|event| event.imported));
+        assert_eq!(import(raw).1[1].id, events[1].id);
+
+        let (_, access) =
+            import(r#"10.0.0.1 - - [01/Mar/2024:10:00:00 +0000] "GET / HTTP/1.1" 503 12"#);
+        assert_eq!(access[0].event_id, Some(503));
+        assert_eq!(access[0].severity, "error");
+    }
+
+    #[test]
+    fn prefers_a_matching_parser_profile() {
+        let profile = ParserProfile {
+            id: "billing".to_string(),
+            name: "Billing".to_string(),
+            pattern: r"^(?P<timestamp>\d{8}-\d{6}) \[(?P<severity>\w)\] (?P<provider>\w+)#(?P<event_id>\d+) (?P<message>.*)$".to_string(),
+            timestamp_format: "%Y%m%d-%H%M%S".to_string(),
+            severity_map: [("e".to_string(), "error".to_string())].into_iter().collect(),
+        };
+        let raw = "20240301-100000 [E] ledger#17 balance mismatch\n";
+        let mut events = Vec::new();
+        let profiles = vec![ProfileParser::new(profile).unwrap()];
+        let summary = import_log(Cursor::new(raw), None, profiles, &options(), |batch| {
+            events.extend(batch);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(summary.profile.as_deref(), Some("Billing"));
+        assert_eq!(summary.format, None);
+        assert_eq!(events[0].timestamp, "2024-03-01T10:00:00+00:00");
+        assert_eq!(events[0].severity, "error");
+        assert_eq!(events[0].provider, "ledger");
+        assert_eq!(events[0].event_id, Some(17));
+        assert_eq!(events[0].message, "balance mismatch");
+    }
+
+    #[test]
+    fn orders_rotations_and_resumes_after_offset() {
+        let dir = std::env::temp_dir().join(format!("hermes-logs-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["app.log", "app.log.1", "app.log.2.gz", ".hidden", "old.xz"] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+        let rotated = fs::File::create(dir.join("app.log.2.gz")).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(rotated, flate2::Compression::fast());
+        std::io::Write::write_all(&mut encoder, b"2024-03-01 10:00:00 ERROR first\n").unwrap();
+        encoder.finish().unwrap();
+
+        let files = list_log_directory(&dir).unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["app.log.2.gz", "app.log.1", "app.log"]);
+
+        let import = |offset| {
+            let mut messages = Vec::new();
+            let summary =
+                import_log_path(&files[0], offset, None, Vec::new(), &options(), |batch| {
+                    messages.extend(batch.into_iter().map(|event| event.message));
+                    Ok(())
+                })
+                .unwrap();
+            (summary.end_offset, messages)
+        };
+        let (end, messages) = import(0);
+        assert_eq!(messages, ["ERROR first"]);
+        assert_eq!(import(end), (end, Vec::new()));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
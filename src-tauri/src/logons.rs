@@ -0,0 +1,394 @@
+//! Failed-logon and brute-force triage: Windows 4624/4625 and Linux sshd authentication events
+//! aggregated by account and source address, with bursts of failures called out and flagged
+//! when the same source later logged on successfully.
+
+use crate::db;
+use crate::logs::NormalizedEvent;
+use crate::nl_query::EventQueryFilter;
+use crate::sigma::raw_fields;
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Duration, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::LazyLock;
+
+pub const DEFAULT_TOP: usize = 10;
+/// Failures further apart than this end a burst.
+const BURST_GAP_MINUTES: i64 = 2;
+const BURST_MIN_FAILURES: usize = 10;
+/// How long after a burst a success from the same source is reported as a possible compromise.
+const SUCCESS_AFTER_BURST_MINUTES: i64 = 60;
+/// Service and system logons (types 5 and 0) are the machine talking to itself.
+const IGNORED_LOGON_TYPES: [&str; 2] = ["0", "5"];
+
+static SSHD_AUTH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(Failed|Accepted) \S+ for (?:invalid user )?(\S*) from (\S+) port \d+")
+        .expect("sshd pattern is valid")
+});
+
+#[derive(Debug, Clone, PartialEq)]
+struct AuthAttempt {
+    at: DateTime<Utc>,
+    account: String,
+    source: Option<String>,
+    success: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogonAggregate {
+    /// Account name, or source address for `top_sources`.
+    pub key: String,
+    pub failures: usize,
+    pub successes: usize,
+    /// Distinct source addresses for an account, or distinct accounts for a source.
+    pub distinct: usize,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureBurst {
+    /// Source address, or `local` when the events carry none.
+    pub source: String,
+    pub start: String,
+    pub end: String,
+    pub failures: usize,
+    pub accounts: Vec<String>,
+    /// The first success from the same source during the burst or within an hour after it.
+    pub success_after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogonReport {
+    pub window_start: String,
+    pub window_end: String,
+    pub failures: usize,
+    pub successes: usize,
+    pub top_accounts: Vec<LogonAggregate>,
+    pub top_sources: Vec<LogonAggregate>,
+    /// Largest first.
+    pub bursts: Vec<FailureBurst>,
+}
+
+/// `-`, empty and loopback addresses mean the logon did not come over the network.
+fn source_address(value: Option<&str>) -> Option<String> {
+    let value = value?.trim();
+    if value.is_empty() || matches!(value, "-" | "::1" | "127.0.0.1") {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+fn windows_attempt(event: &NormalizedEvent) -> Option<AuthAttempt> {
+    let success = match event.event_id {
+        Some(4624) => true,
+        Some(4625) => false,
+        _ => return None,
+    };
+    if !event
+        .provider
+        .eq_ignore_ascii_case("Microsoft-Windows-Security-Auditing")
+    {
+        return None;
+    }
+    let fields = raw_fields(event.raw_payload.as_deref().unwrap_or_default());
+    let account = fields
+        .get("TargetUserName")
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty() && *name != "-")?;
+    // Computer accounts log on constantly and are never the target of password guessing.
+    if account.ends_with('$')
+        || fields
+            .get("LogonType")
+            .is_some_and(|logon_type| IGNORED_LOGON_TYPES.contains(&logon_type.trim()))
+    {
+        return None;
+    }
+    let account = match fields.get("TargetDomainName").map(|domain| domain.trim()) {
+        Some(domain) if !domain.is_empty() && domain != "-" => format!("{domain}\\{account}"),
+        _ => account.to_string(),
+    };
+    Some(AuthAttempt {
+        at: parse_timestamp(event.timestamp.as_str())?,
+        account,
+        source: source_address(fields.get("IpAddress").map(String::as_str)),
+        success,
+    })
+}
+
+fn sshd_attempt(event: &NormalizedEvent) -> Option<AuthAttempt> {
+    let captures = SSHD_AUTH.captures(event.message.trim())?;
+    Some(AuthAttempt {
+        at: parse_timestamp(event.timestamp.as_str())?,
+        account: captures[2].to_string(),
+        source: source_address(Some(&captures[3])),
+        success: &captures[1] == "Accepted",
+    })
+}
+
+/// The authentication attempt `event` records, if it records one.
+fn auth_attempt(event: &NormalizedEvent) -> Option<AuthAttempt> {
+    if event.os == "windows" {
+        windows_attempt(event)
+    } else {
+        sshd_attempt(event)
+    }
+}
+
+fn aggregate(
+    attempts: &[AuthAttempt],
+    key: impl Fn(&AuthAttempt) -> Option<String>,
+    other: impl Fn(&AuthAttempt) -> Option<String>,
+    top: usize,
+) -> Vec<LogonAggregate> {
+    let mut groups: BTreeMap<String, (LogonAggregate, BTreeSet<String>)> = BTreeMap::new();
+    for attempt in attempts {
+        let Some(key) = key(attempt) else {
+            continue;
+        };
+        let timestamp = attempt.at.to_rfc3339();
+        let (entry, distinct) = groups.entry(key.clone()).or_insert_with(|| {
+            (
+                LogonAggregate {
+                    key,
+                    first_seen: timestamp.clone(),
+                    ..LogonAggregate::default()
+                },
+                BTreeSet::new(),
+            )
+        });
+        if attempt.success {
+            entry.successes += 1;
+        } else {
+            entry.failures += 1;
+        }
+        entry.last_seen = timestamp;
+        if let Some(other) = other(attempt) {
+            distinct.insert(other);
+        }
+    }
+    let mut aggregates: Vec<LogonAggregate> = groups
+        .into_values()
+        .map(|(mut entry, distinct)| {
+            entry.distinct = distinct.len();
+            entry
+        })
+        .filter(|entry| entry.failures > 0)
+        .collect();
+    aggregates.sort_by(|left, right| {
+        right
+            .failures
+            .cmp(&left.failures)
+            .then_with(|| left.key.cmp(&right.key))
+    });
+    aggregates.truncate(top);
+    aggregates
+}
+
+/// Runs of at least `BURST_MIN_FAILURES` failures from one source with no gap longer than
+/// `BURST_GAP_MINUTES`. `attempts` must be in time order.
+fn bursts(attempts: &[AuthAttempt]) -> Vec<FailureBurst> {
+    let mut by_source: BTreeMap<String, Vec<&AuthAttempt>> = BTreeMap::new();
+    for attempt in attempts {
+        let source = attempt
+            .source
+            .clone()
+            .unwrap_or_else(|| "local".to_string());
+        by_source.entry(source).or_default().push(attempt);
+    }
+
+    let gap = Duration::minutes(BURST_GAP_MINUTES);
+    let mut found = Vec::new();
+    for (source, attempts) in by_source {
+        let failures: Vec<&AuthAttempt> = attempts
+            .iter()
+            .copied()
+            .filter(|attempt| !attempt.success)
+            .collect();
+        let mut run_start = 0;
+        for index in 1..=failures.len() {
+            let ends_run =
+                index == failures.len() || failures[index].at - failures[index - 1].at > gap;
+            if !ends_run {
+                continue;
+            }
+            let run = &failures[run_start..index];
+            run_start = index;
+            if run.len() < BURST_MIN_FAILURES {
+                continue;
+            }
+            let (start, end) = (run[0].at, run[run.len() - 1].at);
+            let accounts: BTreeSet<String> =
+                run.iter().map(|attempt| attempt.account.clone()).collect();
+            let success_after = attempts
+                .iter()
+                .find(|attempt| {
+                    attempt.success
+                        && attempt.at >= start
+                        && attempt.at <= end + Duration::minutes(SUCCESS_AFTER_BURST_MINUTES)
+                })
+                .map(|attempt| attempt.at.to_rfc3339());
+            found.push(FailureBurst {
+                source: source.clone(),
+                start: start.to_rfc3339(),
+                end: end.to_rfc3339(),
+                failures: run.len(),
+                accounts: accounts.into_iter().collect(),
+                success_after,
+            });
+        }
+    }
+    found.sort_by_key(|burst| std::cmp::Reverse(burst.failures));
+    found
+}
+
+fn report(mut attempts: Vec<AuthAttempt>, start: &str, end: &str, top: usize) -> LogonReport {
+    attempts.sort_by_key(|attempt| attempt.at);
+    let failures = attempts.iter().filter(|attempt| !attempt.success).count();
+    LogonReport {
+        window_start: start.to_string(),
+        window_end: end.to_string(),
+        failures,
+        successes: attempts.len() - failures,
+        top_accounts: aggregate(
+            attempts.as_slice(),
+            |attempt| Some(attempt.account.clone()),
+            |attempt| attempt.source.clone(),
+            top,
+        ),
+        top_sources: aggregate(
+            attempts.as_slice(),
+            |attempt| attempt.source.clone(),
+            |attempt| Some(attempt.account.clone()),
+            top,
+        ),
+        bursts: bursts(attempts.as_slice()),
+    }
+}
+
+/// Analyzes the stored logon events of `host` between `start` and `end` (RFC3339).
+pub fn analyze_logons(
+    start: &str,
+    end: &str,
+    host: Option<&str>,
+    top: usize,
+) -> Result<LogonReport, String> {
+    let window = EventQueryFilter {
+        start: Some(start.to_string()),
+        end: Some(end.to_string()),
+        ..EventQueryFilter::default()
+    };
+    let passes = [
+        EventQueryFilter {
+            event_id: Some(4625),
+            ..window.clone()
+        },
+        EventQueryFilter {
+            event_id: Some(4624),
+            ..window.clone()
+        },
+        EventQueryFilter {
+            providers: vec!["sshd".to_string()],
+            ..window
+        },
+    ];
+    let mut attempts = Vec::new();
+    for filter in &passes {
+        db::for_each_event_with_raw(filter, host, |event| {
+            attempts.extend(auth_attempt(&event));
+            Ok(())
+        })?;
+    }
+    Ok(report(attempts, start, end, top))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn sshd(message: &str, timestamp: &str) -> NormalizedEvent {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Linux,
+            "sshd",
+            "security",
+            "sshd",
+            None,
+            "warning",
+            message,
+            "web01",
+        );
+        event.timestamp = timestamp.to_string();
+        event
+    }
+
+    #[test]
+    fn parses_sshd_and_windows_attempts() {
+        let failed = sshd(
+            "Failed password for invalid user admin from 203.0.113.7 port 52211 ssh2",
+            "2026-03-01T10:00:00Z",
+        );
+        let attempt = auth_attempt(&failed).unwrap();
+        assert_eq!(attempt.account, "admin");
+        assert_eq!(attempt.source.as_deref(), Some("203.0.113.7"));
+        assert!(!attempt.success);
+        assert!(
+            auth_attempt(&sshd("Server listening on 0.0.0.0", "2026-03-01T10:00:00Z")).is_none()
+        );
+
+        let mut logon = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "Security",
+            "security",
+            "Microsoft-Windows-Security-Auditing",
+            Some(4625),
+            "warning",
+            "An account failed to log on.",
+            "ws01",
+        );
+        logon.timestamp = "2026-03-01T10:00:00Z".to_string();
+        logon.raw_payload = Some("<Event><EventData><Data Name='TargetUserName'>alice</Data><Data Name='TargetDomainName'>CORP</Data><Data Name='LogonType'>3</Data><Data Name='IpAddress'>198.51.100.4</Data></EventData></Event>".to_string());
+        let attempt = auth_attempt(&logon).unwrap();
+        assert_eq!(attempt.account, "CORP\\alice");
+        assert_eq!(attempt.source.as_deref(), Some("198.51.100.4"));
+    }
+
+    #[test]
+    fn finds_bursts_and_success_after() {
+        let mut events: Vec<NormalizedEvent> = (0..12)
+            .map(|second| {
+                sshd(
+                    format!("Failed password for root from 203.0.113.7 port {second} ssh2")
+                        .as_str(),
+                    format!("2026-03-01T10:00:{second:02}Z").as_str(),
+                )
+            })
+            .collect();
+        events.push(sshd(
+            "Accepted password for root from 203.0.113.7 port 1 ssh2",
+            "2026-03-01T10:05:00Z",
+        ));
+        events.push(sshd(
+            "Failed password for bob from 198.51.100.9 port 1 ssh2",
+            "2026-03-01T11:00:00Z",
+        ));
+        let attempts = events.iter().filter_map(auth_attempt).collect();
+        let report = report(attempts, "start", "end", DEFAULT_TOP);
+
+        assert_eq!((report.failures, report.successes), (13, 1));
+        assert_eq!(report.top_accounts[0].key, "root");
+        assert_eq!(report.top_accounts[0].failures, 12);
+        assert_eq!(report.top_sources[0].key, "203.0.113.7");
+        assert_eq!(report.bursts.len(), 1);
+        assert_eq!(report.bursts[0].failures, 12);
+        assert_eq!(
+            report.bursts[0].success_after.as_deref(),
+            Some("2026-03-01T10:05:00+00:00")
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! Linux audit (auditd) records. Each audit event, which may span several records sharing a
+//! serial (SYSCALL plus CWD, PATH and PROCTITLE, say), becomes one `audit` event whose message
+//! keeps every record's key=value pairs for investigations.
+
+use super::{CollectionResult, NormalizedEvent, SupportedOs};
+use crate::timestamps::from_epoch_micros;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::Path;
+use std::process::Command;
+
+pub const AUDIT_CATEGORY: &str = "audit";
+
+const AUDIT_LOG: &str = "/var/log/audit/audit.log";
+const MAX_AUDIT_EVENTS: usize = 2000;
+/// Records that only add context to the main record of an event.
+const AUXILIARY_TYPES: [&str; 7] = [
+    "CWD",
+    "PATH",
+    "PROCTITLE",
+    "EXECVE",
+    "SOCKADDR",
+    "BPRM_FCAPS",
+    "EOE",
+];
+/// Fields worth surfacing in the one-line summary, in display order.
+const SUMMARY_FIELDS: [&str; 10] = [
+    "acct", "auid", "uid", "comm", "exe", "syscall", "addr", "terminal", "key", "res",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+struct AuditRecord {
+    record_type: String,
+    /// Seconds and milliseconds since the epoch, from `msg=audit(1700000000.123:456)`.
+    epoch_millis: i64,
+    serial: u64,
+    /// Free text before the first pair, such as AVC's `avc:  denied  { read } for`.
+    text: String,
+    fields: Vec<(String, String)>,
+}
+
+impl AuditRecord {
+    fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Splits `key=value` pairs, honoring double quotes and the single-quoted `msg='...'` that
+/// user-space records (USER_LOGIN, USER_AUTH, ...) nest their own pairs in. Enriched logs append
+/// interpreted fields after a 0x1d separator; those are kept as well.
+fn parse_pairs(raw: &str, text: &mut String, fields: &mut Vec<(String, String)>) {
+    let mut rest = raw.trim_start();
+    while !rest.is_empty() {
+        let token_end = rest
+            .find(|c: char| c.is_whitespace() || c == '\u{1d}')
+            .unwrap_or(rest.len());
+        let Some((key, after)) = rest[..token_end]
+            .split_once('=')
+            .map(|(key, _)| (key, &rest[key.len() + 1..]))
+            .filter(|(key, _)| !key.is_empty() && !key.contains(['{', '}']))
+        else {
+            if fields.is_empty() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(&rest[..token_end]);
+            }
+            rest =
+                rest[token_end..].trim_start_matches(|c: char| c.is_whitespace() || c == '\u{1d}');
+            continue;
+        };
+        let (value, remaining) = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let body = &after[1..];
+                let end = body.find(quote).unwrap_or(body.len());
+                (&body[..end], body.get(end + 1..).unwrap_or(""))
+            }
+            _ => {
+                let end = after
+                    .find(|c: char| c.is_whitespace() || c == '\u{1d}')
+                    .unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            }
+        };
+        if key == "msg" && after.starts_with('\'') {
+            parse_pairs(value, text, fields);
+        } else {
+            fields.push((key.to_string(), value.to_string()));
+        }
+        rest = remaining.trim_start_matches(|c: char| c.is_whitespace() || c == '\u{1d}');
+    }
+}
+
+fn parse_audit_line(line: &str) -> Option<AuditRecord> {
+    let rest = line.trim().strip_prefix("type=")?;
+    let (record_type, rest) = rest.split_once(' ')?;
+    let rest = rest.trim_start().strip_prefix("msg=audit(")?;
+    let (stamp, rest) = rest.split_once("):")?;
+    let (time, serial) = stamp.split_once(':')?;
+    let (seconds, millis) = time.split_once('.').unwrap_or((time, "0"));
+    let epoch_millis = seconds.parse::<i64>().ok()? * 1000 + millis.parse::<i64>().ok()?;
+    let mut record = AuditRecord {
+        record_type: record_type.to_string(),
+        epoch_millis,
+        serial: serial.parse().ok()?,
+        text: String::new(),
+        fields: Vec::new(),
+    };
+    parse_pairs(rest, &mut record.text, &mut record.fields);
+    Some(record)
+}
+
+fn audit_severity(records: &[AuditRecord]) -> &'static str {
+    let failed = records.iter().any(|record| {
+        matches!(record.field("res"), Some("failed" | "0"))
+            || record.field("success") == Some("no")
+            || record.text.contains("denied")
+    });
+    if records
+        .iter()
+        .any(|record| record.record_type.starts_with("ANOM_"))
+    {
+        "error"
+    } else if failed {
+        "warning"
+    } else {
+        "information"
+    }
+}
+
+/// One event per serial: the first non-auxiliary record names it, and the message lists a
+/// summary line followed by every record with its pairs.
+fn audit_event(records: &[AuditRecord]) -> Option<NormalizedEvent> {
+    let primary = records
+        .iter()
+        .find(|record| !AUXILIARY_TYPES.contains(&record.record_type.as_str()))
+        .or_else(|| records.first())?;
+    let mut summary = primary.record_type.clone();
+    if !primary.text.is_empty() {
+        summary.push(' ');
+        summary.push_str(primary.text.as_str());
+    }
+    for key in SUMMARY_FIELDS {
+        if let Some(value) = primary.field(key) {
+            summary.push_str(format!(" {key}={value}").as_str());
+        }
+    }
+    let mut message = summary;
+    for record in records {
+        message.push('\n');
+        message.push_str(record.record_type.as_str());
+        message.push(':');
+        if !record.text.is_empty() {
+            message.push(' ');
+            message.push_str(record.text.as_str());
+        }
+        for (key, value) in &record.fields {
+            message.push_str(format!(" {key}={value}").as_str());
+        }
+    }
+
+    let timestamp = from_epoch_micros(primary.epoch_millis * 1000)?;
+    let mut event = NormalizedEvent::new(
+        SupportedOs::Linux,
+        "audit",
+        AUDIT_CATEGORY,
+        primary.record_type.as_str(),
+        None,
+        audit_severity(records),
+        message.as_str(),
+        "localhost",
+    );
+    event.timestamp = timestamp.to_rfc3339();
+    event.assign_stable_id();
+    Some(event)
+}
+
+/// Groups raw audit lines by serial and keeps events inside the window, newest `MAX_AUDIT_EVENTS`.
+fn events_from_lines(
+    lines: impl Iterator<Item = String>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Vec<NormalizedEvent> {
+    let start_millis = start.map(|value| value.timestamp_millis());
+    let end_millis = end.map(|value| value.timestamp_millis());
+    let mut grouped: BTreeMap<(i64, u64), Vec<AuditRecord>> = BTreeMap::new();
+    for record in lines.filter_map(|line| parse_audit_line(line.as_str())) {
+        if start_millis.is_some_and(|start| record.epoch_millis < start)
+            || end_millis.is_some_and(|end| record.epoch_millis > end)
+        {
+            continue;
+        }
+        grouped
+            .entry((record.epoch_millis, record.serial))
+            .or_default()
+            .push(record);
+    }
+    let skip = grouped.len().saturating_sub(MAX_AUDIT_EVENTS);
+    grouped
+        .values()
+        .skip(skip)
+        .filter_map(|records| audit_event(records))
+        .collect()
+}
+
+/// Raw audit lines via `ausearch --raw`, elevated with pkexec since the logs are root-only.
+fn read_with_ausearch() -> Result<Vec<String>, String> {
+    let output = Command::new("pkexec")
+        .args(["ausearch", "--raw", "--input-logs"])
+        .output()
+        .map_err(|error| format!("Failed to run ausearch: {error}"))?;
+    // ausearch exits 1 when nothing matched.
+    if !output.status.success() && !output.stdout.is_empty() {
+        return Err(format!(
+            "ausearch exited with status {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Appends auditd events to a host collection. Hosts without auditd are skipped silently, and
+/// so is the root-only log unless elevation was requested, in which case `ausearch` runs
+/// through pkexec.
+pub fn append_audit_events(
+    result: &mut CollectionResult,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    request_elevation: bool,
+) {
+    let path = Path::new(AUDIT_LOG);
+    if !path.exists() {
+        return;
+    }
+    let lines = match fs::File::open(path) {
+        Ok(file) => BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .collect::<Vec<_>>(),
+        Err(error) if error.kind() == ErrorKind::PermissionDenied && request_elevation => {
+            match read_with_ausearch() {
+                Ok(lines) => lines,
+                Err(error) => {
+                    result.warnings.push(error);
+                    return;
+                }
+            }
+        }
+        Err(error) if error.kind() == ErrorKind::PermissionDenied => return,
+        Err(error) => {
+            result
+                .warnings
+                .push(format!("Failed to open audit log {AUDIT_LOG}: {error}"));
+            return;
+        }
+    };
+    result
+        .events
+        .extend(events_from_lines(lines.into_iter(), start, end));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_and_decodes_audit_records() {
+        let raw = [
+            r#"type=USER_LOGIN msg=audit(1700000000.120:88): pid=912 uid=0 auid=4294967295 ses=4294967295 msg='op=login acct="root" exe="/usr/sbin/sshd" hostname=? addr=10.0.0.5 terminal=sshd res=failed'"#,
+            r#"type=SYSCALL msg=audit(1700000001.500:89): arch=c000003e syscall=59 success=yes exit=0 comm="curl" exe="/usr/bin/curl" key="exec""#,
+            r#"type=PROCTITLE msg=audit(1700000001.500:89): proctitle=6375726C"#,
+            r#"type=AVC msg=audit(1700000002.000:90): avc:  denied  { read } for  pid=77 comm="httpd" name="shadow" tclass=file permissive=0"#,
+        ];
+        let events = events_from_lines(raw.iter().map(|line| line.to_string()), None, None);
+        assert_eq!(events.len(), 3);
+
+        assert_eq!(events[0].provider, "USER_LOGIN");
+        assert_eq!(events[0].severity, "warning");
+        assert!(events[0].message.starts_with(
+            "USER_LOGIN acct=root auid=4294967295 uid=0 exe=/usr/sbin/sshd addr=10.0.0.5"
+        ));
+
+        assert_eq!(events[1].provider, "SYSCALL");
+        assert!(events[1]
+            .message
+            .contains("\nPROCTITLE: proctitle=6375726C"));
+        assert_eq!(events[1].category, AUDIT_CATEGORY);
+
+        assert_eq!(events[2].severity, "warning");
+        assert!(events[2]
+            .message
+            .starts_with("AVC avc: denied { read } for comm=httpd"));
+    }
+}
@@ -1,14 +1,46 @@
 use super::{CollectionEstimate, CollectionResult, NormalizedEvent, SupportedOs};
-use crate::settings::RemoteConnectionProfile;
-use chrono::{DateTime, Local, TimeZone, Utc};
+use crate::settings::{JournalFilter, RemoteConnectionProfile};
+use crate::timestamps::from_epoch_micros;
+use chrono::{DateTime, Local, Utc};
 use serde_json::Value;
 use std::io::{BufRead, BufReader, Read};
 use std::process::{Command, Stdio};
 
+#[cfg(target_os = "linux")]
+mod classic;
+#[cfg(all(target_os = "linux", feature = "sd-journal"))]
+mod sd_journal;
+
+/// Collects from the journal, or from the kernel ring buffer and classic syslog files on hosts
+/// where journald is not running or the journal could not be read at all.
 pub fn collect_events_range(
     start: Option<DateTime<Utc>>,
     end: Option<DateTime<Utc>>,
     max_events: Option<u32>,
+    journal_filter: Option<&JournalFilter>,
+    request_elevation: bool,
+) -> CollectionResult {
+    let result = collect_journal_range(start, end, max_events, journal_filter, request_elevation);
+    #[cfg(target_os = "linux")]
+    if result.events.is_empty() && (!result.errors.is_empty() || !classic::journald_available()) {
+        let max = max_events.unwrap_or(2000).min(10000) as usize;
+        let mut fallback = classic::collect(start, end, max);
+        if fallback.events.is_empty() {
+            fallback.errors.splice(0..0, result.errors);
+        } else {
+            fallback.warnings.extend(result.errors);
+        }
+        fallback.warnings.splice(0..0, result.warnings);
+        return fallback;
+    }
+    result
+}
+
+fn collect_journal_range(
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    max_events: Option<u32>,
+    journal_filter: Option<&JournalFilter>,
     request_elevation: bool,
 ) -> CollectionResult {
     let max = max_events.unwrap_or(2000).min(10000) as usize;
@@ -16,6 +48,17 @@ pub fn collect_events_range(
         return CollectionResult::default();
     }
 
+    // libsystemd reads the journal directly; pkexec elevation still needs the journalctl binary.
+    #[cfg(all(target_os = "linux", feature = "sd-journal"))]
+    let sd_journal_error = if request_elevation {
+        None
+    } else {
+        match sd_journal::collect(start, end, max, journal_filter) {
+            Ok(result) => return result,
+            Err(error) => Some(error),
+        }
+    };
+
     let mut args = vec![
         "--no-pager".to_string(),
         "-o".to_string(),
@@ -29,6 +72,7 @@ pub fn collect_events_range(
         args.push("--until".to_string());
         args.push(format_journal_time(value));
     }
+    args.extend(journal_filter_args(journal_filter));
     args.push("-n".to_string());
     args.push(max.to_string());
 
@@ -47,6 +91,10 @@ pub fn collect_events_range(
         .stderr(Stdio::piped());
 
     let mut result = CollectionResult::default();
+    #[cfg(all(target_os = "linux", feature = "sd-journal"))]
+    if let Some(error) = sd_journal_error {
+        result.warnings.push(format!("{error} Falling back to journalctl."));
+    }
 
     let mut child = match command.spawn() {
         Ok(child) => child,
@@ -147,8 +195,19 @@ pub fn collect_events_range(
 pub fn estimate_events_range(
     start: Option<DateTime<Utc>>,
     end: Option<DateTime<Utc>>,
+    journal_filter: Option<&JournalFilter>,
     request_elevation: bool,
 ) -> CollectionEstimate {
+    #[cfg(all(target_os = "linux", feature = "sd-journal"))]
+    let sd_journal_error = if request_elevation {
+        None
+    } else {
+        match sd_journal::estimate(start, end, journal_filter) {
+            Ok(estimate) => return estimate,
+            Err(error) => Some(error),
+        }
+    };
+
     let mut args = vec![
         "--no-pager".to_string(),
         "-o".to_string(),
@@ -162,6 +221,7 @@ pub fn estimate_events_range(
         args.push("--until".to_string());
         args.push(format_journal_time(value));
     }
+    args.extend(journal_filter_args(journal_filter));
 
     let mut command = if request_elevation {
         let mut cmd = Command::new("pkexec");
@@ -176,6 +236,10 @@ pub fn estimate_events_range(
     command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
     let mut estimate = CollectionEstimate::default();
+    #[cfg(all(target_os = "linux", feature = "sd-journal"))]
+    if let Some(error) = sd_journal_error {
+        estimate.warnings.push(format!("{error} Falling back to journalctl."));
+    }
     let mut child = match command.spawn() {
         Ok(child) => child,
         Err(error) => {
@@ -296,17 +360,23 @@ fn stderr_looks_like_permission_issue(stderr: &str) -> bool {
 
 fn parse_journal_line(line: &str) -> Option<NormalizedEvent> {
     let value: Value = serde_json::from_str(line).ok()?;
-    let message = get_string(&value, "MESSAGE").unwrap_or("No log message.");
-    let identifier = get_string(&value, "SYSLOG_IDENTIFIER");
-    let comm = get_string(&value, "_COMM");
-    let unit = get_string(&value, "_SYSTEMD_UNIT");
-    let transport = get_string(&value, "_TRANSPORT");
+    let mut event = parse_journal_value(&value)?;
+    event.raw_payload = Some(line.to_string());
+    Some(event)
+}
+
+fn parse_journal_value(value: &Value) -> Option<NormalizedEvent> {
+    let message = get_string(value, "MESSAGE").unwrap_or("No log message.");
+    let identifier = get_string(value, "SYSLOG_IDENTIFIER");
+    let comm = get_string(value, "_COMM");
+    let unit = get_string(value, "_SYSTEMD_UNIT");
+    let transport = get_string(value, "_TRANSPORT");
 
     let log_name = pick_value(&[identifier, comm, unit, transport]).unwrap_or("journal");
-    let provider = pick_value(&[comm, identifier, get_string(&value, "_EXE")]).unwrap_or("unknown");
+    let provider = pick_value(&[comm, identifier, get_string(value, "_EXE")]).unwrap_or("unknown");
     let category = map_category(&[identifier, comm, unit, transport, Some(provider)]);
     let severity = map_severity(
-        get_string(&value, "PRIORITY").or_else(|| get_string(&value, "SYSLOG_PRIORITY")),
+        get_string(value, "PRIORITY").or_else(|| get_string(value, "SYSLOG_PRIORITY")),
     );
 
     let mut event = NormalizedEvent::new(
@@ -320,7 +390,7 @@ fn parse_journal_line(line: &str) -> Option<NormalizedEvent> {
         "localhost",
     );
 
-    if let Some(timestamp) = parse_journal_timestamp(&value) {
+    if let Some(timestamp) = parse_journal_timestamp(value) {
         event.timestamp = timestamp;
     }
 
@@ -340,11 +410,7 @@ fn parse_journal_timestamp(value: &Value) -> Option<String> {
         _ => return None,
     };
 
-    let secs = micros / 1_000_000;
-    let nanos = ((micros % 1_000_000) * 1000) as u32;
-    Utc.timestamp_opt(secs, nanos)
-        .single()
-        .map(|dt| dt.to_rfc3339())
+    from_epoch_micros(micros).map(|dt| dt.to_rfc3339())
 }
 
 fn map_severity(priority: Option<&str>) -> &'static str {
@@ -370,6 +436,8 @@ fn map_category(values: &[Option<&str>]) -> &'static str {
     let lower = combined.to_ascii_lowercase();
     if lower.contains("audit") {
         "audit"
+    } else if super::printing::is_printing_source(lower.as_str()) {
+        super::printing::PRINTING_CATEGORY
     } else if lower.contains("auth")
         || lower.contains("ssh")
         || lower.contains("sudo")
@@ -399,6 +467,28 @@ fn pick_value<'a>(values: &[Option<&'a str>]) -> Option<&'a str> {
         .find(|value| !value.trim().is_empty())
 }
 
+/// `-u`, `-t` and `-p` match options. journalctl ORs repeated options of one kind and ANDs the
+/// kinds together, so units and identifiers narrow independently.
+fn journal_filter_args(filter: Option<&JournalFilter>) -> Vec<String> {
+    let Some(filter) = filter else {
+        return Vec::new();
+    };
+    let mut args = Vec::new();
+    for unit in &filter.units {
+        args.push("-u".to_string());
+        args.push(unit.clone());
+    }
+    for identifier in &filter.identifiers {
+        args.push("-t".to_string());
+        args.push(identifier.clone());
+    }
+    if !filter.priority.is_empty() {
+        args.push("-p".to_string());
+        args.push(filter.priority.clone());
+    }
+    args
+}
+
 fn sanitize_message(message: &str) -> &str {
     if message.trim().is_empty() {
         return "No log message.";
@@ -412,7 +502,7 @@ pub fn collect_remote_linux_events(
     start: Option<DateTime<Utc>>,
     end: Option<DateTime<Utc>>,
     max_events: Option<u32>,
-    _channels: Option<&[String]>,
+    journal_filter: Option<&JournalFilter>,
 ) -> CollectionResult {
     let max = max_events.unwrap_or(2000).min(10000) as usize;
     if max == 0 {
@@ -422,7 +512,7 @@ pub fn collect_remote_linux_events(
     if profile.auth_type.eq_ignore_ascii_case("password") {
         let mut result = CollectionResult::default();
         result.errors.push(format!(
-            "Remote SSH password authentication is not implemented for {}. Use SSH key-based auth instead.",
+            "Remote SSH password authentication is not implemented for {}. Use SSH key or agent auth instead.",
             profile.host
         ));
         return result;
@@ -443,6 +533,7 @@ pub fn collect_remote_linux_events(
         args.push("--until".to_string());
         args.push(format_journal_time(end_time));
     }
+    args.extend(journal_filter_args(journal_filter));
 
     args.push("-n".to_string());
     args.push(max.to_string());
@@ -453,28 +544,7 @@ pub fn collect_remote_linux_events(
     
     let journal_cmd = format!("journalctl {}", args.join(" "));
 
-    let mut ssh_args = vec![
-        "-o".to_string(), "BatchMode=yes".to_string(),
-        "-o".to_string(), "StrictHostKeyChecking=no".to_string()
-    ];
-
-    if let Some(key_path) = &profile.ssh_key_path {
-        if !key_path.is_empty() {
-            ssh_args.push("-i".to_string());
-            ssh_args.push(key_path.to_string());
-        }
-    }
-
-    let user_host = if profile.username.is_empty() {
-        profile.host.clone()
-    } else {
-        format!("{}@{}", profile.username, profile.host)
-    };
-    ssh_args.push(user_host);
-    ssh_args.push(journal_cmd);
-
-    let mut command = std::process::Command::new("ssh");
-    command.args(&ssh_args);
+    let mut command = crate::remote_common::ssh_command(profile, &journal_cmd);
     command
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped());
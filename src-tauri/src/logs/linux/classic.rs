@@ -0,0 +1,231 @@
+//! Linux collection for hosts without journald (Alpine, older distros, containers): the kernel
+//! ring buffer plus the classic `/var/log/syslog` and `/var/log/messages` files.
+
+use super::{map_category, map_severity};
+use crate::log_import::{self, LogImportOptions};
+use crate::logs::{CollectionResult, NormalizedEvent, SupportedOs};
+use crate::timestamps::{from_epoch_micros, TimestampContext};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::process::Command;
+
+const SYSLOG_FILES: [&str; 2] = ["/var/log/syslog", "/var/log/messages"];
+/// Linux `O_NONBLOCK`, so reading `/dev/kmsg` stops at the end of the buffer instead of waiting.
+const O_NONBLOCK: i32 = 0o4000;
+
+/// True when journald is running, in which case `journalctl` is the source of record.
+pub fn journald_available() -> bool {
+    Path::new("/run/systemd/journal").is_dir()
+}
+
+/// Boot time from `/proc/stat`, to turn kernel timestamps (time since boot) into wall time.
+fn boot_time_micros() -> Option<i64> {
+    let stat = fs::read_to_string("/proc/stat").ok()?;
+    let seconds = stat
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))?
+        .trim()
+        .parse::<i64>()
+        .ok()?;
+    Some(seconds * 1_000_000)
+}
+
+/// One kernel record: syslog priority, microseconds since boot and message.
+struct KernelRecord {
+    priority: u8,
+    since_boot_micros: i64,
+    message: String,
+}
+
+/// `dmesg --json` output (util-linux 2.35 and later). `pri` carries the facility in its upper
+/// bits and `time` is in seconds since boot.
+fn parse_dmesg_json(raw: &str) -> Option<Vec<KernelRecord>> {
+    let value: Value = serde_json::from_str(raw).ok()?;
+    let records = value.get("dmesg")?.as_array()?;
+    Some(
+        records
+            .iter()
+            .filter_map(|record| {
+                Some(KernelRecord {
+                    priority: (record.get("pri")?.as_u64()? & 7) as u8,
+                    since_boot_micros: (record.get("time")?.as_f64()? * 1_000_000.0) as i64,
+                    message: record.get("msg")?.as_str()?.to_string(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// A `/dev/kmsg` record: `priority,sequence,microseconds,flags;message`. Continuation lines
+/// holding device properties start with a space and are skipped.
+fn parse_kmsg_line(line: &str) -> Option<KernelRecord> {
+    let (header, message) = line.split_once(';')?;
+    let mut fields = header.split(',');
+    let priority = fields.next()?.parse::<u32>().ok()?;
+    let _sequence = fields.next()?;
+    let since_boot_micros = fields.next()?.parse::<i64>().ok()?;
+    Some(KernelRecord {
+        priority: (priority & 7) as u8,
+        since_boot_micros,
+        message: message.to_string(),
+    })
+}
+
+fn read_kmsg() -> Result<Vec<KernelRecord>, String> {
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(O_NONBLOCK)
+        .open("/dev/kmsg")
+        .map_err(|error| format!("Failed to open /dev/kmsg: {error}"))?;
+    let mut records = Vec::new();
+    // Each read returns one record; the buffer's end shows up as EAGAIN (WouldBlock).
+    for line in BufReader::new(file).lines() {
+        match line {
+            Ok(line) => records.extend(parse_kmsg_line(line.as_str())),
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => break,
+            // Records overwritten while reading (EPIPE) are skipped.
+            Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => continue,
+            Err(error) => return Err(format!("Failed to read /dev/kmsg: {error}")),
+        }
+    }
+    Ok(records)
+}
+
+fn read_kernel_records() -> Result<Vec<KernelRecord>, String> {
+    let dmesg = Command::new("dmesg").arg("--json").output();
+    if let Ok(output) = dmesg {
+        if output.status.success() {
+            if let Some(records) =
+                parse_dmesg_json(String::from_utf8_lossy(&output.stdout).as_ref())
+            {
+                return Ok(records);
+            }
+        }
+    }
+    read_kmsg()
+}
+
+fn kernel_event(record: KernelRecord, boot_micros: i64) -> Option<NormalizedEvent> {
+    let timestamp = from_epoch_micros(boot_micros + record.since_boot_micros)?;
+    let mut event = NormalizedEvent::new(
+        SupportedOs::Linux,
+        "kernel",
+        "system",
+        "kernel",
+        None,
+        map_severity(Some(record.priority.to_string().as_str())),
+        record.message.trim(),
+        "localhost",
+    );
+    event.timestamp = timestamp.to_rfc3339();
+    event.assign_stable_id();
+    Some(event)
+}
+
+fn read_syslog_file(path: &Path, result: &mut CollectionResult) {
+    let options = LogImportOptions {
+        log_name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "syslog".to_string()),
+        source_host: "localhost".to_string(),
+        os: SupportedOs::Linux,
+        fallback_timestamp: Utc::now(),
+        context: TimestampContext::local(),
+    };
+    let imported = log_import::import_log_path(path, 0, None, Vec::new(), &options, |batch| {
+        result.events.extend(batch.into_iter().map(|mut event| {
+            event.imported = false;
+            event.category = map_category(&[Some(event.provider.as_str())]).to_string();
+            event.assign_stable_id();
+            event
+        }));
+        Ok(())
+    });
+    if let Err(error) = imported {
+        result.warnings.push(format!("{}: {error}", path.display()));
+    }
+}
+
+/// Newest `max` kernel and syslog-file entries in the window. Journal filters do not apply
+/// here since these sources carry no unit or priority fields to match.
+pub fn collect(
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    max: usize,
+) -> CollectionResult {
+    let mut result = CollectionResult::default();
+    if max == 0 {
+        return result;
+    }
+
+    match (read_kernel_records(), boot_time_micros()) {
+        (Ok(records), Some(boot_micros)) => result.events.extend(
+            records
+                .into_iter()
+                .filter_map(|record| kernel_event(record, boot_micros)),
+        ),
+        (Ok(_), None) => result
+            .warnings
+            .push("Kernel messages skipped: boot time is unavailable.".to_string()),
+        (Err(error), _) => result.warnings.push(error),
+    }
+
+    let files: Vec<&Path> = SYSLOG_FILES
+        .iter()
+        .map(Path::new)
+        .filter(|path| path.is_file())
+        .collect();
+    for path in &files {
+        read_syslog_file(path, &mut result);
+    }
+
+    let in_window = |event: &NormalizedEvent| {
+        DateTime::parse_from_rfc3339(event.timestamp.as_str())
+            .map(|timestamp| {
+                let timestamp = timestamp.with_timezone(&Utc);
+                start.is_none_or(|start| timestamp >= start)
+                    && end.is_none_or(|end| timestamp <= end)
+            })
+            .unwrap_or(false)
+    };
+    result.events.retain(in_window);
+    result.events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    if result.events.len() > max {
+        result.events.drain(..result.events.len() - max);
+    }
+    if result.events.is_empty() && files.is_empty() {
+        result.errors.push(
+            "No journald, readable kernel log or /var/log/syslog or /var/log/messages was found."
+                .to_string(),
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_kernel_records() {
+        let records =
+            parse_dmesg_json(r#"{"dmesg":[{"pri":30,"time":1.5,"msg":"usb 1-1: new device"}]}"#)
+                .unwrap();
+        assert_eq!(records[0].priority, 6);
+        assert_eq!(records[0].since_boot_micros, 1_500_000);
+
+        let record = parse_kmsg_line("3,812,2034567,-;EXT4-fs error (device sda1)").unwrap();
+        assert_eq!(record.priority, 3);
+        assert_eq!(record.since_boot_micros, 2_034_567);
+        assert!(parse_kmsg_line(" SUBSYSTEM=usb").is_none());
+
+        let event = kernel_event(record, 1_700_000_000_000_000).unwrap();
+        assert_eq!(event.severity, "error");
+        assert_eq!(event.provider, "kernel");
+    }
+}
@@ -0,0 +1,256 @@
+//! Reads the local journal through libsystemd instead of spawning `journalctl`, so collection
+//! works where the binary is missing from PATH (e.g. Flatpak with a journal sandbox hole). The
+//! newest entry read is saved as a cursor per filter, and later collections stop there instead
+//! of reading the same entries again.
+
+use super::parse_journal_value;
+use crate::db::{self, JournalCursor};
+use crate::logs::{CollectionEstimate, CollectionResult};
+use crate::settings::JournalFilter;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use systemd::journal::{Journal, JournalSeek, OpenOptions};
+
+const PRIORITY_NAMES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+fn priority_level(value: &str) -> Option<u8> {
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|level| *level <= 7)
+        .or_else(|| {
+            PRIORITY_NAMES
+                .iter()
+                .position(|name| *name == value)
+                .map(|level| level as u8)
+        })
+}
+
+/// Levels selected by a `-p` style priority: a single level means it and everything more
+/// severe, `from..to` an inclusive range.
+fn priority_levels(priority: &str) -> Vec<u8> {
+    let (from, to) = match priority.split_once("..") {
+        Some((from, to)) => (priority_level(from), priority_level(to)),
+        None => (Some(0), priority_level(priority)),
+    };
+    match (from, to) {
+        (Some(from), Some(to)) => (from.min(to)..=from.max(to)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// journalctl resolves a bare unit name to its `.service`; matches here are exact, so do the same.
+fn unit_name(unit: &str) -> String {
+    if unit.contains('.') {
+        unit.to_string()
+    } else {
+        format!("{unit}.service")
+    }
+}
+
+/// Opens the system journal with the filter applied as matches. Values of one field are ORed
+/// and different fields ANDed, the same as repeated `-u`, `-t` and `-p` options.
+fn open_journal(filter: Option<&JournalFilter>) -> Result<Journal, String> {
+    let mut journal = OpenOptions::default()
+        .system(true)
+        .local_only(true)
+        .open()
+        .map_err(|error| format!("Failed to open the systemd journal: {error}"))?;
+    if let Some(filter) = filter {
+        for unit in &filter.units {
+            journal
+                .match_add("_SYSTEMD_UNIT", unit_name(unit))
+                .map_err(|error| format!("Invalid journal unit match: {error}"))?;
+        }
+        for identifier in &filter.identifiers {
+            journal
+                .match_add("SYSLOG_IDENTIFIER", identifier.as_str())
+                .map_err(|error| format!("Invalid journal identifier match: {error}"))?;
+        }
+        for level in priority_levels(filter.priority.as_str()) {
+            journal
+                .match_add("PRIORITY", level.to_string())
+                .map_err(|error| format!("Invalid journal priority match: {error}"))?;
+        }
+    }
+    Ok(journal)
+}
+
+fn epoch_usec(value: DateTime<Utc>) -> u64 {
+    value.timestamp_micros().max(0) as u64
+}
+
+/// Cursors are kept per filter, since another filter has read other entries.
+fn cursor_key(filter: Option<&JournalFilter>) -> String {
+    filter
+        .and_then(|filter| serde_json::to_string(filter).ok())
+        .unwrap_or_default()
+}
+
+/// How far a walk got, to resume the next one from.
+struct WalkEnd {
+    /// Cursor of the newest entry visited, or of the resume point when nothing newer was.
+    newest: Option<String>,
+    /// Realtime back to which every entry was visited or had been before.
+    oldest_usec: u64,
+    /// No entry newer than `end` was passed over, so the walk began at the newest entry.
+    from_tail: bool,
+}
+
+/// Walks entries newest first from `end` back to `start`, handing each one's fields (with the
+/// realtime timestamp added) to `visit` until it returns false. Reaching `resume` skips back to
+/// its `oldest_usec`, over entries an earlier walk already visited.
+fn walk_back(
+    journal: &mut Journal,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    resume: Option<&JournalCursor>,
+    mut visit: impl FnMut(Map<String, Value>) -> bool,
+) -> Result<WalkEnd, String> {
+    let seek = match end {
+        Some(end) => JournalSeek::ClockRealtime {
+            usec: epoch_usec(end),
+        },
+        None => JournalSeek::Tail,
+    };
+    journal
+        .seek(seek)
+        .map_err(|error| format!("Failed to seek the systemd journal: {error}"))?;
+    let start_usec = start.map(epoch_usec).unwrap_or(0);
+    let end_usec = end.map(epoch_usec).unwrap_or(u64::MAX);
+    let mut walk = WalkEnd {
+        newest: None,
+        oldest_usec: start_usec,
+        from_tail: true,
+    };
+    let mut resume = resume;
+
+    while let Some(record) = journal
+        .previous_entry()
+        .map_err(|error| format!("Failed to read the systemd journal: {error}"))?
+    {
+        let usec = journal
+            .timestamp_usec()
+            .map_err(|error| format!("Failed to read a journal timestamp: {error}"))?;
+        if usec > end_usec {
+            walk.from_tail = false;
+            continue;
+        }
+        if usec < start_usec {
+            break;
+        }
+        if let Some(seen) = resume {
+            let reached = journal
+                .test_cursor(seen.cursor.as_str())
+                .map_err(|error| format!("Failed to compare journal cursors: {error}"))?;
+            if reached {
+                resume = None;
+                walk.newest.get_or_insert_with(|| seen.cursor.clone());
+                if seen.oldest_usec <= start_usec {
+                    walk.oldest_usec = seen.oldest_usec;
+                    break;
+                }
+                journal
+                    .seek(JournalSeek::ClockRealtime {
+                        usec: seen.oldest_usec,
+                    })
+                    .map_err(|error| format!("Failed to seek the systemd journal: {error}"))?;
+                continue;
+            }
+        }
+        if walk.newest.is_none() {
+            walk.newest = Some(
+                journal
+                    .cursor()
+                    .map_err(|error| format!("Failed to read a journal cursor: {error}"))?,
+            );
+        }
+        let mut fields: Map<String, Value> = record
+            .into_iter()
+            .map(|(key, value)| (key, Value::String(value)))
+            .collect();
+        fields.insert(
+            "__REALTIME_TIMESTAMP".to_string(),
+            Value::String(usec.to_string()),
+        );
+        if !visit(fields) {
+            walk.oldest_usec = usec;
+            break;
+        }
+    }
+    Ok(walk)
+}
+
+/// The newest `max` entries in the window, like `journalctl -n`, leaving out those read by an
+/// earlier collection with the same filter. Entries come back oldest first to match the
+/// subprocess path.
+pub fn collect(
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    max: usize,
+    filter: Option<&JournalFilter>,
+) -> Result<CollectionResult, String> {
+    let mut journal = open_journal(filter)?;
+    let mut result = CollectionResult::default();
+    let key = cursor_key(filter);
+    // Without the saved cursor the whole window is read again, which is slower but complete.
+    let resume = db::get_journal_cursor(key.as_str()).unwrap_or_else(|error| {
+        result.warnings.push(error);
+        None
+    });
+    let walk = walk_back(&mut journal, start, end, resume.as_ref(), |fields| {
+        let value = Value::Object(fields);
+        if let Some(mut event) = parse_journal_value(&value) {
+            event.raw_payload = Some(value.to_string());
+            result.events.push(event);
+        }
+        result.events.len() < max
+    })?;
+    result.events.reverse();
+    // A walk that began below the newest entry would move the cursor backwards.
+    if let (true, Some(cursor)) = (walk.from_tail, walk.newest) {
+        let saved = JournalCursor {
+            cursor,
+            oldest_usec: walk.oldest_usec,
+        };
+        let now = Utc::now().to_rfc3339();
+        if let Err(error) = db::save_journal_cursor(key.as_str(), &saved, now.as_str()) {
+            result.warnings.push(error);
+        }
+    }
+    Ok(result)
+}
+
+pub fn estimate(
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    filter: Option<&JournalFilter>,
+) -> Result<CollectionEstimate, String> {
+    let mut journal = open_journal(filter)?;
+    let mut estimate = CollectionEstimate::default();
+    walk_back(&mut journal, start, end, None, |fields| {
+        estimate.estimated_count += 1;
+        estimate.estimated_bytes += fields
+            .iter()
+            .map(|(key, value)| key.len() + value.as_str().map_or(0, str::len) + 6)
+            .sum::<usize>();
+        true
+    })?;
+    Ok(estimate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_priorities_and_unit_names() {
+        assert_eq!(priority_levels("err"), vec![0, 1, 2, 3]);
+        assert_eq!(priority_levels("warning..2"), vec![2, 3, 4]);
+        assert!(priority_levels("loud").is_empty());
+        assert_eq!(unit_name("sshd"), "sshd.service");
+        assert_eq!(unit_name("backup.timer"), "backup.timer");
+    }
+}
@@ -1,18 +1,47 @@
 use super::{CollectionEstimate, CollectionResult, NormalizedEvent, SupportedOs};
-use crate::settings::RemoteConnectionProfile;
+use crate::settings::{RemoteConnectionProfile, UnifiedLogFilter};
+use crate::timestamps::{normalize_timestamp, TimestampContext, TimestampHint};
 use chrono::{DateTime, Local, Utc};
 use serde_json::Value;
 use std::io::{BufRead, BufReader, Read};
 use std::process::{Command, Stdio};
 
+pub mod legacy;
+
 fn shell_quote(s: &str) -> String {
     format!("'{}'", s.replace('\'', "'\\''"))
 }
 
+/// Escapes a shell command for an AppleScript string literal, so quotes inside a predicate
+/// survive `do shell script`.
+fn applescript_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// `--predicate`, `--info` and `--debug` options for `log show`.
+fn log_filter_args(filter: Option<&UnifiedLogFilter>) -> Vec<String> {
+    let Some(filter) = filter else {
+        return Vec::new();
+    };
+    let mut args = Vec::new();
+    if !filter.predicate.is_empty() {
+        args.push("--predicate".to_string());
+        args.push(filter.predicate.clone());
+    }
+    if filter.include_info {
+        args.push("--info".to_string());
+    }
+    if filter.include_debug {
+        args.push("--debug".to_string());
+    }
+    args
+}
+
 pub fn collect_events_range(
     start: Option<DateTime<Utc>>,
     end: Option<DateTime<Utc>>,
     max_events: Option<u32>,
+    log_filter: Option<&UnifiedLogFilter>,
     request_elevation: bool,
 ) -> CollectionResult {
     let max = max_events.unwrap_or(2000).min(10000) as usize;
@@ -33,13 +62,14 @@ pub fn collect_events_range(
         args.push("--end".to_string());
         args.push(format_log_time(value));
     }
+    args.extend(log_filter_args(log_filter));
 
     let mut command = if request_elevation {
         let mut cmd = Command::new("osascript");
         let shell_args: Vec<String> = args.iter().map(|s| shell_quote(s)).collect();
         let script = format!(
             "do shell script \"log {}\" with administrator privileges",
-            shell_args.join(" ")
+            applescript_escape(shell_args.join(" ").as_str())
         );
         cmd.arg("-e").arg(script);
         cmd
@@ -154,6 +184,7 @@ pub fn collect_events_range(
 pub fn estimate_events_range(
     start: Option<DateTime<Utc>>,
     end: Option<DateTime<Utc>>,
+    log_filter: Option<&UnifiedLogFilter>,
     request_elevation: bool,
 ) -> CollectionEstimate {
     let mut args = vec![
@@ -169,13 +200,14 @@ pub fn estimate_events_range(
         args.push("--end".to_string());
         args.push(format_log_time(value));
     }
+    args.extend(log_filter_args(log_filter));
 
     let mut command = if request_elevation {
         let mut cmd = Command::new("osascript");
         let shell_args: Vec<String> = args.iter().map(|s| shell_quote(s)).collect();
         let script = format!(
             "do shell script \"log {}\" with administrator privileges",
-            shell_args.join(" ")
+            applescript_escape(shell_args.join(" ").as_str())
         );
         cmd.arg("-e").arg(script);
         cmd
@@ -332,10 +364,15 @@ fn parse_log_line(line: &str) -> Option<NormalizedEvent> {
         "localhost",
     );
 
-    if let Some(timestamp) = get_string(&value, "timestamp") {
-        event.timestamp = timestamp.to_string();
+    // `log show` prints local times like `2026-03-01 10:00:00.123456-0800`, which SQLite's
+    // date functions cannot read, so store them in the same UTC form as every other source.
+    if let Some(timestamp) = get_string(&value, "timestamp").and_then(|timestamp| {
+        normalize_timestamp(timestamp, &TimestampHint::Auto, &TimestampContext::local())
+    }) {
+        event.timestamp = timestamp;
     }
 
+    event.raw_payload = Some(line.to_string());
     event.assign_stable_id();
 
     Some(event)
@@ -371,6 +408,10 @@ fn map_category(category: Option<&str>, subsystem: Option<&str>, provider: &str)
     let lower = combined.to_ascii_lowercase();
     if lower.contains("audit") {
         "audit"
+    } else if super::printing::is_printing_source(lower.as_str())
+        || lower.contains("com.apple.print")
+    {
+        super::printing::PRINTING_CATEGORY
     } else if lower.contains("auth") || lower.contains("security") {
         "security"
     } else if lower.contains("kernel") || lower.contains("system") {
@@ -406,7 +447,7 @@ pub fn collect_remote_macos_events(
     start: Option<DateTime<Utc>>,
     end: Option<DateTime<Utc>>,
     max_events: Option<u32>,
-    _channels: Option<&[String]>,
+    log_filter: Option<&UnifiedLogFilter>,
 ) -> CollectionResult {
     let max = max_events.unwrap_or(2000).min(10000) as usize;
     if max == 0 {
@@ -416,7 +457,7 @@ pub fn collect_remote_macos_events(
     if profile.auth_type.eq_ignore_ascii_case("password") {
         let mut result = CollectionResult::default();
         result.errors.push(format!(
-            "Remote SSH password authentication is not implemented for {}. Use SSH key-based auth instead.",
+            "Remote SSH password authentication is not implemented for {}. Use SSH key or agent auth instead.",
             profile.host
         ));
         return result;
@@ -433,31 +474,11 @@ pub fn collect_remote_macos_events(
         args.push("--end".to_string());
         args.push(format!(r#""{}""#, format_log_time(end_time)));
     }
+    args.extend(log_filter_args(log_filter).iter().map(|arg| shell_quote(arg)));
     
     let remote_cmd = format!("log {}", args.join(" "));
 
-    let mut ssh_args = vec![
-        "-o".to_string(), "BatchMode=yes".to_string(),
-        "-o".to_string(), "StrictHostKeyChecking=no".to_string()
-    ];
-
-    if let Some(key_path) = &profile.ssh_key_path {
-        if !key_path.is_empty() {
-            ssh_args.push("-i".to_string());
-            ssh_args.push(key_path.to_string());
-        }
-    }
-
-    let user_host = if profile.username.is_empty() {
-        profile.host.clone()
-    } else {
-        format!("{}@{}", profile.username, profile.host)
-    };
-    ssh_args.push(user_host);
-    ssh_args.push(remote_cmd);
-
-    let mut command = std::process::Command::new("ssh");
-    command.args(&ssh_args);
+    let mut command = crate::remote_common::ssh_command(profile, &remote_cmd);
     command
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped());
@@ -0,0 +1,282 @@
+//! Classic `/var/log` text logs on macOS that `log show` does not return: `install.log`
+//! (installer and software update history), `system.log` and `wifi.log`.
+
+use super::map_category;
+use crate::logs::{CollectionResult, NormalizedEvent, SupportedOs};
+use crate::timestamps::{parse_timestamp_with_hint, TimestampContext, TimestampHint};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use regex::Regex;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::Path;
+use std::sync::LazyLock;
+
+const LOG_DIRECTORY: &str = "/var/log";
+/// Newest entries kept per file, so a years-long `install.log` does not crowd out the rest.
+const MAX_EVENTS_PER_LOG: usize = 2000;
+/// Continuation lines stop being appended once a message reaches this size.
+const MAX_MESSAGE_BYTES: usize = 16 * 1024;
+
+/// `install.log`: `2026-03-01 10:00:00-08 host softwareupdated[412]: message`.
+static INSTALL_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2})([+-]\d{2}(?::?\d{2})?)? \S+ ([^\s:\[]+)(?:\[\d+\])?(?: \([^)]*\))?: ?(.*)$",
+    )
+    .expect("valid install.log pattern")
+});
+/// `system.log`: `Mar  1 10:00:00 host com.apple.xpc.launchd[1] (com.apple.foo): message`.
+static SYSTEM_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^([A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}) \S+ ([^\s:\[]+)(?:\[\d+\])?(?: \([^)]*\))?(?: <\w+>)?: ?(.*)$",
+    )
+    .expect("valid system.log pattern")
+});
+/// `wifi.log`: `Sun Mar  1 10:00:00.123 <airportd[151]> message`.
+static WIFI_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:[A-Z][a-z]{2} )?([A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}(?:\.\d+)?) <([^>\[]+)(?:\[\d+\])?> ?(.*)$",
+    )
+    .expect("valid wifi.log pattern")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LegacyLog {
+    Install,
+    System,
+    Wifi,
+}
+
+const LEGACY_LOGS: [LegacyLog; 3] = [LegacyLog::Install, LegacyLog::System, LegacyLog::Wifi];
+
+impl LegacyLog {
+    fn log_name(self) -> &'static str {
+        match self {
+            Self::Install => "install.log",
+            Self::System => "system.log",
+            Self::Wifi => "wifi.log",
+        }
+    }
+}
+
+struct LegacyLine {
+    timestamp: DateTime<Utc>,
+    provider: String,
+    message: String,
+}
+
+/// `-08`, `+0530` or `+05:30` as a fixed offset.
+fn parse_offset(value: &str) -> Option<FixedOffset> {
+    let sign = if value.starts_with('-') { -1 } else { 1 };
+    let digits: String = value[1..].chars().filter(char::is_ascii_digit).collect();
+    let hours: i32 = digits.get(..2)?.parse().ok()?;
+    let minutes: i32 = digits.get(2..4).map_or(Some(0), |m| m.parse().ok())?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn parse_line(log: LegacyLog, line: &str, context: &TimestampContext) -> Option<LegacyLine> {
+    let (timestamp, provider, message) = match log {
+        LegacyLog::Install => {
+            let captures = INSTALL_LINE.captures(line)?;
+            let naive = NaiveDateTime::parse_from_str(&captures[1], "%Y-%m-%d %H:%M:%S").ok()?;
+            let offset = match captures.get(2) {
+                Some(offset) => parse_offset(offset.as_str())?,
+                None => context.default_offset,
+            };
+            let timestamp = offset.from_local_datetime(&naive).single()?;
+            (
+                timestamp.with_timezone(&Utc),
+                captures.get(3),
+                captures.get(4),
+            )
+        }
+        LegacyLog::System | LegacyLog::Wifi => {
+            let pattern = if log == LegacyLog::System {
+                &SYSTEM_LINE
+            } else {
+                &WIFI_LINE
+            };
+            let captures = pattern.captures(line)?;
+            let timestamp =
+                parse_timestamp_with_hint(&captures[1], &TimestampHint::Syslog, context)?;
+            (timestamp, captures.get(2), captures.get(3))
+        }
+    };
+    Some(LegacyLine {
+        timestamp,
+        provider: provider?.as_str().trim().to_string(),
+        message: message.map_or("", |m| m.as_str()).trim_end().to_string(),
+    })
+}
+
+/// These logs carry no level, so severity comes from failure wording in the message.
+fn infer_severity(message: &str) -> &'static str {
+    let lower = message.to_ascii_lowercase();
+    if lower.contains("panic") || lower.contains("fatal") {
+        "critical"
+    } else if lower.contains("error") || lower.contains("failed") || lower.contains("failure") {
+        "error"
+    } else if lower.contains("warning") || lower.contains("timed out") {
+        "warning"
+    } else {
+        "information"
+    }
+}
+
+fn build_event(log: LegacyLog, line: LegacyLine) -> NormalizedEvent {
+    // Installer and software update history belongs with the OS rather than the apps it lists.
+    let category = match log {
+        LegacyLog::Install | LegacyLog::Wifi => "system",
+        LegacyLog::System => map_category(None, None, line.provider.as_str()),
+    };
+    let message = if line.message.trim().is_empty() {
+        "No log message."
+    } else {
+        line.message.as_str()
+    };
+    let mut event = NormalizedEvent::new(
+        SupportedOs::Macos,
+        log.log_name(),
+        category,
+        line.provider.as_str(),
+        None,
+        infer_severity(message),
+        message,
+        "localhost",
+    );
+    event.timestamp = line.timestamp.to_rfc3339();
+    event.assign_stable_id();
+    event
+}
+
+/// Parses one legacy log, keeping the newest `max` entries in the window. Lines that do not
+/// start a new entry (package lists, stack traces) belong to the entry before them.
+fn read_legacy_log<R: BufRead>(
+    log: LegacyLog,
+    reader: R,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    max: usize,
+    context: &TimestampContext,
+) -> VecDeque<NormalizedEvent> {
+    let mut events = VecDeque::new();
+    let mut current: Option<LegacyLine> = None;
+    let flush = |line: Option<LegacyLine>, events: &mut VecDeque<NormalizedEvent>| {
+        let Some(line) = line else {
+            return;
+        };
+        let in_window = start.is_none_or(|start| line.timestamp >= start)
+            && end.is_none_or(|end| line.timestamp <= end);
+        if in_window {
+            events.push_back(build_event(log, line));
+            if events.len() > max {
+                events.pop_front();
+            }
+        }
+    };
+
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if line.trim().is_empty() || line.starts_with("--- last message repeated") {
+            continue;
+        }
+        match parse_line(log, line.as_str(), context) {
+            Some(parsed) => flush(current.replace(parsed), &mut events),
+            None => {
+                if let Some(current) = current.as_mut() {
+                    if current.message.len() < MAX_MESSAGE_BYTES {
+                        current.message.push('\n');
+                        current.message.push_str(line.trim_end());
+                    }
+                }
+            }
+        }
+    }
+    flush(current, &mut events);
+    events
+}
+
+/// Appends `install.log`, `system.log` and `wifi.log` entries in the window. Missing files are
+/// skipped quietly since each macOS release keeps a different subset of them.
+pub fn append_legacy_log_events(
+    result: &mut CollectionResult,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) {
+    let context = TimestampContext::local();
+    for log in LEGACY_LOGS {
+        let path = Path::new(LOG_DIRECTORY).join(log.log_name());
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => continue,
+            Err(error) => {
+                result
+                    .warnings
+                    .push(format!("Failed to read {}: {error}", path.display()));
+                continue;
+            }
+        };
+        result.events.extend(read_legacy_log(
+            log,
+            BufReader::new(file),
+            start,
+            end,
+            MAX_EVENTS_PER_LOG,
+            &context,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn parses_install_log_with_continuation_lines() {
+        let raw = "2026-03-01 10:00:00-08 mac softwareupdated[412]: SUOSUServiceDaemon: Install failed with error\n\
+                   \tpackage: com.apple.pkg.macOSBrain\n\
+                   2026-03-01 10:05:00-08 mac installd[803]: PackageKit: Installed \"Safari\" (17.4)\n";
+        let events = read_legacy_log(
+            LegacyLog::Install,
+            Cursor::new(raw),
+            None,
+            None,
+            10,
+            &TimestampContext::utc(),
+        );
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].provider, "softwareupdated");
+        assert_eq!(events[0].severity, "error");
+        assert_eq!(events[0].category, "system");
+        assert!(events[0]
+            .message
+            .ends_with("package: com.apple.pkg.macOSBrain"));
+        assert_eq!(events[0].timestamp, "2026-03-01T18:00:00+00:00");
+        assert_eq!(events[1].log_name, "install.log");
+    }
+
+    #[test]
+    fn parses_system_and_wifi_lines() {
+        let context = TimestampContext::utc();
+        let system = parse_line(
+            LegacyLog::System,
+            "Mar  1 10:00:00 mac com.apple.xpc.launchd[1] (com.apple.foo[42]): Service exited",
+            &context,
+        )
+        .unwrap();
+        assert_eq!(system.provider, "com.apple.xpc.launchd");
+        assert_eq!(system.message, "Service exited");
+
+        let wifi = parse_line(
+            LegacyLog::Wifi,
+            "Sun Mar  1 10:00:00.123 <airportd[151]> _processIPv4Changes: ARP/NDP offloads disabled",
+            &context,
+        )
+        .unwrap();
+        assert_eq!(wifi.provider, "airportd");
+        assert!(wifi.message.starts_with("_processIPv4Changes"));
+    }
+}
@@ -1,9 +1,15 @@
+pub mod audit;
 pub mod linux;
 pub mod macos;
+pub mod printing;
+pub mod progress;
+pub mod publishers;
 pub mod windows;
 
+use crate::settings::{JournalFilter, UnifiedLogFilter};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -25,7 +31,7 @@ impl std::fmt::Display for SupportedOs {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NormalizedEvent {
     pub id: String,
@@ -39,6 +45,32 @@ pub struct NormalizedEvent {
     pub message: String,
     pub source_host: String,
     pub imported: bool,
+    /// Account the event was logged under (Windows `<Security UserID>`), with its resolved name.
+    #[serde(default)]
+    pub user_sid: Option<String>,
+    #[serde(default)]
+    pub user_name: Option<String>,
+    /// Machine name recorded in the event itself, which can differ from `source_host`.
+    #[serde(default)]
+    pub computer: Option<String>,
+    #[serde(default)]
+    pub process_id: Option<u32>,
+    #[serde(default)]
+    pub thread_id: Option<u32>,
+    /// Windows keyword, opcode and task display names.
+    #[serde(default)]
+    pub keywords: Option<String>,
+    #[serde(default)]
+    pub opcode: Option<String>,
+    #[serde(default)]
+    pub task: Option<String>,
+    /// Structured fields pulled from the record (Sysmon `<EventData>`), keyed by field name.
+    #[serde(default)]
+    pub metadata: Option<BTreeMap<String, String>>,
+    /// The record as the source produced it (rendered XML or a JSON line). Stored with the
+    /// event and fetched on demand through `get_event_raw`, so it never rides along in lists.
+    #[serde(default, skip_serializing)]
+    pub raw_payload: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -81,6 +113,16 @@ impl NormalizedEvent {
             message: message.to_string(),
             source_host: source_host.to_string(),
             imported: false,
+            user_sid: None,
+            user_name: None,
+            computer: None,
+            process_id: None,
+            thread_id: None,
+            keywords: None,
+            opcode: None,
+            task: None,
+            metadata: None,
+            raw_payload: None,
         }
     }
 
@@ -134,14 +176,44 @@ pub fn collect_host_events_range_with_windows_channels(
     end: Option<DateTime<Utc>>,
     max_events: Option<u32>,
     windows_channels: Option<&[String]>,
+    journal_filter: Option<&JournalFilter>,
+    unified_log_filter: Option<&UnifiedLogFilter>,
     request_elevation: bool,
 ) -> CollectionResult {
     match detect_host_os() {
         SupportedOs::Windows => {
             windows::collect_events_range_with_channels(start, end, max_events, windows_channels)
         }
-        SupportedOs::Linux => linux::collect_events_range(start, end, max_events, request_elevation),
-        SupportedOs::Macos => macos::collect_events_range(start, end, max_events, request_elevation),
+        SupportedOs::Linux => {
+            progress::report("systemd journal", 0);
+            let mut result = linux::collect_events_range(
+                start,
+                end,
+                max_events,
+                journal_filter,
+                request_elevation,
+            );
+            progress::report("CUPS", result.events.len());
+            printing::append_cups_events(&mut result, start, end);
+            progress::report("audit log", result.events.len());
+            audit::append_audit_events(&mut result, start, end, request_elevation);
+            result
+        }
+        SupportedOs::Macos => {
+            progress::report("unified log", 0);
+            let mut result = macos::collect_events_range(
+                start,
+                end,
+                max_events,
+                unified_log_filter,
+                request_elevation,
+            );
+            progress::report("CUPS", result.events.len());
+            printing::append_cups_events(&mut result, start, end);
+            progress::report("legacy logs", result.events.len());
+            macos::legacy::append_legacy_log_events(&mut result, start, end);
+            result
+        }
     }
 }
 
@@ -149,11 +221,17 @@ pub fn estimate_host_events_range_with_windows_channels(
     start: Option<DateTime<Utc>>,
     end: Option<DateTime<Utc>>,
     windows_channels: Option<&[String]>,
+    journal_filter: Option<&JournalFilter>,
+    unified_log_filter: Option<&UnifiedLogFilter>,
     request_elevation: bool,
 ) -> CollectionEstimate {
     match detect_host_os() {
         SupportedOs::Windows => windows::estimate_events_range_with_channels(start, end, windows_channels),
-        SupportedOs::Linux => linux::estimate_events_range(start, end, request_elevation),
-        SupportedOs::Macos => macos::estimate_events_range(start, end, request_elevation),
+        SupportedOs::Linux => {
+            linux::estimate_events_range(start, end, journal_filter, request_elevation)
+        }
+        SupportedOs::Macos => {
+            macos::estimate_events_range(start, end, unified_log_filter, request_elevation)
+        }
     }
 }
@@ -0,0 +1,309 @@
+use super::{detect_host_os, CollectionResult, NormalizedEvent};
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+pub const PRINTING_CATEGORY: &str = "printing";
+
+const CUPS_ERROR_LOG: &str = "/var/log/cups/error_log";
+const MAX_CUPS_EVENTS: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintComponentSummary {
+    pub name: String,
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub last_seen: String,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintIssueSummary {
+    pub total_events: usize,
+    pub error_count: usize,
+    pub queues: Vec<PrintComponentSummary>,
+    pub drivers: Vec<PrintComponentSummary>,
+    pub events: Vec<NormalizedEvent>,
+}
+
+/// True when a log name, provider or identifier belongs to the print stack.
+pub fn is_printing_source(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    lower.contains("printservice")
+        || lower.contains("print spooler")
+        || lower.contains("spoolsv")
+        || lower.contains("cups")
+}
+
+fn quoted_after<'a>(message: &'a str, marker: &str) -> Option<&'a str> {
+    let index = message.find(marker)?;
+    let rest = &message[index + marker.len()..];
+    let rest = rest.trim_start().strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(&rest[..end])
+}
+
+/// Extracts the printer queue named in a PrintService or CUPS message.
+pub fn extract_print_queue(message: &str) -> Option<String> {
+    if let Some(queue) = quoted_after(message, "Printer") {
+        return Some(queue.to_string());
+    }
+    // CUPS: "[Job 12] ... on printer HP_LaserJet" / "Job 12 queued on \"HP\" by \"user\"."
+    if let Some(queue) = quoted_after(message, "queued on") {
+        return Some(queue.to_string());
+    }
+    // PrintService 307/372: "... was printed on HP LaserJet through port USB001." /
+    // "... failed to print on printer HP LaserJet."
+    for marker in [
+        "failed to print on printer ",
+        "was printed on ",
+        "on printer ",
+    ] {
+        if let Some(index) = message.find(marker) {
+            let rest = &message[index + marker.len()..];
+            let end = rest
+                .find(" through port")
+                .or_else(|| rest.find(". "))
+                .or_else(|| rest.find(['\r', '\n']))
+                .unwrap_or(rest.len());
+            let queue = rest[..end].trim().trim_end_matches('.');
+            if !queue.is_empty() {
+                return Some(queue.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Extracts a driver module or CUPS filter named in a print message.
+pub fn extract_print_driver(message: &str) -> Option<String> {
+    let lower = message.to_ascii_lowercase();
+    if let Some(index) = lower.find("\\spool\\drivers\\") {
+        let rest = &message[index..];
+        let end = rest
+            .find(|ch: char| ch.is_whitespace() || ch == ',' || ch == '"')
+            .unwrap_or(rest.len());
+        let path = &rest[..end];
+        return path
+            .rsplit('\\')
+            .next()
+            .map(|name| name.trim_end_matches('.').to_string());
+    }
+    if let Some(driver) = quoted_after(message, "driver") {
+        return Some(driver.to_string());
+    }
+    if let Some(index) = message.find("filter ") {
+        let rest = message[index + "filter ".len()..].trim_start_matches(['"', '(']);
+        let name: String = rest
+            .chars()
+            .take_while(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.' | '/'))
+            .collect();
+        if !name.is_empty() {
+            return Some(name.rsplit('/').next().unwrap_or(name.as_str()).to_string());
+        }
+    }
+    None
+}
+
+fn cups_severity(level: char) -> Option<&'static str> {
+    match level {
+        'X' | 'A' | 'C' => Some("critical"),
+        'E' => Some("error"),
+        'W' => Some("warning"),
+        'N' | 'I' => Some("information"),
+        // Debug levels are far too noisy to ingest.
+        _ => None,
+    }
+}
+
+/// Parses one `error_log` line: `E [16/Oct/2026:10:00:00 +0000] [Job 12] message`.
+pub fn parse_cups_line(line: &str) -> Option<NormalizedEvent> {
+    let level = line.chars().next()?;
+    let severity = cups_severity(level)?;
+    let rest = line[level.len_utf8()..].trim_start();
+    let rest = rest.strip_prefix('[')?;
+    let (stamp, message) = rest.split_once(']')?;
+    let timestamp = parse_timestamp(stamp)?;
+    let message = message.trim();
+    if message.is_empty() {
+        return None;
+    }
+
+    let mut event = NormalizedEvent::new(
+        detect_host_os(),
+        "cups",
+        PRINTING_CATEGORY,
+        "cupsd",
+        None,
+        severity,
+        message,
+        "localhost",
+    );
+    event.timestamp = timestamp.to_rfc3339();
+    event.assign_stable_id();
+    Some(event)
+}
+
+fn read_cups_log(
+    path: &Path,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Result<Vec<NormalizedEvent>, String> {
+    let file = fs::File::open(path)
+        .map_err(|error| format!("Failed to open CUPS error log {}: {error}", path.display()))?;
+    let mut events = Vec::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Some(event) = parse_cups_line(line.as_str()) else {
+            continue;
+        };
+        let Ok(at) = DateTime::parse_from_rfc3339(event.timestamp.as_str()) else {
+            continue;
+        };
+        let at = at.with_timezone(&Utc);
+        if start.map(|value| at < value).unwrap_or(false)
+            || end.map(|value| at > value).unwrap_or(false)
+        {
+            continue;
+        }
+        events.push(event);
+    }
+    // Keep the most recent entries when the log is larger than the cap.
+    let skip = events.len().saturating_sub(MAX_CUPS_EVENTS);
+    Ok(events.split_off(skip))
+}
+
+/// Appends CUPS `error_log` entries to a host collection. Missing logs are not an error because
+/// many hosts have no printing stack installed.
+pub fn append_cups_events(
+    result: &mut CollectionResult,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) {
+    let path = Path::new(CUPS_ERROR_LOG);
+    if !path.exists() {
+        return;
+    }
+    match read_cups_log(path, start, end) {
+        Ok(mut events) => result.events.append(&mut events),
+        Err(error) => result.warnings.push(error),
+    }
+}
+
+fn bump_component(
+    map: &mut BTreeMap<String, PrintComponentSummary>,
+    name: String,
+    event: &NormalizedEvent,
+) {
+    let entry = map
+        .entry(name.clone())
+        .or_insert_with(|| PrintComponentSummary {
+            name,
+            error_count: 0,
+            warning_count: 0,
+            last_seen: event.timestamp.clone(),
+            last_error: None,
+        });
+    match event.severity.as_str() {
+        "critical" | "error" => {
+            entry.error_count += 1;
+            if entry.last_error.is_none() || event.timestamp >= entry.last_seen {
+                entry.last_error = Some(event.message.clone());
+            }
+        }
+        "warning" => entry.warning_count += 1,
+        _ => {}
+    }
+    if event.timestamp > entry.last_seen {
+        entry.last_seen = event.timestamp.clone();
+    }
+}
+
+fn sorted_components(map: BTreeMap<String, PrintComponentSummary>) -> Vec<PrintComponentSummary> {
+    let mut values: Vec<PrintComponentSummary> = map.into_values().collect();
+    values.sort_by(|left, right| {
+        right
+            .error_count
+            .cmp(&left.error_count)
+            .then_with(|| right.last_seen.cmp(&left.last_seen))
+    });
+    values
+}
+
+pub fn summarize_print_events(events: &[NormalizedEvent]) -> PrintIssueSummary {
+    let printing: Vec<NormalizedEvent> = events
+        .iter()
+        .filter(|event| {
+            event.category == PRINTING_CATEGORY
+                || is_printing_source(event.log_name.as_str())
+                || is_printing_source(event.provider.as_str())
+        })
+        .cloned()
+        .collect();
+
+    let mut queues = BTreeMap::new();
+    let mut drivers = BTreeMap::new();
+    for event in &printing {
+        if let Some(queue) = extract_print_queue(event.message.as_str()) {
+            bump_component(&mut queues, queue, event);
+        }
+        if let Some(driver) = extract_print_driver(event.message.as_str()) {
+            bump_component(&mut drivers, driver, event);
+        }
+    }
+
+    PrintIssueSummary {
+        total_events: printing.len(),
+        error_count: printing
+            .iter()
+            .filter(|event| matches!(event.severity.as_str(), "critical" | "error"))
+            .count(),
+        queues: sorted_components(queues),
+        drivers: sorted_components(drivers),
+        events: printing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cups_error_line() {
+        let event = parse_cups_line(
+            "E [16/Oct/2026:10:00:00 +0200] [Job 12] Printer \"HP_LaserJet\" stopped: filter \"foomatic-rip\" failed",
+        )
+        .unwrap();
+        assert_eq!(event.category, PRINTING_CATEGORY);
+        assert_eq!(event.severity, "error");
+        assert_eq!(event.timestamp, "2026-10-16T08:00:00+00:00");
+        assert_eq!(
+            extract_print_queue(event.message.as_str()).as_deref(),
+            Some("HP_LaserJet")
+        );
+        assert_eq!(
+            extract_print_driver(event.message.as_str()).as_deref(),
+            Some("foomatic-rip")
+        );
+        assert!(parse_cups_line("D [16/Oct/2026:10:00:00 +0000] debug noise").is_none());
+    }
+
+    #[test]
+    fn extracts_windows_queue_and_driver() {
+        assert_eq!(
+            extract_print_queue("Document 5, Print Document owned by alice on \\\\PC was printed on HP LaserJet 400 through port USB001.")
+                .as_deref(),
+            Some("HP LaserJet 400")
+        );
+        assert_eq!(
+            extract_print_driver("The print spooler failed to load a plug-in module C:\\Windows\\system32\\spool\\DRIVERS\\x64\\3\\hpcpp.dll, error code 0x7e.")
+                .as_deref(),
+            Some("hpcpp.dll")
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Collection progress without threading a callback through every collector: a job installs a
+//! reporter for the duration of its work, and collectors call `report` as they move between
+//! sources. With no reporter installed, `report` does nothing.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Receives the source being read and the number of events collected so far.
+pub type Reporter = Arc<dyn Fn(&str, usize) + Send + Sync>;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Reporter>> = const { RefCell::new(None) };
+}
+
+/// Runs `work` with `reporter` installed on this thread, restoring the previous one afterwards.
+pub fn with_reporter<T>(reporter: Reporter, work: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(reporter)));
+    let result = work();
+    CURRENT.with(|current| current.replace(previous));
+    result
+}
+
+/// The reporter installed on this thread, for collectors that hand work to other threads.
+pub fn current() -> Option<Reporter> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+pub fn report(source: &str, collected: usize) {
+    if let Some(reporter) = current() {
+        reporter(source, collected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn reports_only_inside_with_reporter() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        report("ignored", 1);
+        let total = with_reporter(
+            Arc::new(move |source: &str, collected| {
+                sink.lock().unwrap().push((source.to_string(), collected));
+            }),
+            || {
+                report("System", 10);
+                report("Application", 25);
+                25
+            },
+        );
+        report("ignored", 2);
+        assert_eq!(total, 25);
+        assert!(current().is_none());
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![("System".to_string(), 10), ("Application".to_string(), 25)]
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "windows")]
+use super::windows::{last_error, map_severity, to_wide, wide_to_string, EvtHandle};
+#[cfg(target_os = "windows")]
+use std::ptr::{null, null_mut};
+#[cfg(target_os = "windows")]
+use windows_sys::Win32::Foundation::{ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS};
+#[cfg(target_os = "windows")]
+use windows_sys::Win32::System::EventLog::{
+    EventMetadataEventID, EventMetadataEventKeyword, EventMetadataEventLevel,
+    EventMetadataEventMessageID, EventMetadataEventOpcode, EventMetadataEventTask,
+    EventMetadataEventVersion, EvtFormatMessage, EvtFormatMessageId, EvtGetEventMetadataProperty,
+    EvtGetPublisherMetadataProperty, EvtNextEventMetadata, EvtNextPublisherId,
+    EvtOpenEventMetadataEnum, EvtOpenPublisherEnum, EvtOpenPublisherMetadata, EvtVarTypeString,
+    EvtVarTypeUInt32, EvtVarTypeUInt64, PublisherMetadataMessageFilePath, EVT_HANDLE, EVT_VARIANT,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherEventDescription {
+    pub event_id: u32,
+    pub version: u32,
+    pub level: Option<u32>,
+    pub severity: String,
+    pub task: u32,
+    pub opcode: u32,
+    pub keywords: String,
+    /// Message template with `%1`-style inserts, as shown by Event Viewer before rendering.
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublisherMetadata {
+    pub name: String,
+    pub message_file: Option<String>,
+    pub events: Vec<PublisherEventDescription>,
+}
+
+/// Lists the event publishers registered on this machine, optionally filtered by a
+/// case-insensitive substring.
+#[cfg(target_os = "windows")]
+pub fn list_publishers(filter: Option<&str>) -> Result<Vec<String>, String> {
+    let enum_handle = unsafe { EvtOpenPublisherEnum(0, 0) };
+    if enum_handle == 0 {
+        return Err(format!(
+            "Failed to enumerate event publishers (error {}).",
+            last_error()
+        ));
+    }
+    let enum_handle = EvtHandle(enum_handle);
+    let needle = filter
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty());
+
+    let mut publishers = Vec::new();
+    let mut buffer: Vec<u16> = vec![0; 256];
+    loop {
+        let mut used: u32 = 0;
+        let ok = unsafe {
+            EvtNextPublisherId(
+                enum_handle.0,
+                buffer.len() as u32,
+                buffer.as_mut_ptr(),
+                &mut used,
+            )
+        };
+        if ok == 0 {
+            match last_error() {
+                ERROR_NO_MORE_ITEMS => break,
+                ERROR_INSUFFICIENT_BUFFER => {
+                    buffer = vec![0; used as usize + 1];
+                    continue;
+                }
+                error => {
+                    return Err(format!(
+                        "Failed to read the next event publisher (error {error})."
+                    ))
+                }
+            }
+        }
+        let name = wide_to_string(buffer.as_slice());
+        if needle
+            .as_deref()
+            .map_or(true, |needle| name.to_ascii_lowercase().contains(needle))
+        {
+            publishers.push(name);
+        }
+    }
+
+    publishers.sort_by_key(|name| name.to_ascii_lowercase());
+    Ok(publishers)
+}
+
+/// Reads the events a publisher declares in its manifest, with their message templates. When
+/// `event_id` is set only that event's versions are returned.
+#[cfg(target_os = "windows")]
+pub fn publisher_metadata(
+    provider: &str,
+    event_id: Option<u32>,
+) -> Result<PublisherMetadata, String> {
+    let provider_w = to_wide(provider.trim());
+    let meta_handle = unsafe { EvtOpenPublisherMetadata(0, provider_w.as_ptr(), null(), 0, 0) };
+    if meta_handle == 0 {
+        return Err(format!(
+            "No metadata is registered for publisher '{}' (error {}).",
+            provider.trim(),
+            last_error()
+        ));
+    }
+    let meta = EvtHandle(meta_handle);
+
+    let message_file = read_variant(|size, buffer, used| unsafe {
+        EvtGetPublisherMetadataProperty(
+            meta.0,
+            PublisherMetadataMessageFilePath,
+            0,
+            size,
+            buffer,
+            used,
+        ) != 0
+    })
+    .and_then(|buffer| unsafe { variant_string(&buffer) });
+
+    let events_handle = unsafe { EvtOpenEventMetadataEnum(meta.0, 0) };
+    if events_handle == 0 {
+        return Err(format!(
+            "Failed to enumerate events for publisher '{}' (error {}).",
+            provider.trim(),
+            last_error()
+        ));
+    }
+    let events_handle = EvtHandle(events_handle);
+
+    let mut events = Vec::new();
+    loop {
+        let event_handle = unsafe { EvtNextEventMetadata(events_handle.0, 0) };
+        if event_handle == 0 {
+            break;
+        }
+        let event = EvtHandle(event_handle);
+        let property = |id| {
+            read_variant(|size, buffer, used| unsafe {
+                EvtGetEventMetadataProperty(event.0, id, 0, size, buffer, used) != 0
+            })
+        };
+        let number = |id| property(id).and_then(|buffer| unsafe { variant_u64(&buffer) });
+
+        // Manifest event IDs are 16-bit; the high word carries qualifiers.
+        let Some(id) = number(EventMetadataEventID).map(|value| value as u32 & 0xFFFF) else {
+            continue;
+        };
+        if event_id.is_some_and(|wanted| wanted != id) {
+            continue;
+        }
+        let level = number(EventMetadataEventLevel).map(|value| value as u32);
+        let description = number(EventMetadataEventMessageID)
+            .map(|value| value as u32)
+            .filter(|message_id| *message_id != u32::MAX)
+            .and_then(|message_id| format_message_id(meta.0, message_id));
+        events.push(PublisherEventDescription {
+            event_id: id,
+            version: number(EventMetadataEventVersion).unwrap_or(0) as u32,
+            level,
+            severity: map_severity(level).to_string(),
+            task: number(EventMetadataEventTask).unwrap_or(0) as u32,
+            opcode: number(EventMetadataEventOpcode).unwrap_or(0) as u32,
+            keywords: format!("0x{:016X}", number(EventMetadataEventKeyword).unwrap_or(0)),
+            description,
+        });
+    }
+
+    events.sort_by_key(|event| (event.event_id, event.version));
+    Ok(PublisherMetadata {
+        name: provider.trim().to_string(),
+        message_file,
+        events,
+    })
+}
+
+/// Runs a two-call `EvtGet*Property` style fetch. The buffer is `u64`-backed so the variant it
+/// holds is correctly aligned.
+#[cfg(target_os = "windows")]
+fn read_variant<F>(fetch: F) -> Option<Vec<u64>>
+where
+    F: Fn(u32, *mut EVT_VARIANT, *mut u32) -> bool,
+{
+    let mut used: u32 = 0;
+    let mut buffer: Vec<u64> = vec![0; std::mem::size_of::<EVT_VARIANT>().div_ceil(8)];
+    if fetch(
+        (buffer.len() * 8) as u32,
+        buffer.as_mut_ptr().cast(),
+        &mut used,
+    ) {
+        return Some(buffer);
+    }
+    if last_error() != ERROR_INSUFFICIENT_BUFFER || used == 0 {
+        return None;
+    }
+    buffer = vec![0; (used as usize).div_ceil(8)];
+    fetch(
+        (buffer.len() * 8) as u32,
+        buffer.as_mut_ptr().cast(),
+        &mut used,
+    )
+    .then_some(buffer)
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn variant_u64(buffer: &[u64]) -> Option<u64> {
+    let variant = &*(buffer.as_ptr() as *const EVT_VARIANT);
+    if variant.Type == EvtVarTypeUInt32 as u32 {
+        Some(variant.Anonymous.UInt32Val as u64)
+    } else if variant.Type == EvtVarTypeUInt64 as u32 {
+        Some(variant.Anonymous.UInt64Val)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn variant_string(buffer: &[u64]) -> Option<String> {
+    let variant = &*(buffer.as_ptr() as *const EVT_VARIANT);
+    let pointer = variant.Anonymous.StringVal;
+    if variant.Type != EvtVarTypeString as u32 || pointer.is_null() {
+        return None;
+    }
+    let mut length = 0;
+    while *pointer.add(length) != 0 {
+        length += 1;
+    }
+    let value = String::from_utf16_lossy(std::slice::from_raw_parts(pointer, length));
+    (!value.trim().is_empty()).then_some(value)
+}
+
+#[cfg(target_os = "windows")]
+fn format_message_id(meta_handle: EVT_HANDLE, message_id: u32) -> Option<String> {
+    unsafe {
+        let mut buffer_used: u32 = 0;
+        let ok = EvtFormatMessage(
+            meta_handle,
+            0,
+            message_id,
+            0,
+            null(),
+            EvtFormatMessageId,
+            0,
+            null_mut(),
+            &mut buffer_used,
+        );
+        if (ok == 0 && last_error() != ERROR_INSUFFICIENT_BUFFER) || buffer_used == 0 {
+            return None;
+        }
+
+        let mut buffer: Vec<u16> = vec![0; buffer_used as usize];
+        let ok = EvtFormatMessage(
+            meta_handle,
+            0,
+            message_id,
+            0,
+            null(),
+            EvtFormatMessageId,
+            buffer_used,
+            buffer.as_mut_ptr(),
+            &mut buffer_used,
+        );
+        if ok == 0 {
+            return None;
+        }
+
+        let message = wide_to_string(buffer.as_slice());
+        let trimmed = message.trim();
+        (!trimmed.is_empty()).then(|| trimmed.to_string())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn list_publishers(_filter: Option<&str>) -> Result<Vec<String>, String> {
+    Err("Event publisher metadata is only available on Windows.".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn publisher_metadata(
+    _provider: &str,
+    _event_id: Option<u32>,
+) -> Result<PublisherMetadata, String> {
+    Err("Event publisher metadata is only available on Windows.".to_string())
+}
@@ -1,38 +1,78 @@
+#[cfg(target_os = "windows")]
+use super::progress;
 use super::{CollectionEstimate, CollectionResult, NormalizedEvent, SupportedOs};
 use crate::remote_windows::{
     build_summary_events, parse_remote_summary_json, summary_hints_from_events,
 };
 use crate::settings::RemoteConnectionProfile;
 #[cfg(target_os = "windows")]
+use crate::timestamps::{normalize_timestamp, TimestampContext, TimestampHint};
+#[cfg(target_os = "windows")]
 use chrono::SecondsFormat;
 use chrono::{DateTime, Utc};
 #[cfg(target_os = "windows")]
 use serde_json::Value;
 
+#[cfg(any(target_os = "windows", test))]
+mod event_xml;
+#[cfg(any(target_os = "windows", test))]
+mod install_history;
+#[cfg(any(target_os = "windows", test))]
+mod sysmon;
+
+#[cfg(target_os = "windows")]
+use std::collections::HashMap;
 #[cfg(target_os = "windows")]
 use std::ffi::OsStr;
 #[cfg(target_os = "windows")]
 use std::os::windows::ffi::OsStrExt;
 #[cfg(target_os = "windows")]
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(target_os = "windows")]
 use std::ptr::{null, null_mut};
+#[cfg(target_os = "windows")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(target_os = "windows")]
+use std::sync::{LazyLock, Mutex};
+#[cfg(target_os = "windows")]
+use std::thread;
 
 #[cfg(target_os = "windows")]
 use windows_sys::Win32::Foundation::{
-    GetLastError, ERROR_ACCESS_DENIED, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS,
+    GetLastError, LocalFree, ERROR_ACCESS_DENIED, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS,
 };
 #[cfg(target_os = "windows")]
+use windows_sys::Win32::Globalization::LocaleNameToLCID;
+#[cfg(target_os = "windows")]
+use windows_sys::Win32::Security::Authorization::ConvertStringSidToSidW;
+#[cfg(target_os = "windows")]
+use windows_sys::Win32::Security::{LookupAccountSidW, PSID, SID_NAME_USE};
+#[cfg(target_os = "windows")]
 use windows_sys::Win32::System::EventLog::{
-    EvtClose, EvtFormatMessage, EvtFormatMessageEvent, EvtNext, EvtOpenPublisherMetadata, EvtQuery,
-    EvtQueryChannelPath, EvtRender, EvtRenderEventXml, EVT_HANDLE,
+    EvtClose, EvtFormatMessage, EvtFormatMessageEvent, EvtFormatMessageKeyword,
+    EvtFormatMessageOpcode, EvtFormatMessageTask, EvtNext, EvtOpenPublisherMetadata, EvtQuery,
+    EvtQueryChannelPath, EvtRender, EvtRenderEventXml, EVT_FORMAT_MESSAGE_FLAGS, EVT_HANDLE,
 };
 
 #[cfg(target_os = "windows")]
 const DEFAULT_CHANNELS: [&str; 3] = ["Application", "System", "Security"];
 #[cfg(target_os = "windows")]
 const ESTIMATE_SAMPLE_LIMIT: usize = 200;
+/// Formatted strings a channel read keeps for repeated event instances before starting over.
+#[cfg(target_os = "windows")]
+const MAX_CACHED_MESSAGES: usize = 4096;
+/// Channels read at once; the rest wait for a free thread.
+#[cfg(target_os = "windows")]
+const MAX_CHANNEL_THREADS: usize = 4;
 
+/// Account names already looked up, keyed by lookup system and SID. Failed lookups are kept
+/// too so an unknown SID is not retried for every event.
 #[cfg(target_os = "windows")]
-struct EvtHandle(EVT_HANDLE);
+static ACCOUNT_NAMES: LazyLock<Mutex<HashMap<(String, String), Option<String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[cfg(target_os = "windows")]
+pub(super) struct EvtHandle(pub(super) EVT_HANDLE);
 
 #[cfg(target_os = "windows")]
 impl Drop for EvtHandle {
@@ -58,7 +98,12 @@ pub fn collect_events_range_with_channels(
     }
 
     let selected_channels = normalize_channels(channels);
-    collect_with_wevtapi(start, end, max, selected_channels.as_slice())
+    let profile = crate::settings::load_ingest_profile();
+    let locale = message_locale_id(profile.message_locale.as_str());
+    let mut result = collect_with_wevtapi(start, end, max, selected_channels.as_slice(), locale);
+    progress::report("Windows Update history", result.events.len());
+    install_history::append_install_history_events(&mut result, start, end);
+    result
 }
 
 #[cfg(target_os = "windows")]
@@ -107,14 +152,60 @@ fn collect_with_wevtapi(
     end: Option<DateTime<Utc>>,
     max: usize,
     channels: &[&'static str],
+    locale: u32,
 ) -> CollectionResult {
     let query = build_time_query(start, end);
     let mut result = CollectionResult::default();
 
-    for channel in channels {
-        let remaining = max.saturating_sub(result.events.len());
-        match collect_channel_events(*channel, query.as_deref(), remaining) {
+    // The budget is split evenly across channels, the first ones taking the remainder, so a
+    // sync reads at most `max` events in all. Up to `MAX_CHANNEL_THREADS` channels are read at
+    // once, and the results are merged in channel order.
+    let reporter = progress::current();
+    let collected = AtomicUsize::new(0);
+    let next_channel = AtomicUsize::new(0);
+    let budget = |index: usize| max / channels.len() + usize::from(index < max % channels.len());
+    let panicked = |channel: &str| Err(format!("Windows '{channel}' channel collection panicked."));
+    let mut channel_results: Vec<Result<Vec<NormalizedEvent>, String>> =
+        channels.iter().map(|channel| panicked(channel)).collect();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..channels.len().min(MAX_CHANNEL_THREADS))
+            .map(|_| {
+                let query = query.as_deref();
+                let reporter = reporter.clone();
+                let (collected, next_channel, budget, panicked) =
+                    (&collected, &next_channel, &budget, &panicked);
+                scope.spawn(move || {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next_channel.fetch_add(1, Ordering::Relaxed);
+                        let Some(channel) = channels.get(index) else {
+                            break done;
+                        };
+                        let events = panic::catch_unwind(AssertUnwindSafe(|| {
+                            collect_channel_events(channel, query, budget(index), locale)
+                        }))
+                        .unwrap_or_else(|_| panicked(channel));
+                        if let (Some(reporter), Ok(events)) = (reporter.as_ref(), events.as_ref()) {
+                            let total = collected.fetch_add(events.len(), Ordering::Relaxed);
+                            reporter(channel, (total + events.len()).min(max));
+                        }
+                        done.push((index, events));
+                    }
+                })
+            })
+            .collect();
+        for done in workers.into_iter().filter_map(|worker| worker.join().ok()) {
+            for (index, events) in done {
+                channel_results[index] = events;
+            }
+        }
+    });
+
+    for channel_result in channel_results {
+        match channel_result {
             Ok(mut channel_events) => {
+                let remaining = max.saturating_sub(result.events.len());
+                channel_events.truncate(remaining);
                 result.events.append(&mut channel_events);
             }
             Err(error) => {
@@ -192,6 +283,7 @@ fn collect_channel_events(
     channel: &str,
     query: Option<&str>,
     max: usize,
+    locale: u32,
 ) -> Result<Vec<NormalizedEvent>, String> {
     let query = query.unwrap_or("*");
     let channel_w = to_wide(channel);
@@ -209,6 +301,7 @@ fn collect_channel_events(
 
     let _query_handle = EvtHandle(handle);
     let mut events = Vec::new();
+    let mut cache = RenderCache::new(locale);
 
     if max == 0 {
         return Ok(events);
@@ -241,7 +334,7 @@ fn collect_channel_events(
             if event_handle == 0 {
                 continue;
             }
-            let rendered = render_event(event_handle, channel);
+            let rendered = render_event(event_handle, channel, &mut cache);
             unsafe {
                 EvtClose(event_handle);
             }
@@ -330,19 +423,28 @@ fn estimate_channel_events(channel: &str, query: Option<&str>) -> Result<Channel
 }
 
 #[cfg(target_os = "windows")]
-fn render_event(handle: EVT_HANDLE, fallback_channel: &str) -> Option<NormalizedEvent> {
-    let xml = render_event_xml(handle)?;
-    let provider = extract_xml_attr(&xml, "Provider", "Name")
+fn render_event(
+    handle: EVT_HANDLE,
+    fallback_channel: &str,
+    cache: &mut RenderCache,
+) -> Option<NormalizedEvent> {
+    let xml = event_xml::parse_event(render_event_xml(handle)?.as_str())?;
+    let provider = xml
+        .provider
+        .clone()
         .unwrap_or_else(|| "Unknown Provider".to_string());
-    let log_name =
-        extract_xml_tag_value(&xml, "Channel").unwrap_or_else(|| fallback_channel.to_string());
-    let event_id =
-        extract_xml_tag_value(&xml, "EventID").and_then(|value| value.parse::<u32>().ok());
-    let level = extract_xml_tag_value(&xml, "Level").and_then(|value| value.parse::<u32>().ok());
-    let severity = map_severity(level);
+    let log_name = xml
+        .channel
+        .clone()
+        .unwrap_or_else(|| fallback_channel.to_string());
+    let severity = map_severity(xml.level);
     let category = map_category(&log_name);
-    let message = format_event_message(handle, provider.as_str())
-        .or_else(|| extract_event_data(&xml))
+    let formatted = cache
+        .format(handle, &xml, provider.as_str())
+        .unwrap_or_default();
+    let message = formatted
+        .message
+        .or_else(|| xml.data_summary())
         .unwrap_or_else(|| "No event message.".to_string());
 
     let mut event = NormalizedEvent::new(
@@ -350,16 +452,22 @@ fn render_event(handle: EVT_HANDLE, fallback_channel: &str) -> Option<Normalized
         log_name.as_str(),
         category,
         provider.as_str(),
-        event_id,
+        xml.event_id,
         severity,
         sanitize_message(message.as_str()),
         "localhost",
     );
 
-    if let Some(timestamp) = extract_xml_attr(&xml, "TimeCreated", "SystemTime") {
+    if let Some(timestamp) = xml.time_created.as_deref().and_then(normalize_event_time) {
         event.timestamp = timestamp;
     }
 
+    apply_event_identity(&mut event, &xml, None);
+    apply_event_descriptors(&mut event, &xml);
+    event.keywords = formatted.keywords.or(event.keywords);
+    event.opcode = formatted.opcode.or(event.opcode);
+    event.task = formatted.task.or(event.task);
+    event.raw_payload = Some(xml.raw);
     event.assign_stable_id();
 
     Some(event)
@@ -405,15 +513,234 @@ fn render_event_xml(handle: EVT_HANDLE) -> Option<String> {
     }
 }
 
+/// Copies the `<System>` user, computer and process fields onto `event`, resolving the SID on
+/// `system` (the remote host that logged it) or locally.
+#[cfg(target_os = "windows")]
+fn apply_event_identity(
+    event: &mut NormalizedEvent,
+    xml: &event_xml::EventXml,
+    system: Option<&str>,
+) {
+    event.computer = xml.computer.clone();
+    event.process_id = xml.process_id;
+    event.thread_id = xml.thread_id;
+    event.user_name = xml
+        .user_sid
+        .as_deref()
+        .and_then(|sid| resolve_account_name(sid, system));
+    event.user_sid = xml.user_sid.clone();
+}
+
+/// Keyword, opcode and task names from the event XML; used as-is for remote events and as the
+/// fallback when the publisher's own strings cannot be formatted locally.
+#[cfg(target_os = "windows")]
+fn apply_event_descriptors(event: &mut NormalizedEvent, xml: &event_xml::EventXml) {
+    event.keywords = xml.keywords_display();
+    event.opcode = xml.opcode_display();
+    event.task = xml.task_display();
+    if sysmon::is_sysmon_channel(event.log_name.as_str()) {
+        event.metadata = sysmon::event_metadata(xml.event_id, &xml.event_data);
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn resolve_account_name(sid: &str, system: Option<&str>) -> Option<String> {
+    let key = (
+        system.unwrap_or_default().to_ascii_lowercase(),
+        sid.to_string(),
+    );
+    if let Some(name) = ACCOUNT_NAMES.lock().ok()?.get(&key) {
+        return name.clone();
+    }
+    let name = lookup_account_sid(sid, system);
+    if let Ok(mut names) = ACCOUNT_NAMES.lock() {
+        names.insert(key, name.clone());
+    }
+    name
+}
+
+/// `DOMAIN\name` for a SID string through `LookupAccountSidW`.
+#[cfg(target_os = "windows")]
+fn lookup_account_sid(sid: &str, system: Option<&str>) -> Option<String> {
+    let wide_sid = to_wide(sid);
+    let mut psid: PSID = null_mut();
+    if unsafe { ConvertStringSidToSidW(wide_sid.as_ptr(), &mut psid) } == 0 {
+        return None;
+    }
+
+    let wide_system = system.map(to_wide);
+    let mut name = vec![0u16; 256];
+    let mut domain = vec![0u16; 256];
+    let mut name_len = name.len() as u32;
+    let mut domain_len = domain.len() as u32;
+    let mut name_use: SID_NAME_USE = 0;
+    let found = unsafe {
+        LookupAccountSidW(
+            wide_system.as_ref().map_or(null(), |value| value.as_ptr()),
+            psid,
+            name.as_mut_ptr(),
+            &mut name_len,
+            domain.as_mut_ptr(),
+            &mut domain_len,
+            &mut name_use,
+        )
+    };
+    unsafe {
+        LocalFree(psid);
+    }
+    if found == 0 {
+        return None;
+    }
+
+    let name = wide_to_string(&name);
+    let domain = wide_to_string(&domain);
+    if name.is_empty() {
+        None
+    } else if domain.is_empty() {
+        Some(name)
+    } else {
+        Some(format!("{domain}\\{name}"))
+    }
+}
+
+/// Strings the publisher's message table supplies for one event.
+#[cfg(target_os = "windows")]
+#[derive(Clone, Default)]
+struct FormattedEvent {
+    message: Option<String>,
+    keywords: Option<String>,
+    opcode: Option<String>,
+    task: Option<String>,
+}
+
+/// LCID for the configured message locale name (`en-US`), or 0 for the display language when
+/// the setting is `system` or names a locale Windows does not know.
+#[cfg(target_os = "windows")]
+fn message_locale_id(name: &str) -> u32 {
+    let name = name.trim();
+    if name.is_empty() || name.eq_ignore_ascii_case(crate::settings::SYSTEM_MESSAGE_LOCALE) {
+        return 0;
+    }
+    let name_w = to_wide(name);
+    unsafe { LocaleNameToLCID(name_w.as_ptr(), 0) }
+}
+
+/// Publisher metadata handles and formatted strings reused while one channel is read. Opening
+/// a publisher's metadata per event dominated collection time, and a channel holds few
+/// providers, so each is opened once per locale and closed when the read ends. Repeated event
+/// instances (same provider, descriptors and insertion strings) reuse their formatted strings.
+#[cfg(target_os = "windows")]
+struct RenderCache {
+    locale: u32,
+    /// `None` records a provider without metadata so it is not retried for every event.
+    publishers: HashMap<(String, u32), Option<EvtHandle>>,
+    messages: HashMap<String, FormattedEvent>,
+}
+
+#[cfg(target_os = "windows")]
+impl RenderCache {
+    fn new(locale: u32) -> Self {
+        Self {
+            locale,
+            publishers: HashMap::new(),
+            messages: HashMap::new(),
+        }
+    }
+
+    fn publisher(&mut self, provider: &str, locale: u32) -> Option<EVT_HANDLE> {
+        self.publishers
+            .entry((provider.to_string(), locale))
+            .or_insert_with(|| {
+                let provider_w = to_wide(provider);
+                let handle =
+                    unsafe { EvtOpenPublisherMetadata(0, provider_w.as_ptr(), null(), locale, 0) };
+                (handle != 0).then_some(EvtHandle(handle))
+            })
+            .as_ref()
+            .map(|handle| handle.0)
+    }
+
+    fn format(
+        &mut self,
+        handle: EVT_HANDLE,
+        xml: &event_xml::EventXml,
+        provider: &str,
+    ) -> Option<FormattedEvent> {
+        let key = message_cache_key(xml, provider);
+        if let Some(formatted) = key.as_ref().and_then(|key| self.messages.get(key)) {
+            return Some(formatted.clone());
+        }
+        let formatted = self.format_uncached(handle, provider)?;
+        if let Some(key) = key {
+            if self.messages.len() >= MAX_CACHED_MESSAGES {
+                self.messages.clear();
+            }
+            self.messages.insert(key, formatted.clone());
+        }
+        Some(formatted)
+    }
+
+    /// Formats with the requested locale first and falls back to the display language when the
+    /// publisher ships no resources for it.
+    fn format_uncached(&mut self, handle: EVT_HANDLE, provider: &str) -> Option<FormattedEvent> {
+        if self.locale != 0 {
+            let formatted = self
+                .publisher(provider, self.locale)
+                .and_then(|meta_handle| format_event_strings(meta_handle, handle));
+            if formatted.is_some() {
+                return formatted;
+            }
+        }
+        self.publisher(provider, 0)
+            .and_then(|meta_handle| format_event_strings(meta_handle, handle))
+    }
+}
+
+/// Identifies event instances whose formatted strings are interchangeable. Events carrying
+/// `<UserData>` are left out because their insertion strings are not in `event_data`.
 #[cfg(target_os = "windows")]
-fn format_event_message(handle: EVT_HANDLE, provider: &str) -> Option<String> {
-    let provider_w = to_wide(provider);
-    let meta_handle = unsafe { EvtOpenPublisherMetadata(0, provider_w.as_ptr(), null(), 0, 0) };
-    if meta_handle == 0 {
+fn message_cache_key(xml: &event_xml::EventXml, provider: &str) -> Option<String> {
+    if xml.raw.contains("<UserData") {
         return None;
     }
-    let _meta = EvtHandle(meta_handle);
+    let mut key = format!(
+        "{provider}\u{1f}{:?}\u{1f}{:?}\u{1f}{:?}\u{1f}{:?}\u{1f}{:?}",
+        xml.event_id, xml.level, xml.keywords, xml.opcode, xml.task
+    );
+    for (name, value) in &xml.event_data {
+        key.push('\u{1f}');
+        key.push_str(name);
+        key.push('=');
+        key.push_str(value);
+    }
+    Some(key)
+}
 
+#[cfg(target_os = "windows")]
+fn format_event_strings(meta_handle: EVT_HANDLE, handle: EVT_HANDLE) -> Option<FormattedEvent> {
+    let message = format_message_field(meta_handle, handle, EvtFormatMessageEvent)?
+        .into_iter()
+        .next()?;
+    let single = |flags| {
+        format_message_field(meta_handle, handle, flags).and_then(|mut values| values.pop())
+    };
+    Some(FormattedEvent {
+        message: Some(message),
+        keywords: format_message_field(meta_handle, handle, EvtFormatMessageKeyword)
+            .map(|names| names.join(", ")),
+        opcode: single(EvtFormatMessageOpcode),
+        task: single(EvtFormatMessageTask),
+    })
+}
+
+/// Formats one field of an event. Keywords come back as a list of null-separated strings,
+/// so every field is returned as its non-empty parts.
+#[cfg(target_os = "windows")]
+fn format_message_field(
+    meta_handle: EVT_HANDLE,
+    handle: EVT_HANDLE,
+    flags: EVT_FORMAT_MESSAGE_FLAGS,
+) -> Option<Vec<String>> {
     unsafe {
         let mut buffer_used: u32 = 0;
         let ok = EvtFormatMessage(
@@ -422,7 +749,7 @@ fn format_event_message(handle: EVT_HANDLE, provider: &str) -> Option<String> {
             0,
             0,
             null(),
-            EvtFormatMessageEvent,
+            flags,
             0,
             null_mut(),
             &mut buffer_used,
@@ -442,7 +769,7 @@ fn format_event_message(handle: EVT_HANDLE, provider: &str) -> Option<String> {
             0,
             0,
             null(),
-            EvtFormatMessageEvent,
+            flags,
             buffer_used,
             buffer.as_mut_ptr(),
             &mut buffer_used,
@@ -451,11 +778,15 @@ fn format_event_message(handle: EVT_HANDLE, provider: &str) -> Option<String> {
             return None;
         }
 
-        let message = wide_to_string(buffer.as_slice());
-        if message.trim().is_empty() {
+        let values: Vec<String> = buffer[..buffer_used as usize]
+            .split(|ch| *ch == 0)
+            .map(|part| String::from_utf16_lossy(part).trim().to_string())
+            .filter(|part| !part.is_empty())
+            .collect();
+        if values.is_empty() {
             None
         } else {
-            Some(message.trim().to_string())
+            Some(values)
         }
     }
 }
@@ -487,7 +818,9 @@ fn build_time_query(start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) ->
 #[cfg(target_os = "windows")]
 fn map_category(log_name: &str) -> &str {
     let lower = log_name.to_ascii_lowercase();
-    if lower.contains("security") {
+    if super::printing::is_printing_source(lower.as_str()) {
+        super::printing::PRINTING_CATEGORY
+    } else if lower.contains("security") || sysmon::is_sysmon_channel(log_name) {
         "security"
     } else if lower.contains("system") {
         "system"
@@ -497,7 +830,7 @@ fn map_category(log_name: &str) -> &str {
 }
 
 #[cfg(target_os = "windows")]
-fn map_severity(level: Option<u32>) -> &'static str {
+pub(super) fn map_severity(level: Option<u32>) -> &'static str {
     match level {
         Some(1) => "critical",
         Some(2) => "error",
@@ -514,14 +847,11 @@ fn sanitize_message(message: &str) -> &str {
     message
 }
 
+/// Remote WinRM results carry the remote host's local offset; store every event time in UTC so
+/// ordering and range queries compare like with like.
 #[cfg(target_os = "windows")]
-fn extract_xml_attr(xml: &str, element: &str, attr: &str) -> Option<String> {
-    let tag = format!("<{element}");
-    let start = xml.find(&tag)?;
-    let rest = &xml[start..];
-    let end = rest.find('>')?;
-    let segment = &rest[..end];
-    extract_segment_attr(segment, attr)
+fn normalize_event_time(value: &str) -> Option<String> {
+    normalize_timestamp(value, &TimestampHint::Rfc3339, &TimestampContext::utc())
 }
 
 #[cfg(target_os = "windows")]
@@ -533,6 +863,19 @@ fn normalize_channels(channels: Option<&[String]>) -> Vec<&'static str> {
                 "application" => Some("Application"),
                 "system" => Some("System"),
                 "security" => Some("Security"),
+                "microsoft-windows-diagnostics-performance/operational" => {
+                    Some("Microsoft-Windows-Diagnostics-Performance/Operational")
+                }
+                "microsoft-windows-printservice/operational" => {
+                    Some("Microsoft-Windows-PrintService/Operational")
+                }
+                "microsoft-windows-terminalservices-localsessionmanager/operational" => {
+                    Some("Microsoft-Windows-TerminalServices-LocalSessionManager/Operational")
+                }
+                "microsoft-windows-printservice/admin" => {
+                    Some("Microsoft-Windows-PrintService/Admin")
+                }
+                "microsoft-windows-sysmon/operational" => Some(sysmon::SYSMON_CHANNEL),
                 _ => None,
             };
             if let Some(channel) = normalized {
@@ -550,52 +893,6 @@ fn normalize_channels(channels: Option<&[String]>) -> Vec<&'static str> {
     }
 }
 
-#[cfg(target_os = "windows")]
-fn extract_xml_tag_value(xml: &str, tag: &str) -> Option<String> {
-    let start = xml.find(&format!("<{tag}"))?;
-    let rest = &xml[start..];
-    let content_start = rest.find('>')? + start + 1;
-    let content_end = xml[content_start..].find(&format!("</{tag}>"))? + content_start;
-    Some(xml[content_start..content_end].trim().to_string())
-}
-
-#[cfg(target_os = "windows")]
-fn extract_event_data(xml: &str) -> Option<String> {
-    let start = xml.find("<EventData")?;
-    let rest = &xml[start..];
-    let data_start = rest.find('>')? + start + 1;
-    let data_end = xml[data_start..].find("</EventData>")? + data_start;
-    let segment = &xml[data_start..data_end];
-
-    let mut cursor = segment;
-    let mut pairs = Vec::new();
-    loop {
-        let tag_start = match cursor.find("<Data") {
-            Some(value) => value,
-            None => break,
-        };
-        let after_tag = cursor[tag_start..].find('>')? + tag_start;
-        let tag_body = &cursor[tag_start..after_tag];
-        let name = extract_segment_attr(tag_body, "Name").unwrap_or_else(|| "Data".to_string());
-        let value_start = after_tag + 1;
-        let value_end = match cursor[value_start..].find("</Data>") {
-            Some(value) => value + value_start,
-            None => break,
-        };
-        let value = cursor[value_start..value_end].trim().to_string();
-        if !value.is_empty() {
-            pairs.push(format!("{name}={value}"));
-        }
-        cursor = &cursor[value_end + "</Data>".len()..];
-    }
-
-    if pairs.is_empty() {
-        None
-    } else {
-        Some(format!("Data: {}", pairs.join(", ")))
-    }
-}
-
 #[cfg(target_os = "windows")]
 fn normalize_remote_windows_channels(channels: Option<&[String]>) -> Vec<String> {
     normalize_channels(channels)
@@ -625,7 +922,11 @@ fn build_winrm_collection_script(
         .unwrap_or_else(|| "$null".to_string());
 
     let script_block = format!(
-        r#"$Max = {max};
+        r#"try {{
+  [System.Threading.Thread]::CurrentThread.CurrentCulture = 'en-US';
+  [System.Threading.Thread]::CurrentThread.CurrentUICulture = 'en-US'
+}} catch {{}}
+$Max = {max};
 $LogNames = @({log_names});
 $Start = {start_value};
 $End = {end_value};
@@ -637,12 +938,17 @@ $Collected = foreach ($log in $LogNames) {{
   if ($End) {{ $fh.EndTime = $End }}
   try {{
     Get-WinEvent -FilterHashtable $fh -MaxEvents $PerLogMax -ErrorAction Stop |
-      Select-Object Id, LogName, ProviderName, LevelDisplayName, Message, TimeCreated
+      Select-Object Id, LogName, ProviderName, LevelDisplayName, Message, TimeCreated, MachineName, ProcessId, ThreadId, UserId, KeywordsDisplayNames, OpcodeDisplayName, TaskDisplayName
   }} catch {{
     $Warnings += "Windows '$($log)' channel: $($_.Exception.Message)"
   }}
 }};
+$AccountNames = @{{}};
 $Events = @($Collected | Sort-Object TimeCreated -Descending | Select-Object -First $Max | ForEach-Object {{
+  $sid = if ($_.UserId) {{ $_.UserId.Value }} else {{ $null }}
+  if ($sid -and -not $AccountNames.ContainsKey($sid)) {{
+    try {{ $AccountNames[$sid] = $_.UserId.Translate([System.Security.Principal.NTAccount]).Value }} catch {{ $AccountNames[$sid] = $null }}
+  }}
   [PSCustomObject]@{{
     Id = $_.Id
     LogName = $_.LogName
@@ -650,6 +956,19 @@ $Events = @($Collected | Sort-Object TimeCreated -Descending | Select-Object -Fi
     LevelDisplayName = $_.LevelDisplayName
     Message = $_.Message
     TimeCreated = if ($_.TimeCreated) {{ $_.TimeCreated.ToString('o') }} else {{ $null }}
+    MachineName = $_.MachineName
+    ProcessId = $_.ProcessId
+    ThreadId = $_.ThreadId
+    UserSid = $sid
+    UserName = if ($sid) {{ $AccountNames[$sid] }} else {{ $null }}
+    Keywords = if ($_.KeywordsDisplayNames) {{ ($_.KeywordsDisplayNames | Where-Object {{ $_ }}) -join ', ' }} else {{ $null }}
+    Opcode = $_.OpcodeDisplayName
+    Task = $_.TaskDisplayName
+    EventData = if ($_.LogName -eq 'Microsoft-Windows-Sysmon/Operational') {{
+      $data = @{{}};
+      ([xml]$_.ToXml()).Event.EventData.Data | ForEach-Object {{ $data[$_.Name] = $_.'#text' }};
+      $data
+    }} else {{ $null }}
   }}
 }});
 $os = $null;
@@ -840,9 +1159,45 @@ fn parse_winrm_events(value: &Value, source_host: &str) -> Vec<NormalizedEvent>
             sanitize_message(message),
             source_host,
         );
-        if !time.is_empty() {
-            event.timestamp = time.to_string();
+        if let Some(timestamp) = normalize_event_time(time) {
+            event.timestamp = timestamp;
         }
+        let text = |key: &str| {
+            item.get(key)
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let number = |key: &str| {
+            item.get(key)
+                .and_then(Value::as_u64)
+                .and_then(|value| u32::try_from(value).ok())
+        };
+        event.computer = text("MachineName");
+        event.process_id = number("ProcessId");
+        event.thread_id = number("ThreadId");
+        event.user_sid = text("UserSid");
+        event.user_name = text("UserName");
+        event.keywords = text("Keywords");
+        event.opcode = text("Opcode");
+        event.task = text("Task");
+        if sysmon::is_sysmon_channel(log_name) {
+            let event_data: Vec<(String, String)> = item
+                .get("EventData")
+                .and_then(Value::as_object)
+                .map(|data| {
+                    data.iter()
+                        .filter_map(|(name, value)| {
+                            value
+                                .as_str()
+                                .map(|value| (name.clone(), value.to_string()))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            event.metadata = sysmon::event_metadata(event_id, &event_data);
+        }
+        event.raw_payload = Some(item.to_string());
         event.assign_stable_id();
         events.push(event);
     }
@@ -850,37 +1205,22 @@ fn parse_winrm_events(value: &Value, source_host: &str) -> Vec<NormalizedEvent>
 }
 
 #[cfg(target_os = "windows")]
-fn extract_remote_event_fragments(xml: &str) -> Vec<String> {
-    let mut cursor = xml;
-    let mut fragments = Vec::new();
-    while let Some(start) = cursor.find("<Event") {
-        let after_start = &cursor[start..];
-        let Some(end) = after_start.find("</Event>") else {
-            break;
-        };
-        let fragment_end = start + end + "</Event>".len();
-        fragments.push(cursor[start..fragment_end].to_string());
-        cursor = &cursor[fragment_end..];
-    }
-    fragments
-}
-
-#[cfg(target_os = "windows")]
-fn render_remote_xml_fragment(
-    xml: &str,
+fn render_remote_xml_event(
+    xml: &event_xml::EventXml,
     fallback_channel: &str,
     source_host: &str,
-) -> Option<NormalizedEvent> {
-    let provider =
-        extract_xml_attr(xml, "Provider", "Name").unwrap_or_else(|| "Unknown Provider".to_string());
-    let log_name =
-        extract_xml_tag_value(xml, "Channel").unwrap_or_else(|| fallback_channel.to_string());
-    let event_id =
-        extract_xml_tag_value(xml, "EventID").and_then(|value| value.parse::<u32>().ok());
-    let level = extract_xml_tag_value(xml, "Level").and_then(|value| value.parse::<u32>().ok());
-    let severity = map_severity(level);
+) -> NormalizedEvent {
+    let provider = xml
+        .provider
+        .clone()
+        .unwrap_or_else(|| "Unknown Provider".to_string());
+    let log_name = xml
+        .channel
+        .clone()
+        .unwrap_or_else(|| fallback_channel.to_string());
+    let severity = map_severity(xml.level);
     let category = map_category(&log_name);
-    let message = extract_event_data(xml).unwrap_or_else(|| {
+    let message = xml.data_summary().unwrap_or_else(|| {
         "Rendered Windows message unavailable over RPC/DCOM collection.".to_string()
     });
 
@@ -889,16 +1229,19 @@ fn render_remote_xml_fragment(
         log_name.as_str(),
         category,
         provider.as_str(),
-        event_id,
+        xml.event_id,
         severity,
         sanitize_message(message.as_str()),
         source_host,
     );
-    if let Some(timestamp) = extract_xml_attr(xml, "TimeCreated", "SystemTime") {
+    if let Some(timestamp) = xml.time_created.as_deref().and_then(normalize_event_time) {
         event.timestamp = timestamp;
     }
+    apply_event_identity(&mut event, xml, Some(source_host));
+    apply_event_descriptors(&mut event, xml);
+    event.raw_payload = Some(xml.raw.clone());
     event.assign_stable_id();
-    Some(event)
+    event
 }
 
 #[cfg(target_os = "windows")]
@@ -908,26 +1251,7 @@ fn sort_and_cap_remote_events(events: &mut Vec<NormalizedEvent>, max: usize) {
 }
 
 #[cfg(target_os = "windows")]
-fn extract_segment_attr(segment: &str, attr: &str) -> Option<String> {
-    let double = format!("{attr}=\"");
-    if let Some(start) = segment.find(&double) {
-        let value_start = start + double.len();
-        let value_end = segment[value_start..].find('"')? + value_start;
-        return Some(segment[value_start..value_end].to_string());
-    }
-
-    let single = format!("{attr}='");
-    if let Some(start) = segment.find(&single) {
-        let value_start = start + single.len();
-        let value_end = segment[value_start..].find('\'')? + value_start;
-        return Some(segment[value_start..value_end].to_string());
-    }
-
-    None
-}
-
-#[cfg(target_os = "windows")]
-fn to_wide(value: &str) -> Vec<u16> {
+pub(super) fn to_wide(value: &str) -> Vec<u16> {
     OsStr::new(value)
         .encode_wide()
         .chain(std::iter::once(0))
@@ -935,13 +1259,13 @@ fn to_wide(value: &str) -> Vec<u16> {
 }
 
 #[cfg(target_os = "windows")]
-fn wide_to_string(value: &[u16]) -> String {
+pub(super) fn wide_to_string(value: &[u16]) -> String {
     let end = value.iter().position(|ch| *ch == 0).unwrap_or(value.len());
     String::from_utf16_lossy(&value[..end])
 }
 
 #[cfg(target_os = "windows")]
-fn last_error() -> u32 {
+pub(super) fn last_error() -> u32 {
     unsafe { GetLastError() }
 }
 
@@ -1108,14 +1432,12 @@ fn collect_remote_windows_events_rpc(
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        for fragment in extract_remote_event_fragments(stdout.as_ref()) {
-            if let Some(event) = render_remote_xml_fragment(
-                fragment.as_str(),
+        for xml in event_xml::parse_events(stdout.as_ref()) {
+            result.events.push(render_remote_xml_event(
+                &xml,
                 channel.as_str(),
                 profile.host.as_str(),
-            ) {
-                result.events.push(event);
-            }
+            ));
         }
     }
 
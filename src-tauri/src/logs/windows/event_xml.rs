@@ -0,0 +1,367 @@
+//! Parses rendered Windows event XML (`EvtRenderEventXml`, `wevtutil qe /f:xml`) with a real
+//! XML reader, so namespaces, CDATA, entities and nested `<UserData>` payloads come through
+//! intact.
+
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// Standard keyword bits from `winmeta.xml` with their Event Viewer names. Bits 56-63 mark the
+/// channel and bits below 48 belong to the provider.
+const STANDARD_KEYWORDS: [(u64, &str); 8] = [
+    (0x0001_0000_0000_0000, "Response Time"),
+    (0x0002_0000_0000_0000, "WDI Context"),
+    (0x0004_0000_0000_0000, "WDI Diag"),
+    (0x0008_0000_0000_0000, "SQM"),
+    (0x0010_0000_0000_0000, "Audit Failure"),
+    (0x0020_0000_0000_0000, "Audit Success"),
+    (0x0040_0000_0000_0000, "Correlation Hint"),
+    (0x0080_0000_0000_0000, "Classic"),
+];
+const PROVIDER_KEYWORD_MASK: u64 = 0x0000_FFFF_FFFF_FFFF;
+
+/// The `<System>` fields of one event plus every `<EventData>` / `<UserData>` value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventXml {
+    pub provider: Option<String>,
+    pub event_id: Option<u32>,
+    pub level: Option<u32>,
+    pub channel: Option<String>,
+    pub time_created: Option<String>,
+    pub computer: Option<String>,
+    /// `<Security UserID>`, a SID string such as `S-1-5-18`.
+    pub user_sid: Option<String>,
+    pub process_id: Option<u32>,
+    pub thread_id: Option<u32>,
+    pub keywords: Option<u64>,
+    pub opcode: Option<u32>,
+    pub task: Option<u32>,
+    /// Display names from `<RenderingInfo>`, present in `wevtutil /f:RenderedXml` output.
+    pub keyword_names: Vec<String>,
+    pub opcode_name: Option<String>,
+    pub task_name: Option<String>,
+    /// Name/value pairs in document order. Unnamed `<Data>` entries are named `Data`.
+    pub event_data: Vec<(String, String)>,
+    /// The `<Event>` element's own markup, for storing alongside the normalized event.
+    pub raw: String,
+}
+
+impl EventXml {
+    /// Keyword names from the rendering info, else the standard names for the set bits, else
+    /// the provider-specific mask in hex.
+    pub fn keywords_display(&self) -> Option<String> {
+        if !self.keyword_names.is_empty() {
+            return Some(self.keyword_names.join(", "));
+        }
+        let mask = self.keywords?;
+        let names: Vec<&str> = STANDARD_KEYWORDS
+            .iter()
+            .filter(|(bit, _)| mask & bit != 0)
+            .map(|(_, name)| *name)
+            .collect();
+        if !names.is_empty() {
+            Some(names.join(", "))
+        } else if mask & PROVIDER_KEYWORD_MASK != 0 {
+            Some(format!("0x{mask:x}"))
+        } else {
+            None
+        }
+    }
+
+    pub fn opcode_display(&self) -> Option<String> {
+        if let Some(name) = &self.opcode_name {
+            return Some(name.clone());
+        }
+        let name = match self.opcode? {
+            0 => "Info",
+            1 => "Start",
+            2 => "Stop",
+            3 => "DCStart",
+            4 => "DCStop",
+            5 => "Extension",
+            6 => "Reply",
+            7 => "Resume",
+            8 => "Suspend",
+            9 => "Send",
+            240 => "Receive",
+            other => return Some(other.to_string()),
+        };
+        Some(name.to_string())
+    }
+
+    /// Task 0 means none; other unnamed tasks keep their number.
+    pub fn task_display(&self) -> Option<String> {
+        self.task_name.clone().or_else(|| {
+            self.task
+                .filter(|task| *task != 0)
+                .map(|task| task.to_string())
+        })
+    }
+
+    /// `Data: name=value, ...` for events whose message could not be formatted.
+    pub fn data_summary(&self) -> Option<String> {
+        let pairs: Vec<String> = self
+            .event_data
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        if pairs.is_empty() {
+            None
+        } else {
+            Some(format!("Data: {}", pairs.join(", ")))
+        }
+    }
+}
+
+fn attribute(element: &BytesStart<'_>, name: &str) -> Option<String> {
+    element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|value| value.unescape_value().ok())
+        .map(|value| value.trim().to_string())
+}
+
+/// Where the reader is inside the current `<Event>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Other,
+    System,
+    EventData,
+    UserData,
+    RenderingInfo,
+}
+
+struct EventBuilder {
+    event: EventXml,
+    /// Byte offset of the `<Event` tag in the input.
+    offset: usize,
+    section: Section,
+    /// Element names from `<Event>` down to the current element.
+    path: Vec<String>,
+    data_name: Option<String>,
+    text: String,
+}
+
+impl EventBuilder {
+    fn new(offset: usize) -> Self {
+        Self {
+            event: EventXml::default(),
+            offset,
+            section: Section::Other,
+            path: Vec::new(),
+            data_name: None,
+            text: String::new(),
+        }
+    }
+
+    fn start(&mut self, element: &BytesStart<'_>) {
+        let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+        self.text.clear();
+        if self.path.len() == 1 {
+            self.section = match name.as_str() {
+                "System" => Section::System,
+                "EventData" => Section::EventData,
+                "UserData" => Section::UserData,
+                "RenderingInfo" => Section::RenderingInfo,
+                _ => Section::Other,
+            };
+        }
+        match (self.section, name.as_str()) {
+            (Section::System, "Provider") => {
+                self.event.provider =
+                    attribute(element, "Name").or_else(|| attribute(element, "EventSourceName"));
+            }
+            (Section::System, "TimeCreated") => {
+                self.event.time_created = attribute(element, "SystemTime");
+            }
+            (Section::System, "Execution") => {
+                self.event.process_id =
+                    attribute(element, "ProcessID").and_then(|value| value.parse().ok());
+                self.event.thread_id =
+                    attribute(element, "ThreadID").and_then(|value| value.parse().ok());
+            }
+            (Section::System, "Security") => {
+                self.event.user_sid = attribute(element, "UserID").filter(|sid| !sid.is_empty());
+            }
+            (Section::EventData, "Data") => self.data_name = attribute(element, "Name"),
+            _ => {}
+        }
+        self.path.push(name);
+    }
+
+    fn end(&mut self) {
+        let Some(name) = self.path.pop() else {
+            return;
+        };
+        let value = self.text.trim().to_string();
+        self.text.clear();
+        match (self.section, name.as_str()) {
+            (Section::System, "EventID") => self.event.event_id = value.parse().ok(),
+            (Section::System, "Level") => self.event.level = value.parse().ok(),
+            (Section::System, "Channel") if !value.is_empty() => self.event.channel = Some(value),
+            (Section::System, "Computer") if !value.is_empty() => self.event.computer = Some(value),
+            (Section::System, "Keywords") => {
+                self.event.keywords = u64::from_str_radix(value.trim_start_matches("0x"), 16).ok()
+            }
+            (Section::System, "Opcode") => self.event.opcode = value.parse().ok(),
+            (Section::System, "Task") => self.event.task = value.parse().ok(),
+            (Section::RenderingInfo, "Keyword") if !value.is_empty() => {
+                self.event.keyword_names.push(value)
+            }
+            (Section::RenderingInfo, "Opcode") if !value.is_empty() => {
+                self.event.opcode_name = Some(value)
+            }
+            (Section::RenderingInfo, "Task") if !value.is_empty() => {
+                self.event.task_name = Some(value)
+            }
+            (Section::EventData, "Data") => {
+                let name = self.data_name.take().unwrap_or_else(|| "Data".to_string());
+                self.event.event_data.push((name, value));
+            }
+            (Section::EventData, "Binary") => {
+                self.event.event_data.push(("Binary".to_string(), value))
+            }
+            // `<UserData>` wraps a provider-specific element; its children are the values.
+            (Section::UserData, _) if self.path.len() >= 3 => {
+                self.event.event_data.push((name, value))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Every `<Event>` in `xml`, which may be a single rendered event or the root-less stream
+/// `wevtutil` prints. Parsing stops at the first malformed markup, keeping earlier events.
+pub fn parse_events(xml: &str) -> Vec<EventXml> {
+    let mut reader = Reader::from_str(xml);
+    let mut events = Vec::new();
+    let mut current: Option<EventBuilder> = None;
+
+    loop {
+        let offset = reader.buffer_position() as usize;
+        match reader.read_event() {
+            Ok(Event::Start(element)) => {
+                if let Some(builder) = current.as_mut() {
+                    builder.start(&element);
+                } else if element.local_name().as_ref() == b"Event" {
+                    let mut builder = EventBuilder::new(offset);
+                    builder.path.push("Event".to_string());
+                    current = Some(builder);
+                }
+            }
+            Ok(Event::Empty(element)) => {
+                if let Some(builder) = current.as_mut() {
+                    builder.start(&element);
+                    builder.end();
+                }
+            }
+            Ok(Event::End(_)) => {
+                if let Some(builder) = current.as_mut() {
+                    builder.end();
+                    if builder.path.is_empty() {
+                        let end = reader.buffer_position() as usize;
+                        builder.event.raw =
+                            xml.get(builder.offset..end).unwrap_or_default().to_string();
+                        events.extend(current.take().map(|builder| builder.event));
+                    }
+                }
+            }
+            Ok(Event::Text(text)) => {
+                if let (Some(builder), Ok(text)) = (current.as_mut(), text.decode()) {
+                    builder.text.push_str(text.as_ref());
+                }
+            }
+            Ok(Event::CData(data)) => {
+                if let (Some(builder), Ok(data)) = (current.as_mut(), data.decode()) {
+                    builder.text.push_str(data.as_ref());
+                }
+            }
+            Ok(Event::GeneralRef(reference)) => {
+                let Some(builder) = current.as_mut() else {
+                    continue;
+                };
+                if let Ok(Some(ch)) = reference.resolve_char_ref() {
+                    builder.text.push(ch);
+                } else if let Some(value) = reference
+                    .decode()
+                    .ok()
+                    .and_then(|name| resolve_predefined_entity(name.as_ref()))
+                {
+                    builder.text.push_str(value);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+    events
+}
+
+/// The first event in `xml`.
+pub fn parse_event(xml: &str) -> Option<EventXml> {
+    parse_events(xml).into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_value<'a>(event: &'a EventXml, name: &str) -> Option<&'a str> {
+        event
+            .event_data
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn parses_system_and_event_data_fields() {
+        let xml = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'><System><Provider Name='Microsoft-Windows-Security-Auditing' Guid='{54849625}'/><EventID Qualifiers='0'>4625</EventID><Level>0</Level><Task>12544</Task><Opcode>0</Opcode><Keywords>0x8010000000000000</Keywords><TimeCreated SystemTime='2026-03-01T10:00:00.1234567Z'/><Execution ProcessID='684' ThreadID='7420'/><Channel>Security</Channel><Computer>WS01.corp.local</Computer><Security UserID='S-1-5-18'/></System><EventData><Data Name='TargetUserName'>alice</Data><Data Name='CommandLine'><![CDATA[cmd.exe /c "a<b"]]></Data><Data Name='Reason'>Bad &amp; expired &#x41;</Data><Data Name='Empty'/><Data>unnamed</Data></EventData></Event>"#;
+        let event = parse_event(xml).unwrap();
+        assert_eq!(
+            event.provider.as_deref(),
+            Some("Microsoft-Windows-Security-Auditing")
+        );
+        assert_eq!(event.event_id, Some(4625));
+        assert_eq!(event.level, Some(0));
+        assert_eq!(event.channel.as_deref(), Some("Security"));
+        assert_eq!(event.computer.as_deref(), Some("WS01.corp.local"));
+        assert_eq!(event.user_sid.as_deref(), Some("S-1-5-18"));
+        assert_eq!((event.process_id, event.thread_id), (Some(684), Some(7420)));
+        assert_eq!(event.keywords_display().as_deref(), Some("Audit Failure"));
+        assert_eq!(event.opcode_display().as_deref(), Some("Info"));
+        assert_eq!(event.task_display().as_deref(), Some("12544"));
+        assert_eq!(
+            event.time_created.as_deref(),
+            Some("2026-03-01T10:00:00.1234567Z")
+        );
+        assert_eq!(
+            data_value(&event, "CommandLine"),
+            Some(r#"cmd.exe /c "a<b""#)
+        );
+        assert_eq!(data_value(&event, "Reason"), Some("Bad & expired A"));
+        assert_eq!(data_value(&event, "Empty"), Some(""));
+        assert_eq!(
+            event.data_summary().unwrap(),
+            r#"Data: TargetUserName=alice, CommandLine=cmd.exe /c "a<b", Reason=Bad & expired A, Data=unnamed"#
+        );
+    }
+
+    #[test]
+    fn reads_user_data_and_event_streams() {
+        let xml = r#"<Event><System><Provider Name='A'/><EventID>1</EventID></System><UserData><LogFileCleared xmlns='http://manifests.microsoft.com/win/2004/08/windows/eventlog'><SubjectUserName>bob</SubjectUserName></LogFileCleared></UserData></Event>
+<Event><System><Provider Name='B'/><EventID>2</EventID><Task>12544</Task></System><RenderingInfo Culture='en-US'><Task>Logon</Task><Keywords><Keyword>Audit Success</Keyword></Keywords></RenderingInfo></Event>"#;
+        let events = parse_events(xml);
+        assert_eq!(events.len(), 2);
+        assert_eq!(data_value(&events[0], "SubjectUserName"), Some("bob"));
+        assert_eq!(events[1].provider.as_deref(), Some("B"));
+        assert_eq!(events[1].task_display().as_deref(), Some("Logon"));
+        assert_eq!(
+            events[1].keywords_display().as_deref(),
+            Some("Audit Success")
+        );
+        assert!(events[1].raw.ends_with("</RenderingInfo></Event>"));
+    }
+}
@@ -0,0 +1,313 @@
+//! What was installed and when: Windows Update history from the Windows Update Agent and driver
+//! installs from `setupapi.dev.log`, both stored under the `updates` category so they sit next
+//! to the crashes they may have caused.
+
+#[cfg(target_os = "windows")]
+use crate::logs::CollectionResult;
+use crate::logs::{NormalizedEvent, SupportedOs};
+use crate::timestamps::TimestampContext;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde_json::Value;
+use std::io::BufRead;
+
+pub const UPDATES_CATEGORY: &str = "updates";
+
+const UPDATE_LOG: &str = "Windows Update";
+const DRIVER_LOG: &str = "setupapi.dev.log";
+/// Newest driver install sections kept per sync.
+const MAX_DRIVER_EVENTS: usize = 1000;
+
+/// One `>>>  [title]` ... `<<<  [Exit status: ...]` section of `setupapi.dev.log`.
+#[derive(Debug, Default)]
+struct SetupSection {
+    title: String,
+    started: Option<DateTime<Utc>>,
+    status: Option<String>,
+    /// INF files the section names, which identify the driver package.
+    infs: Vec<String>,
+}
+
+/// `Section start 2026/03/01 10:00:00.123`, in local time.
+fn parse_section_time(value: &str, context: &TimestampContext) -> Option<DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(value.trim(), "%Y/%m/%d %H:%M:%S%.f").ok()?;
+    context
+        .default_offset
+        .from_local_datetime(&naive)
+        .single()
+        .map(|value| value.with_timezone(&Utc))
+}
+
+fn section_event(section: SetupSection) -> Option<NormalizedEvent> {
+    let timestamp = section.started?;
+    let status = section.status.as_deref().unwrap_or("unknown");
+    let severity = match status.to_ascii_uppercase().as_str() {
+        "SUCCESS" => "information",
+        value if value.starts_with("SUCCESS") || value == "CANCELLED" => "warning",
+        "UNKNOWN" => "information",
+        _ => "error",
+    };
+    let mut message = format!("{} (exit status: {status})", section.title);
+    if !section.infs.is_empty() {
+        message.push_str(format!("\nINF: {}", section.infs.join(", ")).as_str());
+    }
+    let mut event = NormalizedEvent::new(
+        SupportedOs::Windows,
+        DRIVER_LOG,
+        UPDATES_CATEGORY,
+        "SetupAPI",
+        None,
+        severity,
+        message.as_str(),
+        "localhost",
+    );
+    event.timestamp = timestamp.to_rfc3339();
+    event.assign_stable_id();
+    Some(event)
+}
+
+/// Parses `setupapi.dev.log` into one event per install section that started in the window,
+/// keeping the newest `max`.
+fn read_setupapi_log<R: BufRead>(
+    reader: R,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    max: usize,
+    context: &TimestampContext,
+) -> Vec<NormalizedEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<SetupSection> = None;
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix(">>>") {
+            let rest = rest.trim();
+            if let Some(time) = rest.strip_prefix("Section start") {
+                if let Some(section) = current.as_mut() {
+                    section.started = parse_section_time(time, context);
+                }
+            } else if let Some(title) = rest.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                current = Some(SetupSection {
+                    title: title.trim().to_string(),
+                    ..SetupSection::default()
+                });
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("<<<") {
+            let rest = rest.trim();
+            if let Some(status) = rest
+                .strip_prefix("[Exit status:")
+                .and_then(|value| value.strip_suffix(']'))
+            {
+                let Some(mut section) = current.take() else {
+                    continue;
+                };
+                section.status = Some(status.trim().to_string());
+                let in_window = section.started.is_some_and(|started| {
+                    start.is_none_or(|start| started >= start)
+                        && end.is_none_or(|end| started <= end)
+                });
+                if in_window {
+                    events.extend(section_event(section));
+                }
+            }
+        } else if let Some(section) = current.as_mut() {
+            let lower = trimmed.to_ascii_lowercase();
+            if let Some(index) = lower.find(".inf") {
+                let name = trimmed[..index + 4]
+                    .rsplit(['\\', ' ', '\'', '{', ':'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                if name.len() > 4 && !section.infs.contains(&name) && section.infs.len() < 5 {
+                    section.infs.push(name);
+                }
+            }
+        }
+    }
+    if events.len() > max {
+        events.drain(..events.len() - max);
+    }
+    events
+}
+
+/// Turns Windows Update Agent history entries (as emitted by `UPDATE_HISTORY_SCRIPT`) into events.
+fn parse_update_history(
+    value: Value,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Vec<NormalizedEvent> {
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Null => Vec::new(),
+        other => vec![other],
+    };
+    let mut events = Vec::new();
+    for item in items {
+        let Some(timestamp) = item
+            .get("date")
+            .and_then(Value::as_str)
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&Utc))
+        else {
+            continue;
+        };
+        if start.is_some_and(|start| timestamp < start) || end.is_some_and(|end| timestamp > end) {
+            continue;
+        }
+        let title = item
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or("Unnamed update");
+        let uninstall = item.get("operation").and_then(Value::as_u64) == Some(2);
+        // OperationResultCode: 2 succeeded, 3 succeeded with errors, 4 failed, 5 aborted.
+        let (verb, severity) = match (item.get("resultCode").and_then(Value::as_u64), uninstall) {
+            (Some(2), false) => ("Installed", "information"),
+            (Some(2), true) => ("Uninstalled", "information"),
+            (Some(3), _) => ("Completed with errors", "warning"),
+            (Some(4), false) => ("Failed to install", "error"),
+            (Some(4), true) => ("Failed to uninstall", "error"),
+            (Some(5), _) => ("Aborted", "warning"),
+            _ => ("Started", "information"),
+        };
+        let mut message = format!("{verb}: {title}");
+        if let Some(code) = item
+            .get("hResult")
+            .and_then(Value::as_i64)
+            .filter(|code| *code != 0)
+        {
+            message.push_str(format!(" (0x{:08X})", code as u32).as_str());
+        }
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            UPDATE_LOG,
+            UPDATES_CATEGORY,
+            "Windows Update Agent",
+            None,
+            severity,
+            message.as_str(),
+            "localhost",
+        );
+        event.timestamp = timestamp.to_rfc3339();
+        event.raw_payload = Some(item.to_string());
+        event.assign_stable_id();
+        events.push(event);
+    }
+    events
+}
+
+#[cfg(target_os = "windows")]
+const UPDATE_HISTORY_SCRIPT: &str = "$searcher = (New-Object -ComObject Microsoft.Update.Session).CreateUpdateSearcher(); \
+$count = $searcher.GetTotalHistoryCount(); \
+if ($count -gt 0) { $searcher.QueryHistory(0, $count) | Where-Object { $_.Title } | ForEach-Object { [pscustomobject]@{\
+title=$_.Title;date=$_.Date.ToUniversalTime().ToString('o');operation=[int]$_.Operation;resultCode=[int]$_.ResultCode;hResult=$_.HResult} } | ConvertTo-Json -Compress }";
+
+/// Appends Windows Update history and driver installs from the window to `result`. Either
+/// source failing only adds a warning.
+#[cfg(target_os = "windows")]
+pub fn append_install_history_events(
+    result: &mut CollectionResult,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) {
+    match std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            UPDATE_HISTORY_SCRIPT,
+        ])
+        .output()
+    {
+        Ok(output) if output.stdout.iter().all(u8::is_ascii_whitespace) => {}
+        Ok(output) => match serde_json::from_slice::<Value>(output.stdout.as_slice()) {
+            Ok(value) => result
+                .events
+                .extend(parse_update_history(value, start, end)),
+            Err(_) => result
+                .warnings
+                .push("Windows Update history returned no readable output.".to_string()),
+        },
+        Err(error) => result
+            .warnings
+            .push(format!("Failed to read Windows Update history: {error}")),
+    }
+
+    let windows_dir = std::env::var("WINDIR").unwrap_or_else(|_| r"C:\Windows".to_string());
+    let path = std::path::Path::new(windows_dir.as_str())
+        .join("INF")
+        .join(DRIVER_LOG);
+    match std::fs::File::open(&path) {
+        Ok(file) => result.events.extend(read_setupapi_log(
+            std::io::BufReader::new(file),
+            start,
+            end,
+            MAX_DRIVER_EVENTS,
+            &TimestampContext::local(),
+        )),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => result
+            .warnings
+            .push(format!("Failed to read {}: {error}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Cursor;
+
+    #[test]
+    fn parses_setupapi_sections() {
+        let raw = "[Boot Session: 2026/03/01 09:00:00.000]\n\
+>>>  [Device Install (Hardware initiated) - PCI\\VEN_10DE&DEV_2484]\n\
+>>>  Section start 2026/03/01 10:00:00.123\n\
+     dvi: {Select Best Driver}\n\
+     dvi:      Selected Driver:\n\
+     dvi:           Inf Name   - oem42.inf\n\
+<<<  Section end 2026/03/01 10:00:05.456\n\
+<<<  [Exit status: SUCCESS]\n\
+>>>  [Setup Import Driver Package - C:\\Drivers\\net\\netwtw.inf]\n\
+>>>  Section start 2026/03/02 08:00:00.000\n\
+<<<  Section end 2026/03/02 08:00:01.000\n\
+<<<  [Exit status: FAILURE(0xe0000247)]\n";
+        let events = read_setupapi_log(Cursor::new(raw), None, None, 10, &TimestampContext::utc());
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].category, UPDATES_CATEGORY);
+        assert_eq!(events[0].timestamp, "2026-03-01T10:00:00.123+00:00");
+        assert!(events[0].message.contains("INF: oem42.inf"));
+        assert_eq!(events[0].severity, "information");
+        assert_eq!(events[1].severity, "error");
+        assert!(events[1].message.contains("netwtw.inf"));
+    }
+
+    #[test]
+    fn parses_update_history_entries() {
+        let value = json!([
+            {
+                "title": "2026-03 Cumulative Update for Windows 11 (KB5039999)",
+                "date": "2026-03-10T03:00:00.0000000Z",
+                "operation": 1,
+                "resultCode": 4,
+                "hResult": -2145124329
+            },
+            {
+                "title": "Security Intelligence Update for Microsoft Defender",
+                "date": "2026-03-11T03:00:00.0000000Z",
+                "operation": 1,
+                "resultCode": 2,
+                "hResult": 0
+            }
+        ]);
+        let events = parse_update_history(value, None, None);
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].message,
+            "Failed to install: 2026-03 Cumulative Update for Windows 11 (KB5039999) (0x80240017)"
+        );
+        assert_eq!(events[0].severity, "error");
+        assert_eq!(events[1].log_name, UPDATE_LOG);
+        assert!(events[1].message.starts_with("Installed:"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Structured fields for `Microsoft-Windows-Sysmon/Operational` events. Sysmon puts everything
+//! an investigation pivots on (image, command line, network peer, hashes) in `<EventData>`,
+//! so those pairs are kept as event metadata instead of only in the rendered message.
+
+use std::collections::BTreeMap;
+
+pub const SYSMON_CHANNEL: &str = "Microsoft-Windows-Sysmon/Operational";
+
+pub fn is_sysmon_channel(log_name: &str) -> bool {
+    log_name.eq_ignore_ascii_case(SYSMON_CHANNEL)
+}
+
+/// Sysmon's name for the event ID, recorded as the `EventType` field.
+fn event_type(event_id: u32) -> Option<&'static str> {
+    let name = match event_id {
+        1 => "Process Create",
+        2 => "File Creation Time Changed",
+        3 => "Network Connection",
+        4 => "Sysmon Service State Changed",
+        5 => "Process Terminated",
+        6 => "Driver Loaded",
+        7 => "Image Loaded",
+        8 => "CreateRemoteThread",
+        9 => "RawAccessRead",
+        10 => "Process Access",
+        11 => "File Create",
+        12 => "Registry Object Added or Deleted",
+        13 => "Registry Value Set",
+        14 => "Registry Object Renamed",
+        15 => "File Create Stream Hash",
+        16 => "Sysmon Config State Changed",
+        17 => "Pipe Created",
+        18 => "Pipe Connected",
+        19 => "WMI Event Filter",
+        20 => "WMI Event Consumer",
+        21 => "WMI Event Consumer To Filter",
+        22 => "DNS Query",
+        23 => "File Delete Archived",
+        24 => "Clipboard Changed",
+        25 => "Process Tampering",
+        26 => "File Delete Logged",
+        27 => "File Block Executable",
+        28 => "File Block Shredding",
+        29 => "File Executable Detected",
+        255 => "Error",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Metadata for one Sysmon event: every non-empty `<EventData>` value, plus the event type and
+/// the `Hashes` list split into one field per algorithm (`SHA256`, `MD5`, `IMPHASH`, ...).
+pub fn event_metadata(
+    event_id: Option<u32>,
+    event_data: &[(String, String)],
+) -> Option<BTreeMap<String, String>> {
+    let mut fields = BTreeMap::new();
+    for (name, value) in event_data {
+        let value = value.trim();
+        // Sysmon writes `-` for fields it could not fill.
+        if name.is_empty() || value.is_empty() || value == "-" {
+            continue;
+        }
+        if name == "Hashes" {
+            for (algorithm, digest) in value.split(',').filter_map(|part| part.split_once('=')) {
+                fields.insert(
+                    algorithm.trim().to_ascii_uppercase(),
+                    digest.trim().to_string(),
+                );
+            }
+        }
+        fields.insert(name.clone(), value.to_string());
+    }
+    if fields.is_empty() {
+        return None;
+    }
+    if let Some(name) = event_id.and_then(event_type) {
+        fields.insert("EventType".to_string(), name.to_string());
+    }
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(values: &[(&str, &str)]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_process_create_fields_and_hashes() {
+        let data = pairs(&[
+            ("RuleName", "-"),
+            ("Image", r"C:\Windows\System32\cmd.exe"),
+            ("CommandLine", r#"cmd.exe /c "whoami""#),
+            (
+                "Hashes",
+                "SHA256=9F914D42706FE215501044ACD85A32D58AAEF1419D404FDDFA5D3B48F66CCD9F,IMPHASH=3062ED732D4B25D1C64F084DAC97D37A",
+            ),
+        ]);
+        let fields = event_metadata(Some(1), &data).unwrap();
+        assert_eq!(fields["EventType"], "Process Create");
+        assert_eq!(fields["Image"], r"C:\Windows\System32\cmd.exe");
+        assert_eq!(
+            fields["SHA256"],
+            "9F914D42706FE215501044ACD85A32D58AAEF1419D404FDDFA5D3B48F66CCD9F"
+        );
+        assert_eq!(fields["IMPHASH"], "3062ED732D4B25D1C64F084DAC97D37A");
+        assert!(fields.contains_key("Hashes"));
+        assert!(!fields.contains_key("RuleName"));
+    }
+
+    #[test]
+    fn keeps_network_fields_and_skips_empty_events() {
+        let data = pairs(&[("DestinationIp", "10.0.0.5"), ("DestinationPort", "443")]);
+        let fields = event_metadata(Some(3), &data).unwrap();
+        assert_eq!(fields["EventType"], "Network Connection");
+        assert_eq!(fields["DestinationIp"], "10.0.0.5");
+        assert!(event_metadata(Some(3), &pairs(&[("RuleName", "-")])).is_none());
+        assert!(is_sysmon_channel("microsoft-windows-sysmon/operational"));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::forwarding::authorize;
+use crate::logs::NormalizedEvent;
+use crate::settings::IntegrationConfig;
+use crate::timestamps::parse_timestamp;
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+const PUSH_PATH: &str = "/loki/api/v1/push";
+/// Added to every stream so Hermes events can be told apart from other agents.
+const JOB_LABEL: &str = "hermes";
+
+fn push_url(endpoint: &str) -> String {
+    let trimmed = endpoint.trim().trim_end_matches('/');
+    if trimmed.ends_with(PUSH_PATH) {
+        trimmed.to_string()
+    } else {
+        format!("{trimmed}{PUSH_PATH}")
+    }
+}
+
+/// Maps a setting field to its Loki label name and the event's value for it.
+fn label(event: &NormalizedEvent, field: &str) -> Option<(&'static str, String)> {
+    match field {
+        "os" => Some(("os", event.os.as_str().to_string())),
+        "category" => Some(("category", event.category.as_str().to_string())),
+        "provider" => Some(("provider", event.provider.clone())),
+        "severity" => Some(("severity", event.severity.as_str().to_string())),
+        "logName" => Some(("log_name", event.log_name.clone())),
+        "sourceHost" => Some(("source_host", event.source_host.clone())),
+        _ => None,
+    }
+}
+
+/// Builds a push request body. Events sharing the same label values go into one stream, and
+/// each stream's entries are ordered by time as Loki requires.
+pub fn push_body(labels: &[String], events: &[NormalizedEvent]) -> Result<Value, String> {
+    let mut streams: BTreeMap<BTreeMap<&'static str, String>, Vec<(i64, String)>> = BTreeMap::new();
+    for event in events {
+        let mut stream = BTreeMap::from([("job", JOB_LABEL.to_string())]);
+        stream.extend(
+            labels
+                .iter()
+                .filter_map(|field| label(event, field.as_str()))
+                .filter(|(_, value)| !value.is_empty()),
+        );
+        let nanos = parse_timestamp(event.timestamp.as_str())
+            .unwrap_or_else(Utc::now)
+            .timestamp_nanos_opt()
+            .unwrap_or_default();
+        let line = serde_json::to_string(event)
+            .map_err(|error| format!("Failed to serialize event for Loki: {error}"))?;
+        streams.entry(stream).or_default().push((nanos, line));
+    }
+
+    let streams: Vec<Value> = streams
+        .into_iter()
+        .map(|(stream, mut values)| {
+            values.sort_by_key(|(nanos, _)| *nanos);
+            let values: Vec<Value> = values
+                .into_iter()
+                .map(|(nanos, line)| json!([nanos.to_string(), line]))
+                .collect();
+            json!({ "stream": stream, "values": values })
+        })
+        .collect();
+    Ok(json!({ "streams": streams }))
+}
+
+/// Sends one push request. Loki accepts or rejects the whole body, so any failure is returned
+/// and the batch stays in the spool.
+pub fn push(
+    client: &reqwest::blocking::Client,
+    integration: &IntegrationConfig,
+    events: &[NormalizedEvent],
+) -> Result<(), String> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let body = push_body(integration.loki_labels.as_slice(), events)?;
+    let request = client
+        .post(push_url(integration.endpoint.as_str()))
+        .json(&body);
+    let response = authorize(request, integration, "Bearer")?
+        .send()
+        .map_err(|error| format!("Loki push request failed: {error}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let detail = response.text().unwrap_or_default();
+        return Err(format!("Loki returned HTTP {status}: {}", detail.trim()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn event(severity: &str, timestamp: &str) -> NormalizedEvent {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Linux,
+            "journal",
+            "system",
+            "kernel",
+            None,
+            severity,
+            "disk failure",
+            "host-a",
+        );
+        event.timestamp = timestamp.to_string();
+        event
+    }
+
+    #[test]
+    fn groups_events_into_sorted_streams_by_label() {
+        let events = vec![
+            event("error", "2024-03-01T10:00:02Z"),
+            event("warning", "2024-03-01T10:00:00Z"),
+            event("error", "2024-03-01T10:00:01Z"),
+        ];
+        let labels = vec!["severity".to_string(), "logName".to_string()];
+        let body = push_body(&labels, &events).unwrap();
+        let streams = body["streams"].as_array().unwrap();
+        assert_eq!(streams.len(), 2);
+
+        let errors = &streams[0];
+        assert_eq!(errors["stream"]["severity"], "error");
+        assert_eq!(errors["stream"]["log_name"], "journal");
+        assert_eq!(errors["stream"]["job"], "hermes");
+        assert!(errors["stream"].get("provider").is_none());
+        let values = errors["values"].as_array().unwrap();
+        assert_eq!(values[0][0], "1709287201000000000");
+        assert_eq!(values[1][0], "1709287202000000000");
+        assert_eq!(
+            push_url("http://loki:3100/"),
+            "http://loki:3100/loki/api/v1/push"
+        );
+    }
+}
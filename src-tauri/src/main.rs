@@ -1,12 +1,71 @@
+mod alerts;
+mod anomaly;
+mod archive;
+mod archive_store;
+mod boot;
+mod bugcheck;
+mod case;
+mod chat;
+mod correlation;
 mod crash;
+mod crash_export;
+mod crash_watcher;
+mod crash_window;
 mod db;
 mod diagnostics;
+mod elasticsearch;
+mod embeddings;
+mod encryption;
+mod event_export;
+mod forwarding;
+mod hangs;
+mod health;
+mod inventory;
+mod ioc;
+mod jobs;
+mod known_issues;
 mod llm;
+mod log_import;
+mod logons;
 mod logs;
+mod loki;
+mod nl_query;
+mod parquet_export;
+mod patterns;
+mod perf;
+mod power;
+mod preflight;
+mod prompt_budget;
+mod query_sessions;
+mod quota;
+mod rca;
+mod recent_changes;
+mod regex_search;
+mod reliability;
+mod remote_access;
 mod remote_common;
 mod remote_macos;
 mod remote_windows;
+mod repeats;
+mod replay;
+mod report;
+mod retention;
+mod saved_filters;
+mod scheduler;
 mod settings;
+mod siem;
+mod sigma;
+mod smart;
+mod stability;
+mod summarize;
+mod syslog;
+mod syslog_listener;
+mod system_changes;
+mod timeline;
+mod timestamps;
+mod usb_devices;
+mod wer_config;
+mod workspaces;
 
 use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
 use crash::{
@@ -15,9 +74,23 @@ use crash::{
 };
 use db::{
     cleanup_duplicate_events, correlate_crash_events, get_crash_by_id, get_crashes as read_crashes,
+    get_event_histogram as read_event_histogram, get_metrics as read_metrics,
+    get_inventory_items, get_inventory_snapshots, get_latest_inventory_timestamp,
+    histogram_buckets_per_day, prune_forward_spools, database_used_bytes,
+    prune_oldest_information_events, get_events_by_ids, get_log_names, save_inventory_snapshot, save_metrics,
+    EventHistogramBucket,
+    MetricSample,
     get_local_events as read_local_events, get_local_events_range as read_local_events_range,
-    get_local_events_window as read_local_events_window, prune_events_before, prune_events_outside,
-    save_crashes, save_local_events,
+    get_local_events_window as read_local_events_window, prune_events_outside,
+    save_crashes, save_local_events, get_stability_days, save_stability_days,
+    delete_event_bookmark, get_bookmarked_events_range, get_event_bookmarks as read_event_bookmarks,
+    save_event_bookmark, EventBookmark,
+    get_sync_history as read_sync_history, record_sync_history, SyncHistoryEntry, SyncSourceCount,
+    append_query_step, delete_query_session, get_query_session, get_query_sessions,
+    get_query_steps, save_query_session, QuerySession, QueryStep,
+    append_chat_message, delete_chat_session as remove_chat_session, get_chat_messages as read_chat_messages,
+    get_chat_session, get_chat_sessions, save_chat_session, ChatMessage, ChatSession,
+    get_template_embeddings, save_template_embedding,
 };
 use logs::{
     collect_host_events_range_with_windows_channels, detect_host_os,
@@ -28,18 +101,26 @@ use remote_common::RemoteConnectionTestResult;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use settings::{
-    load_export_dir, load_ingest_profile, load_ingest_window_days,
-    load_llm_settings_with_migration, load_theme, save_export_dir, save_ingest_profile,
-    save_ingest_window_days, save_llm_settings, save_theme, IngestProfile, LlmConnectionProfile,
-    LlmSettings, RemoteConnectionProfile, RemoteProviderAccount,
+    load_display_timezone, load_export_dir, load_ingest_profile, load_ingest_window_days,
+    load_llm_settings_with_migration, load_theme, save_display_timezone, save_export_dir,
+    save_ingest_profile, save_ingest_window_days, save_llm_settings, save_theme, IngestProfile,
+    LlmConnectionProfile,
+    LlmSettings, RemoteConnectionProfile, RemoteProviderAccount, SavedFilter, Workspace,
+    WorkspaceSettings,
 };
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use tauri::menu::{MenuBuilder, SubmenuBuilder};
 use tauri::{AppHandle, Emitter, Manager};
 
 const LLM_KEYCHAIN_SERVICE: &str = "hermes-log-analyst.llm";
+/// Target id that reads events and crashes from every stored host at once.
+const ALL_HOSTS_TARGET: &str = "all-hosts";
+/// Target id prefix naming a stored `source_host` directly, for hosts with no connection profile.
+const HOST_TARGET_PREFIX: &str = "host:";
 
 fn remote_collection_outcome(
     remote: &RemoteConnectionProfile,
@@ -80,7 +161,7 @@ fn remote_collection_outcome(
             start,
             end,
             max_events.or(Some(profile.max_events_per_sync)),
-            Some(profile.windows_channels.as_slice()),
+            Some(&profile.journal_filter),
         ),
         "macos" => {
             if matches!(remote.protocol.as_str(), "jamf" | "intune") {
@@ -104,7 +185,7 @@ fn remote_collection_outcome(
                     start,
                     end,
                     max_events.or(Some(profile.max_events_per_sync)),
-                    Some(profile.windows_channels.as_slice()),
+                    Some(&profile.unified_log_filter),
                 )
             }
         }
@@ -189,6 +270,11 @@ fn run_command(binary: &str, args: &[&str]) -> Option<String> {
 struct SyncOperationResult {
     collected: usize,
     warnings: Vec<String>,
+    /// Storage-quota measures applied while saving this batch.
+    degradations: Vec<String>,
+    /// Recoverable collector errors, such as one unreadable channel.
+    errors: Vec<String>,
+    sources: Vec<SyncSourceCount>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -251,9 +337,120 @@ fn report_collection_outcome(
     Ok(SyncOperationResult {
         collected: outcome.events.len(),
         warnings,
+        degradations: Vec::new(),
+        errors: outcome.errors.clone(),
+        sources: source_counts(outcome.events.as_slice()),
     })
 }
 
+/// Events per log name in a collected batch, largest first.
+fn source_counts(events: &[NormalizedEvent]) -> Vec<SyncSourceCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for event in events {
+        *counts.entry(event.log_name.as_str()).or_default() += 1;
+    }
+    let mut sources: Vec<SyncSourceCount> = counts
+        .into_iter()
+        .map(|(log_name, count)| SyncSourceCount {
+            log_name: log_name.to_string(),
+            count,
+        })
+        .collect();
+    sources.sort_by(|left, right| right.count.cmp(&left.count));
+    sources
+}
+
+/// Records a finished sync in the health tracker and the persisted sync history.
+fn record_sync(
+    kind: &str,
+    host: &str,
+    started_at: DateTime<Utc>,
+    result: &Result<SyncOperationResult, String>,
+) {
+    health::record_sync_result(result.as_ref().err().map(String::as_str));
+    let mut entry = SyncHistoryEntry {
+        id: 0,
+        kind: kind.to_string(),
+        source_host: host.to_string(),
+        started_at: started_at.to_rfc3339(),
+        finished_at: Utc::now().to_rfc3339(),
+        status: "failed".to_string(),
+        collected: 0,
+        warnings: Vec::new(),
+        errors: Vec::new(),
+        sources: Vec::new(),
+    };
+    match result {
+        Ok(report) => {
+            let clean = report.warnings.is_empty() && report.errors.is_empty();
+            entry.status = if clean { "ok" } else { "partial" }.to_string();
+            entry.collected = report.collected;
+            entry.warnings = report
+                .warnings
+                .iter()
+                .chain(&report.degradations)
+                .cloned()
+                .collect();
+            entry.errors = report.errors.clone();
+            entry.sources = report.sources.clone();
+        }
+        Err(error) => entry.errors.push(error.clone()),
+    }
+    if let Err(error) = record_sync_history(&entry) {
+        diagnostics::warn("storage", format!("Failed to record sync history: {error}"));
+    }
+}
+
+fn storage_quota_plan(max_database_mb: u32) -> quota::QuotaPlan {
+    let used_bytes = database_used_bytes().unwrap_or_else(|error| {
+        diagnostics::warn("storage", format!("Database size check failed: {error}"));
+        0
+    });
+    quota::plan_for_usage(used_bytes, u64::from(max_database_mb) * 1024 * 1024)
+}
+
+/// Saves a collected batch within the configured database size, sampling or dropping low-severity
+/// events and pruning old information events as the limit nears. Applied measures are added to
+/// the report so degradation is never silent. Runs of identical messages are stored as one
+/// counted event when the profile collapses repeats.
+fn save_collected_events(
+    events: &[NormalizedEvent],
+    profile: &IngestProfile,
+    report: &mut SyncOperationResult,
+) -> Result<(), String> {
+    let plan = storage_quota_plan(profile.max_database_mb);
+    let (kept, skipped) = quota::degrade_batch(events, &plan);
+    if profile.collapse_repeats {
+        let runs = repeats::collapse_events(kept.as_slice(), repeats::DEFAULT_GAP_SECONDS);
+        db::save_repeated_events(runs.as_slice())?;
+    } else {
+        save_local_events(kept.as_slice())?;
+    }
+
+    if plan.degradations.is_empty() {
+        return Ok(());
+    }
+    let mut degradations = plan.degradations;
+    if skipped > 0 {
+        degradations.push(format!("Skipped {skipped} low-severity events from this sync."));
+    }
+    if plan.prune_information_share > 0.0 {
+        match prune_oldest_information_events(plan.prune_information_share) {
+            Ok(pruned) => {
+                degradations.push(format!("Pruned {pruned} old information events."))
+            }
+            Err(error) => {
+                diagnostics::warn("storage", format!("Quota pruning failed: {error}"))
+            }
+        }
+    }
+    for degradation in &degradations {
+        diagnostics::warn("storage", degradation.clone());
+    }
+    report.degradations = degradations;
+    Ok(())
+}
+
 fn report_collection_estimate(
     context: &str,
     window_start: &DateTime<Utc>,
@@ -1373,18 +1570,99 @@ fn candidate_profiles_for_analysis(
     Ok(candidates)
 }
 
+/// Streams an analysis from providers with a streaming API, passing text deltas to `on_delta`.
+/// Gemini and Claude are not streamed yet; their full response arrives as a single delta.
+fn run_profile_analysis_streaming(
+    profile: &LlmConnectionProfile,
+    prompt: &str,
+    api_key: Option<&str>,
+    cancel: &AtomicBool,
+    on_delta: &mut dyn FnMut(&str),
+) -> Result<(String, String), String> {
+    let provider = profile.provider.trim().to_ascii_lowercase();
+    if matches!(provider.as_str(), "gemini" | "claude") {
+        let (model, response) = run_profile_analysis(profile, prompt, api_key)?;
+        on_delta(response.as_str());
+        return Ok((model, response));
+    }
+    if !provider_is_valid(provider.as_str()) {
+        return Err("Selected profile is not configured as a compatible provider.".to_string());
+    }
+    let base_url = normalize_base_url(profile.base_url.as_str());
+    if base_url.is_empty() {
+        return Err("Base URL is required for local LLM analysis.".to_string());
+    }
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(analysis_timeout_for_profile(profile))
+        .build()
+        .map_err(|error| format!("Failed to initialize HTTP client: {error}"))?;
+    let model = resolve_model_for_profile(profile, &client, api_key)?;
+    let (request, format, label) = if provider == "ollama" {
+        let payload = serde_json::json!({
+            "model": model,
+            "messages": [{ "role": "user", "content": prompt }],
+            "stream": true
+        });
+        (
+            client
+                .post(join_url(base_url.as_str(), "/api/chat"))
+                .json(&payload),
+            llm::StreamFormat::OllamaChat,
+            "Ollama".to_string(),
+        )
+    } else {
+        let payload = serde_json::json!({
+            "model": model,
+            "messages": [{ "role": "user", "content": prompt }],
+            "temperature": 0.2,
+            "stream": true
+        });
+        let mut request = client
+            .post(openai_chat_endpoint(base_url.as_str()))
+            .json(&payload);
+        if let Some(key) = api_key.filter(|value| !value.trim().is_empty()) {
+            request = request.bearer_auth(key);
+        }
+        (request, llm::StreamFormat::OpenAiSse, provider.clone())
+    };
+
+    let response =
+        llm::stream_chat_completion(request, format, label.as_str(), cancel, on_delta)?;
+    Ok((model, response))
+}
+
+fn profile_prompt_budget(profile: &LlmConnectionProfile) -> usize {
+    prompt_budget::prompt_token_budget(profile.provider.as_str(), profile.context_window_tokens)
+}
+
 fn analyze_with_local_llm_sync(
     settings: LlmSettings,
     prompt: String,
     requested_profile_id: Option<String>,
+) -> Result<LlmAnalysisResult, String> {
+    analyze_with_candidate_profiles(
+        settings,
+        prompt,
+        requested_profile_id,
+        None,
+        &mut |profile, prompt, api_key| run_profile_analysis(profile, prompt, api_key),
+    )
+}
+
+/// Tries each candidate profile in order until one answers. A set `cancel` flag stops the
+/// fallback chain instead of moving on to the next profile.
+fn analyze_with_candidate_profiles(
+    settings: LlmSettings,
+    prompt: String,
+    requested_profile_id: Option<String>,
+    cancel: Option<&AtomicBool>,
+    run: &mut dyn FnMut(&LlmConnectionProfile, &str, Option<&str>) -> Result<(String, String), String>,
 ) -> Result<LlmAnalysisResult, String> {
     let trimmed_prompt = prompt.trim().to_string();
     if trimmed_prompt.is_empty() {
         return Err("Prompt is empty.".to_string());
     }
-    if trimmed_prompt.len() > 80_000 {
-        return Err("Prompt is too large (max 80,000 characters).".to_string());
-    }
 
     let candidates = candidate_profiles_for_analysis(
         &settings,
@@ -1410,9 +1688,32 @@ fn analyze_with_local_llm_sync(
             continue;
         }
 
+        // Oversized prompts are compacted to this profile's context window rather than sent
+        // to fail upstream.
+        let compacted = prompt_budget::compact_free_text(
+            trimmed_prompt.as_str(),
+            profile_prompt_budget(profile),
+        );
+        let prompt = compacted
+            .as_ref()
+            .map(|fitted| fitted.text.as_str())
+            .unwrap_or(trimmed_prompt.as_str());
         let api_key = get_profile_keychain_secret(profile.id.as_str())?;
-        match run_profile_analysis(profile, trimmed_prompt.as_str(), api_key.as_deref()) {
+        match run(profile, prompt, api_key.as_deref()) {
             Ok((model, response)) => {
+                let compaction_warning = compacted.as_ref().map(|fitted| {
+                    format!(
+                        "Prompt was shortened to fit the {} context window ({} line(s) omitted).",
+                        profile.name, fitted.omitted_lines
+                    )
+                });
+                let warning = [
+                    profile_warning_for_settings(profile, &settings),
+                    compaction_warning,
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
                 return Ok(LlmAnalysisResult {
                     ok: true,
                     profile_id: profile.id.clone(),
@@ -1422,10 +1723,13 @@ fn analyze_with_local_llm_sync(
                     model,
                     response,
                     fallback_used: index > 0,
-                    warning: profile_warning_for_settings(profile, &settings),
+                    warning: (!warning.is_empty()).then(|| warning.join(" ")),
                 });
             }
             Err(error) => {
+                if cancel.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+                    return Err(llm::STREAM_CANCELLED.to_string());
+                }
                 errors.push(format!("{}: {error}", profile.name));
             }
         }
@@ -1448,6 +1752,40 @@ fn resolve_target_profile(
     settings.profiles.into_iter().find(|p| p.id == id)
 }
 
+/// Host whose stored events a target reads. The replay pseudo-target reads events written by
+/// the fixture replay source, `syslog:<address>` the events a syslog sender delivered, and
+/// `host:<name>` any stored host by name.
+fn resolve_target_host(target_id: Option<&str>) -> String {
+    if target_id == Some(replay::REPLAY_SOURCE_HOST) {
+        return replay::REPLAY_SOURCE_HOST.to_string();
+    }
+    if let Some(sender) =
+        target_id.and_then(|id| id.strip_prefix(syslog_listener::TARGET_PREFIX))
+    {
+        return sender.to_string();
+    }
+    if let Some(host) = target_id.and_then(|id| id.strip_prefix(HOST_TARGET_PREFIX)) {
+        return host.to_string();
+    }
+    resolve_target_profile(target_id)
+        .map(|p| p.host)
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+/// Targets that only read already stored data and have nothing to collect from.
+fn is_stored_host_target(target_id: Option<&str>) -> bool {
+    target_id.is_some_and(|id| id == ALL_HOSTS_TARGET || id.starts_with(HOST_TARGET_PREFIX))
+}
+
+/// Host filter for reads that may span every stored host. `all-hosts` lifts the filter so
+/// imported, remote and local data can be viewed together; any other target narrows to one host.
+fn resolve_host_filter(target_id: Option<&str>) -> Option<String> {
+    if target_id == Some(ALL_HOSTS_TARGET) {
+        return None;
+    }
+    Some(resolve_target_host(target_id))
+}
+
 fn resolve_remote_provider_account(provider: &str) -> Option<RemoteProviderAccount> {
     let settings = crate::settings::load_remote_settings();
     settings
@@ -1477,165 +1815,1842 @@ fn hydrate_remote_provider_token_flags(
 
 #[tauri::command]
 async fn refresh_local_events(target_id: Option<String>) -> Result<SyncOperationResult, String> {
-    let days = load_ingest_window_days();
-    let profile = load_ingest_profile();
-    let now = Utc::now();
-    let start = now - chrono::Duration::days(days as i64);
-    let start_str = start.to_rfc3339();
+    if is_stored_host_target(target_id.as_deref()) {
+        return Ok(SyncOperationResult {
+            collected: 0,
+            warnings: Vec::new(),
+            degradations: Vec::new(),
+            errors: Vec::new(),
+            sources: Vec::new(),
+        });
+    }
+    tauri::async_runtime::spawn_blocking(move || refresh_events(target_id.as_deref(), "refresh"))
+        .await
+        .map_err(|error| {
+            command_error(
+                "runtime",
+                "Failed to join refresh collection task",
+                error.to_string(),
+            )
+        })?
+}
 
-    let target = target_id.clone();
+/// Starts a refresh in the background and returns its job ID; progress and the final
+/// `SyncOperationResult` arrive as `hla://job-progress` events.
+#[tauri::command]
+fn start_refresh_job(app: AppHandle, target_id: Option<String>) -> String {
+    let job = jobs::Job::start(app, "refresh");
+    let job_id = job.id().to_string();
     tauri::async_runtime::spawn_blocking(move || {
-        let remote_profile = resolve_target_profile(target.as_deref());
-
-        let outcome = if let Some(remote) = remote_profile {
-            remote_collection_outcome(
-                &remote,
-                &profile,
-                Some(start),
-                Some(now),
-                Some(profile.max_events_per_sync),
-            )
+        let result = if is_stored_host_target(target_id.as_deref()) {
+            Ok(SyncOperationResult {
+                collected: 0,
+                warnings: Vec::new(),
+                degradations: Vec::new(),
+                errors: Vec::new(),
+                sources: Vec::new(),
+            })
         } else {
-            collect_host_events_range_with_windows_channels(
-                Some(start),
-                Some(now),
-                Some(profile.max_events_per_sync),
-                Some(profile.windows_channels.as_slice()),
-                profile.request_elevation,
-            )
+            job.run(|| refresh_events(target_id.as_deref(), "refresh"))
         };
-        let report = report_collection_outcome("Refresh collection", &outcome)?;
-        save_local_events(outcome.events.as_slice())
-            .map_err(|error| command_error("storage", "Failed to save refreshed events", error))?;
-        if let Err(error) = prune_events_before(start_str.as_str()) {
-            diagnostics::warn("storage", format!("Prune after refresh failed: {error}"));
-        }
-        Ok::<SyncOperationResult, String>(report)
-    })
-    .await
-    .map_err(|error| {
-        command_error(
-            "runtime",
-            "Failed to join refresh collection task",
-            error.to_string(),
-        )
-    })?
+        job.finish(&result);
+    });
+    job_id
 }
 
 #[tauri::command]
-async fn estimate_refresh_local_events() -> Result<EventLoadEstimateResult, String> {
+fn get_job_status(job_id: String) -> Option<jobs::JobStatus> {
+    jobs::job_status(job_id.as_str())
+}
+
+/// Collects the ingest window for the local host or a remote target, stores it and records the
+/// run in the sync history under `kind`.
+fn refresh_events(target_id: Option<&str>, kind: &str) -> Result<SyncOperationResult, String> {
+    let _activity = scheduler::begin_sync();
+    let now = Utc::now();
+    let result = collect_refresh_window(target_id, now);
+    record_sync(kind, resolve_target_host(target_id).as_str(), now, &result);
+    result
+}
+
+fn collect_refresh_window(
+    target_id: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<SyncOperationResult, String> {
     let days = load_ingest_window_days();
     let profile = load_ingest_profile();
-    let now = Utc::now();
     let start = now - chrono::Duration::days(days as i64);
 
-    tauri::async_runtime::spawn_blocking(move || {
-        let estimate = estimate_host_events_range_with_windows_channels(
+    let remote_profile = resolve_target_profile(target_id);
+    let is_local = remote_profile.is_none();
+    let host = remote_profile
+        .as_ref()
+        .map(|remote| remote.host.clone())
+        .unwrap_or_else(|| "localhost".to_string());
+
+    let outcome = if let Some(remote) = remote_profile {
+        remote_collection_outcome(
+            &remote,
+            &profile,
             Some(start),
             Some(now),
+            Some(profile.max_events_per_sync),
+        )
+    } else {
+        collect_host_events_range_with_windows_channels(
+            Some(start),
+            Some(now),
+            Some(profile.max_events_per_sync),
             Some(profile.windows_channels.as_slice()),
+            Some(&profile.journal_filter),
+            Some(&profile.unified_log_filter),
             profile.request_elevation,
-        );
-        report_collection_estimate("Refresh estimate", &start, &now, &estimate)
+        )
+    };
+    let mut report = report_collection_outcome("Refresh collection", &outcome)?;
+    save_collected_events(outcome.events.as_slice(), &profile, &mut report)
+        .map_err(|error| command_error("storage", "Failed to save refreshed events", error))?;
+    promote_event_crashes(outcome.events.as_slice());
+    forwarding::forward_events(outcome.events.as_slice());
+    alerts::evaluate_events(outcome.events.as_slice());
+    if let Err(error) = refresh_stability_index(host.as_str()) {
+        diagnostics::warn("storage", format!("Stability index update failed: {error}"));
+    }
+    if profile.collect_smart && is_local {
+        store_smart_metrics();
+    }
+    if is_local {
+        store_perf_snapshot();
+    }
+    if is_local && cfg!(target_os = "windows") {
+        store_reliability_data(Some(start));
+    }
+    if is_local {
+        capture_inventory_if_stale();
+    }
+    Ok(report)
+}
+
+/// One scheduled refresh of the local host, reported as a `hla://sync-completed` payload.
+fn scheduled_refresh() -> scheduler::SyncCompletedPayload {
+    let started_at = Utc::now();
+    let result = refresh_events(None, "scheduled");
+    let (collected, warnings, error) = match result {
+        Ok(report) => (report.collected, report.warnings, None),
+        Err(error) => (0, Vec::new(), Some(error)),
+    };
+    scheduler::SyncCompletedPayload {
+        started_at: started_at.to_rfc3339(),
+        finished_at: Utc::now().to_rfc3339(),
+        collected,
+        warnings,
+        error,
+    }
+}
+
+/// Creates crash records for crash-describing events (Application Error 1000, BugCheck 1001)
+/// found in a freshly synced batch. Failures are logged so they never fail the sync itself.
+fn promote_event_crashes(events: &[NormalizedEvent]) {
+    let crashes = crash::crashes_from_events(events);
+    if crashes.is_empty() {
+        return;
+    }
+    if let Err(error) = save_crashes(&crashes) {
+        diagnostics::warn("storage", format!("Failed to save crashes promoted from events: {error}"));
+    }
+}
+
+/// Recomputes the stored stability index for the full local days still covered by the ingest
+/// window. Older days keep their stored values because their events may already be pruned.
+fn refresh_stability_index(host: &str) -> Result<Vec<stability::StabilityDay>, String> {
+    let window_days = load_ingest_window_days().max(1) as i64;
+    let now = Local::now();
+    let last_day = now.date_naive();
+    let first_day = last_day - chrono::Duration::days(window_days - 1);
+    let start = Local
+        .from_local_datetime(&first_day.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .earliest()
+        .map(|value| value.with_timezone(&Utc))
+        .unwrap_or_else(|| now.with_timezone(&Utc) - chrono::Duration::days(window_days));
+
+    let events = read_local_events_range(
+        start.to_rfc3339().as_str(),
+        Utc::now().to_rfc3339().as_str(),
+        50000,
+        Some(host),
+    )?;
+    let crashes = read_crashes(5000, Some(host))?;
+    let prior_from = first_day - chrono::Duration::days(stability::TRAILING_DAYS - 1);
+    let prior_to = first_day - chrono::Duration::days(1);
+    let prior = get_stability_days(
+        prior_from.format("%Y-%m-%d").to_string().as_str(),
+        prior_to.format("%Y-%m-%d").to_string().as_str(),
+        host,
+    )?
+    .into_iter()
+    .filter_map(|day| {
+        NaiveDate::parse_from_str(day.day.as_str(), "%Y-%m-%d")
+            .ok()
+            .map(|date| (date, day.daily_score))
+    })
+    .collect();
+
+    let days = stability::compute_stability(
+        first_day,
+        last_day,
+        host,
+        events.as_slice(),
+        crashes.as_slice(),
+        &prior,
+        *now.offset(),
+    );
+    save_stability_days(days.as_slice())?;
+    Ok(days)
+}
+
+#[tauri::command]
+async fn get_stability_index(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<Vec<stability::StabilityDay>, String> {
+    NaiveDate::parse_from_str(from.as_str(), "%Y-%m-%d")
+        .and(NaiveDate::parse_from_str(to.as_str(), "%Y-%m-%d"))
+        .map_err(|_| {
+            command_error(
+                "runtime",
+                "Invalid stability range",
+                "Invalid date format (expected YYYY-MM-DD).",
+            )
+        })?;
+    let host = resolve_target_host(target_id.as_deref());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        refresh_stability_index(host.as_str())
+            .map_err(|error| command_error("storage", "Failed to update stability index", error))?;
+        get_stability_days(from.as_str(), to.as_str(), host.as_str())
+            .map_err(|error| command_error("storage", "Failed to read stability index", error))
     })
     .await
     .map_err(|error| {
         command_error(
             "runtime",
-            "Failed to join refresh estimate task",
+            "Failed to join stability index task",
             error.to_string(),
         )
     })?
 }
 
-#[tauri::command]
-fn get_local_events(
-    target_id: Option<String>,
-    limit: Option<u32>,
-) -> Result<Vec<NormalizedEvent>, String> {
-    let limit = limit.unwrap_or(10000).min(50000);
-    let host = resolve_target_profile(target_id.as_deref())
-        .map(|p| p.host)
-        .unwrap_or_else(|| "localhost".to_string());
-    read_local_events(limit, Some(&host))
-        .map_err(|error| command_error("storage", "Failed to read local events", error))
+fn capture_inventory_snapshot_now() -> Result<inventory::InventorySnapshotSummary, String> {
+    let items = inventory::collect_inventory()?;
+    let snapshot = inventory::InventorySnapshotSummary {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: Utc::now().to_rfc3339(),
+        source_host: "localhost".to_string(),
+        item_count: items.len(),
+    };
+    save_inventory_snapshot(
+        snapshot.id.as_str(),
+        snapshot.timestamp.as_str(),
+        snapshot.source_host.as_str(),
+        items.as_slice(),
+    )?;
+    Ok(snapshot)
 }
 
-#[tauri::command]
-fn get_local_events_range(
-    target_id: Option<String>,
-    from: String,
-    to: String,
-    limit: Option<u32>,
-) -> Result<Vec<NormalizedEvent>, String> {
-    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
-        .map_err(|error| command_error("runtime", "Invalid local events range", error))?;
-    let limit = limit.unwrap_or(10000).min(50000);
-    let start_str = start.to_rfc3339();
-    let end_str = end.to_rfc3339();
-    let host = resolve_target_profile(target_id.as_deref())
-        .map(|p| p.host)
-        .unwrap_or_else(|| "localhost".to_string());
-    read_local_events_range(start_str.as_str(), end_str.as_str(), limit, Some(&host))
-        .map_err(|error| command_error("storage", "Failed to read local events for range", error))
+/// Takes a new service/driver snapshot when the latest one is older than the snapshot interval.
+fn capture_inventory_if_stale() {
+    let latest = match get_latest_inventory_timestamp("localhost") {
+        Ok(value) => value,
+        Err(error) => {
+            diagnostics::warn("storage", format!("Failed to read inventory snapshots: {error}"));
+            return;
+        }
+    };
+    let stale = latest
+        .as_deref()
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|value| {
+            Utc::now() - value.with_timezone(&Utc)
+                >= chrono::Duration::hours(inventory::SNAPSHOT_INTERVAL_HOURS)
+        })
+        .unwrap_or(true);
+    if stale {
+        if let Err(error) = capture_inventory_snapshot_now() {
+            diagnostics::warn("collector", format!("Inventory snapshot failed: {error}"));
+        }
+    }
 }
 
 #[tauri::command]
-fn get_local_events_window(
-    target_id: Option<String>,
-    start: String,
-    end: String,
-    limit: Option<u32>,
-) -> Result<Vec<NormalizedEvent>, String> {
-    let (start_value, end_value) = parse_timestamp_window(start.as_str(), end.as_str())
-        .map_err(|error| command_error("runtime", "Invalid local events window", error))?;
-    let limit = limit.unwrap_or(10000).min(50000);
-    let start_str = start_value.to_rfc3339();
-    let end_str = end_value.to_rfc3339();
-    let host = resolve_target_profile(target_id.as_deref())
-        .map(|p| p.host)
-        .unwrap_or_else(|| "localhost".to_string());
-    read_local_events_window(start_str.as_str(), end_str.as_str(), limit, Some(&host))
-        .map_err(|error| command_error("storage", "Failed to read local events for window", error))
+async fn capture_inventory_snapshot() -> Result<inventory::InventorySnapshotSummary, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        capture_inventory_snapshot_now()
+            .map_err(|error| command_error("collector", "Failed to capture inventory snapshot", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join inventory snapshot task",
+            error.to_string(),
+        )
+    })?
 }
 
 #[tauri::command]
-async fn import_host_crashes(
-    _target_id: Option<String>,
-    limit: Option<u32>,
-) -> Result<usize, String> {
-    let max = limit.unwrap_or(200).clamp(1, 2000) as usize;
+async fn get_inventory_changes(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<Vec<inventory::InventoryChangeSet>, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid inventory range", error))?;
+    let host = resolve_target_host(target_id.as_deref());
 
     tauri::async_runtime::spawn_blocking(move || {
-        let crashes = collect_host_crashes(max)
-            .map_err(|error| command_error("collector", "Crash import failed", error))?;
-        if crashes.is_empty() {
-            return Ok::<usize, String>(0);
+        let snapshots = get_inventory_snapshots(
+            start.to_rfc3339().as_str(),
+            end.to_rfc3339().as_str(),
+            host.as_str(),
+        )
+        .map_err(|error| command_error("storage", "Failed to read inventory snapshots", error))?;
+
+        let mut change_sets = Vec::new();
+        let mut previous: Option<(String, Vec<inventory::InventoryItem>)> = None;
+        for snapshot in snapshots {
+            let items = get_inventory_items(snapshot.id.as_str())
+                .map_err(|error| command_error("storage", "Failed to read inventory items", error))?;
+            if let Some((previous_timestamp, previous_items)) = previous.take() {
+                let changes = inventory::diff_inventory(previous_items.as_slice(), items.as_slice());
+                if !changes.is_empty() {
+                    change_sets.push(inventory::InventoryChangeSet {
+                        from_timestamp: previous_timestamp,
+                        to_timestamp: snapshot.timestamp.clone(),
+                        changes,
+                    });
+                }
+            }
+            previous = Some((snapshot.timestamp, items));
         }
-        save_crashes(&crashes)
-            .map_err(|error| command_error("storage", "Failed to save imported crashes", error))?;
-        Ok(crashes.len())
+        Ok(change_sets)
     })
     .await
     .map_err(|error| {
         command_error(
             "runtime",
-            "Failed to join crash import task",
+            "Failed to join inventory diff task",
             error.to_string(),
         )
     })?
 }
 
 #[tauri::command]
-fn get_crashes(target_id: Option<String>, limit: Option<u32>) -> Result<Vec<CrashRecord>, String> {
-    let limit = limit.unwrap_or(250).min(5000);
-    let host = resolve_target_profile(target_id.as_deref())
+async fn preview_forwarding(
+    integration_id: String,
+    from: String,
+    to: String,
+    limit: Option<u32>,
+) -> Result<forwarding::ForwardingPreview, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid forwarding preview range", error))?;
+    let integration = find_integration(integration_id.as_str())?;
+    let limit = limit.unwrap_or(200).min(5000) as usize;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let events = read_local_events_range(
+            start.to_rfc3339().as_str(),
+            end.to_rfc3339().as_str(),
+            50000,
+            Some("localhost"),
+        )
+        .map_err(|error| command_error("storage", "Failed to read events for forwarding preview", error))?;
+        Ok(forwarding::preview_forwarding(
+            integration.id.as_str(),
+            &integration.filter,
+            events,
+            limit,
+        ))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join forwarding preview task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn get_app_health() -> Result<health::HealthReport, String> {
+    tauri::async_runtime::spawn_blocking(health::check_health)
+        .await
+        .map_err(|error| {
+            command_error("runtime", "Failed to join health check task", error.to_string())
+        })
+}
+
+#[tauri::command]
+async fn run_preflight_checks() -> Result<preflight::PreflightReport, String> {
+    tauri::async_runtime::spawn_blocking(preflight::run_preflight_checks)
+        .await
+        .map_err(|error| {
+            command_error("runtime", "Failed to join preflight task", error.to_string())
+        })
+}
+
+/// Recent sync runs for a target, newest first, with their per-source counts and problems.
+#[tauri::command]
+fn get_sync_history(
+    target_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<SyncHistoryEntry>, String> {
+    let host = resolve_host_filter(target_id.as_deref());
+    read_sync_history(limit.unwrap_or(50).clamp(1, 500), host.as_deref())
+        .map_err(|error| command_error("storage", "Failed to read sync history", error))
+}
+
+/// Replays a JSON/NDJSON export (or the bundled fixture when `path` is omitted) on an
+/// accelerated clock under the `replay` host, for trying alert rules and anomaly detection.
+#[tauri::command]
+fn start_event_replay(
+    app: AppHandle,
+    path: Option<String>,
+    speed: Option<f64>,
+) -> Result<replay::ReplayStatus, String> {
+    let path = path.filter(|value| !value.trim().is_empty());
+    replay::start(app, path.as_deref(), speed)
+        .map_err(|error| command_error("collector", "Failed to start event replay", error))
+}
+
+#[tauri::command]
+fn stop_event_replay() -> replay::ReplayStatus {
+    replay::stop()
+}
+
+#[tauri::command]
+fn get_event_replay_status() -> replay::ReplayStatus {
+    replay::replay_status()
+}
+
+#[tauri::command]
+async fn get_forwarding_status() -> Result<Vec<forwarding::ForwardingStatus>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        forwarding::forwarding_status()
+            .map_err(|error| command_error("storage", "Failed to read forwarding status", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join forwarding status task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn flush_forwarding() -> Result<Vec<forwarding::ForwardingStatus>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        forwarding::flush_spools(true);
+        forwarding::forwarding_status()
+            .map_err(|error| command_error("storage", "Failed to read forwarding status", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join forwarding flush task",
+            error.to_string(),
+        )
+    })?
+}
+
+fn find_integration(integration_id: &str) -> Result<crate::settings::IntegrationConfig, String> {
+    crate::settings::load_integration_settings()
+        .integrations
+        .into_iter()
+        .find(|integration| integration.id == integration_id)
+        .ok_or_else(|| {
+            command_error(
+                "settings",
+                "Integration lookup failed",
+                format!("Unknown integration: {integration_id}"),
+            )
+        })
+}
+
+/// Pushes every stored event matching `filter` to an Elasticsearch/OpenSearch integration in
+/// `_bulk` batches, independent of the forwarding spool.
+#[tauri::command]
+async fn push_events_to_elasticsearch(
+    integration_id: String,
+    filter: nl_query::EventQueryFilter,
+    target_id: Option<String>,
+) -> Result<elasticsearch::BulkPushResult, String> {
+    let integration = find_integration(integration_id.as_str())?;
+    if integration.kind != "elasticsearch" {
+        return Err("Selected integration is not an Elasticsearch integration.".to_string());
+    }
+    let host = resolve_host_filter(target_id.as_deref());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .map_err(|error| {
+                command_error("runtime", "Failed to build HTTP client", error.to_string())
+            })?;
+        let mut total = elasticsearch::BulkPushResult::default();
+        let send = |batch: &mut Vec<NormalizedEvent>, total: &mut elasticsearch::BulkPushResult| {
+            let result = elasticsearch::send_bulk(&client, &integration, batch.as_slice())?;
+            batch.clear();
+            total.sent += result.sent;
+            total.failed += result.failed;
+            total.batches += result.batches;
+            let room = 5usize.saturating_sub(total.errors.len());
+            total.errors.extend(result.errors.into_iter().take(room));
+            Ok::<(), String>(())
+        };
+        let (mut batch, _) = db::stream_matching_events(
+            &filter,
+            host.as_deref(),
+            |_| Ok(Vec::with_capacity(elasticsearch::BULK_BATCH_SIZE)),
+            |batch, event| {
+                batch.push(event);
+                if batch.len() >= elasticsearch::BULK_BATCH_SIZE {
+                    send(batch, &mut total)?;
+                }
+                Ok(())
+            },
+        )
+        .map_err(|error| command_error("collector", "Elasticsearch push failed", error))?;
+        send(&mut batch, &mut total)
+            .map_err(|error| command_error("collector", "Elasticsearch push failed", error))?;
+        Ok(total)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join Elasticsearch push task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Sends every stored event matching `filter` to a syslog integration over one connection and
+/// returns how many were sent.
+#[tauri::command]
+async fn push_events_to_syslog(
+    integration_id: String,
+    filter: nl_query::EventQueryFilter,
+    target_id: Option<String>,
+) -> Result<usize, String> {
+    let integration = find_integration(integration_id.as_str())?;
+    if integration.kind != "syslog" {
+        return Err("Selected integration is not a syslog integration.".to_string());
+    }
+    let host = resolve_host_filter(target_id.as_deref());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let sender = syslog::SyslogSender::connect(&integration, Duration::from_secs(30))
+            .map_err(|error| command_error("collector", "Syslog push failed", error))?;
+        let (sender, sent) = db::stream_matching_events(
+            &filter,
+            host.as_deref(),
+            |_| Ok(sender),
+            |sender, event| sender.send(&event),
+        )
+        .map_err(|error| command_error("collector", "Syslog push failed", error))?;
+        sender
+            .finish()
+            .map_err(|error| command_error("collector", "Syslog push failed", error))?;
+        Ok(sent)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join syslog push task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Collects SMART data for the local host and stores it in the metrics table.
+fn store_smart_metrics() -> smart::SmartCollectionResult {
+    let result = smart::collect_smart_metrics("localhost");
+    for warning in &result.warnings {
+        diagnostics::warn("collector", format!("SMART collection: {warning}"));
+    }
+    if let Err(error) = save_metrics(result.samples.as_slice()) {
+        diagnostics::warn("storage", format!("Failed to save SMART metrics: {error}"));
+    }
+    result
+}
+
+#[tauri::command]
+async fn collect_smart_metrics() -> Result<smart::SmartCollectionResult, String> {
+    tauri::async_runtime::spawn_blocking(store_smart_metrics)
+        .await
+        .map_err(|error| {
+            command_error(
+                "runtime",
+                "Failed to join SMART collection task",
+                error.to_string(),
+            )
+        })
+}
+
+/// Samples CPU and disk load on the local host and stores it as `perf` metrics.
+fn store_perf_snapshot() {
+    let result = perf::collect_perf_snapshot("localhost");
+    for warning in &result.warnings {
+        diagnostics::warn("collector", format!("Load snapshot: {warning}"));
+    }
+    if let Err(error) = save_metrics(result.samples.as_slice()) {
+        diagnostics::warn("storage", format!("Failed to save load snapshot: {error}"));
+    }
+}
+
+/// Collects Reliability Monitor records and stability metrics for the local host and stores them
+/// as `Reliability` events and `reliability` metrics.
+fn store_reliability_data(
+    start: Option<DateTime<Utc>>,
+) -> reliability::ReliabilityCollectionResult {
+    let result = reliability::collect_reliability("localhost", start);
+    for warning in &result.warnings {
+        diagnostics::warn("collector", format!("Reliability collection: {warning}"));
+    }
+    if let Err(error) = save_local_events(result.events.as_slice()) {
+        diagnostics::warn(
+            "storage",
+            format!("Failed to save reliability records: {error}"),
+        );
+    }
+    if let Err(error) = save_metrics(result.samples.as_slice()) {
+        diagnostics::warn(
+            "storage",
+            format!("Failed to save stability metrics: {error}"),
+        );
+    }
+    result
+}
+
+#[tauri::command]
+async fn collect_reliability_data(
+    days: Option<u32>,
+) -> Result<reliability::ReliabilityCollectionResult, String> {
+    let start = Utc::now() - chrono::Duration::days(days.unwrap_or(30).clamp(1, 365) as i64);
+    tauri::async_runtime::spawn_blocking(move || store_reliability_data(Some(start)))
+        .await
+        .map_err(|error| {
+            command_error(
+                "runtime",
+                "Failed to join reliability collection task",
+                error.to_string(),
+            )
+        })
+}
+
+#[tauri::command]
+fn get_metrics(
+    target_id: Option<String>,
+    source: String,
+    from: String,
+    to: String,
+    limit: Option<u32>,
+) -> Result<Vec<MetricSample>, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid metrics range", error))?;
+    let host = resolve_target_host(target_id.as_deref());
+    read_metrics(
+        source.as_str(),
+        start.to_rfc3339().as_str(),
+        end.to_rfc3339().as_str(),
+        limit.unwrap_or(5000).min(50000),
+        Some(&host),
+    )
+    .map_err(|error| command_error("storage", "Failed to read metrics", error))
+}
+
+#[tauri::command]
+async fn estimate_refresh_local_events() -> Result<EventLoadEstimateResult, String> {
+    let days = load_ingest_window_days();
+    let profile = load_ingest_profile();
+    let now = Utc::now();
+    let start = now - chrono::Duration::days(days as i64);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let estimate = estimate_host_events_range_with_windows_channels(
+            Some(start),
+            Some(now),
+            Some(profile.windows_channels.as_slice()),
+            Some(&profile.journal_filter),
+            Some(&profile.unified_log_filter),
+            profile.request_elevation,
+        );
+        report_collection_estimate("Refresh estimate", &start, &now, &estimate)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join refresh estimate task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+fn get_local_events(
+    target_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<NormalizedEvent>, String> {
+    let limit = limit.unwrap_or(10000).min(50000);
+    let host = resolve_host_filter(target_id.as_deref());
+    read_local_events(limit, host.as_deref())
+        .map_err(|error| command_error("storage", "Failed to read local events", error))
+}
+
+#[tauri::command]
+fn get_local_events_range(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+    limit: Option<u32>,
+) -> Result<Vec<NormalizedEvent>, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid local events range", error))?;
+    let limit = limit.unwrap_or(10000).min(50000);
+    let start_str = start.to_rfc3339();
+    let end_str = end.to_rfc3339();
+    let host = resolve_host_filter(target_id.as_deref());
+    read_local_events_range(start_str.as_str(), end_str.as_str(), limit, host.as_deref())
+        .map_err(|error| command_error("storage", "Failed to read local events for range", error))
+}
+
+#[tauri::command]
+fn get_local_events_window(
+    target_id: Option<String>,
+    start: String,
+    end: String,
+    limit: Option<u32>,
+) -> Result<Vec<NormalizedEvent>, String> {
+    let (start_value, end_value) = parse_timestamp_window(start.as_str(), end.as_str())
+        .map_err(|error| command_error("runtime", "Invalid local events window", error))?;
+    let limit = limit.unwrap_or(10000).min(50000);
+    let start_str = start_value.to_rfc3339();
+    let end_str = end_value.to_rfc3339();
+    let host = resolve_host_filter(target_id.as_deref());
+    read_local_events_window(start_str.as_str(), end_str.as_str(), limit, host.as_deref())
+        .map_err(|error| command_error("storage", "Failed to read local events for window", error))
+}
+
+/// Crashes and events in one time-ordered list, so the timeline view needs no client-side merge.
+/// `filter` narrows the events; its own `start`/`end` are replaced by the requested window.
+#[tauri::command]
+fn get_timeline(
+    target_id: Option<String>,
+    start: String,
+    end: String,
+    filter: Option<nl_query::EventQueryFilter>,
+    include_crashes: Option<bool>,
+    limit: Option<u32>,
+) -> Result<db::RecordTimeline, String> {
+    let (start_value, end_value) = parse_timestamp_window(start.as_str(), end.as_str())
+        .map_err(|error| command_error("runtime", "Invalid timeline window", error))?;
+    let filter = nl_query::EventQueryFilter {
+        start: Some(start_value.to_rfc3339()),
+        end: Some(end_value.to_rfc3339()),
+        ..filter.unwrap_or_default()
+    };
+    let limit = limit.unwrap_or(10000).min(50000);
+    let host = resolve_host_filter(target_id.as_deref());
+    db::get_timeline(&filter, host.as_deref(), include_crashes.unwrap_or(true), limit)
+        .map_err(|error| command_error("storage", "Failed to read timeline", error))
+}
+
+#[tauri::command]
+fn get_event_histogram(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+    bucket: Option<String>,
+) -> Result<Vec<EventHistogramBucket>, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid histogram range", error))?;
+    let bucket = bucket.unwrap_or_else(|| "hour".to_string());
+    let buckets_per_day = histogram_buckets_per_day(bucket.as_str()).ok_or_else(|| {
+        command_error(
+            "runtime",
+            "Invalid histogram bucket",
+            format!("Unsupported bucket '{bucket}' (expected 'hour' or 'day')."),
+        )
+    })?;
+    let start_str = start.to_rfc3339();
+    let end_str = end.to_rfc3339();
+    let host = resolve_host_filter(target_id.as_deref());
+    read_event_histogram(start_str.as_str(), end_str.as_str(), buckets_per_day, host.as_deref())
+        .map_err(|error| command_error("storage", "Failed to build event histogram", error))
+}
+
+#[tauri::command]
+async fn analyze_power_events(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<power::PowerAnalysisResult, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid power analysis range", error))?;
+    let profile = resolve_target_profile(target_id.as_deref());
+    let is_local = profile.is_none();
+    let host = profile
         .map(|p| p.host)
         .unwrap_or_else(|| "localhost".to_string());
-    read_crashes(limit, Some(&host))
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let start_str = start.to_rfc3339();
+        let end_str = end.to_rfc3339();
+        let events =
+            read_local_events_range(start_str.as_str(), end_str.as_str(), 50000, Some(&host))
+                .map_err(|error| {
+                    command_error("storage", "Failed to read events for power analysis", error)
+                })?;
+        let crashes = read_crashes(5000, Some(&host))
+            .map_err(|error| command_error("storage", "Failed to read crashes for power analysis", error))?;
+        let battery = if is_local {
+            power::read_battery_health()
+        } else {
+            None
+        };
+        Ok(power::analyze_power_events(
+            events.as_slice(),
+            crashes.as_slice(),
+            battery,
+        ))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join power analysis task",
+            error.to_string(),
+        )
+    })?
+}
+
+fn timestamp_in_range(value: &str, start: &DateTime<Utc>, end: &DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(value)
+        .map(|parsed| {
+            let parsed = parsed.with_timezone(&Utc);
+            parsed >= *start && parsed <= *end
+        })
+        .unwrap_or(false)
+}
+
+/// Reads everything a timeline needs for one host and range. The events are returned too so
+/// callers can reuse them without a second query.
+fn collect_timeline(
+    host: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<(Vec<NormalizedEvent>, timeline::Timeline), String> {
+    let start_str = start.to_rfc3339();
+    let end_str = end.to_rfc3339();
+    let events = read_local_events_range(start_str.as_str(), end_str.as_str(), 50000, Some(host))
+        .map_err(|error| command_error("storage", "Failed to read events for timeline", error))?;
+    let crashes = read_crashes(5000, Some(host))
+        .map_err(|error| command_error("storage", "Failed to read crashes for timeline", error))?;
+    let bookmarks = get_bookmarked_events_range(start_str.as_str(), end_str.as_str(), host)
+        .map_err(|error| command_error("storage", "Failed to read bookmarks for timeline", error))?;
+    let crashes_in_range: Vec<CrashRecord> = crashes
+        .iter()
+        .filter(|crash| timestamp_in_range(crash.timestamp.as_str(), &start, &end))
+        .cloned()
+        .collect();
+    let anomalies = anomaly::detect_anomalies(
+        events.as_slice(),
+        start,
+        end,
+        anomaly::DEFAULT_BUCKET_MINUTES,
+    );
+    let power_incidents = power::classify_power_incidents(events.as_slice(), crashes.as_slice());
+
+    let timeline = timeline::build_timeline(
+        start_str.as_str(),
+        end_str.as_str(),
+        host,
+        timeline::TimelineSources {
+            events: events.as_slice(),
+            crashes: crashes_in_range.as_slice(),
+            anomalies: anomalies.windows.as_slice(),
+            power_incidents: power_incidents.as_slice(),
+            bookmarks: bookmarks.as_slice(),
+        },
+    );
+    Ok((events, timeline))
+}
+
+#[tauri::command]
+async fn build_timeline(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<timeline::Timeline, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid timeline range", error))?;
+    let host = resolve_target_host(target_id.as_deref());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        collect_timeline(host.as_str(), start, end).map(|(_, timeline)| timeline)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join timeline task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IncidentReportRequest {
+    target_id: Option<String>,
+    /// Reports on the crash and its surrounding window when set; otherwise on `from`..`to`.
+    crash_id: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    window_minutes: Option<i64>,
+    analysis: Option<report::ReportAnalysis>,
+}
+
+fn build_incident_report_sync(request: IncidentReportRequest) -> Result<String, String> {
+    let timezone = load_display_timezone();
+    let generated_at = timezone.convert(Utc::now()).to_rfc3339();
+    let analysis = request.analysis.as_ref();
+
+    if let Some(crash_id) = request.crash_id.as_deref() {
+        let window = request.window_minutes.unwrap_or(15).clamp(1, 180);
+        let (crash, related) = load_crash_with_related_events(crash_id, window, 50)?;
+        let crashed_at = timestamps::parse_timestamp(crash.timestamp.as_str())
+            .ok_or_else(|| "Selected crash has no usable timestamp.".to_string())?;
+        let start = crashed_at - chrono::Duration::minutes(window);
+        let end = crashed_at + chrono::Duration::minutes(window);
+        let (events, timeline) = collect_timeline(crash.source_host.as_str(), start, end)?;
+        return Ok(report::render_incident_report(&report::IncidentReport {
+            title: format!("Crash Report: {}", crash.crash_type),
+            source_host: crash.source_host.as_str(),
+            start: timeline.start.as_str(),
+            end: timeline.end.as_str(),
+            generated_at,
+            timezone,
+            crash: Some(&crash),
+            events: events.as_slice(),
+            timeline: &timeline,
+            correlated: related.as_slice(),
+            analysis,
+        }));
+    }
+
+    let (Some(from), Some(to)) = (request.from.as_deref(), request.to.as_deref()) else {
+        return Err("A crash or a date range is required for a report.".to_string());
+    };
+    let (start, end) = parse_local_date_range(from, to)
+        .map_err(|error| command_error("runtime", "Invalid report range", error))?;
+    let host = resolve_target_host(request.target_id.as_deref());
+    let (events, timeline) = collect_timeline(host.as_str(), start, end)?;
+    Ok(report::render_incident_report(&report::IncidentReport {
+        title: format!("Incident Report: {from} to {to}"),
+        source_host: host.as_str(),
+        start: timeline.start.as_str(),
+        end: timeline.end.as_str(),
+        generated_at,
+        timezone,
+        crash: None,
+        events: events.as_slice(),
+        timeline: &timeline,
+        correlated: &[],
+        analysis,
+    }))
+}
+
+#[tauri::command]
+async fn build_incident_report(request: IncidentReportRequest) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || build_incident_report_sync(request))
+        .await
+        .map_err(|error| {
+            command_error(
+                "runtime",
+                "Failed to join incident report task",
+                error.to_string(),
+            )
+        })?
+}
+
+#[tauri::command]
+fn bookmark_event(event_id: String, note: Option<String>) -> Result<(), String> {
+    if event_id.trim().is_empty() {
+        return Err("Event id is required.".to_string());
+    }
+    let note = note
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    save_event_bookmark(event_id.trim(), note.as_deref(), Utc::now().to_rfc3339().as_str())
+        .map_err(|error| command_error("storage", "Failed to bookmark event", error))
+}
+
+#[tauri::command]
+fn remove_event_bookmark(event_id: String) -> Result<bool, String> {
+    delete_event_bookmark(event_id.trim())
+        .map_err(|error| command_error("storage", "Failed to remove event bookmark", error))
+}
+
+/// The original rendered XML or JSON line behind an event, when its collector kept one.
+#[tauri::command]
+fn get_event_raw(event_id: String) -> Result<Option<String>, String> {
+    db::get_event_raw(event_id.trim())
+        .map_err(|error| command_error("storage", "Failed to read raw event payload", error))
+}
+
+#[tauri::command]
+fn get_event_bookmarks() -> Result<Vec<EventBookmark>, String> {
+    read_event_bookmarks()
+        .map_err(|error| command_error("storage", "Failed to read event bookmarks", error))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuerySessionExport {
+    session: QuerySession,
+    steps: Vec<QueryStep>,
+    markdown: String,
+}
+
+fn close_query_session(session_id: &str) -> Result<Option<QuerySession>, String> {
+    let Some(mut session) = get_query_session(session_id)? else {
+        return Ok(None);
+    };
+    if session.ended_at.is_none() {
+        session.ended_at = Some(Utc::now().to_rfc3339());
+        save_query_session(&session)?;
+    }
+    Ok(Some(session))
+}
+
+/// Starts recording query steps under a new session, closing any session still recording.
+#[tauri::command]
+fn start_query_recording(name: Option<String>) -> Result<QuerySession, String> {
+    let started_at = Utc::now();
+    let name = name
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| {
+            format!(
+                "Investigation {}",
+                started_at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            )
+        });
+    let session = QuerySession {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        started_at: started_at.to_rfc3339(),
+        ended_at: None,
+        step_count: 0,
+    };
+    save_query_session(&session)
+        .map_err(|error| command_error("storage", "Failed to start query recording", error))?;
+    if let Some(previous) = query_sessions::set_active_session(Some(session.id.clone())) {
+        close_query_session(previous.as_str())
+            .map_err(|error| command_error("storage", "Failed to close query session", error))?;
+    }
+    Ok(session)
+}
+
+#[tauri::command]
+fn stop_query_recording() -> Result<Option<QuerySession>, String> {
+    let Some(session_id) = query_sessions::set_active_session(None) else {
+        return Ok(None);
+    };
+    close_query_session(session_id.as_str())
+        .map_err(|error| command_error("storage", "Failed to stop query recording", error))
+}
+
+#[tauri::command]
+fn get_active_query_session() -> Result<Option<QuerySession>, String> {
+    match query_sessions::active_session_id() {
+        Some(session_id) => get_query_session(session_id.as_str())
+            .map_err(|error| command_error("storage", "Failed to read query session", error)),
+        None => Ok(None),
+    }
+}
+
+/// Records one query or filter change. Only the parameters are stored, never the matching data;
+/// returns false without storing anything when recording is off.
+#[tauri::command]
+fn record_query_step(action: String, params: Option<Value>) -> Result<bool, String> {
+    let Some(session_id) = query_sessions::active_session_id() else {
+        return Ok(false);
+    };
+    if action.trim().is_empty() {
+        return Err("Query step action is required.".to_string());
+    }
+    append_query_step(
+        session_id.as_str(),
+        Utc::now().to_rfc3339().as_str(),
+        action.trim(),
+        &params.unwrap_or(Value::Null),
+    )
+    .map_err(|error| command_error("storage", "Failed to record query step", error))?;
+    Ok(true)
+}
+
+#[tauri::command]
+fn list_query_sessions() -> Result<Vec<QuerySession>, String> {
+    get_query_sessions()
+        .map_err(|error| command_error("storage", "Failed to read query sessions", error))
+}
+
+#[tauri::command]
+fn export_query_session(session_id: String) -> Result<QuerySessionExport, String> {
+    let session = get_query_session(session_id.trim())
+        .map_err(|error| command_error("storage", "Failed to read query session", error))?
+        .ok_or_else(|| "Query session not found.".to_string())?;
+    let steps = get_query_steps(session.id.as_str())
+        .map_err(|error| command_error("storage", "Failed to read query steps", error))?;
+    let markdown = query_sessions::render_session_markdown(&session, steps.as_slice());
+    Ok(QuerySessionExport {
+        session,
+        steps,
+        markdown,
+    })
+}
+
+#[tauri::command]
+fn delete_query_recording(session_id: String) -> Result<bool, String> {
+    if query_sessions::active_session_id().as_deref() == Some(session_id.trim()) {
+        query_sessions::set_active_session(None);
+    }
+    delete_query_session(session_id.trim())
+        .map_err(|error| command_error("storage", "Failed to delete query session", error))
+}
+
+#[tauri::command]
+async fn analyze_boot_performance(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<boot::BootPerformanceResult, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid boot analysis range", error))?;
+    let host = resolve_host_filter(target_id.as_deref());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let start_str = start.to_rfc3339();
+        let end_str = end.to_rfc3339();
+        let events =
+            read_local_events_range(start_str.as_str(), end_str.as_str(), 50000, host.as_deref())
+                .map_err(|error| {
+                    command_error("storage", "Failed to read events for boot analysis", error)
+                })?;
+        Ok(boot::analyze_boot_performance(events.as_slice()))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join boot analysis task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn detect_anomalies(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+    bucket_minutes: Option<i64>,
+) -> Result<anomaly::AnomalyReport, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid anomaly detection range", error))?;
+    let host = resolve_host_filter(target_id.as_deref());
+    let bucket_minutes = bucket_minutes.unwrap_or(anomaly::DEFAULT_BUCKET_MINUTES);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let events = read_local_events_range(
+            start.to_rfc3339().as_str(),
+            end.to_rfc3339().as_str(),
+            50000,
+            host.as_deref(),
+        )
+        .map_err(|error| command_error("storage", "Failed to read events for anomaly detection", error))?;
+        Ok(anomaly::detect_anomalies(events.as_slice(), start, end, bucket_minutes))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join anomaly detection task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn get_log_patterns(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+    limit: Option<u32>,
+) -> Result<Vec<patterns::LogPattern>, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid log pattern range", error))?;
+    let host = resolve_host_filter(target_id.as_deref());
+    let limit = limit.unwrap_or(200).min(2000) as usize;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let events = read_local_events_range(
+            start.to_rfc3339().as_str(),
+            end.to_rfc3339().as_str(),
+            50000,
+            host.as_deref(),
+        )
+        .map_err(|error| command_error("storage", "Failed to read events for log patterns", error))?;
+        Ok(patterns::mine_log_patterns(events.as_slice(), limit))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join log pattern task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn list_event_publishers(filter: Option<String>) -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::logs::publishers::list_publishers(filter.as_deref())
+            .map_err(|error| command_error("collector", "Failed to list event publishers", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join event publisher task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn get_publisher_metadata(
+    provider: String,
+    event_id: Option<u32>,
+) -> Result<crate::logs::publishers::PublisherMetadata, String> {
+    if provider.trim().is_empty() {
+        return Err("Provider name is required.".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::logs::publishers::publisher_metadata(provider.as_str(), event_id).map_err(|error| {
+            command_error("collector", "Failed to read publisher metadata", error)
+        })
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join publisher metadata task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn analyze_app_hangs(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<hangs::AppHangReport, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid app hang range", error))?;
+    let host = resolve_host_filter(target_id.as_deref());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let start_str = start.to_rfc3339();
+        let end_str = end.to_rfc3339();
+        let events =
+            read_local_events_range(start_str.as_str(), end_str.as_str(), 50000, host.as_deref())
+                .map_err(|error| {
+                    command_error("storage", "Failed to read events for app hang analysis", error)
+                })?;
+        let crashes: Vec<CrashRecord> = read_crashes(5000, host.as_deref())
+            .map_err(|error| {
+                command_error("storage", "Failed to read crashes for app hang analysis", error)
+            })?
+            .into_iter()
+            .filter(|crash| {
+                crash.timestamp.as_str() >= start_str.as_str()
+                    && crash.timestamp.as_str() <= end_str.as_str()
+            })
+            .collect();
+        let mut report = hangs::analyze_app_hangs(events.as_slice(), crashes.as_slice());
+        if let Err(error) = hangs::attach_load_peaks(&mut report) {
+            report
+                .warnings
+                .push(format!("CPU and disk snapshots could not be read: {error}"));
+        }
+        Ok(report)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join app hang analysis task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn get_print_issues(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<logs::printing::PrintIssueSummary, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid print issue range", error))?;
+    let host = resolve_host_filter(target_id.as_deref());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let events = read_local_events_range(
+            start.to_rfc3339().as_str(),
+            end.to_rfc3339().as_str(),
+            50000,
+            host.as_deref(),
+        )
+        .map_err(|error| command_error("storage", "Failed to read events for print issues", error))?;
+        Ok(logs::printing::summarize_print_events(events.as_slice()))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join print issue task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn analyze_remote_access(
+    target_id: Option<String>,
+    from: String,
+    to: String,
+) -> Result<remote_access::RemoteAccessReport, String> {
+    let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
+        .map_err(|error| command_error("runtime", "Invalid remote access range", error))?;
+    let host = resolve_host_filter(target_id.as_deref());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let events = read_local_events_range(
+            start.to_rfc3339().as_str(),
+            end.to_rfc3339().as_str(),
+            50000,
+            host.as_deref(),
+        )
+        .map_err(|error| {
+            command_error("storage", "Failed to read events for remote access audit", error)
+        })?;
+        Ok(remote_access::analyze_remote_access(events.as_slice()))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join remote access audit task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn import_host_crashes(
+    _target_id: Option<String>,
+    limit: Option<u32>,
+    since: Option<String>,
+) -> Result<usize, String> {
+    let max = limit.unwrap_or(200).clamp(1, 2000) as usize;
+    let since = match since.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => Some(
+            DateTime::parse_from_rfc3339(value)
+                .map_err(|_| "Invalid crash import start (expected RFC3339).".to_string())?
+                .with_timezone(&Utc),
+        ),
+        None => None,
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let crashes = collect_host_crashes(max, since)
+            .map_err(|error| command_error("collector", "Crash import failed", error))?;
+        if crashes.is_empty() {
+            return Ok::<usize, String>(0);
+        }
+        save_crashes(&crashes)
+            .map_err(|error| command_error("storage", "Failed to save imported crashes", error))?;
+        Ok(crashes.len())
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join crash import task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+fn export_crashes(
+    format: String,
+    filter: Option<crash_export::CrashExportFilter>,
+    target_id: Option<String>,
+) -> Result<Option<String>, String> {
+    let output_format = format.to_ascii_lowercase();
+    let (extension, filter_name): (&str, &str) = match output_format.as_str() {
+        "json" => ("json", "JSON"),
+        "csv" => ("csv", "CSV"),
+        _ => return Err("Unsupported crash export format.".to_string()),
+    };
+    let filter = filter.unwrap_or_default();
+    let host = resolve_host_filter(target_id.as_deref());
+    let crashes: Vec<CrashRecord> = read_crashes(u32::MAX, host.as_deref())
+        .map_err(|error| command_error("storage", "Failed to read crashes for export", error))?
+        .into_iter()
+        .filter(|crash| crash_export::matches_filter(&filter, crash))
+        .collect();
+    if crashes.is_empty() {
+        return Err("No crashes match the export filter.".to_string());
+    }
+    let timezone = load_display_timezone();
+    let payload = crash_export::build_crash_export(extension, crashes.as_slice(), timezone)
+        .map_err(|error| command_error("crash", "Failed to build crash export", error))?;
+
+    let suggested = format!("hermes-crashes-{}", Local::now().format("%Y%m%d-%H%M%S"));
+    let safe_name = sanitize_filename(suggested.as_str(), extension);
+    let mut dialog = rfd::FileDialog::new()
+        .set_file_name(safe_name.as_str())
+        .add_filter(filter_name, &[extension]);
+    if let Some(base_dir) = load_export_dir()
+        .map(PathBuf::from)
+        .or_else(dirs::download_dir)
+        .filter(|path| path.exists() && path.is_dir())
+    {
+        dialog = dialog.set_directory(base_dir);
+    }
+    let Some(output_path) = dialog.save_file() else {
+        return Ok(None);
+    };
+
+    std::fs::write(&output_path, payload).map_err(|error| {
+        command_error("storage", "Failed to write crash export", error.to_string())
+    })?;
+    Ok(Some(output_path.to_string_lossy().to_string()))
+}
+
+#[tauri::command]
+async fn import_crashes_file(path: String) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let raw = std::fs::read_to_string(path.as_str()).map_err(|error| {
+            command_error("storage", "Failed to read crash export", error.to_string())
+        })?;
+        let crashes = crash_export::parse_crash_export(raw.as_str())
+            .map_err(|error| command_error("crash", "Failed to parse crash export", error))?;
+        if crashes.is_empty() {
+            return Ok(0);
+        }
+        save_crashes(&crashes)
+            .map_err(|error| command_error("storage", "Failed to save imported crashes", error))?;
+        Ok(crashes.len())
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join crash file import task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Loads a CSV, JSON or NDJSON event export (optionally gzip or zip compressed) from another
+/// install. Events keep their original host and are flagged as imported.
+#[tauri::command]
+async fn import_events_file(path: String) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let raw = event_export::read_export_file(Path::new(path.as_str()))
+            .map_err(|error| command_error("storage", "Failed to read event export", error))?;
+        let events = event_export::parse_event_import(raw.as_str())
+            .map_err(|error| command_error("storage", "Failed to parse event export", error))?;
+        if events.is_empty() {
+            return Ok(0);
+        }
+        save_local_events(&events)
+            .map_err(|error| command_error("storage", "Failed to save imported events", error))?;
+        Ok(events.len())
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join event file import task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Merges an `events.db` brought back from another machine. That machine's own rows are tagged
+/// with `host`, which defaults to the file name, or its folder name for a plain `events.db`.
+#[tauri::command]
+async fn import_database(
+    path: String,
+    host: Option<String>,
+) -> Result<db::DatabaseImportSummary, String> {
+    let path = PathBuf::from(path);
+    let host = host
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(|| {
+            let stem = path.file_stem()?.to_string_lossy().to_string();
+            if stem.eq_ignore_ascii_case("events") {
+                path.parent()?.file_name().map(|name| name.to_string_lossy().to_string())
+            } else {
+                Some(stem)
+            }
+        })
+        .unwrap_or_else(|| "imported".to_string());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        db::import_database(path.as_path(), host.as_str())
+            .map_err(|error| command_error("storage", "Failed to import database", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join database import task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogImportResult {
+    path: String,
+    #[serde(flatten)]
+    summary: log_import::LogImportSummary,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogDirectoryImportResult {
+    path: String,
+    files: Vec<LogImportResult>,
+    /// Files that could not be read; the rest of the directory is still imported.
+    errors: Vec<String>,
+}
+
+fn log_import_options(path: &Path, target_id: Option<&str>) -> log_import::LogImportOptions {
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+    let os = match resolve_target_profile(target_id).map(|profile| profile.os.to_ascii_lowercase())
+    {
+        Some(os) if os == "windows" => logs::SupportedOs::Windows,
+        Some(os) if os == "macos" => logs::SupportedOs::Macos,
+        Some(_) => logs::SupportedOs::Linux,
+        None => detect_host_os(),
+    };
+    log_import::LogImportOptions {
+        log_name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string()),
+        source_host: resolve_target_host(target_id),
+        os,
+        fallback_timestamp: modified,
+        context: timestamps::TimestampContext::local(),
+    }
+}
+
+/// Imports a text log file (syslog, JSON lines, access logs or timestamped plain text, plain
+/// or gzip-compressed) as events of the given target. Without `profile_id` the format is
+/// detected from the first lines, trying the saved parser profiles before the built-in formats.
+#[tauri::command]
+async fn import_log_file(
+    path: String,
+    target_id: Option<String>,
+    profile_id: Option<String>,
+) -> Result<LogImportResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        import_log_file_sync(path, target_id.as_deref(), profile_id.as_deref())
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join log file import task",
+            error.to_string(),
+        )
+    })?
+}
+
+fn import_log_file_sync(
+    path: String,
+    target_id: Option<&str>,
+    profile_id: Option<&str>,
+) -> Result<LogImportResult, String> {
+    let (parser, candidates) =
+        log_import::parser_choice(crate::settings::load_parser_profiles().profiles, profile_id)?;
+    let file = Path::new(path.as_str());
+    let options = log_import_options(file, target_id);
+    let mut imported = 0;
+    let summary = log_import::import_log_path(file, 0, parser, candidates, &options, |batch| {
+        imported += batch.len();
+        logs::progress::report(options.log_name.as_str(), imported);
+        save_local_events(batch.as_slice())
+    })
+    .map_err(|error| command_error("storage", "Failed to import log file", error))?;
+    Ok(LogImportResult { path, summary })
+}
+
+/// Imports every log file in a directory, oldest rotation first. Each file's read position is
+/// saved against a fingerprint of its content, so running the import again only reads lines
+/// added since, even after the files have been rotated or compressed.
+#[tauri::command]
+async fn import_log_directory(
+    path: String,
+    target_id: Option<String>,
+    profile_id: Option<String>,
+) -> Result<LogDirectoryImportResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        import_log_directory_sync(path, target_id.as_deref(), profile_id.as_deref())
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join log directory import task",
+            error.to_string(),
+        )
+    })?
+}
+
+fn import_log_directory_sync(
+    path: String,
+    target_id: Option<&str>,
+    profile_id: Option<&str>,
+) -> Result<LogDirectoryImportResult, String> {
+    let (parser, candidates) =
+        log_import::parser_choice(crate::settings::load_parser_profiles().profiles, profile_id)?;
+    let files = log_import::list_log_directory(Path::new(path.as_str()))
+        .map_err(|error| command_error("storage", "Failed to list log directory", error))?;
+    let mut result = LogDirectoryImportResult {
+        path,
+        files: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut total = 0;
+    for file in files {
+        let options = log_import_options(file.as_path(), target_id);
+        let file_path = file.display().to_string();
+        logs::progress::report(options.log_name.as_str(), total);
+        let imported = log_import::file_fingerprint(file.as_path()).and_then(|fingerprint| {
+            let offset = db::get_import_offset(fingerprint.as_str(), options.source_host.as_str())?
+                .unwrap_or(0);
+            let summary = log_import::import_log_path(
+                file.as_path(),
+                offset,
+                parser.clone(),
+                candidates.clone(),
+                &options,
+                |batch| {
+                    total += batch.len();
+                    logs::progress::report(options.log_name.as_str(), total);
+                    save_local_events(batch.as_slice())
+                },
+            )?;
+            db::save_import_offset(
+                fingerprint.as_str(),
+                options.source_host.as_str(),
+                file_path.as_str(),
+                summary.end_offset,
+            )?;
+            Ok(summary)
+        });
+        match imported {
+            Ok(summary) => result.files.push(LogImportResult {
+                path: file_path,
+                summary,
+            }),
+            Err(error) => {
+                diagnostics::warn("import", format!("Skipped {file_path}: {error}"));
+                result.errors.push(format!("{file_path}: {error}"));
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Starts a log import in the background and returns its job ID. A directory is imported like
+/// `import_log_directory`, a file like `import_log_file`; progress and the result arrive as
+/// `hla://job-progress` events.
+#[tauri::command]
+fn start_log_import_job(
+    app: AppHandle,
+    path: String,
+    target_id: Option<String>,
+    profile_id: Option<String>,
+) -> String {
+    let job = jobs::Job::start(app, "import");
+    let job_id = job.id().to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        if Path::new(path.as_str()).is_dir() {
+            let result = job.run(|| {
+                import_log_directory_sync(path, target_id.as_deref(), profile_id.as_deref())
+            });
+            job.finish(&result);
+        } else {
+            let result =
+                job.run(|| import_log_file_sync(path, target_id.as_deref(), profile_id.as_deref()));
+            job.finish(&result);
+        }
+    });
+    job_id
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CaseImportResult {
+    crash_id: String,
+    event_count: usize,
+    bookmark_count: usize,
+    notes: Option<String>,
+    analysis: Option<report::ReportAnalysis>,
+    raw_file_path: Option<String>,
+}
+
+#[tauri::command]
+fn export_case(
+    crash_id: String,
+    notes: Option<String>,
+    analysis: Option<report::ReportAnalysis>,
+) -> Result<Option<String>, String> {
+    let (crash, related) = load_crash_with_related_events(crash_id.as_str(), 15, 500)?;
+    let events: Vec<NormalizedEvent> = related.into_iter().map(|scored| scored.event).collect();
+    let bookmarks = read_event_bookmarks()
+        .map_err(|error| command_error("storage", "Failed to read bookmarks for case", error))?
+        .into_iter()
+        .filter(|bookmark| events.iter().any(|event| event.id == bookmark.event_id))
+        .collect();
+    let case_notes = case::CaseNotes {
+        notes: notes.filter(|value| !value.trim().is_empty()),
+        bookmarks,
+        analysis,
+    };
+
+    let suggested = format!("hermes-case-{}", Local::now().format("%Y%m%d-%H%M%S"));
+    let safe_name = sanitize_filename(suggested.as_str(), case::CASE_EXTENSION);
+    let mut dialog = rfd::FileDialog::new()
+        .set_file_name(safe_name.as_str())
+        .add_filter("Hermes Case", &[case::CASE_EXTENSION]);
+    if let Some(base_dir) = load_export_dir()
+        .map(PathBuf::from)
+        .or_else(dirs::download_dir)
+        .filter(|path| path.exists() && path.is_dir())
+    {
+        dialog = dialog.set_directory(base_dir);
+    }
+    let Some(output_path) = dialog.save_file() else {
+        return Ok(None);
+    };
+
+    let file = std::fs::File::create(&output_path).map_err(|error| {
+        command_error("storage", "Failed to create case bundle", error.to_string())
+    })?;
+    if let Err(error) = case::write_case(file, &crash, events.as_slice(), &case_notes) {
+        let _ = std::fs::remove_file(&output_path);
+        return Err(command_error("crash", "Failed to write case bundle", error));
+    }
+    Ok(Some(output_path.to_string_lossy().to_string()))
+}
+
+#[tauri::command]
+async fn import_case(path: String) -> Result<CaseImportResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = std::fs::File::open(path.as_str()).map_err(|error| {
+            command_error("storage", "Failed to open case bundle", error.to_string())
+        })?;
+        let cases_dir = case::cases_dir()
+            .map_err(|error| command_error("storage", "Failed to prepare cases folder", error))?;
+        let imported = case::read_case(file, cases_dir.as_path())
+            .map_err(|error| command_error("crash", "Failed to read case bundle", error))?;
+
+        save_crashes(std::slice::from_ref(&imported.crash))
+            .map_err(|error| command_error("storage", "Failed to save case crash", error))?;
+        save_local_events(imported.events.as_slice())
+            .map_err(|error| command_error("storage", "Failed to save case events", error))?;
+        for bookmark in &imported.notes.bookmarks {
+            save_event_bookmark(
+                bookmark.event_id.as_str(),
+                bookmark.note.as_deref(),
+                bookmark.created_at.as_str(),
+            )
+            .map_err(|error| command_error("storage", "Failed to save case bookmark", error))?;
+        }
+
+        Ok(CaseImportResult {
+            crash_id: imported.crash.id,
+            event_count: imported.events.len(),
+            bookmark_count: imported.notes.bookmarks.len(),
+            notes: imported.notes.notes,
+            analysis: imported.notes.analysis,
+            raw_file_path: imported.crash.raw_path,
+        })
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join case import task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+fn get_crashes(target_id: Option<String>, limit: Option<u32>) -> Result<Vec<CrashRecord>, String> {
+    let limit = limit.unwrap_or(250).min(5000);
+    let host = resolve_host_filter(target_id.as_deref());
+    read_crashes(limit, host.as_deref())
         .map_err(|error| command_error("storage", "Failed to read crashes", error))
 }
 
+/// Hosts with stored data. Each can be selected with a `host:<name>` target id.
+#[tauri::command]
+fn get_source_hosts() -> Result<Vec<db::SourceHostSummary>, String> {
+    db::get_source_hosts()
+        .map_err(|error| command_error("storage", "Failed to read source hosts", error))
+}
+
 #[tauri::command]
 fn analyze_minidump(
     crash_id: String,
@@ -1673,34 +3688,258 @@ fn analyze_minidump(
     result.map_err(|error| command_error("crash", "Failed to analyze minidump", error))
 }
 
+#[tauri::command]
+fn explain_crash_code(code: String) -> Result<bugcheck::CrashCodeExplanation, String> {
+    if code.trim().is_empty() {
+        return Err(command_error(
+            "crash",
+            "Invalid crash code",
+            "Crash code cannot be empty.",
+        ));
+    }
+    Ok(bugcheck::explain_crash_code(code.as_str()))
+}
+
 #[tauri::command]
 fn cleanup_local_duplicate_events() -> Result<usize, String> {
     cleanup_duplicate_events()
         .map_err(|error| command_error("storage", "Failed to clean up duplicate events", error))
 }
 
+fn load_crash_with_related_events(
+    crash_id: &str,
+    window: i64,
+    max_events: u32,
+) -> Result<(CrashRecord, Vec<correlation::ScoredEvent>), String> {
+    let crash = get_crash_by_id(crash_id)
+        .map_err(|error| command_error("storage", "Failed to load crash for correlation", error))?
+        .ok_or_else(|| "Selected crash was not found.".to_string())?;
+    // Pull a wider candidate set than requested so ranking is not limited to the nearest events.
+    let candidates = correlate_crash_events(crash_id, window, (max_events * 4).max(500))
+        .map_err(|error| command_error("storage", "Failed to correlate crash events", error))?;
+    let mut scored = correlation::score_related_events(&crash, candidates, window);
+    scored.truncate(max_events as usize);
+    Ok((crash, scored))
+}
+
 #[tauri::command]
 fn get_crash_related_events(
     crash_id: String,
     window_minutes: Option<i64>,
     limit: Option<u32>,
-) -> Result<Vec<NormalizedEvent>, String> {
+) -> Result<Vec<correlation::ScoredEvent>, String> {
     let window = window_minutes.unwrap_or(15).clamp(1, 180);
     let max_events = limit.unwrap_or(200).min(2000);
-    correlate_crash_events(crash_id.as_str(), window, max_events)
-        .map_err(|error| command_error("storage", "Failed to correlate crash events", error))
+    load_crash_with_related_events(crash_id.as_str(), window, max_events)
+        .map(|(_, scored)| scored)
+}
+
+#[tauri::command]
+fn get_ingest_window_days() -> u32 {
+    load_ingest_window_days()
+}
+
+#[tauri::command]
+fn set_ingest_window_days(app: AppHandle, days: u32) -> Result<u32, String> {
+    save_ingest_window_days(days)
+        .map_err(|error| command_error("settings", "Failed to save ingest window", error))?;
+    broadcast_settings_changed(&app, settings::INGEST_DAYS_SECTION);
+    Ok(load_ingest_window_days())
+}
+
+/// Reports database usage against the configured limit and the degradation that the next sync
+/// would apply.
+#[tauri::command]
+fn get_storage_quota_status() -> quota::QuotaPlan {
+    storage_quota_plan(load_ingest_profile().max_database_mb)
+}
+
+/// Applies the retention policy immediately instead of waiting for the hourly run.
+#[tauri::command]
+async fn run_retention_now() -> Result<retention::RetentionReport, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        retention::apply_retention(&load_ingest_profile(), Utc::now())
+            .map_err(|error| command_error("storage", "Failed to apply retention policy", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join retention task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Archived days between `start` and `end`, at most `archive::MAX_SEARCH_DAYS` apart, holding
+/// events that match `query`, for choosing which days to rehydrate.
+#[tauri::command]
+async fn search_archive(
+    query: String,
+    start: String,
+    end: String,
+) -> Result<archive::ArchiveSearchReport, String> {
+    let start = archive::parse_day(start.as_str())?;
+    let end = archive::parse_day(end.as_str())?;
+    let retention = load_ingest_profile().retention;
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = archive_store::ArchiveStore::configured(&retention)?;
+        archive::search_archive(&store, query.as_str(), start, end)
+            .map_err(|error| command_error("storage", "Archive search failed", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join archive search task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Loads the archived events of `days` (`YYYY-MM-DD`) back into the database, where retention
+/// holds them for a week.
+#[tauri::command]
+async fn rehydrate_archive_days(days: Vec<String>) -> Result<archive::RehydrateReport, String> {
+    let retention = load_ingest_profile().retention;
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = archive_store::ArchiveStore::configured(&retention)?;
+        archive::rehydrate_days(&store, days.as_slice(), Utc::now())
+            .map_err(|error| command_error("storage", "Failed to rehydrate archived days", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join archive rehydrate task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn get_db_stats() -> Result<db::DatabaseStats, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        db::get_database_stats()
+            .map_err(|error| command_error("storage", "Failed to read database statistics", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join database statistics task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Rewrites the database file without its free pages. Refused while a sync is writing, since
+/// `VACUUM` needs the database to itself.
+#[tauri::command]
+async fn compact_database() -> Result<db::CompactionResult, String> {
+    if scheduler::sync_running() {
+        return Err(command_error(
+            "storage",
+            "Failed to compact database",
+            "A sync is still running; try again when it finishes.",
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(|| {
+        db::compact_database()
+            .map_err(|error| command_error("storage", "Failed to compact database", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join database compaction task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+fn get_encryption_status() -> Result<encryption::EncryptionStatus, String> {
+    encryption::encryption_status()
+        .map_err(|error| command_error("storage", "Failed to read encryption status", error))
+}
+
+/// Encrypts or decrypts `events.db` in place. Like compaction, it needs the database to itself.
+#[tauri::command]
+async fn set_database_encryption(enabled: bool) -> Result<encryption::EncryptionStatus, String> {
+    if scheduler::sync_running() {
+        return Err(command_error(
+            "storage",
+            "Failed to change database encryption",
+            "A sync is still running; try again when it finishes.",
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let status = if enabled {
+            encryption::enable_encryption()
+        } else {
+            encryption::disable_encryption()
+        };
+        status.map_err(|error| {
+            command_error("storage", "Failed to change database encryption", error)
+        })
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join database encryption task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+fn list_workspaces() -> WorkspaceSettings {
+    workspaces::list_workspaces()
+}
+
+#[tauri::command]
+fn create_workspace(name: String) -> Result<Workspace, String> {
+    workspaces::create_workspace(name.as_str())
+        .map_err(|error| command_error("storage", "Failed to create workspace", error))
+}
+
+/// Switches every later query and sync to another workspace's database.
+#[tauri::command]
+fn open_workspace(workspace_id: String) -> Result<WorkspaceSettings, String> {
+    if scheduler::sync_running() {
+        return Err(command_error(
+            "storage",
+            "Failed to open workspace",
+            "A sync is still running; try again when it finishes.",
+        ));
+    }
+    workspaces::open_workspace(workspace_id.as_str())
+        .map_err(|error| command_error("storage", "Failed to open workspace", error))
+}
+
+#[tauri::command]
+fn archive_workspace(workspace_id: String) -> Result<WorkspaceSettings, String> {
+    workspaces::archive_workspace(workspace_id.as_str())
+        .map_err(|error| command_error("storage", "Failed to archive workspace", error))
+}
+
+#[tauri::command]
+fn list_saved_filters() -> Vec<SavedFilter> {
+    saved_filters::list_saved_filters()
 }
 
 #[tauri::command]
-fn get_ingest_window_days() -> u32 {
-    load_ingest_window_days()
+fn save_saved_filter(filter: SavedFilter) -> Result<SavedFilter, String> {
+    saved_filters::save_saved_filter(filter)
+        .map_err(|error| command_error("settings", "Failed to save filter", error))
 }
 
 #[tauri::command]
-fn set_ingest_window_days(days: u32) -> Result<u32, String> {
-    save_ingest_window_days(days)
-        .map_err(|error| command_error("settings", "Failed to save ingest window", error))?;
-    Ok(load_ingest_window_days())
+fn delete_saved_filter(filter_id: String) -> Result<(), String> {
+    saved_filters::delete_saved_filter(filter_id.as_str())
+        .map_err(|error| command_error("settings", "Failed to delete saved filter", error))
 }
 
 #[tauri::command]
@@ -1709,30 +3948,37 @@ fn get_ingest_profile() -> IngestProfile {
 }
 
 #[tauri::command]
-fn set_ingest_profile(profile: IngestProfile) -> Result<IngestProfile, String> {
-    save_ingest_profile(profile)
-        .map_err(|error| command_error("settings", "Failed to save ingest profile", error))
+fn set_ingest_profile(app: AppHandle, profile: IngestProfile) -> Result<IngestProfile, String> {
+    let saved = save_ingest_profile(profile)
+        .map_err(|error| command_error("settings", "Failed to save ingest profile", error))?;
+    broadcast_settings_changed(&app, settings::INGEST_PROFILE_SECTION);
+    Ok(saved)
 }
 
-#[tauri::command]
-fn get_llm_settings() -> LlmSettings {
+/// Loads LLM settings, moving any API keys still stored in `settings.json` into the OS
+/// keychain. The file is only rewritten without them, and the migration recorded, once every
+/// key has been stored, so a keychain failure never loses a secret; the migration is retried on
+/// the next load and skipped after it succeeds.
+fn load_llm_settings() -> LlmSettings {
     let load_result = load_llm_settings_with_migration();
-    if !load_result.migrated_api_keys.is_empty() {
-        for secret in load_result.migrated_api_keys {
-            match set_profile_keychain_secret(secret.profile_id.as_str(), secret.api_key.as_str()) {
-                Ok(_) => {}
-                Err(error) => diagnostics::warn(
-                    "settings",
-                    format!(
-                        "Failed to migrate legacy API key for profile '{}': {error}",
-                        secret.profile_id
-                    ),
+    let mut keychain_failed = false;
+    for secret in load_result.migrated_api_keys.iter() {
+        if let Err(error) =
+            set_profile_keychain_secret(secret.profile_id.as_str(), secret.api_key.as_str())
+        {
+            keychain_failed = true;
+            diagnostics::warn(
+                "settings",
+                format!(
+                    "Failed to migrate plaintext API key for profile '{}': {error}",
+                    secret.profile_id
                 ),
-            }
+            );
         }
     }
 
-    if load_result.migrated_from_legacy {
+    let needs_rewrite = load_result.migrated_from_legacy || load_result.key_migration_pending;
+    if needs_rewrite && !keychain_failed {
         if let Err(error) = save_llm_settings(load_result.settings.clone()) {
             diagnostics::warn(
                 "settings",
@@ -1744,13 +3990,24 @@ fn get_llm_settings() -> LlmSettings {
 }
 
 #[tauri::command]
-fn set_llm_settings(settings: LlmSettings) -> Result<LlmSettings, String> {
-    save_llm_settings(settings)
-        .map_err(|error| command_error("settings", "Failed to save LLM settings", error))
+fn get_llm_settings() -> LlmSettings {
+    load_llm_settings()
+}
+
+#[tauri::command]
+fn set_llm_settings(app: AppHandle, settings: LlmSettings) -> Result<LlmSettings, String> {
+    let saved = save_llm_settings(settings)
+        .map_err(|error| command_error("settings", "Failed to save LLM settings", error))?;
+    broadcast_settings_changed(&app, settings::LLM_SETTINGS_SECTION);
+    Ok(saved)
 }
 
 #[tauri::command]
-fn set_llm_profile_api_key(profile_id: String, api_key: String) -> Result<LlmSettings, String> {
+fn set_llm_profile_api_key(
+    app: AppHandle,
+    profile_id: String,
+    api_key: String,
+) -> Result<LlmSettings, String> {
     let id = profile_id.trim().to_string();
     if id.is_empty() {
         return Err("Profile ID is required.".to_string());
@@ -1763,7 +4020,7 @@ fn set_llm_profile_api_key(profile_id: String, api_key: String) -> Result<LlmSet
         );
     }
 
-    let mut settings = load_llm_settings_with_migration().settings;
+    let mut settings = load_llm_settings();
     let Some(profile) = settings
         .profiles
         .iter_mut()
@@ -1774,18 +4031,21 @@ fn set_llm_profile_api_key(profile_id: String, api_key: String) -> Result<LlmSet
     set_profile_keychain_secret(id.as_str(), key.as_str())
         .map_err(|error| command_error("settings", "Failed to save profile API key", error))?;
     profile.api_key_configured = true;
-    save_llm_settings(settings)
-        .map_err(|error| command_error("settings", "Failed to persist profile key status", error))
+    let saved = save_llm_settings(settings).map_err(|error| {
+        command_error("settings", "Failed to persist profile key status", error)
+    })?;
+    broadcast_settings_changed(&app, settings::LLM_SETTINGS_SECTION);
+    Ok(saved)
 }
 
 #[tauri::command]
-fn clear_llm_profile_api_key(profile_id: String) -> Result<LlmSettings, String> {
+fn clear_llm_profile_api_key(app: AppHandle, profile_id: String) -> Result<LlmSettings, String> {
     let id = profile_id.trim().to_string();
     if id.is_empty() {
         return Err("Profile ID is required.".to_string());
     }
 
-    let mut settings = load_llm_settings_with_migration().settings;
+    let mut settings = load_llm_settings();
     let Some(profile) = settings
         .profiles
         .iter_mut()
@@ -1796,8 +4056,11 @@ fn clear_llm_profile_api_key(profile_id: String) -> Result<LlmSettings, String>
     clear_profile_keychain_secret(id.as_str())
         .map_err(|error| command_error("settings", "Failed to clear profile API key", error))?;
     profile.api_key_configured = false;
-    save_llm_settings(settings)
-        .map_err(|error| command_error("settings", "Failed to persist profile key status", error))
+    let saved = save_llm_settings(settings).map_err(|error| {
+        command_error("settings", "Failed to persist profile key status", error)
+    })?;
+    broadcast_settings_changed(&app, settings::LLM_SETTINGS_SECTION);
+    Ok(saved)
 }
 
 #[tauri::command]
@@ -1817,52 +4080,726 @@ fn list_llm_network_interfaces(
 }
 
 #[tauri::command]
-fn scan_lan_llm_providers(
-    interface_id: Option<String>,
-    max_hosts: Option<u32>,
-) -> Vec<llm::LlmEndpointCandidate> {
-    llm::scan_lan_providers(
-        interface_id.as_deref(),
-        max_hosts.unwrap_or(256).clamp(16, 2048) as usize,
-    )
+fn scan_lan_llm_providers(
+    interface_id: Option<String>,
+    max_hosts: Option<u32>,
+) -> Vec<llm::LlmEndpointCandidate> {
+    llm::scan_lan_providers(
+        interface_id.as_deref(),
+        max_hosts.unwrap_or(256).clamp(16, 2048) as usize,
+    )
+}
+
+#[tauri::command]
+async fn test_llm_profile_connection(
+    profile: LlmConnectionProfile,
+) -> Result<LlmConnectionTestResult, String> {
+    let api_key = get_profile_keychain_secret(profile.id.as_str())
+        .map_err(|error| command_error("settings", "Failed to read profile API key", error))?;
+    tauri::async_runtime::spawn_blocking(move || test_llm_profile_connection_sync(profile, api_key))
+        .await
+        .map_err(|error| {
+            command_error(
+                "runtime",
+                "Failed to join LLM profile connection test task",
+                error.to_string(),
+            )
+        })
+}
+
+/// Probes a saved profile's models endpoint with its stored credentials and reports reachability,
+/// whether the key was accepted, round-trip latency and whether the configured model exists.
+#[tauri::command]
+async fn check_llm_provider(profile_id: String) -> Result<llm::LlmProviderHealth, String> {
+    let settings = load_llm_settings();
+    let profile = find_profile_by_id(&settings, profile_id.trim())
+        .cloned()
+        .ok_or_else(|| "Requested LLM profile was not found.".to_string())?;
+    let api_key = get_profile_keychain_secret(profile.id.as_str())
+        .map_err(|error| command_error("settings", "Failed to read profile API key", error))?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider = profile.provider.trim().to_ascii_lowercase();
+        let has_api_key = api_key
+            .as_deref()
+            .is_some_and(|value| !value.trim().is_empty());
+        let started = std::time::Instant::now();
+        let result = test_llm_profile_connection_sync(profile.clone(), api_key);
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        let (reachable, auth_valid) =
+            llm::assess_provider_probe(provider.as_str(), result.status_code, has_api_key);
+        let configured_model = profile.model.trim();
+        let model_available = (!configured_model.is_empty() && !result.detected_models.is_empty())
+            .then(|| {
+                result
+                    .detected_models
+                    .iter()
+                    .any(|model| model.eq_ignore_ascii_case(configured_model))
+            });
+
+        llm::LlmProviderHealth {
+            profile_id: profile.id.clone(),
+            profile_name: profile.name.clone(),
+            provider,
+            base_url: result.base_url,
+            reachable,
+            auth_valid,
+            latency_ms: result.status_code.map(|_| elapsed_ms),
+            status_code: result.status_code,
+            model_available,
+            message: result.message,
+        }
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join LLM provider check task",
+            error.to_string(),
+        )
+    })
+}
+
+#[tauri::command]
+async fn analyze_with_local_llm(
+    prompt: String,
+    profile_id: Option<String>,
+) -> Result<LlmAnalysisResult, String> {
+    let settings = load_llm_settings();
+    let analysis = tauri::async_runtime::spawn_blocking(move || {
+        analyze_with_local_llm_sync(settings, prompt, profile_id)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join local LLM analysis task",
+            error.to_string(),
+        )
+    })?;
+
+    analysis.map_err(|error| command_error("llm", "Local LLM analysis failed", error))
+}
+
+/// Streams an analysis to the frontend as `hla://llm-chunk` events tagged with `stream_id`, then
+/// returns the same result as `analyze_with_local_llm`. `cancel_llm_stream` stops it early.
+#[tauri::command]
+async fn analyze_with_local_llm_stream(
+    app: AppHandle,
+    prompt: String,
+    profile_id: Option<String>,
+    stream_id: String,
+) -> Result<LlmAnalysisResult, String> {
+    let settings = load_llm_settings();
+    let cancel = llm::register_stream(stream_id.as_str());
+    let task_stream_id = stream_id.clone();
+    let analysis = tauri::async_runtime::spawn_blocking(move || {
+        let emit = |profile_id: &str, delta: &str, done: bool| {
+            let chunk = llm::LlmChunk {
+                stream_id: task_stream_id.clone(),
+                profile_id: profile_id.to_string(),
+                delta: delta.to_string(),
+                done,
+            };
+            if let Err(error) = app.emit(llm::LLM_CHUNK_EVENT, chunk) {
+                diagnostics::warn("llm", format!("Failed to emit LLM chunk: {error}"));
+            }
+        };
+        let result = analyze_with_candidate_profiles(
+            settings,
+            prompt,
+            profile_id,
+            Some(cancel.as_ref()),
+            &mut |profile, prompt, api_key| {
+                run_profile_analysis_streaming(
+                    profile,
+                    prompt,
+                    api_key,
+                    cancel.as_ref(),
+                    &mut |delta| emit(profile.id.as_str(), delta, false),
+                )
+            },
+        );
+        let profile_id = result
+            .as_ref()
+            .map(|analysis| analysis.profile_id.clone())
+            .unwrap_or_default();
+        emit(profile_id.as_str(), "", true);
+        result
+    })
+    .await;
+    llm::finish_stream(stream_id.as_str());
+
+    analysis
+        .map_err(|error| {
+            command_error(
+                "runtime",
+                "Failed to join streamed LLM analysis task",
+                error.to_string(),
+            )
+        })?
+        .map_err(|error| command_error("llm", "Streamed LLM analysis failed", error))
+}
+
+#[tauri::command]
+fn cancel_llm_stream(stream_id: String) -> bool {
+    llm::cancel_stream(stream_id.as_str())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CrashRcaResult {
+    crash_id: String,
+    #[serde(flatten)]
+    rca: rca::CrashRca,
+    related_event_count: usize,
+    /// Service and driver changes from the day before the crash that were given to the model.
+    system_change_count: usize,
+    profile_name: String,
+    model: String,
+    fallback_used: bool,
+    warning: Option<String>,
+    /// True when paths, accounts and addresses were masked because a candidate profile is remote.
+    redacted: bool,
+}
+
+#[tauri::command]
+async fn analyze_crash(
+    crash_id: String,
+    profile_id: Option<String>,
+) -> Result<CrashRcaResult, String> {
+    let settings = load_llm_settings();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let (crash, related) = load_crash_with_related_events(crash_id.as_str(), 15, 40)?;
+        let changes = system_changes::changes_before_crash(&crash).unwrap_or_else(|error| {
+            diagnostics::warn(
+                "storage",
+                format!("Failed to read system changes before crash: {error}"),
+            );
+            Vec::new()
+        });
+        let candidates = candidate_profiles_for_analysis(
+            &settings,
+            profile_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty()),
+        )?;
+        let redacted = candidates
+            .iter()
+            .any(|profile| profile_warning_for_settings(profile, &settings).is_some());
+        let token_budget = candidates
+            .iter()
+            .map(profile_prompt_budget)
+            .min()
+            .unwrap_or_else(|| prompt_budget::prompt_token_budget("", 0));
+        let prompt = rca::build_crash_prompt(
+            &crash,
+            related.as_slice(),
+            changes.as_slice(),
+            redacted,
+            token_budget,
+        );
+        let analysis = analyze_with_local_llm_sync(settings, prompt, profile_id)?;
+        Ok::<_, String>(CrashRcaResult {
+            crash_id,
+            rca: rca::parse_rca_response(analysis.response.as_str()),
+            related_event_count: related.len(),
+            system_change_count: changes.len(),
+            profile_name: analysis.profile_name,
+            model: analysis.model,
+            fallback_used: analysis.fallback_used,
+            warning: analysis.warning,
+            redacted,
+        })
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join crash analysis task",
+            error.to_string(),
+        )
+    })?;
+
+    result.map_err(|error| command_error("llm", "Crash root-cause analysis failed", error))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventSummaryResult {
+    #[serde(flatten)]
+    summary: summarize::EventSummary,
+    profile_name: String,
+    model: String,
+    fallback_used: bool,
+    warning: Option<String>,
+    redacted: bool,
+}
+
+/// Summarizes a selection of stored events with the configured LLM. Repeated messages are folded
+/// and the least important ones dropped so the prompt stays within the token budget.
+#[tauri::command]
+async fn summarize_events(
+    event_ids: Vec<String>,
+    profile_id: Option<String>,
+) -> Result<EventSummaryResult, String> {
+    if event_ids.is_empty() {
+        return Err("Select at least one event to summarize.".to_string());
+    }
+    let settings = load_llm_settings();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let events = get_events_by_ids(event_ids.as_slice())?;
+        if events.is_empty() {
+            return Err("None of the selected events are stored anymore.".to_string());
+        }
+        let candidates = candidate_profiles_for_analysis(
+            &settings,
+            profile_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty()),
+        )?;
+        let redacted = candidates
+            .iter()
+            .any(|profile| profile_warning_for_settings(profile, &settings).is_some());
+        let (lines, omitted) = summarize::compact_events(
+            events.as_slice(),
+            summarize::DEFAULT_SUMMARY_TOKEN_BUDGET,
+            redacted,
+        );
+        let prompt = summarize::build_summary_prompt(lines.as_slice(), events.len(), omitted);
+        let analysis = analyze_with_local_llm_sync(settings, prompt, profile_id)?;
+        let mut summary = summarize::parse_summary_response(analysis.response.as_str());
+        summary.event_count = events.len();
+        summary.included_groups = lines.len();
+        summary.omitted_groups = omitted;
+        Ok::<_, String>(EventSummaryResult {
+            summary,
+            profile_name: analysis.profile_name,
+            model: analysis.model,
+            fallback_used: analysis.fallback_used,
+            warning: analysis.warning,
+            redacted,
+        })
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join event summary task",
+            error.to_string(),
+        )
+    })?;
+
+    result.map_err(|error| command_error("llm", "Event summary failed", error))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventQueryTranslation {
+    question: String,
+    filter: nl_query::EventQueryFilter,
+    /// Parts of the model's answer that were dropped or adjusted during validation.
+    notes: Vec<String>,
+    profile_name: String,
+    model: String,
+    fallback_used: bool,
+}
+
+/// Shows how sample values from a log file would be read with a timestamp format hint, so the
+/// hint can be checked before an import. Values without an offset use `utc_offset_minutes`, or
+/// the host's local offset when it is omitted.
+#[tauri::command]
+fn preview_timestamp_parsing(
+    samples: Vec<String>,
+    hint: Option<String>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<Vec<Option<String>>, String> {
+    let hint = timestamps::TimestampHint::from_name(hint.as_deref().unwrap_or("auto"))
+        .map_err(|error| command_error("settings", "Invalid timestamp format", error))?;
+    let mut context = timestamps::TimestampContext::local();
+    if let Some(minutes) = utc_offset_minutes {
+        context.default_offset = chrono::FixedOffset::east_opt(minutes.saturating_mul(60))
+            .ok_or_else(|| {
+                command_error(
+                    "settings",
+                    "Invalid UTC offset",
+                    format!("{minutes} minutes is out of range."),
+                )
+            })?;
+    }
+    Ok(samples
+        .iter()
+        .take(100)
+        .map(|sample| timestamps::normalize_timestamp(sample, &hint, &context))
+        .collect())
+}
+
+/// Turns a plain-language search into a validated event filter. The model only produces the
+/// filter; it is never given stored events or allowed to supply query text for the database.
+#[tauri::command]
+async fn translate_event_query(
+    question: String,
+    target_id: Option<String>,
+    profile_id: Option<String>,
+) -> Result<EventQueryTranslation, String> {
+    let question = question.trim().to_string();
+    if question.is_empty() {
+        return Err("Enter a question to translate.".to_string());
+    }
+    let settings = load_llm_settings();
+    let host = resolve_target_host(target_id.as_deref());
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let log_names = get_log_names(host.as_str())?;
+        let prompt = nl_query::build_filter_prompt(
+            question.as_str(),
+            Local::now().fixed_offset(),
+            log_names.as_slice(),
+        );
+        let analysis = analyze_with_local_llm_sync(settings, prompt, profile_id)?;
+        let (filter, notes) = nl_query::validate_filter_response(
+            analysis.response.as_str(),
+            Utc::now(),
+            log_names.as_slice(),
+        )?;
+        Ok::<_, String>(EventQueryTranslation {
+            question,
+            filter,
+            notes,
+            profile_name: analysis.profile_name,
+            model: analysis.model,
+            fallback_used: analysis.fallback_used,
+        })
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join query translation task",
+            error.to_string(),
+        )
+    })?;
+
+    result.map_err(|error| command_error("llm", "Query translation failed", error))
+}
+
+#[tauri::command]
+fn create_chat_session(title: Option<String>) -> Result<ChatSession, String> {
+    let now = Utc::now().to_rfc3339();
+    let session = ChatSession {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: title
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "New conversation".to_string()),
+        created_at: now.clone(),
+        updated_at: now,
+        message_count: 0,
+    };
+    save_chat_session(&session)
+        .map_err(|error| command_error("storage", "Failed to create chat session", error))?;
+    Ok(session)
+}
+
+#[tauri::command]
+fn list_chat_sessions() -> Result<Vec<ChatSession>, String> {
+    get_chat_sessions()
+        .map_err(|error| command_error("storage", "Failed to read chat sessions", error))
+}
+
+#[tauri::command]
+fn get_chat_messages(session_id: String) -> Result<Vec<ChatMessage>, String> {
+    read_chat_messages(session_id.trim())
+        .map_err(|error| command_error("storage", "Failed to read chat messages", error))
+}
+
+#[tauri::command]
+fn delete_chat_session(session_id: String) -> Result<bool, String> {
+    remove_chat_session(session_id.trim())
+        .map_err(|error| command_error("storage", "Failed to delete chat session", error))
+}
+
+#[tauri::command]
+fn export_chat_session(session_id: String) -> Result<String, String> {
+    let session = get_chat_session(session_id.trim())
+        .map_err(|error| command_error("storage", "Failed to read chat session", error))?
+        .ok_or_else(|| "Chat session not found.".to_string())?;
+    let messages = read_chat_messages(session.id.as_str())
+        .map_err(|error| command_error("storage", "Failed to read chat messages", error))?;
+    Ok(chat::render_chat_markdown(&session, messages.as_slice()))
+}
+
+/// Sends a message in a conversation and returns the assistant's reply. Both messages are only
+/// stored once the model has answered, so a failed request leaves the history unchanged.
+#[tauri::command]
+async fn send_chat_message(
+    session_id: String,
+    content: String,
+    profile_id: Option<String>,
+    event_ids: Option<Vec<String>>,
+    crash_id: Option<String>,
+) -> Result<ChatMessage, String> {
+    let content = content.trim().to_string();
+    if content.is_empty() {
+        return Err("Message is empty.".to_string());
+    }
+    let settings = load_llm_settings();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let mut session = get_chat_session(session_id.trim())?
+            .ok_or_else(|| "Chat session not found.".to_string())?;
+        let history = read_chat_messages(session.id.as_str())?;
+
+        let candidates = candidate_profiles_for_analysis(
+            &settings,
+            profile_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty()),
+        )?;
+        let redact = candidates
+            .iter()
+            .any(|profile| profile_warning_for_settings(profile, &settings).is_some());
+        let crash = match crash_id.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+            Some(id) => Some(get_crash_by_id(id)?.ok_or_else(|| "Crash not found.".to_string())?),
+            None => None,
+        };
+        let events = get_events_by_ids(event_ids.unwrap_or_default().as_slice())?;
+
+        let user_message = ChatMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id: session.id.clone(),
+            role: "user".to_string(),
+            content,
+            context: chat::build_context_block(crash.as_ref(), events.as_slice(), redact),
+            profile_name: None,
+            model: None,
+            created_at: Utc::now().to_rfc3339(),
+        };
+        let prompt = chat::build_chat_prompt(history.as_slice(), &user_message);
+        let analysis = analyze_with_local_llm_sync(settings, prompt, profile_id)?;
+        let reply = ChatMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id: session.id.clone(),
+            role: "assistant".to_string(),
+            content: analysis.response.trim().to_string(),
+            context: None,
+            profile_name: Some(analysis.profile_name),
+            model: Some(analysis.model),
+            created_at: Utc::now().to_rfc3339(),
+        };
+
+        if history.is_empty() && session.title == "New conversation" {
+            session.title = chat::title_from_message(user_message.content.as_str());
+            save_chat_session(&session)?;
+        }
+        append_chat_message(&user_message)?;
+        append_chat_message(&reply)?;
+        Ok::<_, String>(reply)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join chat task",
+            error.to_string(),
+        )
+    })?;
+
+    result.map_err(|error| command_error("llm", "Chat message failed", error))
+}
+
+/// Reads the Windows Error Reporting and crash dump settings, explaining why dumps may be
+/// missing. `app` adds the LocalDumps override for one executable.
+#[tauri::command]
+async fn get_wer_dump_status(app: Option<String>) -> Result<wer_config::WerDumpStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        wer_config::read_wer_dump_status(app.as_deref().filter(|value| !value.trim().is_empty()))
+            .map_err(|error| command_error("crash", "Failed to read WER settings", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join WER settings task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Turns on local crash dumps for one executable (or all when `app` is empty). Requires the app
+/// to run elevated.
+#[tauri::command]
+async fn enable_wer_local_dumps(
+    app: Option<String>,
+    dump_type: String,
+    dump_folder: Option<String>,
+    dump_count: Option<u32>,
+) -> Result<wer_config::WerDumpStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        wer_config::enable_local_dumps(
+            app.as_deref().filter(|value| !value.trim().is_empty()),
+            dump_type.as_str(),
+            dump_folder.as_deref().filter(|value| !value.trim().is_empty()),
+            dump_count,
+        )
+        .map_err(|error| command_error("crash", "Failed to enable local crash dumps", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join WER settings task",
+            error.to_string(),
+        )
+    })?
 }
 
-#[tauri::command]
-async fn test_llm_profile_connection(
-    profile: LlmConnectionProfile,
-) -> Result<LlmConnectionTestResult, String> {
-    let api_key = get_profile_keychain_secret(profile.id.as_str())
-        .map_err(|error| command_error("settings", "Failed to read profile API key", error))?;
-    tauri::async_runtime::spawn_blocking(move || test_llm_profile_connection_sync(profile, api_key))
-        .await
-        .map_err(|error| {
-            command_error(
-                "runtime",
-                "Failed to join LLM profile connection test task",
-                error.to_string(),
-            )
-        })
+/// Distinct templates embedded per search; the rest are indexed by later searches.
+const MAX_NEW_EMBEDDINGS: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimilarEventsResult {
+    event_id: String,
+    template: String,
+    model: String,
+    matches: Vec<embeddings::SimilarEventMatch>,
+    newly_indexed: usize,
+    /// Templates not yet embedded and therefore not compared this time.
+    pending_templates: usize,
 }
 
+/// Finds stored events whose message template is semantically close to the given event's, using
+/// embeddings from the first Ollama or OpenAI-compatible profile. Vectors are cached per template.
 #[tauri::command]
-async fn analyze_with_local_llm(
-    prompt: String,
+async fn find_similar_events(
+    event_id: String,
+    k: Option<usize>,
     profile_id: Option<String>,
-) -> Result<LlmAnalysisResult, String> {
-    let settings = load_llm_settings_with_migration().settings;
-    let analysis = tauri::async_runtime::spawn_blocking(move || {
-        analyze_with_local_llm_sync(settings, prompt, profile_id)
+    model: Option<String>,
+) -> Result<SimilarEventsResult, String> {
+    let settings = load_llm_settings();
+    let k = k.unwrap_or(10).clamp(1, 100);
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let target = get_events_by_ids(&[event_id.trim().to_string()])?
+            .pop()
+            .ok_or_else(|| "Event not found.".to_string())?;
+        let target_template = patterns::message_template(target.message.as_str());
+        if target_template.is_empty() {
+            return Err("The event has no message to compare.".to_string());
+        }
+
+        let candidates = candidate_profiles_for_analysis(
+            &settings,
+            profile_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty()),
+        )?;
+        let profile = candidates
+            .iter()
+            .find(|profile| {
+                profile.enabled
+                    && embeddings::default_embedding_model(
+                        profile.provider.trim().to_ascii_lowercase().as_str(),
+                    )
+                    .is_some()
+            })
+            .ok_or_else(|| {
+                "No enabled Ollama or OpenAI-compatible profile is available for embeddings."
+                    .to_string()
+            })?;
+        let provider = profile.provider.trim().to_ascii_lowercase();
+        let model = model
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .or_else(|| embeddings::default_embedding_model(provider.as_str()).map(str::to_string))
+            .unwrap_or_default();
+        let api_key = get_profile_keychain_secret(profile.id.as_str())?;
+
+        let mut groups: std::collections::BTreeMap<String, Vec<NormalizedEvent>> =
+            std::collections::BTreeMap::new();
+        for event in read_local_events(20000, Some(target.source_host.as_str()))? {
+            if event.id == target.id {
+                continue;
+            }
+            let template = patterns::message_template(event.message.as_str());
+            if !template.is_empty() {
+                groups.entry(template).or_default().push(event);
+            }
+        }
+
+        let mut cache = get_template_embeddings(model.as_str())?;
+        let mut missing: Vec<&String> = groups
+            .keys()
+            .filter(|template| **template != target_template && !cache.contains_key(*template))
+            .collect();
+        if !cache.contains_key(&target_template) {
+            missing.insert(0, &target_template);
+        }
+        let pending_templates = missing.len().saturating_sub(MAX_NEW_EMBEDDINGS);
+        let mut newly_indexed = 0;
+        for template in missing.into_iter().take(MAX_NEW_EMBEDDINGS) {
+            let vector = embeddings::request_embedding(
+                provider.as_str(),
+                profile.base_url.as_str(),
+                model.as_str(),
+                template.as_str(),
+                api_key.as_deref(),
+            )?;
+            let bytes = embeddings::encode_vector(vector.as_slice());
+            save_template_embedding(model.as_str(), template.as_str(), bytes.as_slice())?;
+            cache.insert(template.clone(), bytes);
+            newly_indexed += 1;
+        }
+
+        let target_vector = cache
+            .get(&target_template)
+            .map(|bytes| embeddings::decode_vector(bytes.as_slice()))
+            .ok_or_else(|| "The event's template could not be embedded.".to_string())?;
+        let mut matches: Vec<embeddings::SimilarEventMatch> = groups
+            .into_iter()
+            .filter_map(|(template, mut events)| {
+                let vector = embeddings::decode_vector(cache.get(&template)?.as_slice());
+                let similarity =
+                    embeddings::cosine_similarity(target_vector.as_slice(), vector.as_slice());
+                events.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
+                let first_seen = events.first()?.timestamp.clone();
+                let occurrences = events.len();
+                let event = events.pop()?;
+                Some(embeddings::SimilarEventMatch {
+                    template,
+                    similarity,
+                    occurrences,
+                    first_seen,
+                    last_seen: event.timestamp.clone(),
+                    event,
+                })
+            })
+            .collect();
+        matches.sort_by(|left, right| right.similarity.total_cmp(&left.similarity));
+        matches.truncate(k);
+
+        Ok::<_, String>(SimilarEventsResult {
+            event_id: target.id,
+            template: target_template,
+            model,
+            matches,
+            newly_indexed,
+            pending_templates,
+        })
     })
     .await
     .map_err(|error| {
         command_error(
             "runtime",
-            "Failed to join local LLM analysis task",
+            "Failed to join similar events task",
             error.to_string(),
         )
     })?;
 
-    analysis.map_err(|error| command_error("llm", "Local LLM analysis failed", error))
+    result.map_err(|error| command_error("llm", "Similar event search failed", error))
 }
 
 #[tauri::command]
@@ -1941,18 +4878,25 @@ async fn backfill_local_events(from: String, to: String) -> Result<SyncOperation
     let (start, end) = parse_local_date_range(from.as_str(), to.as_str())
         .map_err(|error| command_error("runtime", "Invalid backfill range", error))?;
     let profile = load_ingest_profile();
+    let started_at = Utc::now();
 
-    tauri::async_runtime::spawn_blocking(move || {
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let _activity = scheduler::begin_sync();
         let outcome = collect_host_events_range_with_windows_channels(
             Some(start),
             Some(end),
             Some(profile.max_events_per_sync),
             Some(profile.windows_channels.as_slice()),
+            Some(&profile.journal_filter),
+            Some(&profile.unified_log_filter),
             profile.request_elevation,
         );
-        let report = report_collection_outcome("Range backfill collection", &outcome)?;
-        save_local_events(outcome.events.as_slice())
+        let mut report = report_collection_outcome("Range backfill collection", &outcome)?;
+        save_collected_events(outcome.events.as_slice(), &profile, &mut report)
             .map_err(|error| command_error("storage", "Failed to save backfilled events", error))?;
+        promote_event_crashes(outcome.events.as_slice());
+        forwarding::forward_events(outcome.events.as_slice());
+        alerts::evaluate_events(outcome.events.as_slice());
         Ok::<SyncOperationResult, String>(report)
     })
     .await
@@ -1962,7 +4906,9 @@ async fn backfill_local_events(from: String, to: String) -> Result<SyncOperation
             "Failed to join backfill collection task",
             error.to_string(),
         )
-    })?
+    })?;
+    record_sync("backfill", "localhost", started_at, &result);
+    result
 }
 
 #[tauri::command]
@@ -1977,19 +4923,27 @@ async fn sync_local_events_range(
     let start_str = start.to_rfc3339();
     let end_str = end.to_rfc3339();
     let replace = replace_outside_range.unwrap_or(false);
+    let started_at = Utc::now();
 
-    tauri::async_runtime::spawn_blocking(move || {
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let _activity = scheduler::begin_sync();
         let outcome = collect_host_events_range_with_windows_channels(
             Some(start),
             Some(end),
             Some(profile.max_events_per_sync),
             Some(profile.windows_channels.as_slice()),
+            Some(&profile.journal_filter),
+            Some(&profile.unified_log_filter),
             profile.request_elevation,
         );
-        let report = report_collection_outcome("Range sync collection", &outcome)?;
-        save_local_events(outcome.events.as_slice()).map_err(|error| {
-            command_error("storage", "Failed to save range-synced events", error)
-        })?;
+        let mut report = report_collection_outcome("Range sync collection", &outcome)?;
+        save_collected_events(outcome.events.as_slice(), &profile, &mut report)
+            .map_err(|error| {
+                command_error("storage", "Failed to save range-synced events", error)
+            })?;
+        promote_event_crashes(outcome.events.as_slice());
+        forwarding::forward_events(outcome.events.as_slice());
+        alerts::evaluate_events(outcome.events.as_slice());
         if replace {
             prune_events_outside(start_str.as_str(), end_str.as_str()).map_err(|error| {
                 command_error("storage", "Failed to prune out-of-range events", error)
@@ -2004,7 +4958,49 @@ async fn sync_local_events_range(
             "Failed to join range sync task",
             error.to_string(),
         )
-    })?
+    })?;
+    record_sync("range", "localhost", started_at, &result);
+    result
+}
+
+/// Collects and stores one window for the local host or a remote target. `channels` overrides the
+/// ingest profile's Windows channels for this collection only.
+fn sync_events_window(
+    target_id: Option<&str>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    channels: Option<Vec<String>>,
+) -> Result<SyncOperationResult, String> {
+    let _activity = scheduler::begin_sync();
+    let mut profile = load_ingest_profile();
+    if let Some(channels) = channels.filter(|channels| !channels.is_empty()) {
+        profile.windows_channels = channels;
+    }
+    let max_events = profile.max_events_per_sync.max(5000);
+    let remote_profile = resolve_target_profile(target_id);
+
+    let outcome = if let Some(remote) = remote_profile {
+        remote_collection_outcome(&remote, &profile, Some(start), Some(end), Some(max_events))
+    } else {
+        collect_host_events_range_with_windows_channels(
+            Some(start),
+            Some(end),
+            Some(max_events),
+            Some(profile.windows_channels.as_slice()),
+            Some(&profile.journal_filter),
+            Some(&profile.unified_log_filter),
+            profile.request_elevation,
+        )
+    };
+    let mut report = report_collection_outcome("Crash window collection", &outcome)?;
+    save_collected_events(outcome.events.as_slice(), &profile, &mut report)
+        .map_err(|error| {
+            command_error("storage", "Failed to save crash-window events", error)
+        })?;
+    promote_event_crashes(outcome.events.as_slice());
+    forwarding::forward_events(outcome.events.as_slice());
+    alerts::evaluate_events(outcome.events.as_slice());
+    Ok(report)
 }
 
 #[tauri::command]
@@ -2015,44 +5011,402 @@ async fn sync_local_events_window(
 ) -> Result<SyncOperationResult, String> {
     let (start_value, end_value) = parse_timestamp_window(start.as_str(), end.as_str())
         .map_err(|error| command_error("runtime", "Invalid sync window", error))?;
-    let profile = load_ingest_profile();
-    let target = target_id.clone();
-    let max_events = profile.max_events_per_sync.max(5000);
 
+    let host = resolve_target_host(target_id.as_deref());
+    let started_at = Utc::now();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        sync_events_window(target_id.as_deref(), start_value, end_value, None)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join crash window sync task",
+            error.to_string(),
+        )
+    })?;
+    record_sync("window", host.as_str(), started_at, &result);
+    result
+}
+
+/// Known-issue rules matching the stored crash or event with `id`, with their diagnosis and fix.
+#[tauri::command]
+fn match_known_issues(id: String) -> Result<known_issues::KnownIssueReport, String> {
+    let report = known_issues::match_known_issues(id.trim())
+        .map_err(|error| command_error("runtime", "Failed to match known issues", error))?;
+    for error in &report.rule_errors {
+        diagnostics::warn("rules", error);
+    }
+    Ok(report)
+}
+
+/// Driver, update, service and configuration changes from the `hours` before a crash
+/// (24 by default), closest to the crash first.
+#[tauri::command]
+async fn get_recent_changes(
+    crash_id: String,
+    hours: Option<u32>,
+) -> Result<recent_changes::RecentChangesReport, String> {
+    let hours = hours
+        .unwrap_or(recent_changes::DEFAULT_HOURS)
+        .clamp(1, recent_changes::MAX_HOURS);
+    tauri::async_runtime::spawn_blocking(move || {
+        let crash = get_crash_by_id(crash_id.as_str())
+            .map_err(|error| command_error("storage", "Failed to load crash", error))?
+            .ok_or_else(|| "Selected crash was not found.".to_string())?;
+        let (start, end) = recent_changes::window(&crash, hours)
+            .map_err(|error| command_error("runtime", "Failed to list recent changes", error))?;
+        let events = read_local_events_range(
+            start.as_str(),
+            end.as_str(),
+            50000,
+            Some(crash.source_host.as_str()),
+        )
+        .map_err(|error| command_error("storage", "Failed to read events before crash", error))?;
+        recent_changes::recent_changes(&crash, events.as_slice(), hours)
+            .map_err(|error| command_error("runtime", "Failed to list recent changes", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join recent changes task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Imports an indicator feed read by the frontend, replacing an earlier feed of the same name.
+#[tauri::command]
+fn import_ioc_feed(name: String, content: String) -> Result<ioc::IocImportResult, String> {
+    ioc::import_feed(name.as_str(), content.as_str())
+        .map_err(|error| command_error("rules", "Failed to import IOC feed", error))
+}
+
+#[tauri::command]
+fn list_ioc_feeds() -> Result<Vec<db::IocFeedSummary>, String> {
+    ioc::list_feeds().map_err(|error| command_error("storage", "Failed to list IOC feeds", error))
+}
+
+#[tauri::command]
+fn delete_ioc_feed(feed: String) -> Result<(), String> {
+    db::delete_ioc_feed(feed.as_str())
+        .map_err(|error| command_error("storage", "Failed to delete IOC feed", error))
+}
+
+/// Looks for every loaded indicator in the stored events of a host, optionally within
+/// `[start, end]`, and records new matches.
+#[tauri::command]
+async fn run_ioc_scan(
+    target_id: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+) -> Result<ioc::IocScanResult, String> {
+    let mut filter = nl_query::EventQueryFilter::default();
+    if let (Some(start), Some(end)) = (start.as_deref(), end.as_deref()) {
+        let (start_value, end_value) = parse_timestamp_window(start, end)
+            .map_err(|error| command_error("runtime", "Invalid IOC scan window", error))?;
+        filter.start = Some(start_value.to_rfc3339());
+        filter.end = Some(end_value.to_rfc3339());
+    }
+    let host = resolve_host_filter(target_id.as_deref());
+    tauri::async_runtime::spawn_blocking(move || {
+        ioc::scan(&filter, host.as_deref())
+            .map_err(|error| command_error("rules", "Failed to run IOC scan", error))
+    })
+    .await
+    .map_err(|error| command_error("runtime", "Failed to join IOC scan task", error.to_string()))?
+}
+
+#[tauri::command]
+fn list_ioc_matches(
+    target_id: Option<String>,
+    feed: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<db::IocMatch>, String> {
+    let limit = limit.unwrap_or(500).clamp(1, 5000);
+    let host = resolve_host_filter(target_id.as_deref());
+    db::get_ioc_matches(limit, feed.as_deref(), host.as_deref())
+        .map_err(|error| command_error("storage", "Failed to read IOC matches", error))
+}
+
+/// Service installs, driver loads, launchd registrations and new systemd units between `start`
+/// and `end`, newest first.
+#[tauri::command]
+async fn get_system_changes(
+    target_id: Option<String>,
+    start: String,
+    end: String,
+    limit: Option<usize>,
+) -> Result<system_changes::SystemChangesReport, String> {
+    let (start_value, end_value) = parse_timestamp_window(start.as_str(), end.as_str())
+        .map_err(|error| command_error("runtime", "Invalid system changes window", error))?;
+    let limit = limit
+        .unwrap_or(system_changes::DEFAULT_LIMIT)
+        .clamp(1, 5000);
+    let host = resolve_host_filter(target_id.as_deref());
+    tauri::async_runtime::spawn_blocking(move || {
+        system_changes::system_changes(
+            start_value.to_rfc3339().as_str(),
+            end_value.to_rfc3339().as_str(),
+            host.as_deref(),
+            limit,
+        )
+        .map_err(|error| command_error("storage", "Failed to read system changes", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join system changes task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// USB and removable device connects and disconnects between `start` and `end`, newest first.
+#[tauri::command]
+async fn get_usb_device_activity(
+    target_id: Option<String>,
+    start: String,
+    end: String,
+    limit: Option<usize>,
+) -> Result<usb_devices::UsbActivityReport, String> {
+    let (start_value, end_value) = parse_timestamp_window(start.as_str(), end.as_str())
+        .map_err(|error| command_error("runtime", "Invalid device activity window", error))?;
+    let limit = limit.unwrap_or(usb_devices::DEFAULT_LIMIT).clamp(1, 5000);
+    let host = resolve_host_filter(target_id.as_deref());
+    tauri::async_runtime::spawn_blocking(move || {
+        usb_devices::usb_activity(
+            start_value.to_rfc3339().as_str(),
+            end_value.to_rfc3339().as_str(),
+            host.as_deref(),
+            limit,
+        )
+        .map_err(|error| command_error("storage", "Failed to read device activity", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join device activity task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Stored events whose message matches the regular expression `pattern`, newest first.
+#[tauri::command]
+async fn search_events_regex(
+    pattern: String,
+    target_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<regex_search::RegexSearchResult, String> {
+    // A mistyped pattern is reported to the user, not logged as a storage failure.
+    regex_search::compile(pattern.as_str())?;
+    let limit = limit.unwrap_or(regex_search::DEFAULT_LIMIT).clamp(1, 5000);
+    let host = resolve_host_filter(target_id.as_deref());
+    tauri::async_runtime::spawn_blocking(move || {
+        regex_search::search_events(pattern.as_str(), host.as_deref(), limit)
+            .map_err(|error| command_error("storage", "Regex search failed", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join regex search task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Runs of identical messages among the events matching `filter`, most recently seen first.
+/// Repeats within `gap_seconds` of each other fold into one run.
+#[tauri::command]
+async fn get_repeated_events(
+    filter: nl_query::EventQueryFilter,
+    target_id: Option<String>,
+    gap_seconds: Option<i64>,
+    limit: Option<usize>,
+) -> Result<repeats::RepeatedEventsReport, String> {
+    let gap_seconds = gap_seconds.unwrap_or(repeats::DEFAULT_GAP_SECONDS);
+    let limit = limit.unwrap_or(repeats::DEFAULT_LIMIT).clamp(1, 5000);
+    let host = resolve_host_filter(target_id.as_deref());
+    tauri::async_runtime::spawn_blocking(move || {
+        repeats::repeated_events(&filter, host.as_deref(), gap_seconds, limit)
+            .map_err(|error| command_error("storage", "Failed to read repeated events", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join repeated events task",
+            error.to_string(),
+        )
+    })?
+}
+
+/// Failed and successful logons between `start` and `end`, grouped by account and source
+/// address, with bursts of failures for brute-force triage.
+#[tauri::command]
+async fn analyze_logons(
+    target_id: Option<String>,
+    start: String,
+    end: String,
+    top: Option<usize>,
+) -> Result<logons::LogonReport, String> {
+    let (start_value, end_value) = parse_timestamp_window(start.as_str(), end.as_str())
+        .map_err(|error| command_error("runtime", "Invalid logon analysis window", error))?;
+    let top = top.unwrap_or(logons::DEFAULT_TOP).clamp(1, 100);
+    let host = resolve_host_filter(target_id.as_deref());
+    tauri::async_runtime::spawn_blocking(move || {
+        logons::analyze_logons(
+            start_value.to_rfc3339().as_str(),
+            end_value.to_rfc3339().as_str(),
+            host.as_deref(),
+            top,
+        )
+        .map_err(|error| command_error("storage", "Failed to analyze logons", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join logon analysis task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+fn list_sigma_rules() -> Result<sigma::SigmaRuleList, String> {
+    sigma::list_rules().map_err(|error| command_error("rules", "Failed to list Sigma rules", error))
+}
+
+/// Imports Sigma rule files read by the frontend; rules replace stored rules with the same id.
+#[tauri::command]
+fn import_sigma_rules(
+    files: Vec<sigma::SigmaRuleFile>,
+) -> Result<sigma::SigmaImportResult, String> {
+    let result = sigma::import_rules(files.as_slice())
+        .map_err(|error| command_error("rules", "Failed to import Sigma rules", error))?;
+    for error in &result.errors {
+        diagnostics::warn("rules", error);
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+fn delete_sigma_rule(rule_id: String) -> Result<(), String> {
+    sigma::delete_rule(rule_id.as_str())
+        .map_err(|error| command_error("rules", "Failed to delete Sigma rule", error))
+}
+
+/// Evaluates every supported Sigma rule against the stored events of a host, optionally within
+/// `[start, end]`, and records new detections.
+#[tauri::command]
+async fn run_sigma_scan(
+    target_id: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+) -> Result<sigma::SigmaScanResult, String> {
+    let mut filter = nl_query::EventQueryFilter::default();
+    if let (Some(start), Some(end)) = (start.as_deref(), end.as_deref()) {
+        let (start_value, end_value) = parse_timestamp_window(start, end)
+            .map_err(|error| command_error("runtime", "Invalid Sigma scan window", error))?;
+        filter.start = Some(start_value.to_rfc3339());
+        filter.end = Some(end_value.to_rfc3339());
+    }
+    let host = resolve_host_filter(target_id.as_deref());
     tauri::async_runtime::spawn_blocking(move || {
-        let remote_profile = resolve_target_profile(target.as_deref());
+        let result = sigma::scan(&filter, host.as_deref())
+            .map_err(|error| command_error("rules", "Failed to run Sigma scan", error))?;
+        for error in &result.rule_errors {
+            diagnostics::warn("rules", error);
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join Sigma scan task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+fn list_sigma_detections(
+    target_id: Option<String>,
+    rule_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<db::SigmaDetection>, String> {
+    let limit = limit.unwrap_or(500).clamp(1, 5000);
+    let host = resolve_host_filter(target_id.as_deref());
+    db::get_sigma_detections(limit, rule_id.as_deref(), host.as_deref())
+        .map_err(|error| command_error("storage", "Failed to read Sigma detections", error))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CrashCollectionPlan {
+    suggestion: crash_window::CollectionSuggestion,
+    sync_result: Option<SyncOperationResult>,
+}
+
+/// Suggests a collection window and channel set for a crash and, when `sync` is true, collects
+/// exactly that window straight away.
+#[tauri::command]
+async fn suggest_crash_collection(
+    crash_id: String,
+    target_id: Option<String>,
+    sync: Option<bool>,
+) -> Result<CrashCollectionPlan, String> {
+    let plan = tauri::async_runtime::spawn_blocking(move || {
+        let crash = get_crash_by_id(crash_id.as_str())
+            .map_err(|error| command_error("storage", "Failed to load crash", error))?
+            .ok_or_else(|| "Selected crash was not found.".to_string())?;
+        let suggestion =
+            crash_window::suggest_collection_window(&crash, Utc::now()).map_err(|error| {
+                command_error("runtime", "Failed to suggest a collection window", error)
+            })?;
+        if !sync.unwrap_or(false) {
+            return Ok(CrashCollectionPlan {
+                suggestion,
+                sync_result: None,
+            });
+        }
 
-        let outcome = if let Some(remote) = remote_profile {
-            remote_collection_outcome(
-                &remote,
-                &profile,
-                Some(start_value),
-                Some(end_value),
-                Some(max_events),
-            )
-        } else {
-            collect_host_events_range_with_windows_channels(
-                Some(start_value),
-                Some(end_value),
-                Some(max_events),
-                Some(profile.windows_channels.as_slice()),
-                profile.request_elevation,
-            )
-        };
-        let report = report_collection_outcome("Crash window collection", &outcome)?;
-        save_local_events(outcome.events.as_slice()).map_err(|error| {
-            command_error("storage", "Failed to save crash-window events", error)
-        })?;
-        Ok::<SyncOperationResult, String>(report)
+        let (start, end) =
+            parse_timestamp_window(suggestion.start.as_str(), suggestion.end.as_str())?;
+        let started_at = Utc::now();
+        let result = sync_events_window(
+            target_id.as_deref(),
+            start,
+            end,
+            Some(suggestion.channels.clone()),
+        );
+        record_sync(
+            "crash-window",
+            resolve_target_host(target_id.as_deref()).as_str(),
+            started_at,
+            &result,
+        );
+        Ok::<_, String>(CrashCollectionPlan {
+            suggestion,
+            sync_result: Some(result?),
+        })
     })
     .await
     .map_err(|error| {
         command_error(
             "runtime",
-            "Failed to join crash window sync task",
+            "Failed to join crash collection task",
             error.to_string(),
         )
-    })?
+    })?;
+    plan
 }
 
 #[tauri::command]
@@ -2069,6 +5423,8 @@ async fn estimate_local_events_range(
             Some(start),
             Some(end),
             Some(profile.windows_channels.as_slice()),
+            Some(&profile.journal_filter),
+            Some(&profile.unified_log_filter),
             profile.request_elevation,
         );
         report_collection_estimate("Range estimate", &start, &end, &estimate)
@@ -2117,13 +5473,31 @@ fn open_external_url(url: String) -> Result<(), String> {
         .map_err(|error| command_error("runtime", "Failed to open external URL", error.to_string()))
 }
 
+#[tauri::command]
+fn get_display_timezone() -> String {
+    load_display_timezone().name()
+}
+
+#[tauri::command]
+fn set_display_timezone(app: AppHandle, timezone: String) -> Result<String, String> {
+    let saved = save_display_timezone(timezone.as_str())
+        .map_err(|error| command_error("settings", "Failed to save time zone", error))?;
+    broadcast_settings_changed(&app, settings::DISPLAY_TIMEZONE_SECTION);
+    Ok(saved)
+}
+
+#[tauri::command]
+fn list_timezones() -> Vec<&'static str> {
+    timestamps::timezone_names()
+}
+
 #[tauri::command]
 fn get_export_directory() -> Option<String> {
     load_export_dir()
 }
 
 #[tauri::command]
-fn choose_export_directory() -> Result<Option<String>, String> {
+fn choose_export_directory(app: AppHandle) -> Result<Option<String>, String> {
     let chosen = rfd::FileDialog::new().pick_folder();
     let Some(path) = chosen else {
         return Ok(None);
@@ -2137,29 +5511,19 @@ fn choose_export_directory() -> Result<Option<String>, String> {
             error,
         )
     })?;
+    broadcast_settings_changed(&app, settings::EXPORT_DIR_SECTION);
     Ok(Some(value))
 }
 
 #[tauri::command]
-fn set_export_directory(path: Option<String>) -> Result<(), String> {
+fn set_export_directory(app: AppHandle, path: Option<String>) -> Result<(), String> {
     save_export_dir(path.as_deref())
-        .map_err(|error| command_error("settings", "Failed to update export directory", error))
+        .map_err(|error| command_error("settings", "Failed to update export directory", error))?;
+    broadcast_settings_changed(&app, settings::EXPORT_DIR_SECTION);
+    Ok(())
 }
 
-#[tauri::command]
-fn export_events(
-    format: String,
-    filename: String,
-    events: Vec<NormalizedEvent>,
-) -> Result<String, String> {
-    let output_format = format.to_ascii_lowercase();
-    let extension = match output_format.as_str() {
-        "json" => "json",
-        "csv" => "csv",
-        "txt" => "txt",
-        _ => return Err("Unsupported export format.".to_string()),
-    };
-
+fn resolve_export_dir() -> Result<PathBuf, String> {
     let base_dir = load_export_dir()
         .map(PathBuf::from)
         .or_else(dirs::download_dir)
@@ -2174,14 +5538,21 @@ fn export_events(
     if !base_dir.exists() || !base_dir.is_dir() {
         return Err("Configured export directory is invalid.".to_string());
     }
+    Ok(base_dir)
+}
 
-    let safe_name = sanitize_filename(filename.as_str(), extension);
-    let output_path = base_dir.join(safe_name);
-    let payload = build_export_payload(extension, &events)?;
+#[tauri::command]
+fn export_events(
+    format: String,
+    filename: String,
+    events: Vec<NormalizedEvent>,
+) -> Result<String, String> {
+    let (extension, compression) = event_export::parse_export_format(format.as_str())?;
 
-    std::fs::write(&output_path, payload).map_err(|error| {
-        command_error("storage", "Failed to write export file", error.to_string())
-    })?;
+    let base_dir = resolve_export_dir()?;
+    let safe_name = export_file_name(filename.as_str(), extension.as_str(), compression);
+    let output_path = base_dir.join(safe_name);
+    write_events_file(&output_path, extension.as_str(), compression, &events)?;
     Ok(output_path.to_string_lossy().to_string())
 }
 
@@ -2191,20 +5562,36 @@ fn export_events_with_dialog(
     suggested_filename: String,
     events: Vec<NormalizedEvent>,
 ) -> Result<Option<String>, String> {
-    let output_format = format.to_ascii_lowercase();
-    let (extension, filter_name): (&str, &str) = match output_format.as_str() {
-        "json" => ("json", "JSON"),
-        "csv" => ("csv", "CSV"),
-        "txt" => ("txt", "Text"),
+    let (extension, compression) = event_export::parse_export_format(format.as_str())?;
+    let filter_name = match extension.as_str() {
+        "json" => "JSON",
+        "ndjson" => "NDJSON",
+        "csv" => "CSV",
+        "txt" => "Text",
+        "parquet" => "Parquet",
+        "cef" => "CEF",
+        "leef" => "LEEF",
         _ => return Err("Unsupported export format.".to_string()),
     };
 
-    let safe_name = sanitize_filename(suggested_filename.as_str(), extension);
+    let safe_name = export_file_name(
+        suggested_filename.as_str(),
+        extension.as_str(),
+        compression,
+    );
     let mut dialog = rfd::FileDialog::new().set_file_name(safe_name.as_str());
-    dialog = match extension {
-        "json" => dialog.add_filter(filter_name, &["json"]),
-        "csv" => dialog.add_filter(filter_name, &["csv"]),
-        "txt" => dialog.add_filter(filter_name, &["txt"]),
+    dialog = match (compression, extension.as_str()) {
+        (Some(compression), _) => dialog.add_filter(
+            format!("{filter_name} ({})", compression.extension()),
+            &[compression.extension()],
+        ),
+        (None, "json") => dialog.add_filter(filter_name, &["json"]),
+        (None, "ndjson") => dialog.add_filter(filter_name, &["ndjson", "jsonl"]),
+        (None, "csv") => dialog.add_filter(filter_name, &["csv"]),
+        (None, "txt") => dialog.add_filter(filter_name, &["txt"]),
+        (None, "parquet") => dialog.add_filter(filter_name, &["parquet"]),
+        (None, "cef") => dialog.add_filter(filter_name, &["cef", "log"]),
+        (None, "leef") => dialog.add_filter(filter_name, &["leef", "log"]),
         _ => dialog,
     };
 
@@ -2220,13 +5607,87 @@ fn export_events_with_dialog(
         return Ok(None);
     };
 
-    let payload = build_export_payload(extension, &events)?;
-    std::fs::write(&output_path, payload).map_err(|error| {
-        command_error("storage", "Failed to write export file", error.to_string())
-    })?;
+    write_events_file(&output_path, extension.as_str(), compression, &events)?;
     Ok(Some(output_path.to_string_lossy().to_string()))
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryExportResult {
+    path: String,
+    event_count: usize,
+    /// Zone the exported timestamps are written in.
+    timezone: String,
+}
+
+#[tauri::command]
+async fn export_query(
+    filter: nl_query::EventQueryFilter,
+    format: String,
+    filename: String,
+    target_id: Option<String>,
+) -> Result<QueryExportResult, String> {
+    let (extension, compression) = event_export::parse_export_format(format.as_str())?;
+    let output_path = resolve_export_dir()?.join(export_file_name(
+        filename.as_str(),
+        extension.as_str(),
+        compression,
+    ));
+    let host = resolve_host_filter(target_id.as_deref());
+    let timezone = load_display_timezone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let streamed = db::stream_matching_events(
+            &filter,
+            host.as_deref(),
+            |event_count| {
+                let file = event_export::ExportFile::create(&output_path, compression)?;
+                event_export::EventExportWriter::start(
+                    file,
+                    extension.as_str(),
+                    event_count,
+                    timezone,
+                )
+            },
+            |writer, event| writer.write_event(&event),
+        )
+        .and_then(|(writer, _)| writer.finish())
+        .and_then(|(file, event_count)| file.finish().map(|_| event_count));
+
+        match streamed {
+            Ok(event_count) => Ok(QueryExportResult {
+                path: output_path.to_string_lossy().to_string(),
+                event_count,
+                timezone: timezone.name(),
+            }),
+            Err(error) => {
+                let _ = std::fs::remove_file(&output_path);
+                Err(command_error(
+                    "storage",
+                    "Failed to export matching events",
+                    error,
+                ))
+            }
+        }
+    })
+    .await
+    .map_err(|error| command_error("runtime", "Failed to join export task", error.to_string()))?
+}
+
+/// Exports the events matching a saved filter, resolved at the time of the export.
+#[tauri::command]
+async fn export_saved_filter(
+    filter_id: String,
+    format: String,
+    filename: String,
+    target_id: Option<String>,
+) -> Result<QueryExportResult, String> {
+    let saved = saved_filters::find_saved_filter(filter_id.as_str())
+        .map_err(|error| command_error("storage", "Failed to export saved filter", error))?;
+    let filter = saved_filters::to_query_filter(&saved, Utc::now());
+    export_query(filter, format, filename, target_id).await
+}
+
 #[tauri::command]
 fn save_text_with_dialog(
     suggested_filename: String,
@@ -2285,12 +5746,24 @@ fn set_app_theme(app: AppHandle, theme: String) {
     apply_theme(&app, theme.as_str());
 }
 
+/// Tells every window which settings section changed so each can reload it.
+fn broadcast_settings_changed(app: &AppHandle, section: &str) {
+    if let Err(error) = app.emit(settings::SETTINGS_CHANGED_EVENT, section) {
+        diagnostics::warn(
+            "runtime",
+            format!("Failed to broadcast settings change: {error}"),
+        );
+    }
+}
+
 fn apply_theme(app: &AppHandle, theme: &str) {
     if let Err(error) = save_theme(theme) {
         diagnostics::warn(
             "settings",
             format!("Failed to persist theme '{theme}': {error}"),
         );
+    } else {
+        broadcast_settings_changed(app, settings::THEME_SECTION);
     }
 
     let native_theme = match theme {
@@ -2505,6 +5978,127 @@ fn save_remote_settings(
     crate::settings::save_remote_settings(settings).map(hydrate_remote_provider_token_flags)
 }
 
+#[tauri::command]
+fn get_integration_settings() -> crate::settings::IntegrationSettings {
+    crate::settings::load_integration_settings()
+}
+
+#[tauri::command]
+fn save_integration_settings(
+    settings: crate::settings::IntegrationSettings,
+) -> Result<crate::settings::IntegrationSettings, String> {
+    let saved = crate::settings::save_integration_settings(settings)?;
+    let active_ids: Vec<String> = saved
+        .integrations
+        .iter()
+        .map(|integration| integration.id.clone())
+        .collect();
+    if let Err(error) = prune_forward_spools(active_ids.as_slice()) {
+        diagnostics::warn("storage", format!("Failed to prune forwarding spools: {error}"));
+    }
+    Ok(saved)
+}
+
+#[tauri::command]
+fn get_alert_settings() -> crate::settings::AlertSettings {
+    crate::settings::load_alert_settings()
+}
+
+#[tauri::command]
+fn save_alert_settings(
+    settings: crate::settings::AlertSettings,
+) -> Result<crate::settings::AlertSettings, String> {
+    for rule in &settings.rules {
+        alerts::CompiledRule::new(rule.clone())?;
+    }
+    crate::settings::save_alert_settings(settings)
+}
+
+#[tauri::command]
+fn get_syslog_listener_settings() -> crate::settings::SyslogListenerSettings {
+    crate::settings::load_syslog_listener_settings()
+}
+
+/// Saves the listener settings and starts, restarts or stops the listener to match them.
+#[tauri::command]
+async fn save_syslog_listener_settings(
+    settings: crate::settings::SyslogListenerSettings,
+) -> Result<syslog_listener::SyslogListenerStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let saved = crate::settings::save_syslog_listener_settings(settings)?;
+        if !saved.enabled {
+            syslog_listener::stop();
+            return Ok(syslog_listener::status());
+        }
+        syslog_listener::start(&saved)
+            .map_err(|error| command_error("syslog", "Failed to start syslog listener", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join syslog listener task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+fn get_syslog_listener_status() -> syslog_listener::SyslogListenerStatus {
+    syslog_listener::status()
+}
+
+#[tauri::command]
+fn get_parser_profiles() -> crate::settings::ParserProfileSettings {
+    crate::settings::load_parser_profiles()
+}
+
+#[tauri::command]
+fn save_parser_profiles(
+    settings: crate::settings::ParserProfileSettings,
+) -> Result<crate::settings::ParserProfileSettings, String> {
+    for profile in &settings.profiles {
+        log_import::ProfileParser::new(profile.clone())?;
+    }
+    crate::settings::save_parser_profiles(settings)
+}
+
+#[tauri::command]
+async fn get_alert_deliveries(limit: Option<u32>) -> Result<Vec<db::AlertDelivery>, String> {
+    let limit = limit.unwrap_or(100).clamp(1, 1000);
+    tauri::async_runtime::spawn_blocking(move || {
+        db::get_alert_deliveries(limit)
+            .map_err(|error| command_error("storage", "Failed to read alert deliveries", error))
+    })
+    .await
+    .map_err(|error| {
+        command_error(
+            "runtime",
+            "Failed to join alert delivery task",
+            error.to_string(),
+        )
+    })?
+}
+
+#[tauri::command]
+async fn send_test_alert(rule: crate::settings::AlertRule) -> Result<db::AlertDelivery, String> {
+    tauri::async_runtime::spawn_blocking(move || alerts::send_test_alert(rule))
+        .await
+        .map_err(|error| {
+            command_error("runtime", "Failed to join test alert task", error.to_string())
+        })?
+}
+
+#[tauri::command]
+fn save_integration_secret(integration_id: String, secret: String) -> Result<(), String> {
+    crate::settings::set_integration_secret(&integration_id, &secret)
+}
+
+#[tauri::command]
+fn clear_integration_secret(integration_id: String) -> Result<(), String> {
+    crate::settings::clear_integration_secret(&integration_id)
+}
+
 #[tauri::command]
 fn save_remote_profile_secret(profile_id: String, secret: String) -> Result<(), String> {
     crate::settings::set_remote_profile_secret(&profile_id, &secret)
@@ -2525,6 +6119,16 @@ fn clear_remote_provider_secret(provider_id: String) -> Result<(), String> {
     crate::settings::clear_remote_provider_secret(&provider_id)
 }
 
+#[tauri::command]
+fn save_archive_store_secret(secret: String) -> Result<(), String> {
+    crate::settings::set_archive_store_secret(&secret)
+}
+
+#[tauri::command]
+fn clear_archive_store_secret() -> Result<(), String> {
+    crate::settings::clear_archive_store_secret()
+}
+
 #[tauri::command]
 async fn test_remote_connection(
     profile: RemoteConnectionProfile,
@@ -2549,28 +6153,9 @@ async fn test_remote_connection(
                 provider_secret.as_deref(),
             ))
         } else if os == "linux" && protocol == "ssh" {
-            let mut ssh_args = vec![
-                "-o".to_string(),
-                "BatchMode=yes".to_string(),
-                "-o".to_string(),
-                "StrictHostKeyChecking=no".to_string(),
-                "-o".to_string(),
-                "ConnectTimeout=10".to_string(),
-            ];
-            if let Some(key_path) = &profile.ssh_key_path {
-                if !key_path.trim().is_empty() {
-                    ssh_args.push("-i".to_string());
-                    ssh_args.push(key_path.trim().to_string());
-                }
-            }
-            let user_host = if profile.username.trim().is_empty() {
-                profile.host.trim().to_string()
-            } else {
-                format!("{}@{}", profile.username.trim(), profile.host.trim())
-            };
-            ssh_args.push(user_host);
-            ssh_args.push("journalctl --version >/dev/null".to_string());
-            let output = Command::new("ssh").args(&ssh_args).output();
+            let output =
+                crate::remote_common::ssh_command(&profile, "journalctl --version >/dev/null")
+                    .output();
             let result = match output {
                 Ok(output) if output.status.success() => RemoteConnectionTestResult {
                     ok: true,
@@ -2692,99 +6277,37 @@ fn sanitize_filename(filename: &str, extension: &str) -> String {
     clean
 }
 
-fn csv_escape(value: &str) -> String {
-    let text = csv_formula_safe(value);
-    if text.contains(',') || text.contains('"') || text.contains('\n') || text.contains('\r') {
-        format!("\"{}\"", text.replace('"', "\"\""))
+/// File name for an export, keeping a compression suffix last, e.g. `events.csv.gz`.
+fn export_file_name(
+    filename: &str,
+    extension: &str,
+    compression: Option<event_export::ExportCompression>,
+) -> String {
+    let Some(compression) = compression else {
+        return sanitize_filename(filename, extension);
+    };
+    let suffix = format!(".{}", compression.extension());
+    let base = if filename.to_ascii_lowercase().ends_with(suffix.as_str()) {
+        &filename[..filename.len() - suffix.len()]
     } else {
-        text
-    }
-}
-
-fn csv_formula_safe(value: &str) -> String {
-    let leading_trimmed = value.trim_start_matches([' ', '\t', '\r', '\n']);
-    match leading_trimmed.chars().next() {
-        Some('=') | Some('+') | Some('-') | Some('@') => format!("'{value}"),
-        _ => value.to_string(),
-    }
-}
-
-fn build_csv(events: &[NormalizedEvent]) -> String {
-    let mut lines = Vec::with_capacity(events.len() + 1);
-    lines
-        .push("timestamp,os,logName,category,provider,eventId,severity,message,source".to_string());
-
-    for event in events {
-        let row = [
-            csv_escape(event.timestamp.as_str()),
-            csv_escape(event.os.as_str()),
-            csv_escape(event.log_name.as_str()),
-            csv_escape(event.category.as_str()),
-            csv_escape(event.provider.as_str()),
-            csv_escape(
-                event
-                    .event_id
-                    .map(|id| id.to_string())
-                    .unwrap_or_default()
-                    .as_str(),
-            ),
-            csv_escape(event.severity.as_str()),
-            csv_escape(event.message.as_str()),
-            csv_escape(if event.imported {
-                "Imported"
-            } else {
-                "Live/Local"
-            }),
-        ]
-        .join(",");
-        lines.push(row);
-    }
-
-    lines.join("\n")
-}
-
-fn build_plain_text(events: &[NormalizedEvent]) -> String {
-    let mut lines = Vec::with_capacity(events.len() * 10);
-    for event in events {
-        lines.push(format!("Timestamp: {}", event.timestamp));
-        lines.push(format!("OS: {}", event.os));
-        lines.push(format!("Type: {} / {}", event.log_name, event.category));
-        lines.push(format!("Provider: {}", event.provider));
-        lines.push(format!(
-            "Event ID: {}",
-            event
-                .event_id
-                .map(|id| id.to_string())
-                .unwrap_or_else(|| "-".to_string())
-        ));
-        lines.push(format!("Severity: {}", event.severity));
-        lines.push(format!(
-            "Source: {}",
-            if event.imported {
-                "Imported"
-            } else {
-                "Live/Local"
-            }
-        ));
-        lines.push(format!("Message: {}", event.message));
-        lines.push("---".to_string());
-    }
-    lines.join("\n")
+        filename
+    };
+    format!("{}{suffix}", sanitize_filename(base, extension))
 }
 
-fn build_export_payload(extension: &str, events: &[NormalizedEvent]) -> Result<String, String> {
-    match extension {
-        "json" => serde_json::to_string_pretty(events).map_err(|error| {
-            command_error(
-                "runtime",
-                "Failed to serialize export JSON payload",
-                error.to_string(),
-            )
-        }),
-        "csv" => Ok(build_csv(events)),
-        "txt" => Ok(build_plain_text(events)),
-        _ => Err("Unsupported export format.".to_string()),
+fn write_events_file(
+    path: &Path,
+    extension: &str,
+    compression: Option<event_export::ExportCompression>,
+    events: &[NormalizedEvent],
+) -> Result<(), String> {
+    let timezone = load_display_timezone();
+    let written = event_export::write_export_file(path, extension, compression, events, timezone);
+    if let Err(error) = written {
+        let _ = std::fs::remove_file(path);
+        return Err(command_error("storage", "Failed to write export file", error));
     }
+    Ok(())
 }
 
 fn main() {
@@ -2804,9 +6327,24 @@ fn main() {
 
     diagnostics::info("startup", "Launching Hermes application");
     configure_linux_runtime_defaults();
+    if let Err(error) = workspaces::restore_active() {
+        diagnostics::warn(
+            "storage",
+            format!("Failed to open the last workspace: {error}"),
+        );
+    }
 
     let builder = tauri::Builder::default()
-        .setup(setup_menu)
+        .setup(|app| {
+            setup_menu(app)?;
+            crash_watcher::start(app.handle().clone());
+            health::start(app.handle().clone());
+            scheduler::start(app.handle().clone(), scheduled_refresh);
+            retention::start();
+            forwarding::start();
+            syslog_listener::start_if_enabled();
+            Ok(())
+        })
         .on_menu_event(|app, event| {
             let menu_id = event.id().as_ref().to_string();
             diagnostics::info("runtime", format!("Menu event received: id='{menu_id}'"));
@@ -2848,6 +6386,18 @@ fn main() {
             clear_remote_profile_secret,
             save_remote_provider_secret,
             clear_remote_provider_secret,
+            save_archive_store_secret,
+            clear_archive_store_secret,
+            get_integration_settings,
+            save_integration_settings,
+            get_alert_settings,
+            save_alert_settings,
+            get_alert_deliveries,
+            send_test_alert,
+            save_integration_secret,
+            clear_integration_secret,
+            push_events_to_elasticsearch,
+            push_events_to_syslog,
             test_remote_connection,
             open_external_url,
             restart_elevated,
@@ -2855,14 +6405,89 @@ fn main() {
             get_local_events,
             get_local_events_range,
             get_local_events_window,
+            get_timeline,
+            get_event_histogram,
+            analyze_power_events,
+            analyze_boot_performance,
+            build_timeline,
+            build_incident_report,
+            bookmark_event,
+            remove_event_bookmark,
+            get_event_bookmarks,
+            get_event_raw,
+            start_query_recording,
+            stop_query_recording,
+            get_active_query_session,
+            record_query_step,
+            list_query_sessions,
+            export_query_session,
+            delete_query_recording,
+            analyze_app_hangs,
+            detect_anomalies,
+            get_log_patterns,
+            list_event_publishers,
+            get_publisher_metadata,
+            get_print_issues,
+            analyze_remote_access,
+            collect_smart_metrics,
+            collect_reliability_data,
+            get_metrics,
+            capture_inventory_snapshot,
+            get_inventory_changes,
+            get_stability_index,
+            preview_forwarding,
+            get_forwarding_status,
+            start_event_replay,
+            stop_event_replay,
+            get_event_replay_status,
+            flush_forwarding,
+            get_app_health,
+            run_preflight_checks,
+            get_sync_history,
+            start_refresh_job,
+            start_log_import_job,
+            get_job_status,
             import_host_crashes,
+            get_wer_dump_status,
+            enable_wer_local_dumps,
             get_crashes,
+            get_source_hosts,
+            export_crashes,
+            import_crashes_file,
+            import_events_file,
+            import_database,
+            import_log_file,
+            import_log_directory,
+            get_parser_profiles,
+            get_syslog_listener_settings,
+            save_syslog_listener_settings,
+            get_syslog_listener_status,
+            save_parser_profiles,
+            export_case,
+            import_case,
             analyze_minidump,
+            explain_crash_code,
             cleanup_local_duplicate_events,
             get_crash_related_events,
             get_ingest_window_days,
             set_ingest_window_days,
             get_ingest_profile,
+            get_storage_quota_status,
+            run_retention_now,
+            search_archive,
+            rehydrate_archive_days,
+            get_db_stats,
+            compact_database,
+            get_encryption_status,
+            set_database_encryption,
+            list_workspaces,
+            create_workspace,
+            open_workspace,
+            archive_workspace,
+            list_saved_filters,
+            save_saved_filter,
+            delete_saved_filter,
+            export_saved_filter,
             set_ingest_profile,
             get_llm_settings,
             set_llm_settings,
@@ -2873,30 +6498,79 @@ fn main() {
             scan_lan_llm_providers,
             test_llm_profile_connection,
             analyze_with_local_llm,
+            check_llm_provider,
+            analyze_with_local_llm_stream,
+            cancel_llm_stream,
+            analyze_crash,
+            summarize_events,
+            translate_event_query,
+            preview_timestamp_parsing,
+            find_similar_events,
+            create_chat_session,
+            list_chat_sessions,
+            get_chat_messages,
+            send_chat_message,
+            delete_chat_session,
+            export_chat_session,
             open_path_in_shell,
             backfill_local_events,
             estimate_local_events_range,
             estimate_refresh_local_events,
             sync_local_events_range,
             sync_local_events_window,
+            suggest_crash_collection,
+            get_recent_changes,
+            match_known_issues,
+            list_sigma_rules,
+            import_sigma_rules,
+            delete_sigma_rule,
+            run_sigma_scan,
+            list_sigma_detections,
+            analyze_logons,
+            import_ioc_feed,
+            list_ioc_feeds,
+            delete_ioc_feed,
+            run_ioc_scan,
+            list_ioc_matches,
+            get_usb_device_activity,
+            get_repeated_events,
+            search_events_regex,
+            get_system_changes,
+            get_display_timezone,
+            set_display_timezone,
+            list_timezones,
             get_export_directory,
             choose_export_directory,
             set_export_directory,
             export_events,
             export_events_with_dialog,
+            export_query,
             save_text_with_dialog,
             quit_app,
             set_app_theme,
             get_saved_theme
         ]);
 
-    if let Err(error) = builder.run(tauri::generate_context!()) {
-        diagnostics::error(
-            "startup",
-            format!("Error while running Tauri application: {error}"),
-        );
-        panic!("error while running tauri application: {error}");
-    }
+    let app = match builder.build(tauri::generate_context!()) {
+        Ok(app) => app,
+        Err(error) => {
+            diagnostics::error(
+                "startup",
+                format!("Error while running Tauri application: {error}"),
+            );
+            panic!("error while running tauri application: {error}");
+        }
+    };
+    app.run(|_, event| {
+        if let tauri::RunEvent::Exit = event {
+            if let Err(error) = db::checkpoint() {
+                diagnostics::warn(
+                    "storage",
+                    format!("Failed to checkpoint database on exit: {error}"),
+                );
+            }
+        }
+    });
 }
 
 #[cfg(test)]
@@ -3018,6 +6692,130 @@ mod tests {
         }
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn retention_holds_rehydrated_events() {
+        let data_home = TempXdgDataHome::new("rehydrate-hold");
+        let archive_root = data_home.root.join("archive");
+        fs::create_dir_all(&archive_root).expect("create archive folder");
+        let now: DateTime<Utc> = "2026-03-10T12:00:00Z".parse().expect("now");
+        let event = |timestamp: &str, message: &str| {
+            let mut event = NormalizedEvent::new(
+                SupportedOs::Windows,
+                "System",
+                "system",
+                "Disk",
+                Some(7),
+                "error",
+                message,
+                "localhost",
+            );
+            event.timestamp = timestamp.to_string();
+            event.assign_stable_id();
+            event
+        };
+        let archived = event("2026-01-05T08:00:00Z", "bad block");
+        let stale = event("2026-01-06T08:00:00Z", "retry succeeded");
+        let recent = event("2026-03-09T08:00:00Z", "bad block");
+        let store = archive_store::ArchiveStore::Folder(archive_root);
+        archive::archive_events(&store, vec![archived.clone()], now).expect("archive old day");
+        archive::rehydrate_days(&store, &["2026-01-05".to_string()], now)
+            .expect("rehydrate old day");
+        save_local_events(&[stale.clone(), recent.clone()]).expect("save live events");
+
+        let profile = settings::IngestProfile {
+            retention: settings::RetentionPolicy {
+                max_age_days: 30,
+                max_events: 1,
+                ..settings::RetentionPolicy::default()
+            },
+            ..settings::IngestProfile::default()
+        };
+        let ids = vec![archived.id.clone(), stale.id.clone(), recent.id.clone()];
+        let kept = || {
+            let mut kept = db::get_events_by_ids(ids.as_slice())
+                .expect("read events")
+                .into_iter()
+                .map(|event| event.id)
+                .collect::<Vec<_>>();
+            kept.sort();
+            kept
+        };
+        let mut expected = vec![archived.id.clone(), recent.id.clone()];
+        expected.sort();
+
+        // The rehydrated event is older than the age limit and does not count toward the event
+        // limit, so only the stale live event goes.
+        let report = retention::apply_retention(&profile, now).expect("apply retention");
+        assert_eq!(report.deleted_events, 1);
+        assert_eq!(kept(), expected);
+
+        // Once the hold runs out the rehydrated copy goes; the archive still has it.
+        retention::apply_retention(&profile, now + chrono::Duration::days(8))
+            .expect("apply retention after hold");
+        assert_eq!(kept(), vec![recent.id.clone()]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn archived_workspace_keeps_writes_left_in_its_log() {
+        let _data_home = TempXdgDataHome::new("workspace-archive");
+        // Points the database back at events.db even if an assert fails.
+        struct DefaultDatabase;
+        impl Drop for DefaultDatabase {
+            fn drop(&mut self) {
+                let _ = db::set_database_file(None);
+            }
+        }
+        let _default = DefaultDatabase;
+
+        let workspace = workspaces::create_workspace("Case 42").expect("create workspace");
+        workspaces::open_workspace(workspace.id.as_str()).expect("open workspace");
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Linux,
+            "syslog",
+            "system",
+            "sshd",
+            None,
+            "error",
+            "Connection reset",
+            "localhost",
+        );
+        event.assign_stable_id();
+        save_local_events(&[event.clone()]).expect("save event");
+        let path = db::db_path().expect("workspace database");
+        workspaces::open_workspace(settings::DEFAULT_WORKSPACE_ID).expect("open default");
+        // The first connection to events.db closes the pooled ones to the workspace.
+        db::count_events().expect("open default database");
+
+        // A write still in the log, as after a crash.
+        let conn = rusqlite::Connection::open(&path).expect("open workspace file");
+        conn.set_db_config(
+            rusqlite::config::DbConfig::SQLITE_DBCONFIG_NO_CKPT_ON_CLOSE,
+            true,
+        )
+        .expect("keep log on close");
+        conn.execute("UPDATE events SET message = 'Connection reset by peer'", [])
+            .expect("write to log");
+        drop(conn);
+        let mut wal = path.clone().into_os_string();
+        wal.push("-wal");
+        assert!(fs::metadata(PathBuf::from(&wal)).expect("log file").len() > 0);
+
+        let archived = workspaces::archive_workspace(workspace.id.as_str()).expect("archive");
+        assert!(archived
+            .workspaces
+            .iter()
+            .any(|entry| entry.id == workspace.id && entry.archived));
+        assert!(!path.exists());
+        assert!(!PathBuf::from(&wal).exists());
+
+        workspaces::open_workspace(workspace.id.as_str()).expect("restore workspace");
+        let restored = db::get_events_by_ids(&[event.id.clone()]).expect("read restored events");
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].message, "Connection reset by peer");
+    }
+
     #[cfg(target_os = "linux")]
     fn system_has_coredumps() -> bool {
         let Ok(output) = Command::new("coredumpctl")
@@ -3053,6 +6851,8 @@ mod tests {
             Some(now),
             Some(500),
             None,
+            None,
+            None,
             false,
         );
 
@@ -3088,7 +6888,7 @@ mod tests {
     fn linux_live_imports_and_analyzes_core_dumps_when_present() {
         let _temp_data_home = TempXdgDataHome::new("linux-live-crashes");
         let has_coredumps = system_has_coredumps();
-        let crashes = collect_host_crashes(200).expect("collect live Linux crashes");
+        let crashes = collect_host_crashes(200, None).expect("collect live Linux crashes");
 
         if has_coredumps {
             assert!(
@@ -3137,6 +6937,7 @@ mod tests {
                 model: String::new(),
                 enabled: true,
                 api_key_configured: false,
+                context_window_tokens: 0,
             },
             LlmConnectionProfile {
                 id: "linux-live-lmstudio".to_string(),
@@ -3147,6 +6948,7 @@ mod tests {
                 model: String::new(),
                 enabled: true,
                 api_key_configured: false,
+                context_window_tokens: 0,
             },
         ];
 
@@ -3194,4 +6996,98 @@ mod tests {
             "Expected local LLM analysis to return non-empty text."
         );
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn regex_search_runs_through_sqlite_regexp() {
+        let _data_home = TempXdgDataHome::new("regex-search");
+        let event = |index: usize, host: &str, message: &str| {
+            let mut event = NormalizedEvent::new(
+                SupportedOs::Linux,
+                "journal",
+                "system",
+                "systemd",
+                None,
+                "error",
+                message,
+                host,
+            );
+            event.timestamp = format!("2026-03-10T12:{:02}:{:02}Z", index / 60, index % 60);
+            event.assign_stable_id();
+            event
+        };
+        save_local_events(&[
+            event(0, "web-1", "Failed to start nginx.service"),
+            event(1, "web-1", "Started nginx.service"),
+            event(2, "db-1", "failed to start postgresql.service"),
+            event(3, "db-1", "Failed to start postgresql.service"),
+        ])
+        .expect("save events");
+
+        let result = regex_search::search_events(r"(?i)^failed to start \w+\.service$", None, 2)
+            .expect("search");
+        let messages = result
+            .events
+            .iter()
+            .map(|event| (event.source_host.as_str(), event.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                ("db-1", "Failed to start postgresql.service"),
+                ("db-1", "failed to start postgresql.service"),
+            ]
+        );
+        assert!(result.truncated);
+        assert!(!result.timed_out);
+
+        let on_host =
+            regex_search::search_events(r"^Failed", Some("web-1"), 10).expect("search host");
+        assert_eq!(on_host.events.len(), 1);
+        assert_eq!(on_host.events[0].message, "Failed to start nginx.service");
+        assert!(!on_host.truncated);
+
+        assert!(db::search_events_regex("(unclosed", None, 10, Duration::from_secs(10)).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn regex_search_stops_at_its_time_limit() {
+        let _data_home = TempXdgDataHome::new("regex-timeout");
+        let long_run = "a".repeat(200);
+        let events = (0..3000)
+            .map(|index| {
+                let mut event = NormalizedEvent::new(
+                    SupportedOs::Linux,
+                    "journal",
+                    "system",
+                    "app",
+                    None,
+                    "info",
+                    format!("{long_run}{index}").as_str(),
+                    "localhost",
+                );
+                event.timestamp =
+                    format!("2026-03-10T{:02}:{:02}:00Z", index / 60 % 24, index % 60);
+                event.assign_stable_id();
+                event
+            })
+            .collect::<Vec<_>>();
+        save_local_events(&events).expect("save events");
+
+        // Backtracks exponentially in engines that backtrack; the regex crate stays linear.
+        let pathological = "^(a|aa)+$";
+        let (matches, timed_out) =
+            db::search_events_regex(pathological, None, 10, Duration::from_secs(30))
+                .expect("finish pathological scan");
+        assert!(matches.is_empty());
+        assert!(!timed_out);
+
+        let (_, timed_out) = db::search_events_regex(pathological, None, 10, Duration::ZERO)
+            .expect("interrupt scan");
+        assert!(timed_out);
+        let after = regex_search::search_events("a+0$", None, 10).expect("search after timeout");
+        assert!(!after.timed_out);
+        assert!(!after.events.is_empty());
+    }
 }
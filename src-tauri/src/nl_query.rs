@@ -0,0 +1,248 @@
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+const SEVERITIES: [&str; 4] = ["information", "warning", "error", "critical"];
+const CATEGORIES: [&str; 7] = [
+    "application",
+    "security",
+    "system",
+    "audit",
+    "printing",
+    "updates",
+    "other",
+];
+const ALLOWED_KEYS: [&str; 8] = [
+    "text",
+    "severities",
+    "logName",
+    "category",
+    "eventId",
+    "provider",
+    "start",
+    "end",
+];
+const MAX_TEXT_CHARS: usize = 200;
+/// Matches the largest window `get_local_events_window` accepts.
+const MAX_WINDOW_DAYS: i64 = 7;
+
+/// Structured filter for the stored events. Every field is optional; an empty filter matches all.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventQueryFilter {
+    pub text: Option<String>,
+    /// Severities to include; empty means all.
+    pub severities: Vec<String>,
+    pub log_name: Option<String>,
+    pub category: Option<String>,
+    pub event_id: Option<u32>,
+    pub provider: Option<String>,
+    /// Any of these providers, case-insensitive; used by saved filters.
+    #[serde(default)]
+    pub providers: Vec<String>,
+    /// RFC3339 bounds suitable for `get_local_events_window`.
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+/// Builds the translation prompt. The model only ever sees the question and the allowed values,
+/// never stored events, and must answer with the filter object alone.
+pub fn build_filter_prompt(
+    question: &str,
+    now: DateTime<FixedOffset>,
+    log_names: &[String],
+) -> String {
+    let logs = if log_names.is_empty() {
+        "any".to_string()
+    } else {
+        log_names.join(", ")
+    };
+    [
+        "Translate the log search request below into a JSON filter for an event viewer.".to_string(),
+        "Respond with a single JSON object and nothing else. Use only these keys and omit any you do not need:".to_string(),
+        "{\"text\": string, \"severities\": [\"information\" | \"warning\" | \"error\" | \"critical\"], \"logName\": string, \"category\": string, \"eventId\": integer, \"provider\": string, \"start\": RFC3339 timestamp, \"end\": RFC3339 timestamp}".to_string(),
+        format!("Allowed categories: {}.", CATEGORIES.join(", ")),
+        format!("Allowed log names: {logs}."),
+        format!(
+            "The current local time is {}. Resolve relative times such as \"yesterday afternoon\" against it and keep the UTC offset.",
+            now.to_rfc3339()
+        ),
+        "Use Windows event IDs when the request names a well-known event, for example 4625 for failed logons.".to_string(),
+        String::new(),
+        format!("Request: {}", question.trim()),
+    ]
+    .join("\n")
+}
+
+fn clean_text(value: &str) -> Option<String> {
+    let cleaned: String = value
+        .chars()
+        .filter(|ch| !ch.is_control())
+        .take(MAX_TEXT_CHARS)
+        .collect();
+    let trimmed = cleaned.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+fn string_field(object: &Map<String, Value>, key: &str) -> Option<String> {
+    object.get(key).and_then(Value::as_str).and_then(clean_text)
+}
+
+fn parse_bound(
+    object: &Map<String, Value>,
+    key: &str,
+    notes: &mut Vec<String>,
+) -> Option<DateTime<Utc>> {
+    let raw = string_field(object, key)?;
+    match DateTime::parse_from_rfc3339(raw.as_str()) {
+        Ok(value) => Some(value.with_timezone(&Utc)),
+        Err(_) => {
+            notes.push(format!(
+                "Ignored {key} time '{raw}' because it is not RFC3339."
+            ));
+            None
+        }
+    }
+}
+
+/// Parses and validates a model reply into a filter. Unknown keys and out-of-range values are
+/// dropped with a note rather than passed through, so the result is always safe to apply.
+pub fn validate_filter_response(
+    response: &str,
+    now: DateTime<Utc>,
+    log_names: &[String],
+) -> Result<(EventQueryFilter, Vec<String>), String> {
+    let value = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => {
+            serde_json::from_str::<Value>(&response[start..=end]).ok()
+        }
+        _ => None,
+    };
+    let Some(Value::Object(object)) = value else {
+        return Err("The model did not return a JSON filter.".to_string());
+    };
+
+    let mut notes = Vec::new();
+    for key in object.keys() {
+        if !ALLOWED_KEYS.contains(&key.as_str()) {
+            notes.push(format!("Ignored unsupported filter field '{key}'."));
+        }
+    }
+
+    let mut filter = EventQueryFilter {
+        text: string_field(&object, "text"),
+        provider: string_field(&object, "provider"),
+        ..EventQueryFilter::default()
+    };
+
+    if let Some(items) = object.get("severities").and_then(Value::as_array) {
+        for item in items.iter().filter_map(Value::as_str) {
+            let normalized = match item.trim().to_ascii_lowercase().as_str() {
+                "info" | "informational" => "information".to_string(),
+                other => other.to_string(),
+            };
+            if SEVERITIES.contains(&normalized.as_str()) {
+                if !filter.severities.contains(&normalized) {
+                    filter.severities.push(normalized);
+                }
+            } else {
+                notes.push(format!("Ignored unknown severity '{item}'."));
+            }
+        }
+    }
+
+    if let Some(log_name) = string_field(&object, "logName") {
+        match log_names
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(log_name.as_str()))
+        {
+            Some(known) => filter.log_name = Some(known.clone()),
+            None if log_names.is_empty() => filter.log_name = Some(log_name),
+            None => notes.push(format!(
+                "Ignored log '{log_name}' because no events are stored for it."
+            )),
+        }
+    }
+
+    if let Some(category) = string_field(&object, "category") {
+        let lower = category.to_ascii_lowercase();
+        if CATEGORIES.contains(&lower.as_str()) {
+            filter.category = Some(lower);
+        } else {
+            notes.push(format!("Ignored unknown category '{category}'."));
+        }
+    }
+
+    match object.get("eventId") {
+        None | Some(Value::Null) => {}
+        Some(value) => match value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|text| text.trim().parse().ok()))
+        {
+            Some(id) if id <= u64::from(u16::MAX) => filter.event_id = Some(id as u32),
+            _ => notes.push(format!("Ignored invalid event ID {value}.")),
+        },
+    }
+
+    let mut start = parse_bound(&object, "start", &mut notes);
+    let mut end = parse_bound(&object, "end", &mut notes);
+    if let (Some(left), Some(right)) = (start, end) {
+        if left > right {
+            start = Some(right);
+            end = Some(left);
+        }
+    }
+    if end.is_some_and(|value| value > now) {
+        end = Some(now);
+    }
+    if start.is_some() && end.is_none() {
+        end = Some(now);
+    }
+    if let (Some(left), Some(right)) = (start, end) {
+        if right - left > Duration::days(MAX_WINDOW_DAYS) {
+            start = Some(right - Duration::days(MAX_WINDOW_DAYS));
+            notes.push(format!(
+                "Time range shortened to the last {MAX_WINDOW_DAYS} days of the request."
+            ));
+        }
+    }
+    filter.start = start.map(|value| value.to_rfc3339());
+    filter.end = end.map(|value| value.to_rfc3339());
+
+    Ok((filter, notes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-02T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn validates_a_typical_reply() {
+        let reply = "```json\n{\"severities\": [\"Error\", \"info\"], \"logName\": \"security\", \"eventId\": 4625, \"start\": \"2026-03-01T12:00:00+01:00\", \"end\": \"2026-03-01T18:00:00+01:00\"}\n```";
+        let (filter, notes) =
+            validate_filter_response(reply, now(), &["Security".to_string()]).unwrap();
+        assert!(notes.is_empty());
+        assert_eq!(filter.severities, vec!["error", "information"]);
+        assert_eq!(filter.log_name.as_deref(), Some("Security"));
+        assert_eq!(filter.event_id, Some(4625));
+        assert_eq!(filter.start.as_deref(), Some("2026-03-01T11:00:00+00:00"));
+    }
+
+    #[test]
+    fn drops_unsafe_or_unknown_values() {
+        let reply = "{\"sql\": \"DROP TABLE events\", \"category\": \"kernel\", \"eventId\": 999999, \"start\": \"2026-01-01T00:00:00Z\", \"end\": \"2026-04-01T00:00:00Z\"}";
+        let (filter, notes) = validate_filter_response(reply, now(), &[]).unwrap();
+        assert_eq!(filter.category, None);
+        assert_eq!(filter.event_id, None);
+        assert_eq!(filter.end.as_deref(), Some("2026-03-02T09:00:00+00:00"));
+        assert_eq!(filter.start.as_deref(), Some("2026-02-23T09:00:00+00:00"));
+        assert_eq!(notes.len(), 4);
+        assert!(validate_filter_response("no idea", now(), &[]).is_err());
+    }
+}
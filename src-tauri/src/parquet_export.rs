@@ -0,0 +1,228 @@
+use crate::event_export::{EVENT_EXPORT_SCHEMA, EVENT_EXPORT_SCHEMA_VERSION};
+use crate::logs::NormalizedEvent;
+use crate::timestamps::parse_timestamp;
+use arrow_array::builder::{
+    BooleanBuilder, StringBuilder, StringDictionaryBuilder, TimestampMicrosecondBuilder,
+    UInt32Builder,
+};
+use arrow_array::types::Int32Type;
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Rows buffered before a record batch is handed to the writer.
+const BATCH_ROWS: usize = 8192;
+
+fn dictionary_field(name: &str) -> Field {
+    Field::new(
+        name,
+        DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+        false,
+    )
+}
+
+/// File-level key/value metadata matching the JSON export header.
+fn export_metadata() -> Vec<KeyValue> {
+    vec![
+        KeyValue::new("schema".to_string(), EVENT_EXPORT_SCHEMA.to_string()),
+        KeyValue::new(
+            "schemaVersion".to_string(),
+            EVENT_EXPORT_SCHEMA_VERSION.to_string(),
+        ),
+    ]
+}
+
+/// Column layout for event exports. Low-cardinality text is dictionary encoded and the
+/// timestamp is a real UTC timestamp so DuckDB and Spark can filter on it without casting.
+pub fn event_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
+        dictionary_field("os"),
+        dictionary_field("logName"),
+        dictionary_field("category"),
+        dictionary_field("provider"),
+        Field::new("eventId", DataType::UInt32, true),
+        dictionary_field("severity"),
+        Field::new("message", DataType::Utf8, false),
+        dictionary_field("sourceHost"),
+        Field::new("imported", DataType::Boolean, false),
+    ]))
+}
+
+#[derive(Default)]
+struct EventColumns {
+    id: StringBuilder,
+    timestamp: TimestampMicrosecondBuilder,
+    os: StringDictionaryBuilder<Int32Type>,
+    log_name: StringDictionaryBuilder<Int32Type>,
+    category: StringDictionaryBuilder<Int32Type>,
+    provider: StringDictionaryBuilder<Int32Type>,
+    event_id: UInt32Builder,
+    severity: StringDictionaryBuilder<Int32Type>,
+    message: StringBuilder,
+    source_host: StringDictionaryBuilder<Int32Type>,
+    imported: BooleanBuilder,
+    rows: usize,
+}
+
+impl EventColumns {
+    fn push(&mut self, event: &NormalizedEvent) {
+        self.id.append_value(event.id.as_str());
+        self.timestamp.append_option(
+            parse_timestamp(event.timestamp.as_str()).map(|parsed| parsed.timestamp_micros()),
+        );
+        self.os.append_value(event.os.as_str());
+        self.log_name.append_value(event.log_name.as_str());
+        self.category.append_value(event.category.as_str());
+        self.provider.append_value(event.provider.as_str());
+        self.event_id.append_option(event.event_id);
+        self.severity.append_value(event.severity.as_str());
+        self.message.append_value(event.message.as_str());
+        self.source_host.append_value(event.source_host.as_str());
+        self.imported.append_value(event.imported);
+        self.rows += 1;
+    }
+
+    fn finish(&mut self, schema: &SchemaRef) -> Result<RecordBatch, String> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(self.id.finish()),
+            Arc::new(self.timestamp.finish().with_timezone("UTC")),
+            Arc::new(self.os.finish()),
+            Arc::new(self.log_name.finish()),
+            Arc::new(self.category.finish()),
+            Arc::new(self.provider.finish()),
+            Arc::new(self.event_id.finish()),
+            Arc::new(self.severity.finish()),
+            Arc::new(self.message.finish()),
+            Arc::new(self.source_host.finish()),
+            Arc::new(self.imported.finish()),
+        ];
+        self.rows = 0;
+        RecordBatch::try_new(schema.clone(), columns)
+            .map_err(|error| format!("Failed to build Parquet batch: {error}"))
+    }
+}
+
+/// Streams events into a Snappy-compressed Parquet file in fixed-size batches.
+pub struct ParquetEventWriter<W: Write + Send> {
+    writer: ArrowWriter<W>,
+    schema: SchemaRef,
+    columns: EventColumns,
+    written: usize,
+}
+
+impl<W: Write + Send> ParquetEventWriter<W> {
+    pub fn start(out: W) -> Result<Self, String> {
+        let schema = event_schema();
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_key_value_metadata(Some(export_metadata()))
+            .build();
+        let writer = ArrowWriter::try_new(out, schema.clone(), Some(properties))
+            .map_err(|error| format!("Failed to start Parquet export: {error}"))?;
+        Ok(Self {
+            writer,
+            schema,
+            columns: EventColumns::default(),
+            written: 0,
+        })
+    }
+
+    pub fn write_event(&mut self, event: &NormalizedEvent) -> Result<(), String> {
+        self.columns.push(event);
+        self.written += 1;
+        if self.columns.rows >= BATCH_ROWS {
+            self.flush_batch()?;
+        }
+        Ok(())
+    }
+
+    fn flush_batch(&mut self) -> Result<(), String> {
+        if self.columns.rows == 0 {
+            return Ok(());
+        }
+        let batch = self.columns.finish(&self.schema)?;
+        self.writer
+            .write(&batch)
+            .map_err(|error| format!("Failed to write Parquet batch: {error}"))
+    }
+
+    pub fn finish(mut self) -> Result<(W, usize), String> {
+        self.flush_batch()?;
+        let out = self
+            .writer
+            .into_inner()
+            .map_err(|error| format!("Failed to finish Parquet export: {error}"))?;
+        Ok((out, self.written))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::TimestampMicrosecondType;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn round_trips_events_with_typed_columns() {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "System",
+            "system",
+            "disk",
+            Some(7),
+            "error",
+            "bad block",
+            "host-a",
+        );
+        event.timestamp = "2024-03-01T10:00:00Z".to_string();
+
+        let path = std::env::temp_dir().join(format!("hermes-{}.parquet", uuid::Uuid::new_v4()));
+        let mut writer = ParquetEventWriter::start(std::fs::File::create(&path).unwrap()).unwrap();
+        writer.write_event(&event).unwrap();
+        let (_, written) = writer.finish().unwrap();
+        assert_eq!(written, 1);
+
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap()).unwrap();
+        let metadata = builder
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap();
+        assert!(metadata
+            .iter()
+            .any(|entry| entry.key == "schema"
+                && entry.value.as_deref() == Some(EVENT_EXPORT_SCHEMA)));
+        let reader = builder.build().unwrap();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let _ = std::fs::remove_file(&path);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 1);
+        let timestamps = batch
+            .column_by_name("timestamp")
+            .unwrap()
+            .as_primitive::<TimestampMicrosecondType>();
+        assert_eq!(timestamps.value(0), 1_709_287_200_000_000);
+        assert_eq!(
+            batch
+                .column_by_name("message")
+                .unwrap()
+                .as_string::<i32>()
+                .value(0),
+            "bad block"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use crate::logs::NormalizedEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+const WILDCARD: &str = "<*>";
+/// Share of tokens that must match for a message to join an existing template.
+const SIMILARITY_THRESHOLD: f64 = 0.5;
+/// Long messages are clustered by their opening tokens only.
+const MAX_TOKENS: usize = 40;
+const MAX_EXAMPLES: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogPattern {
+    pub template: String,
+    pub count: usize,
+    pub providers: Vec<String>,
+    pub severities: BTreeMap<String, usize>,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub examples: Vec<String>,
+}
+
+struct Cluster {
+    tokens: Vec<String>,
+    count: usize,
+    providers: BTreeMap<String, usize>,
+    severities: BTreeMap<String, usize>,
+    first_seen: String,
+    last_seen: String,
+    examples: Vec<String>,
+}
+
+/// Replaces tokens that are obviously variable (numbers, addresses, ids) before clustering.
+fn mask_token(token: &str) -> String {
+    if token.chars().any(|ch| ch.is_ascii_digit()) {
+        return WILDCARD.to_string();
+    }
+    let trimmed = token.trim_matches(|ch: char| !ch.is_alphanumeric());
+    let is_hex = trimmed.len() >= 8 && trimmed.chars().all(|ch| ch.is_ascii_hexdigit());
+    if is_hex {
+        return WILDCARD.to_string();
+    }
+    token.to_string()
+}
+
+fn tokenize(message: &str) -> Vec<String> {
+    message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .split_whitespace()
+        .take(MAX_TOKENS)
+        .map(mask_token)
+        .collect()
+}
+
+/// The masked first line of a message, so occurrences of one message template compare equal.
+pub fn message_template(message: &str) -> String {
+    tokenize(message).join(" ")
+}
+
+fn similarity(template: &[String], tokens: &[String]) -> f64 {
+    let matching = template
+        .iter()
+        .zip(tokens)
+        .filter(|(left, right)| left == right || left.as_str() == WILDCARD)
+        .count();
+    matching as f64 / template.len().max(1) as f64
+}
+
+/// Groups tokens by length and leading token, the two-level prefix tree of Drain.
+fn group_key(tokens: &[String]) -> (usize, String) {
+    let first = tokens.first().cloned().unwrap_or_default();
+    (tokens.len(), first)
+}
+
+/// Clusters event messages into templates Drain-style: messages with the same token count and
+/// leading token merge when enough tokens match, and differing positions become `<*>`.
+pub fn mine_log_patterns(events: &[NormalizedEvent], limit: usize) -> Vec<LogPattern> {
+    let mut groups: HashMap<(usize, String), Vec<Cluster>> = HashMap::new();
+
+    for event in events {
+        let tokens = tokenize(event.message.as_str());
+        if tokens.is_empty() {
+            continue;
+        }
+        let clusters = groups.entry(group_key(tokens.as_slice())).or_default();
+        let best = clusters
+            .iter_mut()
+            .map(|cluster| {
+                (
+                    similarity(cluster.tokens.as_slice(), tokens.as_slice()),
+                    cluster,
+                )
+            })
+            .filter(|(score, _)| *score >= SIMILARITY_THRESHOLD)
+            .max_by(|(left, _), (right, _)| left.total_cmp(right))
+            .map(|(_, cluster)| cluster);
+
+        let cluster = match best {
+            Some(cluster) => {
+                for (template, token) in cluster.tokens.iter_mut().zip(&tokens) {
+                    if template != token {
+                        *template = WILDCARD.to_string();
+                    }
+                }
+                cluster
+            }
+            None => {
+                clusters.push(Cluster {
+                    tokens: tokens.clone(),
+                    count: 0,
+                    providers: BTreeMap::new(),
+                    severities: BTreeMap::new(),
+                    first_seen: event.timestamp.clone(),
+                    last_seen: event.timestamp.clone(),
+                    examples: Vec::new(),
+                });
+                clusters.last_mut().expect("cluster was just pushed")
+            }
+        };
+
+        cluster.count += 1;
+        *cluster.providers.entry(event.provider.clone()).or_insert(0) += 1;
+        *cluster
+            .severities
+            .entry(event.severity.clone())
+            .or_insert(0) += 1;
+        if event.timestamp < cluster.first_seen {
+            cluster.first_seen = event.timestamp.clone();
+        }
+        if event.timestamp > cluster.last_seen {
+            cluster.last_seen = event.timestamp.clone();
+        }
+        if cluster.examples.len() < MAX_EXAMPLES && !cluster.examples.contains(&event.message) {
+            cluster.examples.push(event.message.clone());
+        }
+    }
+
+    let mut patterns: Vec<LogPattern> = groups
+        .into_values()
+        .flatten()
+        .map(|cluster| {
+            let mut providers: Vec<(String, usize)> = cluster.providers.into_iter().collect();
+            providers
+                .sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
+            LogPattern {
+                template: cluster.tokens.join(" "),
+                count: cluster.count,
+                providers: providers
+                    .into_iter()
+                    .map(|(provider, _)| provider)
+                    .collect(),
+                severities: cluster.severities,
+                first_seen: cluster.first_seen,
+                last_seen: cluster.last_seen,
+                examples: cluster.examples,
+            }
+        })
+        .collect();
+    patterns.sort_by(|left, right| {
+        right
+            .count
+            .cmp(&left.count)
+            .then_with(|| left.template.cmp(&right.template))
+    });
+    patterns.truncate(limit);
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: usize, provider: &str, message: &str) -> NormalizedEvent {
+        NormalizedEvent {
+            id: id.to_string(),
+            timestamp: format!("2026-03-01T10:{:02}:00Z", id % 60),
+            os: "linux".to_string(),
+            log_name: "journald".to_string(),
+            category: "system".to_string(),
+            provider: provider.to_string(),
+            event_id: None,
+            severity: "warning".to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn collapses_variable_messages_into_templates() {
+        let mut events: Vec<NormalizedEvent> = (0..20)
+            .map(|index| {
+                event(
+                    index,
+                    "NetworkManager",
+                    format!("Connection to 10.0.0.{index} timed out").as_str(),
+                )
+            })
+            .collect();
+        events.push(event(
+            20,
+            "sshd",
+            "Accepted publickey for alice from host-a",
+        ));
+        events.push(event(21, "sshd", "Accepted publickey for bob from host-b"));
+        events.push(event(22, "kernel", "Out of memory: Killed process"));
+
+        let patterns = mine_log_patterns(events.as_slice(), 10);
+        assert_eq!(patterns.len(), 3);
+        assert_eq!(patterns[0].template, "Connection to <*> timed out");
+        assert_eq!(patterns[0].count, 20);
+        assert_eq!(patterns[0].examples.len(), MAX_EXAMPLES);
+        assert_eq!(patterns[0].first_seen, "2026-03-01T10:00:00Z");
+        assert_eq!(patterns[0].last_seen, "2026-03-01T10:19:00Z");
+        assert_eq!(patterns[1].template, "Accepted publickey for <*> from <*>");
+        assert_eq!(patterns[1].providers, vec!["sshd".to_string()]);
+    }
+
+    #[test]
+    fn keeps_dissimilar_messages_apart() {
+        let events = vec![
+            event(0, "app", "Service started successfully"),
+            event(1, "app", "Service failed to start"),
+        ];
+        // Same leading token but different lengths never merge.
+        assert_eq!(mine_log_patterns(events.as_slice(), 10).len(), 2);
+    }
+}
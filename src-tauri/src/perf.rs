@@ -0,0 +1,280 @@
+//! CPU and disk load snapshots: one `perf` metric sample for overall CPU use and one per physical
+//! disk for the share of time it was busy, each measured over about a second. Taken with every
+//! local refresh so app hangs and other stalls can be lined up with the load around them.
+
+use crate::db::MetricSample;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+#[cfg(any(target_os = "windows", test))]
+use serde_json::Value;
+#[cfg(any(target_os = "linux", test))]
+use std::collections::BTreeMap;
+
+pub const PERF_SOURCE: &str = "perf";
+pub const CPU_METRIC: &str = "cpu_percent";
+pub const DISK_BUSY_METRIC: &str = "disk_busy_percent";
+#[cfg(target_os = "linux")]
+const SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfSnapshotResult {
+    pub samples: Vec<MetricSample>,
+    pub warnings: Vec<String>,
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux", test))]
+fn sample(host: &str, timestamp: &str, device: &str, name: &str, value: f64) -> MetricSample {
+    MetricSample {
+        timestamp: timestamp.to_string(),
+        source_host: host.to_string(),
+        source: PERF_SOURCE.to_string(),
+        device: device.to_string(),
+        name: name.to_string(),
+        value: (value * 10.0).round().clamp(0.0, 1000.0) / 10.0,
+        unit: Some("%".to_string()),
+        status: None,
+    }
+}
+
+/// Busy share between two readings of an idle counter and a clock in the same units.
+#[cfg(any(target_os = "linux", test))]
+fn busy_percent(
+    idle_before: u64,
+    idle_after: u64,
+    time_before: u64,
+    time_after: u64,
+) -> Option<f64> {
+    let elapsed = time_after
+        .checked_sub(time_before)
+        .filter(|elapsed| *elapsed > 0)?;
+    let idle = idle_after.saturating_sub(idle_before).min(elapsed);
+    Some(100.0 * (1.0 - idle as f64 / elapsed as f64))
+}
+
+/// `(idle, total)` jiffies from the aggregate `cpu` line of `/proc/stat`; iowait counts as idle.
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_stat(raw: &str) -> Option<(u64, u64)> {
+    let fields = raw
+        .lines()
+        .find(|line| line.starts_with("cpu "))?
+        .split_whitespace()
+        .skip(1)
+        .map(|value| value.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let idle = fields.get(3)? + fields.get(4).copied().unwrap_or(0);
+    Some((idle, fields.iter().sum()))
+}
+
+/// Milliseconds spent doing I/O per device from `/proc/diskstats`, for the devices in `disks`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_diskstats(raw: &str, disks: &[String]) -> BTreeMap<String, u64> {
+    raw.lines()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let name = (*fields.get(2)?).to_string();
+            let io_ticks = fields.get(12)?.parse::<u64>().ok()?;
+            disks.contains(&name).then_some((name, io_ticks))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn collect_linux_snapshot(host: &str) -> PerfSnapshotResult {
+    let mut result = PerfSnapshotResult::default();
+    // Whole disks only; partitions would count the same I/O twice.
+    let disks = std::fs::read_dir("/sys/block")
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| {
+                    !["loop", "ram", "zram"]
+                        .iter()
+                        .any(|skip| name.starts_with(skip))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let read = |path: &str| std::fs::read_to_string(path).unwrap_or_default();
+
+    let started = std::time::Instant::now();
+    let cpu_before = parse_proc_stat(read("/proc/stat").as_str());
+    let disks_before = parse_diskstats(read("/proc/diskstats").as_str(), disks.as_slice());
+    std::thread::sleep(SAMPLE_INTERVAL);
+    let cpu_after = parse_proc_stat(read("/proc/stat").as_str());
+    let disks_after = parse_diskstats(read("/proc/diskstats").as_str(), disks.as_slice());
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    let timestamp = Utc::now().to_rfc3339();
+    match (cpu_before, cpu_after) {
+        (Some((idle_before, total_before)), Some((idle_after, total_after))) => {
+            if let Some(value) = busy_percent(idle_before, idle_after, total_before, total_after) {
+                result
+                    .samples
+                    .push(sample(host, timestamp.as_str(), "cpu", CPU_METRIC, value));
+            }
+        }
+        _ => result
+            .warnings
+            .push("CPU load is unavailable: /proc/stat could not be read.".to_string()),
+    }
+    for (disk, after) in &disks_after {
+        let Some(before) = disks_before.get(disk) else {
+            continue;
+        };
+        if elapsed_ms == 0 {
+            break;
+        }
+        let busy = after.saturating_sub(*before).min(elapsed_ms);
+        result.samples.push(sample(
+            host,
+            timestamp.as_str(),
+            disk.as_str(),
+            DISK_BUSY_METRIC,
+            100.0 * busy as f64 / elapsed_ms as f64,
+        ));
+    }
+    result
+}
+
+/// Formatted counters report the change since the provider's previous sample, so a first query
+/// primes them and the second, a second later, is kept. CIM class names are used rather than
+/// `Get-Counter` paths, which are localized.
+#[cfg(target_os = "windows")]
+const WINDOWS_SNAPSHOT_SCRIPT: &str = "$query = { [pscustomobject]@{\
+cpu=(Get-CimInstance Win32_PerfFormattedData_PerfOS_Processor -Filter \"Name='_Total'\" -ErrorAction Stop).PercentProcessorTime;\
+disks=@(Get-CimInstance Win32_PerfFormattedData_PerfDisk_PhysicalDisk -ErrorAction Stop | Where-Object { $_.Name -ne '_Total' } | ForEach-Object { [pscustomobject]@{name=$_.Name;idle=$_.PercentIdleTime} })} }; \
+$null = & $query; Start-Sleep -Milliseconds 1000; & $query | ConvertTo-Json -Compress -Depth 3";
+
+/// Turns the snapshot script's output into samples; disks report idle time, stored as busy.
+#[cfg(any(target_os = "windows", test))]
+fn parse_windows_snapshot(value: &Value, host: &str, timestamp: &str) -> Vec<MetricSample> {
+    let mut samples = Vec::new();
+    if let Some(cpu) = value.get("cpu").and_then(Value::as_f64) {
+        samples.push(sample(host, timestamp, "cpu", CPU_METRIC, cpu));
+    }
+    let disks = value
+        .get("disks")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for disk in &disks {
+        let (Some(name), Some(idle)) = (
+            disk.get("name").and_then(Value::as_str),
+            disk.get("idle").and_then(Value::as_f64),
+        ) else {
+            continue;
+        };
+        samples.push(sample(
+            host,
+            timestamp,
+            name,
+            DISK_BUSY_METRIC,
+            100.0 - idle,
+        ));
+    }
+    samples
+}
+
+#[cfg(target_os = "windows")]
+fn collect_windows_snapshot(host: &str) -> PerfSnapshotResult {
+    let mut result = PerfSnapshotResult::default();
+    let output = match std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            WINDOWS_SNAPSHOT_SCRIPT,
+        ])
+        .output()
+    {
+        Ok(output) => output,
+        Err(error) => {
+            result
+                .warnings
+                .push(format!("Failed to read performance counters: {error}"));
+            return result;
+        }
+    };
+    if !output.status.success() {
+        result.warnings.push(format!(
+            "Performance counters are unavailable: {}",
+            String::from_utf8_lossy(output.stderr.as_slice()).trim()
+        ));
+        return result;
+    }
+    let Ok(value) = serde_json::from_slice::<Value>(output.stdout.as_slice()) else {
+        result
+            .warnings
+            .push("Performance counters returned no readable output.".to_string());
+        return result;
+    };
+    result.samples = parse_windows_snapshot(&value, host, Utc::now().to_rfc3339().as_str());
+    result
+}
+
+/// Measures CPU use and per-disk busy time on this machine over about a second.
+pub fn collect_perf_snapshot(host: &str) -> PerfSnapshotResult {
+    #[cfg(target_os = "windows")]
+    {
+        return collect_windows_snapshot(host);
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        return collect_linux_snapshot(host);
+    }
+
+    #[allow(unreachable_code)]
+    {
+        let _ = host;
+        PerfSnapshotResult {
+            warnings: vec!["CPU and disk load snapshots are not available on this OS.".to_string()],
+            ..PerfSnapshotResult::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_cpu_and_disk_load_from_proc() {
+        let before = "cpu  100 0 100 700 100 0 0 0 0 0\ncpu0 50 0 50 350 50 0 0 0 0 0\n";
+        let after = "cpu  250 0 150 800 100 0 0 0 0 0\ncpu0 120 0 80 400 50 0 0 0 0 0\n";
+        let (idle_before, total_before) = parse_proc_stat(before).expect("before");
+        let (idle_after, total_after) = parse_proc_stat(after).expect("after");
+        // 300 jiffies passed, 100 of them idle.
+        let cpu = busy_percent(idle_before, idle_after, total_before, total_after).expect("cpu");
+        assert!((cpu - 66.666).abs() < 0.01);
+
+        let disks = vec!["sda".to_string()];
+        let stats = "   8       0 sda 1 2 3 4 5 6 7 8 0 9120 10\n\
+                     8       1 sda1 1 2 3 4 5 6 7 8 0 9000 10\n";
+        assert_eq!(
+            parse_diskstats(stats, disks.as_slice()).get("sda"),
+            Some(&9120)
+        );
+    }
+
+    #[test]
+    fn reads_windows_counters() {
+        let value = serde_json::json!({
+            "cpu": 75,
+            "disks": [{ "name": "0 C:", "idle": 10 }, { "name": "1 D:" }]
+        });
+        let samples = parse_windows_snapshot(&value, "pc01", "2026-03-02T10:00:00+00:00");
+        let values: Vec<(&str, &str, f64)> = samples
+            .iter()
+            .map(|sample| (sample.device.as_str(), sample.name.as_str(), sample.value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![("cpu", CPU_METRIC, 75.0), ("0 C:", DISK_BUSY_METRIC, 90.0)]
+        );
+        assert!(parse_windows_snapshot(&Value::Null, "pc01", "").is_empty());
+    }
+}
@@ -0,0 +1,438 @@
+use crate::crash::CrashRecord;
+use crate::logs::NormalizedEvent;
+use crate::timestamps::parse_timestamp;
+use serde::{Deserialize, Serialize};
+
+const POWER_EVIDENCE_WINDOW_MINUTES: i64 = 30;
+const CRASH_MATCH_WINDOW_MINUTES: i64 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerIncident {
+    pub timestamp: String,
+    pub event_id: String,
+    pub classification: String,
+    pub summary: String,
+    pub evidence: Vec<String>,
+    pub related_crash_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryHealth {
+    pub present: bool,
+    pub design_capacity: Option<u64>,
+    pub full_charge_capacity: Option<u64>,
+    pub health_percent: Option<f64>,
+    pub cycle_count: Option<u64>,
+    pub charge_percent: Option<f64>,
+    pub on_ac_power: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerAnalysisResult {
+    pub incidents: Vec<PowerIncident>,
+    pub power_loss_count: usize,
+    pub crash_count: usize,
+    pub unknown_count: usize,
+    pub battery: Option<BatteryHealth>,
+    pub warnings: Vec<String>,
+}
+
+fn provider_matches(event: &NormalizedEvent, needle: &str) -> bool {
+    event.provider.to_ascii_lowercase().contains(needle)
+}
+
+/// Returns true for records that represent the system going down without a clean shutdown.
+pub fn is_unexpected_shutdown(event: &NormalizedEvent) -> bool {
+    match event.os.as_str() {
+        "windows" => {
+            (event.event_id == Some(41) && provider_matches(event, "kernel-power"))
+                || (event.event_id == Some(6008) && provider_matches(event, "eventlog"))
+        }
+        "macos" => macos_shutdown_cause(event.message.as_str()).is_some(),
+        _ => {
+            let lower = event.message.to_ascii_lowercase();
+            lower.contains("unclean shutdown")
+                || lower.contains("was not cleanly shut down")
+                || lower.contains("journal file corrupted or uncleanly shut down")
+        }
+    }
+}
+
+fn macos_shutdown_cause(message: &str) -> Option<i64> {
+    let lower = message.to_ascii_lowercase();
+    let index = lower.find("previous shutdown cause:")?;
+    let rest = message[index + "previous shutdown cause:".len()..].trim_start();
+    let token: String = rest
+        .chars()
+        .take_while(|ch| *ch == '-' || ch.is_ascii_digit())
+        .collect();
+    let cause = token.parse::<i64>().ok()?;
+    // Cause 5 is a normal shutdown; 3 is a user-initiated hard shutdown.
+    if cause == 5 || cause == 3 {
+        None
+    } else {
+        Some(cause)
+    }
+}
+
+/// Signals that the machine was running from battery, lost AC, or hit a critical charge level.
+fn power_loss_signal(event: &NormalizedEvent) -> Option<String> {
+    let lower = event.message.to_ascii_lowercase();
+    if event.os == "windows"
+        && provider_matches(event, "kernel-power")
+        && event.event_id == Some(105)
+        && (lower.contains("ac") || lower.contains("dc"))
+    {
+        return Some(format!("Power source change at {}", event.timestamp));
+    }
+    if (provider_matches(event, "battery") || provider_matches(event, "upower"))
+        && (lower.contains("critical") || lower.contains("low") || lower.contains("discharg"))
+    {
+        return Some(format!(
+            "Battery warning at {}: {}",
+            event.timestamp,
+            summarize(&event.message)
+        ));
+    }
+    [
+        "ac adapter disconnected",
+        "ac power lost",
+        "on battery power",
+        "switched to battery",
+        "battery critically low",
+        "battery level critical",
+        "critical battery",
+        "ups on battery",
+        "power failure",
+    ]
+    .iter()
+    .find(|pattern| lower.contains(*pattern))
+    .map(|pattern| format!("'{pattern}' reported at {}", event.timestamp))
+}
+
+fn bugcheck_in_message(message: &str) -> Option<String> {
+    let index = message.find("BugcheckCode=")?;
+    let value: String = message[index + "BugcheckCode=".len()..]
+        .chars()
+        .take_while(|ch| ch.is_ascii_alphanumeric())
+        .collect();
+    if value.is_empty() || value == "0" || value == "0x0" {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn summarize(message: &str) -> String {
+    let single_line = message.replace(['\r', '\n'], " ");
+    if single_line.chars().count() <= 160 {
+        single_line
+    } else {
+        let truncated: String = single_line.chars().take(160).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Classifies every unexpected shutdown in `events` as a power loss, a crash, or unknown.
+pub fn classify_power_incidents(
+    events: &[NormalizedEvent],
+    crashes: &[CrashRecord],
+) -> Vec<PowerIncident> {
+    let mut incidents = Vec::new();
+    let evidence_window = chrono::Duration::minutes(POWER_EVIDENCE_WINDOW_MINUTES);
+    let crash_window = chrono::Duration::minutes(CRASH_MATCH_WINDOW_MINUTES);
+
+    for event in events.iter().filter(|event| is_unexpected_shutdown(event)) {
+        let Some(shutdown_at) = parse_timestamp(event.timestamp.as_str()) else {
+            continue;
+        };
+
+        let mut evidence = Vec::new();
+        let mut power_loss = false;
+        let mut crash_code = bugcheck_in_message(event.message.as_str());
+
+        if let Some(cause) = macos_shutdown_cause(event.message.as_str()) {
+            evidence.push(format!("macOS previous shutdown cause {cause}"));
+            if cause == 0 || cause == -3 || cause == -128 {
+                power_loss = true;
+            }
+        }
+
+        for candidate in events {
+            if candidate.source_host != event.source_host {
+                continue;
+            }
+            let Some(candidate_at) = parse_timestamp(candidate.timestamp.as_str()) else {
+                continue;
+            };
+            if candidate_at > shutdown_at || shutdown_at - candidate_at > evidence_window {
+                continue;
+            }
+            if let Some(signal) = power_loss_signal(candidate) {
+                power_loss = true;
+                evidence.push(signal);
+            }
+        }
+
+        let related_crash = crashes.iter().find(|crash| {
+            crash.source_host == event.source_host
+                && parse_timestamp(crash.timestamp.as_str())
+                    .map(|crash_at| (crash_at - shutdown_at).abs() <= crash_window)
+                    .unwrap_or(false)
+        });
+        if let Some(crash) = related_crash {
+            evidence.push(format!("Crash record {} ({})", crash.id, crash.crash_type));
+            if crash_code.is_none() {
+                crash_code = crash.code.clone().or_else(|| Some(crash.crash_type.clone()));
+            }
+        }
+
+        let (classification, summary) = if let Some(code) = crash_code.as_ref() {
+            evidence.push(format!("Bugcheck/crash code {code}"));
+            (
+                "crash",
+                format!("Unexpected shutdown coincides with a crash ({code})."),
+            )
+        } else if power_loss {
+            (
+                "power_loss",
+                "Unexpected shutdown preceded by battery or AC power-loss signals.".to_string(),
+            )
+        } else {
+            (
+                "unknown",
+                "Unexpected shutdown without crash data or power-loss evidence.".to_string(),
+            )
+        };
+
+        incidents.push(PowerIncident {
+            timestamp: event.timestamp.clone(),
+            event_id: event.id.clone(),
+            classification: classification.to_string(),
+            summary,
+            evidence,
+            related_crash_id: related_crash.map(|crash| crash.id.clone()),
+        });
+    }
+
+    incidents.sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+    incidents
+}
+
+pub fn analyze_power_events(
+    events: &[NormalizedEvent],
+    crashes: &[CrashRecord],
+    battery: Option<BatteryHealth>,
+) -> PowerAnalysisResult {
+    let incidents = classify_power_incidents(events, crashes);
+    let mut result = PowerAnalysisResult {
+        power_loss_count: incidents
+            .iter()
+            .filter(|incident| incident.classification == "power_loss")
+            .count(),
+        crash_count: incidents
+            .iter()
+            .filter(|incident| incident.classification == "crash")
+            .count(),
+        unknown_count: incidents
+            .iter()
+            .filter(|incident| incident.classification == "unknown")
+            .count(),
+        incidents,
+        battery,
+        warnings: Vec::new(),
+    };
+
+    if let Some(health) = result.battery.as_ref().and_then(|battery| battery.health_percent) {
+        if health < 60.0 {
+            result.warnings.push(format!(
+                "Battery full-charge capacity is {health:.0}% of design; worn batteries make sudden power loss more likely."
+            ));
+        }
+    }
+
+    result
+}
+
+fn finalize_battery(mut battery: BatteryHealth) -> BatteryHealth {
+    if let (Some(design), Some(full)) = (battery.design_capacity, battery.full_charge_capacity) {
+        if design > 0 {
+            battery.health_percent = Some((full as f64 / design as f64) * 100.0);
+        }
+    }
+    battery
+}
+
+#[cfg(target_os = "linux")]
+pub fn read_battery_health() -> Option<BatteryHealth> {
+    let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    let mut battery = BatteryHealth::default();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let read = |name: &str| {
+            std::fs::read_to_string(path.join(name))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        match read("type").as_deref() {
+            Some("Battery") => {
+                battery.present = true;
+                battery.design_capacity = read("energy_full_design")
+                    .or_else(|| read("charge_full_design"))
+                    .and_then(|value| value.parse().ok());
+                battery.full_charge_capacity = read("energy_full")
+                    .or_else(|| read("charge_full"))
+                    .and_then(|value| value.parse().ok());
+                battery.cycle_count = read("cycle_count").and_then(|value| value.parse().ok());
+                battery.charge_percent = read("capacity").and_then(|value| value.parse().ok());
+            }
+            Some("Mains") => {
+                battery.on_ac_power = read("online").map(|value| value == "1");
+            }
+            _ => {}
+        }
+    }
+    battery.present.then(|| finalize_battery(battery))
+}
+
+#[cfg(target_os = "macos")]
+pub fn read_battery_health() -> Option<BatteryHealth> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-rn", "AppleSmartBattery"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let read = |key: &str| -> Option<String> {
+        let pattern = format!("\"{key}\" = ");
+        text.lines()
+            .find_map(|line| line.trim().strip_prefix(pattern.as_str()).map(str::to_string))
+    };
+    read("DesignCapacity")?;
+    let battery = BatteryHealth {
+        present: true,
+        design_capacity: read("DesignCapacity").and_then(|value| value.parse().ok()),
+        full_charge_capacity: read("AppleRawMaxCapacity")
+            .or_else(|| read("MaxCapacity"))
+            .and_then(|value| value.parse().ok()),
+        cycle_count: read("CycleCount").and_then(|value| value.parse().ok()),
+        charge_percent: read("CurrentCapacity").and_then(|value| value.parse().ok()),
+        on_ac_power: read("ExternalConnected").map(|value| value == "Yes"),
+        health_percent: None,
+    };
+    Some(finalize_battery(battery))
+}
+
+#[cfg(target_os = "windows")]
+pub fn read_battery_health() -> Option<BatteryHealth> {
+    let script = "$d=(Get-CimInstance -Namespace root\\wmi -ClassName BatteryStaticData -ErrorAction SilentlyContinue | Select-Object -First 1).DesignedCapacity; \
+$f=(Get-CimInstance -Namespace root\\wmi -ClassName BatteryFullChargedCapacity -ErrorAction SilentlyContinue | Select-Object -First 1).FullChargedCapacity; \
+$b=Get-CimInstance -ClassName Win32_Battery -ErrorAction SilentlyContinue | Select-Object -First 1; \
+if ($b) { [pscustomobject]@{design=$d;full=$f;charge=$b.EstimatedChargeRemaining;status=$b.BatteryStatus} | ConvertTo-Json -Compress }";
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()
+        .ok()?;
+    let value: serde_json::Value = serde_json::from_slice(output.stdout.as_slice()).ok()?;
+    let battery = BatteryHealth {
+        present: true,
+        design_capacity: value.get("design").and_then(|entry| entry.as_u64()),
+        full_charge_capacity: value.get("full").and_then(|entry| entry.as_u64()),
+        cycle_count: None,
+        charge_percent: value.get("charge").and_then(|entry| entry.as_f64()),
+        // Win32_Battery.BatteryStatus 2 means "connected to AC".
+        on_ac_power: value
+            .get("status")
+            .and_then(|entry| entry.as_u64())
+            .map(|status| status == 2),
+        health_percent: None,
+    };
+    Some(finalize_battery(battery))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn read_battery_health() -> Option<BatteryHealth> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        os: &str,
+        timestamp: &str,
+        provider: &str,
+        event_id: Option<u32>,
+        message: &str,
+    ) -> NormalizedEvent {
+        NormalizedEvent {
+            id: format!("evt-{timestamp}-{provider}"),
+            timestamp: timestamp.to_string(),
+            os: os.to_string(),
+            log_name: "System".to_string(),
+            category: "system".to_string(),
+            provider: provider.to_string(),
+            event_id,
+            severity: "critical".to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn kernel_power_with_battery_warning_is_power_loss() {
+        let events = vec![
+            event(
+                "windows",
+                "2026-03-01T10:00:00Z",
+                "Microsoft-Windows-Kernel-Power",
+                Some(41),
+                "The system has rebooted without cleanly shutting down first.",
+            ),
+            event(
+                "windows",
+                "2026-03-01T09:50:00Z",
+                "Microsoft-Windows-Battery",
+                Some(13),
+                "Battery level critical.",
+            ),
+        ];
+
+        let incidents = classify_power_incidents(&events, &[]);
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].classification, "power_loss");
+    }
+
+    #[test]
+    fn kernel_power_with_bugcheck_is_crash() {
+        let events = vec![event(
+            "windows",
+            "2026-03-01T10:00:00Z",
+            "Microsoft-Windows-Kernel-Power",
+            Some(41),
+            "Data: BugcheckCode=159, BugcheckParameter1=0x3",
+        )];
+
+        let incidents = classify_power_incidents(&events, &[]);
+        assert_eq!(incidents[0].classification, "crash");
+    }
+
+    #[test]
+    fn macos_shutdown_cause_zero_is_power_loss() {
+        let events = vec![event(
+            "macos",
+            "2026-03-01T10:00:00Z",
+            "kernel",
+            None,
+            "Previous shutdown cause: 0",
+        )];
+
+        let incidents = classify_power_incidents(&events, &[]);
+        assert_eq!(incidents[0].classification, "power_loss");
+        assert!(macos_shutdown_cause("Previous shutdown cause: 5").is_none());
+    }
+}
@@ -0,0 +1,332 @@
+//! Permission preflight: checks that the collectors for this OS can actually read their sources
+//! and says how to fix the ones that cannot, before a sync quietly returns partial data.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightCheck {
+    pub id: String,
+    pub title: String,
+    /// `pass`, `warning`, `fail` or `skipped`.
+    pub status: String,
+    pub detail: String,
+    pub remediation: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightReport {
+    pub os: String,
+    pub checked_at: String,
+    pub checks: Vec<PreflightCheck>,
+}
+
+fn check(
+    id: &str,
+    title: &str,
+    status: &str,
+    detail: String,
+    remediation: Option<&str>,
+) -> PreflightCheck {
+    PreflightCheck {
+        id: id.to_string(),
+        title: title.to_string(),
+        status: status.to_string(),
+        detail,
+        remediation: remediation.map(ToString::to_string),
+    }
+}
+
+/// Runs `program args` and returns its trimmed stdout when it exits successfully.
+fn command_output(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|error| error.to_string())?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(output.stdout.as_slice())
+            .trim()
+            .to_string())
+    } else {
+        Err(String::from_utf8_lossy(output.stderr.as_slice())
+            .trim()
+            .to_string())
+    }
+}
+
+/// Whether a directory of crash reports can be listed. A missing directory is not a
+/// permission problem, so it is reported as skipped.
+fn directory_check(id: &str, title: &str, path: &Path, remediation: &str) -> PreflightCheck {
+    match std::fs::read_dir(path) {
+        Ok(_) => check(
+            id,
+            title,
+            "pass",
+            format!("{} is readable.", path.display()),
+            None,
+        ),
+        Err(error) if error.kind() == ErrorKind::NotFound => check(
+            id,
+            title,
+            "skipped",
+            format!("{} does not exist on this machine.", path.display()),
+            None,
+        ),
+        Err(error) if error.kind() == ErrorKind::PermissionDenied => check(
+            id,
+            title,
+            "fail",
+            format!("{} cannot be read: {error}", path.display()),
+            Some(remediation),
+        ),
+        Err(error) => check(
+            id,
+            title,
+            "warning",
+            format!("{} could not be checked: {error}", path.display()),
+            Some(remediation),
+        ),
+    }
+}
+
+/// Journal access from `id -u` and `id -Gn`: root and members of `systemd-journal`, `adm` or
+/// `wheel` see the system journal; everyone else only sees their own user journal.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn journal_group_check(uid: &str, groups: &str) -> PreflightCheck {
+    const TITLE: &str = "System journal access";
+    const REMEDIATION: &str = "Run `sudo usermod -aG systemd-journal $USER`, then sign out and back in, or enable 'Request elevation' in the ingest profile.";
+    if uid.trim() == "0" {
+        return check(
+            "journal-groups",
+            TITLE,
+            "pass",
+            "Running as root.".to_string(),
+            None,
+        );
+    }
+    let granted: Vec<&str> = groups
+        .split_whitespace()
+        .filter(|group| matches!(*group, "systemd-journal" | "adm" | "wheel"))
+        .collect();
+    if granted.is_empty() {
+        check(
+            "journal-groups",
+            TITLE,
+            "warning",
+            "Not in systemd-journal or adm; only this user's own journal entries are visible."
+                .to_string(),
+            Some(REMEDIATION),
+        )
+    } else {
+        check(
+            "journal-groups",
+            TITLE,
+            "pass",
+            format!("Member of {}.", granted.join(", ")),
+            None,
+        )
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn platform_checks() -> Vec<PreflightCheck> {
+    let mut checks = Vec::new();
+    checks.push(match command_output("journalctl", &["--version"]) {
+        Ok(version) => check(
+            "journalctl",
+            "journalctl available",
+            "pass",
+            version.lines().next().unwrap_or_default().to_string(),
+            None,
+        ),
+        Err(error) => check(
+            "journalctl",
+            "journalctl available",
+            "fail",
+            format!("journalctl could not be run: {error}"),
+            Some("Install systemd's journalctl, or import log files manually on hosts without systemd."),
+        ),
+    });
+    match (
+        command_output("id", &["-u"]),
+        command_output("id", &["-Gn"]),
+    ) {
+        (Ok(uid), Ok(groups)) => checks.push(journal_group_check(uid.as_str(), groups.as_str())),
+        (Err(error), _) | (_, Err(error)) => checks.push(check(
+            "journal-groups",
+            "System journal access",
+            "warning",
+            format!("Group membership could not be read: {error}"),
+            None,
+        )),
+    }
+    checks.push(directory_check(
+        "crash-reports",
+        "Crash reports (/var/crash)",
+        Path::new("/var/crash"),
+        "Add your user to the group owning /var/crash (usually `whoopsie` or `adm`).",
+    ));
+    checks.push(directory_check(
+        "audit-log",
+        "Audit log (/var/log/audit)",
+        Path::new("/var/log/audit"),
+        "Audit records need root; enable 'Request elevation' in the ingest profile.",
+    ));
+    checks
+}
+
+#[cfg(target_os = "macos")]
+fn platform_checks() -> Vec<PreflightCheck> {
+    const FULL_DISK_ACCESS: &str = "Open System Settings > Privacy & Security > Full Disk Access, enable Hermes, then restart it.";
+    let mut checks = Vec::new();
+    checks.push(match command_output("/usr/bin/log", &["help"]) {
+        Ok(_) => check(
+            "log-show",
+            "Unified log (`log show`)",
+            "pass",
+            "`log` is available.".to_string(),
+            None,
+        ),
+        Err(error) => check(
+            "log-show",
+            "Unified log (`log show`)",
+            "fail",
+            format!("`log` could not be run: {error}"),
+            None,
+        ),
+    });
+    checks.push(directory_check(
+        "diagnostic-reports",
+        "System crash reports",
+        Path::new("/Library/Logs/DiagnosticReports"),
+        FULL_DISK_ACCESS,
+    ));
+    if let Some(home) = dirs::home_dir() {
+        checks.push(directory_check(
+            "user-diagnostic-reports",
+            "User crash reports",
+            home.join("Library/Logs/DiagnosticReports").as_path(),
+            FULL_DISK_ACCESS,
+        ));
+    }
+    checks
+}
+
+#[cfg(target_os = "windows")]
+fn platform_checks() -> Vec<PreflightCheck> {
+    const ELEVATE: &str = "Restart Hermes with 'Run as administrator', or add your account to the Event Log Readers group for Security log access.";
+    let mut checks = Vec::new();
+    let admin = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "([Security.Principal.WindowsPrincipal][Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)",
+        ],
+    );
+    checks.push(match admin.as_deref() {
+        Ok("True") => check(
+            "admin",
+            "Administrator rights",
+            "pass",
+            "Hermes is running elevated.".to_string(),
+            None,
+        ),
+        Ok(_) => check(
+            "admin",
+            "Administrator rights",
+            "warning",
+            "Hermes is not elevated; the Security log and system WER reports may be unreadable."
+                .to_string(),
+            Some(ELEVATE),
+        ),
+        Err(error) => check(
+            "admin",
+            "Administrator rights",
+            "warning",
+            format!("Elevation could not be determined: {error}"),
+            None,
+        ),
+    });
+    checks.push(
+        match command_output("wevtutil", &["qe", "Security", "/c:1", "/f:text"]) {
+            Ok(_) => check(
+                "security-log",
+                "Security event log",
+                "pass",
+                "The Security log is readable.".to_string(),
+                None,
+            ),
+            Err(error) => check(
+                "security-log",
+                "Security event log",
+                "fail",
+                format!("The Security log cannot be read: {error}"),
+                Some(ELEVATE),
+            ),
+        },
+    );
+    for (id, title, path) in [
+        (
+            "wer-archive",
+            "WER report archive",
+            r"C:\ProgramData\Microsoft\Windows\WER\ReportArchive",
+        ),
+        (
+            "wer-queue",
+            "WER report queue",
+            r"C:\ProgramData\Microsoft\Windows\WER\ReportQueue",
+        ),
+        ("minidumps", "Kernel minidumps", r"C:\Windows\Minidump"),
+    ] {
+        checks.push(directory_check(id, title, Path::new(path), ELEVATE));
+    }
+    checks
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn platform_checks() -> Vec<PreflightCheck> {
+    Vec::new()
+}
+
+pub fn run_preflight_checks() -> PreflightReport {
+    PreflightReport {
+        os: std::env::consts::OS.to_string(),
+        checked_at: Utc::now().to_rfc3339(),
+        checks: platform_checks(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_groups_grant_access() {
+        assert_eq!(journal_group_check("0", "root").status, "pass");
+        let member = journal_group_check("1000", "dave wheel systemd-journal");
+        assert_eq!(member.status, "pass");
+        assert_eq!(member.detail, "Member of wheel, systemd-journal.");
+        let other = journal_group_check("1000", "dave docker");
+        assert_eq!(other.status, "warning");
+        assert!(other.remediation.is_some());
+    }
+
+    #[test]
+    fn missing_directory_is_skipped() {
+        let result = directory_check(
+            "missing",
+            "Missing",
+            Path::new("/nonexistent/hermes-preflight"),
+            "n/a",
+        );
+        assert_eq!(result.status, "skipped");
+        assert!(result.remediation.is_none());
+    }
+}
@@ -0,0 +1,239 @@
+/// Tokens held back from the context window for the model's reply.
+const RESPONSE_RESERVE_TOKENS: usize = 1024;
+/// Upper bound for any single prompt regardless of the model's window, to keep request bodies
+/// and latency reasonable.
+const MAX_PROMPT_TOKENS: usize = 100_000;
+const MIN_PROMPT_TOKENS: usize = 512;
+
+/// Rough token count used for budgeting (about four characters per token).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Context window assumed for a provider when the profile does not set one. Local runtimes
+/// default to small windows unless the model is loaded with a larger one.
+pub fn default_context_window(provider: &str) -> usize {
+    match provider.trim().to_ascii_lowercase().as_str() {
+        "openai" => 128_000,
+        "claude" => 200_000,
+        "gemini" => 1_000_000,
+        "perplexity" => 127_000,
+        "lmstudio" => 4096,
+        "ollama" | "openai_compatible" => 8192,
+        _ => 8192,
+    }
+}
+
+/// Tokens available for the prompt itself, given the provider and the profile's configured
+/// context window (0 means use the provider default).
+pub fn prompt_token_budget(provider: &str, configured_window: u32) -> usize {
+    let window = if configured_window == 0 {
+        default_context_window(provider)
+    } else {
+        configured_window as usize
+    };
+    window
+        .saturating_sub(RESPONSE_RESERVE_TOKENS.min(window / 4))
+        .clamp(MIN_PROMPT_TOKENS, MAX_PROMPT_TOKENS)
+}
+
+#[derive(Debug, Clone)]
+struct PromptSection {
+    heading: Option<String>,
+    lines: Vec<String>,
+    required: bool,
+}
+
+/// A prompt assembled from sections. Required sections (instructions, the crash summary) are kept
+/// whole; ranked sections keep as many leading lines as the budget allows, so callers put the
+/// most important evidence first.
+#[derive(Debug, Clone, Default)]
+pub struct PromptBuilder {
+    sections: Vec<PromptSection>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FittedPrompt {
+    pub text: String,
+    pub estimated_tokens: usize,
+    pub omitted_lines: usize,
+}
+
+fn omission_note(count: usize) -> String {
+    format!("[... {count} line(s) omitted to fit the model context window ...]")
+}
+
+impl PromptBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn required(mut self, lines: Vec<String>) -> Self {
+        self.sections.push(PromptSection {
+            heading: None,
+            lines,
+            required: true,
+        });
+        self
+    }
+
+    pub fn ranked(mut self, heading: impl Into<String>, lines: Vec<String>) -> Self {
+        self.sections.push(PromptSection {
+            heading: Some(heading.into()),
+            lines,
+            required: false,
+        });
+        self
+    }
+
+    /// Fills ranked sections in order after every required section is placed.
+    pub fn build(self, token_budget: usize) -> FittedPrompt {
+        let cost = |line: &String| estimate_tokens(line.as_str()) + 1;
+        let mut remaining = token_budget.saturating_sub(
+            self.sections
+                .iter()
+                .filter(|section| section.required)
+                .flat_map(|section| section.lines.iter())
+                .map(cost)
+                .sum(),
+        );
+
+        let mut output = Vec::new();
+        let mut omitted_lines = 0;
+        for section in self.sections {
+            if section.required {
+                output.extend(section.lines);
+                continue;
+            }
+            let total = section.lines.len();
+            let mut kept = Vec::new();
+            if let Some(heading) = section.heading.as_ref() {
+                let heading_cost = cost(heading) + estimate_tokens(omission_note(total).as_str());
+                if heading_cost > remaining && total > 0 {
+                    omitted_lines += total;
+                    continue;
+                }
+                remaining = remaining.saturating_sub(heading_cost);
+            }
+            for line in section.lines {
+                let line_cost = cost(&line);
+                if line_cost > remaining {
+                    break;
+                }
+                remaining -= line_cost;
+                kept.push(line);
+            }
+            let dropped = total - kept.len();
+            omitted_lines += dropped;
+            if let Some(heading) = section.heading {
+                output.push(heading);
+            }
+            output.extend(kept);
+            if dropped > 0 {
+                output.push(omission_note(dropped));
+            }
+        }
+
+        let text = output.join("\n");
+        FittedPrompt {
+            estimated_tokens: estimate_tokens(text.as_str()),
+            text,
+            omitted_lines,
+        }
+    }
+}
+
+/// Last-resort compaction for a free-form prompt: keeps the opening instructions and the closing
+/// lines and drops the middle. Returns `None` when the prompt already fits.
+pub fn compact_free_text(prompt: &str, token_budget: usize) -> Option<FittedPrompt> {
+    if estimate_tokens(prompt) <= token_budget {
+        return None;
+    }
+    let lines = prompt.lines().collect::<Vec<_>>();
+    let note_tokens = estimate_tokens(omission_note(lines.len()).as_str()) + 1;
+    let mut head_budget = token_budget.saturating_sub(note_tokens) * 3 / 5;
+    let mut tail_budget = token_budget.saturating_sub(note_tokens) - head_budget;
+
+    let mut head = Vec::new();
+    for line in lines.iter() {
+        let cost = estimate_tokens(line) + 1;
+        if cost > head_budget {
+            break;
+        }
+        head_budget -= cost;
+        head.push(*line);
+    }
+    tail_budget += head_budget;
+    let mut tail = Vec::new();
+    for line in lines[head.len()..].iter().rev() {
+        let cost = estimate_tokens(line) + 1;
+        if cost > tail_budget {
+            break;
+        }
+        tail_budget -= cost;
+        tail.push(*line);
+    }
+    tail.reverse();
+
+    let omitted_lines = lines.len() - head.len() - tail.len();
+    let mut output = head.into_iter().map(str::to_string).collect::<Vec<_>>();
+    if omitted_lines > 0 {
+        output.push(omission_note(omitted_lines));
+    }
+    output.extend(tail.into_iter().map(str::to_string));
+    let mut text = output.join("\n");
+    if omitted_lines == 0 || estimate_tokens(text.as_str()) > token_budget {
+        // A few very long lines: fall back to a hard character cut.
+        text = prompt.chars().take(token_budget * 4).collect();
+    }
+    Some(FittedPrompt {
+        estimated_tokens: estimate_tokens(text.as_str()),
+        text,
+        omitted_lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_required_sections_and_leading_ranked_lines() {
+        let events = (0..200)
+            .map(|index| format!("- event {index:03} with a moderately long message body"))
+            .collect::<Vec<_>>();
+        let prompt = PromptBuilder::new()
+            .required(vec![
+                "Analyze the crash.".to_string(),
+                "Crash: kernel panic".to_string(),
+            ])
+            .ranked("Events:", events)
+            .build(300);
+
+        assert!(prompt.estimated_tokens <= 300);
+        assert!(prompt
+            .text
+            .starts_with("Analyze the crash.\nCrash: kernel panic\nEvents:\n- event 000"));
+        assert!(prompt.omitted_lines > 0);
+        assert!(prompt
+            .text
+            .ends_with(omission_note(prompt.omitted_lines).as_str()));
+    }
+
+    #[test]
+    fn budgets_follow_provider_windows_and_compact_free_text() {
+        assert_eq!(prompt_token_budget("ollama", 0), 8192 - 1024);
+        assert_eq!(prompt_token_budget("gemini", 0), MAX_PROMPT_TOKENS);
+        assert_eq!(prompt_token_budget("lmstudio", 2048), 2048 - 512);
+
+        let prompt = (0..500)
+            .map(|index| format!("line {index}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(compact_free_text(prompt.as_str(), 10_000).is_none());
+        let compacted = compact_free_text(prompt.as_str(), 200).unwrap();
+        assert!(compacted.estimated_tokens <= 200);
+        assert!(compacted.text.starts_with("line 0\n"));
+        assert!(compacted.text.ends_with("line 499"));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::db::{QuerySession, QueryStep};
+use serde_json::Value;
+use std::sync::Mutex;
+
+/// Id of the session currently being recorded; steps are dropped while this is empty.
+static ACTIVE_SESSION: Mutex<Option<String>> = Mutex::new(None);
+
+pub fn active_session_id() -> Option<String> {
+    ACTIVE_SESSION.lock().ok().and_then(|guard| guard.clone())
+}
+
+/// Makes `session_id` the recording target and returns the session it replaced, if any.
+pub fn set_active_session(session_id: Option<String>) -> Option<String> {
+    match ACTIVE_SESSION.lock() {
+        Ok(mut guard) => std::mem::replace(&mut *guard, session_id),
+        Err(_) => None,
+    }
+}
+
+fn describe_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => {
+            let trimmed = text.trim();
+            (!trimmed.is_empty()).then(|| trimmed.to_string())
+        }
+        Value::Array(items) => {
+            let parts: Vec<String> = items.iter().filter_map(describe_value).collect();
+            (!parts.is_empty()).then(|| parts.join(", "))
+        }
+        // Toggle maps such as `{ "error": true, "warning": false }` read as the enabled keys.
+        Value::Object(map) if map.values().all(Value::is_boolean) => {
+            let enabled: Vec<&str> = map
+                .iter()
+                .filter(|(_, value)| value.as_bool() == Some(true))
+                .map(|(key, _)| key.as_str())
+                .collect();
+            (!enabled.is_empty()).then(|| enabled.join(", "))
+        }
+        other => Some(other.to_string()),
+    }
+}
+
+/// One-line description of a step's parameters, skipping empty fields.
+pub fn describe_step(step: &QueryStep) -> String {
+    let details = match &step.params {
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(key, value)| describe_value(value).map(|text| format!("{key}={text}")))
+            .collect::<Vec<_>>()
+            .join("; "),
+        other => describe_value(other).unwrap_or_default(),
+    };
+    if details.is_empty() {
+        step.action.clone()
+    } else {
+        format!("{}: {details}", step.action)
+    }
+}
+
+/// Renders a recorded session as numbered "how I found this" steps for a case write-up.
+pub fn render_session_markdown(session: &QuerySession, steps: &[QueryStep]) -> String {
+    let mut lines = vec![
+        format!("# {}", session.name),
+        String::new(),
+        format!(
+            "Recorded {} to {}.",
+            session.started_at,
+            session.ended_at.as_deref().unwrap_or("now")
+        ),
+        String::new(),
+    ];
+    if steps.is_empty() {
+        lines.push("No query steps were recorded.".to_string());
+    }
+    for step in steps {
+        lines.push(format!(
+            "{}. `{}` {}",
+            step.seq,
+            step.timestamp,
+            describe_step(step)
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_steps_without_empty_filters() {
+        let session = QuerySession {
+            id: "s1".to_string(),
+            name: "Disk resets on FS-01".to_string(),
+            started_at: "2026-03-01T09:00:00Z".to_string(),
+            ended_at: Some("2026-03-01T09:20:00Z".to_string()),
+            step_count: 2,
+        };
+        let steps = vec![
+            QueryStep {
+                seq: 1,
+                timestamp: "2026-03-01T09:01:00Z".to_string(),
+                action: "filter".to_string(),
+                params: json!({
+                    "search": "",
+                    "severities": {"error": true, "warning": false},
+                    "provider": "disk",
+                    "eventId": null
+                }),
+            },
+            QueryStep {
+                seq: 2,
+                timestamp: "2026-03-01T09:05:00Z".to_string(),
+                action: "open crash".to_string(),
+                params: json!({}),
+            },
+        ];
+
+        let markdown = render_session_markdown(&session, steps.as_slice());
+        assert!(markdown.starts_with("# Disk resets on FS-01"));
+        assert!(
+            markdown.contains("1. `2026-03-01T09:01:00Z` filter: provider=disk; severities=error")
+        );
+        assert!(markdown.ends_with("2. `2026-03-01T09:05:00Z` open crash"));
+    }
+}
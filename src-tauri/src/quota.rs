@@ -0,0 +1,176 @@
+use crate::forwarding::severity_rank;
+use crate::logs::NormalizedEvent;
+use serde::{Deserialize, Serialize};
+
+/// Share of the limit at which information events start being sampled on ingest.
+const SAMPLING_THRESHOLD: f64 = 0.75;
+/// Share of the limit at which information events are no longer stored and old ones are pruned.
+const PRUNE_THRESHOLD: f64 = 0.9;
+/// Pruning aims to bring the database back under this share of the limit.
+const PRUNE_TARGET: f64 = 0.7;
+const NEAR_LIMIT_SAMPLE_EVERY: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaPlan {
+    pub used_bytes: u64,
+    /// 0 when no limit is configured.
+    pub max_bytes: u64,
+    /// One of unlimited, ok, near, critical or over.
+    pub level: String,
+    /// Keep one information event in this many on ingest; 1 keeps them all.
+    pub information_sample_every: usize,
+    /// Events below this severity are not stored while set.
+    pub min_severity: Option<String>,
+    /// Oldest information events to delete from the database, as a share of the stored ones.
+    pub prune_information_share: f64,
+    pub degradations: Vec<String>,
+}
+
+fn megabytes(bytes: u64) -> String {
+    format!("{:.0} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Chooses how aggressively to degrade ingest for the current database size. Sampling starts at
+/// 75% of the limit, information events stop being stored and the oldest are pruned from 90%, and
+/// once over the limit only errors and critical events are kept.
+pub fn plan_for_usage(used_bytes: u64, max_bytes: u64) -> QuotaPlan {
+    let mut plan = QuotaPlan {
+        used_bytes,
+        max_bytes,
+        level: "unlimited".to_string(),
+        information_sample_every: 1,
+        min_severity: None,
+        prune_information_share: 0.0,
+        degradations: Vec::new(),
+    };
+    if max_bytes == 0 {
+        return plan;
+    }
+
+    let usage = used_bytes as f64 / max_bytes as f64;
+    let usage_label = format!(
+        "Database is at {} of {} ({:.0}%).",
+        megabytes(used_bytes),
+        megabytes(max_bytes),
+        usage * 100.0
+    );
+    if usage < SAMPLING_THRESHOLD {
+        plan.level = "ok".to_string();
+        return plan;
+    }
+
+    plan.degradations.push(usage_label);
+    if usage < PRUNE_THRESHOLD {
+        plan.level = "near".to_string();
+        plan.information_sample_every = NEAR_LIMIT_SAMPLE_EVERY;
+        plan.degradations.push(format!(
+            "Sampling information events: keeping 1 in {NEAR_LIMIT_SAMPLE_EVERY}."
+        ));
+        return plan;
+    }
+
+    let floor = if usage < 1.0 {
+        plan.level = "critical".to_string();
+        "warning"
+    } else {
+        plan.level = "over".to_string();
+        "error"
+    };
+    plan.min_severity = Some(floor.to_string());
+    plan.prune_information_share = ((usage - PRUNE_TARGET) / usage).clamp(0.1, 1.0);
+    plan.degradations.push(format!(
+        "Severity floor raised: only {floor} and above are stored."
+    ));
+    plan.degradations.push(format!(
+        "Pruning the oldest {:.0}% of stored information events.",
+        plan.prune_information_share * 100.0
+    ));
+    plan
+}
+
+/// Applies the plan's sampling and severity floor to a collected batch. Returns the events to
+/// store and how many were skipped.
+pub fn degrade_batch(
+    events: &[NormalizedEvent],
+    plan: &QuotaPlan,
+) -> (Vec<NormalizedEvent>, usize) {
+    let floor = plan.min_severity.as_deref().map(severity_rank).unwrap_or(0);
+    let sample_every = plan.information_sample_every.max(1);
+    let mut information_seen = 0usize;
+    let kept: Vec<NormalizedEvent> = events
+        .iter()
+        .filter(|event| {
+            let rank = severity_rank(event.severity.as_str());
+            if rank < floor {
+                return false;
+            }
+            if event.severity == "information" {
+                information_seen += 1;
+                return (information_seen - 1) % sample_every == 0;
+            }
+            true
+        })
+        .cloned()
+        .collect();
+    let skipped = events.len() - kept.len();
+    (kept, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    fn event(id: usize, severity: &str) -> NormalizedEvent {
+        NormalizedEvent {
+            id: id.to_string(),
+            timestamp: "2026-03-01T10:00:00Z".to_string(),
+            os: "windows".to_string(),
+            log_name: "System".to_string(),
+            category: "system".to_string(),
+            provider: "Test".to_string(),
+            event_id: Some(1),
+            severity: severity.to_string(),
+            message: String::new(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn degradation_escalates_with_usage() {
+        assert_eq!(plan_for_usage(500 * MB, 0).level, "unlimited");
+        assert!(plan_for_usage(100 * MB, 1000 * MB).degradations.is_empty());
+
+        let near = plan_for_usage(800 * MB, 1000 * MB);
+        assert_eq!(near.level, "near");
+        assert_eq!(near.information_sample_every, 4);
+        assert!(near.min_severity.is_none());
+
+        let critical = plan_for_usage(950 * MB, 1000 * MB);
+        assert_eq!(critical.min_severity.as_deref(), Some("warning"));
+        assert!(critical.prune_information_share > 0.0);
+
+        let over = plan_for_usage(1200 * MB, 1000 * MB);
+        assert_eq!(over.level, "over");
+        assert_eq!(over.min_severity.as_deref(), Some("error"));
+    }
+
+    #[test]
+    fn batch_sampling_keeps_problems() {
+        let events: Vec<NormalizedEvent> = (0..8)
+            .map(|index| event(index, "information"))
+            .chain([event(8, "warning"), event(9, "error")])
+            .collect();
+
+        let (kept, skipped) = degrade_batch(events.as_slice(), &plan_for_usage(80, 100));
+        assert_eq!(kept.len(), 4);
+        assert_eq!(skipped, 6);
+
+        let (kept, _) = degrade_batch(events.as_slice(), &plan_for_usage(120, 100));
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].severity, "error");
+    }
+}
@@ -0,0 +1,361 @@
+use crate::correlation::ScoredEvent;
+use crate::crash::CrashRecord;
+use crate::forwarding::severity_rank;
+use crate::prompt_budget::PromptBuilder;
+use crate::system_changes::{self, SystemChange};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const REDACTION: &str = "<sensitive info redacted>";
+/// Highest-ranked correlated events included in the prompt.
+const MAX_PROMPT_EVENTS: usize = 25;
+const MAX_EVENT_MESSAGE_CHARS: usize = 400;
+const SENSITIVE_KEYS: [&str; 12] = [
+    "user", "username", "account", "host", "hostname", "computer", "machine", "token", "apikey",
+    "api_key", "secret", "password",
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashRca {
+    pub probable_cause: String,
+    /// "high", "medium", "low" or "unknown" when the model did not say.
+    pub confidence: String,
+    pub evidence: Vec<String>,
+    pub next_steps: Vec<String>,
+    pub raw_response: String,
+}
+
+fn is_ipv4(token: &str) -> bool {
+    let parts: Vec<&str> = token.split('.').collect();
+    parts.len() == 4
+        && parts.iter().all(|part| {
+            !part.is_empty() && part.len() <= 3 && part.chars().all(|ch| ch.is_ascii_digit())
+        })
+}
+
+fn is_sensitive_token(token: &str) -> bool {
+    let trimmed =
+        token.trim_matches(|ch: char| matches!(ch, '(' | ')' | ',' | ';' | '"' | '\'' | '[' | ']'));
+    if trimmed.is_empty() {
+        return false;
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    if let Some((key, value)) = lower.split_once(['=', ':']) {
+        if !value.is_empty() && SENSITIVE_KEYS.contains(&key) {
+            return true;
+        }
+    }
+    let bytes = trimmed.as_bytes();
+    let windows_path =
+        bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\';
+    let unix_path = trimmed.starts_with('/') && trimmed.matches('/').count() >= 2;
+    let long_secret = trimmed.len() >= 24
+        && trimmed
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+    (trimmed.contains('@') && trimmed.contains('.'))
+        || trimmed.contains("://")
+        || trimmed.starts_with("\\\\")
+        || trimmed.starts_with("S-1-")
+        || windows_path
+        || unix_path
+        || long_secret
+        || is_ipv4(trimmed.split(':').next().unwrap_or_default())
+}
+
+/// Replaces addresses, paths, account names and secret-looking tokens before text leaves the host.
+pub fn redact_sensitive_text(input: &str) -> String {
+    input
+        .lines()
+        .map(|line| {
+            line.split(' ')
+                .map(|token| {
+                    if is_sensitive_token(token) {
+                        REDACTION
+                    } else {
+                        token
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn truncate_chars(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        return value.to_string();
+    }
+    let mut truncated: String = value.chars().take(max).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+/// Builds a root-cause prompt from a crash and its ranked surrounding events. The model is asked for
+/// JSON so the answer can be returned as structured fields.
+/// Builds the RCA prompt within `token_budget`. The crash itself is always included; service and
+/// driver changes from the day before come next, then surrounding events ordered critical/error
+/// first, each trimmed from the end when the budget runs out.
+pub fn build_crash_prompt(
+    crash: &CrashRecord,
+    events: &[ScoredEvent],
+    changes: &[SystemChange],
+    redact: bool,
+    token_budget: usize,
+) -> String {
+    let protect = |value: &str| {
+        if redact {
+            redact_sensitive_text(value)
+        } else {
+            value.to_string()
+        }
+    };
+    let os_label = match crash.os.as_str() {
+        "windows" => "Windows",
+        "macos" => "macOS",
+        _ => "Linux",
+    };
+
+    let mut lines = vec![
+        format!("Act as a senior {os_label} crash triage and root-cause analysis specialist."),
+        "Analyze the crash below using only the evidence provided. Prefer evidence-backed reasoning over speculation and suggest safe read-only checks before changes.".to_string(),
+        format!("Preserve \"{REDACTION}\" placeholders exactly if present."),
+        "Respond with a single JSON object and nothing else, using these keys:".to_string(),
+        "{\"probableCause\": string, \"confidence\": \"high\" | \"medium\" | \"low\", \"evidence\": [string], \"nextSteps\": [string]}".to_string(),
+        String::new(),
+        "Crash:".to_string(),
+        format!("- Summary: {}", protect(crash.summary.as_str())),
+        format!("- Type: {}", protect(crash.crash_type.as_str())),
+        format!("- Source: {}", protect(crash.source.as_str())),
+        format!("- Timestamp: {}", crash.timestamp),
+        format!("- Code: {}", protect(crash.code.as_deref().unwrap_or("Unavailable"))),
+        format!(
+            "- Suspected component: {}",
+            protect(crash.suspected_component.as_deref().unwrap_or("Unknown"))
+        ),
+    ];
+    if let Some(details) = crash
+        .details
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+    {
+        lines.push("- Details:".to_string());
+        for line in details
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .take(20)
+        {
+            lines.push(format!("  {}", protect(line.trim())));
+        }
+    }
+
+    lines.push(String::new());
+    let change_lines = changes
+        .iter()
+        .map(|change| format!("- {}", protect(system_changes::describe(change).as_str())))
+        .collect::<Vec<_>>();
+    if change_lines.is_empty() {
+        lines.push(
+            "Service and driver changes: none were recorded in the day before the crash."
+                .to_string(),
+        );
+    }
+    let mut ordered = events.iter().take(MAX_PROMPT_EVENTS).collect::<Vec<_>>();
+    if ordered.is_empty() {
+        lines.push("Surrounding events: none were recorded near the crash.".to_string());
+    }
+    // Stable sort: within a severity, events stay in relevance order.
+    ordered.sort_by_key(|scored| std::cmp::Reverse(severity_rank(scored.event.severity.as_str())));
+    let event_lines = ordered
+        .into_iter()
+        .map(|scored| {
+            let event = &scored.event;
+            let event_id = event
+                .event_id
+                .map(|value| format!(" id {value}"))
+                .unwrap_or_default();
+            let message = truncate_chars(
+                event.message.replace('\n', " ").trim(),
+                MAX_EVENT_MESSAGE_CHARS,
+            );
+            format!(
+                "- [{score}] {timestamp} {severity} {provider}{event_id}: {message}",
+                score = scored.relevance_score.round(),
+                timestamp = event.timestamp,
+                severity = event.severity,
+                provider = protect(event.provider.as_str()),
+                message = protect(message.as_str()),
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut prompt = PromptBuilder::new().required(lines);
+    if !change_lines.is_empty() {
+        prompt = prompt.ranked(
+            "Service and driver changes in the day before the crash, newest first:",
+            change_lines,
+        );
+    }
+    if !event_lines.is_empty() {
+        prompt = prompt.ranked(
+            "Surrounding events, most severe first and then by relevance score:",
+            event_lines,
+        );
+    }
+    prompt.build(token_budget).text
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::trim).map(ToString::to_string))
+            .filter(|item| !item.is_empty())
+            .collect(),
+        Some(Value::String(text)) if !text.trim().is_empty() => vec![text.trim().to_string()],
+        _ => Vec::new(),
+    }
+}
+
+fn normalize_confidence(value: Option<&Value>) -> String {
+    let label = match value {
+        Some(Value::String(text)) => text.trim().to_ascii_lowercase(),
+        Some(Value::Number(number)) => match number.as_f64().unwrap_or_default() {
+            score if score >= 0.7 => "high".to_string(),
+            score if score >= 0.4 => "medium".to_string(),
+            _ => "low".to_string(),
+        },
+        _ => String::new(),
+    };
+    match label.as_str() {
+        "high" | "medium" | "low" => label,
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Extracts the structured RCA from a model reply, tolerating code fences and surrounding prose.
+/// Replies without usable JSON fall back to the first paragraph and any bulleted steps.
+pub fn parse_rca_response(response: &str) -> CrashRca {
+    let parsed = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => {
+            serde_json::from_str::<Value>(&response[start..=end]).ok()
+        }
+        _ => None,
+    };
+
+    if let Some(value) = parsed.filter(Value::is_object) {
+        let probable_cause = value
+            .get("probableCause")
+            .or_else(|| value.get("probable_cause"))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string();
+        if !probable_cause.is_empty() {
+            return CrashRca {
+                probable_cause,
+                confidence: normalize_confidence(value.get("confidence")),
+                evidence: string_list(value.get("evidence")),
+                next_steps: string_list(value.get("nextSteps").or_else(|| value.get("next_steps"))),
+                raw_response: response.to_string(),
+            };
+        }
+    }
+
+    let probable_cause = response
+        .split("\n\n")
+        .map(str::trim)
+        .find(|paragraph| !paragraph.is_empty())
+        .unwrap_or_default()
+        .to_string();
+    let next_steps = response
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            line.strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| {
+                    let digits = line.find(|ch: char| !ch.is_ascii_digit())?;
+                    (digits > 0).then(|| line[digits..].trim_start_matches(['.', ')']))
+                })
+        })
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(ToString::to_string)
+        .collect();
+    CrashRca {
+        probable_cause,
+        confidence: "unknown".to_string(),
+        evidence: Vec::new(),
+        next_steps,
+        raw_response: response.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fenced_json_reply() {
+        let reply = "Here is the analysis:\n```json\n{\"probableCause\": \"Faulty GPU driver nvlddmkm.sys\", \"confidence\": \"High\", \"evidence\": [\"Display driver stopped responding 2m before\"], \"nextSteps\": [\"Roll back the display driver\", \"Check for a newer WHQL driver\"]}\n```";
+        let rca = parse_rca_response(reply);
+        assert_eq!(rca.probable_cause, "Faulty GPU driver nvlddmkm.sys");
+        assert_eq!(rca.confidence, "high");
+        assert_eq!(rca.evidence.len(), 1);
+        assert_eq!(rca.next_steps[0], "Roll back the display driver");
+    }
+
+    #[test]
+    fn falls_back_to_prose_reply() {
+        let reply = "The crash is most likely caused by memory corruption in mso.dll.\n\nNext steps:\n1. Run an Office repair\n- Check for add-ins";
+        let rca = parse_rca_response(reply);
+        assert_eq!(
+            rca.probable_cause,
+            "The crash is most likely caused by memory corruption in mso.dll."
+        );
+        assert_eq!(rca.confidence, "unknown");
+        assert_eq!(
+            rca.next_steps,
+            vec!["Run an Office repair", "Check for add-ins"]
+        );
+    }
+
+    #[test]
+    fn lists_changes_before_the_crash_in_the_prompt() {
+        let crash = CrashRecord::new(
+            "windows",
+            "WER",
+            "Blue Screen",
+            Some("0x000000D1"),
+            "Bugcheck",
+            None,
+            None,
+            "localhost",
+            false,
+        );
+        let change = SystemChange {
+            timestamp: "2026-03-01T09:00:00Z".to_string(),
+            kind: "driver".to_string(),
+            name: "netfilter".to_string(),
+            path: Some("system32\\drivers\\netfilter.sys".to_string()),
+            ..SystemChange::default()
+        };
+        let prompt = build_crash_prompt(&crash, &[], &[change], false, 4000);
+        assert!(prompt.contains("Service and driver changes in the day before the crash"));
+        assert!(prompt.contains("driver netfilter (system32\\drivers\\netfilter.sys)"));
+        assert!(prompt.contains("Surrounding events: none were recorded near the crash."));
+    }
+
+    #[test]
+    fn redacts_addresses_paths_and_accounts() {
+        let redacted = redact_sensitive_text(
+            "Login by user=alice from 10.0.0.12 loaded C:\\Users\\alice\\app.dll via https://example.com/x",
+        );
+        assert!(!redacted.contains("alice"));
+        assert!(!redacted.contains("10.0.0.12"));
+        assert!(!redacted.contains("example.com"));
+        assert!(redacted.starts_with("Login by "));
+    }
+}
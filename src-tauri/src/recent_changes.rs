@@ -0,0 +1,278 @@
+//! "What changed before this crash": driver loads, OS and package updates, new services and
+//! configuration changes recorded in the hours leading up to a crash.
+
+use crate::boot::is_update_event;
+use crate::crash::CrashRecord;
+use crate::logs::NormalizedEvent;
+use crate::timestamps::parse_timestamp;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_HOURS: u32 = 24;
+pub const MAX_HOURS: u32 = 14 * 24;
+const MAX_DETAIL_CHARS: usize = 240;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentChange {
+    pub timestamp: String,
+    /// One of driver, update, service or config.
+    pub kind: String,
+    pub title: String,
+    pub detail: String,
+    pub provider: String,
+    pub event_id: Option<u32>,
+    /// Id of the stored event, for drill-down.
+    pub reference_id: String,
+    pub minutes_before_crash: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentChangesReport {
+    pub crash_id: String,
+    pub crash_timestamp: String,
+    pub window_start: String,
+    pub hours: u32,
+    pub counts: BTreeMap<String, usize>,
+    /// Newest first, so the change closest to the crash leads.
+    pub changes: Vec<RecentChange>,
+}
+
+/// The `[start, end]` window to read for `crash`, as RFC3339 strings.
+pub fn window(crash: &CrashRecord, hours: u32) -> Result<(String, String), String> {
+    let crashed_at = parse_timestamp(crash.timestamp.as_str())
+        .ok_or_else(|| "Crash timestamp is not a valid time.".to_string())?;
+    let start = crashed_at - Duration::hours(i64::from(hours));
+    Ok((start.to_rfc3339(), crashed_at.to_rfc3339()))
+}
+
+fn is_driver_change(provider: &str, message: &str, event: &NormalizedEvent) -> bool {
+    (provider.contains("service control manager")
+        && event.event_id == Some(7045)
+        && message.contains("kernel mode driver"))
+        || (provider.contains("userpnp") && matches!(event.event_id, Some(20001) | Some(20003)))
+        || (provider.contains("kernel-pnp") && matches!(event.event_id, Some(400) | Some(410)))
+        || provider.contains("dkms")
+        || (provider == "kernel"
+            && (message.contains("loading out-of-tree module")
+                || message.contains("module verification failed")))
+        || ((provider.contains("kernelmanagerd") || provider.contains("kextd"))
+            && message.contains("load"))
+}
+
+fn is_service_change(provider: &str, message: &str, event: &NormalizedEvent) -> bool {
+    (provider.contains("service control manager") && event.event_id == Some(7045))
+        || (provider == "systemd" && message.contains("created symlink"))
+        || (provider.contains("launchd")
+            && (message.contains("bootstrap") || message.contains("service added")))
+}
+
+fn is_config_change(provider: &str, message: &str, event: &NormalizedEvent) -> bool {
+    // Start-type changes, audit policy, registry values, firewall rules and Group Policy.
+    (provider.contains("service control manager") && event.event_id == Some(7040))
+        || (provider.contains("security-auditing")
+            && matches!(
+                event.event_id,
+                Some(4657) | Some(4719) | Some(4946) | Some(4947) | Some(4948) | Some(4950)
+            ))
+        || (provider.contains("grouppolicy") && matches!(event.event_id, Some(1502) | Some(1503)))
+        || (provider == "systemd" && message.starts_with("reloading"))
+        || (provider == "sudo" && message.contains("command=") && message.contains("/etc/"))
+}
+
+/// The kind of change `event` records, if it records one. Drivers are checked before services
+/// because Windows registers a driver install as a service install.
+pub fn classify_change(event: &NormalizedEvent) -> Option<&'static str> {
+    let provider = event.provider.to_ascii_lowercase();
+    let message = event.message.trim().to_ascii_lowercase();
+    if is_driver_change(provider.as_str(), message.as_str(), event) {
+        Some("driver")
+    } else if is_update_event(event) {
+        Some("update")
+    } else if is_service_change(provider.as_str(), message.as_str(), event) {
+        Some("service")
+    } else if is_config_change(provider.as_str(), message.as_str(), event) {
+        Some("config")
+    } else {
+        None
+    }
+}
+
+fn title(kind: &str, event: &NormalizedEvent) -> String {
+    match kind {
+        "driver" => format!("Driver change from {}", event.provider),
+        "update" => format!("Update activity from {}", event.provider),
+        "service" => format!("New service registered by {}", event.provider),
+        _ => format!("Configuration change from {}", event.provider),
+    }
+}
+
+fn first_line(message: &str) -> String {
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= MAX_DETAIL_CHARS {
+        line.to_string()
+    } else {
+        let truncated: String = line.chars().take(MAX_DETAIL_CHARS).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Lists the changes among `events` from the `hours` before `crash`. Events from other hosts
+/// or outside the window are ignored, so callers may pass a wider read.
+pub fn recent_changes(
+    crash: &CrashRecord,
+    events: &[NormalizedEvent],
+    hours: u32,
+) -> Result<RecentChangesReport, String> {
+    let crashed_at = parse_timestamp(crash.timestamp.as_str())
+        .ok_or_else(|| "Crash timestamp is not a valid time.".to_string())?;
+    let start = crashed_at - Duration::hours(i64::from(hours));
+
+    let mut changes = Vec::new();
+    for event in events
+        .iter()
+        .filter(|event| event.source_host == crash.source_host)
+    {
+        let Some(at) = parse_timestamp(event.timestamp.as_str()) else {
+            continue;
+        };
+        if at < start || at > crashed_at {
+            continue;
+        }
+        let Some(kind) = classify_change(event) else {
+            continue;
+        };
+        changes.push(RecentChange {
+            timestamp: event.timestamp.clone(),
+            kind: kind.to_string(),
+            title: title(kind, event),
+            detail: first_line(event.message.as_str()),
+            provider: event.provider.clone(),
+            event_id: event.event_id,
+            reference_id: event.id.clone(),
+            minutes_before_crash: (crashed_at - at).num_minutes(),
+        });
+    }
+    changes.sort_by_key(|change| change.minutes_before_crash);
+
+    let mut counts = BTreeMap::new();
+    for change in &changes {
+        *counts.entry(change.kind.clone()).or_insert(0) += 1;
+    }
+    Ok(RecentChangesReport {
+        crash_id: crash.id.clone(),
+        crash_timestamp: crash.timestamp.clone(),
+        window_start: start.to_rfc3339(),
+        hours,
+        counts,
+        changes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn event(
+        provider: &str,
+        event_id: Option<u32>,
+        message: &str,
+        timestamp: &str,
+    ) -> NormalizedEvent {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "System",
+            "system",
+            provider,
+            event_id,
+            "information",
+            message,
+            "localhost",
+        );
+        event.timestamp = timestamp.to_string();
+        event
+    }
+
+    #[test]
+    fn classifies_drivers_before_services() {
+        let driver = event(
+            "Service Control Manager",
+            Some(7045),
+            "A service was installed in the system. Service Type: kernel mode driver",
+            "2026-03-01T09:00:00Z",
+        );
+        let service = event(
+            "Service Control Manager",
+            Some(7045),
+            "A service was installed in the system. Service Type: user mode service",
+            "2026-03-01T09:00:00Z",
+        );
+        let noise = event(
+            "Service Control Manager",
+            Some(7036),
+            "entered the running state",
+            "2026-03-01T09:00:00Z",
+        );
+        assert_eq!(classify_change(&driver), Some("driver"));
+        assert_eq!(classify_change(&service), Some("service"));
+        assert_eq!(classify_change(&noise), None);
+    }
+
+    #[test]
+    fn lists_changes_in_the_window_closest_first() {
+        let mut crash = CrashRecord::new(
+            "windows",
+            "WER",
+            "Blue Screen",
+            None,
+            "Bugcheck 0x9f",
+            None,
+            None,
+            "localhost",
+            false,
+        );
+        crash.timestamp = "2026-03-01T10:00:00Z".to_string();
+        let events = vec![
+            event(
+                "Microsoft-Windows-WindowsUpdateClient",
+                Some(19),
+                "Installation Successful",
+                "2026-03-01T08:00:00Z",
+            ),
+            event(
+                "Microsoft-Windows-GroupPolicy",
+                Some(1502),
+                "Group policy settings were applied",
+                "2026-03-01T09:30:00Z",
+            ),
+            event(
+                "Microsoft-Windows-WindowsUpdateClient",
+                Some(19),
+                "Installation Successful",
+                "2026-02-27T08:00:00Z",
+            ),
+            event(
+                "Microsoft-Windows-GroupPolicy",
+                Some(1502),
+                "After the crash",
+                "2026-03-01T10:30:00Z",
+            ),
+        ];
+        let report = recent_changes(&crash, events.as_slice(), 24).unwrap();
+        let kinds: Vec<_> = report
+            .changes
+            .iter()
+            .map(|change| change.kind.as_str())
+            .collect();
+        assert_eq!(kinds, vec!["config", "update"]);
+        assert_eq!(report.changes[0].minutes_before_crash, 30);
+        assert_eq!(report.counts.get("update"), Some(&1));
+    }
+}
@@ -0,0 +1,81 @@
+//! Regular-expression search over stored event messages. Patterns compile with the `regex` crate
+//! under size and nesting limits, and SQLite runs them through the `REGEXP` function registered
+//! on each connection, so a search scans the table without loading it into memory. The crate
+//! matches in linear time; a scan over a very large table is still cut off after a time limit.
+
+use crate::db;
+use crate::logs::NormalizedEvent;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+const MAX_PATTERN_CHARS: usize = 1000;
+const NEST_LIMIT: u32 = 64;
+/// A search still scanning after this long stops and returns what it found.
+const TIME_LIMIT: Duration = Duration::from_secs(10);
+pub const DEFAULT_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegexSearchResult {
+    /// Newest first.
+    pub events: Vec<NormalizedEvent>,
+    /// More events matched than the limit allowed.
+    pub truncated: bool,
+    /// The scan hit the time limit; older matches may be missing.
+    pub timed_out: bool,
+}
+
+/// Compiles a user pattern, rejecting ones that are empty, overlong or compile too large.
+/// Matching is case-sensitive unless the pattern starts with `(?i)`.
+pub fn compile(pattern: &str) -> Result<Regex, String> {
+    if pattern.trim().is_empty() {
+        return Err("Search pattern is empty.".to_string());
+    }
+    if pattern.chars().count() > MAX_PATTERN_CHARS {
+        return Err(format!(
+            "Search pattern is longer than {MAX_PATTERN_CHARS} characters."
+        ));
+    }
+    RegexBuilder::new(pattern)
+        .size_limit(PATTERN_SIZE_LIMIT)
+        .dfa_size_limit(PATTERN_SIZE_LIMIT)
+        .nest_limit(NEST_LIMIT)
+        .build()
+        .map_err(|error| format!("Invalid search pattern: {error}"))
+}
+
+/// Stored events whose message matches `pattern`, newest first.
+pub fn search_events(
+    pattern: &str,
+    host: Option<&str>,
+    limit: usize,
+) -> Result<RegexSearchResult, String> {
+    compile(pattern)?;
+    let (mut events, timed_out) = db::search_events_regex(pattern, host, limit + 1, TIME_LIMIT)?;
+    let truncated = events.len() > limit;
+    events.truncate(limit);
+    Ok(RegexSearchResult {
+        events,
+        truncated,
+        timed_out,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiles_patterns_within_limits() {
+        let regex = compile(r"(?i)failed to start \w+\.service").expect("valid pattern");
+        assert!(regex.is_match("Failed to start nginx.service"));
+
+        assert!(compile("   ").is_err());
+        assert!(compile("(unclosed").is_err());
+        assert!(compile("a".repeat(MAX_PATTERN_CHARS + 1).as_str()).is_err());
+        assert!(compile(format!("{}a{}", "(".repeat(100), ")".repeat(100)).as_str()).is_err());
+        assert!(compile(r"\w{1000}\w{1000}\w{1000}").is_err());
+    }
+}
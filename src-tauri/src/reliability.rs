@@ -0,0 +1,286 @@
+//! Windows Reliability Monitor history: `Win32_ReliabilityRecords` become events in a
+//! `Reliability` log (so they line up with crashes in the timeline and crash windows) and
+//! `Win32_ReliabilityStabilityMetrics` become `reliability` metric samples.
+
+use crate::db::MetricSample;
+use crate::logs::NormalizedEvent;
+#[cfg(any(target_os = "windows", test))]
+use crate::logs::SupportedOs;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(any(target_os = "windows", test))]
+use serde_json::Value;
+#[cfg(any(target_os = "windows", test))]
+use std::collections::BTreeMap;
+
+pub const RELIABILITY_SOURCE: &str = "reliability";
+pub const RELIABILITY_LOG: &str = "Reliability";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReliabilityCollectionResult {
+    pub events: Vec<NormalizedEvent>,
+    pub samples: Vec<MetricSample>,
+    pub warnings: Vec<String>,
+}
+
+/// Reliability Monitor's row for a record, with the severity Hermes stores it under.
+#[cfg(any(target_os = "windows", test))]
+fn classify_record(provider: &str, event_id: Option<u32>) -> (&'static str, &'static str) {
+    let provider = provider.to_ascii_lowercase();
+    match (provider.as_str(), event_id) {
+        ("microsoft-windows-wer-systemerrorreporting", _)
+        | ("eventlog", Some(6008))
+        | ("microsoft-windows-kernel-power", Some(41)) => ("Windows failures", "critical"),
+        ("application error" | "application hang" | "windows error reporting", _) => {
+            ("Application failures", "error")
+        }
+        ("msiinstaller", Some(11708)) | ("microsoft-windows-windowsupdateclient", Some(20)) => {
+            ("Warnings", "warning")
+        }
+        ("msiinstaller", _)
+        | ("microsoft-windows-windowsupdateclient", _)
+        | ("microsoft-windows-setup", _) => ("Information", "information"),
+        _ => ("Miscellaneous failures", "error"),
+    }
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn json_items(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(items) => items,
+        Value::Null => Vec::new(),
+        other => vec![other],
+    }
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn parse_time(value: Option<&Value>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(Value::as_str)
+        .and_then(|text| DateTime::parse_from_rfc3339(text).ok())
+        .map(|parsed| parsed.with_timezone(&Utc))
+}
+
+/// Turns `Win32_ReliabilityRecords` rows (as emitted by `collect_windows_reliability`) into
+/// events, keeping those at or after `start`.
+#[cfg(any(target_os = "windows", test))]
+fn parse_reliability_records(
+    value: Value,
+    host: &str,
+    start: Option<DateTime<Utc>>,
+) -> Vec<NormalizedEvent> {
+    let mut events = Vec::new();
+    for item in json_items(value) {
+        let Some(timestamp) = parse_time(item.get("timeGenerated")) else {
+            continue;
+        };
+        if start.is_some_and(|start| timestamp < start) {
+            continue;
+        }
+        let text = |key: &str| {
+            item.get(key)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let provider = text("sourceName").unwrap_or("Reliability Monitor");
+        let event_id = item
+            .get("eventIdentifier")
+            .and_then(Value::as_u64)
+            .and_then(|value| u32::try_from(value).ok());
+        let (row, severity) = classify_record(provider, event_id);
+        let category = if row == "Windows failures" {
+            "system"
+        } else {
+            "application"
+        };
+        let message = text("message").unwrap_or("No reliability record message.");
+
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            RELIABILITY_LOG,
+            category,
+            provider,
+            event_id,
+            severity,
+            message,
+            host,
+        );
+        event.timestamp = timestamp.to_rfc3339();
+        event.computer = text("computerName").map(str::to_string);
+        event.user_name = text("user").map(str::to_string);
+        let mut metadata = BTreeMap::new();
+        metadata.insert("ReliabilityCategory".to_string(), row.to_string());
+        for (key, field) in [("productName", "ProductName"), ("logFile", "SourceLog")] {
+            if let Some(value) = text(key) {
+                metadata.insert(field.to_string(), value.to_string());
+            }
+        }
+        event.metadata = Some(metadata);
+        event.raw_payload = Some(item.to_string());
+        event.assign_stable_id();
+        events.push(event);
+    }
+    events
+}
+
+/// Turns `Win32_ReliabilityStabilityMetrics` rows into `stability_index` samples (1-10).
+#[cfg(any(target_os = "windows", test))]
+fn parse_stability_metrics(
+    value: Value,
+    host: &str,
+    start: Option<DateTime<Utc>>,
+) -> Vec<MetricSample> {
+    json_items(value)
+        .into_iter()
+        .filter_map(|item| {
+            let timestamp = parse_time(item.get("endMeasurementDate"))?;
+            if start.is_some_and(|start| timestamp < start) {
+                return None;
+            }
+            Some(MetricSample {
+                timestamp: timestamp.to_rfc3339(),
+                source_host: host.to_string(),
+                source: RELIABILITY_SOURCE.to_string(),
+                device: "system".to_string(),
+                name: "stability_index".to_string(),
+                value: item.get("systemStabilityIndex")?.as_f64()?,
+                unit: None,
+                status: None,
+            })
+        })
+        .collect()
+}
+
+/// Reads Reliability Monitor records and stability metrics generated since `start`.
+pub fn collect_reliability(
+    host: &str,
+    start: Option<DateTime<Utc>>,
+) -> ReliabilityCollectionResult {
+    #[cfg(target_os = "windows")]
+    {
+        return collect_windows_reliability(host, start);
+    }
+
+    #[allow(unreachable_code)]
+    {
+        let _ = (host, start);
+        ReliabilityCollectionResult {
+            warnings: vec!["Reliability Monitor data is only available on Windows.".to_string()],
+            ..ReliabilityCollectionResult::default()
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn run_cim_query(script: &str, class: &str, warnings: &mut Vec<String>) -> Value {
+    let output = match std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()
+    {
+        Ok(output) => output,
+        Err(error) => {
+            warnings.push(format!("Failed to query {class}: {error}"));
+            return Value::Null;
+        }
+    };
+    if output.stdout.iter().all(u8::is_ascii_whitespace) {
+        // No rows at all, or the Reliability Analysis task has never run on this machine.
+        if !output.status.success() {
+            warnings.push(format!(
+                "{class} is unavailable: {}",
+                String::from_utf8_lossy(output.stderr.as_slice()).trim()
+            ));
+        }
+        return Value::Null;
+    }
+    serde_json::from_slice(output.stdout.as_slice()).unwrap_or_else(|_| {
+        warnings.push(format!("{class} returned no readable output."));
+        Value::Null
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn collect_windows_reliability(
+    host: &str,
+    start: Option<DateTime<Utc>>,
+) -> ReliabilityCollectionResult {
+    let mut result = ReliabilityCollectionResult::default();
+    let records_script = "Get-CimInstance Win32_ReliabilityRecords -ErrorAction Stop | ForEach-Object { [pscustomobject]@{\
+sourceName=$_.SourceName;eventIdentifier=$_.EventIdentifier;productName=$_.ProductName;message=$_.Message;\
+timeGenerated=$_.TimeGenerated.ToUniversalTime().ToString('o');logFile=$_.LogFile;computerName=$_.ComputerName;user=$_.User} } | ConvertTo-Json -Compress";
+    let metrics_script = "Get-CimInstance Win32_ReliabilityStabilityMetrics -ErrorAction Stop | ForEach-Object { [pscustomobject]@{\
+systemStabilityIndex=$_.SystemStabilityIndex;endMeasurementDate=$_.EndMeasurementDate.ToUniversalTime().ToString('o')} } | ConvertTo-Json -Compress";
+
+    let records = run_cim_query(
+        records_script,
+        "Win32_ReliabilityRecords",
+        &mut result.warnings,
+    );
+    result.events = parse_reliability_records(records, host, start);
+    let metrics = run_cim_query(
+        metrics_script,
+        "Win32_ReliabilityStabilityMetrics",
+        &mut result.warnings,
+    );
+    result.samples = parse_stability_metrics(metrics, host, start);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn classifies_reliability_records() {
+        let value = json!([
+            {
+                "sourceName": "Application Error",
+                "eventIdentifier": 1000,
+                "productName": "explorer.exe",
+                "message": "Faulting application name: explorer.exe",
+                "timeGenerated": "2026-03-02T09:00:00.0000000Z",
+                "logFile": "Application"
+            },
+            {
+                "sourceName": "Microsoft-Windows-WER-SystemErrorReporting",
+                "eventIdentifier": 1001,
+                "message": "The computer has rebooted from a bugcheck.",
+                "timeGenerated": "2026-03-03T09:00:00.0000000Z"
+            },
+            {
+                "sourceName": "MsiInstaller",
+                "eventIdentifier": 11707,
+                "message": "Product: Tool -- Installation completed successfully.",
+                "timeGenerated": "2026-02-01T09:00:00.0000000Z"
+            }
+        ]);
+        let start = DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let events = parse_reliability_records(value, "localhost", Some(start));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].log_name, RELIABILITY_LOG);
+        assert_eq!(events[0].severity, "error");
+        let metadata = events[0].metadata.as_ref().unwrap();
+        assert_eq!(metadata["ReliabilityCategory"], "Application failures");
+        assert_eq!(metadata["ProductName"], "explorer.exe");
+        assert_eq!(events[1].severity, "critical");
+        assert_eq!(events[1].category, "system");
+        assert_eq!(events[1].timestamp, "2026-03-03T09:00:00+00:00");
+    }
+
+    #[test]
+    fn parses_single_stability_metric() {
+        let value = json!({
+            "systemStabilityIndex": 7.43,
+            "endMeasurementDate": "2026-03-03T10:00:00.0000000Z"
+        });
+        let samples = parse_stability_metrics(value, "localhost", None);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].source, RELIABILITY_SOURCE);
+        assert_eq!(samples[0].value, 7.43);
+    }
+}
@@ -0,0 +1,394 @@
+use crate::logs::NormalizedEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSessionEvent {
+    pub timestamp: String,
+    pub protocol: String,
+    pub action: String,
+    pub user: Option<String>,
+    pub source_address: Option<String>,
+    pub session_id: Option<String>,
+    pub record_id: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSourceSummary {
+    pub address: String,
+    pub protocols: Vec<String>,
+    pub users: Vec<String>,
+    pub session_count: usize,
+    pub failed_count: usize,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteAccessReport {
+    pub events: Vec<RemoteSessionEvent>,
+    pub sources: Vec<RemoteSourceSummary>,
+    pub warnings: Vec<String>,
+}
+
+/// Reads a labelled value from either a rendered Windows message ("Source Network Address: 1.2.3.4")
+/// or the `Data: Key=value, ...` fallback used when rendering is unavailable.
+fn labelled_value(message: &str, labels: &[&str]) -> Option<String> {
+    if let Some(data) = message.strip_prefix("Data: ") {
+        for pair in data.split(", ") {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            if labels
+                .iter()
+                .any(|label| key.trim().eq_ignore_ascii_case(label))
+            {
+                return clean_value(value);
+            }
+        }
+        return None;
+    }
+    for line in message.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if labels
+            .iter()
+            .any(|label| key.trim().eq_ignore_ascii_case(label))
+        {
+            return clean_value(value);
+        }
+    }
+    None
+}
+
+fn clean_value(value: &str) -> Option<String> {
+    let value = value.trim();
+    // Windows uses "-" or "LOCAL" when a field does not apply to the session.
+    if value.is_empty() || value == "-" || value.eq_ignore_ascii_case("local") {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Returns the token that follows `marker`, e.g. the address after "from ".
+fn word_after(message: &str, marker: &str) -> Option<String> {
+    let index = message.find(marker)?;
+    message[index + marker.len()..]
+        .split_whitespace()
+        .next()
+        .map(|word| word.trim_end_matches([',', ';']).to_string())
+}
+
+fn windows_session(event: &NormalizedEvent) -> Option<RemoteSessionEvent> {
+    let provider = event.provider.to_ascii_lowercase();
+    let log_name = event.log_name.to_ascii_lowercase();
+    let (protocol, action) = if provider.contains("terminalservices-localsessionmanager")
+        || log_name.contains("terminalservices-localsessionmanager")
+    {
+        let action = match event.event_id? {
+            21 => "logon",
+            23 => "logoff",
+            24 => "disconnect",
+            25 => "reconnect",
+            _ => return None,
+        };
+        ("rdp", action)
+    } else if provider.contains("terminalservices-remoteconnectionmanager")
+        && event.event_id == Some(1149)
+    {
+        ("rdp", "authenticated")
+    } else if log_name == "security" {
+        match event.event_id? {
+            4778 => ("rdp", "reconnect"),
+            4779 => ("rdp", "disconnect"),
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    let message = event.message.as_str();
+    let user = labelled_value(message, &["User", "Account Name", "AccountName", "Param1"]);
+    let user = match labelled_value(message, &["AccountDomain", "Account Domain", "Param2"]) {
+        Some(domain)
+            if user
+                .as_deref()
+                .map(|value| !value.contains('\\'))
+                .unwrap_or(false) =>
+        {
+            user.map(|value| format!("{domain}\\{value}"))
+        }
+        _ => user,
+    };
+    Some(RemoteSessionEvent {
+        timestamp: event.timestamp.clone(),
+        protocol: protocol.to_string(),
+        action: action.to_string(),
+        user,
+        source_address: labelled_value(
+            message,
+            &[
+                "Source Network Address",
+                "Client Address",
+                "ClientAddress",
+                "Address",
+                "Param3",
+            ],
+        ),
+        session_id: labelled_value(
+            message,
+            &["Session ID", "SessionID", "Session Name", "SessionName"],
+        ),
+        record_id: event.id.clone(),
+        message: event.message.clone(),
+    })
+}
+
+fn ssh_session(event: &NormalizedEvent) -> Option<RemoteSessionEvent> {
+    let provider = event.provider.to_ascii_lowercase();
+    if !provider.contains("sshd") {
+        return None;
+    }
+    let message = event.message.as_str();
+    let (action, user) = if let Some(rest) = message.strip_prefix("Accepted ") {
+        ("logon", word_after(rest, " for "))
+    } else if message.starts_with("Failed password") || message.starts_with("Failed publickey") {
+        let user =
+            word_after(message, " for invalid user ").or_else(|| word_after(message, " for "));
+        ("failed", user)
+    } else if message.starts_with("Invalid user ") {
+        ("failed", word_after(message, "Invalid user "))
+    } else if message.starts_with("Disconnected from") || message.starts_with("Received disconnect")
+    {
+        ("disconnect", word_after(message, " user "))
+    } else if message.contains("session closed for user") {
+        ("logoff", word_after(message, "session closed for user "))
+    } else {
+        return None;
+    };
+
+    Some(RemoteSessionEvent {
+        timestamp: event.timestamp.clone(),
+        protocol: "ssh".to_string(),
+        action: action.to_string(),
+        user,
+        source_address: word_after(message, " from ")
+            .filter(|value| value.parse::<std::net::IpAddr>().is_ok())
+            .or_else(|| {
+                // "Disconnected from user bob 10.0.0.5 port 22" puts the address before "port".
+                let before_port = message.split(" port ").next()?;
+                before_port
+                    .rsplit(' ')
+                    .next()
+                    .filter(|value| value.parse::<std::net::IpAddr>().is_ok())
+                    .map(ToString::to_string)
+            }),
+        session_id: None,
+        record_id: event.id.clone(),
+        message: event.message.clone(),
+    })
+}
+
+/// macOS screensharingd logs lines such as
+/// "Authentication: SUCCEEDED :: User Name: bob :: Viewer Address: 10.0.0.5 :: Type: DH".
+fn screen_sharing_session(event: &NormalizedEvent) -> Option<RemoteSessionEvent> {
+    let provider = event.provider.to_ascii_lowercase();
+    if !provider.contains("screensharing") {
+        return None;
+    }
+    let message = event.message.as_str();
+    let field = |label: &str| {
+        message.split("::").find_map(|part| {
+            let (key, value) = part.split_once(':')?;
+            if key.trim().eq_ignore_ascii_case(label) {
+                clean_value(value)
+            } else {
+                None
+            }
+        })
+    };
+    let lower = message.to_ascii_lowercase();
+    let action = if lower.contains("authentication: succeeded") {
+        "logon"
+    } else if lower.contains("authentication: failed") {
+        "failed"
+    } else if lower.contains("connection closed") || lower.contains("viewer disconnected") {
+        "disconnect"
+    } else {
+        return None;
+    };
+
+    Some(RemoteSessionEvent {
+        timestamp: event.timestamp.clone(),
+        protocol: "screen-sharing".to_string(),
+        action: action.to_string(),
+        user: field("User Name"),
+        source_address: field("Viewer Address"),
+        session_id: None,
+        record_id: event.id.clone(),
+        message: event.message.clone(),
+    })
+}
+
+pub fn parse_remote_session(event: &NormalizedEvent) -> Option<RemoteSessionEvent> {
+    match event.os.as_str() {
+        "windows" => windows_session(event),
+        "macos" => screen_sharing_session(event).or_else(|| ssh_session(event)),
+        _ => ssh_session(event),
+    }
+}
+
+fn push_unique(values: &mut Vec<String>, value: &str) {
+    if !values.iter().any(|existing| existing == value) {
+        values.push(value.to_string());
+    }
+}
+
+pub fn analyze_remote_access(events: &[NormalizedEvent]) -> RemoteAccessReport {
+    let mut sessions: Vec<RemoteSessionEvent> =
+        events.iter().filter_map(parse_remote_session).collect();
+    sessions.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
+
+    let mut sources: BTreeMap<String, RemoteSourceSummary> = BTreeMap::new();
+    for session in &sessions {
+        let Some(address) = session.source_address.as_deref() else {
+            continue;
+        };
+        let entry = sources
+            .entry(address.to_string())
+            .or_insert_with(|| RemoteSourceSummary {
+                address: address.to_string(),
+                protocols: Vec::new(),
+                users: Vec::new(),
+                session_count: 0,
+                failed_count: 0,
+                first_seen: session.timestamp.clone(),
+                last_seen: session.timestamp.clone(),
+            });
+        push_unique(&mut entry.protocols, session.protocol.as_str());
+        if let Some(user) = session.user.as_deref() {
+            push_unique(&mut entry.users, user);
+        }
+        match session.action.as_str() {
+            "logon" | "reconnect" => entry.session_count += 1,
+            "failed" => entry.failed_count += 1,
+            _ => {}
+        }
+        entry.last_seen = session.timestamp.clone();
+    }
+
+    let mut sources: Vec<RemoteSourceSummary> = sources.into_values().collect();
+    sources.sort_by(|left, right| {
+        (right.session_count + right.failed_count)
+            .cmp(&(left.session_count + left.failed_count))
+            .then_with(|| right.last_seen.cmp(&left.last_seen))
+    });
+
+    let mut warnings = Vec::new();
+    if sessions.is_empty() {
+        warnings.push(
+            "No remote session events found in this range. On Windows, include the TerminalServices-LocalSessionManager/Operational channel in the ingest profile."
+                .to_string(),
+        );
+    }
+
+    RemoteAccessReport {
+        events: sessions,
+        sources,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        os: &str,
+        log_name: &str,
+        provider: &str,
+        event_id: Option<u32>,
+        message: &str,
+    ) -> NormalizedEvent {
+        NormalizedEvent {
+            id: format!("evt-{provider}-{}", event_id.unwrap_or_default()),
+            timestamp: "2026-03-01T10:00:00Z".to_string(),
+            os: os.to_string(),
+            log_name: log_name.to_string(),
+            category: "security".to_string(),
+            provider: provider.to_string(),
+            event_id,
+            severity: "information".to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn parses_rdp_and_security_sessions() {
+        let logon = event(
+            "windows",
+            "Microsoft-Windows-TerminalServices-LocalSessionManager/Operational",
+            "Microsoft-Windows-TerminalServices-LocalSessionManager",
+            Some(21),
+            "Remote Desktop Services: Session logon succeeded:\r\n\r\nUser: CORP\\alice\r\nSession ID: 2\r\nSource Network Address: 10.0.0.5",
+        );
+        let parsed = parse_remote_session(&logon).unwrap();
+        assert_eq!(parsed.action, "logon");
+        assert_eq!(parsed.user.as_deref(), Some("CORP\\alice"));
+        assert_eq!(parsed.session_id.as_deref(), Some("2"));
+        assert_eq!(parsed.source_address.as_deref(), Some("10.0.0.5"));
+
+        let reconnect = event(
+            "windows",
+            "Security",
+            "Microsoft-Windows-Security-Auditing",
+            Some(4778),
+            "Data: AccountName=bob, AccountDomain=CORP, LogonID=0x1, SessionName=RDP-Tcp#3, ClientName=LAPTOP, ClientAddress=10.0.0.9",
+        );
+        let parsed = parse_remote_session(&reconnect).unwrap();
+        assert_eq!(parsed.action, "reconnect");
+        assert_eq!(parsed.user.as_deref(), Some("CORP\\bob"));
+        assert_eq!(parsed.source_address.as_deref(), Some("10.0.0.9"));
+    }
+
+    #[test]
+    fn summarizes_ssh_and_screen_sharing_sources() {
+        let events = vec![
+            event("linux", "journald", "sshd", None, "Accepted publickey for alice from 192.168.1.20 port 51234 ssh2"),
+            event("linux", "journald", "sshd", None, "Failed password for invalid user admin from 192.168.1.20 port 51240 ssh2"),
+            event(
+                "macos",
+                "unified",
+                "screensharingd",
+                None,
+                "Authentication: SUCCEEDED :: User Name: carol :: Viewer Address: 192.168.1.30 :: Type: DH",
+            ),
+        ];
+        let report = analyze_remote_access(&events);
+        assert_eq!(report.events.len(), 3);
+        let ssh_source = report
+            .sources
+            .iter()
+            .find(|source| source.address == "192.168.1.20")
+            .unwrap();
+        assert_eq!(ssh_source.session_count, 1);
+        assert_eq!(ssh_source.failed_count, 1);
+        assert_eq!(
+            ssh_source.users,
+            vec!["alice".to_string(), "admin".to_string()]
+        );
+        assert!(report
+            .sources
+            .iter()
+            .any(|source| source.address == "192.168.1.30"
+                && source.protocols == vec!["screen-sharing".to_string()]));
+    }
+}
@@ -1,4 +1,6 @@
+use crate::settings::RemoteConnectionProfile;
 use serde::{Deserialize, Serialize};
+use std::process::Command;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,3 +16,105 @@ pub struct RemoteConnectionTestResult {
     pub provider_resolved_name: Option<String>,
     pub provider_last_resolved_at: Option<String>,
 }
+
+const SSH_CONNECT_TIMEOUT_SECONDS: u32 = 10;
+
+/// Splits `host:port` into the host and port, leaving bare and IPv6 hosts untouched.
+fn split_ssh_port(host: &str) -> (&str, Option<&str>) {
+    match host.rsplit_once(':') {
+        Some((name, port))
+            if !name.contains(':')
+                && !port.is_empty()
+                && port.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            (name, Some(port))
+        }
+        _ => (host, None),
+    }
+}
+
+/// Arguments for running `remote_command` on a profile's host over OpenSSH. Sessions never
+/// prompt, so auth is a key file (`key`) or whatever the running ssh-agent offers (`agent`).
+/// Unknown host keys are remembered on first contact and changed keys are refused.
+pub fn ssh_args(profile: &RemoteConnectionProfile, remote_command: &str) -> Vec<String> {
+    let mut args = vec![
+        "-o".to_string(),
+        "BatchMode=yes".to_string(),
+        "-o".to_string(),
+        "StrictHostKeyChecking=accept-new".to_string(),
+        "-o".to_string(),
+        format!("ConnectTimeout={SSH_CONNECT_TIMEOUT_SECONDS}"),
+        "-o".to_string(),
+        "ServerAliveInterval=15".to_string(),
+    ];
+    let key_path = profile
+        .ssh_key_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty());
+    if let (false, Some(key_path)) = (profile.auth_type.eq_ignore_ascii_case("agent"), key_path) {
+        args.extend([
+            "-i".to_string(),
+            key_path.to_string(),
+            "-o".to_string(),
+            "IdentitiesOnly=yes".to_string(),
+        ]);
+    }
+    let (host, port) = split_ssh_port(profile.host.trim());
+    if let Some(port) = port {
+        args.extend(["-p".to_string(), port.to_string()]);
+    }
+    args.push(if profile.username.trim().is_empty() {
+        host.to_string()
+    } else {
+        format!("{}@{host}", profile.username.trim())
+    });
+    args.push(remote_command.to_string());
+    args
+}
+
+pub fn ssh_command(profile: &RemoteConnectionProfile, remote_command: &str) -> Command {
+    let mut command = Command::new("ssh");
+    command.args(ssh_args(profile, remote_command));
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_ssh_arguments_for_key_and_agent_auth() {
+        let mut profile = RemoteConnectionProfile {
+            id: "srv".to_string(),
+            name: "Server".to_string(),
+            host: "srv.lan:2222".to_string(),
+            os: "linux".to_string(),
+            protocol: "ssh".to_string(),
+            username: "ops".to_string(),
+            ssh_key_path: Some(" ~/.ssh/id_ed25519 ".to_string()),
+            auth_type: "key".to_string(),
+            provider_device_id: None,
+            provider_last_resolved_name: None,
+            provider_last_resolved_at: None,
+            secret_configured: false,
+        };
+        let args = ssh_args(&profile, "journalctl -o json");
+        assert!(args.contains(&"StrictHostKeyChecking=accept-new".to_string()));
+        assert!(args
+            .windows(2)
+            .any(|pair| pair == ["-i", "~/.ssh/id_ed25519"]));
+        assert!(args.windows(2).any(|pair| pair == ["-p", "2222"]));
+        assert_eq!(
+            args[args.len() - 2..],
+            ["ops@srv.lan", "journalctl -o json"]
+        );
+
+        profile.auth_type = "agent".to_string();
+        profile.host = "fe80::1".to_string();
+        let args = ssh_args(&profile, "true");
+        assert!(!args.contains(&"-i".to_string()));
+        assert!(!args.contains(&"-p".to_string()));
+        assert_eq!(args[args.len() - 2], "ops@fe80::1");
+    }
+}
@@ -5,7 +5,7 @@ use chrono::{DateTime, Utc};
 use reqwest::blocking::Client;
 use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
 use serde_json::Value;
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
@@ -53,7 +53,7 @@ fn test_macos_ssh_connection(profile: &RemoteConnectionProfile) -> RemoteConnect
             protocol: "ssh".to_string(),
             host: profile.host.clone(),
             status: "collection unsupported".to_string(),
-            message: "Remote macOS SSH password authentication is not implemented. Use SSH key-based auth or a managed-provider path instead.".to_string(),
+            message: "Remote macOS SSH password authentication is not implemented. Use SSH key or agent auth, or a managed-provider path instead.".to_string(),
             warnings: Vec::new(),
             collection_mode: "unsupported".to_string(),
             provider_device_id: None,
@@ -62,30 +62,10 @@ fn test_macos_ssh_connection(profile: &RemoteConnectionProfile) -> RemoteConnect
         };
     }
 
-    let mut ssh_args = vec![
-        "-o".to_string(),
-        "BatchMode=yes".to_string(),
-        "-o".to_string(),
-        "StrictHostKeyChecking=no".to_string(),
-        "-o".to_string(),
-        "ConnectTimeout=10".to_string(),
-    ];
-    if let Some(key_path) = &profile.ssh_key_path {
-        if !key_path.trim().is_empty() {
-            ssh_args.push("-i".to_string());
-            ssh_args.push(key_path.trim().to_string());
-        }
-    }
-    let user_host = if profile.username.trim().is_empty() {
-        profile.host.trim().to_string()
-    } else {
-        format!("{}@{}", profile.username.trim(), profile.host.trim())
-    };
-    ssh_args.push(user_host);
-    ssh_args.push("log show --last 1m --style ndjson >/dev/null".to_string());
-
-    let output = Command::new("ssh")
-        .args(&ssh_args)
+    let output = crate::remote_common::ssh_command(
+        profile,
+        "log show --last 1m --style ndjson >/dev/null",
+    )
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
         .output();
@@ -0,0 +1,199 @@
+//! Repeated-message collapsing: events from one host with the same provider, event ID and
+//! message, each logged within a gap of the one before, folded into one row with a count and the
+//! first and last time seen. Applied at query time, and before a sync is stored when the ingest
+//! profile asks for it.
+
+use crate::db::{self, RepeatedEvent};
+use crate::logs::NormalizedEvent;
+use crate::nl_query::EventQueryFilter;
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Repeats further apart than this start a new run.
+pub const DEFAULT_GAP_SECONDS: i64 = 300;
+const MAX_GAP_SECONDS: i64 = 86_400;
+pub const DEFAULT_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepeatedEventsReport {
+    /// Most recently seen first.
+    pub runs: Vec<RepeatedEvent>,
+    /// Events the runs stand for, repeats included.
+    pub total_events: u64,
+    pub total_runs: usize,
+    /// Runs standing for more than one event.
+    pub collapsed_runs: usize,
+}
+
+type RunKey = (String, String, Option<u32>, String);
+
+fn run_key(event: &NormalizedEvent) -> RunKey {
+    (
+        event.source_host.clone(),
+        event.provider.to_ascii_lowercase(),
+        event.event_id,
+        event.message.trim().to_string(),
+    )
+}
+
+/// Folds runs that arrive in time order. Each key has at most one open run: a repeat within the
+/// gap extends it, a later one closes it and opens the next.
+struct RunFolder {
+    gap: Duration,
+    open: HashMap<RunKey, (RepeatedEvent, DateTime<Utc>)>,
+    closed: Vec<RepeatedEvent>,
+}
+
+impl RunFolder {
+    fn new(gap_seconds: i64) -> Self {
+        Self {
+            gap: Duration::seconds(gap_seconds.clamp(0, MAX_GAP_SECONDS)),
+            open: HashMap::new(),
+            closed: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, run: RepeatedEvent) {
+        // Without a usable time there is nothing to measure the gap against.
+        let Some(first) = parse_timestamp(run.first_timestamp.as_str()) else {
+            self.closed.push(run);
+            return;
+        };
+        let last = parse_timestamp(run.last_timestamp.as_str())
+            .unwrap_or(first)
+            .max(first);
+        let key = run_key(&run.event);
+        match self.open.get_mut(&key) {
+            Some((open, open_last)) if first - *open_last <= self.gap => {
+                open.repeat_count = open.repeat_count.saturating_add(run.repeat_count);
+                if last > *open_last {
+                    *open_last = last;
+                    open.last_timestamp = run.last_timestamp;
+                }
+            }
+            _ => {
+                if let Some((previous, _)) = self.open.insert(key, (run, last)) {
+                    self.closed.push(previous);
+                }
+            }
+        }
+    }
+
+    /// Every run, oldest first.
+    fn finish(mut self) -> Vec<RepeatedEvent> {
+        self.closed
+            .extend(self.open.into_values().map(|(run, _)| run));
+        self.closed
+            .sort_by_key(|run| parse_timestamp(run.first_timestamp.as_str()));
+        self.closed
+    }
+}
+
+fn single(event: NormalizedEvent) -> RepeatedEvent {
+    RepeatedEvent {
+        repeat_count: 1,
+        first_timestamp: event.timestamp.clone(),
+        last_timestamp: event.timestamp.clone(),
+        event,
+    }
+}
+
+/// Collapses a collected batch before it is stored. The first event of each run is kept.
+pub fn collapse_events(events: &[NormalizedEvent], gap_seconds: i64) -> Vec<RepeatedEvent> {
+    let mut ordered = events.to_vec();
+    ordered.sort_by_key(|event| parse_timestamp(event.timestamp.as_str()));
+    let mut folder = RunFolder::new(gap_seconds);
+    for event in ordered {
+        folder.push(single(event));
+    }
+    folder.finish()
+}
+
+/// Collapses the stored events matching `filter`, adding to runs already collapsed at ingest.
+pub fn repeated_events(
+    filter: &EventQueryFilter,
+    host: Option<&str>,
+    gap_seconds: i64,
+    limit: usize,
+) -> Result<RepeatedEventsReport, String> {
+    let mut folder = RunFolder::new(gap_seconds);
+    db::for_each_event_run(filter, host, |run| {
+        folder.push(run);
+        Ok(())
+    })?;
+    let mut runs = folder.finish();
+    let total_events = runs.iter().map(|run| u64::from(run.repeat_count)).sum();
+    let total_runs = runs.len();
+    let collapsed_runs = runs.iter().filter(|run| run.repeat_count > 1).count();
+    runs.sort_by_key(|run| std::cmp::Reverse(parse_timestamp(run.last_timestamp.as_str())));
+    runs.truncate(limit);
+    Ok(RepeatedEventsReport {
+        runs,
+        total_events,
+        total_runs,
+        collapsed_runs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn event(timestamp: &str, provider: &str, message: &str) -> NormalizedEvent {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Linux,
+            "journal",
+            "system",
+            provider,
+            None,
+            "error",
+            message,
+            "web01",
+        );
+        event.timestamp = timestamp.to_string();
+        event
+    }
+
+    #[test]
+    fn folds_interleaved_repeats_and_splits_on_gaps() {
+        let events = vec![
+            event("2026-03-01T10:00:00Z", "myapp", "connection refused"),
+            event("2026-03-01T10:00:01Z", "sshd", "session opened"),
+            event("2026-03-01T10:00:02Z", "myapp", "connection refused"),
+            event("2026-03-01T10:03:00Z", "MyApp", "connection refused"),
+            event("2026-03-01T11:00:00Z", "myapp", "connection refused"),
+        ];
+        let runs = collapse_events(events.as_slice(), DEFAULT_GAP_SECONDS);
+
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0].event.provider, "myapp");
+        assert_eq!(runs[0].repeat_count, 3);
+        assert_eq!(runs[0].first_timestamp, "2026-03-01T10:00:00Z");
+        assert_eq!(runs[0].last_timestamp, "2026-03-01T10:03:00Z");
+        assert_eq!(runs[1].event.provider, "sshd");
+        assert_eq!(runs[2].repeat_count, 1);
+        assert_eq!(runs[2].first_timestamp, "2026-03-01T11:00:00Z");
+    }
+
+    #[test]
+    fn adds_counts_of_runs_collapsed_at_ingest() {
+        let mut folder = RunFolder::new(DEFAULT_GAP_SECONDS);
+        folder.push(RepeatedEvent {
+            repeat_count: 500,
+            first_timestamp: "2026-03-01T10:00:00Z".to_string(),
+            last_timestamp: "2026-03-01T10:10:00Z".to_string(),
+            event: event("2026-03-01T10:00:00Z", "myapp", "retrying"),
+        });
+        folder.push(single(event("2026-03-01T10:12:00Z", "myapp", "retrying")));
+        folder.push(single(event("not a time", "myapp", "retrying")));
+        let runs = folder.finish();
+
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].repeat_count, 501);
+        assert_eq!(runs[1].last_timestamp, "2026-03-01T10:12:00Z");
+    }
+}
@@ -0,0 +1,263 @@
+use crate::alerts;
+use crate::db::save_local_events;
+use crate::diagnostics;
+use crate::event_export::parse_event_export;
+use crate::logs::NormalizedEvent;
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+pub const REPLAY_EVENTS_EVENT: &str = "hla://replay-events";
+/// Replayed events are stored under their own host so they never mix with collected data.
+pub const REPLAY_SOURCE_HOST: &str = "replay";
+pub const BUNDLED_FIXTURE_NAME: &str = "bundled:disk-failure";
+
+const BUNDLED_FIXTURE: &str = include_str!("../fixtures/replay-sample.ndjson");
+const TICK: Duration = Duration::from_millis(250);
+const MAX_SPEED: f64 = 3600.0;
+const DEFAULT_SPEED: f64 = 60.0;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayStatus {
+    pub running: bool,
+    pub fixture: Option<String>,
+    pub speed: f64,
+    pub total: usize,
+    pub emitted: usize,
+    pub simulated_start: Option<String>,
+    pub simulated_now: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayBatch {
+    pub events: Vec<NormalizedEvent>,
+    pub simulated_now: String,
+    pub emitted: usize,
+    pub total: usize,
+}
+
+/// A fixture laid out on a simulated clock: every event keeps its offset from the first one,
+/// rebased onto `start_at` and compressed by `speed`.
+#[derive(Debug, Clone)]
+pub struct ReplayPlan {
+    start_at: DateTime<Utc>,
+    speed: f64,
+    events: Vec<(ChronoDuration, NormalizedEvent)>,
+}
+
+impl ReplayPlan {
+    pub fn new(
+        events: Vec<NormalizedEvent>,
+        speed: f64,
+        start_at: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        let mut timed = events
+            .into_iter()
+            .filter_map(|event| parse_timestamp(event.timestamp.as_str()).map(|at| (at, event)))
+            .collect::<Vec<_>>();
+        timed.sort_by(|left, right| left.0.cmp(&right.0));
+        let Some(first) = timed.first().map(|(at, _)| *at) else {
+            return Err("Fixture has no events with a readable timestamp.".to_string());
+        };
+        Ok(Self {
+            start_at,
+            speed: speed.clamp(1.0, MAX_SPEED),
+            events: timed
+                .into_iter()
+                .map(|(at, event)| (at - first, event))
+                .collect(),
+        })
+    }
+
+    pub fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn simulated_at(&self, real_elapsed: Duration) -> DateTime<Utc> {
+        let simulated_ms = (real_elapsed.as_millis() as f64 * self.speed) as i64;
+        self.start_at + ChronoDuration::milliseconds(simulated_ms)
+    }
+
+    /// Index one past the last event due after `real_elapsed` of wall-clock time.
+    pub fn due_until(&self, real_elapsed: Duration) -> usize {
+        let simulated = self.simulated_at(real_elapsed) - self.start_at;
+        self.events
+            .partition_point(|(offset, _)| *offset <= simulated)
+    }
+
+    /// The event at `index` as it appears on the simulated clock.
+    pub fn rebased(&self, index: usize) -> NormalizedEvent {
+        let (offset, event) = &self.events[index];
+        let mut event = event.clone();
+        event.timestamp = (self.start_at + *offset).to_rfc3339();
+        event.source_host = REPLAY_SOURCE_HOST.to_string();
+        event.imported = false;
+        event.assign_stable_id();
+        event
+    }
+}
+
+struct ActiveReplay {
+    cancel: Arc<AtomicBool>,
+}
+
+static ACTIVE_REPLAY: Mutex<Option<ActiveReplay>> = Mutex::new(None);
+static REPLAY_STATUS: Mutex<Option<ReplayStatus>> = Mutex::new(None);
+
+fn set_status(update: impl FnOnce(&mut ReplayStatus)) {
+    if let Ok(mut guard) = REPLAY_STATUS.lock() {
+        update(guard.get_or_insert_with(ReplayStatus::default));
+    }
+}
+
+pub fn replay_status() -> ReplayStatus {
+    REPLAY_STATUS
+        .lock()
+        .ok()
+        .and_then(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
+/// Loads a user-provided JSON/NDJSON export, or the bundled fixture when `path` is `None`.
+pub fn load_fixture(path: Option<&str>) -> Result<(String, Vec<NormalizedEvent>), String> {
+    match path {
+        None => Ok((
+            BUNDLED_FIXTURE_NAME.to_string(),
+            parse_event_export(BUNDLED_FIXTURE)?,
+        )),
+        Some(path) => {
+            let raw = std::fs::read_to_string(path)
+                .map_err(|error| format!("Failed to read fixture {path}: {error}"))?;
+            Ok((path.to_string(), parse_event_export(raw.as_str())?))
+        }
+    }
+}
+
+/// Starts replaying a fixture in the background, replacing any replay already running. Due
+/// events are saved under the `replay` host and emitted as `hla://replay-events` batches.
+pub fn start(
+    app: AppHandle,
+    path: Option<&str>,
+    speed: Option<f64>,
+) -> Result<ReplayStatus, String> {
+    let (fixture, events) = load_fixture(path)?;
+    let plan = ReplayPlan::new(events, speed.unwrap_or(DEFAULT_SPEED), Utc::now())?;
+    stop();
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    if let Ok(mut active) = ACTIVE_REPLAY.lock() {
+        *active = Some(ActiveReplay {
+            cancel: cancel.clone(),
+        });
+    }
+    let status = ReplayStatus {
+        running: true,
+        fixture: Some(fixture),
+        speed: plan.speed,
+        total: plan.event_count(),
+        emitted: 0,
+        simulated_start: Some(plan.start_at.to_rfc3339()),
+        simulated_now: Some(plan.start_at.to_rfc3339()),
+        error: None,
+    };
+    set_status(|current| *current = status.clone());
+
+    std::thread::Builder::new()
+        .name("event-replay".to_string())
+        .spawn(move || run(app, plan, cancel))
+        .map_err(|error| format!("Failed to start replay thread: {error}"))?;
+    Ok(status)
+}
+
+pub fn stop() -> ReplayStatus {
+    if let Ok(mut active) = ACTIVE_REPLAY.lock() {
+        if let Some(replay) = active.take() {
+            replay.cancel.store(true, Ordering::SeqCst);
+        }
+    }
+    set_status(|status| status.running = false);
+    replay_status()
+}
+
+fn run(app: AppHandle, plan: ReplayPlan, cancel: Arc<AtomicBool>) {
+    let started = Instant::now();
+    let mut emitted = 0usize;
+    while emitted < plan.event_count() && !cancel.load(Ordering::SeqCst) {
+        std::thread::sleep(TICK);
+        let elapsed = started.elapsed();
+        let due = plan.due_until(elapsed);
+        if due == emitted {
+            continue;
+        }
+
+        if cancel.load(Ordering::SeqCst) {
+            return;
+        }
+        let events = (emitted..due)
+            .map(|index| plan.rebased(index))
+            .collect::<Vec<_>>();
+        if let Err(error) = save_local_events(events.as_slice()) {
+            diagnostics::warn("replay", format!("Failed to save replayed events: {error}"));
+            set_status(|status| {
+                status.running = false;
+                status.error = Some(error);
+            });
+            return;
+        }
+        alerts::evaluate_events(events.as_slice());
+        emitted = due;
+
+        let batch = ReplayBatch {
+            events,
+            simulated_now: plan.simulated_at(elapsed).to_rfc3339(),
+            emitted,
+            total: plan.event_count(),
+        };
+        set_status(|status| {
+            status.emitted = batch.emitted;
+            status.simulated_now = Some(batch.simulated_now.clone());
+        });
+        if let Err(error) = app.emit(REPLAY_EVENTS_EVENT, &batch) {
+            diagnostics::warn("replay", format!("Failed to emit replay batch: {error}"));
+        }
+    }
+    if !cancel.load(Ordering::SeqCst) {
+        set_status(|status| status.running = false);
+        diagnostics::info(
+            "replay",
+            format!("Replay finished after {emitted} event(s)"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_bundled_fixture_on_accelerated_clock() {
+        let (_, events) = load_fixture(None).unwrap();
+        let start_at = DateTime::parse_from_rfc3339("2026-03-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let plan = ReplayPlan::new(events, 60.0, start_at).unwrap();
+
+        assert_eq!(plan.due_until(Duration::ZERO), 1);
+        // Sixty times faster: the fixture's first six simulated minutes pass in six seconds.
+        let due = plan.due_until(Duration::from_secs(6));
+        assert!(due > 1 && due < plan.event_count());
+        assert_eq!(plan.due_until(Duration::from_secs(30 * 60)), plan.event_count());
+
+        let first = plan.rebased(0);
+        assert_eq!(first.timestamp, "2026-03-01T10:00:00+00:00");
+        assert_eq!(first.source_host, REPLAY_SOURCE_HOST);
+        assert_eq!(first.id, plan.rebased(0).id);
+    }
+}
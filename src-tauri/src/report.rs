@@ -0,0 +1,374 @@
+use crate::correlation::ScoredEvent;
+use crate::crash::CrashRecord;
+use crate::forwarding::severity_rank;
+use crate::logs::NormalizedEvent;
+use crate::timeline::Timeline;
+use crate::timestamps::DisplayTimezone;
+use serde::{Deserialize, Serialize};
+
+const SEVERITIES: [(&str, &str); 4] = [
+    ("critical", "#991b1b"),
+    ("error", "#dc2626"),
+    ("warning", "#d97706"),
+    ("information", "#2563eb"),
+];
+const MAX_TABLE_EVENTS: usize = 50;
+const MAX_TIMELINE_ENTRIES: usize = 200;
+const MAX_MESSAGE_CHARS: usize = 300;
+
+/// LLM output the caller already has on screen. Reports never call a model themselves.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportAnalysis {
+    pub profile_name: String,
+    pub model: String,
+    pub summary: String,
+    #[serde(default)]
+    pub sections: Vec<ReportAnalysisSection>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportAnalysisSection {
+    pub title: String,
+    pub items: Vec<String>,
+}
+
+pub struct IncidentReport<'a> {
+    pub title: String,
+    pub source_host: &'a str,
+    pub start: &'a str,
+    pub end: &'a str,
+    pub generated_at: String,
+    /// Zone every time in the report is shown in.
+    pub timezone: DisplayTimezone,
+    pub crash: Option<&'a CrashRecord>,
+    /// Every event in the range, used for the severity breakdown.
+    pub events: &'a [NormalizedEvent],
+    pub timeline: &'a Timeline,
+    /// Events ranked against the crash. Empty for range reports, which list the most severe
+    /// events instead.
+    pub correlated: &'a [ScoredEvent],
+    pub analysis: Option<&'a ReportAnalysis>,
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn truncate(value: &str) -> String {
+    if value.chars().count() <= MAX_MESSAGE_CHARS {
+        value.to_string()
+    } else {
+        let truncated: String = value.chars().take(MAX_MESSAGE_CHARS).collect();
+        format!("{truncated}...")
+    }
+}
+
+fn card(heading: &str, body: String) -> String {
+    format!(
+        "<div class=\"card\"><h2>{}</h2>{body}</div>",
+        escape_html(heading)
+    )
+}
+
+fn summary_card(report: &IncidentReport<'_>) -> String {
+    let mut rows = vec![
+        ("Host", report.source_host.to_string()),
+        (
+            "Range",
+            format!(
+                "{} to {}",
+                report.timezone.format(report.start),
+                report.timezone.format(report.end)
+            ),
+        ),
+        ("Events", report.events.len().to_string()),
+        (
+            "Timeline entries",
+            report.timeline.entries.len().to_string(),
+        ),
+    ];
+    if let Some(crash) = report.crash {
+        rows.push(("Crash", format!("{}: {}", crash.crash_type, crash.summary)));
+        rows.push((
+            "Crashed at",
+            report.timezone.format(crash.timestamp.as_str()),
+        ));
+        if let Some(code) = crash.code.as_deref() {
+            rows.push(("Code", code.to_string()));
+        }
+        if let Some(component) = crash.suspected_component.as_deref() {
+            rows.push(("Suspected component", component.to_string()));
+        }
+    }
+    let body = rows
+        .into_iter()
+        .map(|(label, value)| format!("<tr><th>{label}</th><td>{}</td></tr>", escape_html(&value)))
+        .collect::<String>();
+    card("1) Summary", format!("<table class=\"kv\">{body}</table>"))
+}
+
+/// Horizontal bar chart drawn as inline SVG so the file renders without scripts or network.
+fn severity_chart(events: &[NormalizedEvent]) -> String {
+    let counts = SEVERITIES
+        .iter()
+        .map(|(severity, color)| {
+            let count = events
+                .iter()
+                .filter(|event| event.severity.eq_ignore_ascii_case(severity))
+                .count();
+            (*severity, *color, count)
+        })
+        .collect::<Vec<_>>();
+    let max = counts
+        .iter()
+        .map(|(_, _, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"520\" height=\"{}\" role=\"img\" aria-label=\"Severity breakdown\">",
+        counts.len() * 28
+    );
+    for (index, (severity, color, count)) in counts.iter().enumerate() {
+        let y = index * 28;
+        let width = (*count as f64 / max as f64 * 320.0).round() as usize;
+        svg.push_str(
+            format!(
+                "<text x=\"0\" y=\"{}\" font-size=\"12\">{severity}</text><rect x=\"100\" y=\"{}\" width=\"{width}\" height=\"18\" fill=\"{color}\"/><text x=\"{}\" y=\"{}\" font-size=\"12\">{count}</text>",
+                y + 14,
+                y + 2,
+                106 + width,
+                y + 14
+            )
+            .as_str(),
+        );
+    }
+    svg.push_str("</svg>");
+    card("2) Severity Breakdown", svg)
+}
+
+fn timeline_card(timeline: &Timeline, timezone: DisplayTimezone) -> String {
+    if timeline.entries.is_empty() {
+        return card(
+            "3) Timeline",
+            "<div>No crashes, findings or changes in this range.</div>".to_string(),
+        );
+    }
+    let rows = timeline
+        .entries
+        .iter()
+        .take(MAX_TIMELINE_ENTRIES)
+        .map(|entry| {
+            format!(
+                "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&entry.severity),
+                escape_html(&timezone.format(entry.timestamp.as_str())),
+                escape_html(&entry.kind),
+                escape_html(&entry.title),
+                escape_html(&truncate(&entry.detail))
+            )
+        })
+        .collect::<String>();
+    let mut body = format!(
+        "<table><tr><th>Time</th><th>Kind</th><th>Title</th><th>Detail</th></tr>{rows}</table>"
+    );
+    if timeline.entries.len() > MAX_TIMELINE_ENTRIES {
+        body.push_str(
+            format!(
+                "<div class=\"meta\">{} more entries omitted.</div>",
+                timeline.entries.len() - MAX_TIMELINE_ENTRIES
+            )
+            .as_str(),
+        );
+    }
+    card("3) Timeline", body)
+}
+
+fn event_row(event: &NormalizedEvent, score: Option<f64>, timezone: DisplayTimezone) -> String {
+    let score = score
+        .map(|value| format!("<td>{value:.0}</td>"))
+        .unwrap_or_default();
+    format!(
+        "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>{score}</tr>",
+        escape_html(&event.severity),
+        escape_html(&timezone.format(event.timestamp.as_str())),
+        escape_html(&event.severity),
+        escape_html(&event.provider),
+        event.event_id.map(|id| id.to_string()).unwrap_or_default(),
+        escape_html(&truncate(&event.message))
+    )
+}
+
+fn events_card(report: &IncidentReport<'_>) -> String {
+    let header = "<th>Time</th><th>Severity</th><th>Provider</th><th>Event ID</th><th>Message</th>";
+    let (heading, rows) = if report.crash.is_some() {
+        let rows = report
+            .correlated
+            .iter()
+            .take(MAX_TABLE_EVENTS)
+            .map(|scored| event_row(&scored.event, Some(scored.relevance_score), report.timezone))
+            .collect::<String>();
+        (
+            "4) Correlated Events",
+            format!("<tr>{header}<th>Score</th></tr>{rows}"),
+        )
+    } else {
+        let mut notable = report
+            .events
+            .iter()
+            .filter(|event| severity_rank(event.severity.as_str()) >= severity_rank("warning"))
+            .collect::<Vec<_>>();
+        notable.sort_by(|left, right| {
+            severity_rank(right.severity.as_str())
+                .cmp(&severity_rank(left.severity.as_str()))
+                .then_with(|| left.timestamp.cmp(&right.timestamp))
+        });
+        let rows = notable
+            .into_iter()
+            .take(MAX_TABLE_EVENTS)
+            .map(|event| event_row(event, None, report.timezone))
+            .collect::<String>();
+        ("4) Notable Events", format!("<tr>{header}</tr>{rows}"))
+    };
+    card(heading, format!("<table>{rows}</table>"))
+}
+
+fn analysis_card(analysis: &ReportAnalysis) -> String {
+    let mut body = format!(
+        "<div class=\"meta\">Provider: {}<br/>Model: {}</div><div>{}</div>",
+        escape_html(&analysis.profile_name),
+        escape_html(&analysis.model),
+        escape_html(&analysis.summary)
+    );
+    for section in &analysis.sections {
+        let items = if section.items.is_empty() {
+            "<li>Not provided</li>".to_string()
+        } else {
+            section
+                .items
+                .iter()
+                .map(|item| format!("<li>{}</li>", escape_html(item)))
+                .collect()
+        };
+        body.push_str(format!("<h3>{}</h3><ul>{items}</ul>", escape_html(&section.title)).as_str());
+    }
+    card("5) Analysis", body)
+}
+
+/// Renders a self-contained HTML incident report with inline styles and no external assets.
+pub fn render_incident_report(report: &IncidentReport<'_>) -> String {
+    let mut sections = vec![
+        summary_card(report),
+        severity_chart(report.events),
+        timeline_card(report.timeline, report.timezone),
+        events_card(report),
+    ];
+    if let Some(analysis) = report.analysis {
+        sections.push(analysis_card(analysis));
+    }
+
+    [
+        "<!doctype html>".to_string(),
+        "<html><head><meta charset=\"utf-8\"/>".to_string(),
+        format!("<title>{}</title>", escape_html(&report.title)),
+        "<style>".to_string(),
+        "body{font-family:Segoe UI,Arial,sans-serif;line-height:1.45;color:#111827;margin:32px;}".to_string(),
+        "h1{margin:0 0 6px 0;font-size:24px;} h2{margin:18px 0 6px 0;font-size:16px;} h3{margin:12px 0 4px 0;font-size:14px;}".to_string(),
+        ".meta{color:#4b5563;font-size:12px;margin-bottom:14px;} ul{margin:6px 0 0 20px;}".to_string(),
+        ".card{border:1px solid #d1d5db;padding:12px 14px;border-radius:8px;margin-bottom:10px;}".to_string(),
+        "table{border-collapse:collapse;width:100%;font-size:12px;} th,td{border-bottom:1px solid #e5e7eb;padding:4px 6px;text-align:left;vertical-align:top;}".to_string(),
+        "table.kv{width:auto;} tr.critical td{background:#fee2e2;} tr.error td{background:#fef2f2;} tr.warning td{background:#fffbeb;}".to_string(),
+        "</style></head><body>".to_string(),
+        format!("<h1>{}</h1>", escape_html(&report.title)),
+        format!(
+            "<div class=\"meta\">Generated: {}<br/>Times shown in: {}<br/>Host: {}</div>",
+            escape_html(&report.generated_at),
+            escape_html(&report.timezone.name()),
+            escape_html(report.source_host)
+        ),
+        sections.join(""),
+        "</body></html>".to_string(),
+    ]
+    .join("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn renders_escaped_sections_and_optional_analysis() {
+        let events = vec![
+            NormalizedEvent::new(
+                SupportedOs::Windows,
+                "System",
+                "system",
+                "disk",
+                Some(7),
+                "error",
+                "<script>alert(1)</script>",
+                "host-a",
+            ),
+            NormalizedEvent::new(
+                SupportedOs::Windows,
+                "System",
+                "system",
+                "kernel",
+                None,
+                "information",
+                "boot",
+                "host-a",
+            ),
+        ];
+        let timeline = Timeline {
+            start: "2024-01-01T00:00:00Z".to_string(),
+            end: "2024-01-02T00:00:00Z".to_string(),
+            source_host: "host-a".to_string(),
+            counts: BTreeMap::new(),
+            entries: Vec::new(),
+        };
+        let mut report = IncidentReport {
+            title: "Incident".to_string(),
+            source_host: "host-a",
+            start: timeline.start.as_str(),
+            end: timeline.end.as_str(),
+            generated_at: "now".to_string(),
+            timezone: DisplayTimezone::Utc,
+            crash: None,
+            events: events.as_slice(),
+            timeline: &timeline,
+            correlated: &[],
+            analysis: None,
+        };
+
+        let html = render_incident_report(&report);
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("4) Notable Events"));
+        assert!(html.contains("<svg"));
+        assert!(!html.contains("5) Analysis"));
+
+        let analysis = ReportAnalysis {
+            profile_name: "local".to_string(),
+            model: "m".to_string(),
+            summary: "Disk failing".to_string(),
+            sections: vec![ReportAnalysisSection {
+                title: "Next steps".to_string(),
+                items: vec!["Replace disk".to_string()],
+            }],
+        };
+        report.analysis = Some(&analysis);
+        let html = render_incident_report(&report);
+        assert!(html.contains("5) Analysis"));
+        assert!(html.contains("<li>Replace disk</li>"));
+    }
+}
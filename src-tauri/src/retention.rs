@@ -0,0 +1,157 @@
+//! Retention: deletes events and crashes past the configured age, event count and database
+//! size, then returns the freed pages to the file system. Events are first copied to the
+//! archive when one is set. Runs hourly in the background and on demand from Settings,
+//! and is the only place stored events are aged out. Events rehydrated from the archive are
+//! held for `REHYDRATED_HOLD_DAYS` and then dropped without being archived again.
+
+use crate::archive::archive_oldest_events;
+use crate::archive_store::ArchiveStore;
+use crate::db::{
+    count_events, database_file_bytes, database_used_bytes, prune_crashes_before,
+    prune_events_before, prune_oldest_events, reclaim_free_pages, release_rehydrated_events,
+};
+use crate::diagnostics;
+use crate::scheduler;
+use crate::settings::{load_ingest_profile, IngestProfile};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const FIRST_RUN_DELAY: Duration = Duration::from_secs(5 * 60);
+const RUN_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Size pruning aims to bring the database back under this share of the limit.
+const SIZE_TARGET: f64 = 0.9;
+const REHYDRATED_HOLD_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionReport {
+    pub ran_at: String,
+    /// Events copied to the archive before they were deleted.
+    pub archived_events: usize,
+    pub deleted_events: usize,
+    pub deleted_crashes: usize,
+    pub file_bytes_before: u64,
+    pub file_bytes_after: u64,
+    pub reclaimed_bytes: u64,
+}
+
+/// Oldest events to delete so the database drops to `SIZE_TARGET` of `max_bytes`, assuming
+/// every stored event takes about the same space.
+fn events_over_size(total_events: u64, used_bytes: u64, max_bytes: u64) -> u64 {
+    if max_bytes == 0 || used_bytes <= max_bytes || total_events == 0 {
+        return 0;
+    }
+    let per_event = used_bytes as f64 / total_events as f64;
+    let excess = (used_bytes as f64 - max_bytes as f64 * SIZE_TARGET) / per_event;
+    (excess.ceil() as u64).min(total_events)
+}
+
+/// Archives the `count` oldest events ahead of `prune_oldest_events`.
+fn archive_excess(
+    archive: Option<&ArchiveStore>,
+    count: u64,
+    now: DateTime<Utc>,
+) -> Result<usize, String> {
+    match archive {
+        Some(store) if count > 0 => archive_oldest_events(store, None, Some(count), now),
+        _ => Ok(0),
+    }
+}
+
+pub fn apply_retention(
+    profile: &IngestProfile,
+    now: DateTime<Utc>,
+) -> Result<RetentionReport, String> {
+    let policy = &profile.retention;
+    let mut report = RetentionReport {
+        ran_at: now.to_rfc3339(),
+        file_bytes_before: database_file_bytes()?,
+        ..RetentionReport::default()
+    };
+
+    // An archive that cannot be reached stops retention, rather than losing events.
+    let archive = ArchiveStore::from_policy(policy)?;
+
+    let hold_cutoff = (now - chrono::Duration::days(REHYDRATED_HOLD_DAYS)).to_rfc3339();
+    report.deleted_events += release_rehydrated_events(hold_cutoff.as_str())?;
+
+    if policy.max_age_days > 0 {
+        let cutoff = (now - chrono::Duration::days(i64::from(policy.max_age_days))).to_rfc3339();
+        if let Some(store) = &archive {
+            report.archived_events +=
+                archive_oldest_events(store, Some(cutoff.as_str()), None, now)?;
+        }
+        report.deleted_events += prune_events_before(cutoff.as_str())?;
+        report.deleted_crashes += prune_crashes_before(cutoff.as_str())?;
+    }
+    if policy.max_events > 0 {
+        let excess = count_events()?.saturating_sub(u64::from(policy.max_events));
+        report.archived_events += archive_excess(archive.as_ref(), excess, now)?;
+        report.deleted_events += prune_oldest_events(excess)?;
+    }
+    if profile.max_database_mb > 0 {
+        let max_bytes = u64::from(profile.max_database_mb) * 1024 * 1024;
+        let excess = events_over_size(count_events()?, database_used_bytes()?, max_bytes);
+        report.archived_events += archive_excess(archive.as_ref(), excess, now)?;
+        report.deleted_events += prune_oldest_events(excess)?;
+    }
+
+    reclaim_free_pages()?;
+    report.file_bytes_after = database_file_bytes()?;
+    report.reclaimed_bytes = report
+        .file_bytes_before
+        .saturating_sub(report.file_bytes_after);
+    Ok(report)
+}
+
+/// Starts the hourly retention task.
+pub fn start() {
+    let spawned = std::thread::Builder::new()
+        .name("retention".to_string())
+        .spawn(run);
+    if let Err(error) = spawned {
+        diagnostics::warn(
+            "storage",
+            format!("Failed to start retention thread: {error}"),
+        );
+    }
+}
+
+fn run() {
+    std::thread::sleep(FIRST_RUN_DELAY);
+    loop {
+        // Vacuuming holds the write lock, so it waits for a quiet moment.
+        if !scheduler::sync_running() {
+            match apply_retention(&load_ingest_profile(), Utc::now()) {
+                Ok(report) if report.deleted_events + report.deleted_crashes > 0 => {
+                    diagnostics::info(
+                        "storage",
+                        format!(
+                            "Retention removed {} events and {} crashes, reclaiming {} bytes",
+                            report.deleted_events, report.deleted_crashes, report.reclaimed_bytes
+                        ),
+                    );
+                }
+                Ok(_) => {}
+                Err(error) => diagnostics::warn("storage", format!("Retention failed: {error}")),
+            }
+        }
+        std::thread::sleep(RUN_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_pruning_targets_ninety_percent_of_limit() {
+        let mb = 1024 * 1024;
+        assert_eq!(events_over_size(1000, 90 * mb, 100 * mb), 0);
+        assert_eq!(events_over_size(1000, 120 * mb, 0), 0);
+        // 1000 events in 120 MB is ~123 KB each; reaching 90 MB means dropping 250 of them.
+        assert_eq!(events_over_size(1000, 120 * mb, 100 * mb), 250);
+        assert_eq!(events_over_size(10, 1000 * mb, mb), 10);
+    }
+}
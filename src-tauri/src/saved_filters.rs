@@ -0,0 +1,193 @@
+//! Saved filters: named event filters kept in the `savedFilters` settings section. The event view
+//! applies them directly, exports turn them into an `EventQueryFilter`, and alert rules match
+//! them in memory.
+
+use crate::logs::NormalizedEvent;
+use crate::nl_query::EventQueryFilter;
+use crate::settings::{load_alert_settings, load_saved_filters, save_saved_filters, SavedFilter};
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+pub fn list_saved_filters() -> Vec<SavedFilter> {
+    load_saved_filters().filters
+}
+
+pub fn find_saved_filter(filter_id: &str) -> Result<SavedFilter, String> {
+    list_saved_filters()
+        .into_iter()
+        .find(|filter| filter.id == filter_id)
+        .ok_or_else(|| format!("Saved filter {filter_id} was not found."))
+}
+
+/// Adds `filter`, or replaces the saved filter with the same id, and returns it as stored.
+pub fn save_saved_filter(mut filter: SavedFilter) -> Result<SavedFilter, String> {
+    if filter.name.trim().is_empty() {
+        return Err("Saved filter name is required.".to_string());
+    }
+    let now = Utc::now().to_rfc3339();
+    let mut settings = load_saved_filters();
+    match settings
+        .filters
+        .iter_mut()
+        .find(|existing| !filter.id.is_empty() && existing.id == filter.id)
+    {
+        Some(existing) => {
+            filter.created_at = existing.created_at.clone();
+            filter.updated_at = now;
+            *existing = filter.clone();
+        }
+        None => {
+            filter.id = format!("filter-{}", Uuid::new_v4());
+            filter.created_at = now.clone();
+            filter.updated_at = now;
+            settings.filters.push(filter.clone());
+        }
+    }
+    save_saved_filters(settings)?
+        .filters
+        .into_iter()
+        .find(|existing| existing.id == filter.id)
+        .ok_or_else(|| "Saved filter was rejected.".to_string())
+}
+
+/// Deletes a saved filter unless an alert rule still depends on it.
+pub fn delete_saved_filter(filter_id: &str) -> Result<(), String> {
+    if let Some(rule) = load_alert_settings()
+        .rules
+        .into_iter()
+        .find(|rule| rule.saved_filter_id.as_deref() == Some(filter_id))
+    {
+        return Err(format!(
+            "Alert rule \"{}\" uses this filter; change the rule first.",
+            rule.name
+        ));
+    }
+    let mut settings = load_saved_filters();
+    settings.filters.retain(|filter| filter.id != filter_id);
+    save_saved_filters(settings).map(|_| ())
+}
+
+/// Time bounds of `filter` as of `now`; a rolling window wins over fixed bounds.
+fn time_bounds(
+    filter: &SavedFilter,
+    now: DateTime<Utc>,
+) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    match filter.relative_minutes {
+        Some(minutes) => (Some(now - Duration::minutes(i64::from(minutes))), None),
+        None => (
+            filter.start.as_deref().and_then(parse_timestamp),
+            filter.end.as_deref().and_then(parse_timestamp),
+        ),
+    }
+}
+
+/// The stored-event query equivalent of `filter` at `now`, for exports and pushes.
+pub fn to_query_filter(filter: &SavedFilter, now: DateTime<Utc>) -> EventQueryFilter {
+    let (start, end) = time_bounds(filter, now);
+    EventQueryFilter {
+        text: Some(filter.text.clone()).filter(|text| !text.is_empty()),
+        severities: filter.severities.clone(),
+        log_name: filter.log_name.clone(),
+        category: filter.category.clone(),
+        event_id: filter.event_id,
+        providers: filter.providers.clone(),
+        start: start.map(|value| value.to_rfc3339()),
+        end: end.map(|value| value.to_rfc3339()),
+        ..EventQueryFilter::default()
+    }
+}
+
+pub fn matches(filter: &SavedFilter, event: &NormalizedEvent, now: DateTime<Utc>) -> bool {
+    if !filter.severities.is_empty()
+        && !filter
+            .severities
+            .iter()
+            .any(|severity| severity.eq_ignore_ascii_case(event.severity.as_str()))
+    {
+        return false;
+    }
+    if !filter.providers.is_empty()
+        && !filter
+            .providers
+            .iter()
+            .any(|provider| provider.eq_ignore_ascii_case(event.provider.as_str()))
+    {
+        return false;
+    }
+    if filter
+        .log_name
+        .as_deref()
+        .is_some_and(|log_name| !log_name.eq_ignore_ascii_case(event.log_name.as_str()))
+    {
+        return false;
+    }
+    if filter
+        .category
+        .as_deref()
+        .is_some_and(|category| category != event.category.as_str())
+    {
+        return false;
+    }
+    if filter.event_id.is_some() && filter.event_id != event.event_id {
+        return false;
+    }
+    if !filter.text.is_empty() {
+        let text = filter.text.to_lowercase();
+        if !event.message.to_lowercase().contains(text.as_str())
+            && !event.provider.to_lowercase().contains(text.as_str())
+        {
+            return false;
+        }
+    }
+    let (start, end) = time_bounds(filter, now);
+    if start.is_none() && end.is_none() {
+        return true;
+    }
+    let Some(timestamp) = parse_timestamp(event.timestamp.as_str()) else {
+        return false;
+    };
+    start.is_none_or(|start| timestamp >= start) && end.is_none_or(|end| timestamp <= end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    #[test]
+    fn matches_severity_provider_text_and_rolling_window() {
+        let now = Utc::now();
+        let filter = SavedFilter {
+            name: "Kernel errors last 24h".to_string(),
+            text: "oops".to_string(),
+            severities: vec!["error".to_string(), "critical".to_string()],
+            providers: vec!["kernel".to_string()],
+            relative_minutes: Some(24 * 60),
+            ..SavedFilter::default()
+        };
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Linux,
+            "journal",
+            "system",
+            "Kernel",
+            None,
+            "error",
+            "BUG: kernel Oops at 0x0",
+            "localhost",
+        );
+        event.timestamp = (now - Duration::hours(2)).to_rfc3339();
+        assert!(matches(&filter, &event, now));
+
+        event.timestamp = (now - Duration::hours(30)).to_rfc3339();
+        assert!(!matches(&filter, &event, now));
+
+        event.timestamp = now.to_rfc3339();
+        event.severity = "warning".to_string();
+        assert!(!matches(&filter, &event, now));
+
+        let query = to_query_filter(&filter, now);
+        assert_eq!(query.providers, vec!["kernel".to_string()]);
+        assert!(query.start.is_some() && query.end.is_none());
+    }
+}
@@ -0,0 +1,139 @@
+//! Background sync scheduler: refreshes the local host every `sync_interval_minutes` from the
+//! ingest profile, with a little jitter, and skips a run while another sync is still going.
+
+use crate::diagnostics;
+use crate::settings::load_ingest_profile;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+pub const SYNC_COMPLETED_EVENT: &str = "hla://sync-completed";
+
+/// How often a disabled scheduler looks at the profile again.
+const IDLE_POLL: Duration = Duration::from_secs(60);
+const MAX_JITTER: Duration = Duration::from_secs(60);
+
+static ACTIVE_SYNCS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncCompletedPayload {
+    pub started_at: String,
+    pub finished_at: String,
+    pub collected: usize,
+    pub warnings: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Marks a sync as running until dropped, so scheduled runs do not overlap manual ones.
+pub struct SyncActivity;
+
+impl Drop for SyncActivity {
+    fn drop(&mut self) {
+        ACTIVE_SYNCS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn begin_sync() -> SyncActivity {
+    ACTIVE_SYNCS.fetch_add(1, Ordering::SeqCst);
+    SyncActivity
+}
+
+pub fn sync_running() -> bool {
+    ACTIVE_SYNCS.load(Ordering::SeqCst) > 0
+}
+
+/// Up to a tenth of the interval (at most a minute), so scheduled syncs drift instead of landing
+/// on the same tick as other periodic work.
+fn jitter(interval: Duration, seed: u64) -> Duration {
+    let range = (interval / 10).min(MAX_JITTER).as_millis() as u64;
+    if range == 0 {
+        return Duration::ZERO;
+    }
+    let mixed = seed.wrapping_mul(6364136223846793005).rotate_left(17);
+    Duration::from_millis(mixed % range)
+}
+
+fn next_delay(interval_minutes: u32) -> Option<Duration> {
+    if interval_minutes == 0 {
+        return None;
+    }
+    let interval = Duration::from_secs(u64::from(interval_minutes) * 60);
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.subsec_nanos() as u64)
+        .unwrap_or_default();
+    Some(interval + jitter(interval, seed))
+}
+
+/// Starts the scheduler thread. `sync` runs one refresh of the local host and reports how it went;
+/// each run is announced to the frontend as `hla://sync-completed`.
+pub fn start(app: AppHandle, sync: fn() -> SyncCompletedPayload) {
+    let spawned = std::thread::Builder::new()
+        .name("sync-scheduler".to_string())
+        .spawn(move || run(app, sync));
+    if let Err(error) = spawned {
+        diagnostics::warn(
+            "sync",
+            format!("Failed to start sync scheduler thread: {error}"),
+        );
+    }
+}
+
+fn run(app: AppHandle, sync: fn() -> SyncCompletedPayload) {
+    loop {
+        // The profile is re-read every cycle so interval changes apply without a restart.
+        let Some(delay) = next_delay(load_ingest_profile().sync_interval_minutes) else {
+            std::thread::sleep(IDLE_POLL);
+            continue;
+        };
+        std::thread::sleep(delay);
+        if load_ingest_profile().sync_interval_minutes == 0 {
+            continue;
+        }
+        if sync_running() {
+            diagnostics::info(
+                "sync",
+                "Skipped scheduled sync; another sync is still running",
+            );
+            continue;
+        }
+
+        let payload = sync();
+        if let Some(error) = payload.error.as_deref() {
+            diagnostics::warn("sync", format!("Scheduled sync failed: {error}"));
+        }
+        if let Err(error) = app.emit(SYNC_COMPLETED_EVENT, &payload) {
+            diagnostics::warn("sync", format!("Failed to emit sync completion: {error}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let interval = Duration::from_secs(5 * 60);
+        for seed in [0, 1, 42, u64::MAX] {
+            assert!(jitter(interval, seed) < Duration::from_secs(30));
+        }
+        let hourly = Duration::from_secs(60 * 60);
+        assert!(jitter(hourly, 7) < MAX_JITTER);
+        assert_eq!(next_delay(0), None);
+        assert!(next_delay(15).unwrap() >= Duration::from_secs(15 * 60));
+    }
+
+    #[test]
+    fn activity_guard_tracks_running_syncs() {
+        assert!(!sync_running());
+        let first = begin_sync();
+        let second = begin_sync();
+        drop(first);
+        assert!(sync_running());
+        drop(second);
+        assert!(!sync_running());
+    }
+}
@@ -1,21 +1,94 @@
+use crate::timestamps::DisplayTimezone;
 use dirs::data_local_dir;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use uuid::Uuid;
 
-const THEME_FILE: &str = "theme.txt";
-const EXPORT_DIR_FILE: &str = "export_dir.txt";
-const INGEST_DAYS_FILE: &str = "ingest_window_days.txt";
-const INGEST_PROFILE_FILE: &str = "ingest_profile.json";
-const LLM_SETTINGS_FILE: &str = "llm_settings.json";
-const REMOTE_SETTINGS_FILE: &str = "remote_settings.json";
+/// Every setting the app keeps lives in this one document; secrets stay in the OS keychain.
+const SETTINGS_FILE: &str = "settings.json";
+/// Written into `settings.json`; bump it and upgrade older documents in `parse_settings_file`
+/// when a section changes shape or another file is folded in.
+const SETTINGS_VERSION: u32 = 2;
+/// Emitted with the changed section's name after a save, so every window can reload it.
+pub const SETTINGS_CHANGED_EVENT: &str = "hla://settings-changed";
+pub const THEME_SECTION: &str = "theme";
+pub const EXPORT_DIR_SECTION: &str = "exportDirectory";
+pub const DISPLAY_TIMEZONE_SECTION: &str = "displayTimezone";
+pub const INGEST_DAYS_SECTION: &str = "ingestWindowDays";
+pub const INGEST_PROFILE_SECTION: &str = "ingestProfile";
+pub const LLM_SETTINGS_SECTION: &str = "llm";
+const REMOTE_SETTINGS_SECTION: &str = "remote";
+const INTEGRATION_SETTINGS_SECTION: &str = "integrations";
+const ALERT_SETTINGS_SECTION: &str = "alertRules";
+const PARSER_PROFILES_SECTION: &str = "parserProfiles";
+const SYSLOG_LISTENER_SECTION: &str = "syslogListener";
+const WORKSPACES_SECTION: &str = "workspaces";
+const SAVED_FILTERS_SECTION: &str = "savedFilters";
+/// Records which plaintext API key migration the `llm` section has been through; it is only
+/// scanned for keys while this is older than `LLM_KEY_MIGRATION_VERSION`.
+const LLM_KEY_MIGRATION_SECTION: &str = "llmKeyMigration";
+const LLM_KEY_MIGRATION_VERSION: u64 = 1;
+/// Files the `settings.json` sections replaced: (file, section, holds JSON, settings version
+/// that folded it in). Each is read once, by the first load of a document older than that
+/// version, then removed.
+const LEGACY_SETTINGS_FILES: [(&str, &str, bool, u32); 13] = [
+    ("theme.txt", THEME_SECTION, false, 1),
+    ("export_dir.txt", EXPORT_DIR_SECTION, false, 1),
+    ("display_timezone.txt", DISPLAY_TIMEZONE_SECTION, false, 1),
+    ("ingest_window_days.txt", INGEST_DAYS_SECTION, false, 1),
+    ("ingest_profile.json", INGEST_PROFILE_SECTION, true, 1),
+    ("llm_settings.json", LLM_SETTINGS_SECTION, true, 1),
+    ("remote_settings.json", REMOTE_SETTINGS_SECTION, true, 2),
+    ("integrations.json", INTEGRATION_SETTINGS_SECTION, true, 2),
+    ("alert_rules.json", ALERT_SETTINGS_SECTION, true, 2),
+    ("parser_profiles.json", PARSER_PROFILES_SECTION, true, 2),
+    ("syslog_listener.json", SYSLOG_LISTENER_SECTION, true, 2),
+    ("workspaces.json", WORKSPACES_SECTION, true, 2),
+    ("saved_filters.json", SAVED_FILTERS_SECTION, true, 2),
+];
+pub const DEFAULT_WORKSPACE_ID: &str = "default";
 const DEFAULT_INGEST_DAYS: u32 = 7;
+const DEFAULT_SPOOL_MAX_EVENTS: u32 = 50000;
+const MIN_SPOOL_MAX_EVENTS: u32 = 1000;
+pub const DEFAULT_ELASTICSEARCH_INDEX: &str = "hermes-events";
+pub const LOKI_LABEL_FIELDS: [&str; 6] = [
+    "os",
+    "category",
+    "provider",
+    "severity",
+    "logName",
+    "sourceHost",
+];
+const DEFAULT_LOKI_LABELS: [&str; 4] = ["os", "category", "provider", "severity"];
+const MAX_SPOOL_MAX_EVENTS: u32 = 1_000_000;
+const DEFAULT_ALERT_COOLDOWN_SECONDS: u32 = 300;
+/// A year, the longest rolling window a saved filter keeps.
+const MAX_SAVED_FILTER_MINUTES: u32 = 365 * 24 * 60;
+/// Unprivileged default so the listener works without elevation; 514 needs root or admin.
+const DEFAULT_SYSLOG_LISTENER_PORT: u16 = 5514;
+const MAX_ALERT_COOLDOWN_SECONDS: u32 = 86_400;
 const DEFAULT_MAX_EVENTS_PER_SYNC: u32 = 2000;
 const MIN_MAX_EVENTS_PER_SYNC: u32 = 100;
 const MAX_MAX_EVENTS_PER_SYNC: u32 = 20000;
+const MIN_MAX_DATABASE_MB: u32 = 64;
+const MAX_MAX_DATABASE_MB: u32 = 1_048_576;
+const MIN_SYNC_INTERVAL_MINUTES: u32 = 5;
+const MAX_SYNC_INTERVAL_MINUTES: u32 = 24 * 60;
+const MAX_RETENTION_DAYS: u32 = 3650;
+const MIN_RETENTION_EVENTS: u32 = 1000;
+const MIN_CONTEXT_WINDOW_TOKENS: u32 = 1024;
+const MAX_CONTEXT_WINDOW_TOKENS: u32 = 2_000_000;
 const DEFAULT_WINDOWS_CHANNELS: [&str; 3] = ["Application", "System", "Security"];
+/// Windows event messages are formatted in English by default so keyword detectors and the
+/// support team see the same text on localized installs.
+const DEFAULT_MESSAGE_LOCALE: &str = "en-US";
+/// `message_locale` value that formats messages in the OS display language.
+pub const SYSTEM_MESSAGE_LOCALE: &str = "system";
 const DEFAULT_LLM_PROFILE_PROVIDER: &str = "ollama";
 const DEFAULT_LLM_PROFILE_SCOPE: &str = "local";
 
@@ -30,6 +103,9 @@ pub struct LlmConnectionProfile {
     pub model: String,
     pub enabled: bool,
     pub api_key_configured: bool,
+    /// Model context window in tokens used to budget prompts; 0 uses the provider default.
+    #[serde(default)]
+    pub context_window_tokens: u32,
 }
 
 impl LlmConnectionProfile {
@@ -43,6 +119,7 @@ impl LlmConnectionProfile {
             model: String::new(),
             enabled: true,
             api_key_configured: false,
+            context_window_tokens: 0,
         }
     }
 }
@@ -82,6 +159,92 @@ pub struct IngestProfile {
     pub windows_channels: Vec<String>,
     #[serde(default)]
     pub request_elevation: bool,
+    #[serde(default)]
+    pub collect_smart: bool,
+    /// Size limit for the event database in MiB; 0 leaves it unlimited.
+    #[serde(default)]
+    pub max_database_mb: u32,
+    #[serde(default)]
+    pub journal_filter: JournalFilter,
+    #[serde(default)]
+    pub unified_log_filter: UnifiedLogFilter,
+    /// Minutes between background syncs of the local host; 0 turns the scheduler off.
+    #[serde(default)]
+    pub sync_interval_minutes: u32,
+    #[serde(default)]
+    pub retention: RetentionPolicy,
+    /// Locale name (`en-US`, `de-DE`) Windows event messages are formatted in, or `system` for
+    /// the display language. Publishers without resources for it fall back to the display
+    /// language.
+    #[serde(default = "default_message_locale")]
+    pub message_locale: String,
+    /// Stores runs of identical messages from a sync as one event with a repeat count.
+    #[serde(default)]
+    pub collapse_repeats: bool,
+}
+
+fn default_message_locale() -> String {
+    DEFAULT_MESSAGE_LOCALE.to_string()
+}
+
+/// Limits the background retention task enforces on stored data, together with
+/// `max_database_mb`. 0 turns a limit off.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionPolicy {
+    /// Events and crashes older than this many days are deleted.
+    pub max_age_days: u32,
+    /// The oldest events beyond this count are deleted.
+    pub max_events: u32,
+    /// Folder pruned events are archived to as day partitions before they are deleted. Empty
+    /// turns folder archiving off.
+    pub archive_directory: String,
+    /// Bucket or container partitions go to instead of the folder when its kind is set.
+    pub object_store: ObjectStoreSettings,
+}
+
+/// An S3 bucket (or S3-compatible service) or Azure Blob container for the archive. The S3
+/// secret access key or the Blob SAS token is kept in the OS keychain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ObjectStoreSettings {
+    /// `s3` or `azure`; empty archives to the folder.
+    pub kind: String,
+    pub bucket: String,
+    /// S3 region; empty means `us-east-1`.
+    pub region: String,
+    /// S3-compatible endpoint such as a MinIO URL, addressed path-style. Empty uses AWS.
+    pub endpoint: String,
+    pub access_key_id: String,
+    /// `https://<account>.blob.core.windows.net/<container>`.
+    pub container_url: String,
+    /// Key prefix the `events/` partitions are stored under.
+    pub prefix: String,
+}
+
+/// journalctl match options for Linux collection. Empty lists and an empty priority pull the
+/// whole journal.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct JournalFilter {
+    /// systemd units passed as `-u`, e.g. `sshd.service`.
+    pub units: Vec<String>,
+    /// Syslog identifiers passed as `-t`, e.g. `kernel`.
+    pub identifiers: Vec<String>,
+    /// Priority passed as `-p`: a level such as `err` or `3`, or a range such as `0..4`.
+    pub priority: String,
+}
+
+/// `log show` options for macOS collection. An empty predicate pulls every entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UnifiedLogFilter {
+    /// Passed as `--predicate`, e.g. `subsystem == "com.apple.TimeMachine" OR messageType == error`.
+    pub predicate: String,
+    /// Adds `--info` so info-level entries are included.
+    pub include_info: bool,
+    /// Adds `--debug` so debug-level entries are included.
+    pub include_debug: bool,
 }
 
 impl Default for IngestProfile {
@@ -94,6 +257,14 @@ impl Default for IngestProfile {
                 .map(|value| value.to_string())
                 .collect(),
             request_elevation: false,
+            collect_smart: false,
+            max_database_mb: 0,
+            journal_filter: JournalFilter::default(),
+            unified_log_filter: UnifiedLogFilter::default(),
+            sync_interval_minutes: 0,
+            retention: RetentionPolicy::default(),
+            message_locale: default_message_locale(),
+            collapse_repeats: false,
         }
     }
 }
@@ -110,7 +281,7 @@ pub struct RemoteConnectionProfile {
     #[serde(alias = "ssh_key_path")]
     pub ssh_key_path: Option<String>,
     #[serde(alias = "auth_type")]
-    pub auth_type: String, // "key_only" or "password"
+    pub auth_type: String, // "key", "agent" or "password"
     #[serde(default)]
     pub provider_device_id: Option<String>,
     #[serde(default)]
@@ -171,9 +342,218 @@ impl Default for RemoteSettings {
     }
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardingFilter {
+    /// Lowest severity that is forwarded; empty forwards everything.
+    #[serde(default)]
+    pub min_severity: String,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub providers: Vec<String>,
+    #[serde(default)]
+    pub event_ids: Vec<u32>,
+    /// Case-insensitive substrings; events whose message contains one are never forwarded.
+    #[serde(default)]
+    pub exclude_patterns: Vec<String>,
+    #[serde(default)]
+    pub include_imported: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationConfig {
+    pub id: String,
+    pub name: String,
+    pub kind: String, // "webhook", "syslog", "elasticsearch" or "loki"
+    pub endpoint: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub filter: ForwardingFilter,
+    /// Events kept on disk while the destination is unreachable; the oldest are dropped beyond this.
+    #[serde(default)]
+    pub spool_max_events: u32,
+    /// Target index for Elasticsearch/OpenSearch integrations.
+    #[serde(default)]
+    pub index: String,
+    /// "none", "basic" or "apiKey". The password or key itself lives in the OS keychain.
+    #[serde(default)]
+    pub auth_type: String,
+    #[serde(default)]
+    pub username: String,
+    /// Event fields sent as Loki stream labels; the rest stay in the log line.
+    #[serde(default)]
+    pub loki_labels: Vec<String>,
+    /// Message body for syslog integrations: "rfc5424", "cef" or "leef".
+    #[serde(default)]
+    pub syslog_format: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationSettings {
+    #[serde(default)]
+    pub integrations: Vec<IntegrationConfig>,
+}
+
+/// A condition that posts to a webhook when matching events arrive during sync or replay.
+/// Empty criteria match everything, so a rule usually sets at least a minimum severity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertRule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub min_severity: String,
+    #[serde(default)]
+    pub providers: Vec<String>,
+    #[serde(default)]
+    pub event_ids: Vec<u32>,
+    /// Regular expression matched against the event message; empty matches any message.
+    #[serde(default)]
+    pub message_pattern: String,
+    pub webhook_url: String,
+    /// "generic", "slack" or "teams".
+    #[serde(default)]
+    pub webhook_format: String,
+    /// Minimum gap between two posts for this rule; matches in between are counted, not sent.
+    #[serde(default)]
+    pub cooldown_seconds: u32,
+    /// Saved filter an event must also match for the rule to fire.
+    #[serde(default)]
+    pub saved_filter_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertSettings {
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,
+}
+
+/// A named event filter ("Kernel errors last 24h") that the event view, exports and alert rules
+/// can apply by id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SavedFilter {
+    pub id: String,
+    pub name: String,
+    /// Matched against message and provider, case-insensitively.
+    pub text: String,
+    /// Severities to include; empty means all.
+    pub severities: Vec<String>,
+    /// Exact provider names, case-insensitive; empty means all.
+    pub providers: Vec<String>,
+    pub log_name: Option<String>,
+    pub category: Option<String>,
+    pub event_id: Option<u32>,
+    /// Window ending when the filter is applied, in minutes; replaces `start` and `end` when set.
+    pub relative_minutes: Option<u32>,
+    /// RFC3339 bounds of a fixed time range.
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub sort_column: Option<String>,
+    /// `asc` or `desc`.
+    pub sort_direction: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedFilterSettings {
+    #[serde(default)]
+    pub filters: Vec<SavedFilter>,
+}
+
+/// A named investigation with its own database file. The default workspace keeps `events.db`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub last_opened_at: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceSettings {
+    pub active_id: String,
+    #[serde(default)]
+    pub workspaces: Vec<Workspace>,
+}
+
+impl Default for WorkspaceSettings {
+    fn default() -> Self {
+        sanitize_workspace_settings(WorkspaceSettings {
+            active_id: DEFAULT_WORKSPACE_ID.to_string(),
+            workspaces: Vec::new(),
+        })
+    }
+}
+
+/// A user-defined line format for file import. Named groups in `pattern` (`timestamp`,
+/// `severity`, `provider`, `event_id` and `message`) become event fields; a pattern without a
+/// `message` group keeps the whole line as the message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParserProfile {
+    pub id: String,
+    pub name: String,
+    pub pattern: String,
+    /// A timestamp hint name such as `rfc3339` or `epoch_ms`, or a strftime pattern.
+    #[serde(default)]
+    pub timestamp_format: String,
+    /// Raw `severity` group values (matched case-insensitively) mapped to event severities.
+    /// Unmapped values fall back to the usual level names.
+    #[serde(default)]
+    pub severity_map: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParserProfileSettings {
+    #[serde(default)]
+    pub profiles: Vec<ParserProfile>,
+}
+
+/// The opt-in syslog receiver for routers, NAS boxes and other LAN appliances.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyslogListenerSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address to listen on; `0.0.0.0` accepts from the whole LAN.
+    #[serde(default)]
+    pub bind_address: String,
+    #[serde(default)]
+    pub port: u16,
+    /// "udp", "tcp" or "both".
+    #[serde(default)]
+    pub protocol: String,
+}
+
+impl Default for SyslogListenerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "0.0.0.0".to_string(),
+            port: DEFAULT_SYSLOG_LISTENER_PORT,
+            protocol: "udp".to_string(),
+        }
+    }
+}
+
 fn sanitize_remote_auth_type(value: &str) -> String {
     match value.trim().to_ascii_lowercase().as_str() {
         "password" => "password".to_string(),
+        "agent" | "ssh_agent" => "agent".to_string(),
         "key_only" | "key" | "" => "key".to_string(),
         _ => "key".to_string(),
     }
@@ -296,6 +676,370 @@ fn sanitize_remote_settings(settings: RemoteSettings) -> RemoteSettings {
     }
 }
 
+fn sanitize_integration_kind(value: &str) -> Option<&'static str> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "webhook" => Some("webhook"),
+        "syslog" => Some("syslog"),
+        "elasticsearch" => Some("elasticsearch"),
+        "loki" => Some("loki"),
+        _ => None,
+    }
+}
+
+/// Keeps known label fields in their canonical spelling. An empty selection falls back to the
+/// defaults because Loki rejects streams without labels.
+fn sanitize_loki_labels(values: Vec<String>) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for value in values {
+        let Some(field) = LOKI_LABEL_FIELDS
+            .iter()
+            .find(|field| field.eq_ignore_ascii_case(value.trim()))
+        else {
+            continue;
+        };
+        if !labels.iter().any(|label| label == field) {
+            labels.push(field.to_string());
+        }
+    }
+    if labels.is_empty() {
+        DEFAULT_LOKI_LABELS
+            .iter()
+            .map(|field| field.to_string())
+            .collect()
+    } else {
+        labels
+    }
+}
+
+fn sanitize_integration_auth_type(value: &str) -> &'static str {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "basic" => "basic",
+        "apikey" | "api_key" => "apiKey",
+        _ => "none",
+    }
+}
+
+/// Elasticsearch index names must be lowercase and cannot start with `_`, `-` or `+`.
+fn sanitize_index_name(value: &str) -> String {
+    let index: String = value
+        .trim()
+        .to_ascii_lowercase()
+        .chars()
+        .filter(|ch| {
+            !matches!(
+                ch,
+                '\\' | '/' | '*' | '?' | '"' | '<' | '>' | '|' | ' ' | ',' | '#'
+            )
+        })
+        .collect();
+    let index = index.trim_start_matches(['_', '-', '+']);
+    if index.is_empty() {
+        DEFAULT_ELASTICSEARCH_INDEX.to_string()
+    } else {
+        index.to_string()
+    }
+}
+
+fn sanitize_string_list(values: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    values
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty() && seen.insert(value.to_ascii_lowercase()))
+        .collect()
+}
+
+fn sanitize_forwarding_filter(filter: ForwardingFilter) -> ForwardingFilter {
+    let min_severity = sanitize_severity(filter.min_severity.as_str());
+    let mut event_ids = filter.event_ids;
+    event_ids.sort_unstable();
+    event_ids.dedup();
+    ForwardingFilter {
+        min_severity,
+        categories: sanitize_string_list(filter.categories)
+            .into_iter()
+            .map(|value| value.to_ascii_lowercase())
+            .collect(),
+        providers: sanitize_string_list(filter.providers),
+        event_ids,
+        exclude_patterns: sanitize_string_list(filter.exclude_patterns),
+        include_imported: filter.include_imported,
+    }
+}
+
+fn sanitize_severity(value: &str) -> String {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "critical" => "critical",
+        "error" => "error",
+        "warning" => "warning",
+        "information" => "information",
+        _ => "",
+    }
+    .to_string()
+}
+
+fn sanitize_alert_settings(settings: AlertSettings) -> AlertSettings {
+    let mut seen_ids = HashSet::new();
+    let rules = settings
+        .rules
+        .into_iter()
+        .filter_map(|mut rule| {
+            let id = if rule.id.trim().is_empty() {
+                format!("alert-{}", Uuid::new_v4())
+            } else {
+                rule.id.trim().to_string()
+            };
+            if !seen_ids.insert(id.to_ascii_lowercase()) {
+                return None;
+            }
+            rule.id = id;
+            rule.name = if rule.name.trim().is_empty() {
+                "Alert rule".to_string()
+            } else {
+                rule.name.trim().to_string()
+            };
+            rule.min_severity = sanitize_severity(rule.min_severity.as_str());
+            rule.providers = sanitize_string_list(rule.providers);
+            rule.event_ids.sort_unstable();
+            rule.event_ids.dedup();
+            rule.message_pattern = rule.message_pattern.trim().to_string();
+            rule.webhook_url = rule.webhook_url.trim().to_string();
+            rule.webhook_format = match rule.webhook_format.trim().to_ascii_lowercase().as_str() {
+                "slack" => "slack",
+                "teams" => "teams",
+                _ => "generic",
+            }
+            .to_string();
+            rule.cooldown_seconds = if rule.cooldown_seconds == 0 {
+                DEFAULT_ALERT_COOLDOWN_SECONDS
+            } else {
+                rule.cooldown_seconds.min(MAX_ALERT_COOLDOWN_SECONDS)
+            };
+            Some(rule)
+        })
+        .collect();
+    AlertSettings { rules }
+}
+
+fn sanitize_parser_profiles(settings: ParserProfileSettings) -> ParserProfileSettings {
+    let mut seen_ids = HashSet::new();
+    let profiles = settings
+        .profiles
+        .into_iter()
+        .filter_map(|mut profile| {
+            let id = if profile.id.trim().is_empty() {
+                format!("parser-{}", Uuid::new_v4())
+            } else {
+                profile.id.trim().to_string()
+            };
+            if !seen_ids.insert(id.to_ascii_lowercase()) || profile.pattern.trim().is_empty() {
+                return None;
+            }
+            profile.id = id;
+            profile.name = if profile.name.trim().is_empty() {
+                "Parser profile".to_string()
+            } else {
+                profile.name.trim().to_string()
+            };
+            profile.timestamp_format = profile.timestamp_format.trim().to_string();
+            profile.severity_map = profile
+                .severity_map
+                .into_iter()
+                .filter_map(|(raw, severity)| {
+                    let raw = raw.trim().to_ascii_lowercase();
+                    let severity = sanitize_severity(severity.as_str());
+                    (!raw.is_empty() && !severity.is_empty()).then_some((raw, severity))
+                })
+                .collect();
+            Some(profile)
+        })
+        .collect();
+    ParserProfileSettings { profiles }
+}
+
+fn sanitize_syslog_listener_settings(settings: SyslogListenerSettings) -> SyslogListenerSettings {
+    let bind_address = settings.bind_address.trim();
+    SyslogListenerSettings {
+        enabled: settings.enabled,
+        bind_address: if bind_address.parse::<std::net::IpAddr>().is_ok() {
+            bind_address.to_string()
+        } else {
+            "0.0.0.0".to_string()
+        },
+        port: if settings.port == 0 {
+            DEFAULT_SYSLOG_LISTENER_PORT
+        } else {
+            settings.port
+        },
+        protocol: match settings.protocol.trim().to_ascii_lowercase().as_str() {
+            "tcp" => "tcp",
+            "both" => "both",
+            _ => "udp",
+        }
+        .to_string(),
+    }
+}
+
+fn sanitize_integration_settings(settings: IntegrationSettings) -> IntegrationSettings {
+    let mut seen_ids = HashSet::new();
+    let integrations = settings
+        .integrations
+        .into_iter()
+        .filter_map(|mut integration| {
+            let kind = sanitize_integration_kind(integration.kind.as_str())?;
+            let id = if integration.id.trim().is_empty() {
+                format!("integration-{}", Uuid::new_v4())
+            } else {
+                integration.id.trim().to_string()
+            };
+            if !seen_ids.insert(id.to_ascii_lowercase()) {
+                return None;
+            }
+
+            integration.id = id;
+            integration.kind = kind.to_string();
+            integration.name = if integration.name.trim().is_empty() {
+                kind.to_string()
+            } else {
+                integration.name.trim().to_string()
+            };
+            integration.endpoint = integration.endpoint.trim().to_string();
+            integration.filter = sanitize_forwarding_filter(integration.filter);
+            integration.spool_max_events = if integration.spool_max_events == 0 {
+                DEFAULT_SPOOL_MAX_EVENTS
+            } else {
+                integration
+                    .spool_max_events
+                    .clamp(MIN_SPOOL_MAX_EVENTS, MAX_SPOOL_MAX_EVENTS)
+            };
+            integration.index = sanitize_index_name(integration.index.as_str());
+            integration.auth_type =
+                sanitize_integration_auth_type(integration.auth_type.as_str()).to_string();
+            integration.username = integration.username.trim().to_string();
+            integration.loki_labels = sanitize_loki_labels(integration.loki_labels);
+            integration.syslog_format = match integration
+                .syslog_format
+                .trim()
+                .to_ascii_lowercase()
+                .as_str()
+            {
+                "cef" => "cef",
+                "leef" => "leef",
+                _ => "rfc5424",
+            }
+            .to_string();
+            Some(integration)
+        })
+        .collect();
+    IntegrationSettings { integrations }
+}
+
+fn sanitize_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn sanitize_saved_filters(settings: SavedFilterSettings) -> SavedFilterSettings {
+    let mut seen_ids = HashSet::new();
+    let filters = settings
+        .filters
+        .into_iter()
+        .filter_map(|mut filter| {
+            let id = if filter.id.trim().is_empty() {
+                format!("filter-{}", Uuid::new_v4())
+            } else {
+                filter.id.trim().to_string()
+            };
+            if filter.name.trim().is_empty() || !seen_ids.insert(id.to_ascii_lowercase()) {
+                return None;
+            }
+            filter.id = id;
+            filter.name = filter.name.trim().to_string();
+            filter.text = filter.text.trim().to_string();
+            filter.severities = sanitize_string_list(
+                filter
+                    .severities
+                    .iter()
+                    .map(|severity| sanitize_severity(severity))
+                    .collect(),
+            );
+            filter.providers = sanitize_string_list(filter.providers);
+            filter.log_name = sanitize_optional(filter.log_name);
+            filter.category = sanitize_optional(filter.category);
+            filter.relative_minutes = filter
+                .relative_minutes
+                .filter(|minutes| *minutes > 0)
+                .map(|minutes| minutes.min(MAX_SAVED_FILTER_MINUTES));
+            filter.start = sanitize_optional(filter.start);
+            filter.end = sanitize_optional(filter.end);
+            filter.sort_column = sanitize_optional(filter.sort_column);
+            filter.sort_direction = filter.sort_column.as_ref().map(|_| {
+                if filter.sort_direction.as_deref() == Some("asc") {
+                    "asc".to_string()
+                } else {
+                    "desc".to_string()
+                }
+            });
+            Some(filter)
+        })
+        .collect();
+    SavedFilterSettings { filters }
+}
+
+/// Drops unnamed and duplicate workspaces, makes sure the default one exists and falls back to
+/// it when the active workspace is missing or archived.
+fn sanitize_workspace_settings(settings: WorkspaceSettings) -> WorkspaceSettings {
+    let mut seen = HashSet::new();
+    let mut workspaces: Vec<Workspace> = settings
+        .workspaces
+        .into_iter()
+        .filter_map(|mut workspace| {
+            workspace.id = workspace.id.trim().to_string();
+            workspace.name = workspace.name.trim().to_string();
+            if workspace.id.is_empty()
+                || workspace.name.is_empty()
+                || !workspace
+                    .id
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+                || !seen.insert(workspace.id.clone())
+            {
+                return None;
+            }
+            Some(workspace)
+        })
+        .collect();
+    match workspaces
+        .iter_mut()
+        .find(|workspace| workspace.id == DEFAULT_WORKSPACE_ID)
+    {
+        Some(default) => default.archived = false,
+        None => workspaces.insert(
+            0,
+            Workspace {
+                id: DEFAULT_WORKSPACE_ID.to_string(),
+                name: "Default".to_string(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                last_opened_at: None,
+                archived: false,
+            },
+        ),
+    }
+    let active_id = if workspaces
+        .iter()
+        .any(|workspace| workspace.id == settings.active_id && !workspace.archived)
+    {
+        settings.active_id
+    } else {
+        DEFAULT_WORKSPACE_ID.to_string()
+    };
+    WorkspaceSettings {
+        active_id,
+        workspaces,
+    }
+}
+
 fn settings_dir() -> Result<PathBuf, String> {
     let mut base = data_local_dir().ok_or("Unable to resolve local data directory")?;
     base.push("hermes-log-analyst");
@@ -303,40 +1047,156 @@ fn settings_dir() -> Result<PathBuf, String> {
     Ok(base)
 }
 
-fn theme_path() -> Result<PathBuf, String> {
-    let mut dir = settings_dir()?;
-    dir.push(THEME_FILE);
-    Ok(dir)
+/// Writes through a sibling temp file and a rename, so a crash mid-write leaves the previous
+/// file in place instead of a truncated one.
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    let mut file = fs::File::create(&temp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp, path)
+}
+
+/// The versioned `settings.json` document. Sections stay raw JSON so each loader keeps its own
+/// sanitizing, and one malformed section falls back to its default without losing the others.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SettingsFile {
+    #[serde(default)]
+    version: u32,
+    #[serde(flatten)]
+    sections: BTreeMap<String, Value>,
+}
+
+/// Serializes read-modify-write cycles on `settings.json` across commands and background tasks.
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Reads `settings.json` from `dir`, falling back to defaults when it does not parse. The
+/// corrupt file is left where it is; only a save sets it aside.
+fn read_settings_file(dir: &Path) -> Result<SettingsFile, String> {
+    Ok(parse_settings_file(dir)?.unwrap_or_default())
+}
+
+/// Reads `settings.json` ahead of a save. A document that does not parse is first moved to a
+/// timestamped `settings.json.bad-*` copy, so the save cannot overwrite the sections it still
+/// holds; if it cannot be moved the save fails instead.
+fn read_settings_for_write(dir: &Path) -> Result<SettingsFile, String> {
+    if let Some(document) = parse_settings_file(dir)? {
+        return Ok(document);
+    }
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+    fs::rename(
+        dir.join(SETTINGS_FILE),
+        dir.join(format!("{SETTINGS_FILE}.bad-{stamp}")),
+    )
+    .map_err(|error| format!("Settings file is invalid and could not be set aside: {error}"))?;
+    read_settings_file(dir)
+}
+
+/// Reads `settings.json` from `dir`, upgrading a missing or older document from the legacy
+/// files, or `None` when it does not parse.
+fn parse_settings_file(dir: &Path) -> Result<Option<SettingsFile>, String> {
+    let raw = match fs::read_to_string(dir.join(SETTINGS_FILE)) {
+        Ok(raw) => raw,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return migrate_legacy_settings(dir, SettingsFile::default()).map(Some);
+        }
+        Err(error) => return Err(format!("Failed to read settings: {error}")),
+    };
+    let Ok(document) = serde_json::from_str::<SettingsFile>(raw.as_str()) else {
+        return Ok(None);
+    };
+    if document.version < SETTINGS_VERSION {
+        return migrate_legacy_settings(dir, document).map(Some);
+    }
+    Ok(Some(document))
 }
 
-fn export_dir_path() -> Result<PathBuf, String> {
-    let mut dir = settings_dir()?;
-    dir.push(EXPORT_DIR_FILE);
-    Ok(dir)
+/// Folds the per-setting files older builds wrote into `document`, taking only those added
+/// after its version. The old files are only removed once the new document is on disk.
+fn migrate_legacy_settings(dir: &Path, mut document: SettingsFile) -> Result<SettingsFile, String> {
+    let mut migrated = Vec::new();
+    for (file, section, is_json, since) in LEGACY_SETTINGS_FILES {
+        if since <= document.version {
+            continue;
+        }
+        let legacy = dir.join(file);
+        let Ok(raw) = fs::read_to_string(&legacy) else {
+            continue;
+        };
+        let value = if is_json {
+            serde_json::from_str::<Value>(raw.as_str()).ok()
+        } else {
+            Some(Value::String(raw.trim().to_string()))
+        };
+        if let Some(value) = value {
+            document.sections.insert(section.to_string(), value);
+        }
+        migrated.push(legacy);
+    }
+    document.version = SETTINGS_VERSION;
+    if migrated.is_empty() {
+        return Ok(document);
+    }
+    write_settings_file(dir, &document)?;
+    for legacy in migrated {
+        let _ = fs::remove_file(legacy);
+    }
+    Ok(document)
 }
 
-fn ingest_days_path() -> Result<PathBuf, String> {
-    let mut dir = settings_dir()?;
-    dir.push(INGEST_DAYS_FILE);
-    Ok(dir)
+fn write_settings_file(dir: &Path, document: &SettingsFile) -> Result<(), String> {
+    let payload = serde_json::to_string_pretty(document)
+        .map_err(|error| format!("Failed to serialize settings: {error}"))?;
+    write_atomic(dir.join(SETTINGS_FILE).as_path(), payload.as_bytes())
+        .map_err(|error| format!("Failed to save settings: {error}"))
 }
 
-fn ingest_profile_path() -> Result<PathBuf, String> {
-    let mut dir = settings_dir()?;
-    dir.push(INGEST_PROFILE_FILE);
-    Ok(dir)
+fn load_section(section: &str) -> Option<Value> {
+    let _guard = SETTINGS_LOCK.lock().ok()?;
+    read_settings_file(settings_dir().ok()?.as_path())
+        .ok()?
+        .sections
+        .remove(section)
 }
 
-fn llm_settings_path() -> Result<PathBuf, String> {
-    let mut dir = settings_dir()?;
-    dir.push(LLM_SETTINGS_FILE);
-    Ok(dir)
+fn save_section(section: &str, value: Option<Value>) -> Result<(), String> {
+    save_sections(vec![(section, value)])
 }
 
-fn remote_settings_path() -> Result<PathBuf, String> {
-    let mut dir = settings_dir()?;
-    dir.push(REMOTE_SETTINGS_FILE);
-    Ok(dir)
+/// Replaces several sections of `settings.json` in a single write.
+fn save_sections(updates: Vec<(&str, Option<Value>)>) -> Result<(), String> {
+    let _guard = SETTINGS_LOCK
+        .lock()
+        .map_err(|_| "Settings lock is poisoned".to_string())?;
+    save_sections_in(settings_dir()?.as_path(), updates)
+}
+
+/// Replaces one section of `settings.json` in `dir`, or removes it when `value` is `None`.
+fn save_section_in(dir: &Path, section: &str, value: Option<Value>) -> Result<(), String> {
+    save_sections_in(dir, vec![(section, value)])
+}
+
+fn save_sections_in(dir: &Path, updates: Vec<(&str, Option<Value>)>) -> Result<(), String> {
+    let mut document = read_settings_for_write(dir)?;
+    for (section, value) in updates {
+        match value {
+            Some(value) => document.sections.insert(section.to_string(), value),
+            None => document.sections.remove(section),
+        };
+    }
+    document.version = SETTINGS_VERSION;
+    write_settings_file(dir, &document)
+}
+
+fn load_text_section(section: &str) -> Option<String> {
+    match load_section(section)? {
+        Value::String(value) => Some(value.trim().to_string()),
+        Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
 }
 
 pub fn save_theme(theme: &str) -> Result<(), String> {
@@ -344,15 +1204,12 @@ pub fn save_theme(theme: &str) -> Result<(), String> {
         return Err("Invalid theme value".to_string());
     }
 
-    let path = theme_path()?;
-    fs::write(path, theme.as_bytes()).map_err(|e| format!("Failed to save theme: {e}"))?;
-    Ok(())
+    save_section(THEME_SECTION, Some(Value::from(theme)))
+        .map_err(|e| format!("Failed to save theme: {e}"))
 }
 
 pub fn load_theme() -> Option<String> {
-    let path = theme_path().ok()?;
-    let raw = fs::read_to_string(path).ok()?;
-    let value = raw.trim().to_string();
+    let value = load_text_section(THEME_SECTION)?;
     if value == "system" || value == "light" || value == "dark" {
         Some(value)
     } else {
@@ -361,8 +1218,6 @@ pub fn load_theme() -> Option<String> {
 }
 
 pub fn save_export_dir(path: Option<&str>) -> Result<(), String> {
-    let storage_path = export_dir_path()?;
-
     match path {
         Some(value) if !value.trim().is_empty() => {
             let candidate = PathBuf::from(value.trim());
@@ -372,24 +1227,19 @@ pub fn save_export_dir(path: Option<&str>) -> Result<(), String> {
             if !candidate.is_dir() {
                 return Err("Export path must be a directory.".to_string());
             }
-            fs::write(storage_path, candidate.to_string_lossy().as_bytes())
-                .map_err(|e| format!("Failed to save export directory: {e}"))?;
-        }
-        _ => {
-            if storage_path.exists() {
-                fs::remove_file(storage_path)
-                    .map_err(|e| format!("Failed to clear export directory: {e}"))?;
-            }
+            save_section(
+                EXPORT_DIR_SECTION,
+                Some(Value::from(candidate.to_string_lossy().as_ref())),
+            )
+            .map_err(|e| format!("Failed to save export directory: {e}"))
         }
+        _ => save_section(EXPORT_DIR_SECTION, None)
+            .map_err(|e| format!("Failed to clear export directory: {e}")),
     }
-
-    Ok(())
 }
 
 pub fn load_export_dir() -> Option<String> {
-    let path = export_dir_path().ok()?;
-    let raw = fs::read_to_string(path).ok()?;
-    let value = raw.trim().to_string();
+    let value = load_text_section(EXPORT_DIR_SECTION)?;
     if value.is_empty() {
         return None;
     }
@@ -402,30 +1252,35 @@ pub fn load_export_dir() -> Option<String> {
     }
 }
 
+/// Validates and stores the export and report time zone, returning its canonical name.
+pub fn save_display_timezone(value: &str) -> Result<String, String> {
+    let timezone = DisplayTimezone::from_name(value)?;
+    save_section(DISPLAY_TIMEZONE_SECTION, Some(Value::from(timezone.name())))
+        .map_err(|e| format!("Failed to save time zone: {e}"))?;
+    Ok(timezone.name())
+}
+
+/// The saved time zone, or the system zone when none is saved or the saved name is unknown.
+pub fn load_display_timezone() -> DisplayTimezone {
+    load_text_section(DISPLAY_TIMEZONE_SECTION)
+        .and_then(|name| DisplayTimezone::from_name(name.as_str()).ok())
+        .unwrap_or_default()
+}
+
 pub fn save_ingest_window_days(days: u32) -> Result<(), String> {
     if days == 0 || days > 365 {
         return Err("Ingest window must be between 1 and 365 days.".to_string());
     }
 
-    let path = ingest_days_path()?;
-    fs::write(path, days.to_string().as_bytes())
-        .map_err(|e| format!("Failed to save ingest window: {e}"))?;
-    Ok(())
+    save_section(INGEST_DAYS_SECTION, Some(Value::from(days)))
+        .map_err(|e| format!("Failed to save ingest window: {e}"))
 }
 
 pub fn load_ingest_window_days() -> u32 {
-    let path = ingest_days_path();
-    if path.is_err() {
-        return DEFAULT_INGEST_DAYS;
-    }
-    let Ok(path) = path else {
-        return DEFAULT_INGEST_DAYS;
-    };
-    let Ok(raw) = fs::read_to_string(path) else {
+    let Some(raw) = load_text_section(INGEST_DAYS_SECTION) else {
         return DEFAULT_INGEST_DAYS;
     };
-    raw.trim()
-        .parse::<u32>()
+    raw.parse::<u32>()
         .ok()
         .filter(|value| *value > 0 && *value <= 365)
         .unwrap_or(DEFAULT_INGEST_DAYS)
@@ -436,6 +1291,17 @@ fn normalize_windows_channel(value: &str) -> Option<&'static str> {
         "application" => Some("Application"),
         "system" => Some("System"),
         "security" => Some("Security"),
+        "microsoft-windows-diagnostics-performance/operational" => {
+            Some("Microsoft-Windows-Diagnostics-Performance/Operational")
+        }
+        "microsoft-windows-printservice/operational" => {
+            Some("Microsoft-Windows-PrintService/Operational")
+        }
+        "microsoft-windows-terminalservices-localsessionmanager/operational" => {
+            Some("Microsoft-Windows-TerminalServices-LocalSessionManager/Operational")
+        }
+        "microsoft-windows-printservice/admin" => Some("Microsoft-Windows-PrintService/Admin"),
+        "microsoft-windows-sysmon/operational" => Some("Microsoft-Windows-Sysmon/Operational"),
         _ => None,
     }
 }
@@ -460,19 +1326,148 @@ fn sanitize_ingest_profile(profile: IngestProfile) -> IngestProfile {
             .clamp(MIN_MAX_EVENTS_PER_SYNC, MAX_MAX_EVENTS_PER_SYNC),
         windows_channels: channels,
         request_elevation: profile.request_elevation,
+        collect_smart: profile.collect_smart,
+        max_database_mb: if profile.max_database_mb == 0 {
+            0
+        } else {
+            profile
+                .max_database_mb
+                .clamp(MIN_MAX_DATABASE_MB, MAX_MAX_DATABASE_MB)
+        },
+        journal_filter: sanitize_journal_filter(profile.journal_filter),
+        unified_log_filter: UnifiedLogFilter {
+            predicate: sanitize_log_predicate(profile.unified_log_filter.predicate.as_str()),
+            ..profile.unified_log_filter
+        },
+        sync_interval_minutes: if profile.sync_interval_minutes == 0 {
+            0
+        } else {
+            profile
+                .sync_interval_minutes
+                .clamp(MIN_SYNC_INTERVAL_MINUTES, MAX_SYNC_INTERVAL_MINUTES)
+        },
+        retention: RetentionPolicy {
+            max_age_days: profile.retention.max_age_days.min(MAX_RETENTION_DAYS),
+            max_events: if profile.retention.max_events == 0 {
+                0
+            } else {
+                profile.retention.max_events.max(MIN_RETENTION_EVENTS)
+            },
+            archive_directory: profile.retention.archive_directory.trim().to_string(),
+            object_store: sanitize_object_store(profile.retention.object_store),
+        },
+        message_locale: sanitize_message_locale(profile.message_locale.as_str()),
+        collapse_repeats: profile.collapse_repeats,
     }
 }
 
-pub fn load_ingest_profile() -> IngestProfile {
-    let path_result = ingest_profile_path();
-    if path_result.is_err() {
-        return IngestProfile::default();
+fn sanitize_object_store(store: ObjectStoreSettings) -> ObjectStoreSettings {
+    let kind = store.kind.trim().to_ascii_lowercase();
+    ObjectStoreSettings {
+        kind: if kind == "s3" || kind == "azure" {
+            kind
+        } else {
+            String::new()
+        },
+        bucket: store.bucket.trim().to_string(),
+        region: store.region.trim().to_string(),
+        endpoint: store.endpoint.trim().trim_end_matches('/').to_string(),
+        access_key_id: store.access_key_id.trim().to_string(),
+        container_url: store.container_url.trim().trim_end_matches('/').to_string(),
+        prefix: store.prefix.trim().trim_matches('/').to_string(),
+    }
+}
+
+/// Keeps `system` or a well-formed locale name such as `en-US` or `zh-Hans-CN`; anything else
+/// reverts to the default.
+fn sanitize_message_locale(value: &str) -> String {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case(SYSTEM_MESSAGE_LOCALE) {
+        return SYSTEM_MESSAGE_LOCALE.to_string();
+    }
+    let mut parts = value.split('-');
+    let language_ok = parts.next().is_some_and(|part| {
+        (2..=3).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphabetic())
+    });
+    let rest_ok = parts.all(|part| {
+        (2..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    if language_ok && rest_ok {
+        value.to_string()
+    } else {
+        default_message_locale()
+    }
+}
+
+const MAX_LOG_PREDICATE_CHARS: usize = 2000;
+
+/// Predicates are validated by `log` itself; this only drops control characters (a predicate
+/// is one line) and caps the length.
+fn sanitize_log_predicate(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(MAX_LOG_PREDICATE_CHARS)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+const JOURNAL_PRIORITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+const MAX_JOURNAL_MATCHES: usize = 32;
+
+/// Unit and identifier names are also sent over SSH inside a shell command, so anything beyond
+/// the characters systemd itself uses in names is dropped rather than quoted.
+fn sanitize_journal_names(values: Vec<String>) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for value in values {
+        let value = value.trim();
+        let valid = !value.is_empty()
+            && value.len() <= 256
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '@' | '.' | '_' | '-' | ':'));
+        if valid && !names.iter().any(|entry| entry == value) {
+            names.push(value.to_string());
+        }
+        if names.len() >= MAX_JOURNAL_MATCHES {
+            break;
+        }
+    }
+    names
+}
+
+fn sanitize_journal_priority(value: &str) -> String {
+    let level = |part: &str| {
+        let part = part.trim().to_ascii_lowercase();
+        let known = JOURNAL_PRIORITIES.contains(&part.as_str())
+            || (part.len() == 1 && matches!(part.as_bytes()[0], b'0'..=b'7'));
+        known.then_some(part)
+    };
+    match value.split_once("..") {
+        Some((from, to)) => match (level(from), level(to)) {
+            (Some(from), Some(to)) => format!("{from}..{to}"),
+            _ => String::new(),
+        },
+        None => level(value).unwrap_or_default(),
     }
-    let path = path_result.unwrap();
-    let Ok(raw) = std::fs::read_to_string(&path) else {
+}
+
+fn sanitize_journal_filter(filter: JournalFilter) -> JournalFilter {
+    JournalFilter {
+        units: sanitize_journal_names(filter.units),
+        identifiers: sanitize_journal_names(filter.identifiers),
+        priority: sanitize_journal_priority(filter.priority.as_str()),
+    }
+}
+
+pub fn load_ingest_profile() -> IngestProfile {
+    let Some(value) = load_section(INGEST_PROFILE_SECTION) else {
         return IngestProfile::default();
     };
-    let Ok(parsed) = serde_json::from_str::<IngestProfile>(raw.as_str()) else {
+    let Ok(parsed) = serde_json::from_value::<IngestProfile>(value) else {
         return IngestProfile::default();
     };
     sanitize_ingest_profile(parsed)
@@ -480,26 +1475,18 @@ pub fn load_ingest_profile() -> IngestProfile {
 
 pub fn save_ingest_profile(profile: IngestProfile) -> Result<IngestProfile, String> {
     let sanitized = sanitize_ingest_profile(profile);
-    let path = ingest_profile_path()?;
-    let payload = serde_json::to_string_pretty(&sanitized)
+    let payload = serde_json::to_value(&sanitized)
         .map_err(|error| format!("Failed to serialize ingest profile: {error}"))?;
-    fs::write(path, payload.as_bytes())
+    save_section(INGEST_PROFILE_SECTION, Some(payload))
         .map_err(|error| format!("Failed to save ingest profile: {error}"))?;
     Ok(sanitized)
 }
 
 pub fn load_remote_settings() -> RemoteSettings {
-    let path = remote_settings_path();
-    if path.is_err() {
-        return RemoteSettings::default();
-    }
-    let Ok(path) = path else {
-        return RemoteSettings::default();
-    };
-    let Ok(raw) = fs::read_to_string(&path) else {
+    let Some(value) = load_section(REMOTE_SETTINGS_SECTION) else {
         return RemoteSettings::default();
     };
-    let Ok(parsed) = serde_json::from_str::<RemoteSettings>(raw.as_str()) else {
+    let Ok(parsed) = serde_json::from_value::<RemoteSettings>(value) else {
         return RemoteSettings::default();
     };
     sanitize_remote_settings(parsed)
@@ -507,14 +1494,133 @@ pub fn load_remote_settings() -> RemoteSettings {
 
 pub fn save_remote_settings(settings: RemoteSettings) -> Result<RemoteSettings, String> {
     let sanitized = sanitize_remote_settings(settings);
-    let path = remote_settings_path()?;
-    let payload = serde_json::to_string_pretty(&sanitized)
+    let payload = serde_json::to_value(&sanitized)
         .map_err(|error| format!("Failed to serialize remote settings: {error}"))?;
-    fs::write(path, payload.as_bytes())
+    save_section(REMOTE_SETTINGS_SECTION, Some(payload))
         .map_err(|error| format!("Failed to save remote settings: {error}"))?;
     Ok(sanitized)
 }
 
+pub fn load_integration_settings() -> IntegrationSettings {
+    let Some(value) = load_section(INTEGRATION_SETTINGS_SECTION) else {
+        return IntegrationSettings::default();
+    };
+    let Ok(parsed) = serde_json::from_value::<IntegrationSettings>(value) else {
+        return IntegrationSettings::default();
+    };
+    sanitize_integration_settings(parsed)
+}
+
+pub fn save_integration_settings(
+    settings: IntegrationSettings,
+) -> Result<IntegrationSettings, String> {
+    let sanitized = sanitize_integration_settings(settings);
+    let payload = serde_json::to_value(&sanitized)
+        .map_err(|error| format!("Failed to serialize integration settings: {error}"))?;
+    save_section(INTEGRATION_SETTINGS_SECTION, Some(payload))
+        .map_err(|error| format!("Failed to save integration settings: {error}"))?;
+    Ok(sanitized)
+}
+
+pub fn load_alert_settings() -> AlertSettings {
+    let Some(value) = load_section(ALERT_SETTINGS_SECTION) else {
+        return AlertSettings::default();
+    };
+    let Ok(parsed) = serde_json::from_value::<AlertSettings>(value) else {
+        return AlertSettings::default();
+    };
+    sanitize_alert_settings(parsed)
+}
+
+pub fn save_alert_settings(settings: AlertSettings) -> Result<AlertSettings, String> {
+    let sanitized = sanitize_alert_settings(settings);
+    let payload = serde_json::to_value(&sanitized)
+        .map_err(|error| format!("Failed to serialize alert settings: {error}"))?;
+    save_section(ALERT_SETTINGS_SECTION, Some(payload))
+        .map_err(|error| format!("Failed to save alert settings: {error}"))?;
+    Ok(sanitized)
+}
+
+pub fn load_saved_filters() -> SavedFilterSettings {
+    let Some(value) = load_section(SAVED_FILTERS_SECTION) else {
+        return SavedFilterSettings::default();
+    };
+    let Ok(parsed) = serde_json::from_value::<SavedFilterSettings>(value) else {
+        return SavedFilterSettings::default();
+    };
+    sanitize_saved_filters(parsed)
+}
+
+pub fn save_saved_filters(settings: SavedFilterSettings) -> Result<SavedFilterSettings, String> {
+    let sanitized = sanitize_saved_filters(settings);
+    let payload = serde_json::to_value(&sanitized)
+        .map_err(|error| format!("Failed to serialize saved filters: {error}"))?;
+    save_section(SAVED_FILTERS_SECTION, Some(payload))
+        .map_err(|error| format!("Failed to save saved filters: {error}"))?;
+    Ok(sanitized)
+}
+
+pub fn load_workspace_settings() -> WorkspaceSettings {
+    let Some(value) = load_section(WORKSPACES_SECTION) else {
+        return WorkspaceSettings::default();
+    };
+    let Ok(parsed) = serde_json::from_value::<WorkspaceSettings>(value) else {
+        return WorkspaceSettings::default();
+    };
+    sanitize_workspace_settings(parsed)
+}
+
+pub fn save_workspace_settings(settings: WorkspaceSettings) -> Result<WorkspaceSettings, String> {
+    let sanitized = sanitize_workspace_settings(settings);
+    let payload = serde_json::to_value(&sanitized)
+        .map_err(|error| format!("Failed to serialize workspaces: {error}"))?;
+    save_section(WORKSPACES_SECTION, Some(payload))
+        .map_err(|error| format!("Failed to save workspaces: {error}"))?;
+    Ok(sanitized)
+}
+
+pub fn load_syslog_listener_settings() -> SyslogListenerSettings {
+    let Some(value) = load_section(SYSLOG_LISTENER_SECTION) else {
+        return SyslogListenerSettings::default();
+    };
+    let Ok(parsed) = serde_json::from_value::<SyslogListenerSettings>(value) else {
+        return SyslogListenerSettings::default();
+    };
+    sanitize_syslog_listener_settings(parsed)
+}
+
+pub fn save_syslog_listener_settings(
+    settings: SyslogListenerSettings,
+) -> Result<SyslogListenerSettings, String> {
+    let sanitized = sanitize_syslog_listener_settings(settings);
+    let payload = serde_json::to_value(&sanitized)
+        .map_err(|error| format!("Failed to serialize syslog listener settings: {error}"))?;
+    save_section(SYSLOG_LISTENER_SECTION, Some(payload))
+        .map_err(|error| format!("Failed to save syslog listener settings: {error}"))?;
+    Ok(sanitized)
+}
+
+pub fn load_parser_profiles() -> ParserProfileSettings {
+    let Some(value) = load_section(PARSER_PROFILES_SECTION) else {
+        return ParserProfileSettings::default();
+    };
+    let Ok(parsed) = serde_json::from_value::<ParserProfileSettings>(value) else {
+        return ParserProfileSettings::default();
+    };
+    sanitize_parser_profiles(parsed)
+}
+
+pub fn save_parser_profiles(
+    settings: ParserProfileSettings,
+) -> Result<ParserProfileSettings, String> {
+    let sanitized = sanitize_parser_profiles(settings);
+    let payload = serde_json::to_value(&sanitized)
+        .map_err(|error| format!("Failed to serialize parser profiles: {error}"))?;
+    save_section(PARSER_PROFILES_SECTION, Some(payload))
+        .map_err(|error| format!("Failed to save parser profiles: {error}"))?;
+    Ok(sanitized)
+}
+
 fn sanitize_trusted_hosts(values: Vec<String>) -> Vec<String> {
     let mut seen = HashSet::new();
     let mut hosts = Vec::new();
@@ -622,6 +1728,11 @@ fn sanitize_profile(
         profile.base_url = default_base_url_for_provider(profile.provider.as_str()).to_string();
     }
     profile.model = profile.model.trim().to_string();
+    if profile.context_window_tokens != 0 {
+        profile.context_window_tokens = profile
+            .context_window_tokens
+            .clamp(MIN_CONTEXT_WINDOW_TOKENS, MAX_CONTEXT_WINDOW_TOKENS);
+    }
     profile
 }
 
@@ -705,6 +1816,9 @@ pub struct LlmSettingsLoadResult {
     pub settings: LlmSettings,
     pub migrated_api_keys: Vec<LlmApiKeyMigration>,
     pub migrated_from_legacy: bool,
+    /// The `llm` section was scanned for plaintext keys and should be saved back, which also
+    /// records the migration so later loads skip the scan.
+    pub key_migration_pending: bool,
 }
 
 fn legacy_profile(
@@ -735,6 +1849,7 @@ fn legacy_profile(
         model,
         enabled: legacy.enabled,
         api_key_configured: !api_key.is_empty(),
+        context_window_tokens: 0,
     };
     let migration = if api_key.is_empty() {
         None
@@ -803,50 +1918,125 @@ fn load_legacy_llm_settings(raw: &str) -> Option<LlmSettingsLoadResult> {
         settings,
         migrated_api_keys,
         migrated_from_legacy: true,
+        key_migration_pending: false,
     })
 }
 
-pub fn load_llm_settings_with_migration() -> LlmSettingsLoadResult {
-    let Ok(path) = llm_settings_path() else {
-        return LlmSettingsLoadResult {
-            settings: LlmSettings::default(),
-            migrated_api_keys: Vec::new(),
-            migrated_from_legacy: false,
-        };
+/// Finds API keys written into profile entries by hand or by older builds, so they can be moved
+/// into the OS keychain and dropped from the `llm` section of `settings.json`.
+fn plaintext_profile_api_keys(raw: &str) -> Vec<LlmApiKeyMigration> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(raw) else {
+        return Vec::new();
     };
-    let Ok(raw) = fs::read_to_string(path) else {
+    let Some(profiles) = value.get("profiles").and_then(|entry| entry.as_array()) else {
+        return Vec::new();
+    };
+    profiles
+        .iter()
+        .filter_map(|profile| {
+            let profile_id = profile.get("id")?.as_str()?.trim();
+            let api_key = profile
+                .get("apiKey")
+                .or_else(|| profile.get("api_key"))?
+                .as_str()?
+                .trim();
+            if profile_id.is_empty() || api_key.is_empty() {
+                return None;
+            }
+            Some(LlmApiKeyMigration {
+                profile_id: profile_id.to_string(),
+                api_key: api_key.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn load_llm_settings_with_migration() -> LlmSettingsLoadResult {
+    let document = SETTINGS_LOCK
+        .lock()
+        .ok()
+        .and_then(|_guard| read_settings_file(settings_dir().ok()?.as_path()).ok())
+        .unwrap_or_default();
+    llm_settings_load_result(&document)
+}
+
+fn llm_settings_load_result(document: &SettingsFile) -> LlmSettingsLoadResult {
+    let Some(raw) = document
+        .sections
+        .get(LLM_SETTINGS_SECTION)
+        .map(|value| value.to_string())
+    else {
         return LlmSettingsLoadResult {
             settings: LlmSettings::default(),
             migrated_api_keys: Vec::new(),
             migrated_from_legacy: false,
+            key_migration_pending: false,
         };
     };
     if let Ok(parsed) = serde_json::from_str::<LlmSettings>(raw.as_str()) {
+        let key_migration_pending = document
+            .sections
+            .get(LLM_KEY_MIGRATION_SECTION)
+            .and_then(Value::as_u64)
+            .unwrap_or(0)
+            < LLM_KEY_MIGRATION_VERSION;
+        let migrated_api_keys = if key_migration_pending {
+            plaintext_profile_api_keys(raw.as_str())
+        } else {
+            Vec::new()
+        };
+        let mut settings = sanitize_llm_settings(parsed);
+        for profile in settings.profiles.iter_mut() {
+            if migrated_api_keys
+                .iter()
+                .any(|secret| secret.profile_id == profile.id)
+            {
+                profile.api_key_configured = true;
+            }
+        }
         return LlmSettingsLoadResult {
-            settings: sanitize_llm_settings(parsed),
-            migrated_api_keys: Vec::new(),
+            settings,
+            migrated_api_keys,
             migrated_from_legacy: false,
+            key_migration_pending,
         };
     }
     load_legacy_llm_settings(raw.as_str()).unwrap_or(LlmSettingsLoadResult {
         settings: LlmSettings::default(),
         migrated_api_keys: Vec::new(),
         migrated_from_legacy: false,
+        key_migration_pending: false,
     })
 }
 
+/// Saves LLM settings. Profiles never serialize an API key, so the saved section is always
+/// clear of them and is marked as migrated in the same write.
 pub fn save_llm_settings(settings: LlmSettings) -> Result<LlmSettings, String> {
     let sanitized = sanitize_llm_settings(settings);
-    let path = llm_settings_path()?;
-    let payload = serde_json::to_string_pretty(&sanitized)
-        .map_err(|error| format!("Failed to serialize LLM settings: {error}"))?;
-    fs::write(path, payload.as_bytes())
+    save_sections(llm_settings_sections(&sanitized)?)
         .map_err(|error| format!("Failed to save LLM settings: {error}"))?;
     Ok(sanitized)
 }
 
+fn llm_settings_sections(
+    settings: &LlmSettings,
+) -> Result<Vec<(&'static str, Option<Value>)>, String> {
+    let payload = serde_json::to_value(settings)
+        .map_err(|error| format!("Failed to serialize LLM settings: {error}"))?;
+    Ok(vec![
+        (LLM_SETTINGS_SECTION, Some(payload)),
+        (
+            LLM_KEY_MIGRATION_SECTION,
+            Some(Value::from(LLM_KEY_MIGRATION_VERSION)),
+        ),
+    ])
+}
+
 const REMOTE_HOST_KEYCHAIN_SERVICE: &str = "hermes-log-analyst-remote-hosts";
 const REMOTE_PROVIDER_KEYCHAIN_SERVICE: &str = "hermes-log-analyst-remote-providers";
+const INTEGRATION_KEYCHAIN_SERVICE: &str = "hermes-log-analyst-integrations";
+const ARCHIVE_KEYCHAIN_SERVICE: &str = "hermes-log-analyst-archive";
+const ARCHIVE_STORE_ACCOUNT: &str = "object-store";
 
 pub fn set_remote_profile_secret(profile_id: &str, secret: &str) -> Result<(), String> {
     let entry = keyring::Entry::new(REMOTE_HOST_KEYCHAIN_SERVICE, profile_id)
@@ -917,3 +2107,210 @@ pub fn get_remote_provider_secret(provider_id: &str) -> Result<Option<String>, S
         Err(error) => Err(format!("Unable to read secret from OS keychain: {error}")),
     }
 }
+
+pub fn set_integration_secret(integration_id: &str, secret: &str) -> Result<(), String> {
+    let entry = keyring::Entry::new(INTEGRATION_KEYCHAIN_SERVICE, integration_id)
+        .map_err(|error| format!("Unable to open OS keychain entry: {error}"))?;
+    entry
+        .set_password(secret)
+        .map_err(|error| format!("Unable to save secret in OS keychain: {error}"))
+}
+
+pub fn clear_integration_secret(integration_id: &str) -> Result<(), String> {
+    let entry = keyring::Entry::new(INTEGRATION_KEYCHAIN_SERVICE, integration_id)
+        .map_err(|error| format!("Unable to open OS keychain entry: {error}"))?;
+    match entry.delete_credential() {
+        Ok(_) => Ok(()),
+        Err(keyring::Error::NoEntry) => Ok(()),
+        Err(error) => Err(format!("Unable to clear secret from OS keychain: {error}")),
+    }
+}
+
+pub fn set_archive_store_secret(secret: &str) -> Result<(), String> {
+    let entry = keyring::Entry::new(ARCHIVE_KEYCHAIN_SERVICE, ARCHIVE_STORE_ACCOUNT)
+        .map_err(|error| format!("Unable to open OS keychain entry: {error}"))?;
+    entry
+        .set_password(secret)
+        .map_err(|error| format!("Unable to save secret in OS keychain: {error}"))
+}
+
+pub fn clear_archive_store_secret() -> Result<(), String> {
+    let entry = keyring::Entry::new(ARCHIVE_KEYCHAIN_SERVICE, ARCHIVE_STORE_ACCOUNT)
+        .map_err(|error| format!("Unable to open OS keychain entry: {error}"))?;
+    match entry.delete_credential() {
+        Ok(_) => Ok(()),
+        Err(keyring::Error::NoEntry) => Ok(()),
+        Err(error) => Err(format!("Unable to clear secret from OS keychain: {error}")),
+    }
+}
+
+pub fn get_archive_store_secret() -> Result<Option<String>, String> {
+    let entry = keyring::Entry::new(ARCHIVE_KEYCHAIN_SERVICE, ARCHIVE_STORE_ACCOUNT)
+        .map_err(|error| format!("Unable to open OS keychain entry: {error}"))?;
+    match entry.get_password() {
+        Ok(value) => {
+            let trimmed = value.trim().to_string();
+            if trimmed.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(trimmed))
+            }
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(format!("Unable to read secret from OS keychain: {error}")),
+    }
+}
+
+pub fn get_integration_secret(integration_id: &str) -> Result<Option<String>, String> {
+    let entry = keyring::Entry::new(INTEGRATION_KEYCHAIN_SERVICE, integration_id)
+        .map_err(|error| format!("Unable to open OS keychain entry: {error}"))?;
+    match entry.get_password() {
+        Ok(value) => {
+            let trimmed = value.trim().to_string();
+            if trimmed.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(trimmed))
+            }
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(format!("Unable to read secret from OS keychain: {error}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_test_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hla-settings-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create settings dir");
+        dir
+    }
+
+    #[test]
+    fn migrates_legacy_files_into_settings_json() {
+        let dir = settings_test_dir();
+        fs::write(dir.join("theme.txt"), "dark\n").expect("write theme");
+        fs::write(dir.join("ingest_window_days.txt"), "30").expect("write days");
+        fs::write(
+            dir.join("ingest_profile.json"),
+            r#"{"autoSyncOnStartup":true,"maxEventsPerSync":5000,"windowsChannels":["System"]}"#,
+        )
+        .expect("write profile");
+        fs::write(dir.join("remote_settings.json"), "{}").expect("write remote settings");
+
+        let document = read_settings_file(dir.as_path()).expect("migrate");
+        assert_eq!(document.version, SETTINGS_VERSION);
+        assert_eq!(document.sections[THEME_SECTION], Value::from("dark"));
+        assert_eq!(document.sections[INGEST_DAYS_SECTION], Value::from("30"));
+        assert_eq!(
+            document.sections[INGEST_PROFILE_SECTION]["maxEventsPerSync"],
+            5000
+        );
+        assert!(dir.join(SETTINGS_FILE).exists());
+        assert!(!dir.join("theme.txt").exists());
+        assert!(!dir.join("ingest_profile.json").exists());
+        assert!(document.sections.contains_key(REMOTE_SETTINGS_SECTION));
+        assert!(!dir.join("remote_settings.json").exists());
+
+        save_section_in(dir.as_path(), INGEST_DAYS_SECTION, Some(Value::from(14))).expect("save");
+        let reread = read_settings_file(dir.as_path()).expect("reread");
+        assert_eq!(reread.sections[INGEST_DAYS_SECTION], Value::from(14));
+        assert_eq!(reread.sections[THEME_SECTION], Value::from("dark"));
+        assert!(!dir.join(format!("{SETTINGS_FILE}.tmp")).exists());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn upgrades_a_version_one_document_with_the_remaining_files() {
+        let dir = settings_test_dir();
+        fs::write(dir.join(SETTINGS_FILE), r#"{"version":1,"theme":"dark"}"#)
+            .expect("write settings");
+        fs::write(dir.join("workspaces.json"), r#"{"activeId":"default"}"#)
+            .expect("write workspaces");
+        fs::write(dir.join("theme.txt"), "light").expect("write stale theme");
+
+        let document = read_settings_file(dir.as_path()).expect("upgrade");
+        assert_eq!(document.version, SETTINGS_VERSION);
+        assert_eq!(document.sections[THEME_SECTION], Value::from("dark"));
+        assert_eq!(document.sections[WORKSPACES_SECTION]["activeId"], "default");
+        assert!(!dir.join("workspaces.json").exists());
+        assert!(dir.join("theme.txt").exists());
+
+        let reread = read_settings_file(dir.as_path()).expect("reread");
+        assert_eq!(reread.version, SETTINGS_VERSION);
+        assert_eq!(reread.sections[WORKSPACES_SECTION]["activeId"], "default");
+        fs::remove_dir_all(dir).ok();
+    }
+
+    fn set_aside_copies(dir: &Path) -> Vec<String> {
+        fs::read_dir(dir)
+            .expect("list settings dir")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(format!("{SETTINGS_FILE}.bad-").as_str())
+            })
+            .map(|entry| fs::read_to_string(entry.path()).expect("read set-aside copy"))
+            .collect()
+    }
+
+    #[test]
+    fn sets_a_corrupt_settings_file_aside_only_when_saving() {
+        let dir = settings_test_dir();
+        let corrupt = r#"{"version":2,"theme":"dark","llm":{"profiles":["#;
+        fs::write(dir.join(SETTINGS_FILE), corrupt).expect("write corrupt settings");
+
+        let document = read_settings_file(dir.as_path()).expect("fall back");
+        assert!(document.sections.is_empty());
+        assert_eq!(
+            fs::read_to_string(dir.join(SETTINGS_FILE)).expect("corrupt file left in place"),
+            corrupt
+        );
+        assert!(set_aside_copies(dir.as_path()).is_empty());
+
+        save_section_in(dir.as_path(), THEME_SECTION, Some(Value::from("light"))).expect("save");
+        let saved = read_settings_file(dir.as_path()).expect("reread");
+        assert_eq!(saved.sections[THEME_SECTION], Value::from("light"));
+        assert_eq!(set_aside_copies(dir.as_path()), vec![corrupt.to_string()]);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn moves_plaintext_api_keys_out_of_the_llm_section_once() {
+        let dir = settings_test_dir();
+        let llm = r#"{"allowLanDiscovery":false,"neverSendRawEventToUntrusted":true,"trustedHosts":[],"backupProfileId":"","defaultProfileId":"p1","profiles":[{"id":"p1","name":"OpenAI","provider":"openai","scope":"cloud","baseUrl":"https://api.openai.com","model":"gpt","enabled":true,"apiKeyConfigured":false,"apiKey":"sk-first"}]}"#;
+        let document_with = |llm: &str| format!(r#"{{"version":{SETTINGS_VERSION},"llm":{llm}}}"#);
+        fs::write(dir.join(SETTINGS_FILE), document_with(llm)).expect("write settings");
+
+        let loaded = llm_settings_load_result(&read_settings_file(dir.as_path()).expect("read"));
+        assert!(loaded.key_migration_pending);
+        assert_eq!(loaded.migrated_api_keys.len(), 1);
+        assert_eq!(loaded.migrated_api_keys[0].profile_id, "p1");
+        assert_eq!(loaded.migrated_api_keys[0].api_key, "sk-first");
+        assert!(loaded.settings.profiles[0].api_key_configured);
+
+        let sections = llm_settings_sections(&loaded.settings).expect("serialize");
+        save_sections_in(dir.as_path(), sections).expect("save");
+        let raw = fs::read_to_string(dir.join(SETTINGS_FILE)).expect("read saved settings");
+        assert!(!raw.contains("sk-first"));
+        let reloaded = llm_settings_load_result(&read_settings_file(dir.as_path()).expect("read"));
+        assert!(!reloaded.key_migration_pending);
+        assert!(reloaded.migrated_api_keys.is_empty());
+        assert!(reloaded.settings.profiles[0].api_key_configured);
+
+        let mut document = read_settings_file(dir.as_path()).expect("read");
+        document.sections.insert(
+            LLM_SETTINGS_SECTION.to_string(),
+            serde_json::from_str(llm.replace("sk-first", "sk-second").as_str()).expect("llm"),
+        );
+        write_settings_file(dir.as_path(), &document).expect("write key back");
+        let skipped = llm_settings_load_result(&read_settings_file(dir.as_path()).expect("read"));
+        assert!(!skipped.key_migration_pending);
+        assert!(skipped.migrated_api_keys.is_empty());
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,161 @@
+use crate::logs::NormalizedEvent;
+use crate::timestamps::parse_timestamp;
+
+const VENDOR: &str = "Hermes";
+const PRODUCT: &str = "Hermes Log Analyst";
+const PRODUCT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// CEF `Name` is meant to be a short summary; longer messages stay whole in `msg`.
+const CEF_NAME_MAX_CHARS: usize = 128;
+
+/// CEF severity on its 0-10 scale.
+pub fn cef_severity(severity: &str) -> u8 {
+    match severity {
+        "critical" => 10,
+        "error" => 7,
+        "warning" => 4,
+        "information" => 1,
+        _ => 0,
+    }
+}
+
+fn cef_header(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+fn cef_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+fn receipt_millis(event: &NormalizedEvent) -> Option<i64> {
+    parse_timestamp(event.timestamp.as_str()).map(|parsed| parsed.timestamp_millis())
+}
+
+/// Formats one event as an ArcSight Common Event Format (CEF:0) record. The event ID is the
+/// signature ID and the remaining normalized fields map to standard or custom-string keys.
+pub fn cef_line(event: &NormalizedEvent) -> String {
+    let signature = event
+        .event_id
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "0".to_string());
+    let name: String = event
+        .message
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(CEF_NAME_MAX_CHARS)
+        .collect();
+    let mut extension = Vec::new();
+    if let Some(millis) = receipt_millis(event) {
+        extension.push(format!("rt={millis}"));
+    }
+    for (key, value) in [
+        ("dvchost", event.source_host.as_str()),
+        ("cat", event.category.as_str()),
+        ("deviceFacility", event.log_name.as_str()),
+        ("externalId", event.id.as_str()),
+        ("cs1Label", "os"),
+        ("cs1", event.os.as_str()),
+        ("cs2Label", "provider"),
+        ("cs2", event.provider.as_str()),
+        ("msg", event.message.as_str()),
+    ] {
+        extension.push(format!("{key}={}", cef_value(value)));
+    }
+    format!(
+        "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+        cef_header(VENDOR),
+        cef_header(PRODUCT),
+        cef_header(PRODUCT_VERSION),
+        cef_header(signature.as_str()),
+        cef_header(if name.is_empty() {
+            event.provider.as_str()
+        } else {
+            name.as_str()
+        }),
+        cef_severity(event.severity.as_str()),
+        extension.join(" ")
+    )
+}
+
+fn leef_header(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// LEEF 1.0 separates attributes with tabs, so tabs and line breaks in values become spaces.
+fn leef_value(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+/// Formats one event as an IBM QRadar Log Event Extended Format (LEEF:1.0) record.
+pub fn leef_line(event: &NormalizedEvent) -> String {
+    let event_id = event
+        .event_id
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| event.provider.clone());
+    let severity = cef_severity(event.severity.as_str()).to_string();
+    let mut attributes = Vec::new();
+    if let Some(millis) = receipt_millis(event) {
+        attributes.push(format!("devTime={millis}"));
+    }
+    for (key, value) in [
+        ("sev", severity.as_str()),
+        ("cat", event.category.as_str()),
+        ("identHostName", event.source_host.as_str()),
+        ("os", event.os.as_str()),
+        ("logName", event.log_name.as_str()),
+        ("provider", event.provider.as_str()),
+        ("externalId", event.id.as_str()),
+        ("msg", event.message.as_str()),
+    ] {
+        attributes.push(format!("{key}={}", leef_value(value)));
+    }
+    format!(
+        "LEEF:1.0|{}|{}|{}|{}|{}",
+        leef_header(VENDOR),
+        leef_header(PRODUCT),
+        leef_header(PRODUCT_VERSION),
+        leef_header(event_id.as_str()),
+        attributes.join("\t")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    #[test]
+    fn escapes_cef_and_leef_fields() {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "Security",
+            "security",
+            "Microsoft|Auditing",
+            Some(4625),
+            "error",
+            "Logon failed: user=bob\n\tReason: bad password",
+            "host-a",
+        );
+        event.timestamp = "2024-03-01T10:00:00Z".to_string();
+
+        let cef = cef_line(&event);
+        assert!(cef.starts_with("CEF:0|Hermes|Hermes Log Analyst|"));
+        assert!(cef.contains("|4625|Logon failed: user=bob|7|rt=1709287200000 dvchost=host-a"));
+        assert!(cef.contains("cs2=Microsoft|Auditing"));
+        assert!(cef.ends_with("msg=Logon failed: user\\=bob\\n\tReason: bad password"));
+
+        let leef = leef_line(&event);
+        assert!(leef.starts_with("LEEF:1.0|Hermes|Hermes Log Analyst|"));
+        assert!(leef.contains("|4625|devTime=1709287200000\tsev=7\tcat=security"));
+        assert!(leef.contains("provider=Microsoft|Auditing"));
+        assert!(leef.ends_with("msg=Logon failed: user=bob  Reason: bad password"));
+    }
+}
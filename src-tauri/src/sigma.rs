@@ -0,0 +1,1049 @@
+//! Sigma rules: the community detection format, evaluated against stored events. Imported rules
+//! are kept one per file in the `sigma` folder; a scan runs every supported rule over the stored
+//! events and records each hit in the `sigma_detections` table.
+//!
+//! Supported: field selections (maps are AND, lists of maps are OR), keyword lists, the
+//! `contains`, `startswith`, `endswith`, `all` and `re` modifiers, `*`/`?` wildcards, and
+//! conditions built from `and`, `or`, `not`, parentheses and `1 of`/`all of` patterns.
+//! Aggregations and other modifiers mark a rule as unsupported instead of guessing.
+
+use crate::db::{self, SigmaDetection};
+use crate::logs::NormalizedEvent;
+use crate::nl_query::EventQueryFilter;
+use chrono::Utc;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+const RULES_DIR: &str = "sigma";
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+const MAX_MESSAGE_CHARS: usize = 400;
+const SAVE_BATCH: usize = 500;
+const SYSMON_CHANNEL: &str = "Microsoft-Windows-Sysmon/Operational";
+const POWERSHELL_CHANNEL: &str = "Microsoft-Windows-PowerShell/Operational";
+
+/// `<Data Name='Field'>value</Data>` pairs in a stored Windows event's XML.
+static EVENT_DATA: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)<Data Name=['"]([^'"]+)['"]\s*(?:/>|>(.*?)</Data>)"#)
+        .expect("event data pattern is valid")
+});
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigmaRuleSummary {
+    pub id: String,
+    pub title: String,
+    pub level: String,
+    pub status: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    /// `product/service/category` as written in the rule.
+    pub logsource: String,
+    pub file: String,
+    /// Why the rule cannot be evaluated here, when it cannot.
+    pub unsupported: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigmaRuleList {
+    pub rules: Vec<SigmaRuleSummary>,
+    /// Rule files that could not be read or parsed.
+    pub errors: Vec<String>,
+}
+
+/// A rule file picked by the user, sent as text so the backend never reads arbitrary paths.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigmaRuleFile {
+    pub name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigmaImportResult {
+    pub imported: Vec<SigmaRuleSummary>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigmaScanResult {
+    pub scanned_events: usize,
+    pub rules: usize,
+    pub unsupported_rules: usize,
+    pub matches: usize,
+    /// Matches not recorded by an earlier scan.
+    pub new_detections: usize,
+    pub rule_errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct LogSource {
+    product: Option<String>,
+    service: Option<String>,
+    category: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct RuleDocument {
+    id: String,
+    title: String,
+    status: Option<String>,
+    description: Option<String>,
+    level: Option<String>,
+    tags: Vec<String>,
+    logsource: LogSource,
+    detection: Mapping,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MatchMode {
+    Exact,
+    Contains,
+    StartsWith,
+    EndsWith,
+}
+
+enum ValueMatcher {
+    /// A `null` value: the field is absent or empty.
+    Empty,
+    Pattern(Regex),
+}
+
+impl ValueMatcher {
+    fn matches(&self, value: Option<&str>) -> bool {
+        match self {
+            Self::Empty => value.is_none_or(str::is_empty),
+            Self::Pattern(pattern) => value.is_some_and(|value| pattern.is_match(value)),
+        }
+    }
+}
+
+struct FieldMatcher {
+    field: String,
+    /// `|all`: every value must match instead of any.
+    all: bool,
+    values: Vec<ValueMatcher>,
+}
+
+enum Selection {
+    /// Field groups; a group matches when all its fields do, the selection when any group does.
+    Groups(Vec<Vec<FieldMatcher>>),
+    /// Keywords searched for in the event message.
+    Keywords(Vec<ValueMatcher>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Selection(String),
+    AnyOf(Vec<String>),
+    AllOf(Vec<String>),
+    Not(Box<Condition>),
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+}
+
+/// Which stored events a rule's `logsource` covers.
+#[derive(Default)]
+struct SourceMatcher {
+    os: Option<&'static str>,
+    /// Channels (Windows) or identifiers (Linux), compared with the log name and provider.
+    names: Vec<&'static str>,
+    event_ids: Vec<u32>,
+}
+
+struct CompiledRule {
+    source: SourceMatcher,
+    selections: BTreeMap<String, Selection>,
+    condition: Condition,
+}
+
+/// A stored rule and, when it is supported, its compiled form.
+pub struct SigmaRule {
+    summary: SigmaRuleSummary,
+    compiled: Option<CompiledRule>,
+}
+
+/// Field lookup over one event. Raw payload fields are parsed once, on first use.
+struct EventFields<'a> {
+    event: &'a NormalizedEvent,
+    raw: OnceCell<BTreeMap<String, String>>,
+}
+
+impl<'a> EventFields<'a> {
+    fn new(event: &'a NormalizedEvent) -> Self {
+        Self {
+            event,
+            raw: OnceCell::new(),
+        }
+    }
+
+    fn raw_fields(&self) -> &BTreeMap<String, String> {
+        self.raw
+            .get_or_init(|| raw_fields(self.event.raw_payload.as_deref().unwrap_or_default()))
+    }
+
+    fn get(&self, field: &str) -> Option<String> {
+        let event = self.event;
+        match field {
+            "EventID" => return event.event_id.map(|id| id.to_string()),
+            "Provider_Name" | "Provider" => return Some(event.provider.clone()),
+            "Channel" => return Some(event.log_name.clone()),
+            "Computer" | "ComputerName" | "Hostname" => {
+                return event
+                    .computer
+                    .clone()
+                    .or_else(|| Some(event.source_host.clone()))
+            }
+            _ => {}
+        }
+        if let Some(value) = event
+            .metadata
+            .as_ref()
+            .and_then(|metadata| lookup(metadata, field))
+        {
+            return Some(value.to_string());
+        }
+        if let Some(value) = lookup(self.raw_fields(), field) {
+            return Some(value.to_string());
+        }
+        match field {
+            "User" => event.user_name.clone(),
+            "ProcessId" => event.process_id.map(|id| id.to_string()),
+            "Message" => Some(event.message.clone()),
+            _ => None,
+        }
+    }
+}
+
+fn lookup<'a>(fields: &'a BTreeMap<String, String>, field: &str) -> Option<&'a str> {
+    fields
+        .get(field)
+        .or_else(|| {
+            fields
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(field))
+                .map(|(_, value)| value)
+        })
+        .map(String::as_str)
+}
+
+/// Named fields in a raw payload: `<EventData>` values of Windows XML, or the top-level
+/// values of a JSON record such as a journal entry.
+pub fn raw_fields(payload: &str) -> BTreeMap<String, String> {
+    let payload = payload.trim_start();
+    let mut fields = BTreeMap::new();
+    if payload.starts_with('{') {
+        if let Ok(serde_json::Value::Object(object)) = serde_json::from_str(payload) {
+            for (name, value) in object {
+                let value = match value {
+                    serde_json::Value::String(text) => text,
+                    serde_json::Value::Number(number) => number.to_string(),
+                    serde_json::Value::Bool(flag) => flag.to_string(),
+                    _ => continue,
+                };
+                fields.insert(name, value);
+            }
+        }
+    } else if payload.starts_with('<') {
+        for captures in EVENT_DATA.captures_iter(payload) {
+            let value = captures.get(2).map_or("", |value| value.as_str());
+            let value = match value
+                .strip_prefix("<![CDATA[")
+                .and_then(|inner| inner.strip_suffix("]]>"))
+            {
+                Some(inner) => inner.to_string(),
+                None => quick_xml::escape::unescape(value)
+                    .map(|text| text.into_owned())
+                    .unwrap_or_else(|_| value.to_string()),
+            };
+            fields.insert(captures[1].to_string(), value);
+        }
+    }
+    fields
+}
+
+impl SourceMatcher {
+    fn matches(&self, event: &NormalizedEvent) -> bool {
+        self.os.is_none_or(|os| event.os == os)
+            && (self.names.is_empty()
+                || self.names.iter().any(|name| {
+                    name.eq_ignore_ascii_case(event.log_name.as_str())
+                        || name.eq_ignore_ascii_case(event.provider.as_str())
+                }))
+            && (self.event_ids.is_empty()
+                || event
+                    .event_id
+                    .is_some_and(|event_id| self.event_ids.contains(&event_id)))
+    }
+}
+
+fn windows_service(service: &str) -> Option<Vec<&'static str>> {
+    let names = match service {
+        "security" => vec!["Security"],
+        "system" => vec!["System"],
+        "application" => vec!["Application"],
+        "sysmon" => vec![SYSMON_CHANNEL],
+        "powershell" => vec![POWERSHELL_CHANNEL],
+        "powershell-classic" => vec!["Windows PowerShell"],
+        "taskscheduler" => vec!["Microsoft-Windows-TaskScheduler/Operational"],
+        "wmi" => vec!["Microsoft-Windows-WMI-Activity/Operational"],
+        "windefend" => vec!["Microsoft-Windows-Windows Defender/Operational"],
+        "bits-client" => vec!["Microsoft-Windows-Bits-Client/Operational"],
+        "codeintegrity-operational" => vec!["Microsoft-Windows-CodeIntegrity/Operational"],
+        "ntlm" => vec!["Microsoft-Windows-NTLM/Operational"],
+        "firewall-as" => {
+            vec!["Microsoft-Windows-Windows Firewall With Advanced Security/Firewall"]
+        }
+        "terminalservices-localsessionmanager" => {
+            vec!["Microsoft-Windows-TerminalServices-LocalSessionManager/Operational"]
+        }
+        "dns-server" => vec!["DNS Server"],
+        _ => return None,
+    };
+    Some(names)
+}
+
+/// Sysmon event ids for the generic Windows categories, plus PowerShell's script categories.
+fn windows_category(category: &str) -> Option<(&'static str, Vec<u32>)> {
+    let ids = match category {
+        "process_creation" => vec![1],
+        "file_change" => vec![2],
+        "network_connection" => vec![3],
+        "sysmon_status" => vec![4, 16],
+        "process_termination" => vec![5],
+        "driver_load" => vec![6],
+        "image_load" => vec![7],
+        "create_remote_thread" => vec![8],
+        "raw_access_thread" => vec![9],
+        "process_access" => vec![10],
+        "file_event" => vec![11],
+        "registry_add" | "registry_delete" => vec![12],
+        "registry_set" => vec![13],
+        "registry_rename" => vec![14],
+        "registry_event" => vec![12, 13, 14],
+        "create_stream_hash" => vec![15],
+        "pipe_created" => vec![17, 18],
+        "wmi_event" => vec![19, 20, 21],
+        "dns_query" => vec![22],
+        "file_delete" => vec![23, 26],
+        "clipboard_capture" => vec![24],
+        "process_tampering" => vec![25],
+        "file_block_executable" => vec![27],
+        "ps_module" => return Some((POWERSHELL_CHANNEL, vec![4103])),
+        "ps_script" => return Some((POWERSHELL_CHANNEL, vec![4104])),
+        "ps_classic_start" => return Some(("Windows PowerShell", vec![400])),
+        _ => return None,
+    };
+    Some((SYSMON_CHANNEL, ids))
+}
+
+fn linux_service(service: &str) -> Option<Vec<&'static str>> {
+    let names = match service {
+        "syslog" => Vec::new(),
+        "auth" => vec![
+            "sshd",
+            "sudo",
+            "su",
+            "login",
+            "systemd-logind",
+            "polkitd",
+            "passwd",
+            "useradd",
+            "usermod",
+            "groupadd",
+        ],
+        "sshd" => vec!["sshd"],
+        "sudo" => vec!["sudo"],
+        "cron" => vec!["cron", "crond", "anacron"],
+        "auditd" => vec!["audit", "auditd"],
+        "vsftpd" => vec!["vsftpd"],
+        _ => return None,
+    };
+    Some(names)
+}
+
+fn source_matcher(logsource: &LogSource) -> Result<SourceMatcher, String> {
+    let product = logsource.product.as_deref().map(str::to_ascii_lowercase);
+    let service = logsource.service.as_deref().map(str::to_ascii_lowercase);
+    let category = logsource.category.as_deref().map(str::to_ascii_lowercase);
+    let mut source = SourceMatcher {
+        os: match product.as_deref() {
+            None => None,
+            Some("windows") => Some("windows"),
+            Some("linux") => Some("linux"),
+            Some("macos") => Some("macos"),
+            Some(other) => return Err(format!("Product \"{other}\" is not collected.")),
+        },
+        ..SourceMatcher::default()
+    };
+    if let Some(service) = service.as_deref() {
+        source.names = match source.os {
+            Some("windows") => windows_service(service),
+            Some("linux") => linux_service(service),
+            _ => None,
+        }
+        .ok_or_else(|| format!("Log source service \"{service}\" is not supported."))?;
+    }
+    if let Some(category) = category.as_deref() {
+        let (channel, ids) = match source.os {
+            Some("windows") => windows_category(category),
+            _ => None,
+        }
+        .ok_or_else(|| format!("Log source category \"{category}\" is not supported."))?;
+        if source.names.is_empty() {
+            source.names = vec![channel];
+        }
+        source.event_ids = ids;
+    }
+    Ok(source)
+}
+
+/// Regex for a Sigma value: `*` and `?` are wildcards and a backslash escapes them.
+fn wildcard_pattern(value: &str) -> String {
+    let mut pattern = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            '\\' if matches!(chars.peek(), Some('*' | '?' | '\\')) => {
+                let escaped = chars.next().unwrap_or('\\');
+                pattern.push_str(regex::escape(escaped.to_string().as_str()).as_str());
+            }
+            other => pattern.push_str(regex::escape(other.to_string().as_str()).as_str()),
+        }
+    }
+    pattern
+}
+
+fn compile_value(value: &Value, mode: MatchMode, re: bool) -> Result<ValueMatcher, String> {
+    let text = match value {
+        Value::Null => return Ok(ValueMatcher::Empty),
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        _ => return Err("only scalar values are supported".to_string()),
+    };
+    let pattern = if re {
+        text.clone()
+    } else {
+        let body = wildcard_pattern(text.as_str());
+        match mode {
+            MatchMode::Exact => format!("^{body}$"),
+            MatchMode::Contains => body,
+            MatchMode::StartsWith => format!("^{body}"),
+            MatchMode::EndsWith => format!("{body}$"),
+        }
+    };
+    // Sigma values are case-insensitive; `re` patterns say so themselves with `(?i)`.
+    RegexBuilder::new(pattern.as_str())
+        .case_insensitive(!re)
+        .dot_matches_new_line(true)
+        .size_limit(PATTERN_SIZE_LIMIT)
+        .build()
+        .map(ValueMatcher::Pattern)
+        .map_err(|error| format!("invalid pattern {text:?}: {error}"))
+}
+
+fn compile_group(map: &Mapping) -> Result<Vec<FieldMatcher>, String> {
+    map.iter()
+        .map(|(key, value)| {
+            let key = key
+                .as_str()
+                .ok_or_else(|| "field names must be strings".to_string())?;
+            let mut parts = key.split('|');
+            let field = parts.next().unwrap_or_default().trim().to_string();
+            if field.is_empty() {
+                return Err(format!("\"{key}\" has no field name"));
+            }
+            let (mut mode, mut all, mut re) = (MatchMode::Exact, false, false);
+            for modifier in parts {
+                match modifier {
+                    "contains" => mode = MatchMode::Contains,
+                    "startswith" => mode = MatchMode::StartsWith,
+                    "endswith" => mode = MatchMode::EndsWith,
+                    "all" => all = true,
+                    "re" => re = true,
+                    other => return Err(format!("modifier \"{other}\" is not supported")),
+                }
+            }
+            let values = match value {
+                Value::Sequence(items) => items
+                    .iter()
+                    .map(|item| compile_value(item, mode, re))
+                    .collect::<Result<Vec<_>, _>>()?,
+                single => vec![compile_value(single, mode, re)?],
+            };
+            Ok(FieldMatcher { field, all, values })
+        })
+        .collect()
+}
+
+fn compile_selection(name: &str, value: &Value) -> Result<Selection, String> {
+    match value {
+        Value::Mapping(map) => compile_group(map).map(|group| Selection::Groups(vec![group])),
+        Value::Sequence(items) if items.iter().all(Value::is_mapping) => items
+            .iter()
+            .filter_map(Value::as_mapping)
+            .map(compile_group)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Selection::Groups),
+        Value::Sequence(items) => items
+            .iter()
+            .map(|item| compile_value(item, MatchMode::Contains, false))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Selection::Keywords),
+        scalar => compile_value(scalar, MatchMode::Contains, false)
+            .map(|keyword| Selection::Keywords(vec![keyword])),
+    }
+    .map_err(|error| format!("Selection \"{name}\": {error}."))
+}
+
+struct ConditionParser<'a> {
+    tokens: Vec<String>,
+    position: usize,
+    names: &'a [String],
+}
+
+impl ConditionParser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.position).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn keyword(&mut self, word: &str) -> bool {
+        let found = self
+            .peek()
+            .is_some_and(|token| token.eq_ignore_ascii_case(word));
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Condition, String> {
+        let mut terms = vec![self.and()?];
+        while self.keyword("or") {
+            terms.push(self.and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Condition::Or(terms)
+        })
+    }
+
+    fn and(&mut self) -> Result<Condition, String> {
+        let mut terms = vec![self.not()?];
+        while self.keyword("and") {
+            terms.push(self.not()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Condition::And(terms)
+        })
+    }
+
+    fn not(&mut self) -> Result<Condition, String> {
+        if self.keyword("not") {
+            return Ok(Condition::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Condition, String> {
+        let token = self
+            .next()
+            .ok_or_else(|| "Condition ends unexpectedly.".to_string())?;
+        if token == "(" {
+            let inner = self.or()?;
+            if !self.keyword(")") {
+                return Err("Condition is missing a closing parenthesis.".to_string());
+            }
+            return Ok(inner);
+        }
+        let quantifier = token.to_ascii_lowercase();
+        if matches!(quantifier.as_str(), "1" | "any" | "all") && self.keyword("of") {
+            let target = self
+                .next()
+                .ok_or_else(|| format!("\"{token} of\" needs a selection pattern."))?;
+            let names = self.expand(target.as_str())?;
+            return Ok(if quantifier == "all" {
+                Condition::AllOf(names)
+            } else {
+                Condition::AnyOf(names)
+            });
+        }
+        if self.names.contains(&token) {
+            Ok(Condition::Selection(token))
+        } else {
+            Err(format!(
+                "Condition refers to unknown selection \"{token}\"."
+            ))
+        }
+    }
+
+    /// Selection names for `them` (all but `_`-prefixed ones) or a `selection_*` pattern.
+    fn expand(&self, target: &str) -> Result<Vec<String>, String> {
+        let names: Vec<String> = if target.eq_ignore_ascii_case("them") {
+            self.names
+                .iter()
+                .filter(|name| !name.starts_with('_'))
+                .cloned()
+                .collect()
+        } else {
+            let pattern = format!("^{}$", regex::escape(target).replace("\\*", ".*"));
+            let pattern = Regex::new(pattern.as_str()).map_err(|error| error.to_string())?;
+            self.names
+                .iter()
+                .filter(|name| pattern.is_match(name))
+                .cloned()
+                .collect()
+        };
+        if names.is_empty() {
+            Err(format!("\"{target}\" matches no selection."))
+        } else {
+            Ok(names)
+        }
+    }
+}
+
+fn parse_condition(text: &str, names: &[String]) -> Result<Condition, String> {
+    if text.contains('|') {
+        return Err("Aggregation conditions are not supported.".to_string());
+    }
+    let mut parser = ConditionParser {
+        tokens: text
+            .replace('(', " ( ")
+            .replace(')', " ) ")
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+        position: 0,
+        names,
+    };
+    let condition = parser.or()?;
+    match parser.peek() {
+        Some(extra) => Err(format!("Unexpected \"{extra}\" in condition.")),
+        None => Ok(condition),
+    }
+}
+
+impl CompiledRule {
+    fn new(document: &RuleDocument) -> Result<Self, String> {
+        let source = source_matcher(&document.logsource)?;
+        let mut selections = BTreeMap::new();
+        let mut condition = None;
+        for (key, value) in &document.detection {
+            match key.as_str() {
+                Some("condition") => condition = Some(value),
+                Some("timeframe") => {
+                    return Err("Time-window (timeframe) rules are not supported.".to_string())
+                }
+                Some(name) => {
+                    selections.insert(name.to_string(), compile_selection(name, value)?);
+                }
+                None => return Err("Selection names must be strings.".to_string()),
+            }
+        }
+        let names: Vec<String> = selections.keys().cloned().collect();
+        let condition = match condition {
+            Some(Value::String(text)) => parse_condition(text.as_str(), names.as_slice())?,
+            Some(Value::Sequence(items)) => Condition::Or(
+                items
+                    .iter()
+                    .map(|item| {
+                        item.as_str()
+                            .ok_or_else(|| "Conditions must be strings.".to_string())
+                            .and_then(|text| parse_condition(text, names.as_slice()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            _ => return Err("Detection has no condition.".to_string()),
+        };
+        Ok(Self {
+            source,
+            selections,
+            condition,
+        })
+    }
+
+    fn matches(&self, fields: &EventFields<'_>) -> bool {
+        self.source.matches(fields.event) && self.evaluate(&self.condition, fields)
+    }
+
+    fn evaluate(&self, condition: &Condition, fields: &EventFields<'_>) -> bool {
+        match condition {
+            Condition::Selection(name) => self.selection(name, fields),
+            Condition::AnyOf(names) => names.iter().any(|name| self.selection(name, fields)),
+            Condition::AllOf(names) => names.iter().all(|name| self.selection(name, fields)),
+            Condition::Not(inner) => !self.evaluate(inner, fields),
+            Condition::And(terms) => terms.iter().all(|term| self.evaluate(term, fields)),
+            Condition::Or(terms) => terms.iter().any(|term| self.evaluate(term, fields)),
+        }
+    }
+
+    fn selection(&self, name: &str, fields: &EventFields<'_>) -> bool {
+        match self.selections.get(name) {
+            Some(Selection::Groups(groups)) => groups.iter().any(|group| {
+                group.iter().all(|matcher| {
+                    let value = fields.get(matcher.field.as_str());
+                    let mut values = matcher.values.iter();
+                    if matcher.all {
+                        values.all(|expected| expected.matches(value.as_deref()))
+                    } else {
+                        values.any(|expected| expected.matches(value.as_deref()))
+                    }
+                })
+            }),
+            Some(Selection::Keywords(keywords)) => keywords
+                .iter()
+                .any(|keyword| keyword.matches(Some(fields.event.message.as_str()))),
+            None => false,
+        }
+    }
+}
+
+fn parse_document(value: &Value) -> Result<RuleDocument, String> {
+    let document: RuleDocument = serde_yaml::from_value(value.clone())
+        .map_err(|error| format!("Not a Sigma rule: {error}"))?;
+    if document.title.trim().is_empty() {
+        return Err("Rule has no title.".to_string());
+    }
+    if !document.detection.contains_key("condition") {
+        return Err("Rule has no detection condition.".to_string());
+    }
+    Ok(document)
+}
+
+fn load_rule(document: &RuleDocument, file: &str) -> SigmaRule {
+    let compiled = CompiledRule::new(document);
+    let logsource = [
+        document.logsource.product.as_deref(),
+        document.logsource.service.as_deref(),
+        document.logsource.category.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("/");
+    SigmaRule {
+        summary: SigmaRuleSummary {
+            id: document.id.clone(),
+            title: document.title.clone(),
+            level: document
+                .level
+                .clone()
+                .unwrap_or_else(|| "medium".to_string()),
+            status: document.status.clone(),
+            description: document.description.clone(),
+            tags: document.tags.clone(),
+            logsource,
+            file: file.to_string(),
+            unsupported: compiled.as_ref().err().cloned(),
+        },
+        compiled: compiled.ok(),
+    }
+}
+
+fn rules_dir() -> Result<PathBuf, String> {
+    let mut dir = db::data_dir()?;
+    dir.push(RULES_DIR);
+    fs::create_dir_all(&dir)
+        .map_err(|error| format!("Failed to create Sigma rule directory: {error}"))?;
+    Ok(dir)
+}
+
+/// Every stored rule, sorted by file name. Unreadable files are reported in `errors`.
+fn load_rules(errors: &mut Vec<String>) -> Result<Vec<SigmaRule>, String> {
+    let dir = rules_dir()?;
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .map_err(|error| format!("Failed to read Sigma rule directory: {error}"))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "yml" || extension == "yaml")
+        })
+        .collect();
+    files.sort();
+    let mut rules = Vec::new();
+    for path in files {
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let parsed = fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|text| {
+                serde_yaml::from_str::<Value>(text.as_str()).map_err(|e| e.to_string())
+            })
+            .and_then(|value| parse_document(&value));
+        match parsed {
+            Ok(document) => rules.push(load_rule(&document, file.as_str())),
+            Err(error) => errors.push(format!("{file}: {error}")),
+        }
+    }
+    Ok(rules)
+}
+
+pub fn list_rules() -> Result<SigmaRuleList, String> {
+    let mut errors = Vec::new();
+    let rules = load_rules(&mut errors)?;
+    Ok(SigmaRuleList {
+        rules: rules.into_iter().map(|rule| rule.summary).collect(),
+        errors,
+    })
+}
+
+/// A file name from the rule id, keeping only characters that are safe everywhere.
+fn file_name(rule_id: &str) -> String {
+    let stem: String = rule_id
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+        .take(64)
+        .collect();
+    format!("{stem}.yml")
+}
+
+fn import_document(dir: &Path, mut value: Value) -> Result<Option<SigmaRuleSummary>, String> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    if value.get("action").is_some() {
+        return Err("Rule collections are not supported; import each rule on its own.".to_string());
+    }
+    let has_id = value
+        .get("id")
+        .and_then(Value::as_str)
+        .is_some_and(|id| id.chars().any(|ch| ch.is_ascii_alphanumeric()));
+    if !has_id {
+        if let Value::Mapping(map) = &mut value {
+            map.insert(Value::from("id"), Value::from(Uuid::new_v4().to_string()));
+        }
+    }
+    let document = parse_document(&value)?;
+    let file = file_name(document.id.as_str());
+    let text = serde_yaml::to_string(&value)
+        .map_err(|error| format!("Failed to serialize rule: {error}"))?;
+    fs::write(dir.join(file.as_str()), text)
+        .map_err(|error| format!("Failed to save rule: {error}"))?;
+    Ok(Some(load_rule(&document, file.as_str()).summary))
+}
+
+/// Stores every rule in `files`, replacing rules with the same id. A file may hold several
+/// rules as separate YAML documents.
+pub fn import_rules(files: &[SigmaRuleFile]) -> Result<SigmaImportResult, String> {
+    let dir = rules_dir()?;
+    let mut result = SigmaImportResult::default();
+    for file in files {
+        for (index, document) in
+            serde_yaml::Deserializer::from_str(file.content.as_str()).enumerate()
+        {
+            let imported = Value::deserialize(document)
+                .map_err(|error| format!("Invalid YAML: {error}"))
+                .and_then(|value| import_document(dir.as_path(), value));
+            match imported {
+                Ok(Some(summary)) => result.imported.push(summary),
+                Ok(None) => {}
+                Err(error) => {
+                    result
+                        .errors
+                        .push(format!("{} (document {}): {error}", file.name, index + 1))
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Deletes a stored rule together with its detections.
+pub fn delete_rule(rule_id: &str) -> Result<(), String> {
+    let mut errors = Vec::new();
+    let rule = load_rules(&mut errors)?
+        .into_iter()
+        .find(|rule| rule.summary.id == rule_id)
+        .ok_or_else(|| format!("Sigma rule {rule_id} was not found."))?;
+    fs::remove_file(rules_dir()?.join(rule.summary.file.as_str()))
+        .map_err(|error| format!("Failed to delete Sigma rule: {error}"))?;
+    db::delete_sigma_detections(Some(rule_id)).map(|_| ())
+}
+
+fn detection(
+    rule: &SigmaRuleSummary,
+    event: &NormalizedEvent,
+    detected_at: &str,
+) -> SigmaDetection {
+    SigmaDetection {
+        rule_id: rule.id.clone(),
+        rule_title: rule.title.clone(),
+        level: rule.level.clone(),
+        event_id: event.id.clone(),
+        event_timestamp: event.timestamp.clone(),
+        source_host: event.source_host.clone(),
+        provider: event.provider.clone(),
+        event_code: event.event_id,
+        message: event.message.chars().take(MAX_MESSAGE_CHARS).collect(),
+        detected_at: detected_at.to_string(),
+    }
+}
+
+/// Runs every supported rule over the stored events matching `filter` and records the hits.
+/// Re-scanning the same events adds nothing new.
+pub fn scan(filter: &EventQueryFilter, host: Option<&str>) -> Result<SigmaScanResult, String> {
+    let mut rule_errors = Vec::new();
+    let rules = load_rules(&mut rule_errors)?;
+    let active: Vec<(&SigmaRuleSummary, &CompiledRule)> = rules
+        .iter()
+        .filter_map(|rule| {
+            rule.compiled
+                .as_ref()
+                .map(|compiled| (&rule.summary, compiled))
+        })
+        .collect();
+    let mut result = SigmaScanResult {
+        rules: active.len(),
+        unsupported_rules: rules.len() - active.len(),
+        ..SigmaScanResult::default()
+    };
+    if active.is_empty() {
+        result.rule_errors = rule_errors;
+        return Ok(result);
+    }
+
+    let detected_at = Utc::now().to_rfc3339();
+    let mut pending = Vec::new();
+    let mut new_detections = 0usize;
+    result.scanned_events = db::for_each_event_with_raw(filter, host, |event| {
+        let fields = EventFields::new(&event);
+        for (summary, rule) in &active {
+            if rule.matches(&fields) {
+                pending.push(detection(summary, &event, detected_at.as_str()));
+            }
+        }
+        if pending.len() >= SAVE_BATCH {
+            result.matches += pending.len();
+            new_detections += db::save_sigma_detections(pending.as_slice())?;
+            pending.clear();
+        }
+        Ok(())
+    })?;
+    result.matches += pending.len();
+    result.new_detections = new_detections + db::save_sigma_detections(pending.as_slice())?;
+    result.rule_errors = rule_errors;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn compile(yaml: &str) -> Result<CompiledRule, String> {
+        let value: Value = serde_yaml::from_str(yaml).unwrap();
+        CompiledRule::new(&parse_document(&value)?)
+    }
+
+    #[test]
+    fn matches_event_data_with_modifiers_and_filters() {
+        let rule = compile(
+            r#"
+title: Encoded PowerShell launched by Office
+id: 00000000-0000-0000-0000-000000000001
+logsource:
+  product: windows
+  category: process_creation
+detection:
+  selection_parent:
+    ParentImage|endswith:
+      - '\winword.exe'
+      - '\excel.exe'
+  selection_cli:
+    CommandLine|contains|all:
+      - 'powershell'
+      - '-enc'
+  filter_admin:
+    User: 'CORP\admin*'
+  condition: all of selection_* and not 1 of filter_*
+"#,
+        )
+        .unwrap();
+
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            SYSMON_CHANNEL,
+            "security",
+            "Microsoft-Windows-Sysmon",
+            Some(1),
+            "information",
+            "Process Create",
+            "ws01",
+        );
+        event.raw_payload = Some(
+            "<Event><EventData><Data Name='ParentImage'>C:\\Program Files\\Office\\WINWORD.EXE</Data><Data Name='CommandLine'>PowerShell.exe -Enc SQBFAFgA</Data><Data Name='User'>CORP\\alice</Data></EventData></Event>"
+                .to_string(),
+        );
+        assert!(rule.matches(&EventFields::new(&event)));
+
+        event.raw_payload = event
+            .raw_payload
+            .map(|xml| xml.replace("CORP\\alice", "CORP\\administrator"));
+        assert!(!rule.matches(&EventFields::new(&event)));
+
+        event.event_id = Some(3);
+        event.raw_payload = None;
+        assert!(!rule.matches(&EventFields::new(&event)));
+    }
+
+    #[test]
+    fn reports_what_cannot_be_evaluated() {
+        let aggregation = "title: Many failures\nlogsource:\n  product: windows\n  service: security\ndetection:\n  selection:\n    EventID: 4625\n  condition: selection | count() by IpAddress > 10\n";
+        assert!(compile(aggregation)
+            .err()
+            .unwrap_or_default()
+            .contains("Aggregation"));
+
+        let cloud = "title: Cloud\nlogsource:\n  product: aws\n  service: cloudtrail\ndetection:\n  selection:\n    eventName: x\n  condition: selection\n";
+        assert!(compile(cloud).err().unwrap_or_default().contains("aws"));
+
+        let modifier = "title: B64\nlogsource:\n  product: linux\ndetection:\n  selection:\n    cmd|base64offset: x\n  condition: selection\n";
+        assert!(compile(modifier)
+            .err()
+            .unwrap_or_default()
+            .contains("base64offset"));
+
+        let unknown = "title: Typo\nlogsource:\n  product: linux\ndetection:\n  selection:\n    - failed\n  condition: selection or (keywords\n";
+        assert!(compile(unknown).is_err());
+    }
+
+    #[test]
+    fn parses_condition_precedence() {
+        let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(
+            parse_condition("a or b and not c", names.as_slice()).unwrap(),
+            Condition::Or(vec![
+                Condition::Selection("a".to_string()),
+                Condition::And(vec![
+                    Condition::Selection("b".to_string()),
+                    Condition::Not(Box::new(Condition::Selection("c".to_string()))),
+                ]),
+            ])
+        );
+    }
+}
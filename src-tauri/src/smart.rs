@@ -0,0 +1,383 @@
+use crate::db::MetricSample;
+use serde::{Deserialize, Serialize};
+
+pub const SMART_SOURCE: &str = "smart";
+
+/// ATA attributes that indicate media or link problems; the rest are mostly vendor noise.
+#[cfg(not(target_os = "windows"))]
+const TRACKED_ATA_ATTRIBUTES: &[u64] = &[5, 10, 184, 187, 188, 196, 197, 198, 199];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartCollectionResult {
+    pub devices: usize,
+    pub samples: Vec<MetricSample>,
+    pub failing_devices: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+fn sample(
+    timestamp: &str,
+    host: &str,
+    device: &str,
+    name: &str,
+    value: f64,
+    unit: Option<&str>,
+    status: Option<String>,
+) -> MetricSample {
+    MetricSample {
+        timestamp: timestamp.to_string(),
+        source_host: host.to_string(),
+        source: SMART_SOURCE.to_string(),
+        device: device.to_string(),
+        name: name.to_string(),
+        value,
+        unit: unit.map(ToString::to_string),
+        status,
+    }
+}
+
+/// Devices whose overall health check failed or that report reallocated/pending sectors.
+fn failing_devices(samples: &[MetricSample]) -> Vec<String> {
+    let mut devices: Vec<String> = samples
+        .iter()
+        .filter(|sample| match sample.name.as_str() {
+            "health_passed" => sample.value < 1.0,
+            "Reallocated_Sector_Ct"
+            | "Current_Pending_Sector"
+            | "Offline_Uncorrectable"
+            | "media_errors"
+            | "critical_warning" => sample.value > 0.0,
+            _ => false,
+        })
+        .map(|sample| sample.device.clone())
+        .collect();
+    devices.sort();
+    devices.dedup();
+    devices
+}
+
+pub fn collect_smart_metrics(host: &str) -> SmartCollectionResult {
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    #[cfg(target_os = "windows")]
+    let mut result = collect_windows_physical_disks(timestamp.as_str(), host);
+    #[cfg(not(target_os = "windows"))]
+    let mut result = collect_smartctl(timestamp.as_str(), host);
+    result.failing_devices = failing_devices(result.samples.as_slice());
+    result
+}
+
+#[cfg(not(target_os = "windows"))]
+fn collect_smartctl(timestamp: &str, host: &str) -> SmartCollectionResult {
+    let mut result = SmartCollectionResult::default();
+    let scan = match std::process::Command::new("smartctl")
+        .args(["--scan", "--json"])
+        .output()
+    {
+        Ok(output) => output,
+        Err(_) => {
+            result.warnings.push(
+                "smartctl was not found. Install smartmontools to collect drive health data."
+                    .to_string(),
+            );
+            return result;
+        }
+    };
+    let Ok(scan) = serde_json::from_slice::<serde_json::Value>(scan.stdout.as_slice()) else {
+        result
+            .warnings
+            .push("smartctl --scan returned unreadable output.".to_string());
+        return result;
+    };
+
+    let devices = scan
+        .get("devices")
+        .and_then(|value| value.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for device in devices {
+        let Some(name) = device.get("name").and_then(|value| value.as_str()) else {
+            continue;
+        };
+        let mut args = vec!["-a", "--json"];
+        if let Some(kind) = device.get("type").and_then(|value| value.as_str()) {
+            args.push("-d");
+            args.push(kind);
+        }
+        args.push(name);
+        // smartctl's exit status is a bit mask that is non-zero for many healthy drives, so
+        // only the JSON body decides whether the read worked.
+        let output = match std::process::Command::new("smartctl").args(args).output() {
+            Ok(output) => output,
+            Err(error) => {
+                result
+                    .warnings
+                    .push(format!("Failed to run smartctl for {name}: {error}"));
+                continue;
+            }
+        };
+        match serde_json::from_slice::<serde_json::Value>(output.stdout.as_slice()) {
+            Ok(report) => {
+                let samples = parse_smartctl_report(name, &report, timestamp, host);
+                if samples.is_empty() {
+                    result.warnings.push(format!(
+                        "smartctl returned no health data for {name}; elevated access may be required."
+                    ));
+                } else {
+                    result.devices += 1;
+                    result.samples.extend(samples);
+                }
+            }
+            Err(_) => result
+                .warnings
+                .push(format!("smartctl returned unreadable output for {name}.")),
+        }
+    }
+    result
+}
+
+#[cfg(not(target_os = "windows"))]
+fn parse_smartctl_report(
+    name: &str,
+    report: &serde_json::Value,
+    timestamp: &str,
+    host: &str,
+) -> Vec<MetricSample> {
+    let device = match report.get("model_name").and_then(|value| value.as_str()) {
+        Some(model) => format!("{name} ({model})"),
+        None => name.to_string(),
+    };
+    let mut samples = Vec::new();
+    let mut push =
+        |metric: &str, value: Option<f64>, unit: Option<&str>, status: Option<String>| {
+            if let Some(value) = value {
+                samples.push(sample(
+                    timestamp,
+                    host,
+                    device.as_str(),
+                    metric,
+                    value,
+                    unit,
+                    status,
+                ));
+            }
+        };
+
+    if let Some(passed) = report
+        .pointer("/smart_status/passed")
+        .and_then(|value| value.as_bool())
+    {
+        push(
+            "health_passed",
+            Some(if passed { 1.0 } else { 0.0 }),
+            None,
+            Some(if passed { "PASSED" } else { "FAILED" }.to_string()),
+        );
+    }
+    push(
+        "temperature",
+        report
+            .pointer("/temperature/current")
+            .and_then(|value| value.as_f64()),
+        Some("C"),
+        None,
+    );
+    push(
+        "power_on_hours",
+        report
+            .pointer("/power_on_time/hours")
+            .and_then(|value| value.as_f64()),
+        Some("h"),
+        None,
+    );
+
+    if let Some(table) = report
+        .pointer("/ata_smart_attributes/table")
+        .and_then(|value| value.as_array())
+    {
+        for attribute in table {
+            let Some(id) = attribute.get("id").and_then(|value| value.as_u64()) else {
+                continue;
+            };
+            if !TRACKED_ATA_ATTRIBUTES.contains(&id) {
+                continue;
+            }
+            let metric = attribute
+                .get("name")
+                .and_then(|value| value.as_str())
+                .map(ToString::to_string)
+                .unwrap_or_else(|| format!("attribute_{id}"));
+            let status = attribute
+                .get("when_failed")
+                .and_then(|value| value.as_str())
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string);
+            push(
+                metric.as_str(),
+                attribute
+                    .pointer("/raw/value")
+                    .and_then(|value| value.as_f64()),
+                None,
+                status,
+            );
+        }
+    }
+
+    if let Some(log) = report.get("nvme_smart_health_information_log") {
+        for (key, unit) in [
+            ("critical_warning", None),
+            ("percentage_used", Some("%")),
+            ("available_spare", Some("%")),
+            ("media_errors", None),
+            ("num_err_log_entries", None),
+            ("unsafe_shutdowns", None),
+        ] {
+            push(
+                key,
+                log.get(key).and_then(|value| value.as_f64()),
+                unit,
+                None,
+            );
+        }
+    }
+    samples
+}
+
+#[cfg(target_os = "windows")]
+fn collect_windows_physical_disks(timestamp: &str, host: &str) -> SmartCollectionResult {
+    let mut result = SmartCollectionResult::default();
+    let script = "Get-PhysicalDisk | ForEach-Object { $r = $_ | Get-StorageReliabilityCounter -ErrorAction SilentlyContinue; \
+[pscustomobject]@{name=$_.FriendlyName;id=$_.DeviceId;health=[string]$_.HealthStatus;temperature=$r.Temperature;wear=$r.Wear;\
+readErrors=$r.ReadErrorsTotal;writeErrors=$r.WriteErrorsTotal;powerOnHours=$r.PowerOnHours} } | ConvertTo-Json -Compress";
+    let output = match std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()
+    {
+        Ok(output) => output,
+        Err(error) => {
+            result
+                .warnings
+                .push(format!("Failed to run Get-PhysicalDisk: {error}"));
+            return result;
+        }
+    };
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(output.stdout.as_slice()) else {
+        result
+            .warnings
+            .push("Get-PhysicalDisk returned no readable output.".to_string());
+        return result;
+    };
+    let disks = match value {
+        serde_json::Value::Array(items) => items,
+        other => vec![other],
+    };
+    for disk in disks {
+        let samples = parse_physical_disk(&disk, timestamp, host);
+        if !samples.is_empty() {
+            result.devices += 1;
+            result.samples.extend(samples);
+        }
+    }
+    if result
+        .samples
+        .iter()
+        .all(|sample| sample.name == "health_passed")
+    {
+        result.warnings.push(
+            "Storage reliability counters are unavailable; restart Hermes elevated to read them."
+                .to_string(),
+        );
+    }
+    result
+}
+
+#[cfg(target_os = "windows")]
+fn parse_physical_disk(disk: &serde_json::Value, timestamp: &str, host: &str) -> Vec<MetricSample> {
+    let name = disk
+        .get("name")
+        .and_then(|value| value.as_str())
+        .unwrap_or("Disk");
+    let device = match disk.get("id").and_then(|value| value.as_str()) {
+        Some(id) => format!("PhysicalDisk{id} ({name})"),
+        None => name.to_string(),
+    };
+    let mut samples = Vec::new();
+    if let Some(health) = disk.get("health").and_then(|value| value.as_str()) {
+        samples.push(sample(
+            timestamp,
+            host,
+            device.as_str(),
+            "health_passed",
+            if health.eq_ignore_ascii_case("healthy") {
+                1.0
+            } else {
+                0.0
+            },
+            None,
+            Some(health.to_string()),
+        ));
+    }
+    for (key, metric, unit) in [
+        ("temperature", "temperature", Some("C")),
+        ("wear", "percentage_used", Some("%")),
+        ("readErrors", "read_errors_total", None),
+        ("writeErrors", "write_errors_total", None),
+        ("powerOnHours", "power_on_hours", Some("h")),
+    ] {
+        if let Some(value) = disk.get(key).and_then(|value| value.as_f64()) {
+            samples.push(sample(
+                timestamp,
+                host,
+                device.as_str(),
+                metric,
+                value,
+                unit,
+                None,
+            ));
+        }
+    }
+    samples
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_smartctl_ata_and_nvme_reports() {
+        let ata: serde_json::Value = serde_json::from_str(
+            r#"{
+                "model_name": "WDC WD10EZEX",
+                "smart_status": {"passed": true},
+                "temperature": {"current": 34},
+                "power_on_time": {"hours": 18000},
+                "ata_smart_attributes": {"table": [
+                    {"id": 5, "name": "Reallocated_Sector_Ct", "raw": {"value": 8}, "when_failed": ""},
+                    {"id": 9, "name": "Power_On_Hours", "raw": {"value": 18000}},
+                    {"id": 197, "name": "Current_Pending_Sector", "raw": {"value": 0}}
+                ]}
+            }"#,
+        )
+        .unwrap();
+        let samples = parse_smartctl_report("/dev/sda", &ata, "2026-03-01T00:00:00Z", "localhost");
+        assert_eq!(samples.len(), 5);
+        assert!(samples
+            .iter()
+            .all(|sample| sample.device == "/dev/sda (WDC WD10EZEX)"));
+        assert_eq!(
+            failing_devices(samples.as_slice()),
+            vec!["/dev/sda (WDC WD10EZEX)".to_string()]
+        );
+
+        let nvme: serde_json::Value = serde_json::from_str(
+            r#"{"smart_status": {"passed": true}, "nvme_smart_health_information_log": {"critical_warning": 0, "percentage_used": 3, "media_errors": 0}}"#,
+        )
+        .unwrap();
+        let samples =
+            parse_smartctl_report("/dev/nvme0", &nvme, "2026-03-01T00:00:00Z", "localhost");
+        assert!(samples
+            .iter()
+            .any(|sample| sample.name == "percentage_used" && sample.value == 3.0));
+        assert!(failing_devices(samples.as_slice()).is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::crash::CrashRecord;
+use crate::hangs::{is_app_hang_event, is_hang_problem_type};
+use crate::logs::NormalizedEvent;
+use crate::power::is_unexpected_shutdown;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Days of history folded into each day's index, as in Reliability Monitor.
+pub const TRAILING_DAYS: i64 = 28;
+/// Weight multiplier per day of age, so recent days dominate the trend.
+const DECAY: f64 = 0.9;
+const SYSTEM_FAILURE_PENALTY: f64 = 2.0;
+const UNEXPECTED_SHUTDOWN_PENALTY: f64 = 1.5;
+const APP_CRASH_PENALTY: f64 = 1.0;
+const HANG_PENALTY: f64 = 0.5;
+/// Error events only nudge the score: 50 errors cost one point, capped at one point.
+const ERRORS_PER_POINT: f64 = 50.0;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StabilityDay {
+    pub day: String,
+    pub source_host: String,
+    /// Weighted trailing score from 1 (unstable) to 10 (no failures).
+    pub index: f64,
+    pub daily_score: f64,
+    pub app_crashes: u32,
+    pub hangs: u32,
+    pub system_failures: u32,
+    pub unexpected_shutdowns: u32,
+    pub error_count: u32,
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn local_day(timestamp: &str, offset: FixedOffset) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|parsed| parsed.with_timezone(&offset).date_naive())
+}
+
+fn is_system_failure(crash: &CrashRecord) -> bool {
+    let crash_type = crash.crash_type.to_ascii_lowercase();
+    crash_type.contains("blue screen")
+        || crash_type.contains("kernel")
+        || crash_type.contains("bugcheck")
+}
+
+fn daily_score(day: &StabilityDay) -> f64 {
+    let penalty = SYSTEM_FAILURE_PENALTY * day.system_failures as f64
+        + UNEXPECTED_SHUTDOWN_PENALTY * day.unexpected_shutdowns as f64
+        + APP_CRASH_PENALTY * day.app_crashes as f64
+        + HANG_PENALTY * day.hangs as f64
+        + (day.error_count as f64 / ERRORS_PER_POINT).min(1.0);
+    (10.0 - penalty).clamp(1.0, 10.0)
+}
+
+/// Computes a daily stability index for `[first_day, last_day]`. Each day gets a raw score from its
+/// failures; the index is a decaying average of that score and up to 27 earlier days, taken from
+/// `prior_scores` (previously stored days) when they fall before the computed range.
+pub fn compute_stability(
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+    host: &str,
+    events: &[NormalizedEvent],
+    crashes: &[CrashRecord],
+    prior_scores: &BTreeMap<NaiveDate, f64>,
+    offset: FixedOffset,
+) -> Vec<StabilityDay> {
+    let mut days: BTreeMap<NaiveDate, StabilityDay> = BTreeMap::new();
+    let mut day = first_day;
+    while day <= last_day {
+        days.insert(
+            day,
+            StabilityDay {
+                day: day.format("%Y-%m-%d").to_string(),
+                source_host: host.to_string(),
+                ..StabilityDay::default()
+            },
+        );
+        day += Duration::days(1);
+    }
+
+    for crash in crashes {
+        let Some(day) = local_day(crash.timestamp.as_str(), offset) else {
+            continue;
+        };
+        let Some(entry) = days.get_mut(&day) else {
+            continue;
+        };
+        if is_system_failure(crash) {
+            entry.system_failures += 1;
+        } else if is_hang_problem_type(crash.crash_type.as_str()) {
+            entry.hangs += 1;
+        } else {
+            entry.app_crashes += 1;
+        }
+    }
+
+    for event in events {
+        let Some(day) = local_day(event.timestamp.as_str(), offset) else {
+            continue;
+        };
+        let Some(entry) = days.get_mut(&day) else {
+            continue;
+        };
+        if is_unexpected_shutdown(event) {
+            entry.unexpected_shutdowns += 1;
+        } else if is_app_hang_event(event) {
+            entry.hangs += 1;
+        }
+        if matches!(event.severity.as_str(), "error" | "critical") {
+            entry.error_count += 1;
+        }
+    }
+
+    let mut scores = prior_scores.clone();
+    for (day, entry) in &mut days {
+        entry.daily_score = round2(daily_score(entry));
+        scores.insert(*day, entry.daily_score);
+    }
+
+    for (day, entry) in &mut days {
+        let mut weighted = 0.0;
+        let mut weights = 0.0;
+        for age in 0..TRAILING_DAYS {
+            let Some(score) = scores.get(&(*day - Duration::days(age))) else {
+                continue;
+            };
+            let weight = DECAY.powi(age as i32);
+            weighted += score * weight;
+            weights += weight;
+        }
+        entry.index = round2(weighted / weights);
+    }
+
+    days.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn crash(timestamp: &str, crash_type: &str) -> CrashRecord {
+        let mut crash = CrashRecord::new(
+            "windows",
+            "WER",
+            crash_type,
+            None,
+            crash_type,
+            None,
+            None,
+            "localhost",
+            false,
+        );
+        crash.timestamp = timestamp.to_string();
+        crash
+    }
+
+    #[test]
+    fn failures_lower_the_index_and_recover_gradually() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let crashes = vec![
+            crash("2026-03-02T08:00:00Z", "Blue Screen"),
+            crash("2026-03-02T09:00:00Z", "APPCRASH"),
+            crash("2026-03-02T10:00:00Z", "AppHangB1"),
+        ];
+        let days = compute_stability(
+            date("2026-03-01"),
+            date("2026-03-04"),
+            "localhost",
+            &[],
+            crashes.as_slice(),
+            &BTreeMap::new(),
+            utc,
+        );
+
+        assert_eq!(days.len(), 4);
+        assert_eq!(days[0].index, 10.0);
+        assert_eq!(days[1].system_failures, 1);
+        assert_eq!(days[1].app_crashes, 1);
+        assert_eq!(days[1].hangs, 1);
+        assert_eq!(days[1].daily_score, 6.5);
+        assert!(days[1].index < 10.0);
+        assert!(days[2].index > days[1].index);
+        assert!(days[3].index > days[2].index);
+    }
+
+    #[test]
+    fn uses_stored_scores_before_the_range() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let prior = BTreeMap::from([(date("2026-02-28"), 1.0)]);
+        let days = compute_stability(
+            date("2026-03-01"),
+            date("2026-03-01"),
+            "localhost",
+            &[],
+            &[],
+            &prior,
+            utc,
+        );
+        // (10 + 1 * 0.9) / 1.9
+        assert_eq!(days[0].index, 5.74);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::forwarding::severity_rank;
+use crate::logs::NormalizedEvent;
+use crate::prompt_budget::estimate_tokens;
+use crate::rca::redact_sensitive_text;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Token budget for the event block of a summary prompt.
+pub const DEFAULT_SUMMARY_TOKEN_BUDGET: usize = 3000;
+const MAX_LINE_MESSAGE_CHARS: usize = 300;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSummary {
+    pub summary: String,
+    pub notable_findings: Vec<String>,
+    pub event_count: usize,
+    /// Distinct messages that fit in the prompt budget; repeats are folded into one line.
+    pub included_groups: usize,
+    pub omitted_groups: usize,
+    pub raw_response: String,
+}
+
+struct EventGroup<'a> {
+    first: &'a NormalizedEvent,
+    last_timestamp: &'a str,
+    count: usize,
+}
+
+fn message_line(message: &str) -> String {
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= MAX_LINE_MESSAGE_CHARS {
+        line.to_string()
+    } else {
+        let truncated: String = line.chars().take(MAX_LINE_MESSAGE_CHARS).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Folds repeated events into one line each and keeps the most severe and most frequent groups
+/// that fit in `token_budget`. Returns the lines and how many groups were left out.
+pub fn compact_events(
+    events: &[NormalizedEvent],
+    token_budget: usize,
+    redact: bool,
+) -> (Vec<String>, usize) {
+    let mut groups: BTreeMap<(String, Option<u32>, String, String), EventGroup<'_>> =
+        BTreeMap::new();
+    for event in events {
+        let key = (
+            event.provider.clone(),
+            event.event_id,
+            event.severity.clone(),
+            message_line(event.message.as_str()),
+        );
+        let group = groups.entry(key).or_insert(EventGroup {
+            first: event,
+            last_timestamp: event.timestamp.as_str(),
+            count: 0,
+        });
+        group.count += 1;
+        if event.timestamp < group.first.timestamp {
+            group.first = event;
+        }
+        if event.timestamp.as_str() > group.last_timestamp {
+            group.last_timestamp = event.timestamp.as_str();
+        }
+    }
+
+    let mut ordered: Vec<(String, EventGroup<'_>)> = groups
+        .into_iter()
+        .map(|((_, _, _, message), group)| (message, group))
+        .collect();
+    ordered.sort_by(|(_, left), (_, right)| {
+        severity_rank(right.first.severity.as_str())
+            .cmp(&severity_rank(left.first.severity.as_str()))
+            .then(right.count.cmp(&left.count))
+            .then(left.first.timestamp.cmp(&right.first.timestamp))
+    });
+
+    let mut lines = Vec::new();
+    let mut used = 0;
+    let total = ordered.len();
+    for (message, group) in ordered {
+        let event = group.first;
+        let event_id = event
+            .event_id
+            .map(|value| format!(" id {value}"))
+            .unwrap_or_default();
+        let repeats = if group.count > 1 {
+            format!(" (x{}, until {})", group.count, group.last_timestamp)
+        } else {
+            String::new()
+        };
+        let message = if redact {
+            redact_sensitive_text(message.as_str())
+        } else {
+            message
+        };
+        let line = format!(
+            "- {} {} {} {}{event_id}{repeats}: {message}",
+            event.timestamp, event.severity, event.log_name, event.provider
+        );
+        let cost = estimate_tokens(line.as_str());
+        if used + cost > token_budget && !lines.is_empty() {
+            break;
+        }
+        used += cost;
+        lines.push(line);
+    }
+    let omitted = total - lines.len();
+    (lines, omitted)
+}
+
+/// Builds the summary prompt for a selection of events, asking for JSON output.
+pub fn build_summary_prompt(lines: &[String], event_count: usize, omitted: usize) -> String {
+    let mut prompt = vec![
+        "Act as an experienced systems administrator reviewing log events for a colleague.".to_string(),
+        "Summarize the selected events below in plain language and list the notable findings, using only the evidence provided.".to_string(),
+        "Preserve \"<sensitive info redacted>\" placeholders exactly if present.".to_string(),
+        "Respond with a single JSON object and nothing else, using these keys:".to_string(),
+        "{\"summary\": string, \"notableFindings\": [string]}".to_string(),
+        String::new(),
+        format!(
+            "Selected events ({event_count} total, repeats folded, most severe first):"
+        ),
+    ];
+    prompt.extend(lines.iter().cloned());
+    if omitted > 0 {
+        prompt.push(format!(
+            "({omitted} lower-priority distinct messages were omitted to fit the prompt.)"
+        ));
+    }
+    prompt.join("\n")
+}
+
+/// Reads the model reply as JSON when possible, otherwise treats the first paragraph as the
+/// summary and bulleted lines as findings.
+pub fn parse_summary_response(response: &str) -> EventSummary {
+    let parsed = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => {
+            serde_json::from_str::<Value>(&response[start..=end]).ok()
+        }
+        _ => None,
+    };
+    if let Some(value) = parsed.filter(Value::is_object) {
+        let summary = value
+            .get("summary")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string();
+        if !summary.is_empty() {
+            let findings = value
+                .get("notableFindings")
+                .or_else(|| value.get("notable_findings"))
+                .and_then(Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(ToString::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            return EventSummary {
+                summary,
+                notable_findings: findings,
+                raw_response: response.to_string(),
+                ..EventSummary::default()
+            };
+        }
+    }
+
+    let summary = response
+        .split("\n\n")
+        .map(str::trim)
+        .find(|paragraph| !paragraph.is_empty())
+        .unwrap_or_default()
+        .to_string();
+    let notable_findings = response
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")))
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(ToString::to_string)
+        .collect();
+    EventSummary {
+        summary,
+        notable_findings,
+        raw_response: response.to_string(),
+        ..EventSummary::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, timestamp: &str, severity: &str, message: &str) -> NormalizedEvent {
+        NormalizedEvent {
+            id: id.to_string(),
+            timestamp: timestamp.to_string(),
+            os: "windows".to_string(),
+            log_name: "System".to_string(),
+            category: "system".to_string(),
+            provider: "disk".to_string(),
+            event_id: Some(153),
+            severity: severity.to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn folds_repeats_and_respects_budget() {
+        let mut events: Vec<NormalizedEvent> = (0..5)
+            .map(|index| {
+                event(
+                    index.to_string().as_str(),
+                    format!("2026-03-01T10:0{index}:00Z").as_str(),
+                    "warning",
+                    "The IO operation was retried.",
+                )
+            })
+            .collect();
+        events.push(event(
+            "e",
+            "2026-03-01T10:09:00Z",
+            "error",
+            "Reset to device.",
+        ));
+        events.push(event(
+            "i",
+            "2026-03-01T10:10:00Z",
+            "information",
+            "Volume online.",
+        ));
+
+        let (lines, omitted) = compact_events(events.as_slice(), 10_000, false);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(omitted, 0);
+        assert!(lines[0].contains("Reset to device."));
+        assert!(lines[1].contains("(x5, until 2026-03-01T10:04:00Z)"));
+
+        let (lines, omitted) = compact_events(events.as_slice(), 1, false);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(omitted, 2);
+    }
+
+    #[test]
+    fn parses_json_summary() {
+        let summary = parse_summary_response(
+            "{\"summary\": \"Disk resets preceded the crash.\", \"notableFindings\": [\"5 retries on disk 0\"]}",
+        );
+        assert_eq!(summary.summary, "Disk resets preceded the crash.");
+        assert_eq!(summary.notable_findings, vec!["5 retries on disk 0"]);
+    }
+}
@@ -0,0 +1,313 @@
+use crate::logs::NormalizedEvent;
+use crate::settings::IntegrationConfig;
+use crate::siem::{cef_line, leef_line};
+use crate::timestamps::parse_timestamp;
+use chrono::{SecondsFormat, Utc};
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Private enterprise number reserved for documentation (RFC 5612), used as the SD-ID suffix.
+const SD_ID: &str = "hermes@32473";
+const APP_NAME_MAX: usize = 48;
+const MSGID_MAX: usize = 32;
+const HOSTNAME_MAX: usize = 255;
+/// Keeps UDP datagrams well under the IPv4 limit; longer messages are cut.
+const UDP_MAX_BYTES: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogTransport {
+    Udp,
+    Tcp,
+    Tls,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyslogTarget {
+    pub transport: SyslogTransport,
+    pub host: String,
+    pub port: u16,
+}
+
+/// Parses `udp://host:514`, `tcp://host:601` or `tls://host:6514`. A bare `host[:port]`
+/// means UDP, and a missing port takes the transport's registered default.
+pub fn parse_target(endpoint: &str) -> Result<SyslogTarget, String> {
+    let endpoint = endpoint.trim();
+    let (transport, rest) = match endpoint.split_once("://") {
+        Some((scheme, rest)) => {
+            let transport = match scheme.to_ascii_lowercase().as_str() {
+                "udp" => SyslogTransport::Udp,
+                "tcp" => SyslogTransport::Tcp,
+                "tls" => SyslogTransport::Tls,
+                other => return Err(format!("Unsupported syslog transport: {other}")),
+            };
+            (transport, rest)
+        }
+        None => (SyslogTransport::Udp, endpoint),
+    };
+    let rest = rest.trim_end_matches('/');
+    let default_port = match transport {
+        SyslogTransport::Udp => 514,
+        SyslogTransport::Tcp => 601,
+        SyslogTransport::Tls => 6514,
+    };
+    let (host, port) = match rest.rsplit_once(':') {
+        Some((host, port)) if !host.ends_with(':') && !port.contains(']') => {
+            let port = port
+                .parse::<u16>()
+                .map_err(|_| format!("Invalid syslog port: {port}"))?;
+            (host, port)
+        }
+        _ => (rest, default_port),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        return Err("Syslog endpoint has no host.".to_string());
+    }
+    Ok(SyslogTarget {
+        transport,
+        host: host.to_string(),
+        port,
+    })
+}
+
+/// RFC 5424 severity for an event severity.
+pub fn severity_code(severity: &str) -> u8 {
+    match severity {
+        "critical" => 2,
+        "error" => 3,
+        "warning" => 4,
+        "information" => 6,
+        _ => 7,
+    }
+}
+
+fn facility_code(category: &str) -> u8 {
+    match category {
+        // authpriv
+        "security" | "audit" => 10,
+        // daemon
+        "system" => 3,
+        // lpr
+        "printing" => 6,
+        // user
+        _ => 1,
+    }
+}
+
+/// Header fields allow printable US-ASCII only; anything else is replaced and empty values
+/// become the NILVALUE.
+fn header_field(value: &str, max: usize) -> String {
+    let field: String = value
+        .chars()
+        .map(|ch| if ch.is_ascii_graphic() { ch } else { '_' })
+        .take(max)
+        .collect();
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+fn param_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(']', "\\]")
+}
+
+/// Formats one event as an RFC 5424 message without transport framing. With `body_format`
+/// "cef" or "leef" the MSG part carries that record instead of the plain message, which is
+/// how ArcSight and QRadar expect to receive them over syslog.
+pub fn format_message(event: &NormalizedEvent, body_format: &str) -> String {
+    let priority =
+        facility_code(event.category.as_str()) * 8 + severity_code(event.severity.as_str());
+    let timestamp = parse_timestamp(event.timestamp.as_str())
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339_opts(SecondsFormat::Micros, true);
+    let msgid = event.event_id.map(|id| id.to_string()).unwrap_or_default();
+    let body = match body_format {
+        "cef" => cef_line(event),
+        "leef" => leef_line(event),
+        _ => format!("\u{feff}{}", event.message),
+    };
+    format!(
+        "<{priority}>1 {timestamp} {} {} - {} [{SD_ID} os=\"{}\" logName=\"{}\" category=\"{}\" eventRecordId=\"{}\"] {body}",
+        header_field(event.source_host.as_str(), HOSTNAME_MAX),
+        header_field(event.provider.as_str(), APP_NAME_MAX),
+        header_field(msgid.as_str(), MSGID_MAX),
+        param_value(event.os.as_str()),
+        param_value(event.log_name.as_str()),
+        param_value(event.category.as_str()),
+        param_value(event.id.as_str()),
+    )
+}
+
+fn tls_config() -> Arc<ClientConfig> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    Arc::new(
+        ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth(),
+    )
+}
+
+enum Connection {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+    Tls(Box<StreamOwned<ClientConnection, TcpStream>>),
+}
+
+/// An open connection to a syslog server. TCP and TLS use octet-counting framing (RFC 6587).
+pub struct SyslogSender {
+    connection: Connection,
+    body_format: String,
+}
+
+fn resolve(target: &SyslogTarget) -> Result<SocketAddr, String> {
+    (target.host.as_str(), target.port)
+        .to_socket_addrs()
+        .map_err(|error| format!("Failed to resolve {}: {error}", target.host))?
+        .next()
+        .ok_or_else(|| format!("No address found for {}", target.host))
+}
+
+fn connect_tcp(target: &SyslogTarget, timeout: Duration) -> Result<TcpStream, String> {
+    let address = resolve(target)?;
+    let stream = TcpStream::connect_timeout(&address, timeout)
+        .map_err(|error| format!("Failed to connect to syslog server: {error}"))?;
+    stream
+        .set_write_timeout(Some(timeout))
+        .map_err(|error| format!("Failed to configure syslog connection: {error}"))?;
+    Ok(stream)
+}
+
+impl SyslogSender {
+    pub fn connect(integration: &IntegrationConfig, timeout: Duration) -> Result<Self, String> {
+        let target = parse_target(integration.endpoint.as_str())?;
+        let connection = match target.transport {
+            SyslogTransport::Udp => {
+                let address = resolve(&target)?;
+                let local = if address.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                let socket = UdpSocket::bind(local)
+                    .map_err(|error| format!("Failed to open UDP socket: {error}"))?;
+                socket
+                    .connect(address)
+                    .map_err(|error| format!("Failed to reach syslog server: {error}"))?;
+                Connection::Udp(socket)
+            }
+            SyslogTransport::Tcp => Connection::Tcp(connect_tcp(&target, timeout)?),
+            SyslogTransport::Tls => {
+                let server_name = ServerName::try_from(target.host.clone())
+                    .map_err(|error| format!("Invalid TLS server name: {error}"))?;
+                let session = ClientConnection::new(tls_config(), server_name)
+                    .map_err(|error| format!("Failed to start TLS session: {error}"))?;
+                let stream = connect_tcp(&target, timeout)?;
+                Connection::Tls(Box::new(StreamOwned::new(session, stream)))
+            }
+        };
+        Ok(Self {
+            connection,
+            body_format: integration.syslog_format.clone(),
+        })
+    }
+
+    pub fn send(&mut self, event: &NormalizedEvent) -> Result<(), String> {
+        let message = format_message(event, self.body_format.as_str());
+        let result = match &mut self.connection {
+            Connection::Udp(socket) => {
+                let mut end = message.len().min(UDP_MAX_BYTES);
+                while !message.is_char_boundary(end) {
+                    end -= 1;
+                }
+                socket.send(&message.as_bytes()[..end]).map(|_| ())
+            }
+            Connection::Tcp(stream) => write!(stream, "{} {message}", message.len()),
+            Connection::Tls(stream) => write!(stream, "{} {message}", message.len()),
+        };
+        result.map_err(|error| format!("Failed to send syslog message: {error}"))
+    }
+
+    pub fn finish(mut self) -> Result<(), String> {
+        let result = match &mut self.connection {
+            Connection::Udp(_) => Ok(()),
+            Connection::Tcp(stream) => stream.flush(),
+            Connection::Tls(stream) => {
+                stream.conn.send_close_notify();
+                stream.flush()
+            }
+        };
+        result.map_err(|error| format!("Failed to send syslog message: {error}"))
+    }
+}
+
+/// Sends a batch over a fresh connection.
+pub fn send_events(
+    integration: &IntegrationConfig,
+    events: &[NormalizedEvent],
+    timeout: Duration,
+) -> Result<(), String> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let mut sender = SyslogSender::connect(integration, timeout)?;
+    for event in events {
+        sender.send(event)?;
+    }
+    sender.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    #[test]
+    fn parses_endpoints_with_default_ports() {
+        assert_eq!(
+            parse_target("tls://logs.example.com").unwrap(),
+            SyslogTarget {
+                transport: SyslogTransport::Tls,
+                host: "logs.example.com".to_string(),
+                port: 6514,
+            }
+        );
+        let udp = parse_target("10.0.0.5:1514").unwrap();
+        assert_eq!(udp.transport, SyslogTransport::Udp);
+        assert_eq!(udp.port, 1514);
+        assert_eq!(parse_target("tcp://[::1]:601").unwrap().host, "::1");
+        assert!(parse_target("http://host").is_err());
+    }
+
+    #[test]
+    fn formats_rfc5424_message() {
+        let mut event = NormalizedEvent::new(
+            SupportedOs::Windows,
+            "Security",
+            "security",
+            "Microsoft Windows security",
+            Some(4625),
+            "warning",
+            "An account failed to log on.",
+            "host-a",
+        );
+        event.timestamp = "2024-03-01T10:00:00Z".to_string();
+        let message = format_message(&event, "rfc5424");
+        // authpriv (10) * 8 + warning (4)
+        assert!(message.starts_with(
+            "<84>1 2024-03-01T10:00:00.000000Z host-a Microsoft_Windows_security - 4625 [hermes@32473 os=\"windows\""
+        ));
+        assert!(message.ends_with("\u{feff}An account failed to log on."));
+        assert!(format_message(&event, "cef").contains("] CEF:0|Hermes|"));
+    }
+}
@@ -0,0 +1,373 @@
+use crate::alerts;
+use crate::db::save_local_events;
+use crate::diagnostics;
+use crate::log_import::parse_syslog_message;
+use crate::logs::{NormalizedEvent, SupportedOs};
+use crate::settings::{load_syslog_listener_settings, SyslogListenerSettings};
+use crate::timestamps::TimestampContext;
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io::{ErrorKind, Read};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Target ID prefix that selects the events one sender delivered, e.g. `syslog:10.0.0.1`.
+pub const TARGET_PREFIX: &str = "syslog:";
+pub const LOG_NAME: &str = "syslog";
+/// How often blocked sockets wake up to notice the listener being stopped.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+/// Received events held while the database is slow; further messages are counted as dropped.
+const MAX_PENDING_EVENTS: usize = 20_000;
+const MAX_TCP_CONNECTIONS: usize = 64;
+const MAX_MESSAGE_BYTES: usize = 64 * 1024;
+const MAX_DATAGRAM_BYTES: usize = 65_535;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyslogListenerStatus {
+    pub running: bool,
+    pub udp_address: Option<String>,
+    pub tcp_address: Option<String>,
+    pub received: u64,
+    pub dropped: u64,
+    /// Hosts that sent at least one message since the listener started.
+    pub senders: Vec<String>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Default)]
+struct Shared {
+    stop: AtomicBool,
+    connections: AtomicUsize,
+    pending: Mutex<Vec<NormalizedEvent>>,
+    status: Mutex<SyslogListenerStatus>,
+    senders: Mutex<BTreeSet<String>>,
+}
+
+struct Listener {
+    shared: Arc<Shared>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+static LISTENER: Mutex<Option<Listener>> = Mutex::new(None);
+
+/// Turns one received message into an event of the host that sent it.
+pub fn message_event(raw: &str, sender: IpAddr) -> NormalizedEvent {
+    let parsed = parse_syslog_message(raw, &TimestampContext::local());
+    let mut event = NormalizedEvent::new(
+        SupportedOs::Linux,
+        LOG_NAME,
+        "system",
+        parsed.provider.as_deref().unwrap_or(LOG_NAME),
+        parsed.event_id,
+        parsed.severity.unwrap_or("information"),
+        parsed.message.as_str(),
+        sender.to_string().as_str(),
+    );
+    event.timestamp = parsed.timestamp.unwrap_or_else(Utc::now).to_rfc3339();
+    event.assign_stable_id();
+    event
+}
+
+/// Takes the next complete message off a TCP stream buffer. Senders use either RFC 6587
+/// octet counting (`LEN SP MSG`) or newline-terminated messages, so both are accepted.
+fn next_frame(buffer: &mut Vec<u8>) -> Option<Vec<u8>> {
+    let digits = buffer
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit())
+        .count();
+    if digits > 0 && buffer.get(digits) == Some(&b' ') {
+        let length = std::str::from_utf8(&buffer[..digits])
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|length| *length <= MAX_MESSAGE_BYTES);
+        if let Some(length) = length {
+            if buffer.len() < digits + 1 + length {
+                return None;
+            }
+            let frame = buffer[digits + 1..digits + 1 + length].to_vec();
+            buffer.drain(..digits + 1 + length);
+            return Some(frame);
+        }
+    } else if digits == buffer.len() && digits < 10 {
+        // Possibly the start of an octet count; wait for more bytes.
+        return None;
+    }
+    match buffer.iter().position(|byte| *byte == b'\n') {
+        Some(end) => {
+            let frame = buffer[..end].to_vec();
+            buffer.drain(..=end);
+            Some(frame)
+        }
+        None if buffer.len() > MAX_MESSAGE_BYTES => Some(std::mem::take(buffer)),
+        None => None,
+    }
+}
+
+fn record_error(shared: &Shared, error: String) {
+    diagnostics::warn("syslog", error.clone());
+    if let Ok(mut status) = shared.status.lock() {
+        status.last_error = Some(error);
+    }
+}
+
+fn receive(shared: &Shared, raw: &[u8], sender: IpAddr) {
+    let text = String::from_utf8_lossy(raw);
+    if text.trim().is_empty() {
+        return;
+    }
+    let event = message_event(text.as_ref(), sender);
+    let Ok(mut pending) = shared.pending.lock() else {
+        return;
+    };
+    let Ok(mut status) = shared.status.lock() else {
+        return;
+    };
+    status.received += 1;
+    if pending.len() >= MAX_PENDING_EVENTS {
+        status.dropped += 1;
+        return;
+    }
+    pending.push(event);
+    if let Ok(mut senders) = shared.senders.lock() {
+        senders.insert(sender.to_string());
+    }
+}
+
+fn is_timeout(error: &std::io::Error) -> bool {
+    matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+}
+
+fn run_udp(socket: UdpSocket, shared: Arc<Shared>) {
+    let mut buffer = vec![0u8; MAX_DATAGRAM_BYTES];
+    while !shared.stop.load(Ordering::Relaxed) {
+        match socket.recv_from(&mut buffer) {
+            Ok((length, peer)) => receive(&shared, &buffer[..length], peer.ip()),
+            Err(error) if is_timeout(&error) => {}
+            Err(error) => {
+                record_error(&shared, format!("Syslog UDP receive failed: {error}"));
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+fn run_connection(mut stream: TcpStream, peer: IpAddr, shared: Arc<Shared>) {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    while !shared.stop.load(Ordering::Relaxed) {
+        match stream.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(read) => {
+                buffer.extend_from_slice(&chunk[..read]);
+                while let Some(frame) = next_frame(&mut buffer) {
+                    receive(&shared, frame.as_slice(), peer);
+                }
+            }
+            Err(error) if is_timeout(&error) => {}
+            Err(_) => break,
+        }
+    }
+    // A sender may close the connection without terminating its last message.
+    receive(&shared, buffer.as_slice(), peer);
+    shared.connections.fetch_sub(1, Ordering::Relaxed);
+}
+
+fn run_tcp(listener: TcpListener, shared: Arc<Shared>) {
+    while !shared.stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                if shared.connections.load(Ordering::Relaxed) >= MAX_TCP_CONNECTIONS {
+                    record_error(
+                        &shared,
+                        format!("Refused syslog connection from {peer}: too many connections."),
+                    );
+                    continue;
+                }
+                if stream.set_nonblocking(false).is_err()
+                    || stream.set_read_timeout(Some(POLL_INTERVAL)).is_err()
+                {
+                    continue;
+                }
+                shared.connections.fetch_add(1, Ordering::Relaxed);
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || run_connection(stream, peer.ip(), shared));
+            }
+            Err(error) if is_timeout(&error) => thread::sleep(POLL_INTERVAL),
+            Err(error) => {
+                record_error(&shared, format!("Syslog TCP accept failed: {error}"));
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+fn flush(shared: &Shared) {
+    let events = match shared.pending.lock() {
+        Ok(mut pending) => std::mem::take(&mut *pending),
+        Err(_) => return,
+    };
+    if events.is_empty() {
+        return;
+    }
+    if let Err(error) = save_local_events(events.as_slice()) {
+        record_error(shared, format!("Failed to save syslog events: {error}"));
+        return;
+    }
+    alerts::evaluate_events(events.as_slice());
+}
+
+fn run_flusher(shared: Arc<Shared>) {
+    let mut last_flush = Instant::now();
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let stopping = shared.stop.load(Ordering::Relaxed);
+        if stopping || last_flush.elapsed() >= FLUSH_INTERVAL {
+            flush(&shared);
+            last_flush = Instant::now();
+        }
+        if stopping {
+            return;
+        }
+    }
+}
+
+/// Starts receiving syslog on the configured address, replacing a running listener. Received
+/// events are saved every few seconds under the sender's address and checked against alerts.
+pub fn start(settings: &SyslogListenerSettings) -> Result<SyslogListenerStatus, String> {
+    stop();
+    let ip = settings
+        .bind_address
+        .parse::<IpAddr>()
+        .map_err(|_| format!("Invalid listen address '{}'.", settings.bind_address))?;
+    let address = SocketAddr::new(ip, settings.port);
+    let protocol = settings.protocol.as_str();
+    let mut status = SyslogListenerStatus {
+        running: true,
+        ..SyslogListenerStatus::default()
+    };
+
+    let udp = if matches!(protocol, "udp" | "both") {
+        let socket = UdpSocket::bind(address)
+            .map_err(|error| format!("Failed to listen for syslog on udp://{address}: {error}"))?;
+        socket
+            .set_read_timeout(Some(POLL_INTERVAL))
+            .map_err(|error| format!("Failed to configure syslog UDP socket: {error}"))?;
+        status.udp_address = socket.local_addr().ok().map(|bound| bound.to_string());
+        Some(socket)
+    } else {
+        None
+    };
+    let tcp = if matches!(protocol, "tcp" | "both") {
+        let listener = TcpListener::bind(address)
+            .map_err(|error| format!("Failed to listen for syslog on tcp://{address}: {error}"))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|error| format!("Failed to configure syslog TCP listener: {error}"))?;
+        status.tcp_address = listener.local_addr().ok().map(|bound| bound.to_string());
+        Some(listener)
+    } else {
+        None
+    };
+
+    let shared = Arc::new(Shared {
+        status: Mutex::new(status.clone()),
+        ..Shared::default()
+    });
+    let mut threads = Vec::new();
+    if let Some(socket) = udp {
+        let shared = Arc::clone(&shared);
+        threads.push(thread::spawn(move || run_udp(socket, shared)));
+    }
+    if let Some(listener) = tcp {
+        let shared = Arc::clone(&shared);
+        threads.push(thread::spawn(move || run_tcp(listener, shared)));
+    }
+    let flusher = Arc::clone(&shared);
+    threads.push(thread::spawn(move || run_flusher(flusher)));
+
+    diagnostics::info(
+        "syslog",
+        format!("Syslog listener started on {address} ({protocol})."),
+    );
+    if let Ok(mut listener) = LISTENER.lock() {
+        *listener = Some(Listener { shared, threads });
+    }
+    Ok(status)
+}
+
+/// Stops the listener, saving whatever it had received.
+pub fn stop() {
+    let listener = LISTENER
+        .lock()
+        .ok()
+        .and_then(|mut listener| listener.take());
+    if let Some(listener) = listener {
+        listener.shared.stop.store(true, Ordering::Relaxed);
+        for thread in listener.threads {
+            let _ = thread.join();
+        }
+        diagnostics::info("syslog", "Syslog listener stopped.");
+    }
+}
+
+pub fn status() -> SyslogListenerStatus {
+    let Ok(listener) = LISTENER.lock() else {
+        return SyslogListenerStatus::default();
+    };
+    let Some(listener) = listener.as_ref() else {
+        return SyslogListenerStatus::default();
+    };
+    let mut status = listener
+        .shared
+        .status
+        .lock()
+        .map(|status| status.clone())
+        .unwrap_or_default();
+    status.senders = listener
+        .shared
+        .senders
+        .lock()
+        .map(|senders| senders.iter().cloned().collect())
+        .unwrap_or_default();
+    status
+}
+
+/// Starts the listener at launch when the user left it enabled.
+pub fn start_if_enabled() {
+    let settings = load_syslog_listener_settings();
+    if !settings.enabled {
+        return;
+    }
+    if let Err(error) = start(&settings) {
+        diagnostics::warn("syslog", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_octet_counted_and_newline_messages() {
+        let mut buffer = b"12 <13>1 - - x 6 <13>hiplain text\n9".to_vec();
+        assert_eq!(next_frame(&mut buffer).unwrap(), b"<13>1 - - x ");
+        assert_eq!(next_frame(&mut buffer).unwrap(), b"<13>hi");
+        assert_eq!(next_frame(&mut buffer).unwrap(), b"plain text");
+        assert_eq!(next_frame(&mut buffer), None);
+        assert_eq!(buffer, b"9");
+
+        let sender: IpAddr = "192.168.1.20".parse().unwrap();
+        let event = message_event("<11>Mar  1 10:00:00 nas smartd[42]: disk failing", sender);
+        assert_eq!(event.source_host, "192.168.1.20");
+        assert_eq!(event.provider, "smartd");
+        assert_eq!(event.severity, "error");
+        assert_eq!(event.message, "disk failing");
+        assert!(!event.imported);
+    }
+}
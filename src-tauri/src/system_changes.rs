@@ -0,0 +1,401 @@
+//! Service and driver change detection: Windows service installs (7045, 4697), driver loads
+//! (7045 kernel drivers, Sysmon 6, Linux out-of-tree modules, macOS kext loads), launchd
+//! daemon registrations and newly enabled systemd units, with the installed name and path
+//! pulled out of each record.
+
+use crate::crash::CrashRecord;
+use crate::db;
+use crate::logs::NormalizedEvent;
+use crate::nl_query::EventQueryFilter;
+use crate::sigma::raw_fields;
+use crate::timestamps::parse_timestamp;
+use chrono::Duration;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+pub const DEFAULT_LIMIT: usize = 500;
+/// How far before a crash the pre-analysis looks for changes.
+pub const CRASH_LOOKBACK_HOURS: i64 = 24;
+/// Changes listed in a crash analysis prompt; the newest are kept.
+pub const MAX_PROMPT_CHANGES: usize = 15;
+
+static MESSAGE_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*(Service Name|Service File Name|Service Type|Service Start Type|Service Account):\s*(.+?)\s*$")
+        .expect("service field pattern is valid")
+});
+static SYSTEMD_SYMLINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"Created symlink (\S+/([^/\s]+\.(?:service|timer|socket|path|mount))) (?:→|->) (\S+?)\.?$",
+    )
+    .expect("systemd symlink pattern is valid")
+});
+static SYSTEMCTL_ENABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"COMMAND=\S*systemctl (?:\S+ )*?(?:enable|link)(?: --now)? (\S+)")
+        .expect("systemctl pattern is valid")
+});
+static BUNDLE_ID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b((?:system|gui/\d+|user/\d+)/)?([A-Za-z0-9-]+(?:\.[A-Za-z0-9_-]+){2,})\b")
+        .expect("bundle id pattern is valid")
+});
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemChange {
+    pub timestamp: String,
+    /// One of service, driver, launchd or systemd.
+    pub kind: String,
+    /// Service, driver, launchd label or unit name.
+    pub name: String,
+    /// Image path, driver file or unit file, when the record names one.
+    pub path: Option<String>,
+    pub start_type: Option<String>,
+    pub account: Option<String>,
+    /// Signature status for Sysmon driver loads.
+    pub signature: Option<String>,
+    pub source_host: String,
+    pub provider: String,
+    pub event_id: Option<u32>,
+    /// Id of the stored event, for drill-down.
+    pub reference_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemChangesReport {
+    pub window_start: String,
+    pub window_end: String,
+    pub counts: BTreeMap<String, usize>,
+    /// Newest first, capped at the requested limit.
+    pub changes: Vec<SystemChange>,
+    pub total_changes: usize,
+}
+
+fn field(fields: &BTreeMap<String, String>, names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| {
+        fields
+            .get(*name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty() && *value != "-")
+            .map(str::to_string)
+    })
+}
+
+fn change(event: &NormalizedEvent, kind: &str, name: String) -> SystemChange {
+    SystemChange {
+        timestamp: event.timestamp.clone(),
+        kind: kind.to_string(),
+        name,
+        source_host: event.source_host.clone(),
+        provider: event.provider.clone(),
+        event_id: event.event_id,
+        reference_id: event.id.clone(),
+        ..SystemChange::default()
+    }
+}
+
+/// 7045 from the Service Control Manager and 4697 from the Security log. Both record drivers
+/// as services, told apart by the service type.
+fn windows_service_install(event: &NormalizedEvent, provider: &str) -> Option<SystemChange> {
+    let is_scm = provider.contains("service control manager") && event.event_id == Some(7045);
+    let is_audit = provider.contains("security-auditing") && event.event_id == Some(4697);
+    if !is_scm && !is_audit {
+        return None;
+    }
+    let mut fields = raw_fields(event.raw_payload.as_deref().unwrap_or_default());
+    for captures in MESSAGE_FIELD.captures_iter(event.message.as_str()) {
+        let name = match &captures[1] {
+            "Service Name" => "ServiceName",
+            "Service File Name" => "ImagePath",
+            "Service Type" => "ServiceType",
+            "Service Start Type" => "StartType",
+            _ => "AccountName",
+        };
+        fields
+            .entry(name.to_string())
+            .or_insert_with(|| captures[2].to_string());
+    }
+    let name = field(&fields, &["ServiceName"])?;
+    let service_type = field(&fields, &["ServiceType"])
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    // 4697 records the type as a number: 0x1 kernel driver, 0x2 file system driver.
+    let driver = service_type.contains("driver") || matches!(service_type.as_str(), "0x1" | "0x2");
+    let mut change = change(event, if driver { "driver" } else { "service" }, name);
+    change.path = field(&fields, &["ImagePath", "ServiceFileName"]);
+    change.start_type = field(&fields, &["StartType", "ServiceStartType"]);
+    change.account = field(&fields, &["AccountName", "ServiceAccount"]);
+    Some(change)
+}
+
+fn sysmon_driver_load(event: &NormalizedEvent, provider: &str) -> Option<SystemChange> {
+    if !provider.contains("sysmon") || event.event_id != Some(6) {
+        return None;
+    }
+    let mut fields = raw_fields(event.raw_payload.as_deref().unwrap_or_default());
+    if let Some(metadata) = event.metadata.as_ref() {
+        for (name, value) in metadata {
+            fields.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    let path = field(&fields, &["ImageLoaded"])?;
+    let name = path
+        .rsplit(['\\', '/'])
+        .next()
+        .unwrap_or(path.as_str())
+        .to_string();
+    let mut change = change(event, "driver", name);
+    change.path = Some(path);
+    change.signature = match (
+        field(&fields, &["Signature"]),
+        field(&fields, &["SignatureStatus"]),
+    ) {
+        (Some(signer), Some(status)) => Some(format!("{signer} ({status})")),
+        (signer, status) => signer.or(status),
+    };
+    Some(change)
+}
+
+fn unix_change(event: &NormalizedEvent, provider: &str) -> Option<SystemChange> {
+    let message = event.message.trim();
+    let lower = message.to_ascii_lowercase();
+    if provider == "kernel" && lower.contains("loading out-of-tree module") {
+        let name = message.split(':').next().unwrap_or_default().trim();
+        return (!name.is_empty() && !name.contains(' '))
+            .then(|| change(event, "driver", name.to_string()));
+    }
+    if (provider.contains("kernelmanagerd") || provider.contains("kextd")) && lower.contains("load")
+    {
+        let name = BUNDLE_ID.captures(message)?[2].to_string();
+        return Some(change(event, "driver", name));
+    }
+    if (provider.contains("launchd") || provider.contains("backgroundtaskmanagement"))
+        && (lower.contains("bootstrap")
+            || lower.contains("service added")
+            || lower.contains("registered"))
+    {
+        let name = BUNDLE_ID.captures(message)?[2].to_string();
+        return Some(change(event, "launchd", name));
+    }
+    if provider.starts_with("systemd") || provider == "systemctl" {
+        let captures = SYSTEMD_SYMLINK.captures(message)?;
+        let mut change = change(event, "systemd", captures[2].to_string());
+        change.path = Some(captures[3].to_string());
+        return Some(change);
+    }
+    if provider == "sudo" {
+        let unit = SYSTEMCTL_ENABLE.captures(message)?[1].to_string();
+        return Some(change(event, "systemd", unit));
+    }
+    None
+}
+
+/// The service, driver, launchd or systemd change `event` records, if it records one.
+pub fn detect_change(event: &NormalizedEvent) -> Option<SystemChange> {
+    let provider = event.provider.to_ascii_lowercase();
+    windows_service_install(event, provider.as_str())
+        .or_else(|| sysmon_driver_load(event, provider.as_str()))
+        .or_else(|| unix_change(event, provider.as_str()))
+}
+
+fn passes(start: &str, end: &str) -> Vec<EventQueryFilter> {
+    let window = EventQueryFilter {
+        start: Some(start.to_string()),
+        end: Some(end.to_string()),
+        ..EventQueryFilter::default()
+    };
+    let with = |event_id: Option<u32>, providers: &[&str], text: Option<&str>| EventQueryFilter {
+        event_id,
+        providers: providers
+            .iter()
+            .map(|provider| provider.to_string())
+            .collect(),
+        text: text.map(str::to_string),
+        ..window.clone()
+    };
+    vec![
+        with(Some(7045), &[], None),
+        with(Some(4697), &[], None),
+        with(Some(6), &["Microsoft-Windows-Sysmon"], None),
+        with(None, &["kernel"], Some("out-of-tree module")),
+        with(
+            None,
+            &[
+                "kernelmanagerd",
+                "kextd",
+                "launchd",
+                "backgroundtaskmanagementd",
+            ],
+            None,
+        ),
+        with(None, &["systemd", "systemctl"], Some("Created symlink")),
+        with(None, &["sudo"], Some("systemctl")),
+    ]
+}
+
+/// Every change recorded for `host` between `start` and `end` (RFC3339), oldest first.
+pub fn collect_changes(
+    start: &str,
+    end: &str,
+    host: Option<&str>,
+) -> Result<Vec<SystemChange>, String> {
+    let mut changes = Vec::new();
+    for filter in &passes(start, end) {
+        db::for_each_event_with_raw(filter, host, |event| {
+            changes.extend(detect_change(&event));
+            Ok(())
+        })?;
+    }
+    changes.sort_by_cached_key(|change| parse_timestamp(change.timestamp.as_str()));
+    Ok(changes)
+}
+
+/// Service and driver changes for `host` between `start` and `end`, newest `limit` first.
+pub fn system_changes(
+    start: &str,
+    end: &str,
+    host: Option<&str>,
+    limit: usize,
+) -> Result<SystemChangesReport, String> {
+    let changes = collect_changes(start, end, host)?;
+    let mut counts = BTreeMap::new();
+    for change in &changes {
+        *counts.entry(change.kind.clone()).or_insert(0) += 1;
+    }
+    Ok(SystemChangesReport {
+        window_start: start.to_string(),
+        window_end: end.to_string(),
+        counts,
+        total_changes: changes.len(),
+        changes: changes.into_iter().rev().take(limit).collect(),
+    })
+}
+
+/// Changes on the crash host in the day before `crash`, newest first, for crash analysis.
+pub fn changes_before_crash(crash: &CrashRecord) -> Result<Vec<SystemChange>, String> {
+    let crashed_at = parse_timestamp(crash.timestamp.as_str())
+        .ok_or_else(|| "Crash timestamp is not a valid time.".to_string())?;
+    let start = crashed_at - Duration::hours(CRASH_LOOKBACK_HOURS);
+    let mut changes = collect_changes(
+        start.to_rfc3339().as_str(),
+        crashed_at.to_rfc3339().as_str(),
+        Some(crash.source_host.as_str()),
+    )?;
+    changes.reverse();
+    changes.truncate(MAX_PROMPT_CHANGES);
+    Ok(changes)
+}
+
+/// One prompt line per change, e.g. `2026-03-01T09:00:00Z driver nvlddmkm (C:\...\x.sys)`.
+pub fn describe(change: &SystemChange) -> String {
+    let mut line = format!("{} {} {}", change.timestamp, change.kind, change.name);
+    if let Some(path) = change.path.as_deref() {
+        line.push_str(format!(" ({path})").as_str());
+    }
+    if let Some(start_type) = change.start_type.as_deref() {
+        line.push_str(format!(", start {start_type}").as_str());
+    }
+    if let Some(signature) = change.signature.as_deref() {
+        line.push_str(format!(", signed by {signature}").as_str());
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn event(
+        os: SupportedOs,
+        provider: &str,
+        event_id: Option<u32>,
+        message: &str,
+    ) -> NormalizedEvent {
+        NormalizedEvent::new(
+            os,
+            "System",
+            "system",
+            provider,
+            event_id,
+            "information",
+            message,
+            "localhost",
+        )
+    }
+
+    #[test]
+    fn detects_windows_service_and_driver_installs() {
+        let service = event(
+            SupportedOs::Windows,
+            "Service Control Manager",
+            Some(7045),
+            "A service was installed in the system.\n\nService Name:  Remote Helper\nService File Name:  C:\\ProgramData\\rh.exe\nService Type:  user mode service\nService Start Type:  auto start\nService Account:  LocalSystem",
+        );
+        let change = detect_change(&service).unwrap();
+        assert_eq!(change.kind, "service");
+        assert_eq!(change.name, "Remote Helper");
+        assert_eq!(change.path.as_deref(), Some("C:\\ProgramData\\rh.exe"));
+        assert_eq!(change.start_type.as_deref(), Some("auto start"));
+
+        let mut driver = event(
+            SupportedOs::Windows,
+            "Microsoft-Windows-Security-Auditing",
+            Some(4697),
+            "A service was installed in the system.",
+        );
+        driver.raw_payload = Some(
+            "<Event><EventData><Data Name=\"ServiceName\">evil</Data><Data Name=\"ServiceFileName\">C:\\Windows\\evil.sys</Data><Data Name=\"ServiceType\">0x1</Data></EventData></Event>"
+                .to_string(),
+        );
+        assert_eq!(detect_change(&driver).unwrap().kind, "driver");
+
+        let running = event(
+            SupportedOs::Windows,
+            "Service Control Manager",
+            Some(7036),
+            "The Remote Helper service entered the running state.",
+        );
+        assert!(detect_change(&running).is_none());
+    }
+
+    #[test]
+    fn detects_unix_units_modules_and_daemons() {
+        let unit = event(
+            SupportedOs::Linux,
+            "systemd",
+            None,
+            "Created symlink /etc/systemd/system/multi-user.target.wants/miner.service → /etc/systemd/system/miner.service.",
+        );
+        let change = detect_change(&unit).unwrap();
+        assert_eq!(
+            (change.kind.as_str(), change.name.as_str()),
+            ("systemd", "miner.service")
+        );
+        assert_eq!(
+            change.path.as_deref(),
+            Some("/etc/systemd/system/miner.service")
+        );
+
+        let module = event(
+            SupportedOs::Linux,
+            "kernel",
+            None,
+            "nvidia: loading out-of-tree module taints kernel.",
+        );
+        assert_eq!(detect_change(&module).unwrap().name, "nvidia");
+
+        let daemon = event(
+            SupportedOs::Macos,
+            "launchd",
+            None,
+            "Service added: system/com.example.updater",
+        );
+        let change = detect_change(&daemon).unwrap();
+        assert_eq!(
+            (change.kind.as_str(), change.name.as_str()),
+            ("launchd", "com.example.updater")
+        );
+    }
+}
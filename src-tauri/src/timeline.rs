@@ -0,0 +1,289 @@
+use crate::anomaly::AnomalyWindow;
+use crate::boot::is_update_event;
+use crate::crash::CrashRecord;
+use crate::db::EventBookmark;
+use crate::logs::NormalizedEvent;
+use crate::power::PowerIncident;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const MAX_DETAIL_CHARS: usize = 240;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineEntry {
+    pub timestamp: String,
+    /// One of crash, finding, change, power or bookmark.
+    pub kind: String,
+    pub severity: String,
+    pub title: String,
+    pub detail: String,
+    /// Id of the crash, event or incident the entry came from, for drill-down.
+    pub reference_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timeline {
+    pub start: String,
+    pub end: String,
+    pub source_host: String,
+    pub counts: BTreeMap<String, usize>,
+    pub entries: Vec<TimelineEntry>,
+}
+
+/// Everything that can appear on a timeline, already limited to the requested range.
+pub struct TimelineSources<'a> {
+    pub events: &'a [NormalizedEvent],
+    pub crashes: &'a [CrashRecord],
+    pub anomalies: &'a [AnomalyWindow],
+    pub power_incidents: &'a [PowerIncident],
+    pub bookmarks: &'a [(NormalizedEvent, EventBookmark)],
+}
+
+fn first_line(message: &str) -> String {
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= MAX_DETAIL_CHARS {
+        line.to_string()
+    } else {
+        let truncated: String = line.chars().take(MAX_DETAIL_CHARS).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Service and driver installs recorded by the Service Control Manager.
+fn is_service_install(event: &NormalizedEvent) -> bool {
+    event.event_id == Some(7045)
+        && event
+            .provider
+            .to_ascii_lowercase()
+            .contains("service control manager")
+}
+
+fn entry(
+    timestamp: &str,
+    kind: &str,
+    severity: &str,
+    title: String,
+    detail: String,
+    reference_id: Option<String>,
+) -> TimelineEntry {
+    TimelineEntry {
+        timestamp: timestamp.to_string(),
+        kind: kind.to_string(),
+        severity: severity.to_string(),
+        title,
+        detail,
+        reference_id,
+    }
+}
+
+fn crash_severity(crash: &CrashRecord) -> &'static str {
+    let crash_type = crash.crash_type.to_ascii_lowercase();
+    if crash_type.contains("blue screen")
+        || crash_type.contains("bugcheck")
+        || crash_type.contains("kernel")
+    {
+        "critical"
+    } else {
+        "error"
+    }
+}
+
+/// Merges crashes, anomaly findings, installs/updates, power incidents and bookmarked events into
+/// one list ordered by time. Ties keep the order above so a crash sorts before what it caused.
+pub fn build_timeline(
+    start: &str,
+    end: &str,
+    host: &str,
+    sources: TimelineSources<'_>,
+) -> Timeline {
+    let mut entries = Vec::new();
+
+    for crash in sources.crashes {
+        let mut title = format!("{}: {}", crash.crash_type, crash.summary);
+        if let Some(component) = crash.suspected_component.as_deref() {
+            title.push_str(format!(" ({component})").as_str());
+        }
+        entries.push(entry(
+            crash.timestamp.as_str(),
+            "crash",
+            crash_severity(crash),
+            title,
+            crash.code.clone().unwrap_or_default(),
+            Some(crash.id.clone()),
+        ));
+    }
+
+    for anomaly in sources.anomalies {
+        entries.push(entry(
+            anomaly.start.as_str(),
+            "finding",
+            "warning",
+            anomaly.summary.clone(),
+            format!(
+                "{} errors in {} between {} and {}",
+                anomaly.event_count, anomaly.log_name, anomaly.start, anomaly.end
+            ),
+            None,
+        ));
+    }
+
+    for event in sources
+        .events
+        .iter()
+        .filter(|event| is_update_event(event) || is_service_install(event))
+    {
+        let title = if is_service_install(event) {
+            "Service or driver installed".to_string()
+        } else {
+            format!("Update activity from {}", event.provider)
+        };
+        entries.push(entry(
+            event.timestamp.as_str(),
+            "change",
+            "information",
+            title,
+            first_line(event.message.as_str()),
+            Some(event.id.clone()),
+        ));
+    }
+
+    for incident in sources.power_incidents {
+        let severity = if incident.classification == "power_loss" {
+            "critical"
+        } else {
+            "warning"
+        };
+        entries.push(entry(
+            incident.timestamp.as_str(),
+            "power",
+            severity,
+            incident.summary.clone(),
+            incident.evidence.join("; "),
+            Some(incident.event_id.clone()),
+        ));
+    }
+
+    for (event, bookmark) in sources.bookmarks {
+        let title = bookmark
+            .note
+            .clone()
+            .filter(|note| !note.trim().is_empty())
+            .unwrap_or_else(|| format!("{} event {}", event.provider, event.event_id.unwrap_or(0)));
+        entries.push(entry(
+            event.timestamp.as_str(),
+            "bookmark",
+            event.severity.as_str(),
+            title,
+            first_line(event.message.as_str()),
+            Some(event.id.clone()),
+        ));
+    }
+
+    entries.sort_by(|left, right| left.timestamp.cmp(&right.timestamp));
+    let mut counts = BTreeMap::new();
+    for item in &entries {
+        *counts.entry(item.kind.clone()).or_insert(0) += 1;
+    }
+
+    Timeline {
+        start: start.to_string(),
+        end: end.to_string(),
+        source_host: host.to_string(),
+        counts,
+        entries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        id: &str,
+        timestamp: &str,
+        provider: &str,
+        event_id: u32,
+        message: &str,
+    ) -> NormalizedEvent {
+        NormalizedEvent {
+            id: id.to_string(),
+            timestamp: timestamp.to_string(),
+            os: "windows".to_string(),
+            log_name: "System".to_string(),
+            category: "system".to_string(),
+            provider: provider.to_string(),
+            event_id: Some(event_id),
+            severity: "information".to_string(),
+            message: message.to_string(),
+            source_host: "localhost".to_string(),
+            ..NormalizedEvent::default()
+        }
+    }
+
+    #[test]
+    fn merges_sources_in_time_order() {
+        let events = vec![
+            event(
+                "svc",
+                "2026-03-01T09:00:00Z",
+                "Service Control Manager",
+                7045,
+                "A service was installed in the system.\nService Name: FooDriver",
+            ),
+            event("noise", "2026-03-01T09:30:00Z", "Tcpip", 4201, "Network up"),
+        ];
+        let mut crash = CrashRecord::new(
+            "windows",
+            "WER",
+            "Blue Screen",
+            Some("0x0000009F"),
+            "System bugcheck",
+            Some("foo.sys"),
+            None,
+            "localhost",
+            false,
+        );
+        crash.timestamp = "2026-03-01T10:00:00Z".to_string();
+        let bookmarks = vec![(
+            event("mark", "2026-03-01T09:45:00Z", "disk", 153, "IO retried"),
+            EventBookmark {
+                event_id: "mark".to_string(),
+                note: Some("First disk retry".to_string()),
+                created_at: "2026-03-02T08:00:00Z".to_string(),
+            },
+        )];
+
+        let timeline = build_timeline(
+            "2026-03-01T00:00:00Z",
+            "2026-03-01T23:59:59Z",
+            "localhost",
+            TimelineSources {
+                events: events.as_slice(),
+                crashes: std::slice::from_ref(&crash),
+                anomalies: &[],
+                power_incidents: &[],
+                bookmarks: bookmarks.as_slice(),
+            },
+        );
+
+        let kinds: Vec<&str> = timeline
+            .entries
+            .iter()
+            .map(|item| item.kind.as_str())
+            .collect();
+        assert_eq!(kinds, vec!["change", "bookmark", "crash"]);
+        assert_eq!(
+            timeline.entries[0].detail,
+            "A service was installed in the system."
+        );
+        assert_eq!(timeline.entries[1].title, "First disk retry");
+        assert_eq!(timeline.entries[2].severity, "critical");
+        assert_eq!(timeline.counts.get("crash"), Some(&1));
+    }
+}
@@ -0,0 +1,417 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Seconds between 1601-01-01 (the FILETIME epoch) and the Unix epoch.
+const FILETIME_UNIX_OFFSET_SECS: i64 = 11_644_473_600;
+
+/// Formats that carry their own UTC offset.
+const OFFSET_FORMATS: [&str; 3] = [
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%d/%b/%Y:%H:%M:%S %z",
+];
+
+/// Formats without an offset; they are read in the caller's default offset.
+const NAIVE_FORMATS: [&str; 7] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%d/%b/%Y:%H:%M:%S",
+    "%b %d %Y %H:%M:%S",
+    "%a %b %e %H:%M:%S %Y",
+    "%Y%m%dT%H%M%S",
+];
+
+/// How to read a timestamp column. `Auto` tries every supported form in a fixed order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimestampHint {
+    Auto,
+    Rfc3339,
+    Syslog,
+    FileTime,
+    EpochSeconds,
+    EpochMillis,
+    EpochMicros,
+    /// `MM/DD/YYYY HH:MM:SS` with optional AM/PM.
+    UsLocale,
+    /// `DD/MM/YYYY HH:MM:SS` or `DD.MM.YYYY HH:MM:SS`.
+    EuLocale,
+    /// A chrono `strftime` pattern supplied by the user.
+    Custom(String),
+}
+
+impl TimestampHint {
+    /// Parses a hint name such as `epoch_ms`; strings containing `%` are custom patterns.
+    pub fn from_name(value: &str) -> Result<Self, String> {
+        let trimmed = value.trim();
+        if trimmed.contains('%') {
+            return Ok(Self::Custom(trimmed.to_string()));
+        }
+        match trimmed.to_ascii_lowercase().as_str() {
+            "" | "auto" => Ok(Self::Auto),
+            "rfc3339" | "iso8601" => Ok(Self::Rfc3339),
+            "syslog" => Ok(Self::Syslog),
+            "filetime" => Ok(Self::FileTime),
+            "epoch" | "epoch_s" | "unix" => Ok(Self::EpochSeconds),
+            "epoch_ms" => Ok(Self::EpochMillis),
+            "epoch_us" => Ok(Self::EpochMicros),
+            "us" => Ok(Self::UsLocale),
+            "eu" => Ok(Self::EuLocale),
+            other => Err(format!("Unknown timestamp format '{other}'.")),
+        }
+    }
+}
+
+/// Context for values that do not say everything about themselves: naive times are read in
+/// `default_offset`, and syslog times (which have no year) take the year that puts them closest
+/// before `reference`.
+#[derive(Debug, Clone, Copy)]
+pub struct TimestampContext {
+    pub default_offset: FixedOffset,
+    pub reference: DateTime<Utc>,
+}
+
+impl TimestampContext {
+    pub fn utc() -> Self {
+        Self {
+            default_offset: FixedOffset::east_opt(0).expect("zero offset"),
+            reference: Utc::now(),
+        }
+    }
+
+    pub fn local() -> Self {
+        Self {
+            default_offset: *chrono::Local::now().offset(),
+            reference: Utc::now(),
+        }
+    }
+}
+
+fn from_naive(value: NaiveDateTime, offset: FixedOffset) -> Option<DateTime<Utc>> {
+    offset
+        .from_local_datetime(&value)
+        .single()
+        .map(|parsed| parsed.with_timezone(&Utc))
+}
+
+fn parse_rfc3339(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .or_else(|| {
+            OFFSET_FORMATS
+                .iter()
+                .find_map(|format| DateTime::parse_from_str(value, format).ok())
+        })
+        .map(|parsed| parsed.with_timezone(&Utc))
+}
+
+pub fn from_epoch_seconds(value: i64) -> Option<DateTime<Utc>> {
+    DateTime::<Utc>::from_timestamp(value, 0)
+}
+
+pub fn from_epoch_micros(value: i64) -> Option<DateTime<Utc>> {
+    DateTime::<Utc>::from_timestamp_micros(value)
+}
+
+/// Converts a Windows FILETIME (100 ns intervals since 1601-01-01 UTC).
+pub fn from_filetime(value: i64) -> Option<DateTime<Utc>> {
+    let seconds = value.div_euclid(10_000_000) - FILETIME_UNIX_OFFSET_SECS;
+    let nanos = (value.rem_euclid(10_000_000) * 100) as u32;
+    DateTime::<Utc>::from_timestamp(seconds, nanos)
+}
+
+/// Reads `seconds[.fraction]` since the Unix epoch.
+fn parse_epoch_seconds(value: &str) -> Option<DateTime<Utc>> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if !fraction.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let seconds = whole.parse::<i64>().ok()?;
+    let nanos = format!("{fraction:0<9}")[..9].parse::<u32>().unwrap_or(0);
+    DateTime::<Utc>::from_timestamp(seconds, nanos)
+}
+
+/// Reads an all-digit value by magnitude: seconds, milliseconds, microseconds, FILETIME or
+/// nanoseconds.
+fn parse_numeric(value: &str) -> Option<DateTime<Utc>> {
+    let whole = value.split_once('.').map_or(value, |(whole, _)| whole);
+    if whole.is_empty() || !whole.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let number: i64 = whole.parse().ok()?;
+    match whole.len() {
+        9 | 10 => parse_epoch_seconds(value),
+        13 => DateTime::<Utc>::from_timestamp_millis(number),
+        16 => from_epoch_micros(number),
+        17 | 18 => from_filetime(number),
+        19 => Some(DateTime::<Utc>::from_timestamp_nanos(number)),
+        _ => None,
+    }
+}
+
+/// Parses `Mar  1 10:00:00` (optionally with fractional seconds), choosing the year so the
+/// result is not more than a day after the reference time.
+fn parse_syslog(value: &str, context: &TimestampContext) -> Option<DateTime<Utc>> {
+    let normalized = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    let reference_year = context
+        .reference
+        .with_timezone(&context.default_offset)
+        .year();
+    for year in [reference_year, reference_year - 1] {
+        let candidate = format!("{year} {normalized}");
+        let Ok(naive) = NaiveDateTime::parse_from_str(candidate.as_str(), "%Y %b %d %H:%M:%S%.f")
+        else {
+            return None;
+        };
+        let parsed = from_naive(naive, context.default_offset)?;
+        if parsed <= context.reference + chrono::Duration::days(1) {
+            return Some(parsed);
+        }
+    }
+    None
+}
+
+fn parse_locale(value: &str, day_first: bool, offset: FixedOffset) -> Option<DateTime<Utc>> {
+    let formats: &[&str] = if day_first {
+        &[
+            "%d/%m/%Y %H:%M:%S%.f",
+            "%d.%m.%Y %H:%M:%S%.f",
+            "%d-%m-%Y %H:%M:%S%.f",
+            "%d/%m/%Y %H:%M",
+            "%d.%m.%Y %H:%M",
+        ]
+    } else {
+        &[
+            "%m/%d/%Y %I:%M:%S %p",
+            "%m/%d/%Y %H:%M:%S%.f",
+            "%m/%d/%Y %I:%M %p",
+            "%m/%d/%Y %H:%M",
+        ]
+    };
+    formats
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .and_then(|naive| from_naive(naive, offset))
+}
+
+fn parse_custom(value: &str, format: &str, offset: FixedOffset) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value, format)
+        .map(|parsed| parsed.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, format)
+                .ok()
+                .and_then(|naive| from_naive(naive, offset))
+        })
+}
+
+/// Parses a timestamp according to `hint`. Values without an offset are read in
+/// `context.default_offset` rather than silently assumed to be UTC.
+pub fn parse_timestamp_with_hint(
+    value: &str,
+    hint: &TimestampHint,
+    context: &TimestampContext,
+) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let integer = || value.parse::<i64>().ok();
+    match hint {
+        TimestampHint::Rfc3339 => parse_rfc3339(value),
+        TimestampHint::Syslog => parse_syslog(value, context),
+        TimestampHint::FileTime => integer().and_then(from_filetime),
+        TimestampHint::EpochSeconds => parse_epoch_seconds(value),
+        TimestampHint::EpochMillis => integer().and_then(DateTime::<Utc>::from_timestamp_millis),
+        TimestampHint::EpochMicros => integer().and_then(from_epoch_micros),
+        TimestampHint::UsLocale => parse_locale(value, false, context.default_offset),
+        TimestampHint::EuLocale => parse_locale(value, true, context.default_offset),
+        TimestampHint::Custom(format) => {
+            parse_custom(value, format.as_str(), context.default_offset)
+        }
+        TimestampHint::Auto => parse_rfc3339(value)
+            .or_else(|| {
+                DateTime::parse_from_rfc2822(value)
+                    .ok()
+                    .map(|parsed| parsed.with_timezone(&Utc))
+            })
+            .or_else(|| parse_numeric(value))
+            .or_else(|| {
+                NAIVE_FORMATS.iter().find_map(|format| {
+                    NaiveDateTime::parse_from_str(value, format)
+                        .ok()
+                        .and_then(|naive| from_naive(naive, context.default_offset))
+                })
+            })
+            .or_else(|| parse_syslog(value, context))
+            .or_else(|| parse_locale(value, false, context.default_offset)),
+    }
+}
+
+/// Auto-detects a timestamp, reading values without an offset as UTC. This is the form stored
+/// events use, so analysis code shares it.
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    parse_timestamp_with_hint(value, &TimestampHint::Auto, &TimestampContext::utc())
+}
+
+/// Normalizes any supported timestamp to the RFC3339 UTC form events are stored in.
+pub fn normalize_timestamp(
+    value: &str,
+    hint: &TimestampHint,
+    context: &TimestampContext,
+) -> Option<String> {
+    parse_timestamp_with_hint(value, hint, context).map(|parsed| parsed.to_rfc3339())
+}
+
+/// The zone exports and reports show times in. Stored timestamps stay UTC; this only changes
+/// how they are written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayTimezone {
+    /// Whatever zone the operating system is set to, read at the time of formatting.
+    #[default]
+    System,
+    Utc,
+    Named(Tz),
+}
+
+impl DisplayTimezone {
+    /// Parses `system` (or `local`), `utc`, or an IANA zone name such as `Europe/Berlin`.
+    pub fn from_name(value: &str) -> Result<Self, String> {
+        let trimmed = value.trim();
+        match trimmed.to_ascii_lowercase().as_str() {
+            "" | "system" | "local" => Ok(Self::System),
+            "utc" | "z" => Ok(Self::Utc),
+            _ => trimmed
+                .parse::<Tz>()
+                .map(Self::Named)
+                .map_err(|_| format!("Unknown time zone '{trimmed}'.")),
+        }
+    }
+
+    /// The setting value: `system`, `UTC` or the IANA name.
+    pub fn name(&self) -> String {
+        match self {
+            Self::System => "system".to_string(),
+            Self::Utc => "UTC".to_string(),
+            Self::Named(zone) => zone.name().to_string(),
+        }
+    }
+
+    /// `value` as RFC3339 in this zone, with the zone's offset at that instant.
+    pub fn convert(&self, value: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Self::System => value.with_timezone(&chrono::Local).fixed_offset(),
+            Self::Utc => value.fixed_offset(),
+            Self::Named(zone) => value.with_timezone(zone).fixed_offset(),
+        }
+    }
+
+    /// Rewrites a stored timestamp in this zone. Values that cannot be parsed are returned as
+    /// they are rather than dropped.
+    pub fn format(&self, value: &str) -> String {
+        parse_timestamp(value)
+            .map(|parsed| self.convert(parsed).to_rfc3339())
+            .unwrap_or_else(|| value.to_string())
+    }
+}
+
+/// Every IANA zone name `DisplayTimezone::from_name` accepts, for pickers.
+pub fn timezone_names() -> Vec<&'static str> {
+    chrono_tz::TZ_VARIANTS
+        .iter()
+        .map(|zone| zone.name())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(offset_hours: i32) -> TimestampContext {
+        TimestampContext {
+            default_offset: FixedOffset::east_opt(offset_hours * 3600).unwrap(),
+            reference: DateTime::parse_from_rfc3339("2026-03-02T12:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    fn auto(value: &str, offset_hours: i32) -> Option<String> {
+        normalize_timestamp(value, &TimestampHint::Auto, &context(offset_hours))
+    }
+
+    #[test]
+    fn detects_offset_and_numeric_forms() {
+        let expected = Some("2026-03-01T10:00:00+00:00".to_string());
+        assert_eq!(auto("2026-03-01T11:00:00+01:00", 0), expected);
+        assert_eq!(auto("2026-03-01 02:00:00.000000-0800", 0), expected);
+        assert_eq!(auto("01/Mar/2026:10:00:00 +0000", 5), expected);
+        assert_eq!(auto("1772359200", 0), expected);
+        assert_eq!(auto("1772359200000", 0), expected);
+        assert_eq!(auto("1772359200000000", 0), expected);
+        assert_eq!(auto("134168328000000000", 0), expected);
+    }
+
+    #[test]
+    fn naive_values_use_the_default_offset() {
+        assert_eq!(
+            auto("2026-03-01 12:00:00", 2).as_deref(),
+            Some("2026-03-01T10:00:00+00:00")
+        );
+        assert_eq!(
+            auto("Mar  1 12:00:00", 2).as_deref(),
+            Some("2026-03-01T10:00:00+00:00")
+        );
+        // A December syslog line read in early March belongs to the previous year.
+        assert_eq!(
+            auto("Dec 31 23:00:00", 0).as_deref(),
+            Some("2025-12-31T23:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn hints_disambiguate_locale_dates() {
+        let eu = TimestampHint::from_name("eu").unwrap();
+        let us = TimestampHint::from_name("us").unwrap();
+        assert_eq!(
+            normalize_timestamp("03/01/2026 10:00:00", &eu, &context(0)).as_deref(),
+            Some("2026-01-03T10:00:00+00:00")
+        );
+        assert_eq!(
+            normalize_timestamp("03/01/2026 10:00:00 AM", &us, &context(0)).as_deref(),
+            Some("2026-03-01T10:00:00+00:00")
+        );
+        let custom = TimestampHint::from_name("%Y.%m.%d-%H%M").unwrap();
+        assert_eq!(
+            normalize_timestamp("2026.03.01-1000", &custom, &context(0)).as_deref(),
+            Some("2026-03-01T10:00:00+00:00")
+        );
+        assert!(TimestampHint::from_name("lunar").is_err());
+    }
+
+    #[test]
+    fn display_timezone_converts_stored_times() {
+        let berlin = DisplayTimezone::from_name("Europe/Berlin").unwrap();
+        assert_eq!(berlin.name(), "Europe/Berlin");
+        assert_eq!(
+            berlin.format("2026-07-01T10:00:00+00:00"),
+            "2026-07-01T12:00:00+02:00"
+        );
+        assert_eq!(
+            berlin.format("2026-01-01T10:00:00Z"),
+            "2026-01-01T11:00:00+01:00"
+        );
+        assert_eq!(
+            DisplayTimezone::from_name("utc")
+                .unwrap()
+                .format("2026-01-01T11:00:00+01:00"),
+            "2026-01-01T10:00:00+00:00"
+        );
+        assert_eq!(berlin.format("not a time"), "not a time");
+        assert_eq!(
+            DisplayTimezone::from_name("local").unwrap(),
+            DisplayTimezone::System
+        );
+        assert!(DisplayTimezone::from_name("Mars/Olympus").is_err());
+    }
+}
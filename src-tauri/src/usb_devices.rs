@@ -0,0 +1,567 @@
+//! USB and removable device history: connects and disconnects derived from Windows 6416,
+//! DriverFrameworks-UserMode, Kernel-PnP and Partition/Diagnostic events, Linux kernel/udev
+//! `usb X-Y:` messages and macOS mass-storage attach messages.
+
+use crate::db;
+use crate::logs::NormalizedEvent;
+use crate::nl_query::EventQueryFilter;
+use crate::sigma::raw_fields;
+use crate::timestamps::parse_timestamp;
+use chrono::{DateTime, Duration, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+pub const DEFAULT_LIMIT: usize = 500;
+/// Windows logs one plug-in under several providers; repeats of the same device, host and
+/// action closer together than this are folded into the first.
+const DUPLICATE_WINDOW_SECONDS: i64 = 60;
+const DRIVER_FRAMEWORKS_CONNECT: [u32; 3] = [2003, 2004, 2010];
+const DRIVER_FRAMEWORKS_DISCONNECT: [u32; 1] = [2102];
+
+static INSTANCE_ID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\b(?:USBSTOR|USB|SWD\\WPDBUSENUM|STORAGE\\VOLUME)\\[^\s"<>]+"#)
+        .expect("instance id pattern is valid")
+});
+static VID_PID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)VID_([0-9a-f]{4})&PID_([0-9a-f]{4})").expect("vid/pid pattern is valid")
+});
+static USBSTOR_NAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)Ven_([^&\\]*)&Prod_([^&\\]*)").expect("usbstor pattern is valid")
+});
+static KERNEL_USB: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:\[\s*[\d.]+\]\s*)?usb (\d+-[\d.]+): (.+)$")
+        .expect("kernel usb pattern is valid")
+});
+static KERNEL_IDS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"idVendor=([0-9a-fA-F]{4}), idProduct=([0-9a-fA-F]{4})")
+        .expect("kernel id pattern is valid")
+});
+static MACOS_USBMSC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"USBMSC Identifier \(non-unique\): (\S+) 0x([0-9a-fA-F]+) 0x([0-9a-fA-F]+)")
+        .expect("usbmsc pattern is valid")
+});
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsbDeviceEvent {
+    pub timestamp: String,
+    /// `connect` or `disconnect`.
+    pub action: String,
+    pub name: Option<String>,
+    pub manufacturer: Option<String>,
+    /// Windows device instance id, or the Linux bus port such as `1-2.1`.
+    pub device_id: Option<String>,
+    pub vendor_id: Option<String>,
+    pub product_id: Option<String>,
+    pub serial: Option<String>,
+    pub source_host: String,
+    pub provider: String,
+    pub event_code: Option<u32>,
+    /// Id of the stored event, for drill-down.
+    pub reference_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsbDeviceSummary {
+    /// Serial number when known, else `vendor:product`, instance id or name.
+    pub key: String,
+    pub name: Option<String>,
+    pub vendor_id: Option<String>,
+    pub product_id: Option<String>,
+    pub serial: Option<String>,
+    pub hosts: Vec<String>,
+    pub connects: usize,
+    pub disconnects: usize,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsbActivityReport {
+    pub window_start: String,
+    pub window_end: String,
+    /// Most recently seen first.
+    pub devices: Vec<UsbDeviceSummary>,
+    /// Newest first, capped at the requested limit.
+    pub events: Vec<UsbDeviceEvent>,
+    pub total_events: usize,
+}
+
+/// One parsed log line. Linux splits a plug-in over several kernel messages, so lines that
+/// only describe a device carry no action and are folded into the connect on the same port.
+#[derive(Debug, Clone)]
+struct Observation {
+    at: DateTime<Utc>,
+    action: Option<&'static str>,
+    port: Option<String>,
+    event: UsbDeviceEvent,
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty() && *value != "-")
+        .map(str::to_string)
+}
+
+fn base_event(event: &NormalizedEvent, action: &str) -> UsbDeviceEvent {
+    UsbDeviceEvent {
+        timestamp: event.timestamp.clone(),
+        action: action.to_string(),
+        source_host: event.source_host.clone(),
+        provider: event.provider.clone(),
+        event_code: event.event_id,
+        reference_id: event.id.clone(),
+        ..UsbDeviceEvent::default()
+    }
+}
+
+/// Fills vendor, product, serial and, for USBSTOR ids, a readable name from an instance id
+/// such as `USB\VID_0781&PID_5567\4C5300011` or `USBSTOR\Disk&Ven_SanDisk&Prod_Cruzer\4C53&0`.
+fn apply_instance_id(device: &mut UsbDeviceEvent, instance_id: &str) {
+    let instance_id = instance_id.trim();
+    if let Some(captures) = VID_PID.captures(instance_id) {
+        device.vendor_id = Some(captures[1].to_ascii_lowercase());
+        device.product_id = Some(captures[2].to_ascii_lowercase());
+    }
+    if device.name.is_none() {
+        if let Some(captures) = USBSTOR_NAME.captures(instance_id) {
+            let name = format!("{} {}", &captures[1], &captures[2])
+                .replace('_', " ")
+                .trim()
+                .to_string();
+            device.name = Some(name).filter(|name| !name.is_empty());
+        }
+    }
+    let segments: Vec<&str> = instance_id.split('\\').collect();
+    if segments.len() >= 3 {
+        let last = segments[segments.len() - 1];
+        let serial = match last.rsplit_once('&') {
+            Some((serial, suffix)) if suffix.chars().all(|ch| ch.is_ascii_digit()) => serial,
+            _ => last,
+        };
+        // Devices without a serial get a generated id with `&` separators instead.
+        if !serial.is_empty() && !serial.contains('&') && !serial.contains('{') {
+            device.serial = Some(serial.to_ascii_uppercase());
+        }
+    }
+    device.device_id = Some(instance_id.to_string());
+}
+
+fn windows_observation(event: &NormalizedEvent) -> Option<Observation> {
+    let provider = event.provider.to_ascii_lowercase();
+    let event_id = event.event_id?;
+    let fields = raw_fields(event.raw_payload.as_deref().unwrap_or_default());
+    let action = if provider.contains("security-auditing") && event_id == 6416 {
+        "connect"
+    } else if provider.contains("driverframeworks-usermode") {
+        if DRIVER_FRAMEWORKS_CONNECT.contains(&event_id) {
+            "connect"
+        } else if DRIVER_FRAMEWORKS_DISCONNECT.contains(&event_id) {
+            "disconnect"
+        } else {
+            return None;
+        }
+    } else if provider.contains("kernel-pnp") && matches!(event_id, 400 | 410) {
+        "connect"
+    } else if provider.contains("partition") && event_id == 1006 {
+        // The partition driver logs the same event on removal with the capacity zeroed.
+        match fields.get("Capacity").map(|value| value.trim()) {
+            Some("0") => "disconnect",
+            _ => "connect",
+        }
+    } else {
+        return None;
+    };
+
+    let instance_id = ["DeviceId", "DeviceInstanceId", "InstanceId", "ParentId"]
+        .iter()
+        .find_map(|name| non_empty(fields.get(*name)))
+        .filter(|value| INSTANCE_ID.is_match(value))
+        .or_else(|| {
+            INSTANCE_ID
+                .find(event.message.as_str())
+                .or_else(|| INSTANCE_ID.find(event.raw_payload.as_deref().unwrap_or_default()))
+                .map(|found| found.as_str().trim_end_matches(['.', ',']).to_string())
+        });
+    let usb_bus = fields.get("BusType").is_some_and(|bus| bus.trim() == "7");
+    // 6416 covers every external device class, so it is kept even without a USB id.
+    if instance_id.is_none() && !usb_bus && event_id != 6416 {
+        return None;
+    }
+
+    let mut device = base_event(event, action);
+    device.name = ["DeviceDescription", "Model", "FriendlyName"]
+        .iter()
+        .find_map(|name| non_empty(fields.get(*name)));
+    device.manufacturer = non_empty(fields.get("Manufacturer"));
+    if let Some(instance_id) = instance_id {
+        apply_instance_id(&mut device, instance_id.as_str());
+    }
+    if let Some(serial) = non_empty(fields.get("SerialNumber")) {
+        device.serial = Some(serial.to_ascii_uppercase());
+    }
+    Some(Observation {
+        at: parse_timestamp(event.timestamp.as_str())?,
+        action: Some(action),
+        port: None,
+        event: device,
+    })
+}
+
+fn kernel_observation(event: &NormalizedEvent) -> Option<Observation> {
+    let provider = event.provider.to_ascii_lowercase();
+    if provider != "kernel" && !provider.contains("udev") {
+        return None;
+    }
+    let message = event.message.trim();
+    if let Some(captures) = MACOS_USBMSC.captures(message) {
+        let mut device = base_event(event, "connect");
+        device.serial = Some(captures[1].to_ascii_uppercase());
+        device.vendor_id = Some(format!("{:0>4}", captures[2].to_ascii_lowercase()));
+        device.product_id = Some(format!("{:0>4}", captures[3].to_ascii_lowercase()));
+        return Some(Observation {
+            at: parse_timestamp(event.timestamp.as_str())?,
+            action: Some("connect"),
+            port: None,
+            event: device,
+        });
+    }
+
+    let captures = KERNEL_USB.captures(message)?;
+    let port = captures[1].to_string();
+    let detail = captures[2].trim();
+    let mut device = base_event(event, "connect");
+    let action = if detail.starts_with("new ") && detail.contains("USB device number") {
+        Some("connect")
+    } else if detail.starts_with("USB disconnect") {
+        Some("disconnect")
+    } else if let Some(ids) = KERNEL_IDS.captures(detail) {
+        device.vendor_id = Some(ids[1].to_ascii_lowercase());
+        device.product_id = Some(ids[2].to_ascii_lowercase());
+        None
+    } else if let Some(product) = detail.strip_prefix("Product: ") {
+        device.name = Some(product.trim().to_string());
+        None
+    } else if let Some(manufacturer) = detail.strip_prefix("Manufacturer: ") {
+        device.manufacturer = Some(manufacturer.trim().to_string());
+        None
+    } else if let Some(serial) = detail.strip_prefix("SerialNumber: ") {
+        device.serial = Some(serial.trim().to_ascii_uppercase());
+        None
+    } else {
+        return None;
+    };
+    if let Some(action) = action {
+        device.action = action.to_string();
+    }
+    device.device_id = Some(port.clone());
+    Some(Observation {
+        at: parse_timestamp(event.timestamp.as_str())?,
+        action,
+        port: Some(port),
+        event: device,
+    })
+}
+
+fn observation(event: &NormalizedEvent) -> Option<Observation> {
+    windows_observation(event).or_else(|| kernel_observation(event))
+}
+
+/// Copies identifiers `from` has and `into` lacks.
+fn merge_identity(into: &mut UsbDeviceEvent, from: &UsbDeviceEvent) {
+    for (target, source) in [
+        (&mut into.name, &from.name),
+        (&mut into.manufacturer, &from.manufacturer),
+        (&mut into.vendor_id, &from.vendor_id),
+        (&mut into.product_id, &from.product_id),
+        (&mut into.serial, &from.serial),
+    ] {
+        if target.is_none() {
+            target.clone_from(source);
+        }
+    }
+}
+
+fn device_key(event: &UsbDeviceEvent) -> String {
+    if let Some(serial) = event.serial.as_deref() {
+        return serial.to_string();
+    }
+    if let (Some(vendor), Some(product)) = (event.vendor_id.as_deref(), event.product_id.as_deref())
+    {
+        return format!("{vendor}:{product}");
+    }
+    event
+        .device_id
+        .clone()
+        .or_else(|| event.name.clone())
+        .unwrap_or_else(|| "unknown device".to_string())
+}
+
+/// Turns observations into connect/disconnect events, oldest first.
+fn fold(mut observations: Vec<Observation>) -> Vec<UsbDeviceEvent> {
+    observations.sort_by_key(|observation| observation.at);
+    let mut events: Vec<(DateTime<Utc>, UsbDeviceEvent)> = Vec::new();
+    // Latest connect per host and Linux port, which later detail lines describe.
+    let mut open_ports: HashMap<(String, String), usize> = HashMap::new();
+    for observation in observations {
+        let host = observation.event.source_host.clone();
+        match (observation.action, observation.port) {
+            (None, Some(port)) => {
+                if let Some(index) = open_ports.get(&(host, port)) {
+                    merge_identity(&mut events[*index].1, &observation.event);
+                }
+            }
+            (None, None) => {}
+            (Some(action), port) => {
+                let mut event = observation.event;
+                if let Some(port) = port {
+                    let slot = (host, port);
+                    if action == "connect" {
+                        open_ports.insert(slot, events.len());
+                    } else if let Some(index) = open_ports.remove(&slot) {
+                        let connected = events[index].1.clone();
+                        merge_identity(&mut event, &connected);
+                    }
+                }
+                events.push((observation.at, event));
+            }
+        }
+    }
+
+    let mut last_seen: HashMap<(String, String, String), DateTime<Utc>> = HashMap::new();
+    let mut kept: Vec<(DateTime<Utc>, UsbDeviceEvent)> = Vec::new();
+    for (at, event) in events {
+        let slot = (
+            event.source_host.clone(),
+            device_key(&event),
+            event.action.clone(),
+        );
+        let duplicate = last_seen
+            .get(&slot)
+            .is_some_and(|previous| at - *previous <= Duration::seconds(DUPLICATE_WINDOW_SECONDS));
+        last_seen.insert(slot, at);
+        if duplicate {
+            if let Some((_, previous)) = kept.iter_mut().rev().find(|(_, previous)| {
+                previous.source_host == event.source_host
+                    && previous.action == event.action
+                    && device_key(previous) == device_key(&event)
+            }) {
+                merge_identity(previous, &event);
+            }
+            continue;
+        }
+        kept.push((at, event));
+    }
+    kept.into_iter().map(|(_, event)| event).collect()
+}
+
+fn summarize(events: &[UsbDeviceEvent]) -> Vec<UsbDeviceSummary> {
+    let mut devices: BTreeMap<String, (UsbDeviceSummary, BTreeSet<String>)> = BTreeMap::new();
+    for event in events {
+        let key = device_key(event);
+        let (summary, hosts) = devices.entry(key.clone()).or_insert_with(|| {
+            (
+                UsbDeviceSummary {
+                    key,
+                    first_seen: event.timestamp.clone(),
+                    ..UsbDeviceSummary::default()
+                },
+                BTreeSet::new(),
+            )
+        });
+        if summary.name.is_none() {
+            summary.name.clone_from(&event.name);
+        }
+        if summary.vendor_id.is_none() {
+            summary.vendor_id.clone_from(&event.vendor_id);
+            summary.product_id.clone_from(&event.product_id);
+        }
+        if summary.serial.is_none() {
+            summary.serial.clone_from(&event.serial);
+        }
+        if event.action == "connect" {
+            summary.connects += 1;
+        } else {
+            summary.disconnects += 1;
+        }
+        summary.last_seen.clone_from(&event.timestamp);
+        hosts.insert(event.source_host.clone());
+    }
+    let mut devices: Vec<UsbDeviceSummary> = devices
+        .into_values()
+        .map(|(mut summary, hosts)| {
+            summary.hosts = hosts.into_iter().collect();
+            summary
+        })
+        .collect();
+    devices.sort_by(|left, right| right.last_seen.cmp(&left.last_seen));
+    devices
+}
+
+/// Lists USB and removable device activity recorded for `host` between `start` and `end`
+/// (RFC3339), keeping the newest `limit` events.
+pub fn usb_activity(
+    start: &str,
+    end: &str,
+    host: Option<&str>,
+    limit: usize,
+) -> Result<UsbActivityReport, String> {
+    let window = EventQueryFilter {
+        start: Some(start.to_string()),
+        end: Some(end.to_string()),
+        ..EventQueryFilter::default()
+    };
+    let passes = [
+        EventQueryFilter {
+            event_id: Some(6416),
+            ..window.clone()
+        },
+        EventQueryFilter {
+            providers: vec![
+                "Microsoft-Windows-DriverFrameworks-UserMode".to_string(),
+                "Microsoft-Windows-Kernel-PnP".to_string(),
+                "Microsoft-Windows-Partition".to_string(),
+            ],
+            ..window.clone()
+        },
+        EventQueryFilter {
+            text: Some("usb".to_string()),
+            providers: vec![
+                "kernel".to_string(),
+                "systemd-udevd".to_string(),
+                "udevd".to_string(),
+            ],
+            ..window
+        },
+    ];
+    let mut observations = Vec::new();
+    for filter in &passes {
+        db::for_each_event_with_raw(filter, host, |event| {
+            observations.extend(observation(&event));
+            Ok(())
+        })?;
+    }
+
+    let events = fold(observations);
+    let devices = summarize(events.as_slice());
+    let total_events = events.len();
+    Ok(UsbActivityReport {
+        window_start: start.to_string(),
+        window_end: end.to_string(),
+        devices,
+        events: events.into_iter().rev().take(limit).collect(),
+        total_events,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::SupportedOs;
+
+    fn event(
+        os: SupportedOs,
+        provider: &str,
+        event_id: Option<u32>,
+        message: &str,
+        timestamp: &str,
+    ) -> NormalizedEvent {
+        let mut event = NormalizedEvent::new(
+            os,
+            "System",
+            "system",
+            provider,
+            event_id,
+            "information",
+            message,
+            "localhost",
+        );
+        event.timestamp = timestamp.to_string();
+        event
+    }
+
+    #[test]
+    fn folds_linux_kernel_lines_into_connect_and_disconnect() {
+        let lines = [
+            (
+                "usb 1-2: new high-speed USB device number 7 using xhci_hcd",
+                "2026-03-01T09:00:00Z",
+            ),
+            (
+                "usb 1-2: New USB device found, idVendor=0781, idProduct=5567, bcdDevice= 1.00",
+                "2026-03-01T09:00:00Z",
+            ),
+            ("usb 1-2: Product: Cruzer Blade", "2026-03-01T09:00:01Z"),
+            ("usb 1-2: SerialNumber: 4c530001", "2026-03-01T09:00:01Z"),
+            (
+                "usb 1-2: USB disconnect, device number 7",
+                "2026-03-01T09:30:00Z",
+            ),
+            (
+                "usb 1-3: reset high-speed USB device number 2 using xhci_hcd",
+                "2026-03-01T09:31:00Z",
+            ),
+        ];
+        let observations = lines
+            .iter()
+            .filter_map(|(message, at)| {
+                observation(&event(SupportedOs::Linux, "kernel", None, message, at))
+            })
+            .collect();
+        let events = fold(observations);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].action, "connect");
+        assert_eq!(events[0].name.as_deref(), Some("Cruzer Blade"));
+        assert_eq!(events[1].action, "disconnect");
+        assert_eq!(events[1].vendor_id.as_deref(), Some("0781"));
+        assert_eq!(events[1].serial.as_deref(), Some("4C530001"));
+
+        let devices = summarize(events.as_slice());
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].key, "4C530001");
+        assert_eq!((devices[0].connects, devices[0].disconnects), (1, 1));
+    }
+
+    #[test]
+    fn parses_windows_instance_ids_and_folds_duplicate_providers() {
+        let mut audit = event(
+            SupportedOs::Windows,
+            "Microsoft-Windows-Security-Auditing",
+            Some(6416),
+            "A new external device was recognized by the system.",
+            "2026-03-01T09:00:00Z",
+        );
+        audit.raw_payload = Some(
+            "<Event><EventData><Data Name=\"DeviceId\">USBSTOR\\Disk&amp;Ven_SanDisk&amp;Prod_Cruzer_Blade&amp;Rev_1.00\\4C530001&amp;0</Data><Data Name=\"ClassName\">DiskDrive</Data></EventData></Event>"
+                .to_string(),
+        );
+        let umdf = event(
+            SupportedOs::Windows,
+            "Microsoft-Windows-DriverFrameworks-UserMode",
+            Some(2003),
+            "The UMDF Host Process has been asked to load drivers for device USB\\VID_0781&PID_5567\\4C530001.",
+            "2026-03-01T09:00:02Z",
+        );
+        let unrelated = event(
+            SupportedOs::Windows,
+            "Microsoft-Windows-Kernel-PnP",
+            Some(410),
+            "Device ACPI\\PNP0A08\\0 was started.",
+            "2026-03-01T09:00:03Z",
+        );
+        let observations = [audit, umdf, unrelated]
+            .iter()
+            .filter_map(observation)
+            .collect();
+        let events = fold(observations);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name.as_deref(), Some("SanDisk Cruzer Blade"));
+        assert_eq!(events[0].serial.as_deref(), Some("4C530001"));
+        assert_eq!(events[0].vendor_id.as_deref(), Some("0781"));
+    }
+}
@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "windows")]
+const WER_KEY: &str = "HKLM:\\SOFTWARE\\Microsoft\\Windows\\Windows Error Reporting";
+const DEFAULT_DUMP_FOLDER: &str = "%LOCALAPPDATA%\\CrashDumps";
+const DEFAULT_DUMP_COUNT: u32 = 10;
+#[cfg(target_os = "windows")]
+const MAX_DUMP_COUNT: u32 = 100;
+
+/// Registry values as read from the WER, LocalDumps and CrashControl keys. Missing values are
+/// `None`, which Windows treats as its default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WerRegistryValues {
+    pub disabled: Option<u32>,
+    pub dont_show_ui: Option<u32>,
+    pub consent: Option<u32>,
+    #[serde(default)]
+    pub local_dumps: bool,
+    pub dump_folder: Option<String>,
+    pub dump_type: Option<u32>,
+    pub dump_count: Option<u32>,
+    #[serde(default)]
+    pub app_key: bool,
+    pub app_dump_folder: Option<String>,
+    pub app_dump_type: Option<u32>,
+    pub app_dump_count: Option<u32>,
+    pub crash_dump_enabled: Option<u32>,
+    pub wer_service: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WerDumpStatus {
+    pub app: Option<String>,
+    pub reporting_enabled: bool,
+    pub consent: String,
+    /// Whether user-mode crashes of `app` (or any process) write a local dump.
+    pub local_dumps_enabled: bool,
+    pub dump_type: Option<String>,
+    pub dump_folder: Option<String>,
+    pub dump_count: Option<u32>,
+    pub kernel_dump: String,
+    /// Plain-language reasons dumps may be missing, most important first.
+    pub explanations: Vec<String>,
+    pub values: WerRegistryValues,
+}
+
+fn dump_type_label(value: u32) -> &'static str {
+    match value {
+        0 => "custom",
+        2 => "full",
+        _ => "mini",
+    }
+}
+
+fn consent_label(value: Option<u32>) -> &'static str {
+    match value {
+        Some(2) => "parameters only",
+        Some(3) => "parameters and safe data",
+        Some(4) => "all data",
+        _ => "always ask",
+    }
+}
+
+fn kernel_dump_label(value: Option<u32>) -> &'static str {
+    match value {
+        Some(0) => "none",
+        Some(1) => "complete",
+        Some(2) => "kernel",
+        Some(3) => "small",
+        Some(6) => "active",
+        _ => "automatic",
+    }
+}
+
+/// Interprets the raw registry values, explaining why a machine may have no crash dumps.
+pub fn explain_wer_settings(values: WerRegistryValues, app: Option<&str>) -> WerDumpStatus {
+    let mut explanations = Vec::new();
+    let reporting_enabled = values.disabled != Some(1);
+    if !reporting_enabled {
+        explanations.push(
+            "Windows Error Reporting is disabled (Disabled=1), so crashes produce no reports or dumps."
+                .to_string(),
+        );
+    }
+    if values
+        .wer_service
+        .as_deref()
+        .is_some_and(|start| start.eq_ignore_ascii_case("disabled"))
+    {
+        explanations.push("The Windows Error Reporting service (WerSvc) is disabled.".to_string());
+    }
+
+    let app_override = app.is_some() && values.app_key;
+    let local_dumps_enabled = reporting_enabled && (values.local_dumps || app_override);
+    let (dump_type, dump_folder, dump_count) = if app_override {
+        (
+            values.app_dump_type.or(values.dump_type),
+            values
+                .app_dump_folder
+                .clone()
+                .or(values.dump_folder.clone()),
+            values.app_dump_count.or(values.dump_count),
+        )
+    } else {
+        (
+            values.dump_type,
+            values.dump_folder.clone(),
+            values.dump_count,
+        )
+    };
+
+    if local_dumps_enabled {
+        let dump_type = dump_type.unwrap_or(1);
+        if dump_type == 1 {
+            explanations.push(
+                "LocalDumps writes minidumps only; set the dump type to full for heap contents."
+                    .to_string(),
+            );
+        }
+        if dump_count == Some(0) {
+            explanations.push(
+                "DumpCount is 0, so older dumps are never kept past the newest one.".to_string(),
+            );
+        }
+    } else if reporting_enabled {
+        explanations.push(match app {
+            Some(app) => format!(
+                "No LocalDumps key applies to {app}: its crashes are reported to WER but no local dump is kept."
+            ),
+            None => "The LocalDumps key is missing: application crashes are reported to WER but no local dump is kept.".to_string(),
+        });
+    }
+
+    if values.crash_dump_enabled == Some(0) {
+        explanations.push(
+            "Kernel crash dumps are disabled (CrashDumpEnabled=0), so blue screens leave no MEMORY.DMP or minidump."
+                .to_string(),
+        );
+    }
+
+    WerDumpStatus {
+        app: app.map(ToString::to_string),
+        reporting_enabled,
+        consent: consent_label(values.consent).to_string(),
+        local_dumps_enabled,
+        dump_type: local_dumps_enabled.then(|| dump_type_label(dump_type.unwrap_or(1)).to_string()),
+        dump_folder: local_dumps_enabled
+            .then(|| dump_folder.unwrap_or_else(|| DEFAULT_DUMP_FOLDER.to_string())),
+        dump_count: local_dumps_enabled.then(|| dump_count.unwrap_or(DEFAULT_DUMP_COUNT)),
+        kernel_dump: kernel_dump_label(values.crash_dump_enabled).to_string(),
+        explanations,
+        values,
+    }
+}
+
+/// Accepts a bare executable name such as `outlook.exe`, the form LocalDumps subkeys use.
+pub fn validate_app_name(app: &str) -> Result<String, String> {
+    let trimmed = app.trim();
+    let valid = trimmed.len() > 4
+        && trimmed.len() <= 128
+        && trimmed.to_ascii_lowercase().ends_with(".exe")
+        && trimmed
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-' | ' '));
+    if valid {
+        Ok(trimmed.to_string())
+    } else {
+        Err("Application must be an executable name such as outlook.exe.".to_string())
+    }
+}
+
+/// Maps a dump type name to its LocalDumps `DumpType` value.
+pub fn parse_dump_type(value: &str) -> Result<u32, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "mini" => Ok(1),
+        "full" => Ok(2),
+        _ => Err("Dump type must be mini or full.".to_string()),
+    }
+}
+
+fn validate_dump_folder(folder: &str) -> Result<String, String> {
+    let trimmed = folder.trim();
+    if trimmed.is_empty() || trimmed.len() > 240 || trimmed.contains(['\'', '"', '`', '\n', '\r']) {
+        return Err("Dump folder must be a plain path without quotes.".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn run_powershell(script: &str) -> Result<String, String> {
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()
+        .map_err(|error| format!("Failed to launch PowerShell: {error}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(output.stderr.as_slice());
+        if stderr.contains("not allowed") || stderr.contains("UnauthorizedAccess") {
+            return Err(
+                "Changing crash dump settings requires administrator rights. Restart the app elevated and try again."
+                    .to_string(),
+            );
+        }
+        return Err(format!("PowerShell failed: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(output.stdout.as_slice()).to_string())
+}
+
+/// Reads the WER and crash dump configuration, including the LocalDumps override for `app`.
+#[cfg(target_os = "windows")]
+pub fn read_wer_dump_status(app: Option<&str>) -> Result<WerDumpStatus, String> {
+    let app = app.map(validate_app_name).transpose()?;
+    let app_key = app
+        .as_deref()
+        .map(|name| {
+            format!(
+                "Get-ItemProperty -Path \"$wer\\LocalDumps\\{name}\" -ErrorAction SilentlyContinue"
+            )
+        })
+        .unwrap_or_else(|| "$null".to_string());
+    let script = format!(
+        "$wer='{WER_KEY}'; \
+$w=Get-ItemProperty -Path $wer -ErrorAction SilentlyContinue; \
+$c=Get-ItemProperty -Path \"$wer\\Consent\" -ErrorAction SilentlyContinue; \
+$l=Get-ItemProperty -Path \"$wer\\LocalDumps\" -ErrorAction SilentlyContinue; \
+$a={app_key}; \
+$k=Get-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\CrashControl' -ErrorAction SilentlyContinue; \
+$s=(Get-Service WerSvc -ErrorAction SilentlyContinue).StartType; \
+[pscustomobject]@{{disabled=$w.Disabled;dontShowUi=$w.DontShowUI;consent=$c.DefaultConsent;localDumps=[bool]$l;dumpFolder=$l.DumpFolder;dumpType=$l.DumpType;dumpCount=$l.DumpCount;appKey=[bool]$a;appDumpFolder=$a.DumpFolder;appDumpType=$a.DumpType;appDumpCount=$a.DumpCount;crashDumpEnabled=$k.CrashDumpEnabled;werService=if($s){{\"$s\"}}else{{$null}}}} | ConvertTo-Json -Compress"
+    );
+    let output = run_powershell(script.as_str())?;
+    let values: WerRegistryValues = serde_json::from_str(output.trim())
+        .map_err(|error| format!("Failed to parse WER settings: {error}"))?;
+    Ok(explain_wer_settings(values, app.as_deref()))
+}
+
+/// Enables LocalDumps for `app`, or for every process when `app` is `None`. Writing under HKLM
+/// needs an elevated process.
+#[cfg(target_os = "windows")]
+pub fn enable_local_dumps(
+    app: Option<&str>,
+    dump_type: &str,
+    dump_folder: Option<&str>,
+    dump_count: Option<u32>,
+) -> Result<WerDumpStatus, String> {
+    let app = app.map(validate_app_name).transpose()?;
+    let dump_type = parse_dump_type(dump_type)?;
+    let folder = validate_dump_folder(dump_folder.unwrap_or(DEFAULT_DUMP_FOLDER))?;
+    let count = dump_count
+        .unwrap_or(DEFAULT_DUMP_COUNT)
+        .clamp(1, MAX_DUMP_COUNT);
+    let key = match app.as_deref() {
+        Some(name) => format!("{WER_KEY}\\LocalDumps\\{name}"),
+        None => format!("{WER_KEY}\\LocalDumps"),
+    };
+    let script = format!(
+        "$ErrorActionPreference='Stop'; \
+$key='{key}'; \
+New-Item -Path $key -Force | Out-Null; \
+New-ItemProperty -Path $key -Name DumpType -PropertyType DWord -Value {dump_type} -Force | Out-Null; \
+New-ItemProperty -Path $key -Name DumpCount -PropertyType DWord -Value {count} -Force | Out-Null; \
+New-ItemProperty -Path $key -Name DumpFolder -PropertyType ExpandString -Value '{folder}' -Force | Out-Null"
+    );
+    run_powershell(script.as_str())?;
+    read_wer_dump_status(app.as_deref())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn read_wer_dump_status(_app: Option<&str>) -> Result<WerDumpStatus, String> {
+    Err("Windows Error Reporting settings are only available on Windows.".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn enable_local_dumps(
+    _app: Option<&str>,
+    dump_type: &str,
+    dump_folder: Option<&str>,
+    _dump_count: Option<u32>,
+) -> Result<WerDumpStatus, String> {
+    parse_dump_type(dump_type)?;
+    validate_dump_folder(dump_folder.unwrap_or(DEFAULT_DUMP_FOLDER))?;
+    Err("Windows Error Reporting settings are only available on Windows.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explains_missing_local_dumps_and_kernel_dumps() {
+        let status = explain_wer_settings(
+            WerRegistryValues {
+                crash_dump_enabled: Some(0),
+                ..WerRegistryValues::default()
+            },
+            Some("outlook.exe"),
+        );
+        assert!(status.reporting_enabled);
+        assert!(!status.local_dumps_enabled);
+        assert_eq!(status.kernel_dump, "none");
+        assert_eq!(status.explanations.len(), 2);
+        assert!(status.explanations[0].contains("outlook.exe"));
+    }
+
+    #[test]
+    fn app_override_takes_precedence() {
+        let status = explain_wer_settings(
+            WerRegistryValues {
+                local_dumps: true,
+                dump_type: Some(1),
+                app_key: true,
+                app_dump_type: Some(2),
+                app_dump_folder: Some("D:\\Dumps".to_string()),
+                ..WerRegistryValues::default()
+            },
+            Some("outlook.exe"),
+        );
+        assert_eq!(status.dump_type.as_deref(), Some("full"));
+        assert_eq!(status.dump_folder.as_deref(), Some("D:\\Dumps"));
+        assert_eq!(status.dump_count, Some(DEFAULT_DUMP_COUNT));
+        assert!(status.explanations.is_empty());
+    }
+
+    #[test]
+    fn rejects_unsafe_names() {
+        assert!(validate_app_name("outlook.exe").is_ok());
+        assert!(validate_app_name("..\\evil.exe").is_err());
+        assert!(validate_app_name("app'; Remove-Item x; '.exe").is_err());
+        assert!(validate_dump_folder("C:\\Dumps'").is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Workspaces: named investigations ("Customer A incident", "My laptop"), each stored in its own
+//! database file so unrelated cases never share an events table. Opening a workspace points every
+//! later connection at its file; archiving one moves the file aside and hides it from the list.
+
+use crate::db;
+use crate::settings::{
+    load_workspace_settings, save_workspace_settings, Workspace, WorkspaceSettings,
+    DEFAULT_WORKSPACE_ID,
+};
+use chrono::Utc;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const WORKSPACE_DIR: &str = "workspaces";
+const ARCHIVE_DIR: &str = "archive";
+const MAX_NAME_LENGTH: usize = 80;
+
+/// Database file of a workspace. The default workspace keeps the original `events.db`.
+fn database_file(workspace_id: &str, archived: bool) -> Result<PathBuf, String> {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return db::default_db_path();
+    }
+    let mut path = db::data_dir()?;
+    path.push(WORKSPACE_DIR);
+    if archived {
+        path.push(ARCHIVE_DIR);
+    }
+    fs::create_dir_all(&path)
+        .map_err(|error| format!("Failed to create workspace directory: {error}"))?;
+    path.push(format!("{workspace_id}.db"));
+    Ok(path)
+}
+
+/// A readable, unique id: the name's letters and digits plus a random suffix.
+fn workspace_id(name: &str) -> String {
+    let slug = name
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug: String = slug.chars().take(32).collect();
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    if slug.is_empty() {
+        format!("workspace-{suffix}")
+    } else {
+        format!("{}-{suffix}", slug.trim_end_matches('-'))
+    }
+}
+
+fn select_database(workspace_id: &str) -> Result<(), String> {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        db::set_database_file(None)
+    } else {
+        db::set_database_file(Some(database_file(workspace_id, false)?))
+    }
+}
+
+/// Opens the workspace that was active when the app last closed. Called once at startup, before
+/// anything touches the database.
+pub fn restore_active() -> Result<(), String> {
+    select_database(load_workspace_settings().active_id.as_str())
+}
+
+pub fn list_workspaces() -> WorkspaceSettings {
+    load_workspace_settings()
+}
+
+pub fn create_workspace(name: &str) -> Result<Workspace, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Workspace name is required.".to_string());
+    }
+    let mut settings = load_workspace_settings();
+    if settings
+        .workspaces
+        .iter()
+        .any(|workspace| !workspace.archived && workspace.name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("A workspace named \"{name}\" already exists."));
+    }
+    let workspace = Workspace {
+        id: workspace_id(name),
+        name: name.chars().take(MAX_NAME_LENGTH).collect(),
+        created_at: Utc::now().to_rfc3339(),
+        last_opened_at: None,
+        archived: false,
+    };
+    settings.workspaces.push(workspace.clone());
+    save_workspace_settings(settings)?;
+    Ok(workspace)
+}
+
+/// Makes `workspace_id` the active workspace, restoring it first if it was archived.
+pub fn open_workspace(workspace_id: &str) -> Result<WorkspaceSettings, String> {
+    let mut settings = load_workspace_settings();
+    let Some(workspace) = settings
+        .workspaces
+        .iter_mut()
+        .find(|workspace| workspace.id == workspace_id)
+    else {
+        return Err(format!("Workspace {workspace_id} was not found."));
+    };
+    if workspace.archived {
+        let archived = database_file(workspace_id, true)?;
+        if archived.exists() {
+            fs::rename(&archived, database_file(workspace_id, false)?)
+                .map_err(|error| format!("Failed to restore archived workspace: {error}"))?;
+        }
+        workspace.archived = false;
+    }
+    workspace.last_opened_at = Some(Utc::now().to_rfc3339());
+
+    // Leave the current file complete on its own before switching away from it.
+    db::checkpoint()?;
+    settings.active_id = workspace_id.to_string();
+    let settings = save_workspace_settings(settings)?;
+    select_database(settings.active_id.as_str())?;
+    Ok(settings)
+}
+
+/// Archives a workspace other than the default and the open one: its database moves to
+/// `workspaces/archive` and it no longer appears as open-able until restored.
+pub fn archive_workspace(workspace_id: &str) -> Result<WorkspaceSettings, String> {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return Err("The default workspace cannot be archived.".to_string());
+    }
+    let mut settings = load_workspace_settings();
+    if settings.active_id == workspace_id {
+        return Err("Open another workspace before archiving this one.".to_string());
+    }
+    let Some(workspace) = settings
+        .workspaces
+        .iter_mut()
+        .find(|workspace| workspace.id == workspace_id)
+    else {
+        return Err(format!("Workspace {workspace_id} was not found."));
+    };
+    if workspace.archived {
+        return Ok(settings);
+    }
+    let current = database_file(workspace_id, false)?;
+    if current.exists() {
+        // Fold in anything still in the log, which does not move with the file.
+        db::checkpoint_file(current.as_path())?;
+        fs::rename(&current, database_file(workspace_id, true)?)
+            .map_err(|error| format!("Failed to archive workspace database: {error}"))?;
+    }
+    // Empty after the checkpoint, and they would be replayed against the restored file.
+    for sidecar in ["-wal", "-shm"] {
+        let mut name = current.clone().into_os_string();
+        name.push(sidecar);
+        let _ = fs::remove_file(PathBuf::from(name));
+    }
+    workspace.archived = true;
+    save_workspace_settings(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_ids_are_slugs_with_a_suffix() {
+        let id = workspace_id("Customer A: incident #42");
+        assert!(id.starts_with("customer-a-incident-42-"));
+        assert_eq!(id.len(), "customer-a-incident-42-".len() + 8);
+        assert!(workspace_id("???").starts_with("workspace-"));
+    }
+}
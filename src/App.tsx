@@ -1,6 +1,23 @@
 import { useEffect, useMemo, useRef, useState } from "react";
 import {
   analyzeMinidump,
+  getRecentChanges,
+  matchKnownIssues,
+  listSigmaRules,
+  importSigmaRules,
+  deleteSigmaRule,
+  runSigmaScan,
+  listSigmaDetections,
+  analyzeLogons,
+  getUsbDeviceActivity,
+  getRepeatedEvents,
+  searchEventsRegex,
+  getSystemChanges,
+  importIocFeed,
+  listIocFeeds,
+  deleteIocFeed,
+  runIocScan,
+  listIocMatches,
   importHostCrashes,
   exportEventsWithDialog,
   getCrashRelatedEvents,
@@ -9,10 +26,17 @@ import {
   getHostOsVersion,
   getLocalEvents,
   getSavedTheme,
+  getDisplayTimezone,
+  setDisplayTimezone,
+  getExportDirectory,
+  chooseExportDirectory,
+  setExportDirectory,
+  listTimezones,
   getLocalEventsWindow,
   isTauriRuntime,
   openExternalUrl,
   quitApp,
+  recordQueryStep,
   estimateRefreshLocalEvents,
   estimateLocalEventsRange,
   refreshLocalEvents,
@@ -41,9 +65,60 @@ import {
   openPathInShell,
   clearRemoteProfileSecret,
   clearRemoteProviderSecret,
-  testRemoteConnection
+  testRemoteConnection,
+  getEventRaw,
+  runPreflightChecks,
+  getSyncHistory,
+  getDbStats,
+  compactDatabase,
+  getEncryptionStatus,
+  setDatabaseEncryption,
+  listWorkspaces,
+  createWorkspace,
+  openWorkspace,
+  archiveWorkspace,
+  listSavedFilters,
+  saveSavedFilter,
+  deleteSavedFilter,
+  runRefreshJob,
+  runRetentionNow,
+  searchArchive,
+  rehydrateArchiveDays,
+  saveArchiveStoreSecret,
+  clearArchiveStoreSecret,
+  getSourceHosts,
+  hostTargetId,
+  ALL_HOSTS_TARGET
 } from "./lib/backend";
 import type {
+  CrashesImportedPayload,
+  SyncCompletedPayload,
+  HealthReport,
+  PreflightReport,
+  SyncHistoryEntry,
+  JobStatus,
+  RetentionReport,
+  ArchiveSearchReport,
+  ObjectStoreSettings,
+  DatabaseStats,
+  EncryptionStatus,
+  WorkspaceSettings,
+  SavedFilter,
+  RecentChangesReport,
+  KnownIssueMatch,
+  SigmaRuleSummary,
+  SigmaDetection,
+  SigmaScanResult,
+  LogonReport,
+  UsbActivityReport,
+  RepeatedEventsReport,
+  RegexSearchResult,
+  SystemChangesReport,
+  LogonAggregate,
+  IocFeedSummary,
+  IocMatch,
+  IocScanResult,
+  ScoredCrashEvent,
   IngestProfile,
   LlmAnalysisResult,
   LlmConnectionProfile,
@@ -57,9 +132,10 @@ import type {
   RemoteSettings,
   RemoteConnectionProfile,
   RemoteProviderAccount,
-  RemoteConnectionTestResult
+  RemoteConnectionTestResult,
+  SourceHostSummary
 } from "./lib/backend";
-import { exportAsCsv, exportAsJson, exportAsText } from "./lib/export";
+import { exportAsCsv, exportAsJson, exportAsNdjson, exportAsText } from "./lib/export";
 import { applyFilters, defaultFilters } from "./lib/filters";
 import { importSessionEvents } from "./lib/import";
 import { buildCrashRcaPrompt, buildGoogleQuery, buildLlmPrompt, redactSensitiveText } from "./lib/llmPrompt";
@@ -79,11 +155,13 @@ import {
 import type { LlmAnalysisContextKind, LlmAnalysisGuide } from "./lib/crashAnalysis";
 import { cn } from "./lib/cn";
 import { Button } from "./components/Button";
+import { KnownIssueList } from "./components/KnownIssueList";
 import type {
   CrashRecord,
   EventCategory,
   EventFilters,
   EventSeverity,
+  ExportCompression,
   ExportFormat,
   NormalizedEvent,
   SupportedOs,
@@ -96,7 +174,16 @@ const browserDefaultOs: SupportedOs = navigator.userAgent.includes("Windows")
     ? "macos"
     : "linux";
 
-const windowsChannelOptions = ["Application", "System", "Security"] as const;
+const windowsChannelOptions = [
+  "Application",
+  "System",
+  "Security",
+  "Microsoft-Windows-Diagnostics-Performance/Operational",
+  "Microsoft-Windows-PrintService/Operational",
+  "Microsoft-Windows-PrintService/Admin",
+  "Microsoft-Windows-TerminalServices-LocalSessionManager/Operational",
+  "Microsoft-Windows-Sysmon/Operational"
+] as const;
 const llmProviderOptions = [
   { id: "ollama", label: "Ollama (Local)" },
   { id: "lmstudio", label: "LM Studio (Local)" },
@@ -115,6 +202,7 @@ const llmScopeOptions = [
 const MAX_LOCAL_EVENTS_IN_MEMORY = 10000;
 const MAX_IMPORTED_EVENTS_IN_MEMORY = 5000;
 const LOCAL_FETCH_LIMIT = MAX_LOCAL_EVENTS_IN_MEMORY + 1;
+const EMPTY_OBJECT_STORE: ObjectStoreSettings = { kind: "", bucket: "", region: "", endpoint: "", accessKeyId: "", containerUrl: "", prefix: "" };
 const VIRTUAL_ROW_HEIGHT = 36;
 const VIRTUAL_OVERSCAN_ROWS = 20;
 
@@ -209,7 +297,7 @@ interface SortState {
 type WorkspaceTab = "home" | "events" | "crashes" | "data" | "import" | "export" | "settings" | "help";
 type ExportScope = "loaded" | "custom";
 type LlmResponseViewMode = "guide" | "raw";
-type MessageViewMode = "raw" | "parsed";
+type MessageViewMode = "raw" | "parsed" | "source";
 type LoadEstimateMode = "rolling-sync" | "range-load";
 type HelpSectionId =
   | "quick-start"
@@ -337,9 +425,9 @@ interface PrivilegedAccessWarning {
 }
 
 const defaultExportCategoriesByOs: Record<SupportedOs, EventCategory[]> = {
-  windows: ["application", "system", "security", "audit", "other"],
-  linux: ["application", "system", "security", "audit", "other"],
-  macos: ["application", "system", "security", "audit", "other"]
+  windows: ["application", "system", "security", "audit", "printing", "updates", "other"],
+  linux: ["application", "system", "security", "audit", "printing", "updates", "other"],
+  macos: ["application", "system", "security", "audit", "printing", "updates", "other"]
 };
 
 function createDefaultExportFilters(): ExportWizardFilters {
@@ -636,6 +724,12 @@ function flattenStructuredObject(value: unknown, prefix = "", depth = 0): Parsed
 function parseStructuredMessage(event: NormalizedEvent | null): ParsedMessageField[] {
   if (!event) return [];
 
+  if (event.metadata && Object.keys(event.metadata).length > 0) {
+    return Object.entries(event.metadata)
+      .slice(0, 40)
+      .map(([key, value]) => ({ key, value }));
+  }
+
   if (event.raw && typeof event.raw === "object" && !Array.isArray(event.raw)) {
     const rawFields = flattenStructuredObject(event.raw);
     if (rawFields.length > 0) return rawFields.slice(0, 40);
@@ -1383,6 +1477,7 @@ export default function App() {
   const [hostOsVersion, setHostOsVersion] = useState<string>("Unknown");
   const [activeTab, setActiveTab] = useState<WorkspaceTab>("home");
   const [targetHostId, setTargetHostId] = useState<string>("localhost");
+  const [sourceHosts, setSourceHosts] = useState<SourceHostSummary[]>([]);
   const [remoteSelectedId, setRemoteSelectedId] = useState<string>("");
   const [remoteSettings, setRemoteSettingsState] = useState<RemoteSettings>({
     profiles: [],
@@ -1409,9 +1504,10 @@ export default function App() {
   }, []);
 
   useEffect(() => {
+    const storedHostTarget = targetHostId === ALL_HOSTS_TARGET || targetHostId.startsWith("host:");
     if (remoteSettings.profiles.length === 0) {
       if (remoteSelectedId !== "") setRemoteSelectedId("");
-      if (targetHostId !== "localhost") setTargetHostId("localhost");
+      if (targetHostId !== "localhost" && !storedHostTarget) setTargetHostId("localhost");
       return;
     }
 
@@ -1421,6 +1517,7 @@ export default function App() {
 
     if (
       targetHostId !== "localhost" &&
+      !storedHostTarget &&
       !remoteSettings.profiles.some((profile) => profile.id === targetHostId)
     ) {
       setTargetHostId("localhost");
@@ -1435,22 +1532,78 @@ export default function App() {
   const [importedEvents, setImportedEvents] = useState<NormalizedEvent[]>([]);
   const [crashes, setCrashes] = useState<CrashRecord[]>([]);
   const [selectedCrashId, setSelectedCrashId] = useState<string>("");
-  const [correlatedEvents, setCorrelatedEvents] = useState<NormalizedEvent[]>([]);
+  const [correlatedEvents, setCorrelatedEvents] = useState<ScoredCrashEvent[]>([]);
   const [preCrashEvents, setPreCrashEvents] = useState<NormalizedEvent[]>([]);
   const [preCrashWindowMinutes, setPreCrashWindowMinutes] = useState<number>(15);
+  const [recentChangesHours, setRecentChangesHours] = useState<number>(24);
+  const [recentChanges, setRecentChanges] = useState<RecentChangesReport | null>(null);
+  const [isLoadingRecentChanges, setIsLoadingRecentChanges] = useState(false);
+  const [crashKnownIssues, setCrashKnownIssues] = useState<KnownIssueMatch[]>([]);
+  const [eventKnownIssues, setEventKnownIssues] = useState<KnownIssueMatch[]>([]);
+  const [sigmaRules, setSigmaRules] = useState<SigmaRuleSummary[]>([]);
+  const [sigmaDetections, setSigmaDetections] = useState<SigmaDetection[]>([]);
+  const [sigmaScanResult, setSigmaScanResult] = useState<SigmaScanResult | null>(null);
+  const [sigmaMessages, setSigmaMessages] = useState<string[]>([]);
+  const [isSigmaScanning, setIsSigmaScanning] = useState(false);
+  const [logonReportDays, setLogonReportDays] = useState<number>(7);
+  const [logonReport, setLogonReport] = useState<LogonReport | null>(null);
+  const [usbActivityDays, setUsbActivityDays] = useState<number>(30);
+  const [usbActivity, setUsbActivity] = useState<UsbActivityReport | null>(null);
+  const [isLoadingUsbActivity, setIsLoadingUsbActivity] = useState(false);
+  const [regexPattern, setRegexPattern] = useState("");
+  const [regexResult, setRegexResult] = useState<RegexSearchResult | null>(null);
+  const [isRegexSearching, setIsRegexSearching] = useState(false);
+  const [repeatedEventsDays, setRepeatedEventsDays] = useState<number>(1);
+  const [repeatedEvents, setRepeatedEvents] = useState<RepeatedEventsReport | null>(null);
+  const [isLoadingRepeatedEvents, setIsLoadingRepeatedEvents] = useState(false);
+  const [systemChangesDays, setSystemChangesDays] = useState<number>(7);
+  const [systemChanges, setSystemChanges] = useState<SystemChangesReport | null>(null);
+  const [isLoadingSystemChanges, setIsLoadingSystemChanges] = useState(false);
+  const [isAnalyzingLogons, setIsAnalyzingLogons] = useState(false);
+  const [iocFeeds, setIocFeeds] = useState<IocFeedSummary[]>([]);
+  const [iocMatches, setIocMatches] = useState<IocMatch[]>([]);
+  const [iocFeedNameDraft, setIocFeedNameDraft] = useState("");
+  const [iocScanResult, setIocScanResult] = useState<IocScanResult | null>(null);
+  const [iocMessage, setIocMessage] = useState("");
+  const [isIocScanning, setIsIocScanning] = useState(false);
   const [preCrashFocusEnabled, setPreCrashFocusEnabled] = useState(false);
   const [selected, setSelected] = useState<NormalizedEvent | null>(null);
   const [sortState, setSortState] = useState<SortState | null>(null);
   const [exportScope, setExportScope] = useState<ExportScope>("loaded");
   const [exportFormat, setExportFormat] = useState<ExportFormat>("json");
+  const [exportCompression, setExportCompression] = useState<ExportCompression>("none");
   const [exportFilters, setExportFilters] = useState<ExportWizardFilters>(createDefaultExportFilters);
   const [ingestWindowDays, setIngestWindowDaysState] = useState<number>(7);
   const [ingestProfile, setIngestProfileState] = useState<IngestProfile>({
     autoSyncOnStartup: false,
     maxEventsPerSync: 2000,
     windowsChannels: ["Application", "System", "Security"],
-    requestElevation: false
+    requestElevation: false,
+    collectSmart: false,
+    maxDatabaseMb: 0,
+    journalFilter: { units: [], identifiers: [], priority: "" },
+    unifiedLogFilter: { predicate: "", includeInfo: false, includeDebug: false },
+    syncIntervalMinutes: 0,
+    retention: {
+      maxAgeDays: 0,
+      maxEvents: 0,
+      archiveDirectory: "",
+      objectStore: EMPTY_OBJECT_STORE
+    },
+    messageLocale: "en-US",
+    collapseRepeats: false
   });
+  const [retentionRunning, setRetentionRunning] = useState<boolean>(false);
+  const [retentionReport, setRetentionReport] = useState<RetentionReport | null>(null);
+  const [archiveQuery, setArchiveQuery] = useState<string>("");
+  const [archiveStart, setArchiveStart] = useState<string>(() =>
+    new Date(Date.now() - 6 * 24 * 60 * 60 * 1000).toISOString().slice(0, 10)
+  );
+  const [archiveEnd, setArchiveEnd] = useState<string>(() => new Date().toISOString().slice(0, 10));
+  const [archiveSecretDraft, setArchiveSecretDraft] = useState<string>("");
+  const [archiveResult, setArchiveResult] = useState<ArchiveSearchReport | null>(null);
+  const [archiveSelectedDays, setArchiveSelectedDays] = useState<string[]>([]);
+  const [archiveBusy, setArchiveBusy] = useState<boolean>(false);
   const [llmSettings, setLlmSettingsState] = useState<LlmSettings>(createDefaultLlmSettings);
   const [llmSelectedProfileId, setLlmSelectedProfileId] = useState<string>("");
   const [llmApiKeyDraft, setLlmApiKeyDraft] = useState<string>("");
@@ -1479,6 +1632,7 @@ export default function App() {
   const [copyStatus, setCopyStatus] = useState<"idle" | "copied">("idle");
   const [copyEventTextStatus, setCopyEventTextStatus] = useState<"idle" | "copied">("idle");
   const [messageViewMode, setMessageViewMode] = useState<MessageViewMode>("raw");
+  const [selectedEventSource, setSelectedEventSource] = useState<{ eventId: string; payload: string | null; error?: string } | null>(null);
   const [llmWindowOpen, setLlmWindowOpen] = useState(false);
   const [llmAnalysisContextKind, setLlmAnalysisContextKind] = useState<LlmAnalysisContextKind>("event");
   const [llmAnalysisTitle, setLlmAnalysisTitle] = useState("LLM Analysis");
@@ -1501,6 +1655,27 @@ export default function App() {
   const [minidumpAnalysis, setMinidumpAnalysis] = useState<MinidumpAnalysisResult | null>(null);
   const [isAnalyzingMinidump, setIsAnalyzingMinidump] = useState(false);
   const [exportStatus, setExportStatus] = useState<string>("");
+  const [healthAlert, setHealthAlert] = useState<HealthReport | null>(null);
+  const [preflightReport, setPreflightReport] = useState<PreflightReport | null>(null);
+  const [preflightRunning, setPreflightRunning] = useState(false);
+  const [syncHistory, setSyncHistory] = useState<SyncHistoryEntry[] | null>(null);
+  const [dbStats, setDbStats] = useState<DatabaseStats | null>(null);
+  const [workspaceSettings, setWorkspaceSettings] = useState<WorkspaceSettings | null>(null);
+  const [displayTimezone, setDisplayTimezoneState] = useState<string>("system");
+  const [timezoneDraft, setTimezoneDraft] = useState<string>("system");
+  const [exportDirectory, setExportDirectoryState] = useState<string | null>(null);
+  const [timezoneOptions, setTimezoneOptions] = useState<string[]>([]);
+  const [timezoneMessage, setTimezoneMessage] = useState<string>("");
+  const [workspaceNameDraft, setWorkspaceNameDraft] = useState<string>("");
+  const [workspaceBusy, setWorkspaceBusy] = useState<boolean>(false);
+  const [savedFilters, setSavedFilters] = useState<SavedFilter[]>([]);
+  const [savedFilterNameDraft, setSavedFilterNameDraft] = useState<string>("");
+  const [savedFilterWindow, setSavedFilterWindow] = useState<string>("");
+  const [dbCompacting, setDbCompacting] = useState<boolean>(false);
+  const [dbCompactStatus, setDbCompactStatus] = useState<string>("");
+  const [encryptionStatus, setEncryptionStatus] = useState<EncryptionStatus | null>(null);
+  const [encryptionBusy, setEncryptionBusy] = useState<boolean>(false);
+  const [syncProgress, setSyncProgress] = useState<JobStatus | null>(null);
   const llmValidatedProfileSignaturesRef = useRef<Map<string, string>>(new Map());
   const tableContainerRef = useRef<HTMLElement | null>(null);
   const [tableScrollTop, setTableScrollTop] = useState(0);
@@ -1679,6 +1854,45 @@ export default function App() {
     };
   }, [correlatedEvents, crashes, preCrashEvents, selectedCrashHostEvents, selectedCrashId]);
   const selectedEventParsedFields = useMemo(() => parseStructuredMessage(selected), [selected]);
+
+  useEffect(() => {
+    if (messageViewMode !== "source" || !selected || selectedEventSource?.eventId === selected.id) return;
+    const eventId = selected.id;
+    getEventRaw(eventId)
+      .then((payload) => setSelectedEventSource({ eventId, payload }))
+      .catch((error: unknown) => setSelectedEventSource({ eventId, payload: null, error: String(error) }));
+  }, [messageViewMode, selected, selectedEventSource?.eventId]);
+  useEffect(() => {
+    setEventKnownIssues([]);
+    if (!selected) return;
+    let cancelled = false;
+    // Events only in memory (imports not yet stored) have nothing to look up.
+    matchKnownIssues(selected.id)
+      .then((report) => {
+        if (!cancelled) setEventKnownIssues(report?.matches ?? []);
+      })
+      .catch(() => undefined);
+    return () => {
+      cancelled = true;
+    };
+  }, [selected]);
+  useEffect(() => {
+    setCrashKnownIssues([]);
+    if (!selectedCrashId) return;
+    let cancelled = false;
+    matchKnownIssues(selectedCrashId)
+      .then((report) => {
+        if (!cancelled) setCrashKnownIssues(report?.matches ?? []);
+      })
+      .catch(() => undefined);
+    return () => {
+      cancelled = true;
+    };
+  }, [selectedCrashId]);
+  useEffect(() => {
+    if (activeTab !== "data") return;
+    void refreshSigmaNow();
+  }, [activeTab, targetHostId]);
   const selectedCrashOs = useMemo(
     () => crashes.find((crash) => crash.id === selectedCrashId)?.os,
     [crashes, selectedCrashId]
@@ -1952,6 +2166,31 @@ export default function App() {
       });
     }
 
+    if (selected.userName || selected.userSid) {
+      details.push({
+        label: "User",
+        value: selected.userName && selected.userSid ? `${selected.userName} (${selected.userSid})` : selected.userName ?? selected.userSid ?? ""
+      });
+    }
+    if (selected.computer) {
+      details.push({ label: "Computer", value: selected.computer });
+    }
+    if (typeof selected.processId === "number") {
+      details.push({
+        label: "Process / Thread",
+        value: typeof selected.threadId === "number" ? `${selected.processId} / ${selected.threadId}` : `${selected.processId}`
+      });
+    }
+    if (selected.task) {
+      details.push({ label: "Task", value: selected.task });
+    }
+    if (selected.opcode) {
+      details.push({ label: "Opcode", value: selected.opcode });
+    }
+    if (selected.keywords) {
+      details.push({ label: "Keywords", value: selected.keywords });
+    }
+
     details.push({ label: "Source", value: selected.imported ? "Imported" : "Live/Local" });
     return details;
   }, [selected]);
@@ -1989,6 +2228,12 @@ export default function App() {
     : "grid gap-2 lg:grid-cols-[1.35fr_1fr_1fr_0.9fr_0.9fr_1fr]";
 
   function applyCollectorWarnings(context: string, result: SyncOperationResult): void {
+    const degradations = result.degradations ?? [];
+    if (degradations.length > 0) {
+      setCollectorWarning(`${context}: storage limit reached. ${degradations.join(" ")}`);
+      setPrivilegedAccessWarning(null);
+      return;
+    }
     if (result.warnings.length === 0) {
       setCollectorWarning("");
       setPrivilegedAccessWarning(null);
@@ -2086,6 +2331,11 @@ export default function App() {
     setActiveFilters((prev) => (prev.eventId ? { ...prev, eventId: "" } : prev));
   }, [hasWindowsEvents]);
 
+  useEffect(() => {
+    // Dropped by the backend unless an investigation recording is active.
+    void recordQueryStep("filter", activeFilters).catch(() => undefined);
+  }, [activeFilters]);
+
   useEffect(() => {
     setExportFilters((current) => {
       let next = current;
@@ -2212,6 +2462,144 @@ export default function App() {
     setCopyEventTextStatus("idle");
   }, [selected?.id]);
 
+  useEffect(() => {
+    if (!isTauriRuntime() || targetHostId !== "localhost") return;
+
+    let unlisten: (() => void) | undefined;
+    let active = true;
+    void (async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const off = await listen<CrashesImportedPayload>("hla://crashes-imported", (event) => {
+          void refreshCrashes().catch(() => undefined);
+          setExportStatus(
+            `Detected ${event.payload.count} new crash report${event.payload.count === 1 ? "" : "s"}.`
+          );
+          window.setTimeout(() => setExportStatus(""), 2500);
+        });
+        if (active) {
+          unlisten = off;
+        } else {
+          off();
+        }
+      } catch {
+        // Ignore when Tauri event bridge is unavailable.
+      }
+    })();
+
+    return () => {
+      active = false;
+      unlisten?.();
+    };
+  }, [targetHostId]);
+
+  useEffect(() => {
+    if (!isTauriRuntime() || targetHostId !== "localhost" || rangeViewActive) return;
+
+    let unlisten: (() => void) | undefined;
+    let active = true;
+    void (async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const off = await listen<SyncCompletedPayload>("hla://sync-completed", (event) => {
+          if (event.payload.error) {
+            setCollectorWarning(`Background sync failed: ${event.payload.error}`);
+            return;
+          }
+          void getLocalEvents(undefined, LOCAL_FETCH_LIMIT)
+            .then((events) => applyLocalEventsCache(events, "Background sync load"))
+            .catch(() => undefined);
+          setExportStatus(`Background sync: ${event.payload.collected.toLocaleString()} events collected.`);
+          window.setTimeout(() => setExportStatus(""), 2500);
+        });
+        if (active) {
+          unlisten = off;
+        } else {
+          off();
+        }
+      } catch {
+        // Ignore when Tauri event bridge is unavailable.
+      }
+    })();
+
+    return () => {
+      active = false;
+      unlisten?.();
+    };
+  }, [targetHostId, rangeViewActive]);
+
+  useEffect(() => {
+    if (!isTauriRuntime()) return;
+
+    let unlisten: (() => void) | undefined;
+    let active = true;
+    void (async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const off = await listen<HealthReport>("hla://health-alert", (event) => {
+          setHealthAlert(event.payload.findings.length > 0 ? event.payload : null);
+        });
+        if (active) {
+          unlisten = off;
+        } else {
+          off();
+        }
+      } catch {
+        // Ignore when Tauri event bridge is unavailable.
+      }
+    })();
+
+    return () => {
+      active = false;
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!isTauriRuntime()) return;
+
+    // Another window (or this one) saved a settings section; reload it so every window agrees.
+    // Theme changes arrive separately as hla://theme-changed.
+    const reloadSection = async (section: string): Promise<void> => {
+      if (section === "ingestWindowDays") {
+        setIngestWindowDaysState(await getIngestWindowDays());
+      } else if (section === "displayTimezone") {
+        const timezone = await getDisplayTimezone();
+        setDisplayTimezoneState(timezone);
+        setTimezoneDraft(timezone);
+      } else if (section === "exportDirectory") {
+        setExportDirectoryState(await getExportDirectory());
+      } else if (section === "ingestProfile") {
+        setIngestProfileState(await getIngestProfile());
+      } else if (section === "llm") {
+        setLlmSettingsState(await getLlmSettings());
+      }
+    };
+
+    let unlisten: (() => void) | undefined;
+    let active = true;
+    void (async () => {
+      try {
+        const { listen } = await import("@tauri-apps/api/event");
+        const off = await listen<string>("hla://settings-changed", (event) => {
+          void reloadSection(event.payload).catch(() => undefined);
+        });
+        if (active) {
+          unlisten = off;
+        } else {
+          off();
+        }
+      } catch {
+        // Ignore when Tauri event bridge is unavailable.
+      }
+    })();
+
+    return () => {
+      active = false;
+      unlisten?.();
+    };
+  }, []);
+
   useEffect(() => {
     if (llmSettings.profiles.length === 0) {
       if (llmSelectedProfileId !== "") setLlmSelectedProfileId("");
@@ -2277,6 +2665,13 @@ export default function App() {
       const version = await getHostOsVersion().catch(() => "Unknown (not provided by host)");
       setHostOsVersion(version);
       setIngestWindowDaysState(await getIngestWindowDays());
+      const timezone = await getDisplayTimezone();
+      setDisplayTimezoneState(timezone);
+      setTimezoneDraft(timezone);
+      setTimezoneOptions(await listTimezones());
+      setExportDirectoryState(await getExportDirectory());
+      setWorkspaceSettings(await listWorkspaces());
+      setSavedFilters(await listSavedFilters());
       const profile = await getIngestProfile();
       setIngestProfileState(profile);
       const llm = await getLlmSettings();
@@ -2308,6 +2703,7 @@ export default function App() {
       setRangeViewActive(false);
       setRangeLoadMessage("");
       await refreshCrashes();
+      await refreshSourceHosts();
     } catch (error) {
       setLastError(error instanceof Error ? error.message : "Failed to initialize host collector.");
     } finally {
@@ -2337,9 +2733,12 @@ export default function App() {
     }
 
     try {
-      const result = await refreshLocalEvents(targetHostId !== "localhost" ? targetHostId : undefined);
+      const result = await runRefreshJob(targetHostId !== "localhost" ? targetHostId : undefined, (status) =>
+        setSyncProgress(status.state === "running" ? status : null)
+      );
       applyCollectorWarnings("Refresh warning", result);
       applyLocalEventsCache(await getLocalEvents(targetHostId !== "localhost" ? targetHostId : undefined, LOCAL_FETCH_LIMIT), "Refresh load");
+      await refreshSourceHosts();
       if (rangeViewActive) {
         clearAppliedDateRangeFilters();
       }
@@ -2350,14 +2749,65 @@ export default function App() {
     } catch (error) {
       setLastError(error instanceof Error ? error.message : "Refresh failed.");
     } finally {
+      setSyncProgress(null);
       setIsLoading(false);
     }
   }
 
+  async function switchWorkspace(workspaceId: string): Promise<void> {
+    setLastError("");
+    setWorkspaceBusy(true);
+    try {
+      setWorkspaceSettings(await openWorkspace(workspaceId));
+      setDbStats(null);
+      setSyncHistory(null);
+      const events = await getLocalEvents(targetHostId !== "localhost" ? targetHostId : undefined, LOCAL_FETCH_LIMIT);
+      setLocalEvents([]);
+      if (events.length > 0) {
+        applyLocalEventsCache(events, "Workspace load");
+      }
+      setRangeViewActive(false);
+      setRangeLoadMessage("");
+      await refreshCrashes();
+      await refreshSourceHosts();
+    } catch (error) {
+      setLastError(String(error));
+    } finally {
+      setWorkspaceBusy(false);
+    }
+  }
+
+  async function createAndOpenWorkspace(): Promise<void> {
+    const name = workspaceNameDraft.trim();
+    if (!name) return;
+    setLastError("");
+    try {
+      const workspace = await createWorkspace(name);
+      setWorkspaceNameDraft("");
+      await switchWorkspace(workspace.id);
+    } catch (error) {
+      setLastError(String(error));
+    }
+  }
+
+  async function archiveWorkspaceById(workspaceId: string): Promise<void> {
+    setLastError("");
+    try {
+      setWorkspaceSettings(await archiveWorkspace(workspaceId));
+    } catch (error) {
+      setLastError(String(error));
+    }
+  }
+
+  async function refreshSourceHosts(): Promise<void> {
+    setSourceHosts(await getSourceHosts());
+  }
+
   async function refreshCrashes(): Promise<void> {
     const records = await getCrashes(targetHostId !== "localhost" ? targetHostId : undefined);
     setCrashes(records);
     setMinidumpAnalysis(null);
+    setRecentChanges(null);
 
     if (records.length === 0) {
       setSelectedCrashId("");
@@ -2394,6 +2844,7 @@ export default function App() {
     setSelectedCrashId(crashId);
     setPreCrashEvents([]);
     setMinidumpAnalysis(null);
+    setRecentChanges(null);
     if (!crashId) {
       setCorrelatedEvents([]);
       setPreCrashFocusEnabled(false);
@@ -2502,6 +2953,20 @@ export default function App() {
     }
   }
 
+  async function loadRecentChangesNow(): Promise<void> {
+    if (!selectedCrash) return;
+    setLastError("");
+    setIsLoadingRecentChanges(true);
+    try {
+      setRecentChanges(await getRecentChanges(selectedCrash.id, recentChangesHours));
+    } catch (error) {
+      setRecentChanges(null);
+      setLastError(error instanceof Error ? error.message : "Failed to list recent changes.");
+    } finally {
+      setIsLoadingRecentChanges(false);
+    }
+  }
+
   async function openSelectedDumpFolderNow(): Promise<void> {
     if (!selectedCrash?.rawPath) return;
     setLastError("");
@@ -2539,113 +3004,389 @@ export default function App() {
     setFilterDraft(createDefaultFilters());
   }
 
-  function clearAppliedDateRangeFilters(): void {
-    setFilterDraft((prev) => {
-      if (!prev.dateFrom && !prev.dateTo) return prev;
-      return { ...prev, dateFrom: "", dateTo: "" };
-    });
-    setActiveFilters((prev) => {
-      if (!prev.dateFrom && !prev.dateTo) return prev;
-      return { ...prev, dateFrom: "", dateTo: "" };
-    });
+  async function saveCurrentFilter(): Promise<void> {
+    const name = savedFilterNameDraft.trim();
+    if (!name) return;
+    setLastError("");
+    const relativeMinutes = Number(savedFilterWindow) || null;
+    const eventId = Number.parseInt(filterDraft.eventId, 10);
+    try {
+      const saved = await saveSavedFilter({
+        id: "",
+        name,
+        text: filterDraft.text.trim(),
+        severities: (Object.keys(filterDraft.severities) as EventSeverity[]).filter(
+          (level) => filterDraft.severities[level]
+        ),
+        providers: filterDraft.source.trim() ? [filterDraft.source.trim()] : [],
+        logName: filterDraft.logType === "all" ? null : filterDraft.logType,
+        category: filterDraft.category === "all" ? null : filterDraft.category,
+        eventId: Number.isFinite(eventId) ? eventId : null,
+        relativeMinutes,
+        start: !relativeMinutes && filterDraft.dateFrom ? new Date(`${filterDraft.dateFrom}T00:00:00`).toISOString() : null,
+        end: !relativeMinutes && filterDraft.dateTo ? new Date(`${filterDraft.dateTo}T23:59:59.999`).toISOString() : null,
+        sortColumn: sortState?.column ?? null,
+        sortDirection: sortState?.direction ?? null,
+        createdAt: "",
+        updatedAt: ""
+      });
+      setSavedFilters((prev) => [...prev.filter((entry) => entry.id !== saved.id), saved]);
+      setSavedFilterNameDraft("");
+    } catch (error) {
+      setLastError(String(error));
+    }
   }
 
-  async function onImport(fileList: FileList | null): Promise<void> {
-    const file = fileList?.[0];
-    if (!file) return;
+  function applySavedFilter(filter: SavedFilter): void {
+    const defaults = createDefaultFilters();
+    const severities = { ...defaults.severities };
+    if (filter.severities.length > 0) {
+      for (const level of Object.keys(severities) as EventSeverity[]) {
+        severities[level] = filter.severities.includes(level);
+      }
+    }
+    const next: EventFilters = {
+      ...defaults,
+      text: filter.text,
+      severities,
+      logType: filter.logName ?? "all",
+      category: filter.category ?? "all",
+      eventId: filter.eventId === null ? "" : String(filter.eventId),
+      source: filter.providers[0] ?? "",
+      dateFrom: filter.relativeMinutes
+        ? formatDateInputValue(Date.now() - filter.relativeMinutes * 60_000)
+        : filter.start
+          ? formatDateInputValue(Date.parse(filter.start))
+          : "",
+      dateTo: !filter.relativeMinutes && filter.end ? formatDateInputValue(Date.parse(filter.end)) : ""
+    };
+    setFilterDraft(next);
+    setActiveFilters({ ...next, severities: { ...next.severities } });
+    setSortState(
+      filter.sortColumn && filter.sortDirection
+        ? { column: filter.sortColumn as SortColumn, direction: filter.sortDirection }
+        : null
+    );
+  }
 
+  async function deleteSavedFilterById(filterId: string): Promise<void> {
     setLastError("");
     try {
-      const imported = await importSessionEvents(file, hostOs);
-      const mergedImported = [...imported, ...importedEvents];
-      const capped = capEvents(mergedImported, MAX_IMPORTED_EVENTS_IN_MEMORY);
-      setImportedEvents(capped.kept);
-      setExportStatus(
-        `Imported ${imported.length.toLocaleString()} event${imported.length === 1 ? "" : "s"} from ${file.name}.`
-      );
-      window.setTimeout(() => setExportStatus(""), 3000);
-      if (capped.truncated > 0) {
-        setMemoryNotice(
-          `Imported event cache is capped at ${MAX_IMPORTED_EVENTS_IN_MEMORY.toLocaleString()} events; ${capped.truncated.toLocaleString()} imported events were omitted from memory.`
-        );
-      }
+      await deleteSavedFilter(filterId);
+      setSavedFilters((prev) => prev.filter((entry) => entry.id !== filterId));
     } catch (error) {
-      setLastError(error instanceof Error ? error.message : "Import failed.");
+      setLastError(String(error));
     }
   }
 
-  async function copyText(value: string): Promise<void> {
+  async function refreshSigmaNow(): Promise<void> {
+    const hostId = targetHostId !== "localhost" ? targetHostId : undefined;
     try {
-      await navigator.clipboard.writeText(value);
-      return;
-    } catch {
-      const textarea = document.createElement("textarea");
-      textarea.value = value;
-      textarea.setAttribute("readonly", "true");
-      textarea.style.position = "absolute";
-      textarea.style.left = "-9999px";
-      document.body.appendChild(textarea);
-      textarea.select();
-      const copied = document.execCommand("copy");
-      document.body.removeChild(textarea);
-      if (!copied) {
-        throw new Error("Clipboard copy failed.");
-      }
+      const list = await listSigmaRules();
+      setSigmaRules(list.rules);
+      setSigmaMessages(list.errors);
+      setSigmaDetections(await listSigmaDetections(hostId));
+      setIocFeeds(await listIocFeeds());
+      setIocMatches(await listIocMatches(hostId));
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to load detection rules.");
     }
   }
 
-  async function copyPrompt(): Promise<void> {
-    if (!selected) return;
+  async function importSigmaFiles(files: FileList | null): Promise<void> {
+    if (!files || files.length === 0) return;
     setLastError("");
     try {
-      await copyText(buildLlmPrompt(selected, hostOsVersion));
-      setCopyStatus("copied");
-      window.setTimeout(() => setCopyStatus("idle"), 1800);
+      const contents = await Promise.all(
+        Array.from(files).map(async (file) => ({ name: file.name, content: await file.text() }))
+      );
+      const result = await importSigmaRules(contents);
+      await refreshSigmaNow();
+      setSigmaMessages([`Imported ${result.imported.length} rule(s).`, ...result.errors]);
     } catch (error) {
-      setLastError(error instanceof Error ? error.message : "Failed to copy prompt.");
-      setCopyStatus("idle");
+      setLastError(error instanceof Error ? error.message : "Failed to import Sigma rules.");
     }
   }
 
-  function openLlmWindowWithContext(
-    kind: LlmAnalysisContextKind,
-    title: string,
-    description: string,
-    originalContextLabel: string,
-    redactedContextLabel: string,
-    originalContext: string,
-    prompt: string
-  ): void {
-    const preferredProfileId = pickDefaultRunnableLlmProfileId(
-      llmSettings.profiles,
-      llmSettings.defaultProfileId
-    );
+  async function deleteSigmaRuleById(ruleId: string): Promise<void> {
+    setLastError("");
+    try {
+      await deleteSigmaRule(ruleId);
+      setSigmaRules((prev) => prev.filter((rule) => rule.id !== ruleId));
+      setSigmaDetections((prev) => prev.filter((detection) => detection.ruleId !== ruleId));
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to delete Sigma rule.");
+    }
+  }
 
-    setLlmAnalysisContextKind(kind);
-    setLlmAnalysisTitle(title);
-    setLlmContextDescription(description);
-    setLlmOriginalContextLabel(originalContextLabel);
-    setLlmRedactedContextLabel(redactedContextLabel);
-    setLlmOriginalContextDraft(originalContext);
-    setLlmBasePrompt(prompt);
-    setLlmPromptDraft(prompt);
-    setLlmPromptWasRedacted(false);
-    setLlmPromptPreRedactionDraft("");
-    setLlmRunProfileId(preferredProfileId);
-    setLlmRunResult(null);
-    setLlmRunError("");
-    setLlmGuardrailBlock(null);
-    setLlmResponseViewMode("guide");
-    setLlmWindowOpen(true);
+  async function runSigmaScanNow(): Promise<void> {
+    setLastError("");
+    setIsSigmaScanning(true);
+    try {
+      const result = await runSigmaScan(targetHostId !== "localhost" ? targetHostId : undefined);
+      setSigmaScanResult(result);
+      setSigmaMessages(result.ruleErrors);
+      setSigmaDetections(await listSigmaDetections(targetHostId !== "localhost" ? targetHostId : undefined));
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to run Sigma scan.");
+    } finally {
+      setIsSigmaScanning(false);
+    }
   }
 
-  function openLlmAnalysisWindow(): void {
-    if (!selected) return;
-    const prompt = buildLlmPrompt(selected, hostOsVersion, false);
-    openLlmWindowWithContext(
-      "event",
-      "LLM Analysis",
-      "Review original vs redacted context side-by-side, then edit prompt before send. `Copy Event Text` remains unredacted.",
+  async function importIocFile(files: FileList | null): Promise<void> {
+    const file = files?.[0];
+    if (!file) return;
+    setLastError("");
+    try {
+      const name = iocFeedNameDraft.trim() || file.name.replace(/\.[^.]+$/, "");
+      const result = await importIocFeed(name, await file.text());
+      setIocFeedNameDraft("");
+      setIocFeeds(await listIocFeeds());
+      const counts = Object.entries(result.counts)
+        .map(([kind, count]) => `${count} ${kind}`)
+        .join(", ");
+      setIocMessage(
+        `Imported ${result.imported} indicator(s) into "${result.feed}" (${counts}).` +
+          (result.skipped > 0 ? ` Skipped ${result.skipped} line(s), e.g. ${result.skippedExamples.join(" | ")}.` : "")
+      );
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to import IOC feed.");
+    }
+  }
+
+  async function deleteIocFeedByName(feed: string): Promise<void> {
+    setLastError("");
+    try {
+      await deleteIocFeed(feed);
+      setIocFeeds((prev) => prev.filter((entry) => entry.feed !== feed));
+      setIocMatches((prev) => prev.filter((entry) => entry.feed !== feed));
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to delete IOC feed.");
+    }
+  }
+
+  async function runIocScanNow(): Promise<void> {
+    setLastError("");
+    setIsIocScanning(true);
+    try {
+      const hostId = targetHostId !== "localhost" ? targetHostId : undefined;
+      setIocScanResult(await runIocScan(hostId));
+      setIocMatches(await listIocMatches(hostId));
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to run IOC scan.");
+    } finally {
+      setIsIocScanning(false);
+    }
+  }
+
+  async function analyzeLogonsNow(): Promise<void> {
+    setLastError("");
+    setIsAnalyzingLogons(true);
+    try {
+      const end = new Date();
+      const start = new Date(end.getTime() - logonReportDays * 24 * 60 * 60 * 1000);
+      setLogonReport(
+        await analyzeLogons(start.toISOString(), end.toISOString(), targetHostId !== "localhost" ? targetHostId : undefined)
+      );
+    } catch (error) {
+      setLogonReport(null);
+      setLastError(error instanceof Error ? error.message : "Failed to analyze logons.");
+    } finally {
+      setIsAnalyzingLogons(false);
+    }
+  }
+
+  async function loadUsbActivityNow(): Promise<void> {
+    setLastError("");
+    setIsLoadingUsbActivity(true);
+    try {
+      const end = new Date();
+      const start = new Date(end.getTime() - usbActivityDays * 24 * 60 * 60 * 1000);
+      setUsbActivity(
+        await getUsbDeviceActivity(
+          start.toISOString(),
+          end.toISOString(),
+          targetHostId !== "localhost" ? targetHostId : undefined
+        )
+      );
+    } catch (error) {
+      setUsbActivity(null);
+      setLastError(error instanceof Error ? error.message : "Failed to load device activity.");
+    } finally {
+      setIsLoadingUsbActivity(false);
+    }
+  }
+
+  async function runRegexSearchNow(): Promise<void> {
+    if (!regexPattern.trim()) return;
+    setLastError("");
+    setIsRegexSearching(true);
+    try {
+      setRegexResult(
+        await searchEventsRegex(regexPattern, targetHostId !== "localhost" ? targetHostId : undefined)
+      );
+    } catch (error) {
+      setRegexResult(null);
+      setLastError(error instanceof Error ? error.message : "Regex search failed.");
+    } finally {
+      setIsRegexSearching(false);
+    }
+  }
+
+  async function loadRepeatedEventsNow(): Promise<void> {
+    setLastError("");
+    setIsLoadingRepeatedEvents(true);
+    try {
+      const end = new Date();
+      const start = new Date(end.getTime() - repeatedEventsDays * 24 * 60 * 60 * 1000);
+      setRepeatedEvents(
+        await getRepeatedEvents(
+          {
+            text: null,
+            severities: [],
+            logName: null,
+            category: null,
+            eventId: null,
+            provider: null,
+            start: start.toISOString(),
+            end: end.toISOString()
+          },
+          targetHostId !== "localhost" ? targetHostId : undefined
+        )
+      );
+    } catch (error) {
+      setRepeatedEvents(null);
+      setLastError(error instanceof Error ? error.message : "Failed to load repeated messages.");
+    } finally {
+      setIsLoadingRepeatedEvents(false);
+    }
+  }
+
+  async function loadSystemChangesNow(): Promise<void> {
+    setLastError("");
+    setIsLoadingSystemChanges(true);
+    try {
+      const end = new Date();
+      const start = new Date(end.getTime() - systemChangesDays * 24 * 60 * 60 * 1000);
+      setSystemChanges(
+        await getSystemChanges(start.toISOString(), end.toISOString(), targetHostId !== "localhost" ? targetHostId : undefined)
+      );
+    } catch (error) {
+      setSystemChanges(null);
+      setLastError(error instanceof Error ? error.message : "Failed to detect system changes.");
+    } finally {
+      setIsLoadingSystemChanges(false);
+    }
+  }
+
+  function clearAppliedDateRangeFilters(): void {
+    setFilterDraft((prev) => {
+      if (!prev.dateFrom && !prev.dateTo) return prev;
+      return { ...prev, dateFrom: "", dateTo: "" };
+    });
+    setActiveFilters((prev) => {
+      if (!prev.dateFrom && !prev.dateTo) return prev;
+      return { ...prev, dateFrom: "", dateTo: "" };
+    });
+  }
+
+  async function onImport(fileList: FileList | null): Promise<void> {
+    const file = fileList?.[0];
+    if (!file) return;
+
+    setLastError("");
+    try {
+      const imported = await importSessionEvents(file, hostOs);
+      const mergedImported = [...imported, ...importedEvents];
+      const capped = capEvents(mergedImported, MAX_IMPORTED_EVENTS_IN_MEMORY);
+      setImportedEvents(capped.kept);
+      setExportStatus(
+        `Imported ${imported.length.toLocaleString()} event${imported.length === 1 ? "" : "s"} from ${file.name}.`
+      );
+      window.setTimeout(() => setExportStatus(""), 3000);
+      if (capped.truncated > 0) {
+        setMemoryNotice(
+          `Imported event cache is capped at ${MAX_IMPORTED_EVENTS_IN_MEMORY.toLocaleString()} events; ${capped.truncated.toLocaleString()} imported events were omitted from memory.`
+        );
+      }
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Import failed.");
+    }
+  }
+
+  async function copyText(value: string): Promise<void> {
+    try {
+      await navigator.clipboard.writeText(value);
+      return;
+    } catch {
+      const textarea = document.createElement("textarea");
+      textarea.value = value;
+      textarea.setAttribute("readonly", "true");
+      textarea.style.position = "absolute";
+      textarea.style.left = "-9999px";
+      document.body.appendChild(textarea);
+      textarea.select();
+      const copied = document.execCommand("copy");
+      document.body.removeChild(textarea);
+      if (!copied) {
+        throw new Error("Clipboard copy failed.");
+      }
+    }
+  }
+
+  async function copyPrompt(): Promise<void> {
+    if (!selected) return;
+    setLastError("");
+    try {
+      await copyText(buildLlmPrompt(selected, hostOsVersion));
+      setCopyStatus("copied");
+      window.setTimeout(() => setCopyStatus("idle"), 1800);
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to copy prompt.");
+      setCopyStatus("idle");
+    }
+  }
+
+  function openLlmWindowWithContext(
+    kind: LlmAnalysisContextKind,
+    title: string,
+    description: string,
+    originalContextLabel: string,
+    redactedContextLabel: string,
+    originalContext: string,
+    prompt: string
+  ): void {
+    const preferredProfileId = pickDefaultRunnableLlmProfileId(
+      llmSettings.profiles,
+      llmSettings.defaultProfileId
+    );
+
+    setLlmAnalysisContextKind(kind);
+    setLlmAnalysisTitle(title);
+    setLlmContextDescription(description);
+    setLlmOriginalContextLabel(originalContextLabel);
+    setLlmRedactedContextLabel(redactedContextLabel);
+    setLlmOriginalContextDraft(originalContext);
+    setLlmBasePrompt(prompt);
+    setLlmPromptDraft(prompt);
+    setLlmPromptWasRedacted(false);
+    setLlmPromptPreRedactionDraft("");
+    setLlmRunProfileId(preferredProfileId);
+    setLlmRunResult(null);
+    setLlmRunError("");
+    setLlmGuardrailBlock(null);
+    setLlmResponseViewMode("guide");
+    setLlmWindowOpen(true);
+  }
+
+  function openLlmAnalysisWindow(): void {
+    if (!selected) return;
+    const prompt = buildLlmPrompt(selected, hostOsVersion, false);
+    openLlmWindowWithContext(
+      "event",
+      "LLM Analysis",
+      "Review original vs redacted context side-by-side, then edit prompt before send. `Copy Event Text` remains unredacted.",
       "Original Event Context (local only, unredacted)",
       "Redacted Event Context Preview",
       selectedEventOriginalContext,
@@ -3338,6 +4079,101 @@ export default function App() {
     }
   }
 
+  async function saveDisplayTimezone(): Promise<void> {
+    setLastError("");
+    setTimezoneMessage("");
+    try {
+      const saved = await setDisplayTimezone(timezoneDraft.trim() || "system");
+      setDisplayTimezoneState(saved);
+      setTimezoneDraft(saved);
+      setTimezoneMessage(`Exports and reports now use ${saved === "system" ? "the system time zone" : saved}.`);
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  async function pickExportDirectory(): Promise<void> {
+    setLastError("");
+    try {
+      const chosen = await chooseExportDirectory();
+      if (chosen) setExportDirectoryState(chosen);
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  async function clearExportDirectory(): Promise<void> {
+    setLastError("");
+    try {
+      await setExportDirectory(null);
+      setExportDirectoryState(null);
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  async function runPermissionChecks(): Promise<void> {
+    setLastError("");
+    setPreflightRunning(true);
+    try {
+      setPreflightReport(await runPreflightChecks());
+    } catch (error) {
+      setLastError(String(error));
+    } finally {
+      setPreflightRunning(false);
+    }
+  }
+
+  async function loadSyncHistory(): Promise<void> {
+    setLastError("");
+    try {
+      setSyncHistory(await getSyncHistory(targetHostId !== "localhost" ? targetHostId : undefined, 25));
+    } catch (error) {
+      setLastError(String(error));
+    }
+  }
+
+  async function loadDbStats(): Promise<void> {
+    setLastError("");
+    try {
+      setDbStats(await getDbStats());
+      setEncryptionStatus(await getEncryptionStatus());
+    } catch (error) {
+      setLastError(String(error));
+    }
+  }
+
+  async function toggleDatabaseEncryption(): Promise<void> {
+    if (!encryptionStatus) return;
+    setLastError("");
+    setEncryptionBusy(true);
+    try {
+      setEncryptionStatus(await setDatabaseEncryption(!encryptionStatus.enabled));
+      setDbStats(await getDbStats());
+    } catch (error) {
+      setLastError(String(error));
+    } finally {
+      setEncryptionBusy(false);
+    }
+  }
+
+  async function compactDatabaseNow(): Promise<void> {
+    setLastError("");
+    setDbCompacting(true);
+    setDbCompactStatus("");
+    try {
+      const result = await compactDatabase();
+      setDbCompactStatus(
+        `Compacted from ${formatBytesApprox(result.bytesBefore)} to ${formatBytesApprox(result.bytesAfter)}.`
+      );
+      setDbStats(await getDbStats());
+    } catch (error) {
+      setLastError(String(error));
+    } finally {
+      setDbCompacting(false);
+    }
+  }
+
   async function saveIngestCollectionSettings(): Promise<void> {
     setLastError("");
     try {
@@ -3353,7 +4189,24 @@ export default function App() {
         autoSyncOnStartup: ingestProfile.autoSyncOnStartup,
         maxEventsPerSync: maxEvents,
         windowsChannels: channels,
-        requestElevation: ingestProfile.requestElevation ?? false
+        requestElevation: ingestProfile.requestElevation ?? false,
+        collectSmart: ingestProfile.collectSmart ?? false,
+        maxDatabaseMb: Math.max(0, Math.floor(ingestProfile.maxDatabaseMb ?? 0)),
+        journalFilter: {
+          units: ingestProfile.journalFilter.units.map((value) => value.trim()).filter(Boolean),
+          identifiers: ingestProfile.journalFilter.identifiers.map((value) => value.trim()).filter(Boolean),
+          priority: ingestProfile.journalFilter.priority
+        },
+        unifiedLogFilter: { ...ingestProfile.unifiedLogFilter, predicate: ingestProfile.unifiedLogFilter.predicate.trim() },
+        syncIntervalMinutes: Math.max(0, Math.floor(ingestProfile.syncIntervalMinutes ?? 0)),
+        retention: {
+          maxAgeDays: Math.max(0, Math.floor(ingestProfile.retention?.maxAgeDays ?? 0)),
+          maxEvents: Math.max(0, Math.floor(ingestProfile.retention?.maxEvents ?? 0)),
+          archiveDirectory: (ingestProfile.retention?.archiveDirectory ?? "").trim(),
+          objectStore: ingestProfile.retention?.objectStore ?? EMPTY_OBJECT_STORE
+        },
+        messageLocale: (ingestProfile.messageLocale ?? "").trim() || "en-US",
+        collapseRepeats: ingestProfile.collapseRepeats ?? false
       });
       setIngestProfileState(saved);
       setExportStatus("Collection settings saved.");
@@ -3363,41 +4216,123 @@ export default function App() {
     }
   }
 
-
-
-  async function saveRemoteHostSettings(): Promise<void> {
+  async function pruneStoredEventsNow(): Promise<void> {
+    setRetentionRunning(true);
     try {
-      const saved = await saveRemoteSettings(remoteSettings);
-      setRemoteSettingsState(saved);
-      setRemoteProviderTokenDrafts((current) =>
-        saved.providerAccounts.reduce<Record<string, string>>((acc, account) => {
-          acc[account.id] = current[account.id] ?? "";
-          return acc;
-        }, {})
-      );
-      setRemoteProfileSecretDrafts((current) =>
-        saved.profiles.reduce<Record<string, string>>((acc, profile) => {
-          acc[profile.id] = current[profile.id] ?? "";
-          return acc;
-        }, {})
-      );
-      setExportStatus("Remote settings saved successfully.");
-      window.setTimeout(() => setExportStatus(""), 2500);
-    } catch (e: any) {
-      setLastError(e.message || "Failed to save remote settings");
+      const report = await runRetentionNow();
+      setRetentionReport(report);
+      if (report.deletedEvents > 0) {
+        const events = await getLocalEvents(undefined, LOCAL_FETCH_LIMIT);
+        applyLocalEventsCache(events, "Retention reload");
+      }
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to apply retention policy.");
+    } finally {
+      setRetentionRunning(false);
     }
   }
 
-  function updateRemoteProviderAccount(id: string, key: keyof RemoteProviderAccount, value: string | boolean) {
-    setRemoteSettingsState((prev) => ({
-      ...prev,
-      providerAccounts: prev.providerAccounts.map((account) =>
-        account.id === id ? { ...account, [key]: value } : account
-      )
+  function updateObjectStore<K extends keyof ObjectStoreSettings>(key: K, value: ObjectStoreSettings[K]): void {
+    setIngestProfileState((current) => ({
+      ...current,
+      retention: { ...current.retention, objectStore: { ...current.retention.objectStore, [key]: value } }
     }));
   }
 
-  async function saveRemoteProviderTokenNow(account: RemoteProviderAccount): Promise<void> {
+  async function saveArchiveStoreSecretNow(): Promise<void> {
+    const secret = archiveSecretDraft.trim();
+    if (!secret) {
+      setLastError("Enter the secret access key or SAS token first.");
+      return;
+    }
+    try {
+      await saveArchiveStoreSecret(secret);
+      setArchiveSecretDraft("");
+      setExportStatus("Archive credential saved to OS keychain.");
+      window.setTimeout(() => setExportStatus(""), 2500);
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to save archive credential.");
+    }
+  }
+
+  async function clearArchiveStoreSecretNow(): Promise<void> {
+    try {
+      await clearArchiveStoreSecret();
+      setExportStatus("Archive credential removed from OS keychain.");
+      window.setTimeout(() => setExportStatus(""), 2500);
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to clear archive credential.");
+    }
+  }
+
+  async function runArchiveSearch(): Promise<void> {
+    if (!archiveStart || !archiveEnd) {
+      setLastError("Choose the first and last day to search.");
+      return;
+    }
+    setArchiveBusy(true);
+    try {
+      const report = await searchArchive(archiveQuery.trim(), archiveStart, archiveEnd);
+      setArchiveResult(report);
+      setArchiveSelectedDays([]);
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Archive search failed.");
+    } finally {
+      setArchiveBusy(false);
+    }
+  }
+
+  async function rehydrateSelectedArchiveDays(): Promise<void> {
+    setArchiveBusy(true);
+    try {
+      const report = await rehydrateArchiveDays(archiveSelectedDays);
+      setArchiveSelectedDays([]);
+      const events = await getLocalEvents(undefined, LOCAL_FETCH_LIMIT);
+      applyLocalEventsCache(events, "Archive rehydrate");
+      setExportStatus(`Rehydrated ${report.events.toLocaleString()} events from ${report.days} archived day(s).`);
+      window.setTimeout(() => setExportStatus(""), 4000);
+    } catch (error) {
+      setLastError(error instanceof Error ? error.message : "Failed to rehydrate archived days.");
+    } finally {
+      setArchiveBusy(false);
+    }
+  }
+
+
+
+  async function saveRemoteHostSettings(): Promise<void> {
+    try {
+      const saved = await saveRemoteSettings(remoteSettings);
+      setRemoteSettingsState(saved);
+      setRemoteProviderTokenDrafts((current) =>
+        saved.providerAccounts.reduce<Record<string, string>>((acc, account) => {
+          acc[account.id] = current[account.id] ?? "";
+          return acc;
+        }, {})
+      );
+      setRemoteProfileSecretDrafts((current) =>
+        saved.profiles.reduce<Record<string, string>>((acc, profile) => {
+          acc[profile.id] = current[profile.id] ?? "";
+          return acc;
+        }, {})
+      );
+      setExportStatus("Remote settings saved successfully.");
+      window.setTimeout(() => setExportStatus(""), 2500);
+    } catch (e: any) {
+      setLastError(e.message || "Failed to save remote settings");
+    }
+  }
+
+  function updateRemoteProviderAccount(id: string, key: keyof RemoteProviderAccount, value: string | boolean) {
+    setRemoteSettingsState((prev) => ({
+      ...prev,
+      providerAccounts: prev.providerAccounts.map((account) =>
+        account.id === id ? { ...account, [key]: value } : account
+      )
+    }));
+  }
+
+  async function saveRemoteProviderTokenNow(account: RemoteProviderAccount): Promise<void> {
     const secret = (remoteProviderTokenDrafts[account.id] || "").trim();
     if (!secret) {
       setLastError(`Enter a token for ${account.name} first.`);
@@ -3559,6 +4494,9 @@ export default function App() {
           current.sshKeyPath = "";
           current.authType = "key";
         }
+        if ((value === "winrm" || value === "rpc") && current.authType === "agent") {
+          current.authType = "key";
+        }
         if (value === "ssh" || value === "winrm" || value === "rpc") {
           current.providerDeviceId = null;
           current.providerLastResolvedAt = null;
@@ -3821,13 +4759,24 @@ export default function App() {
     return "mixed";
   }
 
-  function buildSuggestedExportFilename(format: ExportFormat, scope: ExportScope, filters: ExportWizardFilters): string {
+  function buildSuggestedExportFilename(
+    format: ExportFormat,
+    scope: ExportScope,
+    filters: ExportWizardFilters,
+    compression: ExportCompression = "none"
+  ): string {
     const timestamp = formatExportTimestamp();
     const scopeTag = scope === "loaded" ? "loaded" : getExportTypeTag(filters);
-    return `${timestamp}-hermes-${scopeTag}.${format}`;
+    const suffix = compression === "none" ? "" : `.${compression}`;
+    return `${timestamp}-hermes-${scopeTag}.${format}${suffix}`;
   }
 
-  async function exportEvents(format: ExportFormat, events: NormalizedEvent[], filename: string): Promise<void> {
+  async function exportEvents(
+    format: ExportFormat,
+    events: NormalizedEvent[],
+    filename: string,
+    compression: ExportCompression = "none"
+  ): Promise<void> {
     if (events.length === 0) {
       setLastError("There are no events in the selected export scope.");
       return;
@@ -3835,7 +4784,11 @@ export default function App() {
     setLastError("");
     try {
       if (isTauriRuntime()) {
-        const location = await exportEventsWithDialog(format, filename, events);
+        const location = await exportEventsWithDialog(
+          compression === "none" ? format : `${format}.${compression}`,
+          filename,
+          events
+        );
         if (!location) {
           setExportStatus("Export canceled.");
           window.setTimeout(() => setExportStatus(""), 2000);
@@ -3846,8 +4799,16 @@ export default function App() {
         return;
       }
 
+      if (format === "parquet" || format === "cef" || format === "leef") {
+        throw new Error(`${format.toUpperCase()} export requires desktop runtime.`);
+      }
+      if (compression !== "none") {
+        throw new Error("Compressed export requires desktop runtime.");
+      }
       if (format === "json") {
         exportAsJson(events, filename);
+      } else if (format === "ndjson") {
+        exportAsNdjson(events, filename);
       } else if (format === "csv") {
         exportAsCsv(events, filename);
       } else {
@@ -3861,8 +4822,9 @@ export default function App() {
   }
 
   async function runExportWizard(): Promise<void> {
-    const filename = buildSuggestedExportFilename(exportFormat, exportScope, exportFilters);
-    await exportEvents(exportFormat, exportPreviewEvents, filename);
+    const compression = exportFormat === "parquet" ? "none" : exportCompression;
+    const filename = buildSuggestedExportFilename(exportFormat, exportScope, exportFilters, compression);
+    await exportEvents(exportFormat, exportPreviewEvents, filename, compression);
   }
 
   function openHelpTab(section: HelpSectionId = "quick-start"): void {
@@ -3905,6 +4867,14 @@ export default function App() {
                 {remoteSettings.profiles.map(p => (
                   <option key={p.id} value={p.id}>{p.name} ({p.host})</option>
                 ))}
+                <option value={ALL_HOSTS_TARGET}>All Hosts</option>
+                {sourceHosts
+                  .filter(entry => entry.host !== "localhost" && !remoteSettings.profiles.some(p => p.host === entry.host))
+                  .map(entry => (
+                    <option key={entry.host} value={hostTargetId(entry.host)}>
+                      {entry.host} (stored, {entry.eventCount.toLocaleString()} events)
+                    </option>
+                  ))}
               </select>
             </div>
             <Button variant="primary" onClick={() => void refreshNow()} disabled={isLoading}>
@@ -3980,9 +4950,39 @@ export default function App() {
             {memoryNotice}
           </div>
         )}
+        {healthAlert && (
+          <div className={cn(panelClass, "border-panel-border bg-[var(--sev-warning)] text-text px-4 py-3 text-sm")}>
+            <div className="flex items-start justify-between gap-3">
+              <div className="grid gap-1">
+                {healthAlert.findings.map((finding) => (
+                  <p key={finding.id}>
+                    <strong>{finding.title}.</strong> {finding.detail}
+                  </p>
+                ))}
+              </div>
+              <Button size="sm" onClick={() => setHealthAlert(null)}>Dismiss</Button>
+            </div>
+          </div>
+        )}
         {exportStatus && (
           <div className={cn(panelClass, "border-ok text-ok")}>{exportStatus}</div>
         )}
+        {syncProgress && (
+          <div className={cn(panelClass, "grid gap-2 px-4 py-3 text-sm text-text")}>
+            <div>
+              Collecting{syncProgress.currentSource ? ` ${syncProgress.currentSource}` : ""}:{" "}
+              {syncProgress.collected.toLocaleString()} events so far
+            </div>
+            <div className="h-1.5 overflow-hidden rounded-full bg-[var(--field-bg)]">
+              <div
+                className="h-full rounded-full bg-accent transition-[width]"
+                style={{
+                  width: `${Math.min(100, (syncProgress.collected / Math.max(1, ingestProfile.maxEventsPerSync)) * 100)}%`
+                }}
+              />
+            </div>
+          </div>
+        )}
 
         <section className={cn(panelClass, "flex flex-wrap items-center gap-2 px-4 py-3")}>
           {workspaceTabs.map((tab) => (
@@ -4301,7 +5301,7 @@ export default function App() {
                 <input
                   className={cn(inputClass, "text-xs")}
                   type="file"
-                  accept=".json,.csv"
+                  accept=".json,.ndjson,.jsonl,.csv"
                   onChange={(event) => void onImport(event.target.files)}
                 />
               </label>
@@ -4353,8 +5353,22 @@ export default function App() {
                   onChange={(event) => setExportFormat(event.target.value as ExportFormat)}
                 >
                   <option value="json">JSON</option>
+                  <option value="ndjson">NDJSON (one event per line)</option>
                   <option value="csv">CSV</option>
                   <option value="txt">Plain Text (.txt)</option>
+                  <option value="parquet">Parquet (DuckDB, Spark)</option>
+                  <option value="cef">CEF (ArcSight)</option>
+                  <option value="leef">LEEF (QRadar)</option>
+                </select>
+                <select
+                  className={selectClass}
+                  value={exportFormat === "parquet" ? "none" : exportCompression}
+                  disabled={exportFormat === "parquet"}
+                  onChange={(event) => setExportCompression(event.target.value as ExportCompression)}
+                >
+                  <option value="none">No compression</option>
+                  <option value="gz">Gzip (.gz)</option>
+                  <option value="zip">Zip archive (.zip)</option>
                 </select>
                 <div className="text-[11px] text-muted">
                   Export opens a save dialog so you can pick destination and filename before writing.
@@ -4541,6 +5555,26 @@ export default function App() {
                 />
                 Allow elevated restart for restricted logs
               </label>
+              <label className="flex items-center gap-2 text-xs text-muted">
+                <input
+                  type="checkbox"
+                  checked={Boolean(ingestProfile.collectSmart)}
+                  onChange={(e) =>
+                    setIngestProfileState((current) => ({ ...current, collectSmart: e.target.checked }))
+                  }
+                />
+                Collect drive health (SMART) data on refresh
+              </label>
+              <label className="flex items-center gap-2 text-xs text-muted">
+                <input
+                  type="checkbox"
+                  checked={Boolean(ingestProfile.collapseRepeats)}
+                  onChange={(e) =>
+                    setIngestProfileState((current) => ({ ...current, collapseRepeats: e.target.checked }))
+                  }
+                />
+                Collapse repeated messages at ingest (identical lines within 5 minutes are stored once with a count)
+              </label>
               <div className="grid gap-2 md:grid-cols-[220px_1fr]">
                 <label className="text-xs text-muted">Max events per sync</label>
                 <input
@@ -4557,28 +5591,618 @@ export default function App() {
                   }
                 />
               </div>
+              <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                <label className="text-xs text-muted">Background sync every (minutes, 0 = off)</label>
+                <input
+                  className={inputClass}
+                  type="number"
+                  min={0}
+                  max={1440}
+                  value={ingestProfile.syncIntervalMinutes ?? 0}
+                  onChange={(e) =>
+                    setIngestProfileState((current) => ({
+                      ...current,
+                      syncIntervalMinutes: Number(e.target.value)
+                    }))
+                  }
+                />
+              </div>
+              <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                <label className="text-xs text-muted">Max database size (MB, 0 = unlimited)</label>
+                <input
+                  className={inputClass}
+                  type="number"
+                  min={0}
+                  value={ingestProfile.maxDatabaseMb ?? 0}
+                  onChange={(e) =>
+                    setIngestProfileState((current) => ({
+                      ...current,
+                      maxDatabaseMb: Number(e.target.value)
+                    }))
+                  }
+                />
+              </div>
+              <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                <label className="text-xs text-muted">Keep events for (days, 0 = forever)</label>
+                <input
+                  className={inputClass}
+                  type="number"
+                  min={0}
+                  max={3650}
+                  value={ingestProfile.retention?.maxAgeDays ?? 0}
+                  onChange={(e) =>
+                    setIngestProfileState((current) => ({
+                      ...current,
+                      retention: { ...current.retention, maxAgeDays: Number(e.target.value) }
+                    }))
+                  }
+                />
+              </div>
+              <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                <label className="text-xs text-muted">Max stored events (0 = unlimited)</label>
+                <input
+                  className={inputClass}
+                  type="number"
+                  min={0}
+                  step={1000}
+                  value={ingestProfile.retention?.maxEvents ?? 0}
+                  onChange={(e) =>
+                    setIngestProfileState((current) => ({
+                      ...current,
+                      retention: { ...current.retention, maxEvents: Number(e.target.value) }
+                    }))
+                  }
+                />
+              </div>
+              <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                <label className="text-xs text-muted">Archive pruned events to (folder, empty = off)</label>
+                <input
+                  className={inputClass}
+                  placeholder="/mnt/log-archive"
+                  value={ingestProfile.retention?.archiveDirectory ?? ""}
+                  onChange={(e) =>
+                    setIngestProfileState((current) => ({
+                      ...current,
+                      retention: { ...current.retention, archiveDirectory: e.target.value }
+                    }))
+                  }
+                />
+              </div>
+              <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                <label className="text-xs text-muted">Or archive to a bucket</label>
+                <select
+                  className={selectClass}
+                  value={ingestProfile.retention?.objectStore?.kind ?? ""}
+                  onChange={(e) => updateObjectStore("kind", e.target.value)}
+                >
+                  <option value="">No (use the folder)</option>
+                  <option value="s3">Amazon S3 or S3-compatible</option>
+                  <option value="azure">Azure Blob Storage</option>
+                </select>
+              </div>
+              {ingestProfile.retention?.objectStore?.kind === "s3" && (
+                <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                  <label className="text-xs text-muted">Bucket</label>
+                  <input
+                    className={inputClass}
+                    placeholder="log-archive"
+                    value={ingestProfile.retention.objectStore.bucket}
+                    onChange={(e) => updateObjectStore("bucket", e.target.value)}
+                  />
+                  <label className="text-xs text-muted">Region</label>
+                  <input
+                    className={inputClass}
+                    placeholder="us-east-1"
+                    value={ingestProfile.retention.objectStore.region}
+                    onChange={(e) => updateObjectStore("region", e.target.value)}
+                  />
+                  <label className="text-xs text-muted">Endpoint (S3-compatible only)</label>
+                  <input
+                    className={inputClass}
+                    placeholder="https://minio.example.net:9000"
+                    value={ingestProfile.retention.objectStore.endpoint}
+                    onChange={(e) => updateObjectStore("endpoint", e.target.value)}
+                  />
+                  <label className="text-xs text-muted">Access key ID</label>
+                  <input
+                    className={inputClass}
+                    value={ingestProfile.retention.objectStore.accessKeyId}
+                    onChange={(e) => updateObjectStore("accessKeyId", e.target.value)}
+                  />
+                </div>
+              )}
+              {ingestProfile.retention?.objectStore?.kind === "azure" && (
+                <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                  <label className="text-xs text-muted">Container URL</label>
+                  <input
+                    className={inputClass}
+                    placeholder="https://account.blob.core.windows.net/log-archive"
+                    value={ingestProfile.retention.objectStore.containerUrl}
+                    onChange={(e) => updateObjectStore("containerUrl", e.target.value)}
+                  />
+                </div>
+              )}
+              {(ingestProfile.retention?.objectStore?.kind ?? "") !== "" && (
+                <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                  <label className="text-xs text-muted">Key prefix</label>
+                  <input
+                    className={inputClass}
+                    placeholder="hermes/"
+                    value={ingestProfile.retention.objectStore.prefix}
+                    onChange={(e) => updateObjectStore("prefix", e.target.value)}
+                  />
+                  <label className="text-xs text-muted">
+                    {ingestProfile.retention.objectStore.kind === "s3" ? "Secret access key" : "SAS token"} (stored in OS keychain)
+                  </label>
+                  <div className="flex flex-wrap gap-2">
+                    <input
+                      className={inputClass}
+                      type="password"
+                      value={archiveSecretDraft}
+                      placeholder="Enter to replace the stored credential"
+                      onChange={(e) => setArchiveSecretDraft(e.target.value)}
+                    />
+                    <Button size="sm" onClick={() => void saveArchiveStoreSecretNow()}>
+                      Save Credential
+                    </Button>
+                    <Button size="sm" onClick={() => void clearArchiveStoreSecretNow()}>
+                      Clear Credential
+                    </Button>
+                  </div>
+                </div>
+              )}
               {hostOs === "windows" && (
                 <div className="grid gap-2">
-                  <div className="text-xs text-muted">Windows Event Logs to ingest</div>
-                  <div className="flex flex-wrap gap-3">
-                    {windowsChannelOptions.map((channel) => (
-                      <label key={channel} className="flex items-center gap-2 text-xs text-muted">
-                        <input
-                          type="checkbox"
-                          checked={ingestProfile.windowsChannels.includes(channel)}
-                          onChange={(e) => toggleWindowsChannel(channel, e.target.checked)}
-                        />
-                        {channel}
-                      </label>
-                    ))}
+                  <div className="text-xs text-muted">Windows Event Logs to ingest</div>
+                  <div className="flex flex-wrap gap-3">
+                    {windowsChannelOptions.map((channel) => (
+                      <label key={channel} className="flex items-center gap-2 text-xs text-muted">
+                        <input
+                          type="checkbox"
+                          checked={ingestProfile.windowsChannels.includes(channel)}
+                          onChange={(e) => toggleWindowsChannel(channel, e.target.checked)}
+                        />
+                        {channel}
+                      </label>
+                    ))}
+                  </div>
+                  <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                    <label className="text-xs text-muted">Event message language</label>
+                    <input
+                      className={inputClass}
+                      list="windows-message-locales"
+                      placeholder="en-US"
+                      value={ingestProfile.messageLocale ?? "en-US"}
+                      onChange={(e) =>
+                        setIngestProfileState((current) => ({
+                          ...current,
+                          messageLocale: e.target.value
+                        }))
+                      }
+                    />
+                    <datalist id="windows-message-locales">
+                      <option value="en-US" />
+                      <option value="system" />
+                      <option value="de-DE" />
+                      <option value="fr-FR" />
+                      <option value="es-ES" />
+                      <option value="ja-JP" />
+                    </datalist>
+                  </div>
+                  <div className="text-[11px] text-muted">
+                    Locale name used to format event messages, or "system" for the OS display language. Publishers without
+                    resources for the locale fall back to the display language.
+                  </div>
+                </div>
+              )}
+              {(hostOs === "linux" || remoteSettings.profiles.some((profile) => profile.os === "linux")) && (
+                <div className="grid gap-2">
+                  <div className="text-xs text-muted">Linux journal filters (empty = entire journal)</div>
+                  <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                    <label className="text-xs text-muted">Units (-u, comma separated)</label>
+                    <input
+                      className={inputClass}
+                      placeholder="sshd.service, myapp.service"
+                      value={ingestProfile.journalFilter.units.join(", ")}
+                      onChange={(e) =>
+                        setIngestProfileState((current) => ({
+                          ...current,
+                          journalFilter: { ...current.journalFilter, units: e.target.value.split(",").map((value) => value.trimStart()) }
+                        }))
+                      }
+                    />
+                    <label className="text-xs text-muted">Identifiers (-t, comma separated)</label>
+                    <input
+                      className={inputClass}
+                      placeholder="kernel"
+                      value={ingestProfile.journalFilter.identifiers.join(", ")}
+                      onChange={(e) =>
+                        setIngestProfileState((current) => ({
+                          ...current,
+                          journalFilter: { ...current.journalFilter, identifiers: e.target.value.split(",").map((value) => value.trimStart()) }
+                        }))
+                      }
+                    />
+                    <label className="text-xs text-muted">Maximum priority (-p)</label>
+                    <select
+                      className={selectClass}
+                      value={ingestProfile.journalFilter.priority}
+                      onChange={(e) =>
+                        setIngestProfileState((current) => ({
+                          ...current,
+                          journalFilter: { ...current.journalFilter, priority: e.target.value }
+                        }))
+                      }
+                    >
+                      <option value="">All priorities</option>
+                      <option value="err">err and above</option>
+                      <option value="warning">warning and above</option>
+                      <option value="notice">notice and above</option>
+                      <option value="info">info and above</option>
+                    </select>
+                  </div>
+                </div>
+              )}
+              {(hostOs === "macos" || remoteSettings.profiles.some((profile) => profile.os === "macos" && profile.protocol === "ssh")) && (
+                <div className="grid gap-2">
+                  <div className="text-xs text-muted">macOS unified log filter (empty predicate = all entries)</div>
+                  <div className="grid gap-2 md:grid-cols-[220px_1fr]">
+                    <label className="text-xs text-muted">Predicate (--predicate)</label>
+                    <input
+                      className={inputClass}
+                      placeholder={'subsystem == "com.apple.TimeMachine" OR messageType == error'}
+                      value={ingestProfile.unifiedLogFilter.predicate}
+                      onChange={(e) =>
+                        setIngestProfileState((current) => ({
+                          ...current,
+                          unifiedLogFilter: { ...current.unifiedLogFilter, predicate: e.target.value }
+                        }))
+                      }
+                    />
+                  </div>
+                  <div className="flex flex-wrap gap-3">
+                    <label className="flex items-center gap-2 text-xs text-muted">
+                      <input
+                        type="checkbox"
+                        checked={ingestProfile.unifiedLogFilter.includeInfo}
+                        onChange={(e) =>
+                          setIngestProfileState((current) => ({
+                            ...current,
+                            unifiedLogFilter: { ...current.unifiedLogFilter, includeInfo: e.target.checked }
+                          }))
+                        }
+                      />
+                      Include info (--info)
+                    </label>
+                    <label className="flex items-center gap-2 text-xs text-muted">
+                      <input
+                        type="checkbox"
+                        checked={ingestProfile.unifiedLogFilter.includeDebug}
+                        onChange={(e) =>
+                          setIngestProfileState((current) => ({
+                            ...current,
+                            unifiedLogFilter: { ...current.unifiedLogFilter, includeDebug: e.target.checked }
+                          }))
+                        }
+                      />
+                      Include debug (--debug)
+                    </label>
+                  </div>
+                </div>
+              )}
+              <div className="flex flex-wrap gap-2">
+                <Button variant="primary" onClick={() => void saveIngestCollectionSettings()}>
+                  Save Collection Settings
+                </Button>
+                <Button onClick={() => void pruneStoredEventsNow()} disabled={retentionRunning}>
+                  {retentionRunning ? "Pruning..." : "Prune Now"}
+                </Button>
+              </div>
+              {retentionReport && (
+                <div className="text-xs text-muted">
+                  {retentionReport.archivedEvents > 0 &&
+                    `Archived ${retentionReport.archivedEvents.toLocaleString()} events. `}
+                  Removed {retentionReport.deletedEvents.toLocaleString()} events and{" "}
+                  {retentionReport.deletedCrashes.toLocaleString()} crashes; reclaimed{" "}
+                  {formatBytesApprox(retentionReport.reclaimedBytes)} (database now{" "}
+                  {formatBytesApprox(retentionReport.fileBytesAfter)}).
+                </div>
+              )}
+              {((ingestProfile.retention?.archiveDirectory ?? "").trim() !== "" ||
+                (ingestProfile.retention?.objectStore?.kind ?? "") !== "") && (
+                <div className="grid gap-2">
+                  <div className="text-xs text-muted">Search archived days (up to 31 at a time)</div>
+                  <div className="flex flex-wrap items-center gap-2">
+                    <input
+                      className={inputClass}
+                      placeholder="Words in message, provider or host"
+                      value={archiveQuery}
+                      onChange={(e) => setArchiveQuery(e.target.value)}
+                    />
+                    <input className={inputClass} type="date" value={archiveStart} onChange={(e) => setArchiveStart(e.target.value)} />
+                    <input className={inputClass} type="date" value={archiveEnd} onChange={(e) => setArchiveEnd(e.target.value)} />
+                    <Button onClick={() => void runArchiveSearch()} disabled={archiveBusy}>
+                      Search Archive
+                    </Button>
+                    <Button
+                      onClick={() => void rehydrateSelectedArchiveDays()}
+                      disabled={archiveBusy || archiveSelectedDays.length === 0}
+                    >
+                      Rehydrate Selected
+                    </Button>
+                  </div>
+                  {archiveResult && (
+                    <div className="grid gap-1 text-xs text-muted">
+                      {archiveResult.partitions.length === 0 && <div>No archived days match.</div>}
+                      {archiveResult.partitions.map((partition) => (
+                        <label key={partition.day} className="flex items-center gap-2">
+                          <input
+                            type="checkbox"
+                            checked={archiveSelectedDays.includes(partition.day)}
+                            onChange={(e) =>
+                              setArchiveSelectedDays((current) =>
+                                e.target.checked ? [...current, partition.day] : current.filter((day) => day !== partition.day)
+                              )
+                            }
+                          />
+                          {partition.day}: {partition.matches.toLocaleString()} of {partition.events.toLocaleString()} events (
+                          {formatBytesApprox(partition.bytes)})
+                        </label>
+                      ))}
+                    </div>
+                  )}
+                </div>
+              )}
+            </div>
+            <div className="grid gap-3 border-t border-panel-border pt-4">
+              <div className="text-sm font-semibold">Time Zone</div>
+              <div className="grid gap-2 md:grid-cols-[220px_1fr_auto]">
+                <label className="text-xs text-muted">Exports and reports</label>
+                <input
+                  className={inputClass}
+                  list="display-timezones"
+                  placeholder="system"
+                  value={timezoneDraft}
+                  onChange={(e) => setTimezoneDraft(e.target.value)}
+                />
+                <Button size="sm" onClick={() => void saveDisplayTimezone()} disabled={timezoneDraft.trim() === displayTimezone}>
+                  Save
+                </Button>
+                <datalist id="display-timezones">
+                  <option value="system" />
+                  <option value="UTC" />
+                  {timezoneOptions.map((zone) => (
+                    <option key={zone} value={zone} />
+                  ))}
+                </datalist>
+              </div>
+              <div className="text-[11px] text-muted">
+                "system" follows the operating system, "UTC" keeps stored times as they are, or pick an IANA zone such as
+                Europe/Berlin. Events are always stored in UTC.
+              </div>
+              {timezoneMessage && <div className="text-xs text-ok">{timezoneMessage}</div>}
+            </div>
+            <div className="grid gap-3 border-t border-panel-border pt-4">
+              <div className="text-sm font-semibold">Export Folder</div>
+              <div className="grid gap-2 md:grid-cols-[220px_1fr_auto_auto]">
+                <label className="text-xs text-muted">Save exports to</label>
+                <div className="break-all text-xs">{exportDirectory ?? "Downloads (default)"}</div>
+                <Button size="sm" onClick={() => void pickExportDirectory()}>
+                  Choose...
+                </Button>
+                <Button size="sm" onClick={() => void clearExportDirectory()} disabled={!exportDirectory}>
+                  Use Default
+                </Button>
+              </div>
+            </div>
+            <div className="grid gap-3 border-t border-panel-border pt-4">
+              <div className="flex items-center justify-between gap-2">
+                <div className="text-sm font-semibold">Permissions</div>
+                <Button size="sm" onClick={() => void runPermissionChecks()} disabled={preflightRunning}>
+                  {preflightRunning ? "Checking..." : "Run Permission Checks"}
+                </Button>
+              </div>
+              {preflightReport && (
+                <div className="grid gap-2">
+                  {preflightReport.checks.map((check) => (
+                    <div key={check.id} className="rounded-lg border border-panel-border bg-[var(--field-bg)] px-3 py-2">
+                      <div className="flex items-center justify-between gap-2">
+                        <div className="text-sm text-text">{check.title}</div>
+                        <span
+                          className={cn(
+                            "rounded px-2 py-0.5 text-[10px] uppercase tracking-wide text-text",
+                            check.status === "pass" && "bg-[var(--sev-info)]",
+                            check.status === "warning" && "bg-[var(--sev-warning)]",
+                            check.status === "fail" && "bg-danger-bg text-danger",
+                            check.status === "skipped" && "text-muted"
+                          )}
+                        >
+                          {check.status}
+                        </span>
+                      </div>
+                      <div className="mt-1 text-xs text-muted">{check.detail}</div>
+                      {check.remediation && <div className="mt-1 text-xs text-text">{check.remediation}</div>}
+                    </div>
+                  ))}
+                  {preflightReport.checks.length === 0 && (
+                    <div className="text-xs text-muted">No permission checks apply to this platform.</div>
+                  )}
+                </div>
+              )}
+            </div>
+            {workspaceSettings && (
+              <div className="grid gap-3 border-t border-panel-border pt-4">
+                <div className="text-sm font-semibold">Workspaces</div>
+                <div className="text-xs text-muted">
+                  Each workspace keeps its events, crashes and history in its own database.
+                </div>
+                <div className="grid gap-2">
+                  {workspaceSettings.workspaces.map((workspace) => (
+                    <div
+                      key={workspace.id}
+                      className="flex flex-wrap items-center justify-between gap-2 rounded-lg border border-panel-border bg-[var(--field-bg)] px-3 py-2"
+                    >
+                      <div>
+                        <div className="text-sm text-text">
+                          {workspace.name}
+                          {workspace.id === workspaceSettings.activeId && (
+                            <span className="ml-2 rounded bg-accent px-2 py-0.5 text-[10px] uppercase tracking-wide text-white">
+                              open
+                            </span>
+                          )}
+                          {workspace.archived && (
+                            <span className="ml-2 text-[10px] uppercase tracking-wide text-muted">archived</span>
+                          )}
+                        </div>
+                        <div className="text-xs text-muted">
+                          Created {new Date(workspace.createdAt).toLocaleDateString()}
+                          {workspace.lastOpenedAt && ` · last opened ${new Date(workspace.lastOpenedAt).toLocaleString()}`}
+                        </div>
+                      </div>
+                      {workspace.id !== workspaceSettings.activeId && (
+                        <div className="flex gap-2">
+                          <Button size="sm" onClick={() => void switchWorkspace(workspace.id)} disabled={workspaceBusy}>
+                            {workspace.archived ? "Restore & Open" : "Open"}
+                          </Button>
+                          {workspace.id !== "default" && !workspace.archived && (
+                            <Button size="sm" onClick={() => void archiveWorkspaceById(workspace.id)} disabled={workspaceBusy}>
+                              Archive
+                            </Button>
+                          )}
+                        </div>
+                      )}
+                    </div>
+                  ))}
+                </div>
+                <div className="flex flex-wrap gap-2">
+                  <input
+                    className={cn(inputClass, "min-w-[240px] flex-1")}
+                    placeholder="New workspace name (e.g. Customer A incident)"
+                    value={workspaceNameDraft}
+                    onChange={(e) => setWorkspaceNameDraft(e.target.value)}
+                  />
+                  <Button
+                    onClick={() => void createAndOpenWorkspace()}
+                    disabled={workspaceBusy || !workspaceNameDraft.trim()}
+                  >
+                    Create & Open
+                  </Button>
+                </div>
+              </div>
+            )}
+            <div className="grid gap-3 border-t border-panel-border pt-4">
+              <div className="flex items-center justify-between gap-2">
+                <div className="text-sm font-semibold">Sync History</div>
+                <Button size="sm" onClick={() => void loadSyncHistory()}>
+                  {syncHistory ? "Reload" : "Show Sync History"}
+                </Button>
+              </div>
+              {syncHistory && (
+                <div className="grid gap-2">
+                  {syncHistory.map((entry) => (
+                    <div key={entry.id} className="rounded-lg border border-panel-border bg-[var(--field-bg)] px-3 py-2">
+                      <div className="flex items-center justify-between gap-2">
+                        <div className="text-sm text-text">
+                          {new Date(entry.startedAt).toLocaleString()} · {entry.kind} · {entry.sourceHost}
+                        </div>
+                        <span
+                          className={cn(
+                            "rounded px-2 py-0.5 text-[10px] uppercase tracking-wide text-text",
+                            entry.status === "ok" && "bg-[var(--sev-info)]",
+                            entry.status === "partial" && "bg-[var(--sev-warning)]",
+                            entry.status === "failed" && "bg-danger-bg text-danger"
+                          )}
+                        >
+                          {entry.status}
+                        </span>
+                      </div>
+                      <div className="mt-1 text-xs text-muted">
+                        {entry.collected.toLocaleString()} events
+                        {entry.sources.length > 0 &&
+                          ` (${entry.sources.map((source) => `${source.logName}: ${source.count.toLocaleString()}`).join(", ")})`}
+                      </div>
+                      {[...entry.errors, ...entry.warnings].map((message, index) => (
+                        <div key={index} className="mt-1 text-xs text-text">
+                          {message}
+                        </div>
+                      ))}
+                    </div>
+                  ))}
+                  {syncHistory.length === 0 && <div className="text-xs text-muted">No syncs recorded yet.</div>}
+                </div>
+              )}
+            </div>
+            <div className="grid gap-3 border-t border-panel-border pt-4">
+              <div className="flex items-center justify-between gap-2">
+                <div className="text-sm font-semibold">Database</div>
+                <div className="flex gap-2">
+                  <Button size="sm" onClick={() => void loadDbStats()}>
+                    {dbStats ? "Reload" : "Show Database Stats"}
+                  </Button>
+                  <Button size="sm" onClick={() => void compactDatabaseNow()} disabled={dbCompacting}>
+                    {dbCompacting ? "Compacting..." : "Compact Database"}
+                  </Button>
+                </div>
+              </div>
+              {dbCompactStatus && <div className="text-xs text-muted">{dbCompactStatus}</div>}
+              {encryptionStatus && (
+                <div className="flex flex-wrap items-center justify-between gap-2 rounded-lg border border-panel-border bg-[var(--field-bg)] px-3 py-2">
+                  <div className="text-xs text-muted">
+                    {!encryptionStatus.available
+                      ? "Encryption at rest is not included in this build."
+                      : encryptionStatus.enabled
+                        ? "Encrypted with SQLCipher; the key is kept in the OS keychain."
+                        : "Stored in plain text."}
+                  </div>
+                  {encryptionStatus.available && (
+                    <Button size="sm" onClick={() => void toggleDatabaseEncryption()} disabled={encryptionBusy}>
+                      {encryptionBusy ? "Rewriting..." : encryptionStatus.enabled ? "Decrypt Database" : "Encrypt Database"}
+                    </Button>
+                  )}
+                </div>
+              )}
+              {dbStats && (
+                <div className="grid gap-2">
+                  <div className="text-xs text-muted">
+                    {dbStats.path} · {formatBytesApprox(dbStats.fileBytes)} on disk
+                    {dbStats.walBytes > 0 && ` + ${formatBytesApprox(dbStats.walBytes)} write-ahead log`} ·{" "}
+                    {formatBytesApprox(dbStats.freeBytes)} free
+                  </div>
+                  <div className="overflow-x-auto rounded-lg border border-panel-border bg-[var(--field-bg)]">
+                    <table className="w-full text-xs">
+                      <thead className="text-left text-muted">
+                        <tr>
+                          <th className="px-3 py-2">Table</th>
+                          <th className="px-3 py-2 text-right">Rows</th>
+                          <th className="px-3 py-2 text-right">Data</th>
+                          <th className="px-3 py-2 text-right">Indexes</th>
+                          <th className="px-3 py-2">Oldest</th>
+                          <th className="px-3 py-2">Newest</th>
+                        </tr>
+                      </thead>
+                      <tbody>
+                        {dbStats.tables.map((table) => (
+                          <tr
+                            key={table.name}
+                            className="border-t border-panel-border text-text"
+                            title={dbStats.indexes
+                              .filter((index) => index.table === table.name)
+                              .map((index) => `${index.name}: ${formatBytesApprox(index.bytes)}`)
+                              .join("\n")}
+                          >
+                            <td className="px-3 py-1.5">{table.name}</td>
+                            <td className="px-3 py-1.5 text-right">{table.rows.toLocaleString()}</td>
+                            <td className="px-3 py-1.5 text-right">{formatBytesApprox(table.bytes)}</td>
+                            <td className="px-3 py-1.5 text-right">{formatBytesApprox(table.indexBytes)}</td>
+                            <td className="px-3 py-1.5">{table.oldest ? new Date(table.oldest).toLocaleString() : "-"}</td>
+                            <td className="px-3 py-1.5">{table.newest ? new Date(table.newest).toLocaleString() : "-"}</td>
+                          </tr>
+                        ))}
+                      </tbody>
+                    </table>
                   </div>
                 </div>
               )}
-              <div className="flex flex-wrap gap-2">
-                <Button variant="primary" onClick={() => void saveIngestCollectionSettings()}>
-                  Save Collection Settings
-                </Button>
-              </div>
             </div>
             <div className="grid gap-3 border-t border-panel-border pt-4">
               <div className="text-sm font-semibold">Remote Hosts (Connections)</div>
@@ -4723,6 +6347,7 @@ export default function App() {
                               <label className="text-xs text-muted">Auth Type
                                 <select className={selectClass} value={selectedRemoteProfile.authType} onChange={e => updateSelectedRemoteProfile("authType", e.target.value)}>
                                   <option value="key">SSH Key / OS Keychain</option>
+                                  <option value="agent">SSH Agent</option>
                                   <option value="password">Password</option>
                                 </select>
                               </label>
@@ -4762,7 +6387,7 @@ export default function App() {
                         )}
                         {selectedRemoteProfile.protocol === "ssh" && selectedRemoteProfile.authType === "password" && (
                           <div className="rounded-lg border border-panel-border bg-panel px-3 py-2 text-xs text-muted">
-                            SSH password authentication is not implemented yet. Use SSH key-based auth for Linux/macOS remote collection.
+                            SSH password authentication is not implemented yet. Use an SSH key or a running ssh-agent for Linux/macOS remote collection.
                           </div>
                         )}
                         {(selectedRemoteProfile.protocol === "jamf" || (selectedRemoteProfile.os === "macos" && selectedRemoteProfile.protocol === "intune")) && (
@@ -5028,6 +6653,24 @@ export default function App() {
                             placeholder="https://host/v1"
                           />
                         </label>
+                        <label className="text-xs text-muted">
+                          Context window (tokens)
+                          <input
+                            className={inputClass}
+                            type="number"
+                            min={0}
+                            step={1024}
+                            value={llmSelectedProfile.contextWindowTokens ?? 0}
+                            onChange={(e) =>
+                              updateLlmProfile(llmSelectedProfile.id, {
+                                contextWindowTokens: Math.max(0, Math.floor(Number(e.target.value) || 0))
+                              })
+                            }
+                          />
+                          <span className="mt-1 block text-[11px]">
+                            Prompts are trimmed to fit this window. Leave at 0 to use the provider default.
+                          </span>
+                        </label>
                         <div className="grid gap-2 md:grid-cols-[1fr_auto_auto] md:items-end">
                           <label className="text-xs text-muted">
                             API key (stored in OS keychain)
@@ -5273,6 +6916,44 @@ export default function App() {
                 Select a crash and click Investigate Pre-Crash to load matching events below.
               </div>
             )}
+            {crashKnownIssues.length > 0 && <KnownIssueList matches={crashKnownIssues} />}
+            <div className="flex flex-wrap items-center gap-2 border-t border-panel-border pt-3">
+              <label className="text-xs text-muted">Changes in the last</label>
+              <select
+                className={cn(selectClass, "w-28 text-xs")}
+                value={recentChangesHours}
+                onChange={(e) => setRecentChangesHours(Number(e.target.value))}
+              >
+                <option value={6}>6 hours</option>
+                <option value={24}>24 hours</option>
+                <option value={72}>3 days</option>
+                <option value={168}>7 days</option>
+              </select>
+              <Button size="sm" onClick={() => void loadRecentChangesNow()} disabled={!selectedCrash || isLoadingRecentChanges}>
+                {isLoadingRecentChanges ? "Checking..." : "What Changed Before"}
+              </Button>
+              {recentChanges && (
+                <span className="text-xs text-muted">
+                  {recentChanges.changes.length === 0
+                    ? `No driver, update, service or configuration changes in the ${recentChanges.hours} hours before this crash.`
+                    : Object.entries(recentChanges.counts)
+                        .map(([kind, count]) => `${count} ${kind}`)
+                        .join(", ")}
+                </span>
+              )}
+            </div>
+            {recentChanges && recentChanges.changes.length > 0 && (
+              <ul className="max-h-56 space-y-1 overflow-auto rounded-lg border border-panel-border bg-[var(--field-bg)] px-3 py-2 text-xs">
+                {recentChanges.changes.map((change) => (
+                  <li key={change.referenceId} className="flex flex-wrap gap-2">
+                    <span className="w-20 shrink-0 text-muted">{change.minutesBeforeCrash} min before</span>
+                    <span className="w-16 shrink-0 font-semibold uppercase text-muted">{change.kind}</span>
+                    <span className="font-semibold">{change.title}</span>
+                    <span className="text-muted">{change.detail}</span>
+                  </li>
+                ))}
+              </ul>
+            )}
             {selectedCrash && isDumpBackedCrash(selectedCrash) && (
               <div className="space-y-3 rounded-xl border border-panel-border bg-[var(--field-bg)] p-3">
                 <div className="flex flex-wrap items-start justify-between gap-3">
@@ -5390,7 +7071,7 @@ export default function App() {
             )}
             {selectedCrash && correlatedEvents.length > 0 && (
               <div className="flex flex-wrap gap-2 border-t border-panel-border pt-3">
-                <div className="w-full text-sm font-semibold">Top Correlated Events (+/-15m)</div>
+                <div className="w-full text-sm font-semibold">Most Relevant Events (+/-15m)</div>
                 {correlatedEvents.slice(0, 8).map((event) => (
                   <button
                     key={event.id}
@@ -5399,10 +7080,11 @@ export default function App() {
                       severityTint(event.severity)
                     )}
                     onClick={() => setSelected(event)}
-                    title={event.message}
+                    title={[event.message, ...event.relevanceReasons].join("\n")}
                   >
                     <span className="font-semibold">{event.provider}</span>
                     <span className="uppercase text-[10px] text-muted">{event.severity}</span>
+                    <span className="text-[10px] text-muted">{Math.round(event.relevanceScore)}</span>
                   </button>
                 ))}
               </div>
@@ -5475,6 +7157,8 @@ export default function App() {
                 <option value="security">Security</option>
                 <option value="system">System</option>
                 <option value="audit">Audit</option>
+                <option value="printing">Printing</option>
+                <option value="updates">Updates</option>
                 <option value="other">Other</option>
               </select>
             </div>
@@ -5501,6 +7185,35 @@ export default function App() {
                 Apply Filters
               </Button>
             </div>
+            <div className="flex flex-wrap items-center gap-2">
+              <span className="text-xs text-muted">Saved views</span>
+              {savedFilters.length === 0 && <span className="text-xs text-muted">None yet</span>}
+              {savedFilters.map((filter) => (
+                <span key={filter.id} className="flex items-center gap-1">
+                  <Button size="sm" onClick={() => applySavedFilter(filter)}>
+                    {filter.name}
+                  </Button>
+                  <Button size="sm" variant="danger" onClick={() => void deleteSavedFilterById(filter.id)} title="Delete saved view">
+                    ×
+                  </Button>
+                </span>
+              ))}
+              <input
+                className={cn(inputClass, "w-48")}
+                value={savedFilterNameDraft}
+                placeholder="Name this view"
+                onChange={(e) => setSavedFilterNameDraft(e.target.value)}
+              />
+              <select className={cn(selectClass, "w-40")} value={savedFilterWindow} onChange={(e) => setSavedFilterWindow(e.target.value)}>
+                <option value="">Dates as entered</option>
+                <option value="60">Last hour</option>
+                <option value="1440">Last 24 hours</option>
+                <option value="10080">Last 7 days</option>
+              </select>
+              <Button size="sm" onClick={() => void saveCurrentFilter()} disabled={!savedFilterNameDraft.trim()}>
+                Save View
+              </Button>
+            </div>
             {activeDateCoverageWarning && (
               <div className="rounded-lg border border-panel-border bg-[var(--sev-warning)] px-3 py-2 text-xs">
                 <div className="flex flex-wrap items-center justify-between gap-2">
@@ -5686,6 +7399,612 @@ export default function App() {
           </section>
         )}
 
+        {activeTab === "data" && (
+          <section className={cn(panelClass, "space-y-3 px-5 py-4")}>
+            <div className="flex flex-wrap items-center justify-between gap-3">
+              <div className="text-sm font-semibold">Sigma Detections</div>
+              <div className="flex flex-wrap items-center gap-2">
+                <label className="text-xs text-muted">
+                  Import rules
+                  <input
+                    className={cn(inputClass, "text-xs")}
+                    type="file"
+                    accept=".yml,.yaml"
+                    multiple
+                    onChange={(event) => {
+                      void importSigmaFiles(event.target.files);
+                      event.target.value = "";
+                    }}
+                  />
+                </label>
+                <Button
+                  size="sm"
+                  variant="primary"
+                  onClick={() => void runSigmaScanNow()}
+                  disabled={isSigmaScanning || sigmaRules.length === 0}
+                >
+                  {isSigmaScanning ? "Scanning..." : "Scan Stored Events"}
+                </Button>
+              </div>
+            </div>
+            <p className="text-[11px] text-muted">
+              Sigma rules are evaluated against every stored event of the selected host. Rules with aggregations or unsupported
+              log sources are kept but skipped.
+            </p>
+            {sigmaScanResult && (
+              <div className="text-xs text-muted">
+                Scanned {sigmaScanResult.scannedEvents.toLocaleString()} events with {sigmaScanResult.rules} rule(s):{" "}
+                {sigmaScanResult.matches.toLocaleString()} match(es), {sigmaScanResult.newDetections.toLocaleString()} new.
+              </div>
+            )}
+            {sigmaMessages.map((message) => (
+              <div key={message} className="text-xs text-muted">
+                {message}
+              </div>
+            ))}
+            {sigmaRules.length === 0 ? (
+              <div className="text-xs text-muted">No Sigma rules imported.</div>
+            ) : (
+              <div className="max-h-56 space-y-1 overflow-auto">
+                {sigmaRules.map((rule) => (
+                  <div
+                    key={rule.id}
+                    className="flex items-center justify-between gap-3 rounded-lg border border-panel-border px-3 py-1.5 text-xs"
+                  >
+                    <div className="min-w-0">
+                      <div className="truncate font-medium">{rule.title}</div>
+                      <div className={cn("truncate text-[11px]", rule.unsupported ? "text-danger" : "text-muted")}>
+                        {rule.level} · {rule.logsource || "any source"}
+                        {rule.unsupported ? ` · ${rule.unsupported}` : ""}
+                      </div>
+                    </div>
+                    <Button size="sm" variant="danger" onClick={() => void deleteSigmaRuleById(rule.id)}>
+                      Delete
+                    </Button>
+                  </div>
+                ))}
+              </div>
+            )}
+            {sigmaDetections.length > 0 && (
+              <div className="max-h-80 overflow-auto rounded-lg border border-panel-border">
+                <table className="w-full text-left text-xs">
+                  <thead className="text-muted">
+                    <tr>
+                      <th className="px-2 py-1">Time</th>
+                      <th className="px-2 py-1">Level</th>
+                      <th className="px-2 py-1">Rule</th>
+                      <th className="px-2 py-1">Host</th>
+                      <th className="px-2 py-1">Event</th>
+                    </tr>
+                  </thead>
+                  <tbody>
+                    {sigmaDetections.map((detection) => (
+                      <tr key={`${detection.ruleId}-${detection.eventId}`} className="border-t border-panel-border">
+                        <td className="whitespace-nowrap px-2 py-1">{new Date(detection.eventTimestamp).toLocaleString()}</td>
+                        <td className="px-2 py-1">{detection.level}</td>
+                        <td className="px-2 py-1">{detection.ruleTitle}</td>
+                        <td className="px-2 py-1">{detection.sourceHost}</td>
+                        <td className="px-2 py-1" title={detection.message}>
+                          {detection.provider}
+                          {detection.eventCode !== null ? ` ${detection.eventCode}` : ""}
+                        </td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+              </div>
+            )}
+          </section>
+        )}
+
+        {activeTab === "data" && (
+          <section className={cn(panelClass, "space-y-3 px-5 py-4")}>
+            <div className="flex flex-wrap items-center justify-between gap-3">
+              <div className="text-sm font-semibold">IOC Matching</div>
+              <div className="flex flex-wrap items-center gap-2">
+                <input
+                  className={inputClass}
+                  value={iocFeedNameDraft}
+                  placeholder="Feed name (defaults to file name)"
+                  onChange={(e) => setIocFeedNameDraft(e.target.value)}
+                />
+                <label className="text-xs text-muted">
+                  Load feed
+                  <input
+                    className={cn(inputClass, "text-xs")}
+                    type="file"
+                    accept=".txt,.csv,.tsv,.ioc"
+                    onChange={(event) => {
+                      void importIocFile(event.target.files);
+                      event.target.value = "";
+                    }}
+                  />
+                </label>
+                <Button
+                  size="sm"
+                  variant="primary"
+                  onClick={() => void runIocScanNow()}
+                  disabled={isIocScanning || iocFeeds.length === 0}
+                >
+                  {isIocScanning ? "Scanning..." : "Match Stored Events"}
+                </Button>
+              </div>
+            </div>
+            <p className="text-[11px] text-muted">
+              One indicator per line (IPs, domains, MD5/SHA1/SHA256 hashes, file names); CSV and defanged values are accepted.
+              Matching runs offline over event messages, Sysmon fields and raw event data.
+            </p>
+            {iocMessage && <div className="text-xs text-muted">{iocMessage}</div>}
+            {iocScanResult && (
+              <div className="text-xs text-muted">
+                Checked {iocScanResult.scannedEvents.toLocaleString()} events against {iocScanResult.indicators.toLocaleString()}{" "}
+                indicators: {iocScanResult.matches.toLocaleString()} match(es), {iocScanResult.newMatches.toLocaleString()} new.
+              </div>
+            )}
+            {iocFeeds.map((feed) => (
+              <div
+                key={feed.feed}
+                className="flex items-center justify-between gap-3 rounded-lg border border-panel-border px-3 py-1.5 text-xs"
+              >
+                <div className="min-w-0">
+                  <div className="truncate font-medium">{feed.feed}</div>
+                  <div className="truncate text-[11px] text-muted">
+                    {Object.entries(feed.counts)
+                      .map(([kind, count]) => `${count.toLocaleString()} ${kind}`)
+                      .join(" · ")}{" "}
+                    · loaded {new Date(feed.addedAt).toLocaleString()}
+                  </div>
+                </div>
+                <Button size="sm" variant="danger" onClick={() => void deleteIocFeedByName(feed.feed)}>
+                  Delete
+                </Button>
+              </div>
+            ))}
+            {iocMatches.length > 0 && (
+              <div className="max-h-80 overflow-auto rounded-lg border border-panel-border">
+                <table className="w-full text-left text-xs">
+                  <thead className="text-muted">
+                    <tr>
+                      <th className="px-2 py-1">Time</th>
+                      <th className="px-2 py-1">Indicator</th>
+                      <th className="px-2 py-1">Found in</th>
+                      <th className="px-2 py-1">Host</th>
+                      <th className="px-2 py-1">Event</th>
+                    </tr>
+                  </thead>
+                  <tbody>
+                    {iocMatches.map((found) => (
+                      <tr key={`${found.feed}-${found.indicator}-${found.eventId}`} className="border-t border-panel-border">
+                        <td className="whitespace-nowrap px-2 py-1">{new Date(found.eventTimestamp).toLocaleString()}</td>
+                        <td className="px-2 py-1">
+                          {found.indicator} <span className="text-muted">({found.kind}, {found.feed})</span>
+                        </td>
+                        <td className="px-2 py-1">{found.field}</td>
+                        <td className="px-2 py-1">{found.sourceHost}</td>
+                        <td className="px-2 py-1" title={found.message}>
+                          {found.provider}
+                          {found.eventCode !== null ? ` ${found.eventCode}` : ""}
+                        </td>
+                      </tr>
+                    ))}
+                  </tbody>
+                </table>
+              </div>
+            )}
+          </section>
+        )}
+
+        {activeTab === "data" && (
+          <section className={cn(panelClass, "space-y-3 px-5 py-4")}>
+            <div className="flex flex-wrap items-center justify-between gap-3">
+              <div className="text-sm font-semibold">Logon Analysis</div>
+              <div className="flex flex-wrap items-center gap-2">
+                <select
+                  className={selectClass}
+                  value={logonReportDays}
+                  onChange={(e) => setLogonReportDays(Number(e.target.value))}
+                >
+                  <option value={1}>Last 24 hours</option>
+                  <option value={7}>Last 7 days</option>
+                  <option value={30}>Last 30 days</option>
+                </select>
+                <Button size="sm" variant="primary" onClick={() => void analyzeLogonsNow()} disabled={isAnalyzingLogons}>
+                  {isAnalyzingLogons ? "Analyzing..." : "Analyze Logons"}
+                </Button>
+              </div>
+            </div>
+            <p className="text-[11px] text-muted">
+              Windows 4624/4625 and sshd authentication events, grouped by account and source address. Bursts are 10+ failures
+              from one source with under two minutes between them.
+            </p>
+            {logonReport && (
+              <div className="space-y-3 text-xs">
+                <div className="text-muted">
+                  {logonReport.failures.toLocaleString()} failed and {logonReport.successes.toLocaleString()} successful logons.
+                </div>
+                {logonReport.bursts.map((burst) => (
+                  <div
+                    key={`${burst.source}-${burst.start}`}
+                    className={cn(
+                      "rounded-lg border px-3 py-2",
+                      burst.successAfter ? "border-danger text-danger" : "border-panel-border"
+                    )}
+                  >
+                    {burst.failures} failures from {burst.source} between {new Date(burst.start).toLocaleString()} and{" "}
+                    {new Date(burst.end).toLocaleString()} against {burst.accounts.join(", ")}
+                    {burst.successAfter
+                      ? ` — followed by a successful logon at ${new Date(burst.successAfter).toLocaleString()}`
+                      : ""}
+                  </div>
+                ))}
+                <div className="grid gap-3 md:grid-cols-2">
+                  {(
+                    [
+                      ["Top attacked accounts", "Sources", logonReport.topAccounts],
+                      ["Top source addresses", "Accounts", logonReport.topSources]
+                    ] as [string, string, LogonAggregate[]][]
+                  ).map(([heading, distinctLabel, rows]) => (
+                    <div key={heading} className="rounded-lg border border-panel-border">
+                      <div className="px-2 py-1 font-medium">{heading}</div>
+                      <table className="w-full text-left">
+                        <thead className="text-muted">
+                          <tr>
+                            <th className="px-2 py-1">Name</th>
+                            <th className="px-2 py-1">Failed</th>
+                            <th className="px-2 py-1">OK</th>
+                            <th className="px-2 py-1">{distinctLabel}</th>
+                            <th className="px-2 py-1">First / last seen</th>
+                          </tr>
+                        </thead>
+                        <tbody>
+                          {rows.map((row) => (
+                            <tr key={row.key} className="border-t border-panel-border">
+                              <td className="px-2 py-1">{row.key}</td>
+                              <td className="px-2 py-1">{row.failures}</td>
+                              <td className="px-2 py-1">{row.successes}</td>
+                              <td className="px-2 py-1">{row.distinct}</td>
+                              <td className="px-2 py-1 text-muted">
+                                {new Date(row.firstSeen).toLocaleString()} / {new Date(row.lastSeen).toLocaleString()}
+                              </td>
+                            </tr>
+                          ))}
+                        </tbody>
+                      </table>
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
+          </section>
+        )}
+
+        {activeTab === "data" && (
+          <section className={cn(panelClass, "space-y-3 px-5 py-4")}>
+            <div className="flex flex-wrap items-center justify-between gap-3">
+              <div className="text-sm font-semibold">Service and Driver Changes</div>
+              <div className="flex flex-wrap items-center gap-2">
+                <select
+                  className={selectClass}
+                  value={systemChangesDays}
+                  onChange={(e) => setSystemChangesDays(Number(e.target.value))}
+                >
+                  <option value={1}>Last 24 hours</option>
+                  <option value={7}>Last 7 days</option>
+                  <option value={30}>Last 30 days</option>
+                </select>
+                <Button
+                  size="sm"
+                  variant="primary"
+                  onClick={() => void loadSystemChangesNow()}
+                  disabled={isLoadingSystemChanges}
+                >
+                  {isLoadingSystemChanges ? "Detecting..." : "Detect Changes"}
+                </Button>
+              </div>
+            </div>
+            <p className="text-[11px] text-muted">
+              Service installs (7045/4697), driver loads (kernel drivers, Sysmon 6, out-of-tree modules, kexts), launchd
+              registrations and newly enabled systemd units. Crash analysis includes the changes from the day before a crash.
+            </p>
+            {systemChanges && (
+              <div className="space-y-3 text-xs">
+                <div className="text-muted">
+                  {systemChanges.totalChanges.toLocaleString()} change(s)
+                  {Object.keys(systemChanges.counts).length > 0
+                    ? `: ${Object.entries(systemChanges.counts)
+                        .map(([kind, count]) => `${count} ${kind}`)
+                        .join(", ")}`
+                    : "."}
+                </div>
+                {systemChanges.changes.length > 0 && (
+                  <div className="max-h-80 overflow-auto rounded-lg border border-panel-border">
+                    <table className="w-full text-left">
+                      <thead className="text-muted">
+                        <tr>
+                          <th className="px-2 py-1">Time</th>
+                          <th className="px-2 py-1">Kind</th>
+                          <th className="px-2 py-1">Name</th>
+                          <th className="px-2 py-1">Path</th>
+                          <th className="px-2 py-1">Details</th>
+                          <th className="px-2 py-1">Host</th>
+                        </tr>
+                      </thead>
+                      <tbody>
+                        {systemChanges.changes.map((change) => (
+                          <tr key={`${change.referenceId}-${change.name}`} className="border-t border-panel-border">
+                            <td className="whitespace-nowrap px-2 py-1">{new Date(change.timestamp).toLocaleString()}</td>
+                            <td className="px-2 py-1">{change.kind}</td>
+                            <td className="px-2 py-1">{change.name}</td>
+                            <td className="break-all px-2 py-1">{change.path ?? "-"}</td>
+                            <td className="px-2 py-1 text-muted">
+                              {[
+                                change.startType && `start ${change.startType}`,
+                                change.account,
+                                change.signature && `signed by ${change.signature}`,
+                                `${change.provider}${change.eventId !== null ? ` ${change.eventId}` : ""}`
+                              ]
+                                .filter(Boolean)
+                                .join(" · ")}
+                            </td>
+                            <td className="px-2 py-1">{change.sourceHost}</td>
+                          </tr>
+                        ))}
+                      </tbody>
+                    </table>
+                  </div>
+                )}
+              </div>
+            )}
+          </section>
+        )}
+
+        {activeTab === "data" && (
+          <section className={cn(panelClass, "space-y-3 px-5 py-4")}>
+            <div className="flex flex-wrap items-center justify-between gap-3">
+              <div className="text-sm font-semibold">USB and Removable Devices</div>
+              <div className="flex flex-wrap items-center gap-2">
+                <select
+                  className={selectClass}
+                  value={usbActivityDays}
+                  onChange={(e) => setUsbActivityDays(Number(e.target.value))}
+                >
+                  <option value={1}>Last 24 hours</option>
+                  <option value={7}>Last 7 days</option>
+                  <option value={30}>Last 30 days</option>
+                  <option value={90}>Last 90 days</option>
+                </select>
+                <Button
+                  size="sm"
+                  variant="primary"
+                  onClick={() => void loadUsbActivityNow()}
+                  disabled={isLoadingUsbActivity}
+                >
+                  {isLoadingUsbActivity ? "Loading..." : "Show Device Activity"}
+                </Button>
+              </div>
+            </div>
+            <p className="text-[11px] text-muted">
+              Built from Windows 6416, DriverFrameworks-UserMode, Kernel-PnP and Partition events, Linux kernel/udev USB
+              messages and macOS mass-storage attach messages already stored for the host.
+            </p>
+            {usbActivity && (
+              <div className="space-y-3 text-xs">
+                <div className="text-muted">
+                  {usbActivity.devices.length.toLocaleString()} device(s), {usbActivity.totalEvents.toLocaleString()}{" "}
+                  connect/disconnect event(s).
+                </div>
+                {usbActivity.devices.length > 0 && (
+                  <div className="overflow-auto rounded-lg border border-panel-border">
+                    <table className="w-full text-left">
+                      <thead className="text-muted">
+                        <tr>
+                          <th className="px-2 py-1">Device</th>
+                          <th className="px-2 py-1">VID:PID</th>
+                          <th className="px-2 py-1">Serial</th>
+                          <th className="px-2 py-1">Hosts</th>
+                          <th className="px-2 py-1">Connects</th>
+                          <th className="px-2 py-1">First / last seen</th>
+                        </tr>
+                      </thead>
+                      <tbody>
+                        {usbActivity.devices.map((device) => (
+                          <tr key={device.key} className="border-t border-panel-border">
+                            <td className="px-2 py-1">{device.name ?? device.key}</td>
+                            <td className="px-2 py-1">
+                              {device.vendorId && device.productId ? `${device.vendorId}:${device.productId}` : "-"}
+                            </td>
+                            <td className="px-2 py-1">{device.serial ?? "-"}</td>
+                            <td className="px-2 py-1">{device.hosts.join(", ")}</td>
+                            <td className="px-2 py-1">{device.connects}</td>
+                            <td className="px-2 py-1 text-muted">
+                              {new Date(device.firstSeen).toLocaleString()} / {new Date(device.lastSeen).toLocaleString()}
+                            </td>
+                          </tr>
+                        ))}
+                      </tbody>
+                    </table>
+                  </div>
+                )}
+                {usbActivity.events.length > 0 && (
+                  <div className="max-h-80 overflow-auto rounded-lg border border-panel-border">
+                    <table className="w-full text-left">
+                      <thead className="text-muted">
+                        <tr>
+                          <th className="px-2 py-1">Time</th>
+                          <th className="px-2 py-1">Action</th>
+                          <th className="px-2 py-1">Device</th>
+                          <th className="px-2 py-1">Identifier</th>
+                          <th className="px-2 py-1">Host</th>
+                          <th className="px-2 py-1">Source</th>
+                        </tr>
+                      </thead>
+                      <tbody>
+                        {usbActivity.events.map((entry) => (
+                          <tr key={`${entry.referenceId}-${entry.action}`} className="border-t border-panel-border">
+                            <td className="whitespace-nowrap px-2 py-1">{new Date(entry.timestamp).toLocaleString()}</td>
+                            <td className={cn("px-2 py-1", entry.action === "connect" ? "text-ok" : "text-muted")}>
+                              {entry.action}
+                            </td>
+                            <td className="px-2 py-1">
+                              {[entry.manufacturer, entry.name].filter(Boolean).join(" ") || "-"}
+                            </td>
+                            <td className="break-all px-2 py-1">{entry.serial ?? entry.deviceId ?? "-"}</td>
+                            <td className="px-2 py-1">{entry.sourceHost}</td>
+                            <td className="px-2 py-1 text-muted">
+                              {entry.provider}
+                              {entry.eventCode !== null ? ` ${entry.eventCode}` : ""}
+                            </td>
+                          </tr>
+                        ))}
+                      </tbody>
+                    </table>
+                  </div>
+                )}
+              </div>
+            )}
+          </section>
+        )}
+
+        {activeTab === "data" && (
+          <section className={cn(panelClass, "space-y-3 px-5 py-4")}>
+            <div className="flex flex-wrap items-center justify-between gap-3">
+              <div className="text-sm font-semibold">Regex Search</div>
+              <div className="flex flex-wrap items-center gap-2">
+                <input
+                  className={cn(inputClass, "w-80 font-mono")}
+                  placeholder="(?i)failed to start \w+\.service"
+                  value={regexPattern}
+                  onChange={(e) => setRegexPattern(e.target.value)}
+                  onKeyDown={(e) => {
+                    if (e.key === "Enter") void runRegexSearchNow();
+                  }}
+                />
+                <Button
+                  size="sm"
+                  variant="primary"
+                  onClick={() => void runRegexSearchNow()}
+                  disabled={isRegexSearching || !regexPattern.trim()}
+                >
+                  {isRegexSearching ? "Searching..." : "Search Messages"}
+                </Button>
+              </div>
+            </div>
+            <p className="text-[11px] text-muted">
+              Matches stored event messages against a regular expression (Rust regex syntax, case-sensitive unless it
+              starts with (?i)). Searches stop after 10 seconds and return what was found.
+            </p>
+            {regexResult && (
+              <div className="space-y-3 text-xs">
+                <div className="text-muted">
+                  {regexResult.events.length.toLocaleString()} matching event(s)
+                  {regexResult.truncated ? "; more matched than shown" : ""}
+                  {regexResult.timedOut ? "; time limit reached, older matches may be missing" : ""}.
+                </div>
+                {regexResult.events.length > 0 && (
+                  <div className="max-h-80 overflow-auto rounded-lg border border-panel-border">
+                    <table className="w-full text-left">
+                      <thead className="text-muted">
+                        <tr>
+                          <th className="px-2 py-1">Time</th>
+                          <th className="px-2 py-1">Host</th>
+                          <th className="px-2 py-1">Source</th>
+                          <th className="px-2 py-1">Message</th>
+                        </tr>
+                      </thead>
+                      <tbody>
+                        {regexResult.events.map((event) => (
+                          <tr key={event.id} className="border-t border-panel-border">
+                            <td className="whitespace-nowrap px-2 py-1">{new Date(event.timestamp).toLocaleString()}</td>
+                            <td className="px-2 py-1">{event.sourceHost}</td>
+                            <td className="px-2 py-1 text-muted">
+                              {event.provider}
+                              {event.eventId != null ? ` ${event.eventId}` : ""}
+                            </td>
+                            <td className="break-all px-2 py-1">{event.message}</td>
+                          </tr>
+                        ))}
+                      </tbody>
+                    </table>
+                  </div>
+                )}
+              </div>
+            )}
+          </section>
+        )}
+
+        {activeTab === "data" && (
+          <section className={cn(panelClass, "space-y-3 px-5 py-4")}>
+            <div className="flex flex-wrap items-center justify-between gap-3">
+              <div className="text-sm font-semibold">Repeated Messages</div>
+              <div className="flex flex-wrap items-center gap-2">
+                <select
+                  className={selectClass}
+                  value={repeatedEventsDays}
+                  onChange={(e) => setRepeatedEventsDays(Number(e.target.value))}
+                >
+                  <option value={1}>Last 24 hours</option>
+                  <option value={7}>Last 7 days</option>
+                  <option value={30}>Last 30 days</option>
+                </select>
+                <Button
+                  size="sm"
+                  variant="primary"
+                  onClick={() => void loadRepeatedEventsNow()}
+                  disabled={isLoadingRepeatedEvents}
+                >
+                  {isLoadingRepeatedEvents ? "Loading..." : "Find Repeats"}
+                </Button>
+              </div>
+            </div>
+            <p className="text-[11px] text-muted">
+              Identical provider, event ID and message from one host, each within 5 minutes of the last, shown as one
+              row with a count. Counts include repeats collapsed at ingest.
+            </p>
+            {repeatedEvents && (
+              <div className="space-y-3 text-xs">
+                <div className="text-muted">
+                  {repeatedEvents.totalEvents.toLocaleString()} event(s) in {repeatedEvents.totalRuns.toLocaleString()}{" "}
+                  row(s); {repeatedEvents.collapsedRuns.toLocaleString()} repeated.
+                </div>
+                {repeatedEvents.runs.length > 0 && (
+                  <div className="max-h-80 overflow-auto rounded-lg border border-panel-border">
+                    <table className="w-full text-left">
+                      <thead className="text-muted">
+                        <tr>
+                          <th className="px-2 py-1">Count</th>
+                          <th className="px-2 py-1">First / last seen</th>
+                          <th className="px-2 py-1">Host</th>
+                          <th className="px-2 py-1">Source</th>
+                          <th className="px-2 py-1">Message</th>
+                        </tr>
+                      </thead>
+                      <tbody>
+                        {repeatedEvents.runs.map((run) => (
+                          <tr key={`${run.id}-${run.firstTimestamp}`} className="border-t border-panel-border">
+                            <td className={cn("px-2 py-1", run.repeatCount > 1 ? "font-semibold" : "text-muted")}>
+                              {run.repeatCount.toLocaleString()}
+                            </td>
+                            <td className="whitespace-nowrap px-2 py-1 text-muted">
+                              {new Date(run.firstTimestamp).toLocaleString()} /{" "}
+                              {new Date(run.lastTimestamp).toLocaleString()}
+                            </td>
+                            <td className="px-2 py-1">{run.sourceHost}</td>
+                            <td className="px-2 py-1 text-muted">
+                              {run.provider}
+                              {run.eventId != null ? ` ${run.eventId}` : ""}
+                            </td>
+                            <td className="break-all px-2 py-1">{run.message}</td>
+                          </tr>
+                        ))}
+                      </tbody>
+                    </table>
+                  </div>
+                )}
+              </div>
+            )}
+          </section>
+        )}
+
         {(activeTab === "events" || activeTab === "crashes") && (
           <section className={cn(panelClass, "flex flex-wrap items-center justify-between gap-3 px-5 py-3")}> 
             <Button size="sm" onClick={resetSort} disabled={!sortState}>Reset Sort</Button>
@@ -5825,6 +8144,7 @@ export default function App() {
                     </div>
                   ))}
                 </div>
+                {eventKnownIssues.length > 0 && <KnownIssueList matches={eventKnownIssues} />}
                 <div className="space-y-1">
                   <div className="flex flex-wrap items-center justify-between gap-2">
                     <div className="text-[10px] uppercase tracking-wide text-muted">Message</div>
@@ -5854,10 +8174,32 @@ export default function App() {
                       >
                         Parsed
                       </button>
+                      <button
+                        type="button"
+                        className={cn(
+                          "rounded px-2 py-1 text-[11px] transition",
+                          messageViewMode === "source" ? "bg-accent text-white" : "text-text hover:bg-accent/10"
+                        )}
+                        onClick={() => setMessageViewMode("source")}
+                      >
+                        Source
+                      </button>
                     </div>
                   </div>
                   {messageViewMode === "raw" ? (
                     <div className="max-h-24 overflow-auto text-sm text-text">{selected.message}</div>
+                  ) : messageViewMode === "source" ? (
+                    selectedEventSource?.eventId !== selected.id ? (
+                      <div className="text-sm text-muted">Loading original record...</div>
+                    ) : selectedEventSource.payload ? (
+                      <pre className="max-h-48 overflow-auto whitespace-pre-wrap break-all rounded-lg border border-panel-border bg-[var(--field-bg)] px-3 py-2 text-xs text-text">
+                        {selectedEventSource.payload}
+                      </pre>
+                    ) : (
+                      <div className="text-sm text-muted">
+                        {selectedEventSource.error ?? "No original record was stored for this event."}
+                      </div>
+                    )
                   ) : selectedEventParsedFields.length > 0 ? (
                     <div className="grid max-h-36 gap-2 overflow-auto sm:grid-cols-2">
                       {selectedEventParsedFields.map((field) => (
@@ -0,0 +1,24 @@
+import type { KnownIssueMatch } from "../lib/backend";
+
+export interface KnownIssueListProps {
+  matches: KnownIssueMatch[];
+}
+
+export function KnownIssueList({ matches }: KnownIssueListProps) {
+  return (
+    <div className="space-y-2 rounded-lg border border-panel-border bg-[var(--field-bg)] px-3 py-2 text-xs">
+      <div className="text-[10px] uppercase tracking-wide text-muted">Known issue</div>
+      {matches.map((match) => (
+        <div key={match.ruleId} className="space-y-1">
+          <div className="text-sm font-semibold text-text">{match.title}</div>
+          <div className="text-text">{match.diagnosis}</div>
+          <div className="text-muted">
+            <span className="font-semibold">Fix: </span>
+            {match.remediation}
+          </div>
+          {match.origin !== "bundled" && <div className="text-muted">Rule from {match.origin}</div>}
+        </div>
+      ))}
+    </div>
+  );
+}
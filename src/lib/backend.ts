@@ -1,17 +1,181 @@
 import type { CrashRecord, NormalizedEvent, SupportedOs } from "../types/events";
 import type { ThemeMode } from "../types/events";
-import type { ExportFormat } from "../types/events";
+import type { CompressedExportFormat, ExportFormat } from "../types/events";
+import type { EventCategory, EventSeverity } from "../types/events";
 
 export interface IngestProfile {
   autoSyncOnStartup: boolean;
   maxEventsPerSync: number;
   windowsChannels: string[];
   requestElevation: boolean;
+  collectSmart: boolean;
+  maxDatabaseMb: number;
+  journalFilter: JournalFilter;
+  unifiedLogFilter: UnifiedLogFilter;
+  syncIntervalMinutes: number;
+  retention: RetentionPolicy;
+  /** Locale name such as "en-US" for Windows event messages, or "system" for the display language. */
+  messageLocale: string;
+  /** Store runs of identical messages from a sync as one event with a repeat count. */
+  collapseRepeats: boolean;
+}
+
+export interface RetentionPolicy {
+  maxAgeDays: number;
+  maxEvents: number;
+  /** Folder pruned events are archived to as day partitions; empty turns archiving off. */
+  archiveDirectory: string;
+  /** Bucket or container used instead of the folder when `kind` is set. */
+  objectStore: ObjectStoreSettings;
+}
+
+/** S3 or Azure Blob archive target; the secret access key or SAS token is kept in the keychain. */
+export interface ObjectStoreSettings {
+  /** "s3", "azure", or "" for the archive folder. */
+  kind: string;
+  bucket: string;
+  region: string;
+  /** S3-compatible endpoint such as MinIO; empty uses AWS. */
+  endpoint: string;
+  accessKeyId: string;
+  containerUrl: string;
+  prefix: string;
+}
+
+export interface JournalFilter {
+  units: string[];
+  identifiers: string[];
+  priority: string;
+}
+
+export interface UnifiedLogFilter {
+  predicate: string;
+  includeInfo: boolean;
+  includeDebug: boolean;
+}
+
+export interface SyncSourceCount {
+  logName: string;
+  count: number;
 }
 
 export interface SyncOperationResult {
   collected: number;
   warnings: string[];
+  degradations: string[];
+  errors: string[];
+  sources: SyncSourceCount[];
+}
+
+export interface SyncCompletedPayload {
+  startedAt: string;
+  finishedAt: string;
+  collected: number;
+  warnings: string[];
+  error: string | null;
+}
+
+export interface JobStatus {
+  jobId: string;
+  kind: string;
+  state: "running" | "completed" | "failed";
+  collected: number;
+  currentSource: string | null;
+  startedAt: string;
+  finishedAt: string | null;
+  error: string | null;
+  result: unknown;
+}
+
+export interface SyncHistoryEntry {
+  id: number;
+  kind: string;
+  sourceHost: string;
+  startedAt: string;
+  finishedAt: string;
+  status: "ok" | "partial" | "failed";
+  collected: number;
+  warnings: string[];
+  errors: string[];
+  sources: SyncSourceCount[];
+}
+
+export interface TableStats {
+  name: string;
+  rows: number;
+  bytes: number;
+  indexBytes: number;
+  oldest: string | null;
+  newest: string | null;
+}
+
+export interface IndexStats {
+  name: string;
+  table: string;
+  bytes: number;
+}
+
+export interface DatabaseStats {
+  path: string;
+  fileBytes: number;
+  walBytes: number;
+  freeBytes: number;
+  pageSize: number;
+  tables: TableStats[];
+  indexes: IndexStats[];
+}
+
+export interface Workspace {
+  id: string;
+  name: string;
+  createdAt: string;
+  lastOpenedAt: string | null;
+  archived: boolean;
+}
+
+export interface WorkspaceSettings {
+  activeId: string;
+  workspaces: Workspace[];
+}
+
+export interface SavedFilter {
+  id: string;
+  name: string;
+  text: string;
+  severities: EventSeverity[];
+  providers: string[];
+  logName: string | null;
+  category: EventCategory | null;
+  eventId: number | null;
+  /** Rolling window such as the last 24 hours; takes precedence over start/end. */
+  relativeMinutes: number | null;
+  start: string | null;
+  end: string | null;
+  sortColumn: string | null;
+  sortDirection: "asc" | "desc" | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type RecordTimelineEntry =
+  | { kind: "event"; record: NormalizedEvent }
+  | { kind: "crash"; record: CrashRecord };
+
+export interface RecordTimeline {
+  entries: RecordTimelineEntry[];
+  /** More entries matched than the limit; the latest ones were left out. */
+  truncated: boolean;
+}
+
+export interface EncryptionStatus {
+  available: boolean;
+  enabled: boolean;
+}
+
+export interface CompactionResult {
+  bytesBefore: number;
+  bytesAfter: number;
+  reclaimedBytes: number;
 }
 
 export interface EventLoadEstimate {
@@ -22,6 +186,807 @@ export interface EventLoadEstimate {
   warnings: string[];
 }
 
+export type HistogramBucketSize = "hour" | "day";
+
+export interface EventHistogramBucket {
+  bucketStart: string;
+  total: number;
+  bySeverity: Record<string, number>;
+}
+
+export interface PowerIncident {
+  timestamp: string;
+  eventId: string;
+  classification: "power_loss" | "crash" | "unknown";
+  summary: string;
+  evidence: string[];
+  relatedCrashId: string | null;
+}
+
+export interface BatteryHealth {
+  present: boolean;
+  designCapacity: number | null;
+  fullChargeCapacity: number | null;
+  healthPercent: number | null;
+  cycleCount: number | null;
+  chargePercent: number | null;
+  onAcPower: boolean | null;
+}
+
+export interface PowerAnalysisResult {
+  incidents: PowerIncident[];
+  powerLossCount: number;
+  crashCount: number;
+  unknownCount: number;
+  battery: BatteryHealth | null;
+  warnings: string[];
+}
+
+export interface BootRecord {
+  timestamp: string;
+  eventId: string;
+  source: string;
+  durationMs: number;
+  kernelMs: number | null;
+  userspaceMs: number | null;
+  baselineMs: number | null;
+  regression: boolean;
+  precededByUpdate: boolean;
+}
+
+export interface BootPerformanceResult {
+  boots: BootRecord[];
+  averageMs: number | null;
+  latestMs: number | null;
+  regressionCount: number;
+  warnings: string[];
+}
+
+export interface AppHangOccurrence {
+  timestamp: string;
+  source: string;
+  version: string | null;
+  recordId: string;
+  sourceHost: string;
+  pressureSignals: string[];
+  /** Highest CPU use among the load snapshots taken around the hang. */
+  peakCpuPercent: number | null;
+  /** Busiest disk among those snapshots, as [device, busy percent]. */
+  peakDiskBusy: [string, number] | null;
+}
+
+export interface AppHangGroup {
+  application: string;
+  hangCount: number;
+  firstSeen: string;
+  lastSeen: string;
+  versions: string[];
+  occurrencesWithPressure: number;
+  occurrences: AppHangOccurrence[];
+}
+
+export interface AppHangReport {
+  totalHangs: number;
+  applications: AppHangGroup[];
+  warnings: string[];
+}
+
+export interface PrintComponentSummary {
+  name: string;
+  errorCount: number;
+  warningCount: number;
+  lastSeen: string;
+  lastError: string | null;
+}
+
+export interface PrintIssueSummary {
+  totalEvents: number;
+  errorCount: number;
+  queues: PrintComponentSummary[];
+  drivers: PrintComponentSummary[];
+  events: NormalizedEvent[];
+}
+
+export interface RemoteSessionEvent {
+  timestamp: string;
+  protocol: string;
+  action: string;
+  user: string | null;
+  sourceAddress: string | null;
+  sessionId: string | null;
+  recordId: string;
+  message: string;
+}
+
+export interface RemoteSourceSummary {
+  address: string;
+  protocols: string[];
+  users: string[];
+  sessionCount: number;
+  failedCount: number;
+  firstSeen: string;
+  lastSeen: string;
+}
+
+export interface RemoteAccessReport {
+  events: RemoteSessionEvent[];
+  sources: RemoteSourceSummary[];
+  warnings: string[];
+}
+
+export interface CrashesImportedPayload {
+  count: number;
+  crashIds: string[];
+}
+
+export interface MetricSample {
+  timestamp: string;
+  sourceHost: string;
+  source: string;
+  device: string;
+  name: string;
+  value: number;
+  unit: string | null;
+  status: string | null;
+}
+
+export interface SmartCollectionResult {
+  devices: number;
+  samples: MetricSample[];
+  failingDevices: string[];
+  warnings: string[];
+}
+
+export interface ReliabilityCollectionResult {
+  events: NormalizedEvent[];
+  samples: MetricSample[];
+  warnings: string[];
+}
+
+export interface InventorySnapshotSummary {
+  id: string;
+  timestamp: string;
+  sourceHost: string;
+  itemCount: number;
+}
+
+export interface InventoryChange {
+  kind: string;
+  name: string;
+  change: string;
+  before: string | null;
+  after: string | null;
+}
+
+export interface InventoryChangeSet {
+  fromTimestamp: string;
+  toTimestamp: string;
+  changes: InventoryChange[];
+}
+
+export interface ScoredCrashEvent extends NormalizedEvent {
+  relevanceScore: number;
+  relevanceReasons: string[];
+}
+
+export type IntegrationKind = "webhook" | "syslog" | "elasticsearch" | "loki";
+
+export interface ForwardingFilter {
+  minSeverity: string;
+  categories: string[];
+  providers: string[];
+  eventIds: number[];
+  excludePatterns: string[];
+  includeImported: boolean;
+}
+
+export interface IntegrationConfig {
+  id: string;
+  name: string;
+  kind: IntegrationKind;
+  endpoint: string;
+  enabled: boolean;
+  filter: ForwardingFilter;
+  spoolMaxEvents: number;
+  index: string;
+  authType: "none" | "basic" | "apiKey";
+  username: string;
+  lokiLabels: string[];
+  syslogFormat: "rfc5424" | "cef" | "leef";
+}
+
+export interface BulkPushResult {
+  sent: number;
+  failed: number;
+  batches: number;
+  errors: string[];
+}
+
+export interface IntegrationSettings {
+  integrations: IntegrationConfig[];
+}
+
+export interface AlertRule {
+  id: string;
+  name: string;
+  enabled: boolean;
+  minSeverity: "" | EventSeverity;
+  providers: string[];
+  eventIds: number[];
+  messagePattern: string;
+  webhookUrl: string;
+  webhookFormat: "generic" | "slack" | "teams";
+  cooldownSeconds: number;
+  savedFilterId?: string | null;
+}
+
+export interface AlertSettings {
+  rules: AlertRule[];
+}
+
+export interface AlertDelivery {
+  id: number;
+  ruleId: string;
+  ruleName: string;
+  sentAt: string;
+  matched: number;
+  suppressed: number;
+  success: boolean;
+  httpStatus: number | null;
+  error: string | null;
+}
+
+export interface ForwardingPreview {
+  integrationId: string;
+  scanned: number;
+  matched: number;
+  excluded: Record<string, number>;
+  events: NormalizedEvent[];
+  truncated: boolean;
+}
+
+export interface ForwardingStatus {
+  integrationId: string;
+  name: string;
+  kind: IntegrationKind;
+  enabled: boolean;
+  queued: number;
+  oldestQueuedTimestamp: string | null;
+  lastAttemptAt: string | null;
+  lastSuccessAt: string | null;
+  lastDeliveredTimestamp: string | null;
+  lastError: string | null;
+  consecutiveFailures: number;
+  deliveredTotal: number;
+  droppedTotal: number;
+  lagSeconds: number | null;
+  nextRetryAt: string | null;
+}
+
+export interface AnomalyWindow {
+  provider: string;
+  logName: string;
+  start: string;
+  end: string;
+  peakAt: string;
+  eventCount: number;
+  peakCount: number;
+  baselineMean: number;
+  zScore: number;
+  ratio: number | null;
+  summary: string;
+}
+
+export interface AnomalyReport {
+  bucketMinutes: number;
+  providersScanned: number;
+  windows: AnomalyWindow[];
+}
+
+export interface HealthFinding {
+  id: string;
+  severity: "warning" | "critical";
+  title: string;
+  detail: string;
+}
+
+export interface HealthReport {
+  status: "ok" | "warning" | "critical";
+  checkedAt: string;
+  sync: {
+    consecutiveFailures: number;
+    lastError: string | null;
+    lastSuccessAt: string | null;
+    lastFailureAt: string | null;
+  };
+  disk: { path: string; availableBytes: number; totalBytes: number } | null;
+  findings: HealthFinding[];
+}
+
+export interface PreflightCheck {
+  id: string;
+  title: string;
+  status: "pass" | "warning" | "fail" | "skipped";
+  detail: string;
+  remediation: string | null;
+}
+
+export interface PreflightReport {
+  os: string;
+  checkedAt: string;
+  checks: PreflightCheck[];
+}
+
+export interface StabilityDay {
+  day: string;
+  sourceHost: string;
+  index: number;
+  dailyScore: number;
+  appCrashes: number;
+  hangs: number;
+  systemFailures: number;
+  unexpectedShutdowns: number;
+  errorCount: number;
+}
+
+export interface LogPattern {
+  template: string;
+  count: number;
+  providers: string[];
+  severities: Record<string, number>;
+  firstSeen: string;
+  lastSeen: string;
+  examples: string[];
+}
+
+export interface PublisherEventDescription {
+  eventId: number;
+  version: number;
+  level: number | null;
+  severity: string;
+  task: number;
+  opcode: number;
+  keywords: string;
+  description: string | null;
+}
+
+export interface PublisherMetadata {
+  name: string;
+  messageFile: string | null;
+  events: PublisherEventDescription[];
+}
+
+export interface CollectionSuggestion {
+  crashId: string;
+  start: string;
+  end: string;
+  channels: string[];
+  reasons: string[];
+}
+
+export interface RecentChange {
+  timestamp: string;
+  kind: "driver" | "update" | "service" | "config";
+  title: string;
+  detail: string;
+  provider: string;
+  eventId: number | null;
+  referenceId: string;
+  minutesBeforeCrash: number;
+}
+
+export interface RecentChangesReport {
+  crashId: string;
+  crashTimestamp: string;
+  windowStart: string;
+  hours: number;
+  counts: Record<string, number>;
+  changes: RecentChange[];
+}
+
+export interface KnownIssueMatch {
+  ruleId: string;
+  title: string;
+  diagnosis: string;
+  remediation: string;
+  references: string[];
+  /** "bundled", or the user rule file the rule came from. */
+  origin: string;
+}
+
+export interface KnownIssueReport {
+  targetKind: "event" | "crash";
+  targetId: string;
+  matches: KnownIssueMatch[];
+  ruleErrors: string[];
+}
+
+export interface SigmaRuleSummary {
+  id: string;
+  title: string;
+  level: string;
+  status: string | null;
+  description: string | null;
+  tags: string[];
+  logsource: string;
+  file: string;
+  unsupported: string | null;
+}
+
+export interface SigmaRuleList {
+  rules: SigmaRuleSummary[];
+  errors: string[];
+}
+
+export interface SigmaRuleFile {
+  name: string;
+  content: string;
+}
+
+export interface SigmaImportResult {
+  imported: SigmaRuleSummary[];
+  errors: string[];
+}
+
+export interface SigmaScanResult {
+  scannedEvents: number;
+  rules: number;
+  unsupportedRules: number;
+  matches: number;
+  newDetections: number;
+  ruleErrors: string[];
+}
+
+export interface SigmaDetection {
+  ruleId: string;
+  ruleTitle: string;
+  level: string;
+  eventId: string;
+  eventTimestamp: string;
+  sourceHost: string;
+  provider: string;
+  eventCode: number | null;
+  message: string;
+  detectedAt: string;
+}
+
+export interface LogonAggregate {
+  key: string;
+  failures: number;
+  successes: number;
+  distinct: number;
+  firstSeen: string;
+  lastSeen: string;
+}
+
+export interface FailureBurst {
+  source: string;
+  start: string;
+  end: string;
+  failures: number;
+  accounts: string[];
+  successAfter: string | null;
+}
+
+export interface LogonReport {
+  windowStart: string;
+  windowEnd: string;
+  failures: number;
+  successes: number;
+  topAccounts: LogonAggregate[];
+  topSources: LogonAggregate[];
+  bursts: FailureBurst[];
+}
+
+export interface SystemChange {
+  timestamp: string;
+  kind: "service" | "driver" | "launchd" | "systemd";
+  name: string;
+  path: string | null;
+  startType: string | null;
+  account: string | null;
+  signature: string | null;
+  sourceHost: string;
+  provider: string;
+  eventId: number | null;
+  referenceId: string;
+}
+
+export interface SystemChangesReport {
+  windowStart: string;
+  windowEnd: string;
+  counts: Record<string, number>;
+  changes: SystemChange[];
+  totalChanges: number;
+}
+
+export interface UsbDeviceEvent {
+  timestamp: string;
+  action: "connect" | "disconnect";
+  name: string | null;
+  manufacturer: string | null;
+  deviceId: string | null;
+  vendorId: string | null;
+  productId: string | null;
+  serial: string | null;
+  sourceHost: string;
+  provider: string;
+  eventCode: number | null;
+  referenceId: string;
+}
+
+export interface UsbDeviceSummary {
+  key: string;
+  name: string | null;
+  vendorId: string | null;
+  productId: string | null;
+  serial: string | null;
+  hosts: string[];
+  connects: number;
+  disconnects: number;
+  firstSeen: string;
+  lastSeen: string;
+}
+
+export interface UsbActivityReport {
+  windowStart: string;
+  windowEnd: string;
+  devices: UsbDeviceSummary[];
+  events: UsbDeviceEvent[];
+  totalEvents: number;
+}
+
+export interface RepeatedEvent extends NormalizedEvent {
+  repeatCount: number;
+  firstTimestamp: string;
+  lastTimestamp: string;
+}
+
+export interface RepeatedEventsReport {
+  runs: RepeatedEvent[];
+  totalEvents: number;
+  totalRuns: number;
+  collapsedRuns: number;
+}
+
+export interface RegexSearchResult {
+  events: NormalizedEvent[];
+  truncated: boolean;
+  timedOut: boolean;
+}
+
+export interface IocFeedSummary {
+  feed: string;
+  addedAt: string;
+  counts: Record<string, number>;
+}
+
+export interface IocImportResult {
+  feed: string;
+  imported: number;
+  counts: Record<string, number>;
+  skipped: number;
+  skippedExamples: string[];
+}
+
+export interface IocScanResult {
+  scannedEvents: number;
+  indicators: number;
+  matches: number;
+  newMatches: number;
+}
+
+export interface IocMatch {
+  feed: string;
+  kind: "ip" | "domain" | "hash" | "filename";
+  indicator: string;
+  field: string;
+  eventId: string;
+  eventTimestamp: string;
+  sourceHost: string;
+  provider: string;
+  eventCode: number | null;
+  message: string;
+  matchedAt: string;
+}
+
+export interface CrashCollectionPlan {
+  suggestion: CollectionSuggestion;
+  syncResult: SyncOperationResult | null;
+}
+
+export interface TimelineEntry {
+  timestamp: string;
+  kind: "crash" | "finding" | "change" | "power" | "bookmark";
+  severity: string;
+  title: string;
+  detail: string;
+  referenceId: string | null;
+}
+
+export interface Timeline {
+  start: string;
+  end: string;
+  sourceHost: string;
+  counts: Record<string, number>;
+  entries: TimelineEntry[];
+}
+
+export interface ReportAnalysisSection {
+  title: string;
+  items: string[];
+}
+
+export interface ReportAnalysis {
+  profileName: string;
+  model: string;
+  summary: string;
+  sections: ReportAnalysisSection[];
+}
+
+export interface IncidentReportRequest {
+  targetId?: string;
+  crashId?: string;
+  from?: string;
+  to?: string;
+  windowMinutes?: number;
+  analysis?: ReportAnalysis;
+}
+
+export interface EventBookmark {
+  eventId: string;
+  note: string | null;
+  createdAt: string;
+}
+
+export interface QuerySession {
+  id: string;
+  name: string;
+  startedAt: string;
+  endedAt: string | null;
+  stepCount: number;
+}
+
+export interface QueryStep {
+  seq: number;
+  timestamp: string;
+  action: string;
+  params: unknown;
+}
+
+export interface QuerySessionExport {
+  session: QuerySession;
+  steps: QueryStep[];
+  markdown: string;
+}
+
+export interface QuotaPlan {
+  usedBytes: number;
+  maxBytes: number;
+  level: "unlimited" | "ok" | "near" | "critical" | "over";
+  informationSampleEvery: number;
+  minSeverity: string | null;
+  pruneInformationShare: number;
+  degradations: string[];
+}
+
+export interface RetentionReport {
+  ranAt: string;
+  archivedEvents: number;
+  deletedEvents: number;
+  deletedCrashes: number;
+  fileBytesBefore: number;
+  fileBytesAfter: number;
+  reclaimedBytes: number;
+}
+
+export interface ArchivePartition {
+  /** UTC day, YYYY-MM-DD. */
+  day: string;
+  files: number;
+  bytes: number;
+  events: number;
+  matches: number;
+}
+
+export interface ArchiveSearchReport {
+  /** Oldest day first. */
+  partitions: ArchivePartition[];
+  totalMatches: number;
+}
+
+export interface RehydrateReport {
+  days: number;
+  events: number;
+}
+
+export interface ChatSession {
+  id: string;
+  title: string;
+  createdAt: string;
+  updatedAt: string;
+  messageCount: number;
+}
+
+export interface ChatMessage {
+  id: string;
+  sessionId: string;
+  role: "user" | "assistant";
+  content: string;
+  context: string | null;
+  profileName: string | null;
+  model: string | null;
+  createdAt: string;
+}
+
+export interface WerDumpStatus {
+  app: string | null;
+  reportingEnabled: boolean;
+  consent: string;
+  localDumpsEnabled: boolean;
+  dumpType: "mini" | "full" | "custom" | null;
+  dumpFolder: string | null;
+  dumpCount: number | null;
+  kernelDump: string;
+  explanations: string[];
+  values: Record<string, unknown>;
+}
+
+export interface SimilarEventMatch {
+  template: string;
+  similarity: number;
+  occurrences: number;
+  firstSeen: string;
+  lastSeen: string;
+  event: NormalizedEvent;
+}
+
+export interface SimilarEventsResult {
+  eventId: string;
+  template: string;
+  model: string;
+  matches: SimilarEventMatch[];
+  newlyIndexed: number;
+  pendingTemplates: number;
+}
+
+export interface ReplayStatus {
+  running: boolean;
+  fixture?: string | null;
+  speed: number;
+  total: number;
+  emitted: number;
+  simulatedStart?: string | null;
+  simulatedNow?: string | null;
+  error?: string | null;
+}
+
+export interface ReplayBatch {
+  events: NormalizedEvent[];
+  simulatedNow: string;
+  emitted: number;
+  total: number;
+}
+
+export const REPLAY_TARGET_ID = "replay";
+
+export interface CrashExportFilter {
+  text?: string;
+  crashType?: string;
+  os?: string;
+  start?: string;
+  end?: string;
+}
+
+export interface CaseImportResult {
+  crashId: string;
+  eventCount: number;
+  bookmarkCount: number;
+  notes: string | null;
+  analysis: ReportAnalysis | null;
+  rawFilePath: string | null;
+}
+
 export interface RemoteConnectionProfile {
   id: string;
   name: string;
@@ -103,22 +1068,96 @@ export async function saveRemoteSettings(settings: RemoteSettings): Promise<Remo
   return invoke<RemoteSettings>("save_remote_settings", { settings });
 }
 
-export async function saveRemoteProfileSecret(profileId: string, secret: string): Promise<void> {
-  if (!isTauriRuntime()) return;
+export async function getIntegrationSettings(): Promise<IntegrationSettings> {
+  if (!isTauriRuntime()) return { integrations: [] };
   const { invoke } = await import("@tauri-apps/api/core");
-  await invoke("save_remote_profile_secret", { profileId, secret });
+  return invoke<IntegrationSettings>("get_integration_settings");
 }
 
-export async function clearRemoteProfileSecret(profileId: string): Promise<void> {
-  if (!isTauriRuntime()) return;
+export async function saveIntegrationSettings(settings: IntegrationSettings): Promise<IntegrationSettings> {
+  if (!isTauriRuntime()) return settings;
   const { invoke } = await import("@tauri-apps/api/core");
-  await invoke("clear_remote_profile_secret", { profileId });
+  return invoke<IntegrationSettings>("save_integration_settings", { settings });
 }
 
-export async function saveRemoteProviderSecret(providerId: string, secret: string): Promise<void> {
-  if (!isTauriRuntime()) return;
+export async function getAlertSettings(): Promise<AlertSettings> {
+  if (!isTauriRuntime()) return { rules: [] };
   const { invoke } = await import("@tauri-apps/api/core");
-  await invoke("save_remote_provider_secret", { providerId, secret });
+  return invoke<AlertSettings>("get_alert_settings");
+}
+
+export async function saveAlertSettings(settings: AlertSettings): Promise<AlertSettings> {
+  if (!isTauriRuntime()) return settings;
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<AlertSettings>("save_alert_settings", { settings });
+}
+
+export async function getAlertDeliveries(limit?: number): Promise<AlertDelivery[]> {
+  if (!isTauriRuntime()) return [];
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<AlertDelivery[]>("get_alert_deliveries", { limit });
+}
+
+export async function sendTestAlert(rule: AlertRule): Promise<AlertDelivery> {
+  if (!isTauriRuntime()) {
+    throw new Error("Test alerts require desktop runtime.");
+  }
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<AlertDelivery>("send_test_alert", { rule });
+}
+
+export async function saveIntegrationSecret(integrationId: string, secret: string): Promise<void> {
+  if (!isTauriRuntime()) return;
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("save_integration_secret", { integrationId, secret });
+}
+
+export async function clearIntegrationSecret(integrationId: string): Promise<void> {
+  if (!isTauriRuntime()) return;
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("clear_integration_secret", { integrationId });
+}
+
+export async function pushEventsToElasticsearch(
+  integrationId: string,
+  filter: EventQueryFilter,
+  targetId?: string
+): Promise<BulkPushResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Elasticsearch push requires desktop runtime.");
+  }
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<BulkPushResult>("push_events_to_elasticsearch", { integrationId, filter, targetId });
+}
+
+export async function pushEventsToSyslog(
+  integrationId: string,
+  filter: EventQueryFilter,
+  targetId?: string
+): Promise<number> {
+  if (!isTauriRuntime()) {
+    throw new Error("Syslog push requires desktop runtime.");
+  }
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<number>("push_events_to_syslog", { integrationId, filter, targetId });
+}
+
+export async function saveRemoteProfileSecret(profileId: string, secret: string): Promise<void> {
+  if (!isTauriRuntime()) return;
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("save_remote_profile_secret", { profileId, secret });
+}
+
+export async function clearRemoteProfileSecret(profileId: string): Promise<void> {
+  if (!isTauriRuntime()) return;
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("clear_remote_profile_secret", { profileId });
+}
+
+export async function saveRemoteProviderSecret(providerId: string, secret: string): Promise<void> {
+  if (!isTauriRuntime()) return;
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("save_remote_provider_secret", { providerId, secret });
 }
 
 export async function clearRemoteProviderSecret(providerId: string): Promise<void> {
@@ -127,6 +1166,19 @@ export async function clearRemoteProviderSecret(providerId: string): Promise<voi
   await invoke("clear_remote_provider_secret", { providerId });
 }
 
+/** Stores the S3 secret access key or Blob SAS token for the archive. */
+export async function saveArchiveStoreSecret(secret: string): Promise<void> {
+  if (!isTauriRuntime()) return;
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("save_archive_store_secret", { secret });
+}
+
+export async function clearArchiveStoreSecret(): Promise<void> {
+  if (!isTauriRuntime()) return;
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("clear_archive_store_secret");
+}
+
 export async function testRemoteConnection(
   profile: RemoteConnectionProfile
 ): Promise<RemoteConnectionTestResult> {
@@ -157,6 +1209,7 @@ export interface LlmConnectionProfile {
   model: string;
   enabled: boolean;
   apiKeyConfigured: boolean;
+  contextWindowTokens?: number;
 }
 
 export interface LlmSettings {
@@ -213,6 +1266,84 @@ export interface LlmAnalysisResult {
   warning: string | null;
 }
 
+export interface LlmProviderHealth {
+  profileId: string;
+  profileName: string;
+  provider: string;
+  baseUrl: string;
+  reachable: boolean;
+  authValid: boolean | null;
+  latencyMs: number | null;
+  statusCode: number | null;
+  modelAvailable: boolean | null;
+  message: string;
+}
+
+export interface LlmChunk {
+  streamId: string;
+  profileId: string;
+  delta: string;
+  done: boolean;
+}
+
+export interface CrashRcaResult {
+  crashId: string;
+  probableCause: string;
+  confidence: "high" | "medium" | "low" | "unknown";
+  evidence: string[];
+  nextSteps: string[];
+  rawResponse: string;
+  relatedEventCount: number;
+  systemChangeCount: number;
+  profileName: string;
+  model: string;
+  fallbackUsed: boolean;
+  warning: string | null;
+  redacted: boolean;
+}
+
+export interface EventQueryFilter {
+  text: string | null;
+  severities: EventSeverity[];
+  logName: string | null;
+  category: EventCategory | null;
+  eventId: number | null;
+  provider: string | null;
+  providers?: string[];
+  start: string | null;
+  end: string | null;
+}
+
+export interface EventQueryTranslation {
+  question: string;
+  filter: EventQueryFilter;
+  notes: string[];
+  profileName: string;
+  model: string;
+  fallbackUsed: boolean;
+}
+
+export interface QueryExportResult {
+  path: string;
+  eventCount: number;
+  /** Zone the exported timestamps are written in: "system", "UTC" or an IANA name. */
+  timezone: string;
+}
+
+export interface EventSummaryResult {
+  summary: string;
+  notableFindings: string[];
+  eventCount: number;
+  includedGroups: number;
+  omittedGroups: number;
+  rawResponse: string;
+  profileName: string;
+  model: string;
+  fallbackUsed: boolean;
+  warning: string | null;
+  redacted: boolean;
+}
+
 export interface MinidumpAnalysisResult {
   ok: boolean;
   crashId: string;
@@ -239,275 +1370,1372 @@ export interface MinidumpAnalysisResult {
   escalateIf: string[];
   warnings: string[];
   unavailableReason: string | null;
+  crashingThreadStack: string[];
+  osBuild: string | null;
+}
+
+export interface CrashCodeExplanation {
+  code: string;
+  known: boolean;
+  kind: "bugcheck" | "exception" | "signal" | "unknown";
+  name: string;
+  description: string;
+  typicalCauses: string[];
+  nextSteps: string[];
+}
+
+function createDefaultLlmProfile(): LlmConnectionProfile {
+  return {
+    id: "profile-ollama-local",
+    name: "Ollama Local",
+    provider: "ollama",
+    scope: "local",
+    baseUrl: "http://127.0.0.1:11434",
+    model: "",
+    enabled: true,
+    apiKeyConfigured: false
+  };
+}
+
+function createDefaultLlmSettings(): LlmSettings {
+  return {
+    allowLanDiscovery: false,
+    neverSendRawEventToUntrusted: true,
+    trustedHosts: [],
+    profiles: [createDefaultLlmProfile()],
+    defaultProfileId: "profile-ollama-local",
+    backupProfileId: "",
+    preferredLanInterfaceId: ""
+  };
+}
+
+export async function setIngestProfile(profile: IngestProfile): Promise<IngestProfile> {
+  if (!isTauriRuntime()) return profile;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<IngestProfile>("set_ingest_profile", { profile });
+}
+
+export async function setIngestWindowDays(days: number): Promise<number> {
+  if (!isTauriRuntime()) return days;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<number>("set_ingest_window_days", { days });
+}
+
+export async function getLlmSettings(): Promise<LlmSettings> {
+  if (!isTauriRuntime()) return createDefaultLlmSettings();
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmSettings>("get_llm_settings");
+}
+
+export async function setLlmSettings(settings: LlmSettings): Promise<LlmSettings> {
+  if (!isTauriRuntime()) return settings;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmSettings>("set_llm_settings", { settings });
+}
+
+export async function setLlmProfileApiKey(profileId: string, apiKey: string): Promise<LlmSettings> {
+  if (!isTauriRuntime()) return createDefaultLlmSettings();
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmSettings>("set_llm_profile_api_key", { profileId, apiKey });
+}
+
+export async function clearLlmProfileApiKey(profileId: string): Promise<LlmSettings> {
+  if (!isTauriRuntime()) return createDefaultLlmSettings();
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmSettings>("clear_llm_profile_api_key", { profileId });
+}
+
+export async function detectLocalLlmProviders(): Promise<LlmEndpointCandidate[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmEndpointCandidate[]>("detect_local_llm_providers");
+}
+
+export async function listLlmNetworkInterfaces(
+  includeNonPrivate = false,
+  includeLoopback = false
+): Promise<LlmNetworkInterface[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmNetworkInterface[]>("list_llm_network_interfaces", {
+    includeNonPrivate,
+    includeLoopback
+  });
+}
+
+export async function scanLanLlmProviders(
+  interfaceId?: string,
+  maxHosts = 256
+): Promise<LlmEndpointCandidate[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmEndpointCandidate[]>("scan_lan_llm_providers", { interfaceId, maxHosts });
+}
+
+export async function testLlmProfileConnection(
+  profile: LlmConnectionProfile
+): Promise<LlmConnectionTestResult> {
+  if (!isTauriRuntime()) {
+    return {
+      ok: false,
+      provider: profile.provider,
+      baseUrl: profile.baseUrl,
+      statusCode: null,
+      message: "Connection test requires desktop runtime.",
+      detectedModels: [],
+      preferredModel: null
+    };
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmConnectionTestResult>("test_llm_profile_connection", { profile });
+}
+
+export async function analyzeWithLocalLlm(
+  prompt: string,
+  profileId?: string
+): Promise<LlmAnalysisResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Local LLM analysis requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmAnalysisResult>("analyze_with_local_llm", { prompt, profileId });
+}
+
+export async function checkLlmProvider(profileId: string): Promise<LlmProviderHealth> {
+  if (!isTauriRuntime()) {
+    throw new Error("LLM provider checks require desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LlmProviderHealth>("check_llm_provider", { profileId });
+}
+
+export async function analyzeWithLocalLlmStream(
+  prompt: string,
+  onChunk: (chunk: LlmChunk) => void,
+  profileId?: string,
+  streamId: string = crypto.randomUUID()
+): Promise<LlmAnalysisResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Local LLM analysis requires desktop runtime.");
+  }
+
+  const { listen } = await import("@tauri-apps/api/event");
+  const unlisten = await listen<LlmChunk>("hla://llm-chunk", (event) => {
+    if (event.payload.streamId === streamId) onChunk(event.payload);
+  });
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    return await invoke<LlmAnalysisResult>("analyze_with_local_llm_stream", {
+      prompt,
+      profileId,
+      streamId,
+    });
+  } finally {
+    unlisten();
+  }
+}
+
+export async function cancelLlmStream(streamId: string): Promise<boolean> {
+  if (!isTauriRuntime()) return false;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<boolean>("cancel_llm_stream", { streamId });
+}
+
+export async function analyzeCrash(crashId: string, profileId?: string): Promise<CrashRcaResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Crash root-cause analysis requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<CrashRcaResult>("analyze_crash", { crashId, profileId });
+}
+
+export async function translateEventQuery(
+  question: string,
+  targetId?: string,
+  profileId?: string
+): Promise<EventQueryTranslation> {
+  if (!isTauriRuntime()) {
+    throw new Error("Natural-language search requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<EventQueryTranslation>("translate_event_query", { question, targetId, profileId });
+}
+
+export async function previewTimestampParsing(
+  samples: string[],
+  hint?: string,
+  utcOffsetMinutes?: number
+): Promise<Array<string | null>> {
+  if (!isTauriRuntime()) return samples.map(() => null);
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<Array<string | null>>("preview_timestamp_parsing", { samples, hint, utcOffsetMinutes });
+}
+
+export async function summarizeEvents(eventIds: string[], profileId?: string): Promise<EventSummaryResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Event summaries require desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<EventSummaryResult>("summarize_events", { eventIds, profileId });
+}
+
+export async function backfillLocalEvents(from: string, to: string): Promise<SyncOperationResult> {
+  if (!isTauriRuntime()) return { collected: 0, warnings: [], degradations: [], errors: [], sources: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SyncOperationResult>("backfill_local_events", { from, to });
+}
+
+export async function syncLocalEventsRange(
+  from: string,
+  to: string,
+  replaceOutsideRange = false
+): Promise<SyncOperationResult> {
+  if (!isTauriRuntime()) return { collected: 0, warnings: [], degradations: [], errors: [], sources: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SyncOperationResult>("sync_local_events_range", { from, to, replaceOutsideRange });
+}
+
+export async function estimateRefreshLocalEvents(): Promise<EventLoadEstimate> {
+  if (!isTauriRuntime()) {
+    return {
+      windowStart: new Date().toISOString(),
+      windowEnd: new Date().toISOString(),
+      estimatedCount: 0,
+      estimatedBytes: 0,
+      warnings: []
+    };
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<EventLoadEstimate>("estimate_refresh_local_events");
+}
+
+export async function estimateLocalEventsRange(from: string, to: string): Promise<EventLoadEstimate> {
+  if (!isTauriRuntime()) {
+    return {
+      windowStart: new Date().toISOString(),
+      windowEnd: new Date().toISOString(),
+      estimatedCount: 0,
+      estimatedBytes: 0,
+      warnings: []
+    };
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<EventLoadEstimate>("estimate_local_events_range", { from, to });
+}
+
+export async function refreshLocalEvents(targetId?: string): Promise<SyncOperationResult> {
+  if (!isTauriRuntime()) return { collected: 0, warnings: [], degradations: [], errors: [], sources: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SyncOperationResult>("refresh_local_events", { targetId });
+}
+
+export async function getLocalEvents(targetId?: string, limit = 10000): Promise<NormalizedEvent[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<NormalizedEvent[]>("get_local_events", { targetId, limit });
+}
+
+export async function getLocalEventsRange(
+  targetId: string | undefined,
+  from: string,
+  to: string,
+  limit = 20000
+): Promise<NormalizedEvent[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<NormalizedEvent[]>("get_local_events_range", { targetId, from, to, limit });
+}
+
+export async function getEventHistogram(
+  targetId: string | undefined,
+  from: string,
+  to: string,
+  bucket: HistogramBucketSize = "hour"
+): Promise<EventHistogramBucket[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<EventHistogramBucket[]>("get_event_histogram", { targetId, from, to, bucket });
+}
+
+export async function analyzePowerEvents(
+  targetId: string | undefined,
+  from: string,
+  to: string
+): Promise<PowerAnalysisResult> {
+  if (!isTauriRuntime()) {
+    return {
+      incidents: [],
+      powerLossCount: 0,
+      crashCount: 0,
+      unknownCount: 0,
+      battery: null,
+      warnings: []
+    };
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<PowerAnalysisResult>("analyze_power_events", { targetId, from, to });
+}
+
+export async function analyzeBootPerformance(
+  targetId: string | undefined,
+  from: string,
+  to: string
+): Promise<BootPerformanceResult> {
+  if (!isTauriRuntime()) {
+    return { boots: [], averageMs: null, latestMs: null, regressionCount: 0, warnings: [] };
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<BootPerformanceResult>("analyze_boot_performance", { targetId, from, to });
+}
+
+export async function analyzeAppHangs(
+  targetId: string | undefined,
+  from: string,
+  to: string
+): Promise<AppHangReport> {
+  if (!isTauriRuntime()) return { totalHangs: 0, applications: [], warnings: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<AppHangReport>("analyze_app_hangs", { targetId, from, to });
+}
+
+export async function getPrintIssues(
+  targetId: string | undefined,
+  from: string,
+  to: string
+): Promise<PrintIssueSummary> {
+  if (!isTauriRuntime()) return { totalEvents: 0, errorCount: 0, queues: [], drivers: [], events: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<PrintIssueSummary>("get_print_issues", { targetId, from, to });
+}
+
+export async function analyzeRemoteAccess(
+  targetId: string | undefined,
+  from: string,
+  to: string
+): Promise<RemoteAccessReport> {
+  if (!isTauriRuntime()) return { events: [], sources: [], warnings: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<RemoteAccessReport>("analyze_remote_access", { targetId, from, to });
+}
+
+export async function collectSmartMetrics(): Promise<SmartCollectionResult> {
+  if (!isTauriRuntime()) return { devices: 0, samples: [], failingDevices: [], warnings: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SmartCollectionResult>("collect_smart_metrics");
+}
+
+export async function collectReliabilityData(days?: number): Promise<ReliabilityCollectionResult> {
+  if (!isTauriRuntime()) return { events: [], samples: [], warnings: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ReliabilityCollectionResult>("collect_reliability_data", { days });
+}
+
+export async function getMetrics(
+  targetId: string | undefined,
+  source: string,
+  from: string,
+  to: string,
+  limit = 5000
+): Promise<MetricSample[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<MetricSample[]>("get_metrics", { targetId, source, from, to, limit });
+}
+
+export async function captureInventorySnapshot(): Promise<InventorySnapshotSummary | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<InventorySnapshotSummary>("capture_inventory_snapshot");
+}
+
+export async function getInventoryChanges(
+  targetId: string | undefined,
+  from: string,
+  to: string
+): Promise<InventoryChangeSet[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<InventoryChangeSet[]>("get_inventory_changes", { targetId, from, to });
+}
+
+export async function previewForwarding(
+  integrationId: string,
+  from: string,
+  to: string,
+  limit = 200
+): Promise<ForwardingPreview> {
+  if (!isTauriRuntime()) {
+    return { integrationId, scanned: 0, matched: 0, excluded: {}, events: [], truncated: false };
+  }
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ForwardingPreview>("preview_forwarding", { integrationId, from, to, limit });
+}
+
+export async function getForwardingStatus(): Promise<ForwardingStatus[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ForwardingStatus[]>("get_forwarding_status");
+}
+
+export async function flushForwarding(): Promise<ForwardingStatus[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ForwardingStatus[]>("flush_forwarding");
+}
+
+export async function detectAnomalies(
+  targetId: string | undefined,
+  from: string,
+  to: string,
+  bucketMinutes?: number
+): Promise<AnomalyReport> {
+  if (!isTauriRuntime()) return { bucketMinutes: bucketMinutes ?? 10, providersScanned: 0, windows: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<AnomalyReport>("detect_anomalies", { targetId, from, to, bucketMinutes });
+}
+
+export async function getAppHealth(): Promise<HealthReport | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<HealthReport>("get_app_health");
+}
+
+export async function runPreflightChecks(): Promise<PreflightReport | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<PreflightReport>("run_preflight_checks");
+}
+
+export async function getSyncHistory(targetId?: string, limit?: number): Promise<SyncHistoryEntry[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SyncHistoryEntry[]>("get_sync_history", { targetId, limit });
+}
+
+export async function getDbStats(): Promise<DatabaseStats | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<DatabaseStats>("get_db_stats");
+}
+
+export async function compactDatabase(): Promise<CompactionResult> {
+  if (!isTauriRuntime()) throw new Error("Database compaction requires desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<CompactionResult>("compact_database");
+}
+
+export async function getEncryptionStatus(): Promise<EncryptionStatus> {
+  if (!isTauriRuntime()) return { available: false, enabled: false };
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<EncryptionStatus>("get_encryption_status");
+}
+
+export async function setDatabaseEncryption(enabled: boolean): Promise<EncryptionStatus> {
+  if (!isTauriRuntime()) throw new Error("Database encryption requires desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<EncryptionStatus>("set_database_encryption", { enabled });
+}
+
+export async function listWorkspaces(): Promise<WorkspaceSettings | null> {
+  if (!isTauriRuntime()) return null;
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<WorkspaceSettings>("list_workspaces");
+}
+
+export async function createWorkspace(name: string): Promise<Workspace> {
+  if (!isTauriRuntime()) throw new Error("Workspaces require desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<Workspace>("create_workspace", { name });
+}
+
+export async function openWorkspace(workspaceId: string): Promise<WorkspaceSettings> {
+  if (!isTauriRuntime()) throw new Error("Workspaces require desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<WorkspaceSettings>("open_workspace", { workspaceId });
+}
+
+export async function archiveWorkspace(workspaceId: string): Promise<WorkspaceSettings> {
+  if (!isTauriRuntime()) throw new Error("Workspaces require desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<WorkspaceSettings>("archive_workspace", { workspaceId });
+}
+
+export async function listSavedFilters(): Promise<SavedFilter[]> {
+  if (!isTauriRuntime()) return [];
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SavedFilter[]>("list_saved_filters");
+}
+
+export async function saveSavedFilter(filter: SavedFilter): Promise<SavedFilter> {
+  if (!isTauriRuntime()) throw new Error("Saved filters require desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SavedFilter>("save_saved_filter", { filter });
+}
+
+export async function deleteSavedFilter(filterId: string): Promise<void> {
+  if (!isTauriRuntime()) throw new Error("Saved filters require desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<void>("delete_saved_filter", { filterId });
+}
+
+export async function exportSavedFilter(
+  filterId: string,
+  format: ExportFormat | CompressedExportFormat,
+  filename: string,
+  targetId?: string
+): Promise<QueryExportResult> {
+  if (!isTauriRuntime()) throw new Error("Database export requires desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<QueryExportResult>("export_saved_filter", { filterId, format, filename, targetId });
+}
+
+export async function getStabilityIndex(
+  targetId: string | undefined,
+  from: string,
+  to: string
+): Promise<StabilityDay[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<StabilityDay[]>("get_stability_index", { targetId, from, to });
+}
+
+export async function getLogPatterns(
+  targetId: string | undefined,
+  from: string,
+  to: string,
+  limit = 200
+): Promise<LogPattern[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LogPattern[]>("get_log_patterns", { targetId, from, to, limit });
+}
+
+export async function listEventPublishers(filter?: string): Promise<string[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string[]>("list_event_publishers", { filter });
+}
+
+export async function getPublisherMetadata(
+  provider: string,
+  eventId?: number
+): Promise<PublisherMetadata> {
+  if (!isTauriRuntime()) return { name: provider, messageFile: null, events: [] };
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<PublisherMetadata>("get_publisher_metadata", { provider, eventId });
+}
+
+export async function buildTimeline(
+  targetId: string | undefined,
+  from: string,
+  to: string
+): Promise<Timeline> {
+  if (!isTauriRuntime()) {
+    return { start: from, end: to, sourceHost: "localhost", counts: {}, entries: [] };
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<Timeline>("build_timeline", { targetId, from, to });
+}
+
+export async function buildIncidentReport(request: IncidentReportRequest): Promise<string> {
+  if (!isTauriRuntime()) {
+    throw new Error("Incident reports require desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string>("build_incident_report", { request });
+}
+
+export async function bookmarkEvent(eventId: string, note?: string): Promise<void> {
+  if (!isTauriRuntime()) return;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  await invoke("bookmark_event", { eventId, note });
+}
+
+export async function removeEventBookmark(eventId: string): Promise<boolean> {
+  if (!isTauriRuntime()) return false;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<boolean>("remove_event_bookmark", { eventId });
+}
+
+export async function getEventBookmarks(): Promise<EventBookmark[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<EventBookmark[]>("get_event_bookmarks");
+}
+
+export async function getEventRaw(eventId: string): Promise<string | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string | null>("get_event_raw", { eventId });
+}
+
+export async function startQueryRecording(name?: string): Promise<QuerySession> {
+  if (!isTauriRuntime()) {
+    throw new Error("Query recording requires the desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<QuerySession>("start_query_recording", { name });
+}
+
+export async function stopQueryRecording(): Promise<QuerySession | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<QuerySession | null>("stop_query_recording");
+}
+
+export async function getActiveQuerySession(): Promise<QuerySession | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<QuerySession | null>("get_active_query_session");
+}
+
+export async function recordQueryStep(action: string, params?: unknown): Promise<boolean> {
+  if (!isTauriRuntime()) return false;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<boolean>("record_query_step", { action, params });
+}
+
+export async function listQuerySessions(): Promise<QuerySession[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<QuerySession[]>("list_query_sessions");
+}
+
+export async function exportQuerySession(sessionId: string): Promise<QuerySessionExport> {
+  if (!isTauriRuntime()) {
+    throw new Error("Query session export requires the desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<QuerySessionExport>("export_query_session", { sessionId });
+}
+
+export async function deleteQueryRecording(sessionId: string): Promise<boolean> {
+  if (!isTauriRuntime()) return false;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<boolean>("delete_query_recording", { sessionId });
+}
+
+export async function createChatSession(title?: string): Promise<ChatSession> {
+  if (!isTauriRuntime()) {
+    throw new Error("Chat requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ChatSession>("create_chat_session", { title });
+}
+
+export async function listChatSessions(): Promise<ChatSession[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ChatSession[]>("list_chat_sessions");
+}
+
+export async function getChatMessages(sessionId: string): Promise<ChatMessage[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ChatMessage[]>("get_chat_messages", { sessionId });
+}
+
+export async function sendChatMessage(
+  sessionId: string,
+  content: string,
+  options: { profileId?: string; eventIds?: string[]; crashId?: string } = {}
+): Promise<ChatMessage> {
+  if (!isTauriRuntime()) {
+    throw new Error("Chat requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ChatMessage>("send_chat_message", {
+    sessionId,
+    content,
+    profileId: options.profileId,
+    eventIds: options.eventIds,
+    crashId: options.crashId
+  });
+}
+
+export async function deleteChatSession(sessionId: string): Promise<boolean> {
+  if (!isTauriRuntime()) return false;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<boolean>("delete_chat_session", { sessionId });
+}
+
+export async function exportChatSession(sessionId: string): Promise<string> {
+  if (!isTauriRuntime()) {
+    throw new Error("Chat export requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string>("export_chat_session", { sessionId });
+}
+
+export async function getWerDumpStatus(app?: string): Promise<WerDumpStatus> {
+  if (!isTauriRuntime()) {
+    throw new Error("WER settings require desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<WerDumpStatus>("get_wer_dump_status", { app });
+}
+
+export async function enableWerLocalDumps(options: {
+  app?: string;
+  dumpType: "mini" | "full";
+  dumpFolder?: string;
+  dumpCount?: number;
+}): Promise<WerDumpStatus> {
+  if (!isTauriRuntime()) {
+    throw new Error("WER settings require desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<WerDumpStatus>("enable_wer_local_dumps", options);
+}
+
+export async function findSimilarEvents(
+  eventId: string,
+  k = 10,
+  options: { profileId?: string; model?: string } = {}
+): Promise<SimilarEventsResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Similar event search requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SimilarEventsResult>("find_similar_events", {
+    eventId,
+    k,
+    profileId: options.profileId,
+    model: options.model
+  });
+}
+
+export async function startEventReplay(path?: string, speed?: number): Promise<ReplayStatus> {
+  if (!isTauriRuntime()) {
+    throw new Error("Event replay requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ReplayStatus>("start_event_replay", { path, speed });
+}
+
+export async function stopEventReplay(): Promise<ReplayStatus | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ReplayStatus>("stop_event_replay");
+}
+
+export async function getEventReplayStatus(): Promise<ReplayStatus | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ReplayStatus>("get_event_replay_status");
+}
+
+export async function importHostCrashes(targetId?: string, limit = 200, since?: string): Promise<number> {
+  if (!isTauriRuntime()) return 0;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<number>("import_host_crashes", { targetId, limit, since });
+}
+
+/** Target id that reads stored events and crashes from every host at once. */
+export const ALL_HOSTS_TARGET = "all-hosts";
+
+/** Target id for a stored host that has no connection profile. */
+export function hostTargetId(host: string): string {
+  return `host:${host}`;
+}
+
+export interface SourceHostSummary {
+  host: string;
+  eventCount: number;
+  crashCount: number;
+  lastSeen: string | null;
+}
+
+export async function getSourceHosts(): Promise<SourceHostSummary[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SourceHostSummary[]>("get_source_hosts");
+}
+
+export async function getCrashes(targetId?: string, limit = 250): Promise<CrashRecord[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<CrashRecord[]>("get_crashes", { targetId, limit });
+}
+
+export async function exportCrashes(
+  format: "json" | "csv",
+  filter?: CrashExportFilter,
+  targetId?: string
+): Promise<string | null> {
+  if (!isTauriRuntime()) {
+    throw new Error("Crash export requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string | null>("export_crashes", { format, filter, targetId });
+}
+
+export async function importCrashesFile(path: string): Promise<number> {
+  if (!isTauriRuntime()) {
+    throw new Error("Crash import requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<number>("import_crashes_file", { path });
+}
+
+export async function importEventsFile(path: string): Promise<number> {
+  if (!isTauriRuntime()) {
+    throw new Error("Event import requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<number>("import_events_file", { path });
+}
+
+export interface DatabaseImportSummary {
+  events: number;
+  crashes: number;
+  hosts: string[];
+}
+
+export async function importDatabase(path: string, host?: string): Promise<DatabaseImportSummary> {
+  if (!isTauriRuntime()) {
+    throw new Error("Database import requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<DatabaseImportSummary>("import_database", { path, host });
+}
+
+export type LogFileFormat = "rfc5424" | "rfc3164" | "jsonLines" | "commonLog" | "plainText";
+
+export interface ParserProfile {
+  id: string;
+  name: string;
+  /** Regex with named groups: timestamp, severity, provider, event_id, message. */
+  pattern: string;
+  timestampFormat: string;
+  severityMap: Record<string, string>;
+}
+
+export interface ParserProfileSettings {
+  profiles: ParserProfile[];
+}
+
+export interface LogImportResult {
+  path: string;
+  format: LogFileFormat | null;
+  profile: string | null;
+  imported: number;
+  skipped: number;
+}
+
+export async function importLogFile(
+  path: string,
+  targetId?: string,
+  profileId?: string
+): Promise<LogImportResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Log file import requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LogImportResult>("import_log_file", { path, targetId, profileId });
+}
+
+export interface LogDirectoryImportResult {
+  path: string;
+  files: LogImportResult[];
+  errors: string[];
+}
+
+/** Imports a log directory oldest rotation first; repeat runs read only lines added since. */
+export async function importLogDirectory(
+  path: string,
+  targetId?: string,
+  profileId?: string
+): Promise<LogDirectoryImportResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Log directory import requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<LogDirectoryImportResult>("import_log_directory", { path, targetId, profileId });
+}
+
+/**
+ * Starts a background job and resolves with its result once it finishes, passing every
+ * `hla://job-progress` update for it to `onProgress`. Updates that arrive before the job ID is
+ * known are held back and replayed.
+ */
+async function runJob<T>(
+  command: string,
+  args: Record<string, unknown>,
+  onProgress?: (status: JobStatus) => void
+): Promise<T> {
+  const { invoke } = await import("@tauri-apps/api/core");
+  const { listen } = await import("@tauri-apps/api/event");
+
+  let jobId: string | null = null;
+  const early: JobStatus[] = [];
+  let settle: (status: JobStatus) => void = () => undefined;
+  const finished = new Promise<JobStatus>((resolve) => {
+    settle = resolve;
+  });
+  const handle = (status: JobStatus) => {
+    if (status.jobId !== jobId) return;
+    onProgress?.(status);
+    if (status.state !== "running") settle(status);
+  };
+  const unlisten = await listen<JobStatus>("hla://job-progress", (event) => {
+    if (jobId === null) {
+      early.push(event.payload);
+    } else {
+      handle(event.payload);
+    }
+  });
+
+  try {
+    jobId = await invoke<string>(command, args);
+    early.forEach(handle);
+    const current = await invoke<JobStatus | null>("get_job_status", { jobId });
+    if (current) handle(current);
+    const status = await finished;
+    if (status.state === "failed") {
+      throw new Error(status.error ?? "Background job failed.");
+    }
+    return status.result as T;
+  } finally {
+    unlisten();
+  }
+}
+
+/** Same as `refreshLocalEvents`, run as a background job that reports progress. */
+export async function runRefreshJob(
+  targetId?: string,
+  onProgress?: (status: JobStatus) => void
+): Promise<SyncOperationResult> {
+  if (!isTauriRuntime()) return { collected: 0, warnings: [], degradations: [], errors: [], sources: [] };
+
+  return runJob<SyncOperationResult>("start_refresh_job", { targetId }, onProgress);
+}
+
+/** Imports a log file or directory as a background job that reports progress. */
+export async function runLogImportJob(
+  path: string,
+  targetId?: string,
+  profileId?: string,
+  onProgress?: (status: JobStatus) => void
+): Promise<LogImportResult | LogDirectoryImportResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Log import requires desktop runtime.");
+  }
+
+  return runJob<LogImportResult | LogDirectoryImportResult>(
+    "start_log_import_job",
+    { path, targetId, profileId },
+    onProgress
+  );
+}
+
+export interface SyslogListenerSettings {
+  enabled: boolean;
+  bindAddress: string;
+  port: number;
+  protocol: "udp" | "tcp" | "both";
+}
+
+export interface SyslogListenerStatus {
+  running: boolean;
+  udpAddress: string | null;
+  tcpAddress: string | null;
+  received: number;
+  dropped: number;
+  /** Sender addresses; select one with the target ID `syslog:<address>`. */
+  senders: string[];
+  lastError: string | null;
+}
+
+export async function getSyslogListenerSettings(): Promise<SyslogListenerSettings> {
+  if (!isTauriRuntime()) {
+    return { enabled: false, bindAddress: "0.0.0.0", port: 5514, protocol: "udp" };
+  }
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SyslogListenerSettings>("get_syslog_listener_settings");
+}
+
+export async function saveSyslogListenerSettings(
+  settings: SyslogListenerSettings
+): Promise<SyslogListenerStatus> {
+  if (!isTauriRuntime()) {
+    throw new Error("Syslog listener requires desktop runtime.");
+  }
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SyslogListenerStatus>("save_syslog_listener_settings", { settings });
+}
+
+export async function getSyslogListenerStatus(): Promise<SyslogListenerStatus | null> {
+  if (!isTauriRuntime()) return null;
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<SyslogListenerStatus>("get_syslog_listener_status");
+}
+
+export async function getParserProfiles(): Promise<ParserProfileSettings> {
+  if (!isTauriRuntime()) return { profiles: [] };
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ParserProfileSettings>("get_parser_profiles");
+}
+
+export async function saveParserProfiles(
+  settings: ParserProfileSettings
+): Promise<ParserProfileSettings> {
+  if (!isTauriRuntime()) return settings;
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ParserProfileSettings>("save_parser_profiles", { settings });
+}
+
+export async function exportCase(
+  crashId: string,
+  notes?: string,
+  analysis?: ReportAnalysis
+): Promise<string | null> {
+  if (!isTauriRuntime()) {
+    throw new Error("Case export requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string | null>("export_case", { crashId, notes, analysis });
 }
 
-function createDefaultLlmProfile(): LlmConnectionProfile {
-  return {
-    id: "profile-ollama-local",
-    name: "Ollama Local",
-    provider: "ollama",
-    scope: "local",
-    baseUrl: "http://127.0.0.1:11434",
-    model: "",
-    enabled: true,
-    apiKeyConfigured: false
-  };
+export async function importCase(path: string): Promise<CaseImportResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Case import requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<CaseImportResult>("import_case", { path });
 }
 
-function createDefaultLlmSettings(): LlmSettings {
-  return {
-    allowLanDiscovery: false,
-    neverSendRawEventToUntrusted: true,
-    trustedHosts: [],
-    profiles: [createDefaultLlmProfile()],
-    defaultProfileId: "profile-ollama-local",
-    backupProfileId: "",
-    preferredLanInterfaceId: ""
-  };
+export async function getCrashRelatedEvents(
+  crashId: string,
+  windowMinutes = 15,
+  limit = 200
+): Promise<ScoredCrashEvent[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ScoredCrashEvent[]>("get_crash_related_events", {
+    crashId,
+    windowMinutes,
+    limit
+  });
 }
 
-export async function setIngestProfile(profile: IngestProfile): Promise<IngestProfile> {
-  if (!isTauriRuntime()) return profile;
+export async function analyzeMinidump(
+  crashId: string,
+  windowMinutes = 15
+): Promise<MinidumpAnalysisResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Minidump analysis requires desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<IngestProfile>("set_ingest_profile", { profile });
+  return invoke<MinidumpAnalysisResult>("analyze_minidump", {
+    crashId,
+    windowMinutes
+  });
 }
 
-export async function setIngestWindowDays(days: number): Promise<number> {
-  if (!isTauriRuntime()) return days;
+export async function explainCrashCode(code: string): Promise<CrashCodeExplanation> {
+  if (!isTauriRuntime()) {
+    throw new Error("Crash code lookup requires desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<number>("set_ingest_window_days", { days });
+  return invoke<CrashCodeExplanation>("explain_crash_code", { code });
 }
 
-export async function getLlmSettings(): Promise<LlmSettings> {
-  if (!isTauriRuntime()) return createDefaultLlmSettings();
+export async function syncLocalEventsWindow(
+  start: string,
+  end: string,
+  targetId?: string
+): Promise<SyncOperationResult> {
+  if (!isTauriRuntime()) return { collected: 0, warnings: [], degradations: [], errors: [], sources: [] };
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmSettings>("get_llm_settings");
+  return invoke<SyncOperationResult>("sync_local_events_window", { targetId, start, end });
 }
 
-export async function setLlmSettings(settings: LlmSettings): Promise<LlmSettings> {
-  if (!isTauriRuntime()) return settings;
+export async function getRecentChanges(crashId: string, hours = 24): Promise<RecentChangesReport> {
+  if (!isTauriRuntime()) {
+    throw new Error("Recent change reports require desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmSettings>("set_llm_settings", { settings });
+  return invoke<RecentChangesReport>("get_recent_changes", { crashId, hours });
 }
 
-export async function setLlmProfileApiKey(profileId: string, apiKey: string): Promise<LlmSettings> {
-  if (!isTauriRuntime()) return createDefaultLlmSettings();
+export async function matchKnownIssues(id: string): Promise<KnownIssueReport | null> {
+  if (!isTauriRuntime()) return null;
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmSettings>("set_llm_profile_api_key", { profileId, apiKey });
+  return invoke<KnownIssueReport>("match_known_issues", { id });
 }
 
-export async function clearLlmProfileApiKey(profileId: string): Promise<LlmSettings> {
-  if (!isTauriRuntime()) return createDefaultLlmSettings();
+export async function suggestCrashCollection(
+  crashId: string,
+  targetId?: string,
+  sync = false
+): Promise<CrashCollectionPlan> {
+  if (!isTauriRuntime()) {
+    throw new Error("Crash collection suggestions require desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmSettings>("clear_llm_profile_api_key", { profileId });
+  return invoke<CrashCollectionPlan>("suggest_crash_collection", { crashId, targetId, sync });
 }
 
-export async function detectLocalLlmProviders(): Promise<LlmEndpointCandidate[]> {
+export async function getLocalEventsWindow(
+  start: string,
+  end: string,
+  limit = 10000,
+  targetId?: string
+): Promise<NormalizedEvent[]> {
   if (!isTauriRuntime()) return [];
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmEndpointCandidate[]>("detect_local_llm_providers");
+  return invoke<NormalizedEvent[]>("get_local_events_window", { targetId, start, end, limit });
 }
 
-export async function listLlmNetworkInterfaces(
-  includeNonPrivate = false,
-  includeLoopback = false
-): Promise<LlmNetworkInterface[]> {
-  if (!isTauriRuntime()) return [];
+export async function getTimeline(
+  start: string,
+  end: string,
+  filter?: EventQueryFilter,
+  targetId?: string,
+  includeCrashes = true,
+  limit = 10000
+): Promise<RecordTimeline> {
+  if (!isTauriRuntime()) return { entries: [], truncated: false };
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmNetworkInterface[]>("list_llm_network_interfaces", {
-    includeNonPrivate,
-    includeLoopback
-  });
+  return invoke<RecordTimeline>("get_timeline", { targetId, start, end, filter, includeCrashes, limit });
 }
 
-export async function scanLanLlmProviders(
-  interfaceId?: string,
-  maxHosts = 256
-): Promise<LlmEndpointCandidate[]> {
-  if (!isTauriRuntime()) return [];
+export async function listSigmaRules(): Promise<SigmaRuleList> {
+  if (!isTauriRuntime()) return { rules: [], errors: [] };
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmEndpointCandidate[]>("scan_lan_llm_providers", { interfaceId, maxHosts });
+  return invoke<SigmaRuleList>("list_sigma_rules");
 }
 
-export async function testLlmProfileConnection(
-  profile: LlmConnectionProfile
-): Promise<LlmConnectionTestResult> {
+export async function importSigmaRules(files: SigmaRuleFile[]): Promise<SigmaImportResult> {
   if (!isTauriRuntime()) {
-    return {
-      ok: false,
-      provider: profile.provider,
-      baseUrl: profile.baseUrl,
-      statusCode: null,
-      message: "Connection test requires desktop runtime.",
-      detectedModels: [],
-      preferredModel: null
-    };
+    throw new Error("Sigma rule import requires desktop runtime.");
   }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmConnectionTestResult>("test_llm_profile_connection", { profile });
+  return invoke<SigmaImportResult>("import_sigma_rules", { files });
 }
 
-export async function analyzeWithLocalLlm(
-  prompt: string,
-  profileId?: string
-): Promise<LlmAnalysisResult> {
+export async function deleteSigmaRule(ruleId: string): Promise<void> {
   if (!isTauriRuntime()) {
-    throw new Error("Local LLM analysis requires desktop runtime.");
+    throw new Error("Sigma rules require desktop runtime.");
   }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<LlmAnalysisResult>("analyze_with_local_llm", { prompt, profileId });
+  await invoke("delete_sigma_rule", { ruleId });
 }
 
-export async function backfillLocalEvents(from: string, to: string): Promise<SyncOperationResult> {
-  if (!isTauriRuntime()) return { collected: 0, warnings: [] };
+export async function runSigmaScan(targetId?: string, start?: string, end?: string): Promise<SigmaScanResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Sigma scans require desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<SyncOperationResult>("backfill_local_events", { from, to });
+  return invoke<SigmaScanResult>("run_sigma_scan", { targetId, start, end });
 }
 
-export async function syncLocalEventsRange(
-  from: string,
-  to: string,
-  replaceOutsideRange = false
-): Promise<SyncOperationResult> {
-  if (!isTauriRuntime()) return { collected: 0, warnings: [] };
+export async function listSigmaDetections(targetId?: string, ruleId?: string, limit = 500): Promise<SigmaDetection[]> {
+  if (!isTauriRuntime()) return [];
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<SyncOperationResult>("sync_local_events_range", { from, to, replaceOutsideRange });
+  return invoke<SigmaDetection[]>("list_sigma_detections", { targetId, ruleId, limit });
 }
 
-export async function estimateRefreshLocalEvents(): Promise<EventLoadEstimate> {
+export async function analyzeLogons(start: string, end: string, targetId?: string, top = 10): Promise<LogonReport> {
   if (!isTauriRuntime()) {
-    return {
-      windowStart: new Date().toISOString(),
-      windowEnd: new Date().toISOString(),
-      estimatedCount: 0,
-      estimatedBytes: 0,
-      warnings: []
-    };
+    throw new Error("Logon analysis requires desktop runtime.");
   }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<EventLoadEstimate>("estimate_refresh_local_events");
+  return invoke<LogonReport>("analyze_logons", { targetId, start, end, top });
 }
 
-export async function estimateLocalEventsRange(from: string, to: string): Promise<EventLoadEstimate> {
+export async function getSystemChanges(
+  start: string,
+  end: string,
+  targetId?: string,
+  limit = 500
+): Promise<SystemChangesReport> {
   if (!isTauriRuntime()) {
-    return {
-      windowStart: new Date().toISOString(),
-      windowEnd: new Date().toISOString(),
-      estimatedCount: 0,
-      estimatedBytes: 0,
-      warnings: []
-    };
+    throw new Error("System change detection requires desktop runtime.");
   }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<EventLoadEstimate>("estimate_local_events_range", { from, to });
-}
-
-export async function refreshLocalEvents(targetId?: string): Promise<SyncOperationResult> {
-  if (!isTauriRuntime()) return { collected: 0, warnings: [] };
-
-  const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<SyncOperationResult>("refresh_local_events", { targetId });
+  return invoke<SystemChangesReport>("get_system_changes", { targetId, start, end, limit });
 }
 
-export async function getLocalEvents(targetId?: string, limit = 10000): Promise<NormalizedEvent[]> {
-  if (!isTauriRuntime()) return [];
+export async function getUsbDeviceActivity(
+  start: string,
+  end: string,
+  targetId?: string,
+  limit = 500
+): Promise<UsbActivityReport> {
+  if (!isTauriRuntime()) {
+    throw new Error("Device activity requires desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<NormalizedEvent[]>("get_local_events", { targetId, limit });
+  return invoke<UsbActivityReport>("get_usb_device_activity", { targetId, start, end, limit });
 }
 
-export async function getLocalEventsRange(
-  targetId: string | undefined,
-  from: string,
-  to: string,
-  limit = 20000
-): Promise<NormalizedEvent[]> {
-  if (!isTauriRuntime()) return [];
+export async function getRepeatedEvents(
+  filter: EventQueryFilter,
+  targetId?: string,
+  gapSeconds = 300,
+  limit = 500
+): Promise<RepeatedEventsReport> {
+  if (!isTauriRuntime()) {
+    throw new Error("Repeated message analysis requires desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<NormalizedEvent[]>("get_local_events_range", { targetId, from, to, limit });
+  return invoke<RepeatedEventsReport>("get_repeated_events", { filter, targetId, gapSeconds, limit });
 }
 
-export async function importHostCrashes(targetId?: string, limit = 200): Promise<number> {
-  if (!isTauriRuntime()) return 0;
+export async function searchEventsRegex(
+  pattern: string,
+  targetId?: string,
+  limit = 500
+): Promise<RegexSearchResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Regex search requires desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<number>("import_host_crashes", { targetId, limit });
+  return invoke<RegexSearchResult>("search_events_regex", { pattern, targetId, limit });
 }
 
-export async function getCrashes(targetId?: string, limit = 250): Promise<CrashRecord[]> {
-  if (!isTauriRuntime()) return [];
+export async function importIocFeed(name: string, content: string): Promise<IocImportResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("IOC feed import requires desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<CrashRecord[]>("get_crashes", { targetId, limit });
+  return invoke<IocImportResult>("import_ioc_feed", { name, content });
 }
 
-export async function getCrashRelatedEvents(
-  crashId: string,
-  windowMinutes = 15,
-  limit = 200
-): Promise<NormalizedEvent[]> {
+export async function listIocFeeds(): Promise<IocFeedSummary[]> {
   if (!isTauriRuntime()) return [];
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<NormalizedEvent[]>("get_crash_related_events", {
-    crashId,
-    windowMinutes,
-    limit
-  });
+  return invoke<IocFeedSummary[]>("list_ioc_feeds");
 }
 
-export async function analyzeMinidump(
-  crashId: string,
-  windowMinutes = 15
-): Promise<MinidumpAnalysisResult> {
+export async function deleteIocFeed(feed: string): Promise<void> {
   if (!isTauriRuntime()) {
-    throw new Error("Minidump analysis requires desktop runtime.");
+    throw new Error("IOC feeds require desktop runtime.");
   }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<MinidumpAnalysisResult>("analyze_minidump", {
-    crashId,
-    windowMinutes
-  });
+  await invoke("delete_ioc_feed", { feed });
 }
 
-export async function syncLocalEventsWindow(
-  start: string,
-  end: string,
-  targetId?: string
-): Promise<SyncOperationResult> {
-  if (!isTauriRuntime()) return { collected: 0, warnings: [] };
+export async function runIocScan(targetId?: string, start?: string, end?: string): Promise<IocScanResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("IOC scans require desktop runtime.");
+  }
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<SyncOperationResult>("sync_local_events_window", { targetId, start, end });
+  return invoke<IocScanResult>("run_ioc_scan", { targetId, start, end });
 }
 
-export async function getLocalEventsWindow(
-  start: string,
-  end: string,
-  limit = 10000,
-  targetId?: string
-): Promise<NormalizedEvent[]> {
+export async function listIocMatches(targetId?: string, feed?: string, limit = 500): Promise<IocMatch[]> {
   if (!isTauriRuntime()) return [];
 
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<NormalizedEvent[]>("get_local_events_window", { targetId, start, end, limit });
+  return invoke<IocMatch[]>("list_ioc_matches", { targetId, feed, limit });
 }
 
 export async function openExternalUrl(url: string): Promise<void> {
@@ -534,6 +2762,29 @@ export async function openPathInShell(path: string): Promise<void> {
   await invoke("open_path_in_shell", { path });
 }
 
+export async function getDisplayTimezone(): Promise<string> {
+  if (!isTauriRuntime()) return "system";
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string>("get_display_timezone");
+}
+
+export async function setDisplayTimezone(timezone: string): Promise<string> {
+  if (!isTauriRuntime()) {
+    throw new Error("Time zone settings require desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string>("set_display_timezone", { timezone });
+}
+
+export async function listTimezones(): Promise<string[]> {
+  if (!isTauriRuntime()) return [];
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<string[]>("list_timezones");
+}
+
 export async function getExportDirectory(): Promise<string | null> {
   if (!isTauriRuntime()) return null;
 
@@ -558,7 +2809,7 @@ export async function setExportDirectory(path: string | null): Promise<void> {
 }
 
 export async function exportEventsToFile(
-  format: ExportFormat,
+  format: ExportFormat | CompressedExportFormat,
   filename: string,
   events: NormalizedEvent[]
 ): Promise<string> {
@@ -571,7 +2822,7 @@ export async function exportEventsToFile(
 }
 
 export async function exportEventsWithDialog(
-  format: ExportFormat,
+  format: ExportFormat | CompressedExportFormat,
   suggestedFilename: string,
   events: NormalizedEvent[]
 ): Promise<string | null> {
@@ -585,6 +2836,20 @@ export async function exportEventsWithDialog(
   });
 }
 
+export async function exportQuery(
+  filter: EventQueryFilter,
+  format: ExportFormat | CompressedExportFormat,
+  filename: string,
+  targetId?: string
+): Promise<QueryExportResult> {
+  if (!isTauriRuntime()) {
+    throw new Error("Database export requires desktop runtime.");
+  }
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<QueryExportResult>("export_query", { filter, format, filename, targetId });
+}
+
 export async function saveTextWithDialog(
   suggestedFilename: string,
   text: string
@@ -665,11 +2930,37 @@ export async function getHostOsVersion(): Promise<string> {
 }
 
 export async function getIngestProfile(): Promise<IngestProfile> {
-  if (!isTauriRuntime()) return { autoSyncOnStartup: false, maxEventsPerSync: 1000, windowsChannels: ["Application", "System", "Security"], requestElevation: false };
+  if (!isTauriRuntime()) return { autoSyncOnStartup: false, maxEventsPerSync: 1000, windowsChannels: ["Application", "System", "Security"], requestElevation: false, collectSmart: false, maxDatabaseMb: 0, journalFilter: { units: [], identifiers: [], priority: "" }, unifiedLogFilter: { predicate: "", includeInfo: false, includeDebug: false }, syncIntervalMinutes: 0, retention: { maxAgeDays: 0, maxEvents: 0, archiveDirectory: "", objectStore: { kind: "", bucket: "", region: "", endpoint: "", accessKeyId: "", containerUrl: "", prefix: "" } }, messageLocale: "en-US", collapseRepeats: false };
   const { invoke } = await import("@tauri-apps/api/core");
   return invoke<IngestProfile>("get_ingest_profile");
 }
 
+export async function getStorageQuotaStatus(): Promise<QuotaPlan | null> {
+  if (!isTauriRuntime()) return null;
+
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<QuotaPlan>("get_storage_quota_status");
+}
+
+export async function runRetentionNow(): Promise<RetentionReport> {
+  if (!isTauriRuntime()) throw new Error("Retention requires desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<RetentionReport>("run_retention_now");
+}
+
+/** Archived days in the range holding events that match `query`; dates are YYYY-MM-DD, at most 31 days apart. */
+export async function searchArchive(query: string, start: string, end: string): Promise<ArchiveSearchReport> {
+  if (!isTauriRuntime()) throw new Error("Archive search requires desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<ArchiveSearchReport>("search_archive", { query, start, end });
+}
+
+export async function rehydrateArchiveDays(days: string[]): Promise<RehydrateReport> {
+  if (!isTauriRuntime()) throw new Error("Archive rehydration requires desktop runtime.");
+  const { invoke } = await import("@tauri-apps/api/core");
+  return invoke<RehydrateReport>("rehydrate_archive_days", { days });
+}
+
 export async function getIngestWindowDays(): Promise<number> {
   if (!isTauriRuntime()) return 7;
   const { invoke } = await import("@tauri-apps/api/core");
@@ -10,8 +10,50 @@ function download(filename: string, content: string, mime: string): void {
   URL.revokeObjectURL(url);
 }
 
+export const EVENT_EXPORT_SCHEMA = "hermes.events";
+// Keep in sync with src-tauri/src/event_export.rs. Version 0 is the bare JSON array written
+// before exports carried a header.
+export const EVENT_EXPORT_SCHEMA_VERSION = 1;
+export const EVENT_EXPORT_FIELDS = [
+  "id",
+  "timestamp",
+  "os",
+  "logName",
+  "category",
+  "provider",
+  "eventId",
+  "severity",
+  "message",
+  "sourceHost",
+  "imported"
+];
+
+function exportHeader(eventCount: number) {
+  return {
+    schema: EVENT_EXPORT_SCHEMA,
+    schemaVersion: EVENT_EXPORT_SCHEMA_VERSION,
+    exportedAt: new Date().toISOString(),
+    fields: EVENT_EXPORT_FIELDS,
+    eventCount
+  };
+}
+
+function exportRecord(event: NormalizedEvent): Record<string, unknown> {
+  const record = event as unknown as Record<string, unknown>;
+  return Object.fromEntries(EVENT_EXPORT_FIELDS.map((field) => [field, record[field] ?? null]));
+}
+
 export function exportAsJson(events: NormalizedEvent[], filename: string): void {
-  download(filename, JSON.stringify(events, null, 2), "application/json");
+  const document = { ...exportHeader(events.length), events: events.map(exportRecord) };
+  download(filename, JSON.stringify(document, null, 2), "application/json");
+}
+
+export function exportAsNdjson(events: NormalizedEvent[], filename: string): void {
+  const lines = [JSON.stringify(exportHeader(events.length))];
+  for (const event of events) {
+    lines.push(JSON.stringify(exportRecord(event)));
+  }
+  download(filename, lines.join("\n"), "application/x-ndjson");
 }
 
 export function exportAsText(events: NormalizedEvent[], filename: string): void {
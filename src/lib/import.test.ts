@@ -0,0 +1,49 @@
+import { describe, expect, it } from "vitest";
+
+import { parseJson, parseNdjson } from "./import";
+
+const event = {
+  id: "evt-1",
+  timestamp: "2026-03-01T10:00:00+00:00",
+  os: "linux",
+  logName: "journal",
+  category: "system",
+  provider: "kernel",
+  eventId: null,
+  severity: "error",
+  message: "disk failure",
+  sourceHost: "localhost",
+  imported: false
+};
+
+describe("import", () => {
+  it("reads bare arrays and versioned export documents", () => {
+    const legacy = parseJson(JSON.stringify([event]), "linux");
+    const versioned = parseJson(
+      JSON.stringify({ schema: "hermes.events", schemaVersion: 1, fields: Object.keys(event), events: [event] }),
+      "linux"
+    );
+
+    expect(legacy[0].message).toBe("disk failure");
+    expect(versioned[0].message).toBe("disk failure");
+    expect(versioned[0].eventId).toBeUndefined();
+    expect(versioned[0].imported).toBe(true);
+  });
+
+  it("accepts newer NDJSON exports and keeps unknown columns on raw", () => {
+    const content = [
+      JSON.stringify({ schema: "hermes.events", schemaVersion: 3, fields: [...Object.keys(event), "machine", "tags"] }),
+      JSON.stringify({ ...event, eventId: 41, machine: "build-01", tags: ["disk"] }),
+      ""
+    ].join("\n");
+
+    const events = parseNdjson(content, "linux");
+    expect(events).toHaveLength(1);
+    expect(events[0].eventId).toBe(41);
+    expect((events[0].raw as Record<string, unknown>).machine).toBe("build-01");
+  });
+
+  it("rejects headers with an invalid schema version", () => {
+    expect(() => parseJson(JSON.stringify({ schemaVersion: "next", events: [] }), "linux")).toThrow(/schemaVersion/);
+  });
+});
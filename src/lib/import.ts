@@ -2,36 +2,91 @@ import type { NormalizedEvent, SupportedOs } from "../types/events";
 
 export async function importSessionEvents(file: File, hostOs: SupportedOs): Promise<NormalizedEvent[]> {
   const content = await file.text();
+  const name = file.name.toLowerCase();
 
-  if (file.name.toLowerCase().endsWith(".json")) {
+  if (name.endsWith(".ndjson") || name.endsWith(".jsonl")) {
+    return parseNdjson(content, hostOs);
+  }
+
+  if (name.endsWith(".json")) {
     return parseJson(content, hostOs);
   }
 
-  if (file.name.toLowerCase().endsWith(".csv")) {
+  if (name.endsWith(".csv")) {
     return parseCsv(content, hostOs);
   }
 
-  throw new Error("Unsupported import type. Use JSON or CSV exports.");
+  throw new Error("Unsupported import type. Use JSON, NDJSON, or CSV exports.");
+}
+
+type RawEvent = Record<string, unknown>;
+
+function isSchemaHeader(value: unknown): value is RawEvent {
+  return typeof value === "object" && value !== null && "schemaVersion" in value && !("message" in value);
+}
+
+function schemaVersionOf(header: RawEvent): number {
+  const version = Number(header.schemaVersion);
+  if (!Number.isInteger(version) || version < 0) {
+    throw new Error("Export header has an invalid schemaVersion.");
+  }
+  return version;
+}
+
+// Version 0 exports are a bare array; version 1 and later wrap events with a schema header.
+// Newer versions are accepted as long as the core fields are present: columns this release
+// does not know about stay available on `raw`.
+export function parseJson(content: string, hostOs: SupportedOs): NormalizedEvent[] {
+  const parsed: unknown = JSON.parse(content);
+  if (Array.isArray(parsed)) return mapRawEvents(parsed, hostOs);
+
+  if (isSchemaHeader(parsed)) {
+    schemaVersionOf(parsed);
+    if (!Array.isArray(parsed.events)) throw new Error("Export document has no events array.");
+    return mapRawEvents(parsed.events, hostOs);
+  }
+
+  throw new Error("Expected a JSON array of events or a Hermes export document.");
 }
 
-function parseJson(content: string, hostOs: SupportedOs): NormalizedEvent[] {
-  const parsed = JSON.parse(content);
-  if (!Array.isArray(parsed)) throw new Error("Expected a JSON array of events.");
-
-  return parsed.map((raw, index) => ({
-    id: crypto.randomUUID(),
-    timestamp: String(raw.timestamp ?? new Date().toISOString()),
-    os: normalizeOs(String(raw.os ?? hostOs)),
-    logName: String(raw.logName ?? "Imported"),
-    category: normalizeCategory(String(raw.category ?? "other")),
-    provider: String(raw.provider ?? "import"),
-    eventId: Number.isFinite(Number(raw.eventId)) ? Number(raw.eventId) : undefined,
-    severity: normalizeSeverity(String(raw.severity ?? "information")),
-    message: String(raw.message ?? `Imported event ${index + 1}`),
-    raw,
-    imported: true,
-    sourceHost: "localhost"
-  }));
+export function parseNdjson(content: string, hostOs: SupportedOs): NormalizedEvent[] {
+  const rows: unknown[] = [];
+  const lines = content.split(/\r?\n/);
+  for (const [index, line] of lines.entries()) {
+    if (!line.trim()) continue;
+    let value: unknown;
+    try {
+      value = JSON.parse(line);
+    } catch {
+      throw new Error(`Line ${index + 1} is not valid JSON.`);
+    }
+    if (rows.length === 0 && isSchemaHeader(value)) {
+      schemaVersionOf(value);
+      continue;
+    }
+    rows.push(value);
+  }
+  return mapRawEvents(rows, hostOs);
+}
+
+function mapRawEvents(rows: unknown[], hostOs: SupportedOs): NormalizedEvent[] {
+  return rows.map((value, index) => {
+    const raw = (typeof value === "object" && value !== null ? value : {}) as RawEvent;
+    return {
+      id: crypto.randomUUID(),
+      timestamp: String(raw.timestamp ?? new Date().toISOString()),
+      os: normalizeOs(String(raw.os ?? hostOs)),
+      logName: String(raw.logName ?? "Imported"),
+      category: normalizeCategory(String(raw.category ?? "other")),
+      provider: String(raw.provider ?? "import"),
+      eventId: raw.eventId != null && Number.isFinite(Number(raw.eventId)) ? Number(raw.eventId) : undefined,
+      severity: normalizeSeverity(String(raw.severity ?? "information")),
+      message: String(raw.message ?? `Imported event ${index + 1}`),
+      raw,
+      imported: true,
+      sourceHost: "localhost"
+    };
+  });
 }
 
 function parseCsv(content: string, hostOs: SupportedOs): NormalizedEvent[] {
@@ -1,6 +1,8 @@
 export type SupportedOs = "windows" | "linux" | "macos";
 export type ThemeMode = "system" | "light" | "dark";
-export type ExportFormat = "json" | "csv" | "txt";
+export type ExportFormat = "json" | "ndjson" | "csv" | "txt" | "parquet" | "cef" | "leef";
+export type ExportCompression = "none" | "gz" | "zip";
+export type CompressedExportFormat = `${ExportFormat}.gz` | `${ExportFormat}.zip`;
 
 export type EventSeverity =
   | "information"
@@ -13,6 +15,8 @@ export type EventCategory =
   | "security"
   | "system"
   | "audit"
+  | "printing"
+  | "updates"
   | "other";
 
 export interface NormalizedEvent {
@@ -28,6 +32,15 @@ export interface NormalizedEvent {
   sourceHost: string;
   raw?: unknown;
   imported?: boolean;
+  userSid?: string | null;
+  userName?: string | null;
+  computer?: string | null;
+  processId?: number | null;
+  threadId?: number | null;
+  keywords?: string | null;
+  opcode?: string | null;
+  task?: string | null;
+  metadata?: Record<string, string> | null;
 }
 
 export interface CrashRecord {
@@ -42,6 +55,7 @@ export interface CrashRecord {
   rawPath?: string;
   sourceHost: string;
   imported?: boolean;
+  details?: string | null;
 }
 
 export interface EventFilters {